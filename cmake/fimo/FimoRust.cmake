@@ -38,6 +38,7 @@ function(fimo_add_rust_bindings NAME)
             --manifest-path ${CMAKE_SOURCE_DIR}/Cargo.toml
             --target-dir ${CMAKE_BINARY_DIR}/target
             --package ${NAME}
+            $<$<BOOL:${FIMO_STD_STATIC_MODULES}>:--features=fimo_std/static_modules>
             $<$<NOT:$<CONFIG:Debug>>:--release>
     )
 
@@ -50,6 +51,7 @@ function(fimo_add_rust_bindings NAME)
                 --manifest-path ${CMAKE_SOURCE_DIR}/Cargo.toml
                 --target-dir ${CMAKE_BINARY_DIR}/target
                 --package ${NAME}
+                $<$<BOOL:${FIMO_STD_STATIC_MODULES}>:--features=fimo_std/static_modules>
                 $<$<NOT:$<CONFIG:Debug>>:--release>
         )
         add_dependencies(fimo_rust_bindings_test_${NAME} fimo_all)
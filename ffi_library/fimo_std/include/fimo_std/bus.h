@@ -0,0 +1,253 @@
+#ifndef FIMO_BUS_H
+#define FIMO_BUS_H
+
+#include <stddef.h>
+
+#include <fimo_std/context.h>
+#include <fimo_std/error.h>
+#include <fimo_std/integers.h>
+#include <fimo_std/utils.h>
+#include <fimo_std/version.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Topic of an event published on the bus.
+ *
+ * A topic is identified by an UUID and a version. An event is
+ * delivered to a subscriber, if the UUIDs match and the version
+ * of the published event is compatible with the version required
+ * by the subscriber, as determined by `fimo_version_compatible`.
+ * A compatible version of a topic may only append new fields to
+ * the end of its payload.
+ */
+typedef struct FimoBusTopic {
+    /**
+     * UUID of the topic.
+     */
+    FimoU8 id[16];
+    /**
+     * Version of the topic.
+     */
+    FimoVersion version;
+} FimoBusTopic;
+
+/**
+ * An event published on the bus.
+ *
+ * The payload is marshaled as an opaque byte buffer. Since queued
+ * events are copied and delivered at a later point in time, the
+ * payload must not contain any pointers to data owned by the
+ * publisher.
+ */
+typedef struct FimoBusEvent {
+    /**
+     * Topic of the event.
+     */
+    const FimoBusTopic *topic;
+    /**
+     * Pointer to the payload of the event.
+     *
+     * May be `NULL`, if `payload_size` is `0`. A payload copied
+     * by the bus is aligned to `FIMO_MALLOC_ALIGNMENT`.
+     */
+    const void *payload;
+    /**
+     * Size of the payload in bytes.
+     */
+    FimoUSize payload_size;
+} FimoBusEvent;
+
+/**
+ * Delivery mode of a subscription.
+ */
+typedef enum FimoBusDeliveryMode {
+    /**
+     * Events are delivered synchronously on the publishing thread.
+     */
+    FIMO_BUS_DELIVERY_MODE_SYNC,
+    /**
+     * Events are copied into a queue owned by the subscription, and
+     * are delivered once the subscriber calls `fimo_bus_dispatch`.
+     */
+    FIMO_BUS_DELIVERY_MODE_QUEUED,
+    FIMO_BUS_DELIVERY_MODE_FORCE32 = 0x7FFFFFFF
+} FimoBusDeliveryMode;
+
+/**
+ * Configuration of a new subscription.
+ */
+typedef struct FimoBusSubscriber {
+    /**
+     * Reserved for future use. Must be `NULL`.
+     */
+    const void *next;
+    /**
+     * Subscribed topic.
+     *
+     * The version is the minimum required version of the topic.
+     */
+    FimoBusTopic topic;
+    /**
+     * Delivery mode of the events.
+     */
+    FimoBusDeliveryMode mode;
+    /**
+     * Data passed to the callbacks.
+     */
+    void *data;
+    /**
+     * Function invoked for each delivered event.
+     *
+     * Must be thread-safe, in case the delivery mode is
+     * `FIMO_BUS_DELIVERY_MODE_SYNC`. The event is only valid for
+     * the duration of the call.
+     */
+    void (*on_event)(void *data, const FimoBusEvent *event);
+    /**
+     * Optional function invoked once the subscription is destroyed.
+     */
+    void (*on_drop)(void *data);
+} FimoBusSubscriber;
+
+/**
+ * Handle to a subscription of the bus.
+ */
+typedef struct FimoBusSubscription FimoBusSubscription;
+
+/**
+ * Topic of the event published after a module has been loaded.
+ *
+ * The payload consists of the null-terminated name of the module.
+ * Synchronous subscribers are invoked after the module subsystem
+ * has been unlocked, and may call back into it.
+ */
+FIMO_EXPORT
+extern const FimoBusTopic FIMO_BUS_TOPIC_MODULE_LOADED;
+
+/**
+ * Topic of the event published after a module has been unloaded.
+ *
+ * The payload consists of the null-terminated name of the module.
+ * Synchronous subscribers are invoked after the module subsystem
+ * has been unlocked, and may call back into it.
+ */
+FIMO_EXPORT
+extern const FimoBusTopic FIMO_BUS_TOPIC_MODULE_UNLOADED;
+
+/**
+ * Topic of the event published after a module binary has failed the
+ * verification of its signature.
+ *
+ * The payload consists of the null-terminated path of the binary.
+ */
+FIMO_EXPORT
+extern const FimoBusTopic FIMO_BUS_TOPIC_MODULE_VERIFICATION_FAILED;
+
+/**
+ * Topic of the event published after the construction of a module
+ * has failed, e.g., due to an error or a panic in its constructor.
+ *
+ * The payload consists of the null-terminated name of the module,
+ * followed by the null-terminated description of the error.
+ * Synchronous subscribers are invoked after the module subsystem
+ * has been unlocked, and may call back into it.
+ */
+FIMO_EXPORT
+extern const FimoBusTopic FIMO_BUS_TOPIC_MODULE_CONSTRUCTION_FAILED;
+
+/**
+ * VTable of the bus subsystem.
+ *
+ * Changing the VTable is a breaking change.
+ */
+typedef struct FimoBusVTableV0 {
+    FimoResult (*subscribe)(void *, const FimoBusSubscriber *, FimoBusSubscription **);
+    FimoResult (*unsubscribe)(void *, FimoBusSubscription *);
+    FimoResult (*publish)(void *, const FimoBusEvent *);
+    FimoResult (*dispatch)(void *, FimoBusSubscription *, FimoUSize *);
+} FimoBusVTableV0;
+
+/**
+ * Subscribes to a topic of the bus.
+ *
+ * If successful, the subscription is written into `subscription`,
+ * and receives all events published to a compatible topic after
+ * this function returns. The subscription must be removed with
+ * `fimo_bus_unsubscribe` before the context is destroyed.
+ *
+ * @param context the context
+ * @param subscriber subscription configuration
+ * @param subscription pointer to the resulting subscription
+ *
+ * @return Status code.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_bus_subscribe(FimoContext context, const FimoBusSubscriber *subscriber,
+                              FimoBusSubscription **subscription);
+
+/**
+ * Removes a subscription from the bus.
+ *
+ * Pending queued events are discarded. Waits until the events that
+ * are being delivered by other threads, either synchronously or by
+ * `fimo_bus_dispatch`, have been handled. If called from an event
+ * handler of the subscription, the handlers running on the current
+ * thread are not waited for. The `on_drop` function of the
+ * subscriber is invoked once all deliveries have completed. The
+ * subscription may not be used afterward.
+ *
+ * @param context the context
+ * @param subscription subscription to remove
+ *
+ * @return Status code.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_bus_unsubscribe(FimoContext context, FimoBusSubscription *subscription);
+
+/**
+ * Publishes an event on the bus.
+ *
+ * Delivers the event synchronously to all subscribers with the
+ * delivery mode `FIMO_BUS_DELIVERY_MODE_SYNC`, and enqueues a
+ * copy of the event for all other subscribers of the topic. The
+ * subscribers are invoked without holding any lock of the bus,
+ * i.e., they may call into the bus themselves.
+ *
+ * @param context the context
+ * @param event event to publish
+ *
+ * @return Status code.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_bus_publish(FimoContext context, const FimoBusEvent *event);
+
+/**
+ * Delivers the queued events of a subscription.
+ *
+ * Invokes the `on_event` function of the subscriber on the calling
+ * thread for each event enqueued before the call, in the order they
+ * were published. Calling this function concurrently for the same
+ * subscription may reorder the events. If `count` is not `NULL`,
+ * the number of delivered events is written into it.
+ *
+ * @param context the context
+ * @param subscription subscription with the delivery mode `FIMO_BUS_DELIVERY_MODE_QUEUED`
+ * @param count optional pointer to the number of delivered events
+ *
+ * @return Status code.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_bus_dispatch(FimoContext context, FimoBusSubscription *subscription, FimoUSize *count);
+
+#ifdef __cplusplus
+}
+#endif // __cplusplus
+
+#endif // FIMO_BUS_H
@@ -0,0 +1,104 @@
+#ifndef FIMO_CLOCK_H
+#define FIMO_CLOCK_H
+
+#include <fimo_std/context.h>
+#include <fimo_std/time.h>
+#include <fimo_std/utils.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Configuration of the clock of the context.
+ *
+ * Can be passed when creating the context, to replace the system
+ * clocks with custom clocks, e.g., a manually advanced clock for
+ * deterministic tests. The clocks are queried by all subsystems
+ * of the context, and may be invoked concurrently from any thread.
+ * If no configuration is passed, the context uses the wall clock
+ * of the system, and its monotonic clock.
+ */
+typedef struct FimoClockConfig {
+    /**
+     * Type of the struct.
+     *
+     * Must be `FIMO_STRUCT_TYPE_CLOCK_CONFIG`.
+     */
+    FimoStructType type;
+    /**
+     * Pointer to a possible extension.
+     *
+     * Reserved for future use. Must be `NULL`.
+     */
+    const struct FimoBaseStructIn *next;
+    /**
+     * Custom data passed to the clock functions.
+     */
+    void *data;
+    /**
+     * Returns the current time of the wall clock.
+     *
+     * Must not be `NULL`.
+     */
+    FimoTime (*now)(void *data);
+    /**
+     * Returns the current time of the monotonic clock.
+     *
+     * Must not be `NULL`. Consecutive calls must never return a
+     * time point that is earlier than a previously returned one.
+     */
+    FimoTimeMonotonic (*monotonic_now)(void *data);
+    /**
+     * Optional function to call when the clock is destroyed.
+     *
+     * The ownership of `data` is transferred to the context, which
+     * invokes the function once it is destroyed, or if its creation
+     * fails.
+     */
+    void (*on_drop)(void *data);
+} FimoClockConfig;
+
+/**
+ * VTable of the clock subsystem.
+ *
+ * Changing the VTable is a breaking change.
+ */
+typedef struct FimoClockVTableV0 {
+    FimoTime (*now)(void *);
+    FimoTimeMonotonic (*monotonic_now)(void *);
+} FimoClockVTableV0;
+
+/**
+ * Returns the current time of the wall clock of the context.
+ *
+ * Unlike `fimo_time_now`, the time is read from the clock configured
+ * at the creation of the context.
+ *
+ * @param context the context
+ *
+ * @return Current time.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoTime fimo_clock_now(FimoContext context);
+
+/**
+ * Returns the current time of the monotonic clock of the context.
+ *
+ * Unlike `fimo_time_monotonic_now`, the time is read from the clock
+ * configured at the creation of the context.
+ *
+ * @param context the context
+ *
+ * @return Current monotonic time.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoTimeMonotonic fimo_clock_monotonic_now(FimoContext context);
+
+#ifdef __cplusplus
+}
+#endif // __cplusplus
+
+#endif // FIMO_CLOCK_H
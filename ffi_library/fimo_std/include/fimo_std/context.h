@@ -32,6 +32,9 @@ typedef enum FimoStructType {
     FIMO_STRUCT_TYPE_TRACING_SUBSCRIBER,
     FIMO_STRUCT_TYPE_MODULE_EXPORT,
     FIMO_STRUCT_TYPE_MODULE_INFO,
+    FIMO_STRUCT_TYPE_TRACING_MESSAGE_TEMPLATE,
+    FIMO_STRUCT_TYPE_CLOCK_CONFIG,
+    FIMO_STRUCT_TYPE_TRACING_SUBSCRIBER_BATCH_EXTENSION,
     FIMO_STRUCT_TYPE_FORCE32 = 0x7FFFFFFF
 } FimoStructType;
 
@@ -0,0 +1,112 @@
+#ifndef FIMO_INTERNAL_BUS_H
+#define FIMO_INTERNAL_BUS_H
+
+#include <fimo_std/bus.h>
+#include <fimo_std/error.h>
+
+#if __APPLE__
+#include <tinycthread/tinycthread.h>
+#else
+#include <threads.h>
+#endif
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * Internal representation of the bus subsystem.
+ */
+typedef struct FimoInternalBusContext {
+    mtx_t mutex;
+    cnd_t idle;
+    FimoBusSubscription *subscriptions;
+} FimoInternalBusContext;
+
+///////////////////////////////////////////////////////////////////////
+//// Trampoline functions
+///////////////////////////////////////////////////////////////////////
+
+FimoResult fimo_internal_trampoline_bus_subscribe(void *ctx, const FimoBusSubscriber *subscriber,
+                                                  FimoBusSubscription **subscription);
+FimoResult fimo_internal_trampoline_bus_unsubscribe(void *ctx, FimoBusSubscription *subscription);
+FimoResult fimo_internal_trampoline_bus_publish(void *ctx, const FimoBusEvent *event);
+FimoResult fimo_internal_trampoline_bus_dispatch(void *ctx, FimoBusSubscription *subscription, FimoUSize *count);
+
+///////////////////////////////////////////////////////////////////////
+//// Bus Subsystem API
+///////////////////////////////////////////////////////////////////////
+
+/**
+ * Initializes the bus subsystem.
+ *
+ * @param ctx partially initialized context
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+FimoResult fimo_internal_bus_init(FimoInternalBusContext *ctx);
+
+/**
+ * Destroys the bus subsystem.
+ *
+ * Removes all remaining subscriptions. The caller must ensure that
+ * they are responsible for destroying the context.
+ *
+ * @param ctx the context
+ */
+void fimo_internal_bus_destroy(FimoInternalBusContext *ctx);
+
+/**
+ * Subscribes to a topic of the bus.
+ *
+ * @param ctx the context
+ * @param subscriber subscription configuration
+ * @param subscription pointer to the resulting subscription
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+FimoResult fimo_internal_bus_subscribe(FimoInternalBusContext *ctx, const FimoBusSubscriber *subscriber,
+                                       FimoBusSubscription **subscription);
+
+/**
+ * Removes a subscription from the bus.
+ *
+ * @param ctx the context
+ * @param subscription subscription to remove
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+FimoResult fimo_internal_bus_unsubscribe(FimoInternalBusContext *ctx, FimoBusSubscription *subscription);
+
+/**
+ * Publishes an event on the bus.
+ *
+ * @param ctx the context
+ * @param event event to publish
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+FimoResult fimo_internal_bus_publish(FimoInternalBusContext *ctx, const FimoBusEvent *event);
+
+/**
+ * Delivers the queued events of a subscription.
+ *
+ * @param ctx the context
+ * @param subscription subscription to dispatch the events of
+ * @param count optional pointer to the number of delivered events
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+FimoResult fimo_internal_bus_dispatch(FimoInternalBusContext *ctx, FimoBusSubscription *subscription,
+                                      FimoUSize *count);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // FIMO_INTERNAL_BUS_H
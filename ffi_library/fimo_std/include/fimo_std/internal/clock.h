@@ -0,0 +1,88 @@
+#ifndef FIMO_INTERNAL_CLOCK_H
+#define FIMO_INTERNAL_CLOCK_H
+
+#include <fimo_std/clock.h>
+#include <fimo_std/error.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * Internal representation of the clock subsystem.
+ */
+typedef struct FimoInternalClockContext {
+    void *data;
+    FimoTime (*now)(void *data);
+    FimoTimeMonotonic (*monotonic_now)(void *data);
+    void (*on_drop)(void *data);
+} FimoInternalClockContext;
+
+///////////////////////////////////////////////////////////////////////
+//// Trampoline functions
+///////////////////////////////////////////////////////////////////////
+
+FimoTime fimo_internal_trampoline_clock_now(void *ctx);
+FimoTimeMonotonic fimo_internal_trampoline_clock_monotonic_now(void *ctx);
+
+///////////////////////////////////////////////////////////////////////
+//// Clock Subsystem API
+///////////////////////////////////////////////////////////////////////
+
+/**
+ * Initializes the clock subsystem.
+ *
+ * If `options` is `NULL`, the clocks of the system are used. On
+ * success, the ownership of the options is transferred to the
+ * subsystem.
+ *
+ * @param ctx partially initialized context
+ * @param options init options
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+FimoResult fimo_internal_clock_init(FimoInternalClockContext *ctx, const FimoClockConfig *options);
+
+/**
+ * Destroys the clock subsystem.
+ *
+ * The caller must ensure that they are responsible for destroying
+ * the context, and that the other subsystems have been destroyed.
+ *
+ * @param ctx the context
+ */
+void fimo_internal_clock_destroy(FimoInternalClockContext *ctx);
+
+/**
+ * Cleans up the resources specified in the options.
+ *
+ * @param options init options
+ */
+void fimo_internal_clock_cleanup_options(const FimoClockConfig *options);
+
+/**
+ * Returns the current time of the wall clock.
+ *
+ * @param ctx the context
+ *
+ * @return Current time.
+ */
+FIMO_MUST_USE
+FimoTime fimo_internal_clock_now(FimoInternalClockContext *ctx);
+
+/**
+ * Returns the current time of the monotonic clock.
+ *
+ * @param ctx the context
+ *
+ * @return Current monotonic time.
+ */
+FIMO_MUST_USE
+FimoTimeMonotonic fimo_internal_clock_monotonic_now(FimoInternalClockContext *ctx);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // FIMO_INTERNAL_CLOCK_H
@@ -6,6 +6,8 @@
 #include <fimo_std/refcount.h>
 #include <fimo_std/version.h>
 
+#include <fimo_std/internal/bus.h>
+#include <fimo_std/internal/clock.h>
 #include <fimo_std/internal/module.h>
 #include <fimo_std/internal/tracing.h>
 
@@ -22,8 +24,10 @@ extern "C" {
  */
 typedef struct FimoInternalContext {
     FimoAtomicRefCount ref_count;
+    FimoInternalClockContext clock;
     FimoInternalTracingContext tracing;
     FimoInternalModuleContext module;
+    FimoInternalBusContext bus;
 } FimoInternalContext;
 
 /**
@@ -0,0 +1,106 @@
+#ifndef FIMO_INTERNAL_ED25519_H
+#define FIMO_INTERNAL_ED25519_H
+
+#include <stdbool.h>
+
+#include <fimo_std/integers.h>
+#include <fimo_std/utils.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * Size of an Ed25519 public key in bytes.
+ */
+#define FIMO_INTERNAL_ED25519_PUBLIC_KEY_SIZE 32
+
+/**
+ * Size of an Ed25519 signature in bytes.
+ */
+#define FIMO_INTERNAL_ED25519_SIGNATURE_SIZE 64
+
+/**
+ * Incremental SHA-512 state.
+ */
+typedef struct FimoInternalSha512 {
+    FimoU64 state[8];
+    FimoU64 length;
+    FimoU8 buffer[128];
+    FimoUSize buffer_len;
+} FimoInternalSha512;
+
+/**
+ * Incremental verifier of an Ed25519 signature.
+ *
+ * The verifier allows checking the signature of large messages,
+ * like module binaries, without loading them into memory.
+ */
+typedef struct FimoInternalEd25519Verifier {
+    FimoInternalSha512 hash;
+    FimoU8 public_key[FIMO_INTERNAL_ED25519_PUBLIC_KEY_SIZE];
+    FimoU8 signature[FIMO_INTERNAL_ED25519_SIGNATURE_SIZE];
+} FimoInternalEd25519Verifier;
+
+/**
+ * Initializes a SHA-512 state.
+ *
+ * @param hash state to initialize
+ */
+void fimo_internal_sha512_init(FimoInternalSha512 *hash);
+
+/**
+ * Appends data to the hashed message.
+ *
+ * @param hash hash state
+ * @param data message data
+ * @param len length of the data
+ */
+void fimo_internal_sha512_update(FimoInternalSha512 *hash, const void *data, FimoUSize len);
+
+/**
+ * Computes the digest of the hashed message.
+ *
+ * @param hash hash state
+ * @param digest resulting digest
+ */
+void fimo_internal_sha512_final(FimoInternalSha512 *hash, FimoU8 digest[64]);
+
+/**
+ * Initializes a verifier of an Ed25519 signature.
+ *
+ * @param verifier verifier to initialize
+ * @param public_key public key of the signer
+ * @param signature signature to verify
+ */
+void fimo_internal_ed25519_verify_init(FimoInternalEd25519Verifier *verifier,
+                                       const FimoU8 public_key[FIMO_INTERNAL_ED25519_PUBLIC_KEY_SIZE],
+                                       const FimoU8 signature[FIMO_INTERNAL_ED25519_SIGNATURE_SIZE]);
+
+/**
+ * Appends data to the signed message.
+ *
+ * @param verifier verifier
+ * @param data message data
+ * @param len length of the data
+ */
+void fimo_internal_ed25519_verify_update(FimoInternalEd25519Verifier *verifier, const void *data, FimoUSize len);
+
+/**
+ * Checks whether the signature is valid for the message.
+ *
+ * The verification is performed in variable time, as it only
+ * operates on public data.
+ *
+ * @param verifier verifier
+ *
+ * @return `true` if the signature is valid.
+ */
+FIMO_MUST_USE
+bool fimo_internal_ed25519_verify_final(FimoInternalEd25519Verifier *verifier);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif // FIMO_INTERNAL_ED25519_H
@@ -22,8 +22,21 @@ typedef struct FimoInternalModuleContext {
     struct hashmap *symbols;
     struct hashmap *modules;
     struct hashmap *namespaces;
+    struct hashmap *param_overrides;
+    struct hashmap *feature_overrides;
     FimoGraph *dependency_graph;
     bool is_loading;
+    mtx_t loaders_mutex;
+    struct ModuleLoader_ *loaders;
+    mtx_t signatures_mutex;
+    FimoModuleSignaturePolicy signature_policy;
+    bool binary_loaded;
+    struct hashmap *trusted_keys;
+    struct hashmap *pinned_keys;
+    FimoModuleFaultPolicy fault_policy;
+    mtx_t events_mutex;
+    struct PendingEvent_ *pending_events;
+    struct PendingEvent_ *pending_events_tail;
 } FimoInternalModuleContext;
 
 ///////////////////////////////////////////////////////////////////////
@@ -50,6 +63,8 @@ FimoResult fimo_internal_trampoline_module_set_append_modules(
         void *ctx, FimoModuleLoadingSet *set, const char *module_path, FimoModuleLoadingFilter filter,
         void *filter_data, void (*export_iterator)(bool (*)(const FimoModuleExport *, void *), void *),
         const void *binary_handle);
+FimoResult fimo_internal_trampoline_module_set_append_group(void *ctx, FimoModuleLoadingSet *set, const char *group,
+                                                          const char *const *modules, FimoUSize modules_count);
 FimoResult fimo_internal_trampoline_module_set_dismiss(void *ctx, FimoModuleLoadingSet *set);
 FimoResult fimo_internal_trampoline_module_set_finish(void *ctx, FimoModuleLoadingSet *set);
 FimoResult fimo_internal_trampoline_module_find_by_name(void *ctx, const char *name, const FimoModuleInfo **module);
@@ -92,6 +107,24 @@ FimoResult fimo_internal_trampoline_module_param_set_inner(void *ctx, const Fimo
                                                            FimoModuleParamType type, FimoModuleParamData *param);
 FimoResult fimo_internal_trampoline_module_get_inner(void *ctx, const FimoModule *module, void *value,
                                                      FimoModuleParamType *type, const FimoModuleParamData *param);
+FimoResult fimo_internal_trampoline_module_symbol_use_counts(void *ctx, const FimoModuleInfo *module,
+                                                             FimoModuleSymbolUseCount **use_counts,
+                                                             FimoUSize *use_counts_count);
+FimoResult fimo_internal_trampoline_module_param_list(void *ctx, const char *module_name, FimoModuleParamInfo **params,
+                                                      FimoUSize *params_count);
+FimoResult fimo_internal_trampoline_module_param_set_override(void *ctx, const char *module_name, const char *param,
+                                                              const void *value, FimoModuleParamType type);
+FimoResult fimo_internal_trampoline_module_loader_register(void *ctx, const FimoModuleLoader *loader);
+FimoResult fimo_internal_trampoline_module_loader_unregister(void *ctx, const char *name);
+FimoResult fimo_internal_trampoline_module_signature_set_policy(void *ctx, FimoModuleSignaturePolicy policy);
+FimoResult fimo_internal_trampoline_module_signature_trust_key(void *ctx, const FimoU8 *key);
+FimoResult fimo_internal_trampoline_module_fault_set_policy(void *ctx, FimoModuleFaultPolicy policy);
+FimoResult fimo_internal_trampoline_module_feature_list(void *ctx, const char *module_name,
+                                                        FimoModuleFeatureInfo **features, FimoUSize *features_count);
+FimoResult fimo_internal_trampoline_module_feature_set_override(void *ctx, const char *module_name,
+                                                                const char *feature, const bool *enabled);
+FimoResult fimo_internal_trampoline_module_feature_enabled(void *ctx, const FimoModule *module, const char *feature,
+                                                           bool *enabled);
 
 ///////////////////////////////////////////////////////////////////////
 //// Module Subsystem API
@@ -286,6 +319,26 @@ fimo_internal_module_set_append_modules(FimoInternalModuleContext *ctx, FimoModu
                                         void (*export_iterator)(bool (*)(const FimoModuleExport *, void *), void *),
                                         const void *binary_handle);
 
+/**
+ * Groups modules of the module set.
+ *
+ * Marks the modules `modules`, which must already be contained in
+ * the set and must not be part of another group, as members of the
+ * module group `group`. The members of a group are loaded atomically.
+ *
+ * @param ctx the context
+ * @param set set of modules
+ * @param group name of the group
+ * @param modules names of the group members
+ * @param modules_count number of group members
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+FimoResult fimo_internal_module_set_append_group(FimoInternalModuleContext *ctx, FimoModuleLoadingSet *set,
+                                                 const char *group, const char *const *modules,
+                                                 FimoUSize modules_count);
+
 /**
  * Destroys the module set without loading any modules.
  *
@@ -530,6 +583,23 @@ FimoResult fimo_internal_module_load_symbol(FimoInternalModuleContext *ctx, cons
 FIMO_MUST_USE
 FimoResult fimo_internal_module_unload(FimoInternalModuleContext *ctx, const FimoModuleInfo *module);
 
+/**
+ * Queries the number of uses of the symbols exported by a module.
+ *
+ * The entries, and the strings they reference, are stored in a single
+ * allocation, which must be freed with `fimo_free`.
+ *
+ * @param ctx the context
+ * @param module module exporting the symbols
+ * @param use_counts resulting array of use counts
+ * @param use_counts_count number of entries in `use_counts`
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+FimoResult fimo_internal_module_symbol_use_counts(FimoInternalModuleContext *ctx, const FimoModuleInfo *module,
+                                                  FimoModuleSymbolUseCount **use_counts, FimoUSize *use_counts_count);
+
 /**
  * Queries the info of a module parameter.
  *
@@ -551,6 +621,155 @@ FimoResult fimo_internal_module_param_query(FimoInternalModuleContext *ctx, cons
                                             FimoModuleParamType *type, FimoModuleParamAccess *read,
                                             FimoModuleParamAccess *write);
 
+/**
+ * Enumerates the parameters of a module.
+ *
+ * The entries, and the strings they reference, are stored in a single
+ * allocation, which must be freed with `fimo_free`.
+ *
+ * @param ctx context
+ * @param module_name name of the module containing the parameters
+ * @param params resulting array of parameter infos
+ * @param params_count number of entries in `params`
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+FimoResult fimo_internal_module_param_list(FimoInternalModuleContext *ctx, const char *module_name,
+                                           FimoModuleParamInfo **params, FimoUSize *params_count);
+
+/**
+ * Overrides the initial value of a module parameter.
+ *
+ * The override is applied to the modules loaded after this call,
+ * before their constructor is called. Setting `value` to `NULL`
+ * removes the override.
+ *
+ * @param ctx context
+ * @param module_name name of the module containing the parameter
+ * @param param name of the parameter
+ * @param value initial value of the parameter
+ * @param type type of the value
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+FimoResult fimo_internal_module_param_set_override(FimoInternalModuleContext *ctx, const char *module_name,
+                                                   const char *param, const void *value, FimoModuleParamType type);
+
+/**
+ * Registers a loader for module binaries of a custom format.
+ *
+ * The loader is used by all future calls to `fimo_module_set_append_modules`
+ * whose binary path is matched by the pattern of the loader. The
+ * name and pattern of the loader are copied, while `data` is owned by
+ * the context until the loader is dropped. Registering a loader with
+ * the same name as an existing loader results in `FIMO_EEXIST`.
+ *
+ * @param ctx context
+ * @param loader loader to register
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+FimoResult fimo_internal_module_loader_register(FimoInternalModuleContext *ctx, const FimoModuleLoader *loader);
+
+/**
+ * Unregisters a loader for module binaries of a custom format.
+ *
+ * The loader is no longer selected for new binaries. Binaries that
+ * are already open keep the loader alive, until they are closed.
+ *
+ * @param ctx context
+ * @param name name of the loader
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+FimoResult fimo_internal_module_loader_unregister(FimoInternalModuleContext *ctx, const char *name);
+
+/**
+ * Sets the verification policy of the signatures of module binaries.
+ *
+ * @param ctx context
+ * @param policy verification policy
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+FimoResult fimo_internal_module_signature_set_policy(FimoInternalModuleContext *ctx, FimoModuleSignaturePolicy policy);
+
+/**
+ * Adds a public key to the set of trusted keys.
+ *
+ * @param ctx context
+ * @param key public key to trust
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+FimoResult fimo_internal_module_signature_trust_key(FimoInternalModuleContext *ctx, const FimoU8 *key);
+
+/**
+ * Sets the policy for handling modules that fail to be constructed.
+ *
+ * @param ctx context
+ * @param policy fault policy
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+FimoResult fimo_internal_module_fault_set_policy(FimoInternalModuleContext *ctx, FimoModuleFaultPolicy policy);
+
+/**
+ * Enumerates the features of a module.
+ *
+ * The entries, and the strings they reference, are stored in a single
+ * allocation, which must be freed with `fimo_free`.
+ *
+ * @param ctx context
+ * @param module_name name of the module declaring the features
+ * @param features resulting array of feature infos
+ * @param features_count number of entries in `features`
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+FimoResult fimo_internal_module_feature_list(FimoInternalModuleContext *ctx, const char *module_name,
+                                             FimoModuleFeatureInfo **features, FimoUSize *features_count);
+
+/**
+ * Overrides the default state of a module feature.
+ *
+ * The override is applied to the modules loaded after this call,
+ * before their constructor is called. Setting `enabled` to `NULL`
+ * removes the override.
+ *
+ * @param ctx context
+ * @param module_name name of the module declaring the feature
+ * @param feature name of the feature
+ * @param enabled state of the feature
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+FimoResult fimo_internal_module_feature_set_override(FimoInternalModuleContext *ctx, const char *module_name,
+                                                     const char *feature, const bool *enabled);
+
+/**
+ * Checks whether a feature of the module is enabled.
+ *
+ * @param ctx context
+ * @param module module of the caller
+ * @param feature name of the feature
+ * @param enabled query result
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+FimoResult fimo_internal_module_feature_enabled(FimoInternalModuleContext *ctx, const FimoModule *module,
+                                                const char *feature, bool *enabled);
+
 /**
  * Sets a module parameter with public write access.
  *
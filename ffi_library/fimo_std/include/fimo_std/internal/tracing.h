@@ -220,6 +220,10 @@ bool fimo_internal_trampoline_tracing_is_enabled(void *ctx);
 FimoResult fimo_internal_trampoline_tracing_register_thread(void *ctx);
 FimoResult fimo_internal_trampoline_tracing_unregister_thread(void *ctx);
 FimoResult fimo_internal_trampoline_tracing_flush(void *ctx);
+FimoResult fimo_internal_trampoline_tracing_event_emit_batch(void *ctx, const FimoTracingRecord *records,
+                                                             FimoUSize record_count);
+FimoResult fimo_internal_trampoline_tracing_span_stack(void *ctx, FimoTracingSpanInfo *spans, FimoUSize capacity,
+                                                       FimoUSize *span_count);
 
 ///////////////////////////////////////////////////////////////////////
 //// Tracing Subsystem API
@@ -525,6 +529,37 @@ FimoResult fimo_internal_tracing_unregister_thread(FimoInternalTracingContext *c
 FIMO_MUST_USE
 FimoResult fimo_internal_tracing_flush(FimoInternalTracingContext *ctx);
 
+/**
+ * Emits a batch of pre-formatted events.
+ *
+ * The records are emitted in order on the active call stack, and are
+ * all timestamped with the same time. Records that would not be traced
+ * are skipped.
+ *
+ * @param ctx the context
+ * @param records array of records to emit
+ * @param record_count number of records
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+FimoResult fimo_internal_tracing_event_emit_batch(FimoInternalTracingContext *ctx, const FimoTracingRecord *records,
+                                                  FimoUSize record_count);
+
+/**
+ * Captures the spans entered on the active call stack.
+ *
+ * @param ctx the context
+ * @param spans array receiving the spans
+ * @param capacity length of `spans`
+ * @param span_count pointer to the number of entered spans
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+FimoResult fimo_internal_tracing_span_stack(FimoInternalTracingContext *ctx, FimoTracingSpanInfo *spans,
+                                            FimoUSize capacity, FimoUSize *span_count);
+
 #ifdef __cplusplus
 }
 #endif
@@ -200,6 +200,51 @@ void fimo_free_sized(void *ptr, size_t size);
 FIMO_EXPORT
 void fimo_free_aligned_sized(void *ptr, size_t alignment, size_t size);
 
+/**
+ * Allocation counters of the default allocator.
+ *
+ * The counters are not synchronized, and must only be accessed by
+ * the thread they are installed on, or while they are not installed.
+ */
+typedef struct FimoMemoryCounters {
+    /**
+     * Number of successful allocations.
+     */
+    size_t allocations;
+    /**
+     * Number of deallocations.
+     */
+    size_t deallocations;
+    /**
+     * Number of allocated bytes.
+     */
+    size_t allocated_bytes;
+    /**
+     * Number of deallocated bytes.
+     *
+     * May be lower than the exact number, on platforms where the
+     * size of an allocation can not be queried.
+     */
+    size_t deallocated_bytes;
+} FimoMemoryCounters;
+
+/**
+ * Installs allocation counters for the calling thread.
+ *
+ * While the counters are installed, all allocations and deallocations
+ * performed by the calling thread through the functions of this header
+ * are recorded in `counters`. A value of `NULL` stops the recording.
+ * The counters are local to the instance of the library, i.e., the
+ * allocations of a binary linking its own copy of the library are
+ * not recorded.
+ *
+ * @param counters: new counters or `NULL`
+ *
+ * @return Previously installed counters or `NULL`.
+ */
+FIMO_EXPORT
+FimoMemoryCounters *fimo_memory_swap_counters(FimoMemoryCounters *counters);
+
 #ifdef __cplusplus
 }
 #endif
@@ -354,7 +354,7 @@ extern "C" {
 /**
  * ABI version of the current module export.
  */
-#define FIMO_MODULE_EXPORT_ABI 0
+#define FIMO_MODULE_EXPORT_ABI 1
 
 #ifdef _WIN32
 #define FIMO_MODULE_EXPORT_MODULE__(VAR)                                                                               \
@@ -370,6 +370,8 @@ extern "C" {
     FimoModuleExport VAR = {.type = FIMO_STRUCT_TYPE_MODULE_EXPORT,                                                    \
                             .next = NULL,                                                                              \
                             .export_abi = FIMO_MODULE_EXPORT_ABI,                                                      \
+                            .context_version = FIMO_VERSION_LONG(FIMO_VERSION_MAJOR, FIMO_VERSION_MINOR,               \
+                                                                 FIMO_VERSION_PATCH, FIMO_VERSION_BUILD_NUMBER),       \
                             .name = NAME,                                                                              \
                             .description = DESC,                                                                       \
                             .author = AUTHOR,                                                                          \
@@ -817,6 +819,75 @@ typedef struct FimoModuleParamDecl {
     } default_value;
 } FimoModuleParamDecl;
 
+/**
+ * Information of a module parameter.
+ */
+typedef struct FimoModuleParamInfo {
+    /**
+     * Name of the parameter.
+     */
+    const char *name;
+    /**
+     * Type of the parameter.
+     */
+    FimoModuleParamType type;
+    /**
+     * Access group specifier for the read permission.
+     */
+    FimoModuleParamAccess read_access;
+    /**
+     * Access group specifier for the write permission.
+     */
+    FimoModuleParamAccess write_access;
+} FimoModuleParamInfo;
+
+/**
+ * Stability of a module feature.
+ */
+typedef enum FimoModuleFeatureStability {
+    /**
+     * The feature is stable.
+     */
+    FIMO_MODULE_FEATURE_STABILITY_STABLE = 0,
+    /**
+     * The feature is experimental, and may change or be removed
+     * in future versions of the module.
+     */
+    FIMO_MODULE_FEATURE_STABILITY_EXPERIMENTAL = 1,
+    /**
+     * The feature is deprecated, and will be removed in a future
+     * version of the module.
+     */
+    FIMO_MODULE_FEATURE_STABILITY_DEPRECATED = 2,
+    FIMO_MODULE_FEATURE_STABILITY_FORCE32 = 0x7FFFFFFF
+} FimoModuleFeatureStability;
+
+/**
+ * Information of a module feature.
+ */
+typedef struct FimoModuleFeatureInfo {
+    /**
+     * Name of the feature.
+     */
+    const char *name;
+    /**
+     * Stability of the feature.
+     */
+    FimoModuleFeatureStability stability;
+    /**
+     * Whether the feature is enabled by default.
+     */
+    bool default_enabled;
+    /**
+     * Whether the context satisfies the version required by the feature.
+     */
+    bool available;
+    /**
+     * Whether the feature is enabled.
+     */
+    bool enabled;
+} FimoModuleFeatureInfo;
+
 /**
  * Declaration of a module resource.
  */
@@ -824,7 +895,9 @@ typedef struct FimoModuleResourceDecl {
     /**
      * Resource path relative to the module directory.
      *
-     * Must not be `NULL` or begin with a slash.
+     * Must not be `NULL`, begin with a slash or a drive prefix, or
+     * contain `..` components, i.e., it may not escape the module
+     * directory.
      */
     const char *path;
 } FimoModuleResourceDecl;
@@ -939,6 +1012,11 @@ typedef enum FimoModuleExportModifierKey {
      * a `FimoModuleInfo`.
      */
     FIMO_MODULE_EXPORT_MODIFIER_KEY_DEPENDENCY,
+    /**
+     * Declares a named feature flag of the module. The value must
+     * be a pointer to a `FimoModuleExportModifierFeature`.
+     */
+    FIMO_MODULE_EXPORT_MODIFIER_KEY_FEATURE,
     FIMO_MODULE_EXPORT_MODIFIER_KEY_LAST,
     FIMO_MODULE_EXPORT_MODIFIER_KEY_FORCE32 = 0x7FFFFFFF
 } FimoModuleExportModifierKey;
@@ -965,6 +1043,39 @@ typedef struct FimoModuleExportModifierDestructor {
     void (*destructor)(void *);
 } FimoModuleExportModifierDestructor;
 
+/**
+ * Value for the `FIMO_MODULE_EXPORT_MODIFIER_KEY_FEATURE` modifier key.
+ *
+ * The state of the feature is resolved when the module is loaded,
+ * before its constructor is called, and remains fixed until the
+ * module is unloaded. A feature is enabled, if it is enabled by
+ * default or by an override, and if the context satisfies the
+ * version required by the feature.
+ */
+typedef struct FimoModuleExportModifierFeature {
+    /**
+     * Name of the feature.
+     *
+     * Must not be `NULL`, and must be unique to the module.
+     */
+    const char *name;
+    /**
+     * Stability of the feature.
+     */
+    FimoModuleFeatureStability stability;
+    /**
+     * Whether the feature is enabled by default.
+     */
+    bool default_enabled;
+    /**
+     * Minimum version of the context required by the feature.
+     *
+     * The feature can not be enabled, if the version of the
+     * context is not compatible with the required version.
+     */
+    FimoVersion required_version;
+} FimoModuleExportModifierFeature;
+
 /**
  * Declaration of a module export.
  */
@@ -987,6 +1098,13 @@ typedef struct FimoModuleExport {
      * Must be `FIMO_MODULE_EXPORT_ABI`.
      */
     FimoI32 export_abi;
+    /**
+     * Version of the context the module was built against.
+     *
+     * The module can only be loaded by a context, whose version
+     * is compatible with the specified version.
+     */
+    FimoVersion context_version;
     /**
      * Module name.
      *
@@ -1110,6 +1228,36 @@ typedef struct FimoModuleRawSymbol {
     _Atomic(FimoUSize) lock;
 } FimoModuleRawSymbol;
 
+/**
+ * Number of uses of a symbol exported by a module.
+ */
+typedef struct FimoModuleSymbolUseCount {
+    /**
+     * Symbol name.
+     */
+    const char *name;
+    /**
+     * Symbol namespace.
+     */
+    const char *ns;
+    /**
+     * Symbol version.
+     */
+    FimoVersion version;
+    /**
+     * Number of times the symbol is currently locked.
+     */
+    FimoUSize use_count;
+    /**
+     * Names of the modules that imported or loaded the symbol.
+     */
+    const char *const *importers;
+    /**
+     * Number of entries in `importers`.
+     */
+    FimoUSize importers_count;
+} FimoModuleSymbolUseCount;
+
 // The use of atomics trips up our generation of rust bindings,
 // so we disable them.
 #ifndef FIMO_STD_BINDGEN
@@ -1212,6 +1360,12 @@ typedef struct FimoModuleInfo {
      * Path to the module directory.
      */
     const char *module_path;
+    /**
+     * Name of the module group containing the module.
+     *
+     * May be `NULL`, if the module is not part of a group.
+     */
+    const char *group;
     /**
      * Increases the reference count of the instance.
      *
@@ -1360,6 +1514,129 @@ typedef void (*FimoModuleLoadingSuccessCallback)(const FimoModuleInfo *arg0, voi
  */
 typedef void (*FimoModuleLoadingErrorCallback)(const FimoModuleExport *arg0, void *arg1);
 
+/**
+ * Loader for module binaries of a custom format.
+ *
+ * Loaders allow loading modules from artifacts that can not be opened
+ * by the native loader, e.g., modules compiled to an intermediate
+ * representation, or modules described by scripts. A loader is
+ * selected for a binary by `fimo_module_set_append_modules`, if its
+ * `pattern` matches the file name of the binary. The pattern may
+ * contain the wildcards `*`, matching any sequence of characters,
+ * and `?`, matching a single character. Registered loaders are tried
+ * in the order of their registration, and binaries not matched by any
+ * loader are opened by the native loader.
+ *
+ * The `load` function opens the binary at the provided path and
+ * writes an opaque handle to it into its last argument. If the
+ * signature of the binary was verified, the path refers to the
+ * verified contents, and may differ from the path of the binary. Afterwards,
+ * `iterate_exports` is called to pass each module exported by the
+ * binary to the provided function, until it returns `false`. The
+ * exports must remain valid until the binary is closed with `unload`,
+ * which happens once no module of the binary is loaded. After the
+ * loader is unregistered and all of its binaries have been closed,
+ * the optional `on_drop` function is called to clean up `data`. All
+ * functions may be called from multiple threads.
+ */
+typedef struct FimoModuleLoader {
+    /**
+     * Reserved for future use. Must be `NULL`.
+     */
+    const void *next;
+    /**
+     * Unique name of the loader.
+     */
+    const char *name;
+    /**
+     * Pattern of the file names handled by the loader.
+     */
+    const char *pattern;
+    /**
+     * Data passed to the functions of the loader.
+     */
+    void *data;
+    /**
+     * Opens a binary.
+     */
+    FimoResult (*load)(void *data, const char *path, void **binary);
+    /**
+     * Iterates over the modules exported by an open binary.
+     */
+    void (*iterate_exports)(void *data, void *binary, bool (*)(const FimoModuleExport *, void *), void *);
+    /**
+     * Closes a binary.
+     */
+    void (*unload)(void *data, void *binary);
+    /**
+     * Optional cleanup function for `data`.
+     */
+    void (*on_drop)(void *data);
+} FimoModuleLoader;
+
+/**
+ * Size of the public key of a module signature in bytes.
+ */
+#define FIMO_MODULE_SIGNATURE_PUBLIC_KEY_SIZE 32
+
+/**
+ * Size of a module signature file in bytes.
+ *
+ * A signature file consists of the Ed25519 public key of the signer,
+ * followed by the 64 byte Ed25519 signature of the contents of the
+ * binary.
+ */
+#define FIMO_MODULE_SIGNATURE_FILE_SIZE (FIMO_MODULE_SIGNATURE_PUBLIC_KEY_SIZE + 64)
+
+/**
+ * Verification policy of the signatures of module binaries.
+ *
+ * The signature of a binary is stored in a detached file, whose path
+ * is the path of the binary with an additional `.sig` extension. The
+ * binary is opened only once, and the verified contents are loaded,
+ * so that the binary can not be replaced after its verification. A binary
+ * failing the verification is not loaded. Each failure is published
+ * on the bus with the `FIMO_BUS_TOPIC_MODULE_VERIFICATION_FAILED` topic.
+ * The modules of the current binary are never verified.
+ */
+typedef enum FimoModuleSignaturePolicy {
+    /**
+     * Signatures are not verified.
+     */
+    FIMO_MODULE_SIGNATURE_POLICY_OFF = 0,
+    /**
+     * Binaries must be signed by a trusted key.
+     */
+    FIMO_MODULE_SIGNATURE_POLICY_REQUIRE = 1,
+    /**
+     * Binaries must be signed by a trusted key, or by the key that
+     * signed the first binary loaded from the same canonical path.
+     * The keys are pinned for the lifetime of the context, and are
+     * not persisted.
+     */
+    FIMO_MODULE_SIGNATURE_POLICY_TRUST_ON_FIRST_USE = 2,
+    FIMO_MODULE_SIGNATURE_POLICY_FORCE32 = 0x7FFFFFFF
+} FimoModuleSignaturePolicy;
+
+/**
+ * Policy for handling modules that fail to be constructed.
+ *
+ * Each failure is published on the bus with the
+ * `FIMO_BUS_TOPIC_MODULE_CONSTRUCTION_FAILED` topic. The modules
+ * depending on a failed module are never loaded.
+ */
+typedef enum FimoModuleFaultPolicy {
+    /**
+     * The remaining modules of the loading set continue to be loaded.
+     */
+    FIMO_MODULE_FAULT_POLICY_CONTINUE = 0,
+    /**
+     * The remaining modules of the loading set are skipped.
+     */
+    FIMO_MODULE_FAULT_POLICY_ABORT = 1,
+    FIMO_MODULE_FAULT_POLICY_FORCE32 = 0x7FFFFFFF
+} FimoModuleFaultPolicy;
+
 /**
  * VTable of the module subsystem.
  *
@@ -1404,6 +1681,18 @@ typedef struct FimoModuleVTableV0 {
     FimoResult (*param_set_inner)(void *, const FimoModule *, const void *, FimoModuleParamType, FimoModuleParamData *);
     FimoResult (*param_get_inner)(void *, const FimoModule *, void *, FimoModuleParamType *,
                                   const FimoModuleParamData *);
+    FimoResult (*symbol_use_counts)(void *, const FimoModuleInfo *, FimoModuleSymbolUseCount **, FimoUSize *);
+    FimoResult (*param_list)(void *, const char *, FimoModuleParamInfo **, FimoUSize *);
+    FimoResult (*param_set_override)(void *, const char *, const char *, const void *, FimoModuleParamType);
+    FimoResult (*loader_register)(void *, const FimoModuleLoader *);
+    FimoResult (*loader_unregister)(void *, const char *);
+    FimoResult (*signature_set_policy)(void *, FimoModuleSignaturePolicy);
+    FimoResult (*signature_trust_key)(void *, const FimoU8 *);
+    FimoResult (*fault_set_policy)(void *, FimoModuleFaultPolicy);
+    FimoResult (*set_append_group)(void *, FimoModuleLoadingSet *, const char *, const char *const *, FimoUSize);
+    FimoResult (*feature_list)(void *, const char *, FimoModuleFeatureInfo **, FimoUSize *);
+    FimoResult (*feature_set_override)(void *, const char *, const char *, const bool *);
+    FimoResult (*feature_enabled)(void *, const FimoModule *, const char *, bool *);
 } FimoModuleVTableV0;
 
 /**
@@ -1562,7 +1851,14 @@ FimoResult fimo_module_set_append_freestanding_module(const FimoModule *module,
  * in an error, if it does not export any modules. The necessary
  * symbols are setup automatically, if the binary was linked with
  * the fimo library. In case of an error, no modules are appended
- * to the set.
+ * to the set. If the file name of the binary is matched by a loader
+ * registered with `fimo_module_loader_register`, the binary is opened
+ * with said loader instead of the native loader. If the library was
+ * built with `FIMO_STD_STATIC_MODULES`, the native loader only supports
+ * the modules of the current binary, and a non `NULL` `module_path` not
+ * matched by a registered loader results in `FIMO_ENOTSUP`. Depending
+ * on the policy set with `fimo_module_signature_set_policy`, the
+ * signature of the binary is verified before it is opened.
  *
  * @param context the context
  * @param module_set set of modules
@@ -1577,6 +1873,119 @@ FIMO_MUST_USE
 FimoResult fimo_module_set_append_modules(FimoContext context, FimoModuleLoadingSet *module_set,
                                           const char *module_path, FimoModuleLoadingFilter filter, void *filter_data);
 
+/**
+ * Groups modules of the module set.
+ *
+ * Marks the modules `modules`, which must already be contained in
+ * the module set, as members of the module group `group`. The
+ * members of a group are loaded atomically, i.e., either all of
+ * them are loaded, or none of them. If the construction of a member
+ * fails, the already constructed members are unloaded again, and
+ * the callbacks of all members are invoked with an error. The
+ * success callbacks of the members are deferred until the entire
+ * group is constructed. Modules depending on a member of a group
+ * are loaded after all members of the group. The name of a group
+ * must be unique among the groups of the set and the groups of the
+ * loaded modules. A module may be a member of at most one group.
+ * Once loaded, the members of a group can only be unloaded together,
+ * see `fimo_module_unload`.
+ *
+ * @param context the context
+ * @param module_set set of modules
+ * @param group name of the group
+ * @param modules names of the group members
+ * @param modules_count number of group members
+ *
+ * @return Status code.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_set_append_group(FimoContext context, FimoModuleLoadingSet *module_set, const char *group,
+                                        const char *const *modules, FimoUSize modules_count);
+
+/**
+ * Registers a loader for module binaries of a custom format.
+ *
+ * The loader is used by all future calls to `fimo_module_set_append_modules`
+ * whose binary path is matched by the pattern of the loader. The
+ * name and pattern of the loader are copied, while `data` is owned by
+ * the context until the loader is dropped. Registering a loader with
+ * the same name as an existing loader results in `FIMO_EEXIST`. On
+ * error, the `on_drop` function of the loader is not called.
+ *
+ * @param context the context
+ * @param loader loader to register
+ *
+ * @return Status code.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_loader_register(FimoContext context, const FimoModuleLoader *loader);
+
+/**
+ * Unregisters a loader for module binaries of a custom format.
+ *
+ * The loader is no longer selected for new binaries. Binaries that
+ * are already open keep the loader alive, until they are closed.
+ * Returns `FIMO_ENOENT`, if no loader with the name is registered.
+ *
+ * @param context the context
+ * @param name name of the loader
+ *
+ * @return Status code.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_loader_unregister(FimoContext context, const char *name);
+
+/**
+ * Sets the verification policy of the signatures of module binaries.
+ *
+ * The policy applies to all future calls to `fimo_module_set_append_modules`.
+ * The default policy is `FIMO_MODULE_SIGNATURE_POLICY_OFF`. Once a binary
+ * has been loaded, the policy can only be made stricter, and relaxing it
+ * fails with `FIMO_EPERM`. This ensures that only the embedder is able to
+ * relax the policy.
+ *
+ * @param context the context
+ * @param policy verification policy
+ *
+ * @return Status code.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_signature_set_policy(FimoContext context, FimoModuleSignaturePolicy policy);
+
+/**
+ * Adds a public key to the set of trusted keys.
+ *
+ * Binaries signed by a trusted key pass the verification with every
+ * policy. Trusting a key multiple times has no effect.
+ *
+ * @param context the context
+ * @param key Ed25519 public key of `FIMO_MODULE_SIGNATURE_PUBLIC_KEY_SIZE` bytes
+ *
+ * @return Status code.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_signature_trust_key(FimoContext context, const FimoU8 *key);
+
+/**
+ * Sets the policy for handling modules that fail to be constructed.
+ *
+ * The policy applies to all future calls to `fimo_module_set_finish`.
+ * The default policy is `FIMO_MODULE_FAULT_POLICY_CONTINUE`.
+ *
+ * @param context the context
+ * @param policy fault policy
+ *
+ * @return Status code.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_fault_set_policy(FimoContext context, FimoModuleFaultPolicy policy);
+
 /**
  * Destroys the module set without loading any modules.
  *
@@ -1812,7 +2221,9 @@ FimoResult fimo_module_load_symbol(const FimoModule *module, const char *name, c
  * If successful, this function unloads the module `module`.
  * To succeed, the module no other module may depend on the module.
  * This function automatically unloads cleans up unreferenced modules,
- * except if they are a pseudo module.
+ * except if they are a pseudo module. If the module is a member of a
+ * module group, all members of the group are unloaded together. To
+ * succeed, no module outside of the group may depend on a member.
  *
  * Setting `module` to `NULL` only runs the cleanup of all loose modules.
  *
@@ -1825,6 +2236,29 @@ FIMO_EXPORT
 FIMO_MUST_USE
 FimoResult fimo_module_unload(FimoContext context, const FimoModuleInfo *module);
 
+/**
+ * Queries the number of uses of the symbols exported by a module.
+ *
+ * A symbol is in use while it is locked, e.g., with `FIMO_MODULE_SYMBOL_LOCK`.
+ * Each entry additionally lists the modules that have imported the symbol
+ * statically, or loaded it with `fimo_module_load_symbol`, and may therefore
+ * be locking it. The counts are only a snapshot, and may change concurrently.
+ * The entries, and the arrays and strings they reference, are stored in a
+ * single allocation, which must be freed with `fimo_free`. This function
+ * fails, if `module` is not loaded.
+ *
+ * @param context the context
+ * @param module module exporting the symbols
+ * @param use_counts resulting array of use counts
+ * @param use_counts_count number of entries in `use_counts`
+ *
+ * @return Status code.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_symbol_use_counts(FimoContext context, const FimoModuleInfo *module,
+                                         FimoModuleSymbolUseCount **use_counts, FimoUSize *use_counts_count);
+
 /**
  * Queries the info of a module parameter.
  *
@@ -1847,6 +2281,112 @@ FimoResult fimo_module_param_query(FimoContext context, const char *module_name,
                                    FimoModuleParamType *type, FimoModuleParamAccess *read,
                                    FimoModuleParamAccess *write);
 
+/**
+ * Enumerates the parameters of a module.
+ *
+ * Queries the name, the datatype, the read access, and the write access
+ * of each parameter of the module. The entries, and the strings they
+ * reference, are stored in a single allocation, which must be freed with
+ * `fimo_free`. This function fails, if the module can not be found.
+ *
+ * @param context context
+ * @param module_name name of the module containing the parameters
+ * @param params resulting array of parameter infos
+ * @param params_count number of entries in `params`
+ *
+ * @return Status code.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_param_list(FimoContext context, const char *module_name, FimoModuleParamInfo **params,
+                                  FimoUSize *params_count);
+
+/**
+ * Overrides the initial value of a module parameter.
+ *
+ * Instead of the default value of its declaration, the parameter will
+ * be initialized with `value`, when the module is loaded. The override
+ * is only applied to modules loaded after this call, and takes effect
+ * before the constructor of the module is called. The value is converted
+ * to the type of the parameter, and loading the module fails, if it is
+ * not representable by that type. Setting `value` to `NULL` removes the
+ * override.
+ *
+ * @param context context
+ * @param module_name name of the module containing the parameter
+ * @param param name of the parameter
+ * @param value initial value of the parameter
+ * @param type type of the value
+ *
+ * @return Status code.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_param_set_override(FimoContext context, const char *module_name, const char *param,
+                                          const void *value, FimoModuleParamType type);
+
+/**
+ * Enumerates the features of a module.
+ *
+ * Queries the name, the stability, and the resolved state of each
+ * feature declared by the module. The entries, and the strings they
+ * reference, are stored in a single allocation, which must be freed
+ * with `fimo_free`. This function fails, if the module can not be
+ * found.
+ *
+ * @param context context
+ * @param module_name name of the module declaring the features
+ * @param features resulting array of feature infos
+ * @param features_count number of entries in `features`
+ *
+ * @return Status code.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_feature_list(FimoContext context, const char *module_name, FimoModuleFeatureInfo **features,
+                                   FimoUSize *features_count);
+
+/**
+ * Overrides the default state of a module feature.
+ *
+ * Instead of the default state of its declaration, the feature will
+ * be enabled or disabled according to `enabled`, when the module is
+ * loaded. The override is only applied to modules loaded after this
+ * call, and takes effect before the constructor of the module is
+ * called. A feature requiring a newer context remains disabled,
+ * even if it is enabled by an override. Overrides of undeclared
+ * features are ignored. Setting `enabled` to `NULL` removes the
+ * override.
+ *
+ * @param context context
+ * @param module_name name of the module declaring the feature
+ * @param feature name of the feature
+ * @param enabled state of the feature
+ *
+ * @return Status code.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_feature_set_override(FimoContext context, const char *module_name, const char *feature,
+                                           const bool *enabled);
+
+/**
+ * Checks whether a feature of the module is enabled.
+ *
+ * Queries the resolved state of a feature declared by the module of
+ * the caller. May be called from within the module constructor. This
+ * function fails, if the module does not declare the feature.
+ *
+ * @param module module of the caller
+ * @param feature name of the feature
+ * @param enabled query result
+ *
+ * @return Status code.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_feature_enabled(const FimoModule *module, const char *feature, bool *enabled);
+
 /**
  * Sets a module parameter with public write access.
  *
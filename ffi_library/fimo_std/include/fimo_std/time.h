@@ -435,6 +435,79 @@ FIMO_EXPORT
 FIMO_MUST_USE
 FimoTime fimo_time_saturating_sub(const FimoTime *time_point, const FimoDuration *duration);
 
+/**
+ * Returns the current time of the monotonic clock.
+ *
+ * @return Current monotonic time.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoTimeMonotonic fimo_time_monotonic_now(void);
+
+/**
+ * Returns the duration elapsed since a prior monotonic time point.
+ *
+ * @param time_point: previous time point (not `NULL`)
+ * @param elapsed: resulting duration (not `NULL`)
+ *
+ * @return Status code.
+ *
+ * @error `FIMO_EOK`: The operation was successful.
+ * @error `FIMO_ERANGE`: The time point @time_point is in the future.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_time_monotonic_elapsed(const FimoTimeMonotonic *time_point, FimoDuration *elapsed);
+
+/**
+ * Returns the difference between two monotonic time points.
+ *
+ * @param time_point: first time point (not `NULL`)
+ * @param earlier_time_point: second time point (not `NULL`)
+ * @param duration: resulting duration (not `NULL`)
+ *
+ * @return Status code.
+ *
+ * @error `FIMO_EOK`: The operation was successful.
+ * @error `FIMO_ERANGE`: The time point @time_point is after @earlier_time_point.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_time_monotonic_duration_since(const FimoTimeMonotonic *time_point,
+                                              const FimoTimeMonotonic *earlier_time_point, FimoDuration *duration);
+
+/**
+ * Adds a duration to a monotonic time point.
+ *
+ * @param time_point: time point (not `NULL`)
+ * @param duration: duration (not `NULL`)
+ * @param out: time point
+ *
+ * @return Status code.
+ *
+ * @error `FIMO_EOK`: The addition was successful.
+ * @error `FIMO_ERANGE`: The addition would result in an overflow.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_time_monotonic_add(const FimoTimeMonotonic *time_point, const FimoDuration *duration,
+                                   FimoTimeMonotonic *out);
+
+/**
+ * Adds a duration to a monotonic time point.
+ *
+ * The result saturates to %FIMO_TIME_MONOTONIC_MAX, if an overflow occurs.
+ *
+ * @param time_point: time point (not `NULL`)
+ * @param duration: duration (not `NULL`)
+ *
+ * @return Shifted time point.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoTimeMonotonic fimo_time_monotonic_saturating_add(const FimoTimeMonotonic *time_point,
+                                                     const FimoDuration *duration);
+
 #ifdef __cplusplus
 }
 #endif
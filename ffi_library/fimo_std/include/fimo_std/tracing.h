@@ -307,7 +307,7 @@ typedef struct FimoTracingEvent {
     /**
      * Pointer to a possible extension.
      *
-     * Reserved for future use. Must be `NULL`.
+     * Must be `NULL` or point to a `FimoTracingMessageTemplate`.
      */
     const FimoBaseStructIn *next;
     /**
@@ -331,6 +331,121 @@ typedef struct FimoTracingEvent {
  */
 typedef FimoResult (*FimoTracingFormat)(char *, FimoUSize, const void *, FimoUSize *);
 
+/**
+ * A named field of a message template.
+ */
+typedef struct FimoTracingField {
+    /**
+     * Name of the field.
+     *
+     * Must not be `NULL`.
+     */
+    const char *name;
+    /**
+     * Formatter of the field value.
+     *
+     * The value is only formatted when the message is rendered.
+     * Must not be `NULL`.
+     */
+    FimoTracingFormat format;
+    /**
+     * Data passed to the formatter.
+     */
+    const void *data;
+} FimoTracingField;
+
+/**
+ * Un-interpolated message of an event.
+ *
+ * A template is a message containing placeholders of the form `{name}`,
+ * which are replaced by the value of the field with the same name, e.g.,
+ * `"loaded {count} assets in {ms} ms"`. Braces can be escaped by doubling
+ * them, i.e., `{{` and `}}`. Placeholders without a matching field are
+ * kept as is.
+ *
+ * A template is attached to an event through its `next` pointer. The
+ * template is preserved through the tracing pipeline, enabling subscribers
+ * to either use the rendered message, or to store the template and the
+ * fields separately.
+ */
+typedef struct FimoTracingMessageTemplate {
+    /**
+     * Type of the struct.
+     *
+     * Must be `FIMO_STRUCT_TYPE_TRACING_MESSAGE_TEMPLATE`.
+     */
+    FimoStructType type;
+    /**
+     * Pointer to a possible extension.
+     *
+     * Reserved for future use. Must be `NULL`.
+     */
+    const FimoBaseStructIn *next;
+    /**
+     * Template of the message.
+     *
+     * Must not be `NULL`.
+     */
+    const char *message;
+    /**
+     * Array of fields referenced by the template.
+     *
+     * Must be `NULL` when there are no fields.
+     */
+    const FimoTracingField *fields;
+    /**
+     * Number of fields.
+     */
+    FimoUSize field_count;
+} FimoTracingMessageTemplate;
+
+/**
+ * A pre-formatted event.
+ *
+ * Records are used to emit multiple events at once, e.g., when
+ * forwarding messages from a different logging system.
+ */
+typedef struct FimoTracingRecord {
+    /**
+     * Event to emit.
+     *
+     * Must not be `NULL`.
+     */
+    const FimoTracingEvent *event;
+    /**
+     * Formatted message of the event.
+     *
+     * Must be `NULL` if the message is empty.
+     */
+    const char *message;
+    /**
+     * Length of the message, without a null terminator.
+     */
+    FimoUSize message_length;
+} FimoTracingRecord;
+
+/**
+ * Snapshot of an entered span.
+ *
+ * The pointers remain valid until the span is exited.
+ */
+typedef struct FimoTracingSpanInfo {
+    /**
+     * Metadata of the span.
+     */
+    const FimoTracingMetadata *metadata;
+    /**
+     * Formatted message of the span.
+     *
+     * Is `NULL` if the message is empty.
+     */
+    const char *message;
+    /**
+     * Length of the message, without a null terminator.
+     */
+    FimoUSize message_length;
+} FimoTracingSpanInfo;
+
 /**
  * VTable of a tracing subscriber.
  *
@@ -443,6 +558,42 @@ typedef struct FimoTracingSubscriberVTable {
     void (*flush)(void *);
 } FimoTracingSubscriberVTable;
 
+/**
+ * Optional extension of a subscriber, to consume batches of events.
+ *
+ * The extension is attached to a subscriber through its `next`
+ * pointer, and must outlive the subscriber. Subscribers without
+ * the extension receive the events of a batch one at a time,
+ * through their `event_emit` function.
+ */
+typedef struct FimoTracingSubscriberBatchExtension {
+    /**
+     * Type of the struct.
+     *
+     * Must be `FIMO_STRUCT_TYPE_TRACING_SUBSCRIBER_BATCH_EXTENSION`.
+     */
+    FimoStructType type;
+    /**
+     * Pointer to a possible extension.
+     *
+     * Reserved for future use. Must be `NULL`.
+     */
+    const struct FimoBaseStructIn *next;
+    /**
+     * Emits a batch of events.
+     *
+     * The events must be handled in order, and are all timestamped
+     * with the same time.
+     *
+     * @param arg0 pointer to the subscriber
+     * @param arg1 time of the events
+     * @param arg2 the call stack
+     * @param arg3 array of records to emit
+     * @param arg4 number of records
+     */
+    void (*event_emit_batch)(void *, const FimoTime *, void *, const FimoTracingRecord *, FimoUSize);
+} FimoTracingSubscriberBatchExtension;
+
 /**
  * A subscriber for tracing events.
  *
@@ -461,7 +612,7 @@ typedef struct FimoTracingSubscriber {
     /**
      * Pointer to a possible extension.
      *
-     * Reserved for future use. Must be `NULL`.
+     * Must be `NULL` or point to a `FimoTracingSubscriberBatchExtension`.
      */
     const struct FimoBaseStructIn *next;
     /**
@@ -540,6 +691,8 @@ typedef struct FimoTracingVTableV0 {
     FimoResult (*register_thread)(void *);
     FimoResult (*unregister_thread)(void *);
     FimoResult (*flush)(void *);
+    FimoResult (*event_emit_batch)(void *, const FimoTracingRecord *, FimoUSize);
+    FimoResult (*span_stack)(void *, FimoTracingSpanInfo *, FimoUSize, FimoUSize *);
 } FimoTracingVTableV0;
 
 /**
@@ -761,6 +914,100 @@ FIMO_MUST_USE
 FimoResult fimo_tracing_event_emit_custom(FimoContext context, const FimoTracingEvent *event, FimoTracingFormat format,
                                           const void *data);
 
+/**
+ * Emits a new event with a message template.
+ *
+ * The `next` pointer of the event must point to a `FimoTracingMessageTemplate`.
+ * The message is rendered with `fimo_tracing_format_template`, and may be cut
+ * of, if the length exceeds the internal formatting buffer size. The fields
+ * are only formatted if the event is not filtered out by the backend.
+ *
+ * @param context the context
+ * @param event the event to emit
+ *
+ * @return Status code.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_tracing_event_emit_template(FimoContext context, const FimoTracingEvent *event);
+
+/**
+ * Emits a batch of pre-formatted events.
+ *
+ * The records are emitted in order on the active call stack, and are
+ * all timestamped with the same time. Records that are filtered out
+ * by the backend are skipped. Subscribers implementing the
+ * `FimoTracingSubscriberBatchExtension` receive consecutive records
+ * with a single call, allowing them to e.g., acquire their locks only
+ * once per batch, while the remaining subscribers receive the records
+ * one at a time. Unlike the other emit functions, the messages are not
+ * cut of at the internal formatting buffer size.
+ *
+ * @param context the context
+ * @param records array of records to emit
+ * @param record_count number of records
+ *
+ * @return Status code.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_tracing_event_emit_batch(FimoContext context, const FimoTracingRecord *records,
+                                         FimoUSize record_count);
+
+/**
+ * Captures the spans entered on the active call stack.
+ *
+ * Writes up to `capacity` spans of the active call stack of the calling
+ * thread into `spans`, ordered from the outermost to the innermost span,
+ * and the total number of entered spans into `span_count`. The spans can
+ * be counted by passing a `capacity` of `0`. No spans are captured, if
+ * the tracing subsystem is disabled or the calling thread is not
+ * registered with it. The captured spans are only valid until they are
+ * exited, and must therefore be copied, if they need to outlive them,
+ * e.g., when attaching them to an error.
+ *
+ * @param context the context
+ * @param spans array receiving the spans
+ * @param capacity length of `spans`
+ * @param span_count pointer to the number of entered spans
+ *
+ * @return Status code.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_tracing_span_stack(FimoContext context, FimoTracingSpanInfo *spans, FimoUSize capacity,
+                                   FimoUSize *span_count);
+
+/**
+ * Returns the message template attached to an event.
+ *
+ * @param event the event
+ *
+ * @return The template, or `NULL` if the event has no template.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+const FimoTracingMessageTemplate *fimo_tracing_event_template(const FimoTracingEvent *event);
+
+/**
+ * Formatter for message templates.
+ *
+ * Renders the `FimoTracingMessageTemplate` pointed to by `data` into the
+ * buffer, by replacing each placeholder with the formatted value of the
+ * field. The message is cut of, if it exceeds the size of the buffer.
+ *
+ * @param buffer destination buffer
+ * @param buffer_size destination buffer size
+ * @param data template to render
+ * @param written_size number of written bytes
+ *
+ * @return Status code.
+ */
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_tracing_format_template(char *buffer, FimoUSize buffer_size, const void *data,
+                                        FimoUSize *written_size);
+
 /**
  * Checks whether the tracing backend is enabled.
  *
@@ -1,6 +1,8 @@
 #ifndef FIMO_VTABLE_H
 #define FIMO_VTABLE_H
 
+#include <fimo_std/bus.h>
+#include <fimo_std/clock.h>
 #include <fimo_std/context.h>
 #include <fimo_std/module.h>
 #include <fimo_std/tracing.h>
@@ -22,6 +24,8 @@ typedef struct FimoContextVTable {
     FimoContextCoreVTableV0 core;
     FimoTracingVTableV0 tracing_v0;
     FimoModuleVTableV0 module_v0;
+    FimoBusVTableV0 bus_v0;
+    FimoClockVTableV0 clock_v0;
 } FimoContextVTable;
 
 #endif // FIMO_VTABLE_H
@@ -0,0 +1,56 @@
+#include <fimo_std/bus.h>
+
+#include <fimo_std/vtable.h>
+
+FIMO_EXPORT
+const FimoBusTopic FIMO_BUS_TOPIC_MODULE_LOADED = {
+        .id = {0xa8, 0x02, 0xcb, 0xc8, 0xc0, 0xbf, 0x48, 0x84, 0xa6, 0x4c, 0xfc, 0xf1, 0x73, 0x7f, 0x5f, 0x81},
+        .version = FIMO_VERSION(0, 1, 0),
+};
+
+FIMO_EXPORT
+const FimoBusTopic FIMO_BUS_TOPIC_MODULE_UNLOADED = {
+        .id = {0xbe, 0x44, 0xe4, 0xaf, 0x72, 0xda, 0x49, 0x2e, 0x9d, 0x9b, 0x3f, 0xe4, 0x52, 0x6a, 0xa7, 0x43},
+        .version = FIMO_VERSION(0, 1, 0),
+};
+
+FIMO_EXPORT
+const FimoBusTopic FIMO_BUS_TOPIC_MODULE_VERIFICATION_FAILED = {
+        .id = {0x5d, 0x17, 0x8e, 0x3a, 0x0b, 0x64, 0x4f, 0xc1, 0x92, 0x2e, 0x71, 0xd8, 0x4a, 0x06, 0xbb, 0x39},
+        .version = FIMO_VERSION(0, 1, 0),
+};
+
+FIMO_EXPORT
+const FimoBusTopic FIMO_BUS_TOPIC_MODULE_CONSTRUCTION_FAILED = {
+        .id = {0x3c, 0x91, 0x0f, 0x6e, 0xd4, 0x27, 0x4b, 0x58, 0x8a, 0xe3, 0x15, 0x7c, 0x62, 0xb9, 0x40, 0xd2},
+        .version = FIMO_VERSION(0, 1, 0),
+};
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_bus_subscribe(const FimoContext context, const FimoBusSubscriber *subscriber,
+                              FimoBusSubscription **subscription) {
+    const FimoContextVTable *vtable = context.vtable;
+    return vtable->bus_v0.subscribe(context.data, subscriber, subscription);
+}
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_bus_unsubscribe(const FimoContext context, FimoBusSubscription *subscription) {
+    const FimoContextVTable *vtable = context.vtable;
+    return vtable->bus_v0.unsubscribe(context.data, subscription);
+}
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_bus_publish(const FimoContext context, const FimoBusEvent *event) {
+    const FimoContextVTable *vtable = context.vtable;
+    return vtable->bus_v0.publish(context.data, event);
+}
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_bus_dispatch(const FimoContext context, FimoBusSubscription *subscription, FimoUSize *count) {
+    const FimoContextVTable *vtable = context.vtable;
+    return vtable->bus_v0.dispatch(context.data, subscription, count);
+}
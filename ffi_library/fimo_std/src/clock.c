@@ -0,0 +1,17 @@
+#include <fimo_std/clock.h>
+
+#include <fimo_std/vtable.h>
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoTime fimo_clock_now(const FimoContext context) {
+    const FimoContextVTable *vtable = context.vtable;
+    return vtable->clock_v0.now(context.data);
+}
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoTimeMonotonic fimo_clock_monotonic_now(const FimoContext context) {
+    const FimoContextVTable *vtable = context.vtable;
+    return vtable->clock_v0.monotonic_now(context.data);
+}
@@ -0,0 +1,465 @@
+#include <fimo_std/internal/bus.h>
+
+#include <string.h>
+
+#include <fimo_std/array_list.h>
+#include <fimo_std/internal/context.h>
+#include <fimo_std/memory.h>
+#include <fimo_std/refcount.h>
+
+#define TO_BUS_CTX_(CTX) &((FimoInternalContext *)CTX)->bus
+
+struct QueuedEvent_ {
+    struct QueuedEvent_ *next;
+    FimoBusTopic topic;
+    void *payload;
+    FimoUSize payload_size;
+};
+
+struct FimoBusSubscription {
+    FimoAtomicRefCount ref_count;
+    FimoBusTopic topic;
+    FimoBusDeliveryMode mode;
+    void *data;
+    void (*on_event)(void *data, const FimoBusEvent *event);
+    void (*on_drop)(void *data);
+    // Guarded by the mutex of the bus.
+    FimoBusSubscription *prev;
+    FimoBusSubscription *next;
+    struct QueuedEvent_ *queue_head;
+    struct QueuedEvent_ *queue_tail;
+    FimoUSize in_flight;
+    bool removed;
+};
+
+struct DeliveryFrame_ {
+    struct DeliveryFrame_ *prev;
+    const FimoBusSubscription *subscription;
+};
+
+// Deliveries in progress on the current thread.
+static _Thread_local struct DeliveryFrame_ *DELIVERIES_ = NULL;
+
+///////////////////////////////////////////////////////////////////////
+//// Queued Event
+///////////////////////////////////////////////////////////////////////
+
+static FimoResult queued_event_new_(const FimoBusEvent *event, struct QueuedEvent_ **element) {
+    FIMO_DEBUG_ASSERT(event && element)
+    FimoResult error = FIMO_EOK;
+    struct QueuedEvent_ *queued = fimo_malloc(sizeof(*queued), &error);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        return error;
+    }
+
+    void *payload = NULL;
+    if (event->payload_size != 0) {
+        payload = fimo_malloc(event->payload_size, &error);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            fimo_free(queued);
+            return error;
+        }
+        memcpy(payload, event->payload, event->payload_size);
+    }
+
+    *queued = (struct QueuedEvent_){
+            .next = NULL,
+            .topic = *event->topic,
+            .payload = payload,
+            .payload_size = event->payload_size,
+    };
+    *element = queued;
+    return FIMO_EOK;
+}
+
+static void queued_event_free_(struct QueuedEvent_ *element) {
+    FIMO_DEBUG_ASSERT(element)
+    fimo_free(element->payload);
+    fimo_free(element);
+}
+
+static void queued_event_free_list_(struct QueuedEvent_ *head) {
+    while (head != NULL) {
+        struct QueuedEvent_ *next = head->next;
+        queued_event_free_(head);
+        head = next;
+    }
+}
+
+///////////////////////////////////////////////////////////////////////
+//// Subscription
+///////////////////////////////////////////////////////////////////////
+
+static bool subscription_matches_(const FimoBusSubscription *subscription, const FimoBusTopic *topic) {
+    FIMO_DEBUG_ASSERT(subscription && topic)
+    return memcmp(subscription->topic.id, topic->id, sizeof(topic->id)) == 0 &&
+           fimo_version_compatible(&topic->version, &subscription->topic.version);
+}
+
+static void subscription_acquire_(FimoBusSubscription *subscription) {
+    FIMO_DEBUG_ASSERT(subscription)
+    fimo_increase_strong_count_atomic(&subscription->ref_count);
+}
+
+static void subscription_release_(FimoBusSubscription *subscription) {
+    FIMO_DEBUG_ASSERT(subscription)
+    if (!fimo_decrease_strong_count_atomic(&subscription->ref_count)) {
+        return;
+    }
+
+    FIMO_DEBUG_ASSERT(subscription->queue_head == NULL)
+    if (subscription->on_drop) {
+        subscription->on_drop(subscription->data);
+    }
+    fimo_free(subscription);
+}
+
+static void subscription_deliver_(const FimoBusSubscription *subscription, const FimoBusEvent *event) {
+    FIMO_DEBUG_ASSERT(subscription && event)
+    struct DeliveryFrame_ frame = {
+            .prev = DELIVERIES_,
+            .subscription = subscription,
+    };
+    DELIVERIES_ = &frame;
+    subscription->on_event(subscription->data, event);
+    DELIVERIES_ = frame.prev;
+}
+
+static FimoUSize subscription_thread_deliveries_(const FimoBusSubscription *subscription) {
+    FIMO_DEBUG_ASSERT(subscription)
+    FimoUSize count = 0;
+    for (const struct DeliveryFrame_ *it = DELIVERIES_; it != NULL; it = it->prev) {
+        if (it->subscription == subscription) {
+            count++;
+        }
+    }
+    return count;
+}
+
+// Must be called while the bus is locked.
+static void subscription_begin_delivery_(FimoBusSubscription *subscription) {
+    FIMO_DEBUG_ASSERT(subscription)
+    subscription->in_flight++;
+    subscription_acquire_(subscription);
+}
+
+///////////////////////////////////////////////////////////////////////
+//// Context
+///////////////////////////////////////////////////////////////////////
+
+static FimoResult ctx_init_(FimoInternalBusContext *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    if (mtx_init(&ctx->mutex, mtx_plain) != thrd_success) {
+        return FIMO_RESULT_FROM_STRING("could not initialize the bus mutex");
+    }
+    if (cnd_init(&ctx->idle) != thrd_success) {
+        mtx_destroy(&ctx->mutex);
+        return FIMO_RESULT_FROM_STRING("could not initialize the bus condition variable");
+    }
+    ctx->subscriptions = NULL;
+    return FIMO_EOK;
+}
+
+static void ctx_deinit_(FimoInternalBusContext *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    FimoBusSubscription *subscription = ctx->subscriptions;
+    while (subscription != NULL) {
+        FimoBusSubscription *next = subscription->next;
+        queued_event_free_list_(subscription->queue_head);
+        subscription->queue_head = NULL;
+        subscription->queue_tail = NULL;
+        subscription_release_(subscription);
+        subscription = next;
+    }
+    ctx->subscriptions = NULL;
+    cnd_destroy(&ctx->idle);
+    mtx_destroy(&ctx->mutex);
+}
+
+static FimoResult ctx_lock_(FimoInternalBusContext *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    if (mtx_lock(&ctx->mutex) != thrd_success) {
+        return FIMO_RESULT_FROM_STRING("could not lock the bus");
+    }
+    return FIMO_EOK;
+}
+
+static void ctx_unlock_(FimoInternalBusContext *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    const int result = mtx_unlock(&ctx->mutex);
+    FIMO_ASSERT(result == thrd_success)
+}
+
+static void ctx_end_delivery_(FimoInternalBusContext *ctx, FimoBusSubscription *subscription) {
+    FIMO_DEBUG_ASSERT(ctx && subscription)
+    const int result = mtx_lock(&ctx->mutex);
+    FIMO_ASSERT(result == thrd_success)
+    FIMO_DEBUG_ASSERT(subscription->in_flight != 0)
+    subscription->in_flight--;
+    if (subscription->in_flight == 0 && subscription->removed) {
+        const int broadcast_result = cnd_broadcast(&ctx->idle);
+        FIMO_ASSERT(broadcast_result == thrd_success)
+    }
+    ctx_unlock_(ctx);
+    subscription_release_(subscription);
+}
+
+static FimoResult ctx_subscribe_(FimoInternalBusContext *ctx, const FimoBusSubscriber *subscriber,
+                                 FimoBusSubscription **subscription) {
+    FIMO_DEBUG_ASSERT(ctx && subscriber && subscription)
+    if (subscriber->next != NULL || subscriber->on_event == NULL) {
+        return FIMO_EINVAL;
+    }
+    if (subscriber->mode != FIMO_BUS_DELIVERY_MODE_SYNC && subscriber->mode != FIMO_BUS_DELIVERY_MODE_QUEUED) {
+        return FIMO_EINVAL;
+    }
+
+    FimoResult error = FIMO_EOK;
+    FimoBusSubscription *element = fimo_malloc(sizeof(*element), &error);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        return error;
+    }
+    *element = (FimoBusSubscription){
+            .ref_count = FIMO_REFCOUNT_INIT,
+            .topic = subscriber->topic,
+            .mode = subscriber->mode,
+            .data = subscriber->data,
+            .on_event = subscriber->on_event,
+            .on_drop = subscriber->on_drop,
+            .prev = NULL,
+            .next = NULL,
+            .queue_head = NULL,
+            .queue_tail = NULL,
+            .in_flight = 0,
+            .removed = false,
+    };
+
+    error = ctx_lock_(ctx);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        fimo_free(element);
+        return error;
+    }
+    element->next = ctx->subscriptions;
+    if (ctx->subscriptions != NULL) {
+        ctx->subscriptions->prev = element;
+    }
+    ctx->subscriptions = element;
+    ctx_unlock_(ctx);
+
+    *subscription = element;
+    return FIMO_EOK;
+}
+
+static FimoResult ctx_unsubscribe_(FimoInternalBusContext *ctx, FimoBusSubscription *subscription) {
+    FIMO_DEBUG_ASSERT(ctx && subscription)
+    FimoResult error = ctx_lock_(ctx);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        return error;
+    }
+    if (subscription->prev != NULL) {
+        subscription->prev->next = subscription->next;
+    }
+    else {
+        ctx->subscriptions = subscription->next;
+    }
+    if (subscription->next != NULL) {
+        subscription->next->prev = subscription->prev;
+    }
+    struct QueuedEvent_ *queue = subscription->queue_head;
+    subscription->prev = NULL;
+    subscription->next = NULL;
+    subscription->queue_head = NULL;
+    subscription->queue_tail = NULL;
+    subscription->removed = true;
+
+    // Wait for the deliveries of the other threads. The deliveries of the current thread can
+    // not complete until we return, i.e., if we are called from the handler of the subscription.
+    const FimoUSize thread_deliveries = subscription_thread_deliveries_(subscription);
+    while (subscription->in_flight > thread_deliveries) {
+        const int result = cnd_wait(&ctx->idle, &ctx->mutex);
+        FIMO_ASSERT(result == thrd_success)
+    }
+    ctx_unlock_(ctx);
+
+    queued_event_free_list_(queue);
+    subscription_release_(subscription);
+    return FIMO_EOK;
+}
+
+static FimoResult ctx_publish_(FimoInternalBusContext *ctx, const FimoBusEvent *event) {
+    FIMO_DEBUG_ASSERT(ctx && event)
+    if (event->topic == NULL || (event->payload == NULL && event->payload_size != 0)) {
+        return FIMO_EINVAL;
+    }
+
+    // Collect the synchronous subscribers, such that they can be invoked without holding the lock.
+    FimoArrayList sync_subscriptions = fimo_array_list_new();
+    FimoResult error = ctx_lock_(ctx);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        return error;
+    }
+    for (FimoBusSubscription *it = ctx->subscriptions; it != NULL; it = it->next) {
+        if (!subscription_matches_(it, event->topic)) {
+            continue;
+        }
+
+        if (it->mode == FIMO_BUS_DELIVERY_MODE_SYNC) {
+            FimoResult push_error = fimo_array_list_push(&sync_subscriptions, sizeof(FimoBusSubscription *),
+                                                         _Alignof(FimoBusSubscription *), &it, NULL);
+            if (FIMO_RESULT_IS_ERROR(push_error)) {
+                error = push_error;
+                continue;
+            }
+            subscription_begin_delivery_(it);
+        }
+        else {
+            struct QueuedEvent_ *queued;
+            FimoResult queue_error = queued_event_new_(event, &queued);
+            if (FIMO_RESULT_IS_ERROR(queue_error)) {
+                error = queue_error;
+                continue;
+            }
+            if (it->queue_tail != NULL) {
+                it->queue_tail->next = queued;
+            }
+            else {
+                it->queue_head = queued;
+            }
+            it->queue_tail = queued;
+        }
+    }
+    ctx_unlock_(ctx);
+
+    const FimoUSize len = fimo_array_list_len(&sync_subscriptions);
+    for (FimoUSize i = 0; i < len; i++) {
+        FimoBusSubscription *const *subscription;
+        FimoResult get_error =
+                fimo_array_list_get(&sync_subscriptions, i, sizeof(FimoBusSubscription *), (const void **)&subscription);
+        FIMO_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(get_error))
+        subscription_deliver_(*subscription, event);
+        ctx_end_delivery_(ctx, *subscription);
+    }
+    fimo_array_list_free(&sync_subscriptions, sizeof(FimoBusSubscription *), _Alignof(FimoBusSubscription *), NULL);
+
+    return error;
+}
+
+static FimoResult ctx_dispatch_(FimoInternalBusContext *ctx, FimoBusSubscription *subscription, FimoUSize *count) {
+    FIMO_DEBUG_ASSERT(ctx && subscription)
+    if (subscription->mode != FIMO_BUS_DELIVERY_MODE_QUEUED) {
+        return FIMO_EINVAL;
+    }
+
+    FimoResult error = ctx_lock_(ctx);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        return error;
+    }
+    if (subscription->removed) {
+        ctx_unlock_(ctx);
+        return FIMO_EINVAL;
+    }
+    // Keep the subscription alive until all events have been delivered.
+    subscription_begin_delivery_(subscription);
+    struct QueuedEvent_ *queue = subscription->queue_head;
+    subscription->queue_head = NULL;
+    subscription->queue_tail = NULL;
+    ctx_unlock_(ctx);
+
+    FimoUSize delivered = 0;
+    while (queue != NULL) {
+        struct QueuedEvent_ *next = queue->next;
+        const FimoBusEvent event = {
+                .topic = &queue->topic,
+                .payload = queue->payload,
+                .payload_size = queue->payload_size,
+        };
+        subscription_deliver_(subscription, &event);
+        queued_event_free_(queue);
+        queue = next;
+        delivered++;
+    }
+    ctx_end_delivery_(ctx, subscription);
+
+    if (count != NULL) {
+        *count = delivered;
+    }
+    return FIMO_EOK;
+}
+
+///////////////////////////////////////////////////////////////////////
+//// Trampoline functions
+///////////////////////////////////////////////////////////////////////
+
+FimoResult fimo_internal_trampoline_bus_subscribe(void *ctx, const FimoBusSubscriber *subscriber,
+                                                  FimoBusSubscription **subscription) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_bus_subscribe(TO_BUS_CTX_(ctx), subscriber, subscription);
+}
+
+FimoResult fimo_internal_trampoline_bus_unsubscribe(void *ctx, FimoBusSubscription *subscription) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_bus_unsubscribe(TO_BUS_CTX_(ctx), subscription);
+}
+
+FimoResult fimo_internal_trampoline_bus_publish(void *ctx, const FimoBusEvent *event) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_bus_publish(TO_BUS_CTX_(ctx), event);
+}
+
+FimoResult fimo_internal_trampoline_bus_dispatch(void *ctx, FimoBusSubscription *subscription, FimoUSize *count) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_bus_dispatch(TO_BUS_CTX_(ctx), subscription, count);
+}
+
+///////////////////////////////////////////////////////////////////////
+//// Bus Subsystem API
+///////////////////////////////////////////////////////////////////////
+
+FIMO_MUST_USE
+FimoResult fimo_internal_bus_init(FimoInternalBusContext *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return ctx_init_(ctx);
+}
+
+void fimo_internal_bus_destroy(FimoInternalBusContext *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    ctx_deinit_(ctx);
+}
+
+FIMO_MUST_USE
+FimoResult fimo_internal_bus_subscribe(FimoInternalBusContext *ctx, const FimoBusSubscriber *subscriber,
+                                       FimoBusSubscription **subscription) {
+    FIMO_DEBUG_ASSERT(ctx)
+    if (subscriber == NULL || subscription == NULL) {
+        return FIMO_EINVAL;
+    }
+    return ctx_subscribe_(ctx, subscriber, subscription);
+}
+
+FIMO_MUST_USE
+FimoResult fimo_internal_bus_unsubscribe(FimoInternalBusContext *ctx, FimoBusSubscription *subscription) {
+    FIMO_DEBUG_ASSERT(ctx)
+    if (subscription == NULL) {
+        return FIMO_EINVAL;
+    }
+    return ctx_unsubscribe_(ctx, subscription);
+}
+
+FIMO_MUST_USE
+FimoResult fimo_internal_bus_publish(FimoInternalBusContext *ctx, const FimoBusEvent *event) {
+    FIMO_DEBUG_ASSERT(ctx)
+    if (event == NULL) {
+        return FIMO_EINVAL;
+    }
+    return ctx_publish_(ctx, event);
+}
+
+FIMO_MUST_USE
+FimoResult fimo_internal_bus_dispatch(FimoInternalBusContext *ctx, FimoBusSubscription *subscription,
+                                      FimoUSize *count) {
+    FIMO_DEBUG_ASSERT(ctx)
+    if (subscription == NULL) {
+        return FIMO_EINVAL;
+    }
+    return ctx_dispatch_(ctx, subscription, count);
+}
@@ -0,0 +1,91 @@
+#include <fimo_std/internal/clock.h>
+
+#include <fimo_std/internal/context.h>
+
+#define TO_CLOCK_CTX_(CTX) &((FimoInternalContext *)CTX)->clock
+
+///////////////////////////////////////////////////////////////////////
+//// System Clock
+///////////////////////////////////////////////////////////////////////
+
+static FimoTime system_now_(void *data) {
+    (void)data;
+    return fimo_time_now();
+}
+
+static FimoTimeMonotonic system_monotonic_now_(void *data) {
+    (void)data;
+    return fimo_time_monotonic_now();
+}
+
+///////////////////////////////////////////////////////////////////////
+//// Trampoline functions
+///////////////////////////////////////////////////////////////////////
+
+FimoTime fimo_internal_trampoline_clock_now(void *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_clock_now(TO_CLOCK_CTX_(ctx));
+}
+
+FimoTimeMonotonic fimo_internal_trampoline_clock_monotonic_now(void *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_clock_monotonic_now(TO_CLOCK_CTX_(ctx));
+}
+
+///////////////////////////////////////////////////////////////////////
+//// Clock Subsystem API
+///////////////////////////////////////////////////////////////////////
+
+FIMO_MUST_USE
+FimoResult fimo_internal_clock_init(FimoInternalClockContext *ctx, const FimoClockConfig *options) {
+    FIMO_DEBUG_ASSERT(ctx)
+    if (options == NULL) {
+        *ctx = (FimoInternalClockContext){
+                .data = NULL,
+                .now = system_now_,
+                .monotonic_now = system_monotonic_now_,
+                .on_drop = NULL,
+        };
+        return FIMO_EOK;
+    }
+
+    if (options->type != FIMO_STRUCT_TYPE_CLOCK_CONFIG || options->next != NULL || options->now == NULL ||
+        options->monotonic_now == NULL) {
+        return FIMO_EINVAL;
+    }
+
+    *ctx = (FimoInternalClockContext){
+            .data = options->data,
+            .now = options->now,
+            .monotonic_now = options->monotonic_now,
+            .on_drop = options->on_drop,
+    };
+    return FIMO_EOK;
+}
+
+void fimo_internal_clock_destroy(FimoInternalClockContext *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    if (ctx->on_drop != NULL) {
+        ctx->on_drop(ctx->data);
+    }
+    *ctx = (FimoInternalClockContext){0};
+}
+
+void fimo_internal_clock_cleanup_options(const FimoClockConfig *options) {
+    FIMO_DEBUG_ASSERT(options)
+    if (options->on_drop != NULL) {
+        options->on_drop(options->data);
+    }
+}
+
+FIMO_MUST_USE
+FimoTime fimo_internal_clock_now(FimoInternalClockContext *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return ctx->now(ctx->data);
+}
+
+FIMO_MUST_USE
+FimoTimeMonotonic fimo_internal_clock_monotonic_now(FimoInternalClockContext *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return ctx->monotonic_now(ctx->data);
+}
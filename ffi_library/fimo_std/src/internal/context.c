@@ -30,6 +30,8 @@ static const FimoContextVTable FIMO_INTERNAL_CONTEXT_VTABLE = {
                         .register_thread = fimo_internal_trampoline_tracing_register_thread,
                         .unregister_thread = fimo_internal_trampoline_tracing_unregister_thread,
                         .flush = fimo_internal_trampoline_tracing_flush,
+                        .event_emit_batch = fimo_internal_trampoline_tracing_event_emit_batch,
+                        .span_stack = fimo_internal_trampoline_tracing_span_stack,
                 },
         .module_v0 =
                 {
@@ -64,6 +66,30 @@ static const FimoContextVTable FIMO_INTERNAL_CONTEXT_VTABLE = {
                         .param_get_private = fimo_internal_trampoline_module_param_get_private,
                         .param_set_inner = fimo_internal_trampoline_module_param_set_inner,
                         .param_get_inner = fimo_internal_trampoline_module_get_inner,
+                        .symbol_use_counts = fimo_internal_trampoline_module_symbol_use_counts,
+                        .param_list = fimo_internal_trampoline_module_param_list,
+                        .param_set_override = fimo_internal_trampoline_module_param_set_override,
+                        .loader_register = fimo_internal_trampoline_module_loader_register,
+                        .loader_unregister = fimo_internal_trampoline_module_loader_unregister,
+                        .signature_set_policy = fimo_internal_trampoline_module_signature_set_policy,
+                        .signature_trust_key = fimo_internal_trampoline_module_signature_trust_key,
+                        .fault_set_policy = fimo_internal_trampoline_module_fault_set_policy,
+                        .set_append_group = fimo_internal_trampoline_module_set_append_group,
+                        .feature_list = fimo_internal_trampoline_module_feature_list,
+                        .feature_set_override = fimo_internal_trampoline_module_feature_set_override,
+                        .feature_enabled = fimo_internal_trampoline_module_feature_enabled,
+                },
+        .bus_v0 =
+                {
+                        .subscribe = fimo_internal_trampoline_bus_subscribe,
+                        .unsubscribe = fimo_internal_trampoline_bus_unsubscribe,
+                        .publish = fimo_internal_trampoline_bus_publish,
+                        .dispatch = fimo_internal_trampoline_bus_dispatch,
+                },
+        .clock_v0 =
+                {
+                        .now = fimo_internal_trampoline_clock_now,
+                        .monotonic_now = fimo_internal_trampoline_clock_monotonic_now,
                 },
 };
 
@@ -79,6 +105,7 @@ FimoResult fimo_internal_context_init(const FimoBaseStructIn **options, FimoCont
     // Parse the options. Each option type may occur at most once.
     FimoResult error;
     const FimoTracingCreationConfig *tracing_config = NULL;
+    const FimoClockConfig *clock_config = NULL;
     if (options != NULL) {
         for (const FimoBaseStructIn **cursor = options; *cursor != NULL; cursor++) {
             const FimoBaseStructIn *option = *cursor;
@@ -91,6 +118,14 @@ FimoResult fimo_internal_context_init(const FimoBaseStructIn **options, FimoCont
                     }
                     tracing_config = (const FimoTracingCreationConfig *)option;
                     break;
+                case FIMO_STRUCT_TYPE_CLOCK_CONFIG:
+                    if (clock_config) {
+                        error = FIMO_EINVAL;
+                        clock_config = NULL;
+                        goto cleanup_options;
+                    }
+                    clock_config = (const FimoClockConfig *)option;
+                    break;
                 default:
                     error = FIMO_EINVAL;
                     goto cleanup_options;
@@ -101,41 +136,65 @@ FimoResult fimo_internal_context_init(const FimoBaseStructIn **options, FimoCont
 
     FimoInternalContext *ctx = fimo_aligned_alloc(_Alignof(FimoInternalContext), sizeof(FimoInternalContext), &error);
     if (FIMO_RESULT_IS_ERROR(error)) {
-        return error;
+        goto cleanup_options;
     }
 
     ctx->ref_count = (FimoAtomicRefCount)FIMO_REFCOUNT_INIT;
 
-    error = fimo_internal_tracing_init(&ctx->tracing, tracing_config);
+    // The clock is used by the other subsystems, therefore it is initialized first.
+    error = fimo_internal_clock_init(&ctx->clock, clock_config);
     if (FIMO_RESULT_IS_ERROR(error)) {
         goto cleanup;
     }
+    clock_config = NULL;
+
+    error = fimo_internal_tracing_init(&ctx->tracing, tracing_config);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        goto deinit_clock;
+    }
     tracing_config = NULL;
 
-    error = fimo_internal_module_init(&ctx->module);
+    error = fimo_internal_bus_init(&ctx->bus);
     if (FIMO_RESULT_IS_ERROR(error)) {
         goto deinit_tracing;
     }
 
+    error = fimo_internal_module_init(&ctx->module);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        goto deinit_bus;
+    }
+
     *context = (FimoContext){.data = ctx, .vtable = &FIMO_INTERNAL_CONTEXT_VTABLE};
     return FIMO_EOK;
 
+deinit_bus:
+    fimo_internal_bus_destroy(&ctx->bus);
 deinit_tracing:
     fimo_internal_tracing_destroy(&ctx->tracing);
+deinit_clock:
+    fimo_internal_clock_destroy(&ctx->clock);
 cleanup:
     fimo_free_aligned_sized(ctx, _Alignof(FimoInternalContext), sizeof(FimoInternalContext));
 cleanup_options:
-    if (tracing_config != NULL) {
-        fimo_internal_tracing_cleanup_options(tracing_config);
+    // While parsing, the options are still referenced by `options`, so we clean them up only once.
+    if (options == NULL) {
+        if (tracing_config != NULL) {
+            fimo_internal_tracing_cleanup_options(tracing_config);
+        }
+        if (clock_config != NULL) {
+            fimo_internal_clock_cleanup_options(clock_config);
+        }
     }
-
-    if (options != NULL) {
+    else {
         for (const FimoBaseStructIn **cursor = options; *cursor != NULL; cursor++) {
             const FimoBaseStructIn *option = *cursor;
             switch (option->type) {
                 case FIMO_STRUCT_TYPE_TRACING_CREATION_CONFIG:
                     fimo_internal_tracing_cleanup_options((const FimoTracingCreationConfig *)option);
                     break;
+                case FIMO_STRUCT_TYPE_CLOCK_CONFIG:
+                    fimo_internal_clock_cleanup_options((const FimoClockConfig *)option);
+                    break;
                 default:
                     break;
             }
@@ -171,7 +230,9 @@ void fimo_internal_context_release(void *ptr) {
 
     // Destroy all submodules.
     fimo_internal_module_destroy(&context->module);
+    fimo_internal_bus_destroy(&context->bus);
     fimo_internal_tracing_destroy(&context->tracing);
+    fimo_internal_clock_destroy(&context->clock);
 
     // Finally deallocate the context.
     fimo_free_aligned_sized(context, _Alignof(FimoInternalContext), sizeof(FimoInternalContext));
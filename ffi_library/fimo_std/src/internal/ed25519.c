@@ -0,0 +1,467 @@
+#include <fimo_std/internal/ed25519.h>
+
+#include <string.h>
+
+// The arithmetic on the curve follows the compact implementation of TweetNaCl. Field elements are
+// represented by 16 limbs of 16 bits, and points by their extended homogeneous coordinates.
+
+///////////////////////////////////////////////////////////////////////
+//// SHA-512
+///////////////////////////////////////////////////////////////////////
+
+static const FimoU64 SHA512_K_[80] = {
+        0x428a2f98d728ae22, 0x7137449123ef65cd, 0xb5c0fbcfec4d3b2f, 0xe9b5dba58189dbbc, 0x3956c25bf348b538,
+        0x59f111f1b605d019, 0x923f82a4af194f9b, 0xab1c5ed5da6d8118, 0xd807aa98a3030242, 0x12835b0145706fbe,
+        0x243185be4ee4b28c, 0x550c7dc3d5ffb4e2, 0x72be5d74f27b896f, 0x80deb1fe3b1696b1, 0x9bdc06a725c71235,
+        0xc19bf174cf692694, 0xe49b69c19ef14ad2, 0xefbe4786384f25e3, 0x0fc19dc68b8cd5b5, 0x240ca1cc77ac9c65,
+        0x2de92c6f592b0275, 0x4a7484aa6ea6e483, 0x5cb0a9dcbd41fbd4, 0x76f988da831153b5, 0x983e5152ee66dfab,
+        0xa831c66d2db43210, 0xb00327c898fb213f, 0xbf597fc7beef0ee4, 0xc6e00bf33da88fc2, 0xd5a79147930aa725,
+        0x06ca6351e003826f, 0x142929670a0e6e70, 0x27b70a8546d22ffc, 0x2e1b21385c26c926, 0x4d2c6dfc5ac42aed,
+        0x53380d139d95b3df, 0x650a73548baf63de, 0x766a0abb3c77b2a8, 0x81c2c92e47edaee6, 0x92722c851482353b,
+        0xa2bfe8a14cf10364, 0xa81a664bbc423001, 0xc24b8b70d0f89791, 0xc76c51a30654be30, 0xd192e819d6ef5218,
+        0xd69906245565a910, 0xf40e35855771202a, 0x106aa07032bbd1b8, 0x19a4c116b8d2d0c8, 0x1e376c085141ab53,
+        0x2748774cdf8eeb99, 0x34b0bcb5e19b48a8, 0x391c0cb3c5c95a63, 0x4ed8aa4ae3418acb, 0x5b9cca4f7763e373,
+        0x682e6ff3d6b2b8a3, 0x748f82ee5defb2fc, 0x78a5636f43172f60, 0x84c87814a1f0ab72, 0x8cc702081a6439ec,
+        0x90befffa23631e28, 0xa4506cebde82bde9, 0xbef9a3f7b2c67915, 0xc67178f2e372532b, 0xca273eceea26619c,
+        0xd186b8c721c0c207, 0xeada7dd6cde0eb1e, 0xf57d4f7fee6ed178, 0x06f067aa72176fba, 0x0a637dc5a2c898a6,
+        0x113f9804bef90dae, 0x1b710b35131c471b, 0x28db77f523047d84, 0x32caab7b40c72493, 0x3c9ebe0a15c9bebc,
+        0x431d67c49c100d4c, 0x4cc5d4becb3e42b6, 0x597f299cfc657e2a, 0x5fcb6fab3ad6faec, 0x6c44198c4a475817,
+};
+
+static FimoU64 sha512_rotr_(const FimoU64 x, const int n) { return (x >> n) | (x << (64 - n)); }
+
+static FimoU64 sha512_load_(const FimoU8 *x) {
+    FimoU64 value = 0;
+    for (int i = 0; i < 8; i++) {
+        value = (value << 8) | x[i];
+    }
+    return value;
+}
+
+static void sha512_store_(FimoU8 *x, FimoU64 value) {
+    for (int i = 7; i >= 0; i--) {
+        x[i] = (FimoU8)value;
+        value >>= 8;
+    }
+}
+
+static void sha512_block_(FimoInternalSha512 *hash, const FimoU8 *block) {
+    FimoU64 w[80];
+    for (int i = 0; i < 16; i++) {
+        w[i] = sha512_load_(block + 8 * i);
+    }
+    for (int i = 16; i < 80; i++) {
+        const FimoU64 s0 = sha512_rotr_(w[i - 15], 1) ^ sha512_rotr_(w[i - 15], 8) ^ (w[i - 15] >> 7);
+        const FimoU64 s1 = sha512_rotr_(w[i - 2], 19) ^ sha512_rotr_(w[i - 2], 61) ^ (w[i - 2] >> 6);
+        w[i] = w[i - 16] + s0 + w[i - 7] + s1;
+    }
+
+    FimoU64 s[8];
+    memcpy(s, hash->state, sizeof(s));
+    for (int i = 0; i < 80; i++) {
+        const FimoU64 e = s[4];
+        const FimoU64 a = s[0];
+        const FimoU64 t1 = s[7] + (sha512_rotr_(e, 14) ^ sha512_rotr_(e, 18) ^ sha512_rotr_(e, 41)) +
+                           ((e & s[5]) ^ (~e & s[6])) + SHA512_K_[i] + w[i];
+        const FimoU64 t2 = (sha512_rotr_(a, 28) ^ sha512_rotr_(a, 34) ^ sha512_rotr_(a, 39)) +
+                           ((a & s[1]) ^ (a & s[2]) ^ (s[1] & s[2]));
+        memmove(s + 1, s, 7 * sizeof(FimoU64));
+        s[4] += t1;
+        s[0] = t1 + t2;
+    }
+    for (int i = 0; i < 8; i++) {
+        hash->state[i] += s[i];
+    }
+}
+
+void fimo_internal_sha512_init(FimoInternalSha512 *hash) {
+    FIMO_DEBUG_ASSERT(hash)
+    static const FimoU64 iv[8] = {
+            0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+            0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+    };
+    memcpy(hash->state, iv, sizeof(iv));
+    hash->length = 0;
+    hash->buffer_len = 0;
+}
+
+void fimo_internal_sha512_update(FimoInternalSha512 *hash, const void *data, FimoUSize len) {
+    FIMO_DEBUG_ASSERT(hash && (data || len == 0))
+    const FimoU8 *bytes = data;
+    hash->length += len;
+    while (len > 0) {
+        FimoUSize count = sizeof(hash->buffer) - hash->buffer_len;
+        if (count > len) {
+            count = len;
+        }
+        memcpy(hash->buffer + hash->buffer_len, bytes, count);
+        hash->buffer_len += count;
+        bytes += count;
+        len -= count;
+        if (hash->buffer_len == sizeof(hash->buffer)) {
+            sha512_block_(hash, hash->buffer);
+            hash->buffer_len = 0;
+        }
+    }
+}
+
+void fimo_internal_sha512_final(FimoInternalSha512 *hash, FimoU8 digest[64]) {
+    FIMO_DEBUG_ASSERT(hash && digest)
+    const FimoU64 bits = hash->length * 8;
+    hash->buffer[hash->buffer_len++] = 0x80;
+    if (hash->buffer_len > sizeof(hash->buffer) - 16) {
+        memset(hash->buffer + hash->buffer_len, 0, sizeof(hash->buffer) - hash->buffer_len);
+        sha512_block_(hash, hash->buffer);
+        hash->buffer_len = 0;
+    }
+    memset(hash->buffer + hash->buffer_len, 0, sizeof(hash->buffer) - hash->buffer_len);
+    sha512_store_(hash->buffer + sizeof(hash->buffer) - 8, bits);
+    sha512_block_(hash, hash->buffer);
+    for (int i = 0; i < 8; i++) {
+        sha512_store_(digest + 8 * i, hash->state[i]);
+    }
+}
+
+///////////////////////////////////////////////////////////////////////
+//// Field Arithmetic
+///////////////////////////////////////////////////////////////////////
+
+typedef FimoI64 Gf_[16];
+
+static const Gf_ GF0_ = {0};
+static const Gf_ GF1_ = {1};
+static const Gf_ D_ = {0x78a3, 0x1359, 0x4dca, 0x75eb, 0xd8ab, 0x4141, 0x0a4d, 0x0070,
+                       0xe898, 0x7779, 0x4079, 0x8cc7, 0xfe73, 0x2b6f, 0x6cee, 0x5203};
+static const Gf_ D2_ = {0xf159, 0x26b2, 0x9b94, 0xebd6, 0xb156, 0x8283, 0x149a, 0x00e0,
+                        0xd130, 0xeef3, 0x80f2, 0x198e, 0xfce7, 0x56df, 0xd9dc, 0x2406};
+static const Gf_ X_ = {0xd51a, 0x8f25, 0x2d60, 0xc956, 0xa7b2, 0x9525, 0xc760, 0x692c,
+                       0xdc5c, 0xfdd6, 0xe231, 0xc0a4, 0x53fe, 0xcd6e, 0x36d3, 0x2169};
+static const Gf_ Y_ = {0x6658, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666,
+                       0x6666, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666, 0x6666};
+static const Gf_ I_ = {0xa0b0, 0x4a0e, 0x1b27, 0xc4ee, 0xe478, 0xad2f, 0x1806, 0x2f43,
+                       0xd7a7, 0x3dfb, 0x0099, 0x2b4d, 0xdf0b, 0x4fc1, 0x2480, 0x2b83};
+
+static void gf_copy_(Gf_ r, const Gf_ a) { memcpy(r, a, sizeof(Gf_)); }
+
+static void gf_carry_(Gf_ o) {
+    for (int i = 0; i < 16; i++) {
+        o[i] += (FimoI64)1 << 16;
+        const FimoI64 c = o[i] >> 16;
+        o[(i + 1) * (i < 15)] += c - 1 + 37 * (c - 1) * (i == 15);
+        o[i] -= c * ((FimoI64)1 << 16);
+    }
+}
+
+static void gf_select_(Gf_ p, Gf_ q, const int b) {
+    const FimoI64 c = ~((FimoI64)b - 1);
+    for (int i = 0; i < 16; i++) {
+        const FimoI64 t = c & (p[i] ^ q[i]);
+        p[i] ^= t;
+        q[i] ^= t;
+    }
+}
+
+static void gf_pack_(FimoU8 *o, const Gf_ n) {
+    Gf_ m, t;
+    gf_copy_(t, n);
+    gf_carry_(t);
+    gf_carry_(t);
+    gf_carry_(t);
+    for (int j = 0; j < 2; j++) {
+        m[0] = t[0] - 0xffed;
+        for (int i = 1; i < 15; i++) {
+            m[i] = t[i] - 0xffff - ((m[i - 1] >> 16) & 1);
+            m[i - 1] &= 0xffff;
+        }
+        m[15] = t[15] - 0x7fff - ((m[14] >> 16) & 1);
+        const int b = (int)((m[15] >> 16) & 1);
+        m[14] &= 0xffff;
+        gf_select_(t, m, 1 - b);
+    }
+    for (int i = 0; i < 16; i++) {
+        o[2 * i] = (FimoU8)(t[i] & 0xff);
+        o[2 * i + 1] = (FimoU8)(t[i] >> 8);
+    }
+}
+
+static bool gf_eq_(const Gf_ a, const Gf_ b) {
+    FimoU8 c[32], d[32];
+    gf_pack_(c, a);
+    gf_pack_(d, b);
+    return memcmp(c, d, sizeof(c)) == 0;
+}
+
+static FimoU8 gf_parity_(const Gf_ a) {
+    FimoU8 d[32];
+    gf_pack_(d, a);
+    return d[0] & 1;
+}
+
+static void gf_unpack_(Gf_ o, const FimoU8 *n) {
+    for (int i = 0; i < 16; i++) {
+        o[i] = n[2 * i] + ((FimoI64)n[2 * i + 1] << 8);
+    }
+    o[15] &= 0x7fff;
+}
+
+static void gf_add_(Gf_ o, const Gf_ a, const Gf_ b) {
+    for (int i = 0; i < 16; i++) {
+        o[i] = a[i] + b[i];
+    }
+}
+
+static void gf_sub_(Gf_ o, const Gf_ a, const Gf_ b) {
+    for (int i = 0; i < 16; i++) {
+        o[i] = a[i] - b[i];
+    }
+}
+
+static void gf_mul_(Gf_ o, const Gf_ a, const Gf_ b) {
+    FimoI64 t[31] = {0};
+    for (int i = 0; i < 16; i++) {
+        for (int j = 0; j < 16; j++) {
+            t[i + j] += a[i] * b[j];
+        }
+    }
+    for (int i = 0; i < 15; i++) {
+        t[i] += 38 * t[i + 16];
+    }
+    for (int i = 0; i < 16; i++) {
+        o[i] = t[i];
+    }
+    gf_carry_(o);
+    gf_carry_(o);
+}
+
+static void gf_square_(Gf_ o, const Gf_ a) { gf_mul_(o, a, a); }
+
+static void gf_invert_(Gf_ o, const Gf_ i) {
+    Gf_ c;
+    gf_copy_(c, i);
+    for (int a = 253; a >= 0; a--) {
+        gf_square_(c, c);
+        if (a != 2 && a != 4) {
+            gf_mul_(c, c, i);
+        }
+    }
+    gf_copy_(o, c);
+}
+
+static void gf_pow2523_(Gf_ o, const Gf_ i) {
+    Gf_ c;
+    gf_copy_(c, i);
+    for (int a = 250; a >= 0; a--) {
+        gf_square_(c, c);
+        if (a != 1) {
+            gf_mul_(c, c, i);
+        }
+    }
+    gf_copy_(o, c);
+}
+
+///////////////////////////////////////////////////////////////////////
+//// Point Arithmetic
+///////////////////////////////////////////////////////////////////////
+
+static void point_add_(Gf_ p[4], Gf_ q[4]) {
+    Gf_ a, b, c, d, t, e, f, g, h;
+    gf_sub_(a, p[1], p[0]);
+    gf_sub_(t, q[1], q[0]);
+    gf_mul_(a, a, t);
+    gf_add_(b, p[0], p[1]);
+    gf_add_(t, q[0], q[1]);
+    gf_mul_(b, b, t);
+    gf_mul_(c, p[3], q[3]);
+    gf_mul_(c, c, D2_);
+    gf_mul_(d, p[2], q[2]);
+    gf_add_(d, d, d);
+    gf_sub_(e, b, a);
+    gf_sub_(f, d, c);
+    gf_add_(g, d, c);
+    gf_add_(h, b, a);
+    gf_mul_(p[0], e, f);
+    gf_mul_(p[1], h, g);
+    gf_mul_(p[2], g, f);
+    gf_mul_(p[3], e, h);
+}
+
+static void point_swap_(Gf_ p[4], Gf_ q[4], const int b) {
+    for (int i = 0; i < 4; i++) {
+        gf_select_(p[i], q[i], b);
+    }
+}
+
+static void point_pack_(FimoU8 *r, Gf_ p[4]) {
+    Gf_ tx, ty, zi;
+    gf_invert_(zi, p[2]);
+    gf_mul_(tx, p[0], zi);
+    gf_mul_(ty, p[1], zi);
+    gf_pack_(r, ty);
+    r[31] ^= (FimoU8)(gf_parity_(tx) << 7);
+}
+
+static void point_scalar_mul_(Gf_ p[4], Gf_ q[4], const FimoU8 *s) {
+    gf_copy_(p[0], GF0_);
+    gf_copy_(p[1], GF1_);
+    gf_copy_(p[2], GF1_);
+    gf_copy_(p[3], GF0_);
+    for (int i = 255; i >= 0; --i) {
+        const int b = (s[i / 8] >> (i & 7)) & 1;
+        point_swap_(p, q, b);
+        point_add_(q, p);
+        point_add_(p, p);
+        point_swap_(p, q, b);
+    }
+}
+
+static void point_scalar_base_(Gf_ p[4], const FimoU8 *s) {
+    Gf_ q[4];
+    gf_copy_(q[0], X_);
+    gf_copy_(q[1], Y_);
+    gf_copy_(q[2], GF1_);
+    gf_mul_(q[3], X_, Y_);
+    point_scalar_mul_(p, q, s);
+}
+
+static bool point_unpack_negated_(Gf_ r[4], const FimoU8 p[32]) {
+    Gf_ t, chk, num, den, den2, den4, den6;
+    gf_copy_(r[2], GF1_);
+    gf_unpack_(r[1], p);
+    gf_square_(num, r[1]);
+    gf_mul_(den, num, D_);
+    gf_sub_(num, num, r[2]);
+    gf_add_(den, r[2], den);
+
+    gf_square_(den2, den);
+    gf_square_(den4, den2);
+    gf_mul_(den6, den4, den2);
+    gf_mul_(t, den6, num);
+    gf_mul_(t, t, den);
+
+    gf_pow2523_(t, t);
+    gf_mul_(t, t, num);
+    gf_mul_(t, t, den);
+    gf_mul_(t, t, den);
+    gf_mul_(r[0], t, den);
+
+    gf_square_(chk, r[0]);
+    gf_mul_(chk, chk, den);
+    if (!gf_eq_(chk, num)) {
+        gf_mul_(r[0], r[0], I_);
+    }
+
+    gf_square_(chk, r[0]);
+    gf_mul_(chk, chk, den);
+    if (!gf_eq_(chk, num)) {
+        return false;
+    }
+
+    if (gf_parity_(r[0]) == (p[31] >> 7)) {
+        gf_sub_(r[0], GF0_, r[0]);
+    }
+    gf_mul_(r[3], r[0], r[1]);
+    return true;
+}
+
+///////////////////////////////////////////////////////////////////////
+//// Scalar Arithmetic
+///////////////////////////////////////////////////////////////////////
+
+// Order of the base point, in little endian.
+static const FimoI64 L_[32] = {0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7,
+                               0xa2, 0xde, 0xf9, 0xde, 0x14, 0,    0,    0,    0,    0,    0,
+                               0,    0,    0,    0,    0,    0,    0,    0,    0,    0x10};
+
+static void scalar_mod_l_(FimoU8 *r, FimoI64 x[64]) {
+    FimoI64 carry;
+    for (int i = 63; i >= 32; --i) {
+        int j;
+        carry = 0;
+        for (j = i - 32; j < i - 12; ++j) {
+            x[j] += carry - 16 * x[i] * L_[j - (i - 32)];
+            carry = (x[j] + 128) >> 8;
+            x[j] -= carry * 256;
+        }
+        x[j] += carry;
+        x[i] = 0;
+    }
+    carry = 0;
+    for (int j = 0; j < 32; j++) {
+        x[j] += carry - (x[31] >> 4) * L_[j];
+        carry = x[j] >> 8;
+        x[j] &= 255;
+    }
+    for (int j = 0; j < 32; j++) {
+        x[j] -= carry * L_[j];
+    }
+    for (int i = 0; i < 32; i++) {
+        x[i + 1] += x[i] >> 8;
+        r[i] = (FimoU8)(x[i] & 255);
+    }
+}
+
+static void scalar_reduce_(FimoU8 r[64]) {
+    FimoI64 x[64];
+    for (int i = 0; i < 64; i++) {
+        x[i] = r[i];
+    }
+    memset(r, 0, 64);
+    scalar_mod_l_(r, x);
+}
+
+static bool scalar_is_canonical_(const FimoU8 s[32]) {
+    for (int i = 31; i >= 0; i--) {
+        if (s[i] != L_[i]) {
+            return s[i] < L_[i];
+        }
+    }
+    return false;
+}
+
+///////////////////////////////////////////////////////////////////////
+//// Ed25519
+///////////////////////////////////////////////////////////////////////
+
+void fimo_internal_ed25519_verify_init(FimoInternalEd25519Verifier *verifier,
+                                       const FimoU8 public_key[FIMO_INTERNAL_ED25519_PUBLIC_KEY_SIZE],
+                                       const FimoU8 signature[FIMO_INTERNAL_ED25519_SIGNATURE_SIZE]) {
+    FIMO_DEBUG_ASSERT(verifier && public_key && signature)
+    memcpy(verifier->public_key, public_key, sizeof(verifier->public_key));
+    memcpy(verifier->signature, signature, sizeof(verifier->signature));
+    fimo_internal_sha512_init(&verifier->hash);
+    fimo_internal_sha512_update(&verifier->hash, signature, 32);
+    fimo_internal_sha512_update(&verifier->hash, public_key, FIMO_INTERNAL_ED25519_PUBLIC_KEY_SIZE);
+}
+
+void fimo_internal_ed25519_verify_update(FimoInternalEd25519Verifier *verifier, const void *data,
+                                         const FimoUSize len) {
+    FIMO_DEBUG_ASSERT(verifier)
+    fimo_internal_sha512_update(&verifier->hash, data, len);
+}
+
+FIMO_MUST_USE
+bool fimo_internal_ed25519_verify_final(FimoInternalEd25519Verifier *verifier) {
+    FIMO_DEBUG_ASSERT(verifier)
+    FimoU8 h[64];
+    fimo_internal_sha512_final(&verifier->hash, h);
+
+    // Reject malleable signatures.
+    const FimoU8 *s = verifier->signature + 32;
+    if (!scalar_is_canonical_(s)) {
+        return false;
+    }
+
+    Gf_ p[4], q[4];
+    if (!point_unpack_negated_(q, verifier->public_key)) {
+        return false;
+    }
+
+    // Check that `s * B - h * A` equals `R`.
+    FimoU8 t[32];
+    scalar_reduce_(h);
+    point_scalar_mul_(p, q, h);
+    point_scalar_base_(q, s);
+    point_add_(p, q);
+    point_pack_(t, p);
+    return memcmp(t, verifier->signature, sizeof(t)) == 0;
+}
@@ -3,14 +3,29 @@
 #include <Windows.h>
 #include <pathcch.h>
 
+#if FIMO_STD_STATIC_MODULES
+#define MODULE_HANDLE_ void *
+#else
 #define MODULE_HANDLE_ HMODULE
+#endif
 #else
 #if __APPLE__
 #define _DARWIN_C_SOURCE
 #else
 #define _GNU_SOURCE
 #endif
+
+// Static builds may target platforms where the dynamic linker is not available.
+#if !FIMO_STD_STATIC_MODULES
 #include <dlfcn.h>
+#endif
+
+#include <fcntl.h>
+#include <sys/stat.h>
+#include <unistd.h>
+#if __linux__
+#include <sys/mman.h>
+#endif
 
 #define MODULE_HANDLE_ void *
 #endif
@@ -18,11 +33,14 @@
 #include <fimo_std/internal/module.h>
 
 #include <fimo_std/internal/context.h>
+#include <fimo_std/internal/ed25519.h>
 #include <fimo_std/internal/tracing.h>
 #include <fimo_std/memory.h>
 #include <fimo_std/refcount.h>
 
+#include <errno.h>
 #include <inttypes.h>
+#include <limits.h>
 #include <stdatomic.h>
 #include <stdio.h>
 #include <stdlib.h>
@@ -356,6 +374,7 @@ struct ModuleInfo_;
 struct ModuleInfoInner_;
 struct ModuleInfoSymbol_;
 struct ModuleInfoParam_;
+struct ModuleInfoFeature_;
 struct ModuleInfoNamespace_;
 struct ModuleInfoDependency_;
 
@@ -380,6 +399,9 @@ static const struct ModuleInfoSymbol_ *module_info_get_symbol_(struct ModuleInfo
 static FimoResult module_info_set_param_(struct ModuleInfoInner_ *inner, const char *name,
                                          const FimoModuleParam *param);
 static const struct ModuleInfoParam_ *module_info_get_param_(struct ModuleInfoInner_ *inner, const char *name);
+static FimoResult module_info_set_feature_(struct ModuleInfoInner_ *inner, const FimoModuleExportModifierFeature *decl,
+                                           bool available, bool enabled);
+static const struct ModuleInfoFeature_ *module_info_get_feature_(struct ModuleInfoInner_ *inner, const char *name);
 static FimoResult module_info_set_ns_(struct ModuleInfoInner_ *inner, const char *name, bool is_static);
 static const struct ModuleInfoNamespace_ *module_info_get_ns_(struct ModuleInfoInner_ *inner, const char *name);
 static void module_info_delete_ns_(struct ModuleInfoInner_ *inner, const char *name);
@@ -390,16 +412,31 @@ static const struct ModuleInfoDependency_ *module_info_get_dependency_(struct Mo
 static void module_info_delete_dependency_(struct ModuleInfoInner_ *inner, const char *name);
 static bool module_info_next_symbol_(struct ModuleInfoInner_ *inner, FimoUSize *it,
                                      const struct ModuleInfoSymbol_ **item);
+static bool module_info_next_param_(struct ModuleInfoInner_ *inner, FimoUSize *it,
+                                    const struct ModuleInfoParam_ **item);
+static bool module_info_next_feature_(struct ModuleInfoInner_ *inner, FimoUSize *it,
+                                      const struct ModuleInfoFeature_ **item);
 static bool module_info_next_ns_(struct ModuleInfoInner_ *inner, FimoUSize *it,
                                  const struct ModuleInfoNamespace_ **item);
 static bool module_info_next_dependency_(struct ModuleInfoInner_ *inner, FimoUSize *it,
                                          const struct ModuleInfoDependency_ **item);
 
+struct ModuleLoader_;
+
+static FimoResult module_loader_new_(const FimoModuleLoader *loader, struct ModuleLoader_ **element);
+static void module_loader_acquire_(struct ModuleLoader_ *element);
+static void module_loader_release_(struct ModuleLoader_ *element);
+static bool module_loader_matches_(const struct ModuleLoader_ *element, const char *path);
+
 struct ModuleHandle_;
 
 static FimoResult module_handle_new_local_(void (*export_iterator)(bool (*)(const FimoModuleExport *, void *), void *),
                                            const void *binary_handle, struct ModuleHandle_ **element);
-static FimoResult module_handle_new_plugin_(const char *path, struct ModuleHandle_ **element);
+static FimoResult module_handle_new_plugin_(const char *path, const char *load_path, struct ModuleHandle_ **element);
+static FimoResult module_handle_new_loader_(struct ModuleLoader_ *loader, const char *path, const char *load_path,
+                                            struct ModuleHandle_ **element);
+static void module_handle_iterate_exports_(struct ModuleHandle_ *element, bool (*f)(const FimoModuleExport *, void *),
+                                           void *data);
 static void module_handle_acquire_(struct ModuleHandle_ *element);
 static void module_handle_release_(struct ModuleHandle_ *element);
 
@@ -417,6 +454,8 @@ static const struct LoadingSetSymbol_ *loading_set_get_symbol_(FimoModuleLoading
 struct Module_;
 struct Symbol_;
 struct Namespace_;
+struct VerifiedBinary_;
+struct PendingEvent_;
 
 static FimoResult ctx_init_(FimoInternalModuleContext *ctx);
 static void ctx_deinit_(FimoInternalModuleContext *ctx);
@@ -424,13 +463,27 @@ static FimoResult ctx_lock_(FimoInternalModuleContext *ctx);
 static FimoResult ctx_unlock_(FimoInternalModuleContext *ctx);
 static FimoResult ctx_add_module_(FimoInternalModuleContext *ctx, struct ModuleInfoInner_ *info_inner);
 static FimoResult ctx_remove_module_(FimoInternalModuleContext *ctx, struct ModuleInfoInner_ *info_inner);
+static bool ctx_can_remove_group_(FimoInternalModuleContext *ctx, const char *group);
+static FimoResult ctx_remove_group_(FimoInternalModuleContext *ctx, const char *group);
 static FimoResult ctx_link_module_(FimoInternalModuleContext *ctx, struct ModuleInfoInner_ *info_inner,
                                    struct ModuleInfoInner_ *other_inner);
 static FimoResult ctx_unlink_module_(FimoInternalModuleContext *ctx, struct ModuleInfoInner_ *info_inner,
                                      struct ModuleInfoInner_ *other_inner);
 static bool ctx_can_remove_module_(FimoInternalModuleContext *ctx, struct ModuleInfoInner_ *info_inner);
+static void ctx_loaders_lock_(FimoInternalModuleContext *ctx);
+static void ctx_loaders_unlock_(FimoInternalModuleContext *ctx);
+static struct ModuleLoader_ *ctx_find_loader_(FimoInternalModuleContext *ctx, const char *path);
+static void ctx_publish_module_event_(FimoInternalModuleContext *ctx, const FimoBusTopic *topic, const char *module);
+static void ctx_queue_module_event_(FimoInternalModuleContext *ctx, const FimoBusTopic *topic, const char *module,
+                                    const char *description);
+static void ctx_publish_pending_events_(FimoInternalModuleContext *ctx);
+static void ctx_signatures_lock_(FimoInternalModuleContext *ctx);
+static void ctx_signatures_unlock_(FimoInternalModuleContext *ctx);
+static FimoResult ctx_verify_binary_(FimoInternalModuleContext *ctx, const char *path,
+                                     struct VerifiedBinary_ *binary);
 static FimoResult ctx_cleanup_loose_modules(FimoInternalModuleContext *ctx);
 static const struct Module_ *ctx_get_module_(FimoInternalModuleContext *ctx, const char *name);
+static bool ctx_has_group_(FimoInternalModuleContext *ctx, const char *group);
 static const struct Symbol_ *ctx_get_symbol_(FimoInternalModuleContext *ctx, const char *name, const char *ns);
 static const struct Symbol_ *ctx_get_symbol_compatible_(FimoInternalModuleContext *ctx, const char *name,
                                                         const char *ns, FimoVersion version);
@@ -446,8 +499,8 @@ static void ctx_remove_symbol_(FimoInternalModuleContext *ctx, const char *name,
 static FimoResult fi_module_new_pseudo_(FimoInternalModuleContext *ctx, const char *name, FimoModule **element);
 static FimoResult fi_module_new_from_export(FimoInternalModuleContext *ctx, FimoModuleLoadingSet *set,
                                             const FimoModuleExport *export, struct ModuleHandle_ *handle,
-                                            FimoModule **element);
-static void fi_module_free_(struct ModuleInfoInner_ *info_inner, FimoContext *context);
+                                            const char *group, FimoModule **element);
+static void fi_module_free_(struct ModuleInfoInner_ *info_inner, FimoContext *context, bool cleanup_export);
 
 static void fi_module_info_acquire_(const FimoModuleInfo *info);
 static void fi_module_info_release_(const FimoModuleInfo *info);
@@ -548,6 +601,144 @@ static void param_data_write_(struct ParamData_ *param, const void *value) {
     }
 }
 
+union ParamValue_ {
+    FimoU8 u8;
+    FimoU16 u16;
+    FimoU32 u32;
+    FimoU64 u64;
+    FimoI8 i8;
+    FimoI16 i16;
+    FimoI32 i32;
+    FimoI64 i64;
+};
+
+static union ParamValue_ param_value_read_(const void *value, const FimoModuleParamType type) {
+    FIMO_DEBUG_ASSERT(value)
+    union ParamValue_ x = {.u64 = 0};
+    switch (type) {
+        case FIMO_MODULE_PARAM_TYPE_U8:
+            x.u8 = *((const FimoU8 *)value);
+            break;
+        case FIMO_MODULE_PARAM_TYPE_U16:
+            x.u16 = *((const FimoU16 *)value);
+            break;
+        case FIMO_MODULE_PARAM_TYPE_U32:
+            x.u32 = *((const FimoU32 *)value);
+            break;
+        case FIMO_MODULE_PARAM_TYPE_U64:
+            x.u64 = *((const FimoU64 *)value);
+            break;
+        case FIMO_MODULE_PARAM_TYPE_I8:
+            x.i8 = *((const FimoI8 *)value);
+            break;
+        case FIMO_MODULE_PARAM_TYPE_I16:
+            x.i16 = *((const FimoI16 *)value);
+            break;
+        case FIMO_MODULE_PARAM_TYPE_I32:
+            x.i32 = *((const FimoI32 *)value);
+            break;
+        case FIMO_MODULE_PARAM_TYPE_I64:
+            x.i64 = *((const FimoI64 *)value);
+            break;
+    }
+    return x;
+}
+
+// Initializes the value of the parameter with a value of a possibly different type.
+static FimoResult param_data_init_converted_(struct ParamData_ *param, const union ParamValue_ *value,
+                                             const FimoModuleParamType type) {
+    FIMO_DEBUG_ASSERT(param && value)
+    bool is_negative = false;
+    FimoI64 signed_value = 0;
+    FimoU64 unsigned_value = 0;
+    switch (type) {
+        case FIMO_MODULE_PARAM_TYPE_U8:
+            unsigned_value = value->u8;
+            break;
+        case FIMO_MODULE_PARAM_TYPE_U16:
+            unsigned_value = value->u16;
+            break;
+        case FIMO_MODULE_PARAM_TYPE_U32:
+            unsigned_value = value->u32;
+            break;
+        case FIMO_MODULE_PARAM_TYPE_U64:
+            unsigned_value = value->u64;
+            break;
+        case FIMO_MODULE_PARAM_TYPE_I8:
+            signed_value = value->i8;
+            break;
+        case FIMO_MODULE_PARAM_TYPE_I16:
+            signed_value = value->i16;
+            break;
+        case FIMO_MODULE_PARAM_TYPE_I32:
+            signed_value = value->i32;
+            break;
+        case FIMO_MODULE_PARAM_TYPE_I64:
+            signed_value = value->i64;
+            break;
+    }
+    if (type >= FIMO_MODULE_PARAM_TYPE_I8) {
+        is_negative = signed_value < 0;
+        unsigned_value = is_negative ? 0 : (FimoU64)signed_value;
+    }
+    else {
+        signed_value = unsigned_value > INT64_MAX ? INT64_MAX : (FimoI64)unsigned_value;
+    }
+
+    switch (param->type) {
+        case FIMO_MODULE_PARAM_TYPE_U8:
+            if (is_negative || unsigned_value > UINT8_MAX) {
+                return FIMO_ERANGE;
+            }
+            param->value.u8 = (FimoU8)unsigned_value;
+            break;
+        case FIMO_MODULE_PARAM_TYPE_U16:
+            if (is_negative || unsigned_value > UINT16_MAX) {
+                return FIMO_ERANGE;
+            }
+            param->value.u16 = (FimoU16)unsigned_value;
+            break;
+        case FIMO_MODULE_PARAM_TYPE_U32:
+            if (is_negative || unsigned_value > UINT32_MAX) {
+                return FIMO_ERANGE;
+            }
+            param->value.u32 = (FimoU32)unsigned_value;
+            break;
+        case FIMO_MODULE_PARAM_TYPE_U64:
+            if (is_negative) {
+                return FIMO_ERANGE;
+            }
+            param->value.u64 = unsigned_value;
+            break;
+        case FIMO_MODULE_PARAM_TYPE_I8:
+            if (signed_value < INT8_MIN || signed_value > INT8_MAX) {
+                return FIMO_ERANGE;
+            }
+            param->value.i8 = (FimoI8)signed_value;
+            break;
+        case FIMO_MODULE_PARAM_TYPE_I16:
+            if (signed_value < INT16_MIN || signed_value > INT16_MAX) {
+                return FIMO_ERANGE;
+            }
+            param->value.i16 = (FimoI16)signed_value;
+            break;
+        case FIMO_MODULE_PARAM_TYPE_I32:
+            if (signed_value < INT32_MIN || signed_value > INT32_MAX) {
+                return FIMO_ERANGE;
+            }
+            param->value.i32 = (FimoI32)signed_value;
+            break;
+        case FIMO_MODULE_PARAM_TYPE_I64:
+            if (!is_negative && unsigned_value > INT64_MAX) {
+                return FIMO_ERANGE;
+            }
+            param->value.i64 = signed_value;
+            break;
+    }
+
+    return FIMO_EOK;
+}
+
 // Heap only.
 struct FimoModuleParam {
     FimoModuleParamAccess read;
@@ -766,6 +957,58 @@ static int module_info_param_cmp_(const struct ModuleInfoParam_ *a, const struct
     return strcmp(a->name, b->name);
 }
 
+///////////////////////////////////////////////////////////////////////
+//// Module Info Feature
+///////////////////////////////////////////////////////////////////////
+
+struct ModuleInfoFeature_ {
+    const char *name;
+    FimoModuleFeatureStability stability;
+    bool default_enabled;
+    bool available;
+    bool enabled;
+};
+
+static FimoResult module_info_feature_new_(const FimoModuleExportModifierFeature *decl, const bool available,
+                                           const bool enabled, struct ModuleInfoFeature_ *element) {
+    FIMO_DEBUG_ASSERT(decl && element)
+    char *name_ = NULL;
+    const FimoResult error = clone_string_(decl->name, &name_);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        return error;
+    }
+
+    *element = (struct ModuleInfoFeature_){
+            .name = name_,
+            .stability = decl->stability,
+            .default_enabled = decl->default_enabled,
+            .available = available,
+            .enabled = enabled,
+    };
+
+    return FIMO_EOK;
+}
+
+static void module_info_feature_free_(struct ModuleInfoFeature_ *element) {
+    FIMO_DEBUG_ASSERT(element)
+    fimo_free((char *)element->name);
+    element->name = NULL;
+}
+
+static uint64_t module_info_feature_hash_(const struct ModuleInfoFeature_ *item, const uint64_t seed0,
+                                          const uint64_t seed1) {
+    FIMO_DEBUG_ASSERT(item)
+    FimoUSize name_len = strlen(item->name);
+    return hashmap_xxhash3(item->name, name_len * sizeof(char), seed0, seed1);
+}
+
+static int module_info_feature_cmp_(const struct ModuleInfoFeature_ *a, const struct ModuleInfoFeature_ *b,
+                                    const void *udata) {
+    FIMO_DEBUG_ASSERT(a && b)
+    (void)udata;
+    return strcmp(a->name, b->name);
+}
+
 ///////////////////////////////////////////////////////////////////////
 //// Module Info Dependency
 ///////////////////////////////////////////////////////////////////////
@@ -774,6 +1017,7 @@ struct ModuleInfoDependency_ {
     const char *name;
     const FimoModuleInfo *info;
     bool is_static;
+    FimoArrayList symbols;
 };
 
 static FimoResult module_info_dependency_new_(const FimoModuleInfo *info, const bool is_static,
@@ -789,6 +1033,7 @@ static FimoResult module_info_dependency_new_(const FimoModuleInfo *info, const
             .name = name_,
             .info = info,
             .is_static = is_static,
+            .symbols = fimo_array_list_new(),
     };
 
     return FIMO_EOK;
@@ -798,6 +1043,30 @@ static void module_info_dependency_free_(struct ModuleInfoDependency_ *element)
     FIMO_DEBUG_ASSERT(element)
     fimo_free((char *)element->name);
     element->name = NULL;
+    fimo_array_list_free(&element->symbols, sizeof(const FimoModuleRawSymbol *), _Alignof(const FimoModuleRawSymbol *),
+                         NULL);
+}
+
+static bool module_info_dependency_has_symbol_(const struct ModuleInfoDependency_ *element,
+                                               const FimoModuleRawSymbol *symbol) {
+    FIMO_DEBUG_ASSERT(element && symbol)
+    const FimoModuleRawSymbol *const *symbols = element->symbols.elements;
+    for (FimoUSize i = 0; i < fimo_array_list_len(&element->symbols); i++) {
+        if (symbols[i] == symbol) {
+            return true;
+        }
+    }
+    return false;
+}
+
+static FimoResult module_info_dependency_add_symbol_(struct ModuleInfoDependency_ *element,
+                                                     const FimoModuleRawSymbol *symbol) {
+    FIMO_DEBUG_ASSERT(element && symbol)
+    if (module_info_dependency_has_symbol_(element, symbol)) {
+        return FIMO_EOK;
+    }
+    return fimo_array_list_push(&element->symbols, sizeof(const FimoModuleRawSymbol *),
+                                _Alignof(const FimoModuleRawSymbol *), &symbol, NULL);
 }
 
 static uint64_t module_info_dependency_hash_(const struct ModuleInfoDependency_ *item, const uint64_t seed0,
@@ -872,6 +1141,7 @@ enum ModuleType_ {
 struct ModuleInfoInner_ {
     struct hashmap *symbols;
     struct hashmap *parameters;
+    struct hashmap *features;
     struct hashmap *namespaces;
     struct hashmap *dependencies;
     struct ModuleHandle_ *handle;
@@ -890,7 +1160,7 @@ struct ModuleInfo_ {
 };
 
 static FimoResult module_info_new_(const char *name, const char *description, const char *author, const char *license,
-                                   const char *module_path, struct ModuleHandle_ *handle,
+                                   const char *module_path, const char *group, struct ModuleHandle_ *handle,
                                    const FimoModuleExport *export, enum ModuleType_ type, struct ModuleInfo_ **info) {
     FIMO_DEBUG_ASSERT(name && info && handle)
     FimoResult error = FIMO_EOK;
@@ -924,6 +1194,12 @@ static FimoResult module_info_new_(const char *name, const char *description, co
         goto module_path_alloc;
     }
 
+    char *group_ = NULL;
+    error = clone_string_(group, &group_);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        goto group_alloc;
+    }
+
     struct hashmap *symbols = hashmap_new_with_allocator(
             malloc_, realloc_, free_, sizeof(struct ModuleInfoSymbol_), 0, 0, 0, (HashFn_)module_info_symbol_hash_,
             (CmpFn_)module_info_symbol_cmp_, (FreeFn_)module_info_symbol_free_, NULL);
@@ -940,6 +1216,14 @@ static FimoResult module_info_new_(const char *name, const char *description, co
         goto alloc_parameters;
     }
 
+    struct hashmap *features = hashmap_new_with_allocator(
+            malloc_, realloc_, free_, sizeof(struct ModuleInfoFeature_), 0, 0, 0, (HashFn_)module_info_feature_hash_,
+            (CmpFn_)module_info_feature_cmp_, (FreeFn_)module_info_feature_free_, NULL);
+    if (features == NULL) {
+        error = FIMO_ENOMEM;
+        goto alloc_features;
+    }
+
     struct hashmap *namespaces =
             hashmap_new_with_allocator(malloc_, realloc_, free_, sizeof(struct ModuleInfoNamespace_), 0, 0, 0,
                                        (HashFn_)module_info_namespace_hash_, (CmpFn_)module_info_namespace_cmp_,
@@ -973,6 +1257,7 @@ static FimoResult module_info_new_(const char *name, const char *description, co
                             .author = author_,
                             .license = license_,
                             .module_path = module_path_,
+                            .group = group_,
                             .acquire = fi_module_info_acquire_,
                             .release = fi_module_info_release_,
                             .is_loaded = fi_module_info_is_loaded,
@@ -984,6 +1269,7 @@ static FimoResult module_info_new_(const char *name, const char *description, co
                     {
                             .symbols = symbols,
                             .parameters = parameters,
+                            .features = features,
                             .namespaces = namespaces,
                             .dependencies = dependencies,
                             .handle = handle,
@@ -1008,10 +1294,14 @@ info_alloc:
 alloc_dependencies:
     hashmap_free(namespaces);
 alloc_namespaces:
+    hashmap_free(features);
+alloc_features:
     hashmap_free(parameters);
 alloc_parameters:
     hashmap_free(symbols);
 alloc_symbols:
+    fimo_free(group_);
+group_alloc:
     fimo_free(module_path_);
 module_path_alloc:
     fimo_free(license_);
@@ -1058,6 +1348,7 @@ static void module_info_release_(const struct ModuleInfo_ *info, const bool clea
     }
     module_info_unlock_(inner);
 
+    fimo_free((char *)info->info.group);
     fimo_free((char *)info->info.module_path);
     fimo_free((char *)info->info.license);
     fimo_free((char *)info->info.author);
@@ -1089,6 +1380,7 @@ static bool module_info_is_detached_(struct ModuleInfoInner_ *inner) {
 static void module_info_detach_(struct ModuleInfoInner_ *inner, const bool cleanup_export) {
     FIMO_DEBUG_ASSERT(inner && !module_info_is_detached_(inner) && module_info_can_unload_(inner))
     hashmap_free(inner->dependencies);
+    hashmap_free(inner->features);
     hashmap_free(inner->parameters);
     hashmap_free(inner->namespaces);
     hashmap_free(inner->symbols);
@@ -1203,6 +1495,37 @@ static const struct ModuleInfoParam_ *module_info_get_param_(struct ModuleInfoIn
     return x;
 }
 
+static FimoResult module_info_set_feature_(struct ModuleInfoInner_ *inner, const FimoModuleExportModifierFeature *decl,
+                                           const bool available, const bool enabled) {
+    FIMO_DEBUG_ASSERT(inner && decl && !module_info_is_detached_(inner))
+    struct ModuleInfoFeature_ f;
+    const FimoResult error = module_info_feature_new_(decl, available, enabled, &f);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        return error;
+    }
+
+    const void *old = hashmap_set(inner->features, &f);
+    if (old) {
+        module_info_feature_free_(&f);
+        return FIMO_EEXIST;
+    }
+
+    if (hashmap_oom(inner->features)) {
+        module_info_feature_free_(&f);
+        return FIMO_ENOMEM;
+    }
+
+    return FIMO_EOK;
+}
+
+static const struct ModuleInfoFeature_ *module_info_get_feature_(struct ModuleInfoInner_ *inner, const char *name) {
+    FIMO_DEBUG_ASSERT(inner && name)
+    if (module_info_is_detached_(inner)) {
+        return NULL;
+    }
+    return hashmap_get(inner->features, &(struct ModuleInfoFeature_){.name = name});
+}
+
 static FimoResult module_info_set_ns_(struct ModuleInfoInner_ *inner, const char *name, const bool is_static) {
     FIMO_DEBUG_ASSERT(inner && name && !module_info_is_detached_(inner))
     struct ModuleInfoNamespace_ ns;
@@ -1296,6 +1619,24 @@ static bool module_info_next_symbol_(struct ModuleInfoInner_ *inner, FimoUSize *
     return hashmap_iter(inner->symbols, it, (void **)item);
 }
 
+static bool module_info_next_param_(struct ModuleInfoInner_ *inner, FimoUSize *it,
+                                    const struct ModuleInfoParam_ **item) {
+    FIMO_DEBUG_ASSERT(inner && it && item)
+    if (module_info_is_detached_(inner)) {
+        return false;
+    }
+    return hashmap_iter(inner->parameters, it, (void **)item);
+}
+
+static bool module_info_next_feature_(struct ModuleInfoInner_ *inner, FimoUSize *it,
+                                      const struct ModuleInfoFeature_ **item) {
+    FIMO_DEBUG_ASSERT(inner && it && item)
+    if (module_info_is_detached_(inner)) {
+        return false;
+    }
+    return hashmap_iter(inner->features, it, (void **)item);
+}
+
 static bool module_info_next_ns_(struct ModuleInfoInner_ *inner, FimoUSize *it,
                                  const struct ModuleInfoNamespace_ **item) {
     FIMO_DEBUG_ASSERT(inner && it && item)
@@ -1314,6 +1655,128 @@ static bool module_info_next_dependency_(struct ModuleInfoInner_ *inner, FimoUSi
     return hashmap_iter(inner->dependencies, it, (void **)item);
 }
 
+///////////////////////////////////////////////////////////////////////
+//// Module Loader
+///////////////////////////////////////////////////////////////////////
+
+// Heap only.
+struct ModuleLoader_ {
+    FimoAtomicRefCount ref_count;
+    struct ModuleLoader_ *next;
+    char *name;
+    char *pattern;
+    void *data;
+    FimoResult (*load)(void *data, const char *path, void **binary);
+    void (*iterate_exports)(void *data, void *binary, bool (*)(const FimoModuleExport *, void *), void *);
+    void (*unload)(void *data, void *binary);
+    void (*on_drop)(void *data);
+};
+
+static FimoResult module_loader_new_(const FimoModuleLoader *loader, struct ModuleLoader_ **element) {
+    FIMO_DEBUG_ASSERT(loader && element)
+    FimoResult error = FIMO_EOK;
+    *element = fimo_malloc(sizeof(**element), &error);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        goto alloc_element;
+    }
+
+    char *name;
+    error = clone_string_(loader->name, &name);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        goto clone_name;
+    }
+
+    char *pattern;
+    error = clone_string_(loader->pattern, &pattern);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        goto clone_pattern;
+    }
+
+    **element = (struct ModuleLoader_){
+            .ref_count = FIMO_REFCOUNT_INIT,
+            .next = NULL,
+            .name = name,
+            .pattern = pattern,
+            .data = loader->data,
+            .load = loader->load,
+            .iterate_exports = loader->iterate_exports,
+            .unload = loader->unload,
+            .on_drop = loader->on_drop,
+    };
+
+    return FIMO_EOK;
+
+clone_pattern:
+    fimo_free(name);
+clone_name:
+    fimo_free(*element);
+alloc_element:
+    return error;
+}
+
+static void module_loader_acquire_(struct ModuleLoader_ *element) {
+    FIMO_DEBUG_ASSERT(element)
+    fimo_increase_strong_count_atomic(&element->ref_count);
+}
+
+static void module_loader_release_(struct ModuleLoader_ *element) {
+    FIMO_DEBUG_ASSERT(element)
+    bool can_destroy = fimo_decrease_strong_count_atomic(&element->ref_count);
+    if (!can_destroy) {
+        return;
+    }
+    if (element->on_drop) {
+        element->on_drop(element->data);
+    }
+    fimo_free(element->pattern);
+    fimo_free(element->name);
+    fimo_free(element);
+}
+
+static bool module_loader_pattern_matches_(const char *pattern, const char *name) {
+    FIMO_DEBUG_ASSERT(pattern && name)
+    // Position of the last `*` wildcard, and of the name character it currently covers,
+    // which allows us to backtrack on a mismatch.
+    const char *star = NULL;
+    const char *star_name = NULL;
+    while (*name != '\0') {
+        if (*pattern == '*') {
+            star = pattern++;
+            star_name = name;
+        }
+        else if (*pattern == '?' || *pattern == *name) {
+            pattern++;
+            name++;
+        }
+        else if (star) {
+            pattern = star + 1;
+            name = ++star_name;
+        }
+        else {
+            return false;
+        }
+    }
+    while (*pattern == '*') {
+        pattern++;
+    }
+    return *pattern == '\0';
+}
+
+static bool module_loader_matches_(const struct ModuleLoader_ *element, const char *path) {
+    FIMO_DEBUG_ASSERT(element && path)
+    const char *file_name = path;
+    for (const char *it = path; *it != '\0'; it++) {
+#if _WIN32
+        if (*it == '/' || *it == '\\') {
+#else
+        if (*it == '/') {
+#endif
+            file_name = it + 1;
+        }
+    }
+    return module_loader_pattern_matches_(element->pattern, file_name);
+}
+
 ///////////////////////////////////////////////////////////////////////
 //// Module Handle
 ///////////////////////////////////////////////////////////////////////
@@ -1324,6 +1787,8 @@ struct ModuleHandle_ {
     MODULE_HANDLE_ handle;
     const char *module_path;
     void (*export_iterator)(bool (*)(const FimoModuleExport *, void *), void *);
+    struct ModuleLoader_ *loader;
+    void *binary;
 };
 
 static FimoResult module_handle_new_local_(void (*export_iterator)(bool (*)(const FimoModuleExport *, void *), void *),
@@ -1338,7 +1803,15 @@ static FimoResult module_handle_new_local_(void (*export_iterator)(bool (*)(cons
     MODULE_HANDLE_ handle;
     char *module_path;
 
-#if _WIN32
+#if FIMO_STD_STATIC_MODULES
+    // Without a dynamic linker we can not query the binary containing the modules,
+    // therefore the paths of the resources are used as is.
+    handle = NULL;
+    error = clone_string_("", &module_path);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        goto get_module_path;
+    }
+#elif _WIN32
     bool found_handle = GetModuleHandleExA(GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS, binary_handle, &handle);
     if (!found_handle) {
         error = FIMO_RESULT_FROM_SYSTEM_ERROR_CODE(GetLastError());
@@ -1418,11 +1891,16 @@ static FimoResult module_handle_new_local_(void (*export_iterator)(bool (*)(cons
             .handle = handle,
             .module_path = module_path,
             .export_iterator = export_iterator,
+            .loader = NULL,
+            .binary = NULL,
     };
 
     return FIMO_EOK;
 
-#if _WIN32
+#if FIMO_STD_STATIC_MODULES
+get_module_path:
+    fimo_free(*element);
+#elif _WIN32
 get_parent:;
 convert_path:;
 get_module_path_w:
@@ -1440,8 +1918,17 @@ alloc_element:
     return error;
 }
 
-static FimoResult module_handle_new_plugin_(const char *path, struct ModuleHandle_ **element) {
-    FIMO_DEBUG_ASSERT(path && element)
+#if FIMO_STD_STATIC_MODULES
+static FimoResult module_handle_new_plugin_(const char *path, const char *load_path, struct ModuleHandle_ **element) {
+    FIMO_DEBUG_ASSERT(path && load_path && element)
+    (void)path;
+    (void)load_path;
+    (void)element;
+    return FIMO_ENOTSUP;
+}
+#else
+static FimoResult module_handle_new_plugin_(const char *path, const char *load_path, struct ModuleHandle_ **element) {
+    FIMO_DEBUG_ASSERT(path && load_path && element)
     char *module_path;
     FimoResult error = path_get_parent_(path, &module_path);
     if (FIMO_RESULT_IS_ERROR(error)) {
@@ -1458,7 +1945,7 @@ static FimoResult module_handle_new_plugin_(const char *path, struct ModuleHandl
 
 #if _WIN32
     wchar_t *wide_path;
-    error = path_utf8_to_wide_(path, &wide_path);
+    error = path_utf8_to_wide_(load_path, &wide_path);
     if (FIMO_RESULT_IS_ERROR(error)) {
         goto get_wide_path;
     }
@@ -1478,7 +1965,7 @@ static FimoResult module_handle_new_plugin_(const char *path, struct ModuleHandl
     *(FARPROC *)&export_iterator = symbol;
     fimo_free(wide_path);
 #else
-    handle = dlopen(path, RTLD_NOW | RTLD_LOCAL);
+    handle = dlopen(load_path, RTLD_NOW | RTLD_LOCAL);
     if (handle == NULL) {
         const char *error_str = dlerror();
         FimoUSize error_str_len = strlen(error_str);
@@ -1519,6 +2006,8 @@ static FimoResult module_handle_new_plugin_(const char *path, struct ModuleHandl
             .handle = handle,
             .module_path = module_path,
             .export_iterator = export_iterator,
+            .loader = NULL,
+            .binary = NULL,
     };
 
     return FIMO_EOK;
@@ -1541,28 +2030,86 @@ alloc_element:
 get_path_parent:
     return error;
 }
+#endif
 
-static void module_handle_acquire_(struct ModuleHandle_ *element) {
-    FIMO_DEBUG_ASSERT(element)
-    fimo_increase_strong_count_atomic(&element->ref_count);
-}
-
-static void module_handle_release_(struct ModuleHandle_ *element) {
-    FIMO_DEBUG_ASSERT(element)
-    bool can_destroy = fimo_decrease_strong_count_atomic(&element->ref_count);
-    if (!can_destroy) {
-        return;
+static FimoResult module_handle_new_loader_(struct ModuleLoader_ *loader, const char *path, const char *load_path,
+                                            struct ModuleHandle_ **element) {
+    FIMO_DEBUG_ASSERT(loader && path && load_path && element)
+    char *module_path;
+    FimoResult error = path_get_parent_(path, &module_path);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        goto get_path_parent;
     }
-    fimo_free((char *)element->module_path);
-    fimo_free(element);
-}
 
-///////////////////////////////////////////////////////////////////////
-//// Loading Set Module
-///////////////////////////////////////////////////////////////////////
+    *element = fimo_malloc(sizeof(**element), &error);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        goto alloc_element;
+    }
+
+    void *binary = NULL;
+    error = loader->load(loader->data, load_path, &binary);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        goto load_binary;
+    }
+
+    module_loader_acquire_(loader);
+    **element = (struct ModuleHandle_){
+            .ref_count = FIMO_REFCOUNT_INIT,
+            .handle = NULL,
+            .module_path = module_path,
+            .export_iterator = NULL,
+            .loader = loader,
+            .binary = binary,
+    };
+
+    return FIMO_EOK;
+
+load_binary:
+    fimo_free(*element);
+alloc_element:
+    fimo_free(module_path);
+get_path_parent:
+    return error;
+}
+
+static void module_handle_iterate_exports_(struct ModuleHandle_ *element, bool (*f)(const FimoModuleExport *, void *),
+                                           void *data) {
+    FIMO_DEBUG_ASSERT(element && f)
+    if (element->loader) {
+        element->loader->iterate_exports(element->loader->data, element->binary, f, data);
+    }
+    else {
+        element->export_iterator(f, data);
+    }
+}
+
+static void module_handle_acquire_(struct ModuleHandle_ *element) {
+    FIMO_DEBUG_ASSERT(element)
+    fimo_increase_strong_count_atomic(&element->ref_count);
+}
+
+static void module_handle_release_(struct ModuleHandle_ *element) {
+    FIMO_DEBUG_ASSERT(element)
+    bool can_destroy = fimo_decrease_strong_count_atomic(&element->ref_count);
+    if (!can_destroy) {
+        return;
+    }
+    if (element->loader) {
+        element->loader->unload(element->loader->data, element->binary);
+        module_loader_release_(element->loader);
+    }
+    fimo_free((char *)element->module_path);
+    fimo_free(element);
+}
+
+///////////////////////////////////////////////////////////////////////
+//// Loading Set Module
+///////////////////////////////////////////////////////////////////////
 
 enum ModuleLoadStatus_ {
     MODULE_LOAD_STATUS_UNLOADED_,
+    // Constructed, but waiting for the remaining modules of its group.
+    MODULE_LOAD_STATUS_PENDING_,
     MODULE_LOAD_STATUS_LOADED_,
     MODULE_LOAD_STATUS_ERROR_,
 };
@@ -1575,6 +2122,7 @@ struct LoadingSetCallback_ {
 
 struct LoadingSetModule_ {
     const char *name;
+    const char *group;
     const FimoModuleInfo *info;
     FimoArrayList callbacks;
     struct ModuleHandle_ *handle;
@@ -1606,6 +2154,7 @@ static FimoResult loading_set_module_new_(const FimoModuleExport *export, struct
 
     *element = (struct LoadingSetModule_){
             .name = name,
+            .group = NULL,
             .info = NULL,
             .callbacks = fimo_array_list_new(),
             .handle = handle,
@@ -1628,6 +2177,7 @@ static void loading_set_module_free_(struct LoadingSetModule_ *element) {
         callback.error(element->export, callback.data);
     }
     fimo_free((char *)element->name);
+    fimo_free((char *)element->group);
     fimo_array_list_free(&element->callbacks, sizeof(struct LoadingSetCallback_), _Alignof(struct LoadingSetCallback_),
                          NULL);
 
@@ -1644,6 +2194,7 @@ static void loading_set_module_free_(struct LoadingSetModule_ *element) {
     module_handle_release_(element->handle);
 
     element->name = NULL;
+    element->group = NULL;
     element->handle = NULL;
     element->export = NULL;
 }
@@ -1653,6 +2204,7 @@ static FimoResult loading_set_module_append_callback_(struct LoadingSetModule_ *
     FIMO_DEBUG_ASSERT(element && callback.success && callback.error)
     switch (element->status) {
         case MODULE_LOAD_STATUS_UNLOADED_:
+        case MODULE_LOAD_STATUS_PENDING_:
             return fimo_array_list_push(&element->callbacks, sizeof(callback), _Alignof(struct LoadingSetModule_),
                                         &callback, NULL);
         case MODULE_LOAD_STATUS_LOADED_: {
@@ -1681,6 +2233,12 @@ static void loading_set_module_signal_error_(struct LoadingSetModule_ *element)
     }
 }
 
+static void loading_set_module_signal_pending_(struct LoadingSetModule_ *element, const FimoModuleInfo *info) {
+    FIMO_DEBUG_ASSERT(element && element->group && info)
+    element->status = MODULE_LOAD_STATUS_PENDING_;
+    element->info = info;
+}
+
 static void loading_set_module_signal_success(struct LoadingSetModule_ *element, const FimoModuleInfo *info) {
     FIMO_DEBUG_ASSERT(element && info)
     element->status = MODULE_LOAD_STATUS_LOADED_;
@@ -1974,6 +2532,107 @@ static bool loading_set_next_module_(FimoModuleLoadingSet *set, FimoUSize *it, c
     return hashmap_iter(set->modules, it, (void *)item);
 }
 
+static bool loading_set_module_in_group_(const struct LoadingSetModule_ *module, const char *group) {
+    FIMO_DEBUG_ASSERT(module && group)
+    return module->group != NULL && strcmp(module->group, group) == 0;
+}
+
+static bool loading_set_group_is_constructed_(FimoModuleLoadingSet *set, const char *group) {
+    FIMO_DEBUG_ASSERT(set && group)
+    FimoUSize it = 0;
+    const struct LoadingSetModule_ *module = NULL;
+    while (loading_set_next_module_(set, &it, &module)) {
+        if (loading_set_module_in_group_(module, group) && module->status != MODULE_LOAD_STATUS_PENDING_) {
+            return false;
+        }
+    }
+    return true;
+}
+
+static void loading_set_signal_group_success_(FimoModuleLoadingSet *set, const char *group) {
+    FIMO_DEBUG_ASSERT(set && group)
+    FimoUSize it = 0;
+    const struct LoadingSetModule_ *module = NULL;
+    while (loading_set_next_module_(set, &it, &module)) {
+        if (loading_set_module_in_group_(module, group)) {
+            FIMO_DEBUG_ASSERT(module->status == MODULE_LOAD_STATUS_PENDING_)
+            loading_set_module_signal_success((struct LoadingSetModule_ *)module, module->info);
+        }
+    }
+}
+
+static void loading_set_signal_group_error_(FimoModuleLoadingSet *set, FimoInternalModuleContext *ctx,
+                                            const char *group) {
+    FIMO_DEBUG_ASSERT(set && ctx && group)
+    WARN_(ctx, "rolling back the loading of the module group, group='%s'", group)
+
+    // Unload the modules of the group that were already constructed. The modules may depend on
+    // each other, so we remove them in multiple passes, until no more modules can be removed.
+    bool removed_module = true;
+    while (removed_module) {
+        removed_module = false;
+        FimoUSize it = 0;
+        const struct LoadingSetModule_ *module = NULL;
+        while (loading_set_next_module_(set, &it, &module)) {
+            if (!loading_set_module_in_group_(module, group) || module->status != MODULE_LOAD_STATUS_PENDING_) {
+                continue;
+            }
+
+            const struct ModuleInfo_ *info = module_info_from_module_info_(module->info);
+            struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
+            if (!ctx_can_remove_module_(ctx, info_inner)) {
+                module_info_unlock_(info_inner);
+                continue;
+            }
+            const FimoResult error = ctx_remove_module_(ctx, info_inner);
+            if (FIMO_RESULT_IS_ERROR(error)) {
+                module_info_unlock_(info_inner);
+                fimo_result_release(error);
+                continue;
+            }
+            // The export is still owned by the set.
+            fi_module_free_(info_inner, NULL, false);
+
+            struct LoadingSetModule_ *module_ = (struct LoadingSetModule_ *)module;
+            module_->status = MODULE_LOAD_STATUS_UNLOADED_;
+            module_->info = NULL;
+            removed_module = true;
+        }
+    }
+
+    FimoUSize it = 0;
+    const struct LoadingSetModule_ *module = NULL;
+    while (loading_set_next_module_(set, &it, &module)) {
+        if (!loading_set_module_in_group_(module, group)) {
+            continue;
+        }
+        switch (module->status) {
+            case MODULE_LOAD_STATUS_UNLOADED_:
+                loading_set_module_signal_error_((struct LoadingSetModule_ *)module);
+                break;
+            case MODULE_LOAD_STATUS_PENDING_:
+                ERROR_(ctx, FIMO_EPERM, "could not roll back the loading of the module, group='%s', module='%s'",
+                       group, module->name)
+                loading_set_module_signal_success((struct LoadingSetModule_ *)module, module->info);
+                break;
+            case MODULE_LOAD_STATUS_LOADED_:
+            case MODULE_LOAD_STATUS_ERROR_:
+                break;
+        }
+    }
+}
+
+static void loading_set_rollback_pending_groups_(FimoModuleLoadingSet *set, FimoInternalModuleContext *ctx) {
+    FIMO_DEBUG_ASSERT(set && ctx)
+    FimoUSize it = 0;
+    const struct LoadingSetModule_ *module = NULL;
+    while (loading_set_next_module_(set, &it, &module)) {
+        if (module->status == MODULE_LOAD_STATUS_PENDING_) {
+            loading_set_signal_group_error_(set, ctx, module->group);
+        }
+    }
+}
+
 static FimoResult loading_set_create_info_(FimoModuleLoadingSet *set, FimoInternalModuleContext *ctx,
                                            struct LoadingSetLoadingInfo_ *element) {
     FIMO_DEBUG_ASSERT(set && ctx && element)
@@ -2009,6 +2668,13 @@ static FimoResult loading_set_create_info_(FimoModuleLoadingSet *set, FimoIntern
                 goto skip_module;
             }
 
+            // Check that no other group with the same name is already loaded.
+            if (module->group != NULL && ctx_has_group_(ctx, module->group)) {
+                WARN_(ctx, "module group with the same name already exists, module='%s', group='%s'", module->name,
+                      module->group);
+                goto skip_module;
+            }
+
             // Check that all imported symbols are already exposed, or will be exposed.
             for (FimoISize i = 0; i < (FimoISize)module->export->symbol_imports_count; i++) {
                 const FimoModuleSymbolImport *import = &module->export->symbol_imports[i];
@@ -2074,6 +2740,9 @@ static FimoResult loading_set_create_info_(FimoModuleLoadingSet *set, FimoIntern
 
         skip_module:
             loading_set_module_signal_error_((void *)module);
+            if (module->group != NULL) {
+                loading_set_signal_group_error_(set, ctx, module->group);
+            }
         }
     }
 
@@ -2112,12 +2781,42 @@ static FimoResult loading_set_create_info_(FimoModuleLoadingSet *set, FimoIntern
                         ERROR_SIMPLE_(ctx, error, "could not connect module to its dependency in the module graph")
                         goto free_modules;
                     }
+
+                    // A module depending on a module group is loaded after the entire group, so that
+                    // the group can still be rolled back without affecting other modules.
+                    const struct LoadingSetModule_ *exporter = loading_set_get_module_(set, symbol->module);
+                    FIMO_DEBUG_ASSERT(exporter)
+                    if (exporter->group == NULL || loading_set_module_in_group_(module, exporter->group)) {
+                        continue;
+                    }
+                    FimoUSize member_it = 0;
+                    const struct LoadingSetModule_ *member = NULL;
+                    while (loading_set_next_module_(set, &member_it, &member)) {
+                        if (!loading_set_module_in_group_(member, exporter->group)) {
+                            continue;
+                        }
+                        const struct LoadingSetLoadingInfoEntry_ *member_entry =
+                                hashmap_get(modules, &(struct LoadingSetLoadingInfoEntry_){
+                                                             .name = member->name,
+                                                     });
+                        if (member_entry == NULL) {
+                            continue;
+                        }
+                        error = fimo_graph_add_edge(module_graph, src_node, member_entry->node, NULL, NULL, &edge_);
+                        if (FIMO_RESULT_IS_ERROR(error)) {
+                            ERROR_SIMPLE_(ctx, error, "could not connect module to its dependency in the module graph")
+                            goto free_modules;
+                        }
+                    }
                 }
             }
             continue;
 
         connect_skip_module:
             loading_set_module_signal_error_((void *)module);
+            if (module->group != NULL) {
+                loading_set_signal_group_error_(set, ctx, module->group);
+            }
         }
     }
 
@@ -2341,2544 +3040,4190 @@ static int namespace_cmp_(const struct Module_ *a, const struct Module_ *b, cons
 }
 
 ///////////////////////////////////////////////////////////////////////
-//// Context
+//// Parameter Override
 ///////////////////////////////////////////////////////////////////////
 
-static FimoResult ctx_init_(FimoInternalModuleContext *ctx) {
-    FIMO_DEBUG_ASSERT(ctx)
-    TRACE_SIMPLE_(ctx, "initializing the module context")
-    const int result = mtx_init(&ctx->mutex, mtx_plain);
-    if (result == thrd_error) {
-        FimoResult error = FIMO_RESULT_FROM_STRING("unknown error");
-        ERROR_SIMPLE_(ctx, error, "could not initialize mutex")
+struct ParamOverride_ {
+    const char *module;
+    const char *param;
+    FimoModuleParamType type;
+    union ParamValue_ value;
+};
+
+static FimoResult param_override_new_(const char *module, const char *param, const void *value,
+                                      const FimoModuleParamType type, struct ParamOverride_ *element) {
+    FIMO_DEBUG_ASSERT(module && param && value && element)
+    char *module_ = NULL;
+    FimoResult error = clone_string_(module, &module_);
+    if (FIMO_RESULT_IS_ERROR(error)) {
         return error;
     }
 
-    FimoResult error;
-    ctx->symbols = hashmap_new_with_allocator(malloc_, realloc_, free_, sizeof(struct Symbol_), 0, 0, 0,
-                                              (HashFn_)symbol_hash_, (CmpFn_)symbol_cmp_, (FreeFn_)symbol_free_, NULL);
-    if (ctx->symbols == NULL) {
-        error = FIMO_ENOMEM;
-        ERROR_SIMPLE_(ctx, error, "could not initialize symbols map")
-        goto deinit_mtx;
+    char *param_ = NULL;
+    error = clone_string_(param, &param_);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        fimo_free(module_);
+        return error;
     }
 
-    ctx->modules = hashmap_new_with_allocator(malloc_, realloc_, free_, sizeof(struct Module_), 0, 0, 0,
-                                              (HashFn_)module_hash_, (CmpFn_)module_cmp_, (FreeFn_)module_free_, NULL);
-    if (ctx->modules == NULL) {
-        error = FIMO_ENOMEM;
-        ERROR_SIMPLE_(ctx, error, "could not initialize modules map")
-        goto deinit_symbols;
+    *element = (struct ParamOverride_){
+            .module = module_,
+            .param = param_,
+            .type = type,
+            .value = param_value_read_(value, type),
+    };
+
+    return FIMO_EOK;
+}
+
+static void param_override_free_(struct ParamOverride_ *element) {
+    FIMO_DEBUG_ASSERT(element)
+    fimo_free((char *)element->param);
+    fimo_free((char *)element->module);
+    element->param = NULL;
+    element->module = NULL;
+}
+
+static uint64_t param_override_hash_(const struct ParamOverride_ *item, const uint64_t seed0, const uint64_t seed1) {
+    FIMO_DEBUG_ASSERT(item)
+    FimoUSize module_len = strlen(item->module);
+    FimoUSize param_len = strlen(item->param);
+
+    const uint64_t module_hash = hashmap_xxhash3(item->module, module_len * sizeof(char), seed0, seed1);
+    const uint64_t param_hash = hashmap_xxhash3(item->param, param_len * sizeof(char), seed0, seed1);
+    return combine_hashes_(module_hash, param_hash);
+}
+
+static int param_override_cmp_(const struct ParamOverride_ *a, const struct ParamOverride_ *b, const void *udata) {
+    FIMO_DEBUG_ASSERT(a && b)
+    (void)udata;
+    const int comp = strcmp(a->module, b->module);
+    if (comp != 0) {
+        return comp;
     }
+    return strcmp(a->param, b->param);
+}
 
-    ctx->namespaces = hashmap_new_with_allocator(malloc_, realloc_, free_, sizeof(struct Namespace_), 0, 0, 0,
-                                                 (HashFn_)namespace_hash_, (CmpFn_)namespace_cmp_,
-                                                 (FreeFn_)namespace_free_, NULL);
-    if (ctx->namespaces == NULL) {
-        error = FIMO_ENOMEM;
-        ERROR_SIMPLE_(ctx, error, "could not initialize namespaces map")
-        goto deinit_modules;
+///////////////////////////////////////////////////////////////////////
+//// Feature Override
+///////////////////////////////////////////////////////////////////////
+
+struct FeatureOverride_ {
+    const char *module;
+    const char *feature;
+    bool enabled;
+};
+
+static FimoResult feature_override_new_(const char *module, const char *feature, const bool enabled,
+                                        struct FeatureOverride_ *element) {
+    FIMO_DEBUG_ASSERT(module && feature && element)
+    char *module_ = NULL;
+    FimoResult error = clone_string_(module, &module_);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        return error;
     }
 
-    error = fimo_graph_new(sizeof(const FimoModule *), 0, NULL, NULL, &ctx->dependency_graph);
+    char *feature_ = NULL;
+    error = clone_string_(feature, &feature_);
     if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not initialize dependency graph")
-        goto deinit_namespaces;
+        fimo_free(module_);
+        return error;
     }
 
-    ctx->is_loading = false;
+    *element = (struct FeatureOverride_){
+            .module = module_,
+            .feature = feature_,
+            .enabled = enabled,
+    };
 
     return FIMO_EOK;
-
-deinit_namespaces:
-    hashmap_free(ctx->namespaces);
-    ctx->namespaces = NULL;
-deinit_modules:
-    hashmap_free(ctx->modules);
-    ctx->modules = NULL;
-deinit_symbols:
-    hashmap_free(ctx->symbols);
-    ctx->symbols = NULL;
-deinit_mtx:
-    mtx_destroy(&ctx->mutex);
-    return error;
 }
 
-static void ctx_deinit_(FimoInternalModuleContext *ctx) {
-    FIMO_DEBUG_ASSERT(ctx)
-    TRACE_SIMPLE_(ctx, "deinitializing the module context")
+static void feature_override_free_(struct FeatureOverride_ *element) {
+    FIMO_DEBUG_ASSERT(element)
+    fimo_free((char *)element->feature);
+    fimo_free((char *)element->module);
+    element->feature = NULL;
+    element->module = NULL;
+}
 
-    // Since the context is being destroyed there must be no one holding a reference
-    // to the main context. As each module implicitly holds a reference to the context,
-    // this must also mean that no modules are loaded.
-    FIMO_ASSERT(hashmap_count(ctx->symbols) == 0);
-    FIMO_ASSERT(hashmap_count(ctx->modules) == 0);
-    FIMO_ASSERT(hashmap_count(ctx->namespaces) == 0);
-    FIMO_ASSERT(fimo_graph_node_count(ctx->dependency_graph) == 0);
-    FIMO_ASSERT_FALSE(ctx->is_loading);
+static uint64_t feature_override_hash_(const struct FeatureOverride_ *item, const uint64_t seed0,
+                                       const uint64_t seed1) {
+    FIMO_DEBUG_ASSERT(item)
+    FimoUSize module_len = strlen(item->module);
+    FimoUSize feature_len = strlen(item->feature);
 
-    fimo_graph_free(ctx->dependency_graph);
-    hashmap_free(ctx->namespaces);
-    hashmap_free(ctx->modules);
-    hashmap_free(ctx->symbols);
-    mtx_destroy(&ctx->mutex);
+    const uint64_t module_hash = hashmap_xxhash3(item->module, module_len * sizeof(char), seed0, seed1);
+    const uint64_t feature_hash = hashmap_xxhash3(item->feature, feature_len * sizeof(char), seed0, seed1);
+    return combine_hashes_(module_hash, feature_hash);
 }
 
-static FimoResult ctx_lock_(FimoInternalModuleContext *ctx) {
-    FIMO_DEBUG_ASSERT(ctx)
-    TRACE_SIMPLE_(ctx, "")
-    if (mtx_lock(&ctx->mutex) == thrd_error) {
-        FimoResult error = FIMO_RESULT_FROM_STRING("unknown error");
-        ERROR_SIMPLE_(ctx, error, "could not lock the context")
-        return error;
+static int feature_override_cmp_(const struct FeatureOverride_ *a, const struct FeatureOverride_ *b,
+                                 const void *udata) {
+    FIMO_DEBUG_ASSERT(a && b)
+    (void)udata;
+    const int comp = strcmp(a->module, b->module);
+    if (comp != 0) {
+        return comp;
     }
-    return FIMO_EOK;
+    return strcmp(a->feature, b->feature);
 }
 
-static FimoResult ctx_unlock_(FimoInternalModuleContext *ctx) {
-    FIMO_DEBUG_ASSERT(ctx)
-    TRACE_SIMPLE_(ctx, "")
-    if (mtx_unlock(&ctx->mutex) == thrd_error) {
-        FimoResult error = FIMO_RESULT_FROM_STRING("unknown error");
-        ERROR_SIMPLE_(ctx, error, "could not unlock the context")
-        return error;
-    }
-    return FIMO_EOK;
+///////////////////////////////////////////////////////////////////////
+//// Signature
+///////////////////////////////////////////////////////////////////////
+
+struct TrustedKey_ {
+    FimoU8 key[FIMO_MODULE_SIGNATURE_PUBLIC_KEY_SIZE];
+};
+
+static uint64_t trusted_key_hash_(const struct TrustedKey_ *item, const uint64_t seed0, const uint64_t seed1) {
+    FIMO_DEBUG_ASSERT(item)
+    return hashmap_xxhash3(item->key, sizeof(item->key), seed0, seed1);
 }
 
-static FimoResult ctx_add_module_(FimoInternalModuleContext *ctx, struct ModuleInfoInner_ *info_inner) {
-    FIMO_DEBUG_ASSERT(ctx && info_inner && !module_info_is_detached_(info_inner))
-    const struct ModuleInfo_ *info = module_info_from_inner_(info_inner);
+static int trusted_key_cmp_(const struct TrustedKey_ *a, const struct TrustedKey_ *b, const void *udata) {
+    FIMO_DEBUG_ASSERT(a && b)
+    (void)udata;
+    return memcmp(a->key, b->key, sizeof(a->key));
+}
 
-    TRACE_(ctx, "module='%s'", info->info.name)
-    if (hashmap_get(ctx->modules, &(struct Module_){.name = info->info.name}) != NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "module already exists, module='%s'", info->info.name)
-        return FIMO_EINVAL;
-    }
+struct PinnedKey_ {
+    const char *path;
+    FimoU8 key[FIMO_MODULE_SIGNATURE_PUBLIC_KEY_SIZE];
+};
 
-    FimoU64 node;
-    FimoResult error = fimo_graph_add_node(ctx->dependency_graph, &info_inner->module, &node);
+static FimoResult pinned_key_new_(const char *path, const FimoU8 *key, struct PinnedKey_ *element) {
+    FIMO_DEBUG_ASSERT(path && key && element)
+    char *path_ = NULL;
+    const FimoResult error = clone_string_(path, &path_);
     if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not add the module to the dependency graph")
         return error;
     }
 
-    // Check for no duplicate symbols.
-    {
-        FimoUSize it = 0;
-        const struct ModuleInfoSymbol_ *symbol = NULL;
-        while (module_info_next_symbol_(info_inner, &it, &symbol)) {
-            const struct Symbol_ *s = ctx_get_symbol_(ctx, symbol->name, symbol->ns);
-            if (s) {
-                error = FIMO_EINVAL;
-                ERROR_(ctx, error, "symbol already exists, module='%s', symbol='%s', ns='%s'", info->info.name,
-                       symbol->name, symbol->ns)
-                goto remove_node;
-            }
-        }
-    }
-    // Check that all imported namespaces exist.
-    {
-        FimoUSize it = 0;
-        const struct ModuleInfoNamespace_ *ns = NULL;
-        while (module_info_next_ns_(info_inner, &it, &ns)) {
-            const struct Namespace_ *n = ctx_get_ns_(ctx, ns->name);
-            if (n == NULL) {
-                error = FIMO_EINVAL;
-                ERROR_(ctx, error, "namespace does not exist, module='%s', ns='%s'", info->info.name, ns->name)
-                goto remove_node;
-            }
-        }
-    }
-    // Acquire all imported namespaces.
-    {
-        FimoUSize it = 0;
-        const struct ModuleInfoNamespace_ *ns = NULL;
-        while (module_info_next_ns_(info_inner, &it, &ns)) {
-            error = ctx_ns_acquire_(ctx, ns->name);
-            FIMO_DEBUG_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
-        }
+    *element = (struct PinnedKey_){.path = path_};
+    memcpy(element->key, key, sizeof(element->key));
+
+    return FIMO_EOK;
+}
+
+static void pinned_key_free_(struct PinnedKey_ *element) {
+    FIMO_DEBUG_ASSERT(element)
+    fimo_free((char *)element->path);
+    element->path = NULL;
+}
+
+static uint64_t pinned_key_hash_(const struct PinnedKey_ *item, const uint64_t seed0, const uint64_t seed1) {
+    FIMO_DEBUG_ASSERT(item)
+    return hashmap_xxhash3(item->path, strlen(item->path) * sizeof(char), seed0, seed1);
+}
+
+static int pinned_key_cmp_(const struct PinnedKey_ *a, const struct PinnedKey_ *b, const void *udata) {
+    FIMO_DEBUG_ASSERT(a && b)
+    (void)udata;
+    return strcmp(a->path, b->path);
+}
+
+static FimoResult signature_open_file_(const char *path, FILE **file) {
+    FIMO_DEBUG_ASSERT(path && file)
+#if _WIN32
+    wchar_t *wide_path;
+    const FimoResult error = path_utf8_to_wide_(path, &wide_path);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        return error;
     }
-    // Check that all dependencies are met and correct.
-    {
-        FimoUSize it = 0;
-        const struct ModuleInfoDependency_ *dependency = NULL;
-        while (module_info_next_dependency_(info_inner, &it, &dependency)) {
-            const struct Module_ *dep_mod = ctx_get_module_(ctx, dependency->name);
-            if (dep_mod == NULL) {
-                error = FIMO_EINVAL;
-                ERROR_(ctx, error, "dependency not found, module='%s', dependency='%s'", info->info.name,
-                       dependency->name)
-                goto release_namespaces;
-            }
-            FIMO_ASSERT(dependency->info == dep_mod->module->module_info)
-            FimoU64 edge;
-            error = fimo_graph_add_edge(ctx->dependency_graph, node, dep_mod->node, NULL, NULL, &edge);
-            if (FIMO_RESULT_IS_ERROR(error)) {
-                ERROR_SIMPLE_(ctx, error, "could not add edge to the dependency graph")
-                goto release_namespaces;
-            }
-        }
+    *file = _wfopen(wide_path, L"rb");
+    fimo_free(wide_path);
+#else
+    *file = fopen(path, "rb");
+#endif
+    if (*file == NULL) {
+        return FIMO_RESULT_FROM_ERROR_CODE(fimo_error_code_from_errno(errno));
     }
+    return FIMO_EOK;
+}
 
-    // Check the modifiers.
-    if (info_inner->export) {
-        for (FimoISize i = 0; i < (FimoISize)info_inner->export->modifiers_count; i++) {
-            const FimoModuleExportModifier *modifier = &info_inner->export->modifiers[i];
-            if (modifier->key != FIMO_MODULE_EXPORT_MODIFIER_KEY_DEPENDENCY) {
-                continue;
-            }
-            const FimoModuleInfo *dependency = modifier->value;
-            FIMO_DEBUG_ASSERT(dependency && dependency->name);
-            const struct Module_ *dep_mod = ctx_get_module_(ctx, dependency->name);
-            if (dep_mod == NULL) {
-                error = FIMO_EINVAL;
-                ERROR_(ctx, error, "dependency not found, module='%s', dependency='%s'", info->info.name,
-                       dependency->name)
-                goto release_namespaces;
-            }
-            FIMO_ASSERT(dependency == dep_mod->module->module_info);
-            FimoU64 edge;
-            error = fimo_graph_add_edge(ctx->dependency_graph, node, dep_mod->node, NULL, NULL, &edge);
-            if (FIMO_RESULT_IS_ERROR(error)) {
-                ERROR_SIMPLE_(ctx, error, "could not add edge to the dependency graph")
-                goto release_namespaces;
-            }
-        }
+// Reads the detached signature file of a binary, located at `<path>.sig`.
+static FimoResult signature_read_(const char *path, FimoU8 signature_file[FIMO_MODULE_SIGNATURE_FILE_SIZE]) {
+    FIMO_DEBUG_ASSERT(path && signature_file)
+    static const char extension[] = ".sig";
+    const FimoUSize path_len = strlen(path);
+
+    FimoResult error = FIMO_EOK;
+    char *signature_path = fimo_malloc(path_len + sizeof(extension), &error);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        return error;
     }
+    memcpy(signature_path, path, path_len);
+    memcpy(signature_path + path_len, extension, sizeof(extension));
 
-    // Check that the dependency graph is cycle free
-    bool is_cyclic;
-    error = fimo_graph_is_cyclic(ctx->dependency_graph, &is_cyclic);
+    FILE *file;
+    error = signature_open_file_(signature_path, &file);
+    fimo_free(signature_path);
     if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not determine if the dependency graph is cycle free")
-        goto release_namespaces;
+        fimo_result_release(error);
+        return FIMO_ENOKEY;
     }
-    if (is_cyclic) {
-        error = FIMO_EINVAL;
-        ERROR_SIMPLE_(ctx, error, "adding the module would introduce a cyclic depdendency")
-        goto release_namespaces;
+
+    // The file must contain exactly one public key and one signature.
+    FimoU8 trailing;
+    const FimoUSize read = fread(signature_file, 1, FIMO_MODULE_SIGNATURE_FILE_SIZE, file);
+    const bool has_trailing = fread(&trailing, 1, 1, file) != 0;
+    fclose(file);
+    if (read != FIMO_MODULE_SIGNATURE_FILE_SIZE || has_trailing) {
+        return FIMO_EBADMSG;
     }
 
-    // Allocate all exported namespaces.
-    {
-        FimoUSize it = 0;
-        const struct ModuleInfoSymbol_ *symbol = NULL;
-        while (module_info_next_symbol_(info_inner, &it, &symbol)) {
-            error = ctx_ns_allocate_if_not_found_(ctx, symbol->ns);
-            if (FIMO_RESULT_IS_ERROR(error)) {
-                ERROR_(ctx, error, "failed to allocate ns, module='%s', ns='%s'", info->info.name, symbol->ns)
-                goto remove_allocated_ns;
-            }
-        }
+    return FIMO_EOK;
+}
+
+// Binary whose contents were verified against their signature.
+//
+// The binary is opened only once, and must be loaded from `load_path`, which refers to the
+// verified contents instead of the original path, so that the binary can not be replaced
+// between its verification and loading. On Linux, the contents are copied into a sealed
+// anonymous file while they are verified. On Windows, other writers are excluded until the
+// binary is closed. Other platforms load the binary through the open file descriptor.
+struct VerifiedBinary_ {
+    char *canonical_path;
+    char *load_path;
+#if _WIN32
+    HANDLE file;
+#else
+    int fd;
+#endif
+};
+
+static void verified_binary_free_(struct VerifiedBinary_ *binary) {
+    FIMO_DEBUG_ASSERT(binary)
+    fimo_free(binary->canonical_path);
+    fimo_free(binary->load_path);
+    binary->canonical_path = NULL;
+    binary->load_path = NULL;
+#if _WIN32
+    if (binary->file != INVALID_HANDLE_VALUE) {
+        CloseHandle(binary->file);
+        binary->file = INVALID_HANDLE_VALUE;
     }
-    // Export all symbols.
-    {
-        FimoUSize it = 0;
-        const struct ModuleInfoSymbol_ *symbol = NULL;
-        while (module_info_next_symbol_(info_inner, &it, &symbol)) {
-            error = ctx_insert_symbol_(ctx, symbol->name, symbol->ns, symbol->version, info->info.name);
-            if (FIMO_RESULT_IS_ERROR(error)) {
-                ERROR_(ctx, error, "failed to allocate export symbol, module='%s', symbol='%s', ns='%s'",
-                       info->info.name, symbol->name, symbol->ns)
-                goto remove_symbol_export;
-            }
-        }
+#else
+    if (binary->fd != -1) {
+        close(binary->fd);
+        binary->fd = -1;
     }
-    // Insert the module.
-    {
-        struct Module_ module_;
-        error = module_new_(info_inner->module, node, &module_);
-        if (FIMO_RESULT_IS_ERROR(error)) {
-            ERROR_(ctx, error, "failed to allocate module, module='%s'", info->info.name)
-            goto remove_symbol_export;
-        }
+#endif
+}
 
-        hashmap_set(ctx->modules, &module_);
-        if (hashmap_oom(ctx->modules)) {
-            module_free_(&module_);
-            error = FIMO_ENOMEM;
-            ERROR_(ctx, error, "failed to insert module, module='%s'", info->info.name)
-            goto remove_symbol_export;
-        }
+#if _WIN32
+static FimoResult verified_binary_new_(const char *path, const FimoU8 signature_file[FIMO_MODULE_SIGNATURE_FILE_SIZE],
+                                       struct VerifiedBinary_ *binary) {
+    FIMO_DEBUG_ASSERT(path && signature_file && binary)
+    *binary = (struct VerifiedBinary_){.file = INVALID_HANDLE_VALUE};
+
+    wchar_t *wide_path;
+    FimoResult error = path_utf8_to_wide_(path, &wide_path);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        return error;
     }
 
-    return FIMO_EOK;
+    // Only readers may share the file, so that it can not be modified until it is closed.
+    binary->file = CreateFileW(wide_path, GENERIC_READ, FILE_SHARE_READ, NULL, OPEN_EXISTING, FILE_ATTRIBUTE_NORMAL,
+                               NULL);
+    fimo_free(wide_path);
+    if (binary->file == INVALID_HANDLE_VALUE) {
+        return FIMO_RESULT_FROM_SYSTEM_ERROR_CODE(GetLastError());
+    }
 
-remove_symbol_export: {
-    FimoUSize it = 0;
-    const struct ModuleInfoSymbol_ *symbol = NULL;
-    while (module_info_next_symbol_(info_inner, &it, &symbol)) {
-        if (ctx_get_symbol_(ctx, symbol->name, symbol->ns)) {
-            ctx_remove_symbol_(ctx, symbol->name, symbol->ns);
+    FimoInternalEd25519Verifier verifier;
+    fimo_internal_ed25519_verify_init(&verifier, signature_file,
+                                      signature_file + FIMO_MODULE_SIGNATURE_PUBLIC_KEY_SIZE);
+
+    FimoU8 buffer[4096];
+    DWORD read;
+    do {
+        if (!ReadFile(binary->file, buffer, sizeof(buffer), &read, NULL)) {
+            error = FIMO_RESULT_FROM_SYSTEM_ERROR_CODE(GetLastError());
+            goto cleanup;
         }
+        fimo_internal_ed25519_verify_update(&verifier, buffer, read);
+    } while (read != 0);
+
+    if (!fimo_internal_ed25519_verify_final(&verifier)) {
+        error = FIMO_EBADMSG;
+        goto cleanup;
     }
-}
-remove_allocated_ns: {
-    FimoUSize it = 0;
-    const struct ModuleInfoSymbol_ *symbol = NULL;
-    while (module_info_next_symbol_(info_inner, &it, &symbol)) {
-        ctx_ns_free_if_empty_(ctx, symbol->name);
+
+    const DWORD canonical_len = GetFinalPathNameByHandleW(binary->file, NULL, 0, FILE_NAME_NORMALIZED);
+    if (canonical_len == 0) {
+        error = FIMO_RESULT_FROM_SYSTEM_ERROR_CODE(GetLastError());
+        goto cleanup;
     }
-}
-release_namespaces: {
-    FimoUSize it = 0;
-    const struct ModuleInfoNamespace_ *ns = NULL;
-    while (module_info_next_ns_(info_inner, &it, &ns)) {
-        ctx_ns_release_(ctx, ns->name);
+    wchar_t *canonical_path = fimo_malloc(canonical_len * sizeof(wchar_t), &error);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        goto cleanup;
     }
-}
-remove_node: {
-    void *data;
-    const FimoResult error_ = fimo_graph_remove_node(ctx->dependency_graph, node, &data);
-    FIMO_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error_))
-    FIMO_ASSERT(data)
-}
+    if (GetFinalPathNameByHandleW(binary->file, canonical_path, canonical_len, FILE_NAME_NORMALIZED) == 0) {
+        error = FIMO_RESULT_FROM_SYSTEM_ERROR_CODE(GetLastError());
+        fimo_free(canonical_path);
+        goto cleanup;
+    }
+    error = path_wide_to_utf8_(canonical_path, &binary->canonical_path);
+    fimo_free(canonical_path);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        goto cleanup;
+    }
+
+    // The file can not be replaced while it is open, therefore it is safe to load it by its path.
+    error = clone_string_(binary->canonical_path, &binary->load_path);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        goto cleanup;
+    }
+
+    return FIMO_EOK;
 
+cleanup:
+    verified_binary_free_(binary);
     return error;
 }
+#else
+static FimoResult verified_binary_new_(const char *path, const FimoU8 signature_file[FIMO_MODULE_SIGNATURE_FILE_SIZE],
+                                       struct VerifiedBinary_ *binary) {
+    FIMO_DEBUG_ASSERT(path && signature_file && binary)
+    *binary = (struct VerifiedBinary_){.fd = -1};
 
-static FimoResult ctx_remove_module_(FimoInternalModuleContext *ctx, struct ModuleInfoInner_ *info_inner) {
-    FIMO_DEBUG_ASSERT(ctx && info_inner && !module_info_is_detached_(info_inner))
-    const struct ModuleInfo_ *info = module_info_from_inner_(info_inner);
+    const int source = open(path, O_RDONLY | O_CLOEXEC);
+    if (source == -1) {
+        return FIMO_RESULT_FROM_ERROR_CODE(fimo_error_code_from_errno(errno));
+    }
 
-    TRACE_(ctx, "module='%s'", info->info.name)
-    if (!ctx_can_remove_module_(ctx, info_inner)) {
-        ERROR_(ctx, FIMO_EPERM, "the module can not be removed, module='%s'", info->info.name)
-        return FIMO_EPERM;
+    FimoResult error = FIMO_EOK;
+    struct stat source_stat;
+    if (fstat(source, &source_stat) != 0) {
+        error = FIMO_RESULT_FROM_ERROR_CODE(fimo_error_code_from_errno(errno));
+        goto cleanup;
+    }
+    if (!S_ISREG(source_stat.st_mode)) {
+        error = FIMO_EINVAL;
+        goto cleanup;
     }
 
-    const struct Module_ *module_ = hashmap_get(ctx->modules, &(struct Module_){
-                                                                      .name = info->info.name,
-                                                              });
-    if (module_ == NULL || module_->module != info_inner->module) {
-        ERROR_(ctx, FIMO_EINVAL, "module is not registered with the backend, module='%s'", info->info.name)
-        return FIMO_EINVAL;
+    // The canonical path must still refer to the opened file.
+    binary->canonical_path = fimo_malloc(PATH_MAX, &error);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        goto cleanup;
+    }
+    struct stat canonical_stat;
+    if (realpath(path, binary->canonical_path) == NULL || stat(binary->canonical_path, &canonical_stat) != 0) {
+        error = FIMO_RESULT_FROM_ERROR_CODE(fimo_error_code_from_errno(errno));
+        goto cleanup;
+    }
+    if (canonical_stat.st_dev != source_stat.st_dev || canonical_stat.st_ino != source_stat.st_ino) {
+        error = FIMO_EAGAIN;
+        goto cleanup;
     }
 
-    FimoUSize count;
-    const FimoU64 node = module_->node;
-    FimoResult error = fimo_graph_neighbors_count(ctx->dependency_graph, node, true, &count);
-    FIMO_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
-    if (count != 0) {
-        ERROR_(ctx, FIMO_EPERM, "module is still in use, module='%s'", info->info.name)
-        return FIMO_EPERM;
+#if __linux__
+    binary->fd = memfd_create("fimo_module", MFD_CLOEXEC | MFD_ALLOW_SEALING);
+    if (binary->fd == -1) {
+        error = FIMO_RESULT_FROM_ERROR_CODE(fimo_error_code_from_errno(errno));
+        goto cleanup;
     }
+#endif
 
-    // Remove all symbols.
-    {
-        FimoUSize it = 0;
-        const struct ModuleInfoSymbol_ *symbol = NULL;
-        while (module_info_next_symbol_(info_inner, &it, &symbol)) {
-            ctx_remove_symbol_(ctx, symbol->name, symbol->ns);
+    FimoInternalEd25519Verifier verifier;
+    fimo_internal_ed25519_verify_init(&verifier, signature_file,
+                                      signature_file + FIMO_MODULE_SIGNATURE_PUBLIC_KEY_SIZE);
+
+    FimoU8 buffer[4096];
+    for (;;) {
+        const ssize_t read_len = read(source, buffer, sizeof(buffer));
+        if (read_len == 0) {
+            break;
         }
-    }
-    // Release all namespaces.
-    {
-        FimoUSize it = 0;
-        const struct ModuleInfoNamespace_ *ns = NULL;
-        while (module_info_next_ns_(info_inner, &it, &ns)) {
-            ctx_ns_release_(ctx, ns->name);
+        if (read_len < 0) {
+            if (errno == EINTR) {
+                continue;
+            }
+            error = FIMO_RESULT_FROM_ERROR_CODE(fimo_error_code_from_errno(errno));
+            goto cleanup;
         }
-    }
-    // Check that no empty namespace is still referenced.
-    {
-        FimoUSize it = 0;
-        const struct ModuleInfoSymbol_ *symbol = NULL;
-        while (module_info_next_symbol_(info_inner, &it, &symbol)) {
-            if (strcmp(GLOBAL_NS, symbol->ns) == 0) {
-                continue;
-            }
-            const struct Namespace_ *ns = ctx_get_ns_(ctx, symbol->ns);
-            if (ns && ns->reference_count != 0 && ns->symbol_count == 0) {
-                error = FIMO_EPERM;
-                ERROR_(ctx, error, "namespace is still in use, module='%s', ns='%s'", info->info.name, ns->name)
-                goto rollback_ns;
+        fimo_internal_ed25519_verify_update(&verifier, buffer, (FimoUSize)read_len);
+#if __linux__
+        for (ssize_t written = 0; written < read_len;) {
+            const ssize_t write_len = write(binary->fd, buffer + written, (size_t)(read_len - written));
+            if (write_len < 0) {
+                if (errno == EINTR) {
+                    continue;
+                }
+                error = FIMO_RESULT_FROM_ERROR_CODE(fimo_error_code_from_errno(errno));
+                goto cleanup;
             }
+            written += write_len;
         }
+#endif
     }
 
-    module_ = hashmap_delete(ctx->modules, &(struct Module_){
-                                                   .name = info->info.name,
-                                           });
-    FIMO_ASSERT(module_)
-    module_free_((struct Module_ *)module_);
+    if (!fimo_internal_ed25519_verify_final(&verifier)) {
+        error = FIMO_EBADMSG;
+        goto cleanup;
+    }
 
-    void *data_;
-    error = fimo_graph_remove_node(ctx->dependency_graph, node, &data_);
-    FIMO_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
-    FIMO_ASSERT(data_)
+#if __linux__
+    if (fcntl(binary->fd, F_ADD_SEALS, F_SEAL_SEAL | F_SEAL_SHRINK | F_SEAL_GROW | F_SEAL_WRITE) != 0) {
+        error = FIMO_RESULT_FROM_ERROR_CODE(fimo_error_code_from_errno(errno));
+        goto cleanup;
+    }
+    static const char fd_directory[] = "/proc/self/fd/";
+#else
+    binary->fd = source;
+    if (lseek(binary->fd, 0, SEEK_SET) != 0) {
+        error = FIMO_RESULT_FROM_ERROR_CODE(fimo_error_code_from_errno(errno));
+        goto cleanup;
+    }
+    static const char fd_directory[] = "/dev/fd/";
+#endif
+
+    const FimoUSize load_path_len = sizeof(fd_directory) + 3 * sizeof(int);
+    binary->load_path = fimo_malloc(load_path_len, &error);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        goto cleanup;
+    }
+    snprintf(binary->load_path, load_path_len, "%s%d", fd_directory, binary->fd);
+#if __linux__
+    close(source);
+#endif
 
     return FIMO_EOK;
 
-rollback_ns:;
-    {
-        FimoUSize it = 0;
-        const struct ModuleInfoNamespace_ *ns = NULL;
-        while (module_info_next_ns_(info_inner, &it, &ns)) {
-            const FimoResult error_ = ctx_ns_acquire_(ctx, ns->name);
-            FIMO_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error_))
-        }
-    }
-    {
-        FimoUSize it = 0;
-        const struct ModuleInfoSymbol_ *symbol = NULL;
-        while (module_info_next_symbol_(info_inner, &it, &symbol)) {
-            const FimoResult error_ =
-                    ctx_insert_symbol_(ctx, symbol->name, symbol->ns, symbol->version, info->info.name);
-            FIMO_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error_))
-        }
+cleanup:
+    if (binary->fd != source) {
+        close(source);
     }
-
+    verified_binary_free_(binary);
     return error;
 }
+#endif
 
-static FimoResult ctx_link_module_(FimoInternalModuleContext *ctx, struct ModuleInfoInner_ *info_inner,
-                                   struct ModuleInfoInner_ *other_inner) {
-    FIMO_DEBUG_ASSERT(ctx && info_inner && other_inner && !module_info_is_detached_(info_inner))
-    const struct ModuleInfo_ *info = module_info_from_inner_(info_inner);
-    const struct ModuleInfo_ *other_info = module_info_from_inner_(other_inner);
+///////////////////////////////////////////////////////////////////////
+//// Pending Event
+///////////////////////////////////////////////////////////////////////
 
-    TRACE_(ctx, "module='%s', other='%s'", info->info.name, other_info->info.name)
-    if (module_info_is_detached_(other_inner)) {
-        ERROR_(ctx, FIMO_EINVAL, "module is not registered with the module subsystem, module='%s'",
-               other_info->info.name)
-        return FIMO_EINVAL;
-    }
-    if (module_info_get_dependency_(info_inner, other_info->info.name) != NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "modules are already linked, module='%s', other='%s'", info->info.name,
-               other_info->info.name)
-        return FIMO_EEXIST;
-    }
-    if (other_info->type == MODULE_TYPE_PSEUDO_) {
-        ERROR_(ctx, FIMO_EPERM, "can not link to a pseudo module, module='%s', other='%s'", info->info.name,
-               other_info->info.name)
-        return FIMO_EPERM;
-    }
+struct PendingEvent_ {
+    struct PendingEvent_ *next;
+    const FimoBusTopic *topic;
+    FimoUSize payload_size;
+    char payload[];
+};
 
-    const struct Module_ *inner_module = ctx_get_module_(ctx, info->info.name);
-    const struct Module_ *other_module = ctx_get_module_(ctx, other_info->info.name);
-    FIMO_DEBUG_ASSERT(inner_module)
-    FIMO_DEBUG_ASSERT(other_module)
+static FimoResult pending_event_new_(const FimoBusTopic *topic, const char *module, const char *description,
+                                     struct PendingEvent_ **element) {
+    FIMO_DEBUG_ASSERT(topic && module && element)
+    const FimoUSize module_len = strlen(module) + 1;
+    const FimoUSize description_len = description ? strlen(description) + 1 : 0;
 
-    bool would_introduce_cycle;
-    FimoResult error = fimo_graph_path_exists(ctx->dependency_graph, other_module->node, inner_module->node,
-                                              &would_introduce_cycle);
+    FimoResult error = FIMO_EOK;
+    struct PendingEvent_ *event = fimo_malloc(sizeof(*event) + module_len + description_len, &error);
     if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not determine if linking the modules would introduce a cycle")
         return error;
     }
+    event->next = NULL;
+    event->topic = topic;
+    event->payload_size = module_len + description_len;
+    memcpy(event->payload, module, module_len);
+    if (description) {
+        memcpy(event->payload + module_len, description, description_len);
+    }
 
-    FimoU64 edge;
-    error = fimo_graph_add_edge(ctx->dependency_graph, inner_module->node, other_module->node, NULL, NULL, &edge);
-    if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not add edge to the dependency graph")
+    *element = event;
+    return FIMO_EOK;
+}
+
+static void pending_event_free_(struct PendingEvent_ *element) {
+    FIMO_DEBUG_ASSERT(element)
+    fimo_free(element);
+}
+
+// Module context whose pending events are being published by the current thread.
+static _Thread_local FimoInternalModuleContext *PUBLISHING_EVENTS_ = NULL;
+
+///////////////////////////////////////////////////////////////////////
+//// Context
+///////////////////////////////////////////////////////////////////////
+
+static FimoResult ctx_init_(FimoInternalModuleContext *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    TRACE_SIMPLE_(ctx, "initializing the module context")
+    const int result = mtx_init(&ctx->mutex, mtx_plain);
+    if (result == thrd_error) {
+        FimoResult error = FIMO_RESULT_FROM_STRING("unknown error");
+        ERROR_SIMPLE_(ctx, error, "could not initialize mutex")
         return error;
     }
 
-    error = module_info_set_dependency_(info_inner, &other_info->info, false);
-    if (FIMO_RESULT_IS_ERROR(error)) {
-        void *edge_data;
-        const FimoResult error_ = fimo_graph_remove_edge(ctx->dependency_graph, edge, &edge_data);
-        FIMO_DEBUG_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error_))
-        (void)error_;
-        FIMO_DEBUG_ASSERT(edge_data == NULL)
-        ERROR_(ctx, error, "could not insert other into the module info dependency map, module='%s', dependency='%s'",
-               info->info.name, other_info->info.name)
+    if (mtx_init(&ctx->loaders_mutex, mtx_plain) == thrd_error) {
+        FimoResult error = FIMO_RESULT_FROM_STRING("unknown error");
+        ERROR_SIMPLE_(ctx, error, "could not initialize loaders mutex")
+        mtx_destroy(&ctx->mutex);
         return error;
     }
+    ctx->loaders = NULL;
 
-    return FIMO_EOK;
-}
-
-static FimoResult ctx_unlink_module_(FimoInternalModuleContext *ctx, struct ModuleInfoInner_ *info_inner,
-                                     struct ModuleInfoInner_ *other_inner) {
-    FIMO_DEBUG_ASSERT(ctx && info_inner && other_inner && !module_info_is_detached_(info_inner) &&
-                      !module_info_is_detached_(other_inner))
-    const struct ModuleInfo_ *info = module_info_from_inner_(info_inner);
-    const struct ModuleInfo_ *other_info = module_info_from_inner_(other_inner);
-
-    TRACE_(ctx, "module='%s', other='%s'", info->info.name, other_info->info.name)
-    const struct ModuleInfoDependency_ *dependency = module_info_get_dependency_(info_inner, other_info->info.name);
-    if (dependency == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "modules are not linked, module='%s', other='%s'", info->info.name,
-               other_info->info.name)
-        return FIMO_EEXIST;
+    if (mtx_init(&ctx->signatures_mutex, mtx_plain) == thrd_error) {
+        FimoResult error = FIMO_RESULT_FROM_STRING("unknown error");
+        ERROR_SIMPLE_(ctx, error, "could not initialize signatures mutex")
+        mtx_destroy(&ctx->loaders_mutex);
+        mtx_destroy(&ctx->mutex);
+        return error;
     }
-    if (dependency->is_static) {
-        ERROR_(ctx, FIMO_EPERM, "can not unlink static module links, module='%s', other='%s'", info->info.name,
-               other_info->info.name)
-        return FIMO_EPERM;
+    ctx->signature_policy = FIMO_MODULE_SIGNATURE_POLICY_OFF;
+    ctx->binary_loaded = false;
+    ctx->fault_policy = FIMO_MODULE_FAULT_POLICY_CONTINUE;
+
+    if (mtx_init(&ctx->events_mutex, mtx_plain) == thrd_error) {
+        FimoResult error = FIMO_RESULT_FROM_STRING("unknown error");
+        ERROR_SIMPLE_(ctx, error, "could not initialize events mutex")
+        mtx_destroy(&ctx->signatures_mutex);
+        mtx_destroy(&ctx->loaders_mutex);
+        mtx_destroy(&ctx->mutex);
+        return error;
     }
+    ctx->pending_events = NULL;
+    ctx->pending_events_tail = NULL;
 
-    const struct Module_ *ctx_module = ctx_get_module_(ctx, info->info.name);
-    const struct Module_ *other_ctx_module = ctx_get_module_(ctx, other_info->info.name);
-    FIMO_DEBUG_ASSERT(ctx_module)
-    FIMO_DEBUG_ASSERT(other_ctx_module)
+    FimoResult error;
+    ctx->symbols = hashmap_new_with_allocator(malloc_, realloc_, free_, sizeof(struct Symbol_), 0, 0, 0,
+                                              (HashFn_)symbol_hash_, (CmpFn_)symbol_cmp_, (FreeFn_)symbol_free_, NULL);
+    if (ctx->symbols == NULL) {
+        error = FIMO_ENOMEM;
+        ERROR_SIMPLE_(ctx, error, "could not initialize symbols map")
+        goto deinit_mtx;
+    }
 
-    FimoU64 edge;
-    bool contained;
-    FimoResult error =
-            fimo_graph_find_edge(ctx->dependency_graph, ctx_module->node, other_ctx_module->node, &edge, &contained);
-    FIMO_DEBUG_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
-    FIMO_DEBUG_ASSERT(contained)
-    (void)error;
+    ctx->modules = hashmap_new_with_allocator(malloc_, realloc_, free_, sizeof(struct Module_), 0, 0, 0,
+                                              (HashFn_)module_hash_, (CmpFn_)module_cmp_, (FreeFn_)module_free_, NULL);
+    if (ctx->modules == NULL) {
+        error = FIMO_ENOMEM;
+        ERROR_SIMPLE_(ctx, error, "could not initialize modules map")
+        goto deinit_symbols;
+    }
 
-    void *edge_data;
-    error = fimo_graph_remove_edge(ctx->dependency_graph, edge, &edge_data);
-    FIMO_DEBUG_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
-    FIMO_DEBUG_ASSERT(edge_data == NULL)
-    (void)error;
+    ctx->namespaces = hashmap_new_with_allocator(malloc_, realloc_, free_, sizeof(struct Namespace_), 0, 0, 0,
+                                                 (HashFn_)namespace_hash_, (CmpFn_)namespace_cmp_,
+                                                 (FreeFn_)namespace_free_, NULL);
+    if (ctx->namespaces == NULL) {
+        error = FIMO_ENOMEM;
+        ERROR_SIMPLE_(ctx, error, "could not initialize namespaces map")
+        goto deinit_modules;
+    }
 
-    module_info_delete_dependency_(info_inner, other_info->info.name);
+    ctx->param_overrides = hashmap_new_with_allocator(malloc_, realloc_, free_, sizeof(struct ParamOverride_), 0, 0, 0,
+                                                      (HashFn_)param_override_hash_, (CmpFn_)param_override_cmp_,
+                                                      (FreeFn_)param_override_free_, NULL);
+    if (ctx->param_overrides == NULL) {
+        error = FIMO_ENOMEM;
+        ERROR_SIMPLE_(ctx, error, "could not initialize parameter overrides map")
+        goto deinit_namespaces;
+    }
 
-    return FIMO_EOK;
-}
+    ctx->feature_overrides = hashmap_new_with_allocator(
+            malloc_, realloc_, free_, sizeof(struct FeatureOverride_), 0, 0, 0, (HashFn_)feature_override_hash_,
+            (CmpFn_)feature_override_cmp_, (FreeFn_)feature_override_free_, NULL);
+    if (ctx->feature_overrides == NULL) {
+        error = FIMO_ENOMEM;
+        ERROR_SIMPLE_(ctx, error, "could not initialize feature overrides map")
+        goto deinit_param_overrides;
+    }
 
-static bool ctx_can_remove_module_(FimoInternalModuleContext *ctx, struct ModuleInfoInner_ *info_inner) {
-    FIMO_DEBUG_ASSERT(ctx && info_inner && !module_info_is_detached_(info_inner));
-    const struct ModuleInfo_ *info = module_info_from_inner_(info_inner);
-    TRACE_(ctx, "module='%s'", info->info.name)
+    ctx->trusted_keys =
+            hashmap_new_with_allocator(malloc_, realloc_, free_, sizeof(struct TrustedKey_), 0, 0, 0,
+                                       (HashFn_)trusted_key_hash_, (CmpFn_)trusted_key_cmp_, NULL, NULL);
+    if (ctx->trusted_keys == NULL) {
+        error = FIMO_ENOMEM;
+        ERROR_SIMPLE_(ctx, error, "could not initialize trusted keys map")
+        goto deinit_feature_overrides;
+    }
 
-    // Check if the module info has been marked as unloadable
-    if (!module_info_can_unload_(info_inner)) {
-        return false;
+    ctx->pinned_keys = hashmap_new_with_allocator(malloc_, realloc_, free_, sizeof(struct PinnedKey_), 0, 0, 0,
+                                                  (HashFn_)pinned_key_hash_, (CmpFn_)pinned_key_cmp_,
+                                                  (FreeFn_)pinned_key_free_, NULL);
+    if (ctx->pinned_keys == NULL) {
+        error = FIMO_ENOMEM;
+        ERROR_SIMPLE_(ctx, error, "could not initialize pinned keys map")
+        goto deinit_trusted_keys;
     }
 
-    // Check that no symbols are in use.
-    {
-        FimoUSize it = 0;
-        const struct ModuleInfoSymbol_ *symbol;
-        while (module_info_next_symbol_(info_inner, &it, &symbol)) {
-            if (FIMO_MODULE_SYMBOL_IS_LOCKED(&symbol->symbol)) {
-                return false;
-            }
-        }
+    error = fimo_graph_new(sizeof(const FimoModule *), 0, NULL, NULL, &ctx->dependency_graph);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_SIMPLE_(ctx, error, "could not initialize dependency graph")
+        goto deinit_pinned_keys;
     }
 
-    // Check that there are no dependencies left.
-    const struct Module_ *module_ = ctx_get_module_(ctx, info->info.name);
-    FIMO_DEBUG_ASSERT(module_)
-    FimoUSize neighbors;
-    FimoResult error = fimo_graph_neighbors_count(ctx->dependency_graph, module_->node, true, &neighbors);
-    FIMO_DEBUG_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
-    (void)error;
+    ctx->is_loading = false;
 
-    return neighbors == 0;
+    return FIMO_EOK;
+
+deinit_pinned_keys:
+    hashmap_free(ctx->pinned_keys);
+    ctx->pinned_keys = NULL;
+deinit_trusted_keys:
+    hashmap_free(ctx->trusted_keys);
+    ctx->trusted_keys = NULL;
+deinit_feature_overrides:
+    hashmap_free(ctx->feature_overrides);
+    ctx->feature_overrides = NULL;
+deinit_param_overrides:
+    hashmap_free(ctx->param_overrides);
+    ctx->param_overrides = NULL;
+deinit_namespaces:
+    hashmap_free(ctx->namespaces);
+    ctx->namespaces = NULL;
+deinit_modules:
+    hashmap_free(ctx->modules);
+    ctx->modules = NULL;
+deinit_symbols:
+    hashmap_free(ctx->symbols);
+    ctx->symbols = NULL;
+deinit_mtx:
+    mtx_destroy(&ctx->events_mutex);
+    mtx_destroy(&ctx->signatures_mutex);
+    mtx_destroy(&ctx->loaders_mutex);
+    mtx_destroy(&ctx->mutex);
+    return error;
 }
 
-static FimoResult ctx_cleanup_loose_modules(FimoInternalModuleContext *ctx) {
+static void ctx_deinit_(FimoInternalModuleContext *ctx) {
     FIMO_DEBUG_ASSERT(ctx)
-    TRACE_SIMPLE_(ctx, "cleaning up loose modules")
+    TRACE_SIMPLE_(ctx, "deinitializing the module context")
 
-    bool has_next;
-    FimoGraphExternals *iter;
-    FimoResult error = fimo_graph_externals_new(ctx->dependency_graph, false, &iter, &has_next);
-    if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not construct externals iterator")
-        return error;
-    }
+    // Since the context is being destroyed there must be no one holding a reference
+    // to the main context. As each module implicitly holds a reference to the context,
+    // this must also mean that no modules are loaded.
+    FIMO_ASSERT(hashmap_count(ctx->symbols) == 0);
+    FIMO_ASSERT(hashmap_count(ctx->modules) == 0);
+    FIMO_ASSERT(hashmap_count(ctx->namespaces) == 0);
+    FIMO_ASSERT(fimo_graph_node_count(ctx->dependency_graph) == 0);
+    FIMO_ASSERT_FALSE(ctx->is_loading);
 
-    while (has_next) {
-        FimoU64 node;
-        const FimoModule **module_ptr;
-        error = fimo_graph_externals_item(iter, &node, (const void **)&module_ptr);
-        FIMO_DEBUG_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
-        FIMO_DEBUG_ASSERT(module_ptr)
-        const FimoModule *module = *module_ptr;
-        const struct ModuleInfo_ *info = module_info_from_module_(module);
-        if (info->type != MODULE_TYPE_REGULAR_) {
-            error = fimo_graph_externals_next(iter, &has_next);
-            FIMO_DEBUG_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
-            continue;
-        }
-        struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
-
-        if (!ctx_can_remove_module_(ctx, info_inner)) {
-            error = fimo_graph_externals_next(iter, &has_next);
-            FIMO_DEBUG_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
-            continue;
-        }
-
-        error = ctx_remove_module_(ctx, info_inner);
-        if (FIMO_RESULT_IS_ERROR(error)) {
-            module_info_unlock_(info_inner);
-            ERROR_(ctx, error, "could not remove module, module='%s'", module->module_info->name)
-            fimo_graph_externals_free(iter);
-            return error;
-        }
-        fi_module_free_(info_inner, NULL);
+    fimo_graph_free(ctx->dependency_graph);
+    hashmap_free(ctx->pinned_keys);
+    hashmap_free(ctx->trusted_keys);
+    hashmap_free(ctx->feature_overrides);
+    hashmap_free(ctx->param_overrides);
+    hashmap_free(ctx->namespaces);
+    hashmap_free(ctx->modules);
+    hashmap_free(ctx->symbols);
 
-        // Rebuild the iterator since we modified the dependency graph
-        fimo_graph_externals_free(iter);
-        error = fimo_graph_externals_new(ctx->dependency_graph, false, &iter, &has_next);
-        if (FIMO_RESULT_IS_ERROR(error)) {
-            ERROR_SIMPLE_(ctx, error, "could not construct externals iterator")
-            return error;
-        }
+    while (ctx->loaders) {
+        struct ModuleLoader_ *loader = ctx->loaders;
+        ctx->loaders = loader->next;
+        module_loader_release_(loader);
     }
-    fimo_graph_externals_free(iter);
-
-    return FIMO_EOK;
+    while (ctx->pending_events) {
+        struct PendingEvent_ *event = ctx->pending_events;
+        ctx->pending_events = event->next;
+        pending_event_free_(event);
+    }
+    ctx->pending_events_tail = NULL;
+    mtx_destroy(&ctx->events_mutex);
+    mtx_destroy(&ctx->signatures_mutex);
+    mtx_destroy(&ctx->loaders_mutex);
+    mtx_destroy(&ctx->mutex);
 }
 
-static const struct Module_ *ctx_get_module_(FimoInternalModuleContext *ctx, const char *name) {
-    FIMO_DEBUG_ASSERT(ctx && name)
-    TRACE_(ctx, "name='%s'", name)
-    return hashmap_get(ctx->modules, &(struct Module_){.name = name});
+static void ctx_loaders_lock_(FimoInternalModuleContext *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    const int result = mtx_lock(&ctx->loaders_mutex);
+    FIMO_ASSERT(result == thrd_success)
 }
 
-static const struct Symbol_ *ctx_get_symbol_(FimoInternalModuleContext *ctx, const char *name, const char *ns) {
-    FIMO_DEBUG_ASSERT(ctx && name && ns)
-    TRACE_(ctx, "name='%s', symbol='%s'", name, ns)
-    return hashmap_get(ctx->symbols, &(struct Symbol_){.name = name, .ns = ns});
+static void ctx_loaders_unlock_(FimoInternalModuleContext *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    const int result = mtx_unlock(&ctx->loaders_mutex);
+    FIMO_ASSERT(result == thrd_success)
 }
 
-static const struct Symbol_ *ctx_get_symbol_compatible_(FimoInternalModuleContext *ctx, const char *name,
-                                                        const char *ns, const FimoVersion version) {
-    FIMO_DEBUG_ASSERT(ctx && name && ns)
-    TRACE_(ctx, "name='%s', symbol='%s'", name, ns)
-    const struct Symbol_ *sym = ctx_get_symbol_(ctx, name, ns);
-    if (sym == NULL || !fimo_version_compatible(&sym->version, &version)) {
-        return NULL;
+static struct ModuleLoader_ *ctx_find_loader_(FimoInternalModuleContext *ctx, const char *path) {
+    FIMO_DEBUG_ASSERT(ctx && path)
+    struct ModuleLoader_ *loader = NULL;
+    ctx_loaders_lock_(ctx);
+    for (struct ModuleLoader_ *it = ctx->loaders; it; it = it->next) {
+        if (module_loader_matches_(it, path)) {
+            module_loader_acquire_(it);
+            loader = it;
+            break;
+        }
     }
-    return sym;
+    ctx_loaders_unlock_(ctx);
+    return loader;
 }
 
-static const struct Namespace_ *ctx_get_ns_(FimoInternalModuleContext *ctx, const char *name) {
-    FIMO_DEBUG_ASSERT(ctx && name)
-    TRACE_(ctx, "name='%s'", name)
-    return hashmap_get(ctx->namespaces, &(struct Namespace_){.name = name});
+static void ctx_signatures_lock_(FimoInternalModuleContext *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    const int result = mtx_lock(&ctx->signatures_mutex);
+    FIMO_ASSERT(result == thrd_success)
 }
 
-static FimoResult ctx_ns_allocate_if_not_found_(FimoInternalModuleContext *ctx, const char *name) {
-    FIMO_DEBUG_ASSERT(ctx && name)
-    TRACE_(ctx, "name='%s'", name)
-    if (strcmp(name, GLOBAL_NS) == 0) {
+static void ctx_signatures_unlock_(FimoInternalModuleContext *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    const int result = mtx_unlock(&ctx->signatures_mutex);
+    FIMO_ASSERT(result == thrd_success)
+}
+
+// Checks whether the signer of a binary is accepted by the policy, pinning the key on first use.
+//
+// The keys are pinned to the canonical path of the binary.
+static FimoResult ctx_accept_signer_(FimoInternalModuleContext *ctx, const char *canonical_path, const FimoU8 *key) {
+    FIMO_DEBUG_ASSERT(ctx && canonical_path && key)
+    if (ctx->signature_policy == FIMO_MODULE_SIGNATURE_POLICY_OFF) {
         return FIMO_EOK;
     }
 
-    if (ctx_get_ns_(ctx, name)) {
+    struct TrustedKey_ trusted_key;
+    memcpy(trusted_key.key, key, sizeof(trusted_key.key));
+    if (hashmap_get(ctx->trusted_keys, &trusted_key) != NULL) {
         return FIMO_EOK;
     }
+    if (ctx->signature_policy != FIMO_MODULE_SIGNATURE_POLICY_TRUST_ON_FIRST_USE) {
+        return FIMO_EKEYREJECTED;
+    }
 
-    struct Namespace_ ns;
-    const FimoResult error = namespace_new_(name, &ns);
+    const struct PinnedKey_ *pinned = hashmap_get(ctx->pinned_keys, &(struct PinnedKey_){.path = canonical_path});
+    if (pinned) {
+        return memcmp(pinned->key, key, sizeof(pinned->key)) == 0 ? FIMO_EOK : FIMO_EKEYREJECTED;
+    }
+
+    struct PinnedKey_ element;
+    FimoResult error = pinned_key_new_(canonical_path, key, &element);
     if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_(ctx, error, "could not create namespace, ns='%s'", name)
         return error;
     }
-
-    hashmap_set(ctx->namespaces, &ns);
-    if (hashmap_oom(ctx->namespaces)) {
-        namespace_free_(&ns);
-        ERROR_(ctx, FIMO_ENOMEM, "could not insert namespace into the context, ns='%s'", name)
+    hashmap_set(ctx->pinned_keys, &element);
+    if (hashmap_oom(ctx->pinned_keys)) {
+        pinned_key_free_(&element);
         return FIMO_ENOMEM;
     }
 
     return FIMO_EOK;
 }
 
-static void ctx_ns_free_if_empty_(FimoInternalModuleContext *ctx, const char *name) {
-    FIMO_DEBUG_ASSERT(ctx && name)
-    TRACE_(ctx, "name='%s'", name)
-    if (strcmp(name, GLOBAL_NS) == 0) {
-        return;
-    }
-
-    const struct Namespace_ *ns = ctx_get_ns_(ctx, name);
-    FIMO_DEBUG_ASSERT(ns)
-    if (ns->reference_count == 0 && ns->symbol_count == 0) {
-        ns = hashmap_delete(ctx->namespaces, ns);
-        namespace_free_((struct Namespace_ *)ns);
+// Verifies the signature of a binary.
+//
+// On success, the binary must be loaded from `binary->load_path`, if it is set. Loading a
+// binary forbids relaxing the signature policy afterwards.
+static FimoResult ctx_verify_binary_(FimoInternalModuleContext *ctx, const char *path,
+                                     struct VerifiedBinary_ *binary) {
+    FIMO_DEBUG_ASSERT(ctx && path && binary)
+#if _WIN32
+    *binary = (struct VerifiedBinary_){.file = INVALID_HANDLE_VALUE};
+#else
+    *binary = (struct VerifiedBinary_){.fd = -1};
+#endif
+    ctx_signatures_lock_(ctx);
+    const FimoModuleSignaturePolicy policy = ctx->signature_policy;
+    ctx->binary_loaded |= policy == FIMO_MODULE_SIGNATURE_POLICY_OFF;
+    ctx_signatures_unlock_(ctx);
+    if (policy == FIMO_MODULE_SIGNATURE_POLICY_OFF) {
+        return FIMO_EOK;
     }
-}
 
-static FimoResult ctx_ns_acquire_(FimoInternalModuleContext *ctx, const char *name) {
-    FIMO_DEBUG_ASSERT(ctx && name)
-    TRACE_(ctx, "name='%s'", name)
-    if (strcmp(name, GLOBAL_NS) == 0) {
-        return FIMO_EOK;
+    TRACE_(ctx, "path='%s'", path)
+    FimoU8 signature_file[FIMO_MODULE_SIGNATURE_FILE_SIZE];
+    FimoResult error = signature_read_(path, signature_file);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_(ctx, error, "could not read the signature of the binary, path='%s'", path)
+        goto verification_failed;
     }
 
-    struct Namespace_ *ns = (struct Namespace_ *)ctx_get_ns_(ctx, name);
-    if (ns == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "namespace not found, ns='%s'", name)
-        return FIMO_EINVAL;
+    // The binary is read without holding the lock, as it may be large.
+    error = verified_binary_new_(path, signature_file, binary);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_(ctx, error, "invalid signature, path='%s'", path)
+        goto verification_failed;
     }
 
-    const FimoIntOptionUSize x = fimo_usize_checked_add(ns->reference_count, 1);
-    if (!x.has_value) {
-        ERROR_(ctx, FIMO_ERANGE, "namespace reference count overflow, ns='%s'", name)
-        return FIMO_ERANGE;
+    ctx_signatures_lock_(ctx);
+    error = ctx_accept_signer_(ctx, binary->canonical_path, signature_file);
+    ctx->binary_loaded |= !FIMO_RESULT_IS_ERROR(error);
+    ctx_signatures_unlock_(ctx);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_(ctx, error, "the signer of the binary is not trusted, path='%s'", path)
+        verified_binary_free_(binary);
+        goto verification_failed;
     }
 
-    ns->reference_count = x.data.value;
     return FIMO_EOK;
+
+verification_failed:
+    ctx_publish_module_event_(ctx, &FIMO_BUS_TOPIC_MODULE_VERIFICATION_FAILED, path);
+    return error;
 }
 
-static void ctx_ns_release_(FimoInternalModuleContext *ctx, const char *name) {
-    FIMO_DEBUG_ASSERT(ctx && name)
-    TRACE_(ctx, "name='%s'", name)
-    if (strcmp(name, GLOBAL_NS) == 0) {
-        return;
+static FimoResult ctx_lock_(FimoInternalModuleContext *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    TRACE_SIMPLE_(ctx, "")
+    if (mtx_lock(&ctx->mutex) == thrd_error) {
+        FimoResult error = FIMO_RESULT_FROM_STRING("unknown error");
+        ERROR_SIMPLE_(ctx, error, "could not lock the context")
+        return error;
     }
+    return FIMO_EOK;
+}
 
-    struct Namespace_ *ns = (struct Namespace_ *)ctx_get_ns_(ctx, name);
-    FIMO_DEBUG_ASSERT(ns)
-    FIMO_DEBUG_ASSERT(ns->reference_count != 0)
-    ns->reference_count--;
-
-    ctx_ns_free_if_empty_(ctx, name);
+static FimoResult ctx_unlock_(FimoInternalModuleContext *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    TRACE_SIMPLE_(ctx, "")
+    ctx_publish_pending_events_(ctx);
+    if (mtx_unlock(&ctx->mutex) == thrd_error) {
+        FimoResult error = FIMO_RESULT_FROM_STRING("unknown error");
+        ERROR_SIMPLE_(ctx, error, "could not unlock the context")
+        return error;
+    }
+    return FIMO_EOK;
 }
 
-static FimoResult ctx_insert_symbol_(FimoInternalModuleContext *ctx, const char *name, const char *ns,
-                                     const FimoVersion version, const char *module) {
-    FIMO_DEBUG_ASSERT(ctx && name && ns && module)
-    TRACE_(ctx, "name='%s', ns='%s', module='%s'", name, ns, module)
-    if (ctx_get_symbol_(ctx, name, ns)) {
-        ERROR_(ctx, FIMO_EINVAL, "symbol already exists, name='%s', ns='%s'", name, ns)
+static FimoResult ctx_add_module_(FimoInternalModuleContext *ctx, struct ModuleInfoInner_ *info_inner) {
+    FIMO_DEBUG_ASSERT(ctx && info_inner && !module_info_is_detached_(info_inner))
+    const struct ModuleInfo_ *info = module_info_from_inner_(info_inner);
+
+    TRACE_(ctx, "module='%s'", info->info.name)
+    if (hashmap_get(ctx->modules, &(struct Module_){.name = info->info.name}) != NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "module already exists, module='%s'", info->info.name)
         return FIMO_EINVAL;
     }
 
-    struct Symbol_ sym;
-    FimoResult error = symbol_new_(name, ns, version, module, &sym);
+    FimoU64 node;
+    FimoResult error = fimo_graph_add_node(ctx->dependency_graph, &info_inner->module, &node);
     if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not create new symbol")
+        ERROR_SIMPLE_(ctx, error, "could not add the module to the dependency graph")
         return error;
     }
 
-    hashmap_set(ctx->symbols, &sym);
-    if (hashmap_oom(ctx->symbols)) {
-        symbol_free_(&sym);
-        ERROR_SIMPLE_(ctx, FIMO_ENOMEM, "could not insert symbol into the context")
-        return FIMO_ENOMEM;
+    // Check for no duplicate symbols.
+    {
+        FimoUSize it = 0;
+        const struct ModuleInfoSymbol_ *symbol = NULL;
+        while (module_info_next_symbol_(info_inner, &it, &symbol)) {
+            const struct Symbol_ *s = ctx_get_symbol_(ctx, symbol->name, symbol->ns);
+            if (s) {
+                error = FIMO_EINVAL;
+                ERROR_(ctx, error, "symbol already exists, module='%s', symbol='%s', ns='%s'", info->info.name,
+                       symbol->name, symbol->ns)
+                goto remove_node;
+            }
+        }
     }
-
-    if (strcmp(ns, GLOBAL_NS) != 0) {
-        struct Namespace_ *ns_ = (struct Namespace_ *)ctx_get_ns_(ctx, ns);
-        if (ns_ == NULL) {
-            error = FIMO_EINVAL;
-            ERROR_(ctx, error, "missing namespace, ns='%s'", ns)
-            goto remove_symbol;
+    // Check that all imported namespaces exist.
+    {
+        FimoUSize it = 0;
+        const struct ModuleInfoNamespace_ *ns = NULL;
+        while (module_info_next_ns_(info_inner, &it, &ns)) {
+            const struct Namespace_ *n = ctx_get_ns_(ctx, ns->name);
+            if (n == NULL) {
+                error = FIMO_EINVAL;
+                ERROR_(ctx, error, "namespace does not exist, module='%s', ns='%s'", info->info.name, ns->name)
+                goto remove_node;
+            }
         }
-
-        const FimoIntOptionUSize x = fimo_usize_checked_add(ns_->symbol_count, 1);
-        if (!x.has_value) {
-            error = FIMO_ERANGE;
-            ERROR_(ctx, error, "namespace symbol count overflow, ns='%s'", ns)
-            goto remove_symbol;
+    }
+    // Acquire all imported namespaces.
+    {
+        FimoUSize it = 0;
+        const struct ModuleInfoNamespace_ *ns = NULL;
+        while (module_info_next_ns_(info_inner, &it, &ns)) {
+            error = ctx_ns_acquire_(ctx, ns->name);
+            FIMO_DEBUG_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
+        }
+    }
+    // Check that all dependencies are met and correct.
+    {
+        FimoUSize it = 0;
+        const struct ModuleInfoDependency_ *dependency = NULL;
+        while (module_info_next_dependency_(info_inner, &it, &dependency)) {
+            const struct Module_ *dep_mod = ctx_get_module_(ctx, dependency->name);
+            if (dep_mod == NULL) {
+                error = FIMO_EINVAL;
+                ERROR_(ctx, error, "dependency not found, module='%s', dependency='%s'", info->info.name,
+                       dependency->name)
+                goto release_namespaces;
+            }
+            FIMO_ASSERT(dependency->info == dep_mod->module->module_info)
+            FimoU64 edge;
+            error = fimo_graph_add_edge(ctx->dependency_graph, node, dep_mod->node, NULL, NULL, &edge);
+            if (FIMO_RESULT_IS_ERROR(error)) {
+                ERROR_SIMPLE_(ctx, error, "could not add edge to the dependency graph")
+                goto release_namespaces;
+            }
         }
-
-        ns_->symbol_count = x.data.value;
     }
 
-    return FIMO_EOK;
-
-remove_symbol: {
-    hashmap_delete(ctx->symbols, &sym);
-    symbol_free_(&sym);
-}
-
-    return error;
-}
-
-static void ctx_remove_symbol_(FimoInternalModuleContext *ctx, const char *name, const char *ns) {
-    FIMO_DEBUG_ASSERT(ctx && name && ns)
-    TRACE_(ctx, "name='%s', ns='%s'", name, ns)
-    struct Symbol_ *sym = (struct Symbol_ *)ctx_get_symbol_(ctx, name, ns);
-    FIMO_DEBUG_ASSERT(sym)
-
-    sym = (struct Symbol_ *)hashmap_delete(ctx->symbols, sym);
-    symbol_free_(sym);
-
-    if (strcmp(ns, GLOBAL_NS) != 0) {
-        struct Namespace_ *ns_ = (struct Namespace_ *)ctx_get_ns_(ctx, ns);
-        FIMO_ASSERT(ns_ && ns_->symbol_count != 0)
-        ns_->symbol_count--;
-        ctx_ns_free_if_empty_(ctx, ns);
-    }
-}
-
-static FimoResult ctx_load_set(FimoInternalModuleContext *ctx, FimoModuleLoadingSet *set) {
-    FIMO_DEBUG_ASSERT(ctx && set)
-    if (ctx->is_loading) {
-        ERROR_SIMPLE_(ctx, FIMO_EPERM, "a set is already being loaded")
-        return FIMO_EPERM;
+    // Check the modifiers.
+    if (info_inner->export) {
+        for (FimoISize i = 0; i < (FimoISize)info_inner->export->modifiers_count; i++) {
+            const FimoModuleExportModifier *modifier = &info_inner->export->modifiers[i];
+            if (modifier->key != FIMO_MODULE_EXPORT_MODIFIER_KEY_DEPENDENCY) {
+                continue;
+            }
+            const FimoModuleInfo *dependency = modifier->value;
+            FIMO_DEBUG_ASSERT(dependency && dependency->name);
+            const struct Module_ *dep_mod = ctx_get_module_(ctx, dependency->name);
+            if (dep_mod == NULL) {
+                error = FIMO_EINVAL;
+                ERROR_(ctx, error, "dependency not found, module='%s', dependency='%s'", info->info.name,
+                       dependency->name)
+                goto release_namespaces;
+            }
+            FIMO_ASSERT(dependency == dep_mod->module->module_info);
+            FimoU64 edge;
+            error = fimo_graph_add_edge(ctx->dependency_graph, node, dep_mod->node, NULL, NULL, &edge);
+            if (FIMO_RESULT_IS_ERROR(error)) {
+                ERROR_SIMPLE_(ctx, error, "could not add edge to the dependency graph")
+                goto release_namespaces;
+            }
+        }
     }
-    FIMO_DEBUG_ASSERT(!set->is_loading)
-    ctx->is_loading = true;
-    set->is_loading = true;
 
-    struct LoadingSetLoadingInfo_ loading_info;
-    set->should_recreate_map = false;
-    FimoResult error = loading_set_create_info_(set, ctx, &loading_info);
+    // Check that the dependency graph is cycle free
+    bool is_cyclic;
+    error = fimo_graph_is_cyclic(ctx->dependency_graph, &is_cyclic);
     if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not construct load order")
-        goto on_critical_error;
+        ERROR_SIMPLE_(ctx, error, "could not determine if the dependency graph is cycle free")
+        goto release_namespaces;
+    }
+    if (is_cyclic) {
+        error = FIMO_EINVAL;
+        ERROR_SIMPLE_(ctx, error, "adding the module would introduce a cyclic depdendency")
+        goto release_namespaces;
     }
 
-    while (!loading_set_loading_info_is_empty_(&loading_info)) {
-        if (set->should_recreate_map) {
-            set->should_recreate_map = false;
-            loading_set_loading_info_free_(&loading_info);
-            error = loading_set_create_info_(set, ctx, &loading_info);
+    // Allocate all exported namespaces.
+    {
+        FimoUSize it = 0;
+        const struct ModuleInfoSymbol_ *symbol = NULL;
+        while (module_info_next_symbol_(info_inner, &it, &symbol)) {
+            error = ctx_ns_allocate_if_not_found_(ctx, symbol->ns);
             if (FIMO_RESULT_IS_ERROR(error)) {
-                ERROR_SIMPLE_(ctx, error, "could not construct load order")
-                goto on_critical_error;
-            }
-        }
-
-        struct LoadingSetModule_ *module = loading_set_loading_info_pop_(&loading_info);
-
-        // Recheck that all dependencies could be loaded.
-        for (FimoISize i = 0; i < (FimoISize)module->export->symbol_imports_count; i++) {
-            const FimoModuleSymbolImport *import = &module->export->symbol_imports[i];
-            const struct LoadingSetSymbol_ *symbol =
-                    loading_set_get_symbol_(set, import->name, import->ns, import->version);
-            // Skip the module if a dependency could not be loaded.
-            if (symbol != NULL) {
-                const struct LoadingSetModule_ *exporter = loading_set_get_module_(set, symbol->module);
-                FIMO_DEBUG_ASSERT(exporter)
-                if (exporter->status == MODULE_LOAD_STATUS_ERROR_) {
-                    WARN_(ctx,
-                          "module can not be loaded as there was an error during the construction of a module "
-                          "it depends on, module='%s', dependency='%s'",
-                          module->name, exporter->name)
-                    goto skip_module;
-                }
+                ERROR_(ctx, error, "failed to allocate ns, module='%s', ns='%s'", info->info.name, symbol->ns)
+                goto remove_allocated_ns;
             }
         }
-
-        // Check that the explicit dependencies exist.
-        for (FimoISize i = 0; i < (FimoISize)module->export->modifiers_count; i++) {
-            const FimoModuleExportModifier *modifier = &module->export->modifiers[i];
-            if (modifier->key != FIMO_MODULE_EXPORT_MODIFIER_KEY_DEPENDENCY) {
-                continue;
-            }
-            const FimoModuleInfo *dependency = modifier->value;
-            FIMO_DEBUG_ASSERT(dependency && dependency->name)
-            if (ctx_get_module_(ctx, dependency->name) == NULL) {
-                WARN_(ctx,
-                      "module can not be loaded as the module specified by the dependency "
-                      "modifier does not exist, module='%s', dependency='%s'",
-                      module->name, dependency->name)
-                goto skip_module;
+    }
+    // Export all symbols.
+    {
+        FimoUSize it = 0;
+        const struct ModuleInfoSymbol_ *symbol = NULL;
+        while (module_info_next_symbol_(info_inner, &it, &symbol)) {
+            error = ctx_insert_symbol_(ctx, symbol->name, symbol->ns, symbol->version, info->info.name);
+            if (FIMO_RESULT_IS_ERROR(error)) {
+                ERROR_(ctx, error, "failed to allocate export symbol, module='%s', symbol='%s', ns='%s'",
+                       info->info.name, symbol->name, symbol->ns)
+                goto remove_symbol_export;
             }
         }
-
-        // Construct the module.
-        FimoModule *constructed;
-        error = fi_module_new_from_export(ctx, set, module->export, module->handle, &constructed);
+    }
+    // Insert the module.
+    {
+        struct Module_ module_;
+        error = module_new_(info_inner->module, node, &module_);
         if (FIMO_RESULT_IS_ERROR(error)) {
-            FimoResultString error_name = fimo_result_error_name(error);
-            FimoResultString error_description = fimo_result_error_description(error);
-            WARN_(ctx, "skipping module due to construction error, module='%s', error='%s:%s'", module->name,
-                  error_name.str, error_description.str)
-            fimo_result_string_release(error_name);
-            fimo_result_string_release(error_description);
-            goto skip_module;
+            ERROR_(ctx, error, "failed to allocate module, module='%s'", info->info.name)
+            goto remove_symbol_export;
         }
 
-        // Register it with the backend.
-        const struct ModuleInfo_ *constructed_info = module_info_from_module_(constructed);
-        struct ModuleInfoInner_ *constructed_info_inner = module_info_lock_(constructed_info);
-        error = ctx_add_module_(ctx, constructed_info_inner);
-        if (FIMO_RESULT_IS_ERROR(error)) {
-            ERROR_SIMPLE_(ctx, error, "could not register module with the backend")
-            fi_module_free_(constructed_info_inner, NULL);
-            goto free_loding_info;
+        hashmap_set(ctx->modules, &module_);
+        if (hashmap_oom(ctx->modules)) {
+            module_free_(&module_);
+            error = FIMO_ENOMEM;
+            ERROR_(ctx, error, "failed to insert module, module='%s'", info->info.name)
+            goto remove_symbol_export;
         }
-        module_info_unlock_(constructed_info_inner);
-
-        // Signal loading success.
-        loading_set_module_signal_success(module, constructed->module_info);
-        continue;
-
-    skip_module:
-        loading_set_module_signal_error_(module);
     }
 
-    set->is_loading = false;
-    ctx->is_loading = false;
+    ctx_queue_module_event_(ctx, &FIMO_BUS_TOPIC_MODULE_LOADED, info->info.name, NULL);
     return FIMO_EOK;
 
-free_loding_info:
-    loading_set_loading_info_free_(&loading_info);
-on_critical_error:
-    set->is_loading = false;
-    ctx->is_loading = false;
+remove_symbol_export: {
+    FimoUSize it = 0;
+    const struct ModuleInfoSymbol_ *symbol = NULL;
+    while (module_info_next_symbol_(info_inner, &it, &symbol)) {
+        if (ctx_get_symbol_(ctx, symbol->name, symbol->ns)) {
+            ctx_remove_symbol_(ctx, symbol->name, symbol->ns);
+        }
+    }
+}
+remove_allocated_ns: {
+    FimoUSize it = 0;
+    const struct ModuleInfoSymbol_ *symbol = NULL;
+    while (module_info_next_symbol_(info_inner, &it, &symbol)) {
+        ctx_ns_free_if_empty_(ctx, symbol->name);
+    }
+}
+release_namespaces: {
+    FimoUSize it = 0;
+    const struct ModuleInfoNamespace_ *ns = NULL;
+    while (module_info_next_ns_(info_inner, &it, &ns)) {
+        ctx_ns_release_(ctx, ns->name);
+    }
+}
+remove_node: {
+    void *data;
+    const FimoResult error_ = fimo_graph_remove_node(ctx->dependency_graph, node, &data);
+    FIMO_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error_))
+    FIMO_ASSERT(data)
+}
+
     return error;
 }
 
-///////////////////////////////////////////////////////////////////////
-//// Fimo Module
-///////////////////////////////////////////////////////////////////////
+static FimoResult ctx_remove_module_(FimoInternalModuleContext *ctx, struct ModuleInfoInner_ *info_inner) {
+    FIMO_DEBUG_ASSERT(ctx && info_inner && !module_info_is_detached_(info_inner))
+    const struct ModuleInfo_ *info = module_info_from_inner_(info_inner);
 
-static FimoResult fi_module_new_pseudo_(FimoInternalModuleContext *ctx, const char *name, FimoModule **element) {
-    FIMO_DEBUG_ASSERT(ctx && name && element)
-    TRACE_(ctx, "name='%s', element='%p'", name, (void *)element)
-    struct ModuleHandle_ *handle;
-    void (*iterator)(bool (*)(const FimoModuleExport *, void *), void *) = fimo_impl_module_export_iterator;
-    FimoResult error = module_handle_new_local_(fimo_impl_module_export_iterator, *(const void **)&iterator, &handle);
-    if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not construct module handle")
-        return error;
+    TRACE_(ctx, "module='%s'", info->info.name)
+    if (!ctx_can_remove_module_(ctx, info_inner)) {
+        ERROR_(ctx, FIMO_EPERM, "the module can not be removed, module='%s'", info->info.name)
+        return FIMO_EPERM;
     }
 
-    struct ModuleInfo_ *info;
-    error = module_info_new_(name, NULL, NULL, NULL, NULL, handle, NULL, MODULE_TYPE_PSEUDO_, &info);
-    if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not construct module info")
-        module_handle_release_(handle);
-        return error;
+    const struct Module_ *module_ = hashmap_get(ctx->modules, &(struct Module_){
+                                                                      .name = info->info.name,
+                                                              });
+    if (module_ == NULL || module_->module != info_inner->module) {
+        ERROR_(ctx, FIMO_EINVAL, "module is not registered with the backend, module='%s'", info->info.name)
+        return FIMO_EINVAL;
     }
 
-    fimo_internal_context_acquire(TO_CTX_(ctx));
-    FimoContext ctx_;
-    error = fimo_internal_context_to_public_ctx(TO_CTX_(ctx), &ctx_);
+    FimoUSize count;
+    const FimoU64 node = module_->node;
+    FimoResult error = fimo_graph_neighbors_count(ctx->dependency_graph, node, true, &count);
     FIMO_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
-
-    *element = fimo_malloc(sizeof(**element), &error);
-    if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not allocate module")
-        goto release_ctx;
+    if (count != 0) {
+        ERROR_(ctx, FIMO_EPERM, "module is still in use, module='%s'", info->info.name)
+        return FIMO_EPERM;
     }
-    info->inner.module = *element;
-
-    **element = (FimoModule){
-            .parameters = NULL,
-            .resources = NULL,
-            .imports = NULL,
-            .exports = NULL,
-            .module_info = &info->info,
-            .context = ctx_,
-            .module_data = NULL,
-    };
 
+    // Remove all symbols.
+    {
+        FimoUSize it = 0;
+        const struct ModuleInfoSymbol_ *symbol = NULL;
+        while (module_info_next_symbol_(info_inner, &it, &symbol)) {
+            ctx_remove_symbol_(ctx, symbol->name, symbol->ns);
+        }
+    }
+    // Release all namespaces.
+    {
+        FimoUSize it = 0;
+        const struct ModuleInfoNamespace_ *ns = NULL;
+        while (module_info_next_ns_(info_inner, &it, &ns)) {
+            ctx_ns_release_(ctx, ns->name);
+        }
+    }
+    // Check that no empty namespace is still referenced.
+    {
+        FimoUSize it = 0;
+        const struct ModuleInfoSymbol_ *symbol = NULL;
+        while (module_info_next_symbol_(info_inner, &it, &symbol)) {
+            if (strcmp(GLOBAL_NS, symbol->ns) == 0) {
+                continue;
+            }
+            const struct Namespace_ *ns = ctx_get_ns_(ctx, symbol->ns);
+            if (ns && ns->reference_count != 0 && ns->symbol_count == 0) {
+                error = FIMO_EPERM;
+                ERROR_(ctx, error, "namespace is still in use, module='%s', ns='%s'", info->info.name, ns->name)
+                goto rollback_ns;
+            }
+        }
+    }
+
+    module_ = hashmap_delete(ctx->modules, &(struct Module_){
+                                                   .name = info->info.name,
+                                           });
+    FIMO_ASSERT(module_)
+    module_free_((struct Module_ *)module_);
+
+    void *data_;
+    error = fimo_graph_remove_node(ctx->dependency_graph, node, &data_);
+    FIMO_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
+    FIMO_ASSERT(data_)
+
+    ctx_queue_module_event_(ctx, &FIMO_BUS_TOPIC_MODULE_UNLOADED, info->info.name, NULL);
     return FIMO_EOK;
 
-release_ctx:
-    fimo_internal_context_release(TO_CTX_(ctx));
-    module_info_release_(info, false);
+rollback_ns:;
+    {
+        FimoUSize it = 0;
+        const struct ModuleInfoNamespace_ *ns = NULL;
+        while (module_info_next_ns_(info_inner, &it, &ns)) {
+            const FimoResult error_ = ctx_ns_acquire_(ctx, ns->name);
+            FIMO_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error_))
+        }
+    }
+    {
+        FimoUSize it = 0;
+        const struct ModuleInfoSymbol_ *symbol = NULL;
+        while (module_info_next_symbol_(info_inner, &it, &symbol)) {
+            const FimoResult error_ =
+                    ctx_insert_symbol_(ctx, symbol->name, symbol->ns, symbol->version, info->info.name);
+            FIMO_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error_))
+        }
+    }
 
     return error;
 }
 
-static FimoResult fi_module_new_from_export(FimoInternalModuleContext *ctx, FimoModuleLoadingSet *set,
-                                            const FimoModuleExport *export, struct ModuleHandle_ *handle,
-                                            FimoModule **element) {
-    FIMO_DEBUG_ASSERT(ctx && export && handle && element)
-    module_handle_acquire_(handle);
-    *element = NULL;
+static FimoResult ctx_link_module_(FimoInternalModuleContext *ctx, struct ModuleInfoInner_ *info_inner,
+                                   struct ModuleInfoInner_ *other_inner) {
+    FIMO_DEBUG_ASSERT(ctx && info_inner && other_inner && !module_info_is_detached_(info_inner))
+    const struct ModuleInfo_ *info = module_info_from_inner_(info_inner);
+    const struct ModuleInfo_ *other_info = module_info_from_inner_(other_inner);
 
-    struct ModuleInfo_ *info;
-    FimoResult error = module_info_new_(export->name, export->description, export->author, export->license,
-                                        handle->module_path, handle, export, MODULE_TYPE_REGULAR_, &info);
+    TRACE_(ctx, "module='%s', other='%s'", info->info.name, other_info->info.name)
+    if (module_info_is_detached_(other_inner)) {
+        ERROR_(ctx, FIMO_EINVAL, "module is not registered with the module subsystem, module='%s'",
+               other_info->info.name)
+        return FIMO_EINVAL;
+    }
+    if (module_info_get_dependency_(info_inner, other_info->info.name) != NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "modules are already linked, module='%s', other='%s'", info->info.name,
+               other_info->info.name)
+        return FIMO_EEXIST;
+    }
+    if (other_info->type == MODULE_TYPE_PSEUDO_) {
+        ERROR_(ctx, FIMO_EPERM, "can not link to a pseudo module, module='%s', other='%s'", info->info.name,
+               other_info->info.name)
+        return FIMO_EPERM;
+    }
+
+    const struct Module_ *inner_module = ctx_get_module_(ctx, info->info.name);
+    const struct Module_ *other_module = ctx_get_module_(ctx, other_info->info.name);
+    FIMO_DEBUG_ASSERT(inner_module)
+    FIMO_DEBUG_ASSERT(other_module)
+
+    bool would_introduce_cycle;
+    FimoResult error = fimo_graph_path_exists(ctx->dependency_graph, other_module->node, inner_module->node,
+                                              &would_introduce_cycle);
     if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not construct module info")
-        module_handle_release_(handle);
+        ERROR_SIMPLE_(ctx, error, "could not determine if linking the modules would introduce a cycle")
         return error;
     }
-    struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
 
-    fimo_internal_context_acquire(TO_CTX_(ctx));
-    FimoContext ctx_;
-    error = fimo_internal_context_to_public_ctx(TO_CTX_(ctx), &ctx_);
-    FIMO_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
+    FimoU64 edge;
+    error = fimo_graph_add_edge(ctx->dependency_graph, inner_module->node, other_module->node, NULL, NULL, &edge);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_SIMPLE_(ctx, error, "could not add edge to the dependency graph")
+        return error;
+    }
 
-    *element = fimo_malloc(sizeof(**element), &error);
+    error = module_info_set_dependency_(info_inner, &other_info->info, false);
     if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not allocate module")
-        goto release_ctx;
+        void *edge_data;
+        const FimoResult error_ = fimo_graph_remove_edge(ctx->dependency_graph, edge, &edge_data);
+        FIMO_DEBUG_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error_))
+        (void)error_;
+        FIMO_DEBUG_ASSERT(edge_data == NULL)
+        ERROR_(ctx, error, "could not insert other into the module info dependency map, module='%s', dependency='%s'",
+               info->info.name, other_info->info.name)
+        return error;
     }
-    info->inner.module = *element;
 
-    **element = (FimoModule){
-            .parameters = NULL,
-            .resources = NULL,
-            .imports = NULL,
-            .exports = NULL,
-            .module_info = &info->info,
-            .context = ctx_,
-            .module_data = NULL,
-    };
+    return FIMO_EOK;
+}
 
-    // Init parameters.
-    FimoArrayList params = fimo_array_list_new();
-    for (FimoISize i = 0; i < export->parameters_count; i++) {
-        const FimoModuleParamDecl *decl = &export->parameters[i];
-        struct ParamData_ param_data = {
-                .owner = *element,
-                .type = decl->type,
-        };
-        switch (decl->type) {
-            case FIMO_MODULE_PARAM_TYPE_U8:
-                param_data.value.u8 = decl->default_value.u8;
-                break;
-            case FIMO_MODULE_PARAM_TYPE_U16:
-                param_data.value.u16 = decl->default_value.u16;
-                break;
-            case FIMO_MODULE_PARAM_TYPE_U32:
-                param_data.value.u32 = decl->default_value.u32;
-                break;
-            case FIMO_MODULE_PARAM_TYPE_U64:
-                param_data.value.u64 = decl->default_value.u64;
-                break;
-            case FIMO_MODULE_PARAM_TYPE_I8:
-                param_data.value.i8 = decl->default_value.i8;
-                break;
-            case FIMO_MODULE_PARAM_TYPE_I16:
-                param_data.value.i16 = decl->default_value.i16;
-                break;
-            case FIMO_MODULE_PARAM_TYPE_I32:
-                param_data.value.i32 = decl->default_value.i32;
-                break;
-            case FIMO_MODULE_PARAM_TYPE_I64:
-                param_data.value.i64 = decl->default_value.i64;
-                break;
-        }
-        FimoModuleParam *param;
-        error = param_new_(decl->read_access, decl->write_access, decl->setter, decl->getter, param_data, &param);
-        if (FIMO_RESULT_IS_ERROR(error)) {
-            ERROR_SIMPLE_(ctx, error, "could not initialize parameter")
-            goto release_parameters;
-        }
-        error = fimo_array_list_push(&params, sizeof(FimoModuleParam *), _Alignof(FimoModuleParam *), &param, NULL);
-        if (FIMO_RESULT_IS_ERROR(error)) {
-            ERROR_SIMPLE_(ctx, error, "could not insert parameter into the parameter table")
-            goto release_parameters;
-        }
-        error = module_info_set_param_(info_inner, decl->name, param);
-        if (FIMO_RESULT_IS_ERROR(error)) {
-            ERROR_SIMPLE_(ctx, error, "could not insert parameter into the module info")
-            goto release_parameters;
-        }
-    }
-    (*element)->parameters = params.elements;
+static FimoResult ctx_unlink_module_(FimoInternalModuleContext *ctx, struct ModuleInfoInner_ *info_inner,
+                                     struct ModuleInfoInner_ *other_inner) {
+    FIMO_DEBUG_ASSERT(ctx && info_inner && other_inner && !module_info_is_detached_(info_inner) &&
+                      !module_info_is_detached_(other_inner))
+    const struct ModuleInfo_ *info = module_info_from_inner_(info_inner);
+    const struct ModuleInfo_ *other_info = module_info_from_inner_(other_inner);
 
-    // Init resources.
-    FimoArrayList resources = fimo_array_list_new();
-    for (FimoISize i = 0; i < export->resources_count; i++) {
-        const FimoModuleResourceDecl *resource = &export->resources[i];
-        char *resource_path;
-        error = path_join(handle->module_path, resource->path, &resource_path);
-        if (FIMO_RESULT_IS_ERROR(error)) {
-            ERROR_SIMPLE_(ctx, error, "could not construct resource path")
-            goto release_resources;
-        }
-        error = fimo_array_list_push(&resources, sizeof(const char *), _Alignof(const char *), &resource_path, NULL);
-        if (FIMO_RESULT_IS_ERROR(error)) {
-            ERROR_SIMPLE_(ctx, error, "could not insert resource into the resource table")
-            goto release_resources;
-        }
+    TRACE_(ctx, "module='%s', other='%s'", info->info.name, other_info->info.name)
+    const struct ModuleInfoDependency_ *dependency = module_info_get_dependency_(info_inner, other_info->info.name);
+    if (dependency == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "modules are not linked, module='%s', other='%s'", info->info.name,
+               other_info->info.name)
+        return FIMO_EEXIST;
     }
-    (*element)->resources = resources.elements;
-
-    // Init namespaces.
-    for (FimoISize i = 0; i < export->namespace_imports_count; i++) {
-        const FimoModuleNamespaceImport *import = &export->namespace_imports[i];
-        const struct Namespace_ *ns = ctx_get_ns_(ctx, import->name);
-        if (ns == NULL) {
-            ERROR_(ctx, error, "could not find namespace, ns='%s'", import->name)
-            goto release_namespaces;
-        }
-        error = module_info_set_ns_(info_inner, import->name, true);
-        if (FIMO_RESULT_IS_ERROR(error)) {
-            ERROR_SIMPLE_(ctx, error, "could not insert namespace into the module info")
-            goto release_namespaces;
-        }
+    if (dependency->is_static) {
+        ERROR_(ctx, FIMO_EPERM, "can not unlink static module links, module='%s', other='%s'", info->info.name,
+               other_info->info.name)
+        return FIMO_EPERM;
     }
 
-    // Init imports.
-    FimoArrayList imports = fimo_array_list_new();
-    for (FimoISize i = 0; i < export->symbol_imports_count; i++) {
-        const FimoModuleSymbolImport *import = &export->symbol_imports[i];
-        const struct Symbol_ *symbol = ctx_get_symbol_compatible_(ctx, import->name, import->ns, import->version);
-        if (symbol == NULL) {
-            ERROR_(ctx, error, "could not find symbol, symbol='%s', ns='%s'", import->name, import->ns)
-            goto release_imports;
-        }
-        const struct Module_ *module = ctx_get_module_(ctx, symbol->module);
-        FIMO_DEBUG_ASSERT(module);
-        const struct ModuleInfo_ *module_info = module_info_from_module_(module->module);
-        struct ModuleInfoInner_ *module_info_inner = module_info_lock_(module_info);
-        const struct ModuleInfoSymbol_ *module_info_symbol =
-                module_info_get_symbol_(module_info_inner, symbol->name, symbol->ns, symbol->version);
-        FIMO_DEBUG_ASSERT(module_info_symbol);
-        const FimoModuleRawSymbol *raw_symbol = &module_info_symbol->symbol;
-        error = fimo_array_list_push(&imports, sizeof(FimoModuleRawSymbol *), _Alignof(FimoModuleRawSymbol *),
-                                     &raw_symbol, NULL);
-        if (FIMO_RESULT_IS_ERROR(error)) {
-            module_info_unlock_(module_info_inner);
-            ERROR_SIMPLE_(ctx, error, "could not insert symbol into the import table")
-            goto release_imports;
-        }
-        if (module_info_get_dependency_(info_inner, symbol->module) == NULL) {
-            error = module_info_set_dependency_(info_inner, module->module->module_info, true);
-            if (FIMO_RESULT_IS_ERROR(error)) {
-                module_info_unlock_(module_info_inner);
-                ERROR_SIMPLE_(ctx, error, "could not insert dependency into the module info")
-                goto release_imports;
+    const struct Module_ *ctx_module = ctx_get_module_(ctx, info->info.name);
+    const struct Module_ *other_ctx_module = ctx_get_module_(ctx, other_info->info.name);
+    FIMO_DEBUG_ASSERT(ctx_module)
+    FIMO_DEBUG_ASSERT(other_ctx_module)
+
+    FimoU64 edge;
+    bool contained;
+    FimoResult error =
+            fimo_graph_find_edge(ctx->dependency_graph, ctx_module->node, other_ctx_module->node, &edge, &contained);
+    FIMO_DEBUG_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
+    FIMO_DEBUG_ASSERT(contained)
+    (void)error;
+
+    void *edge_data;
+    error = fimo_graph_remove_edge(ctx->dependency_graph, edge, &edge_data);
+    FIMO_DEBUG_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
+    FIMO_DEBUG_ASSERT(edge_data == NULL)
+    (void)error;
+
+    module_info_delete_dependency_(info_inner, other_info->info.name);
+
+    return FIMO_EOK;
+}
+
+static bool ctx_can_remove_module_(FimoInternalModuleContext *ctx, struct ModuleInfoInner_ *info_inner) {
+    FIMO_DEBUG_ASSERT(ctx && info_inner && !module_info_is_detached_(info_inner));
+    const struct ModuleInfo_ *info = module_info_from_inner_(info_inner);
+    TRACE_(ctx, "module='%s'", info->info.name)
+
+    // Check if the module info has been marked as unloadable
+    if (!module_info_can_unload_(info_inner)) {
+        return false;
+    }
+
+    // Check that no symbols are in use.
+    {
+        FimoUSize it = 0;
+        const struct ModuleInfoSymbol_ *symbol;
+        while (module_info_next_symbol_(info_inner, &it, &symbol)) {
+            if (FIMO_MODULE_SYMBOL_IS_LOCKED(&symbol->symbol)) {
+                return false;
             }
         }
-        module_info_unlock_(module_info_inner);
     }
-    (*element)->imports = imports.elements;
 
-    // Init the module.
-    if (export->module_constructor) {
-        void *module_data = NULL;
+    // Check that there are no dependencies left.
+    const struct Module_ *module_ = ctx_get_module_(ctx, info->info.name);
+    FIMO_DEBUG_ASSERT(module_)
+    FimoUSize neighbors;
+    FimoResult error = fimo_graph_neighbors_count(ctx->dependency_graph, module_->node, true, &neighbors);
+    FIMO_DEBUG_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
+    (void)error;
+
+    return neighbors == 0;
+}
+
+static bool module_in_group_(const FimoModule *module, const char *group) {
+    FIMO_DEBUG_ASSERT(module && group)
+    const char *module_group = module->module_info->group;
+    return module_group != NULL && strcmp(module_group, group) == 0;
+}
+
+static bool ctx_can_remove_group_(FimoInternalModuleContext *ctx, const char *group) {
+    FIMO_DEBUG_ASSERT(ctx && group)
+    TRACE_(ctx, "group='%s'", group)
+
+    // The modules of the group may only be used by other modules of the same group.
+    FimoUSize it = 0;
+    const struct Module_ *module_ = NULL;
+    while (hashmap_iter(ctx->modules, &it, (void **)&module_)) {
+        if (!module_in_group_(module_->module, group)) {
+            continue;
+        }
+
+        const struct ModuleInfo_ *info = module_info_from_module_(module_->module);
+        struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
+        const bool can_unload = module_info_can_unload_(info_inner);
         module_info_unlock_(info_inner);
-        loading_set_unlock_(set);
-        ctx_unlock_(ctx);
-        error = export->module_constructor(*element, set, &module_data);
-        ctx_lock_(ctx);
-        loading_set_lock_(set);
-        module_info_lock_(info);
-        (*element)->module_data = module_data;
-        if (FIMO_RESULT_IS_ERROR(error)) {
-            ERROR_SIMPLE_(ctx, error, "could not construct the module data")
-            goto release_imports;
+        if (!can_unload) {
+            return false;
         }
-    }
 
-    // Init the static exports.
-    FimoArrayList exports = fimo_array_list_new();
-    for (FimoISize i = 0; i < export->symbol_exports_count; i++) {
-        const FimoModuleSymbolExport *symbol = &export->symbol_exports[i];
-        const struct ModuleInfoSymbol_ *info_symbol;
-        error = module_info_set_symbol_(info_inner, symbol->name, symbol->ns, symbol->version, NULL, symbol->symbol,
-                                        &info_symbol);
+        bool has_next;
+        FimoGraphNeighbors *neighbors;
+        FimoResult error = fimo_graph_neighbors_new(ctx->dependency_graph, module_->node, true, &neighbors, &has_next);
         if (FIMO_RESULT_IS_ERROR(error)) {
-            ERROR_SIMPLE_(ctx, error, "could not insert symbol into the module info")
-            goto release_exports;
+            ERROR_SIMPLE_(ctx, error, "could not construct neighbors iterator")
+            fimo_result_release(error);
+            return false;
         }
-        FIMO_DEBUG_ASSERT(info_symbol)
-        const FimoModuleRawSymbol *raw_symbol = &info_symbol->symbol;
-        error = fimo_array_list_push(&exports, sizeof(const FimoModuleRawSymbol *),
-                                     _Alignof(const FimoModuleRawSymbol *), &raw_symbol, NULL);
-        if (FIMO_RESULT_IS_ERROR(error)) {
-            ERROR_SIMPLE_(ctx, error, "could not insert symbol into the export table")
-            goto release_exports;
+        bool is_used = false;
+        while (has_next && !is_used) {
+            FimoU64 node;
+            error = fimo_graph_neighbors_item(neighbors, &node);
+            FIMO_DEBUG_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
+            const FimoModule **dependent;
+            error = fimo_graph_node_data(ctx->dependency_graph, node, (const void **)&dependent);
+            FIMO_DEBUG_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
+            is_used = !module_in_group_(*dependent, group);
+            error = fimo_graph_neighbors_next(neighbors, &has_next);
+            FIMO_DEBUG_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
+        }
+        fimo_graph_neighbors_free(neighbors);
+        if (is_used) {
+            return false;
         }
     }
 
-    // Init the dynamic exports.
-    for (FimoISize i = 0; i < export->dynamic_symbol_exports_count; i++) {
-        const FimoModuleDynamicSymbolExport *symbol = &export->dynamic_symbol_exports[i];
-        void *sym;
-        error = symbol->constructor(*element, &sym);
-        if (FIMO_RESULT_IS_ERROR(error)) {
-            ERROR_(ctx, error, "could not construct symbol, symbol='%s', ns='%s'", symbol->name, symbol->ns)
-            goto release_exports;
+    return true;
+}
+
+static FimoResult ctx_remove_group_(FimoInternalModuleContext *ctx, const char *group) {
+    FIMO_DEBUG_ASSERT(ctx && group)
+    TRACE_(ctx, "group='%s'", group)
+
+    if (!ctx_can_remove_group_(ctx, group)) {
+        ERROR_(ctx, FIMO_EPERM, "the group is still in use, group='%s'", group)
+        return FIMO_EPERM;
+    }
+
+    // Remove the modules, starting with the ones that are not used by other modules of the group.
+    bool removed_module = true;
+    while (removed_module) {
+        removed_module = false;
+        FimoUSize it = 0;
+        const struct Module_ *module_ = NULL;
+        while (hashmap_iter(ctx->modules, &it, (void **)&module_)) {
+            if (!module_in_group_(module_->module, group)) {
+                continue;
+            }
+
+            const struct ModuleInfo_ *info = module_info_from_module_(module_->module);
+            struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
+            if (!ctx_can_remove_module_(ctx, info_inner)) {
+                module_info_unlock_(info_inner);
+                continue;
+            }
+            const FimoResult error = ctx_remove_module_(ctx, info_inner);
+            if (FIMO_RESULT_IS_ERROR(error)) {
+                module_info_unlock_(info_inner);
+                ERROR_(ctx, error, "could not remove module, group='%s', module='%s'", group, info->info.name)
+                return error;
+            }
+            fi_module_free_(info_inner, NULL, true);
+
+            // Restart the iteration, since we modified the map.
+            removed_module = true;
+            break;
         }
-        const struct ModuleInfoSymbol_ *info_symbol;
-        error = module_info_set_symbol_(info_inner, symbol->name, symbol->ns, symbol->version, symbol->destructor, sym,
-                                        &info_symbol);
+    }
+
+    if (ctx_has_group_(ctx, group)) {
+        ERROR_(ctx, FIMO_EPERM, "the group could only be partially removed, group='%s'", group)
+        return FIMO_EPERM;
+    }
+
+    return FIMO_EOK;
+}
+
+static FimoResult ctx_cleanup_loose_modules(FimoInternalModuleContext *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    TRACE_SIMPLE_(ctx, "cleaning up loose modules")
+
+    bool has_next;
+    FimoGraphExternals *iter;
+    FimoResult error = fimo_graph_externals_new(ctx->dependency_graph, false, &iter, &has_next);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_SIMPLE_(ctx, error, "could not construct externals iterator")
+        return error;
+    }
+
+    while (has_next) {
+        FimoU64 node;
+        const FimoModule **module_ptr;
+        error = fimo_graph_externals_item(iter, &node, (const void **)&module_ptr);
+        FIMO_DEBUG_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
+        FIMO_DEBUG_ASSERT(module_ptr)
+        const FimoModule *module = *module_ptr;
+        const struct ModuleInfo_ *info = module_info_from_module_(module);
+        if (info->type != MODULE_TYPE_REGULAR_) {
+            error = fimo_graph_externals_next(iter, &has_next);
+            FIMO_DEBUG_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
+            continue;
+        }
+
+        // The modules of a group can only be removed together.
+        if (module->module_info->group != NULL) {
+            if (!ctx_can_remove_group_(ctx, module->module_info->group)) {
+                error = fimo_graph_externals_next(iter, &has_next);
+                FIMO_DEBUG_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
+                continue;
+            }
+
+            // Copy the name of the group, as it is owned by the modules we are about to remove.
+            char *group;
+            error = clone_string_(module->module_info->group, &group);
+            if (FIMO_RESULT_IS_ERROR(error)) {
+                ERROR_SIMPLE_(ctx, error, "could not clone the group name")
+                fimo_graph_externals_free(iter);
+                return error;
+            }
+            error = ctx_remove_group_(ctx, group);
+            fimo_free(group);
+            if (FIMO_RESULT_IS_ERROR(error)) {
+                fimo_graph_externals_free(iter);
+                return error;
+            }
+
+            // Rebuild the iterator since we modified the dependency graph
+            fimo_graph_externals_free(iter);
+            error = fimo_graph_externals_new(ctx->dependency_graph, false, &iter, &has_next);
+            if (FIMO_RESULT_IS_ERROR(error)) {
+                ERROR_SIMPLE_(ctx, error, "could not construct externals iterator")
+                return error;
+            }
+            continue;
+        }
+
+        struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
+
+        if (!ctx_can_remove_module_(ctx, info_inner)) {
+            error = fimo_graph_externals_next(iter, &has_next);
+            FIMO_DEBUG_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
+            continue;
+        }
+
+        error = ctx_remove_module_(ctx, info_inner);
         if (FIMO_RESULT_IS_ERROR(error)) {
-            ERROR_SIMPLE_(ctx, error, "could not insert symbol into the module info")
-            symbol->destructor(sym);
-            goto release_exports;
+            module_info_unlock_(info_inner);
+            ERROR_(ctx, error, "could not remove module, module='%s'", module->module_info->name)
+            fimo_graph_externals_free(iter);
+            return error;
         }
-        FIMO_DEBUG_ASSERT(info_symbol)
-        const FimoModuleRawSymbol *raw_symbol = &info_symbol->symbol;
-        error = fimo_array_list_push(&exports, sizeof(const FimoModuleRawSymbol *),
-                                     _Alignof(const FimoModuleRawSymbol *), &raw_symbol, NULL);
+        fi_module_free_(info_inner, NULL, true);
+
+        // Rebuild the iterator since we modified the dependency graph
+        fimo_graph_externals_free(iter);
+        error = fimo_graph_externals_new(ctx->dependency_graph, false, &iter, &has_next);
         if (FIMO_RESULT_IS_ERROR(error)) {
-            ERROR_SIMPLE_(ctx, error, "could not insert symbol into the export table")
-            goto release_exports;
+            ERROR_SIMPLE_(ctx, error, "could not construct externals iterator")
+            return error;
         }
     }
-    (*element)->exports = exports.elements;
+    fimo_graph_externals_free(iter);
+
+    return FIMO_EOK;
+}
+
+// Must be called while the context is unlocked.
+static void ctx_publish_module_event_(FimoInternalModuleContext *ctx, const FimoBusTopic *topic, const char *module) {
+    FIMO_DEBUG_ASSERT(ctx && topic && module)
+    const FimoBusEvent event = {
+            .topic = topic,
+            .payload = module,
+            .payload_size = strlen(module) + 1,
+    };
+    const FimoResult error = fimo_internal_bus_publish(&(TO_CTX_(ctx))->bus, &event);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        WARN_(ctx, "could not publish the module event, module='%s'", module)
+        fimo_result_release(error);
+    }
+}
+
+// Must be called while the context is locked. The event is published by `ctx_unlock_`, after
+// the context has been unlocked, so that synchronous subscribers may call back into it.
+static void ctx_queue_module_event_(FimoInternalModuleContext *ctx, const FimoBusTopic *topic, const char *module,
+                                    const char *description) {
+    FIMO_DEBUG_ASSERT(ctx && topic && module)
+    struct PendingEvent_ *event;
+    const FimoResult error = pending_event_new_(topic, module, description, &event);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        WARN_(ctx, "could not allocate the module event, module='%s'", module)
+        fimo_result_release(error);
+        return;
+    }
+
+    if (ctx->pending_events_tail) {
+        ctx->pending_events_tail->next = event;
+    }
+    else {
+        ctx->pending_events = event;
+    }
+    ctx->pending_events_tail = event;
+}
+
+// Must be called while the context is locked, and returns with the context locked.
+//
+// Only one thread publishes the events at a time, so that they are delivered in the order in which
+// they were queued. If another thread is already publishing, it will also publish the events queued
+// by the current thread before releasing the events mutex. The same applies to a subscriber calling
+// back into the context. As the events mutex is never waited on while the context is locked, the
+// two mutexes can not deadlock.
+static void ctx_publish_pending_events_(FimoInternalModuleContext *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    if (ctx->pending_events == NULL || PUBLISHING_EVENTS_ == ctx ||
+        mtx_trylock(&ctx->events_mutex) != thrd_success) {
+        return;
+    }
+
+    FimoInternalModuleContext *publishing = PUBLISHING_EVENTS_;
+    PUBLISHING_EVENTS_ = ctx;
+    while (ctx->pending_events) {
+        struct PendingEvent_ *events = ctx->pending_events;
+        ctx->pending_events = NULL;
+        ctx->pending_events_tail = NULL;
+
+        mtx_unlock(&ctx->mutex);
+        while (events) {
+            struct PendingEvent_ *event = events;
+            events = event->next;
+
+            const FimoBusEvent bus_event = {
+                    .topic = event->topic,
+                    .payload = event->payload,
+                    .payload_size = event->payload_size,
+            };
+            const FimoResult error = fimo_internal_bus_publish(&(TO_CTX_(ctx))->bus, &bus_event);
+            if (FIMO_RESULT_IS_ERROR(error)) {
+                WARN_(ctx, "could not publish the module event, module='%s'", event->payload)
+                fimo_result_release(error);
+            }
+            pending_event_free_(event);
+        }
+        mtx_lock(&ctx->mutex);
+    }
+    PUBLISHING_EVENTS_ = publishing;
+    mtx_unlock(&ctx->events_mutex);
+}
+
+static const struct Module_ *ctx_get_module_(FimoInternalModuleContext *ctx, const char *name) {
+    FIMO_DEBUG_ASSERT(ctx && name)
+    TRACE_(ctx, "name='%s'", name)
+    return hashmap_get(ctx->modules, &(struct Module_){.name = name});
+}
+
+static bool ctx_has_group_(FimoInternalModuleContext *ctx, const char *group) {
+    FIMO_DEBUG_ASSERT(ctx && group)
+    TRACE_(ctx, "group='%s'", group)
+    FimoUSize it = 0;
+    const struct Module_ *module = NULL;
+    while (hashmap_iter(ctx->modules, &it, (void **)&module)) {
+        if (module_in_group_(module->module, group)) {
+            return true;
+        }
+    }
+    return false;
+}
+
+static const struct Symbol_ *ctx_get_symbol_(FimoInternalModuleContext *ctx, const char *name, const char *ns) {
+    FIMO_DEBUG_ASSERT(ctx && name && ns)
+    TRACE_(ctx, "name='%s', symbol='%s'", name, ns)
+    return hashmap_get(ctx->symbols, &(struct Symbol_){.name = name, .ns = ns});
+}
+
+static const struct Symbol_ *ctx_get_symbol_compatible_(FimoInternalModuleContext *ctx, const char *name,
+                                                        const char *ns, const FimoVersion version) {
+    FIMO_DEBUG_ASSERT(ctx && name && ns)
+    TRACE_(ctx, "name='%s', symbol='%s'", name, ns)
+    const struct Symbol_ *sym = ctx_get_symbol_(ctx, name, ns);
+    if (sym == NULL || !fimo_version_compatible(&sym->version, &version)) {
+        return NULL;
+    }
+    return sym;
+}
+
+static const struct Namespace_ *ctx_get_ns_(FimoInternalModuleContext *ctx, const char *name) {
+    FIMO_DEBUG_ASSERT(ctx && name)
+    TRACE_(ctx, "name='%s'", name)
+    return hashmap_get(ctx->namespaces, &(struct Namespace_){.name = name});
+}
+
+static FimoResult ctx_ns_allocate_if_not_found_(FimoInternalModuleContext *ctx, const char *name) {
+    FIMO_DEBUG_ASSERT(ctx && name)
+    TRACE_(ctx, "name='%s'", name)
+    if (strcmp(name, GLOBAL_NS) == 0) {
+        return FIMO_EOK;
+    }
+
+    if (ctx_get_ns_(ctx, name)) {
+        return FIMO_EOK;
+    }
+
+    struct Namespace_ ns;
+    const FimoResult error = namespace_new_(name, &ns);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_(ctx, error, "could not create namespace, ns='%s'", name)
+        return error;
+    }
+
+    hashmap_set(ctx->namespaces, &ns);
+    if (hashmap_oom(ctx->namespaces)) {
+        namespace_free_(&ns);
+        ERROR_(ctx, FIMO_ENOMEM, "could not insert namespace into the context, ns='%s'", name)
+        return FIMO_ENOMEM;
+    }
+
+    return FIMO_EOK;
+}
+
+static void ctx_ns_free_if_empty_(FimoInternalModuleContext *ctx, const char *name) {
+    FIMO_DEBUG_ASSERT(ctx && name)
+    TRACE_(ctx, "name='%s'", name)
+    if (strcmp(name, GLOBAL_NS) == 0) {
+        return;
+    }
+
+    const struct Namespace_ *ns = ctx_get_ns_(ctx, name);
+    FIMO_DEBUG_ASSERT(ns)
+    if (ns->reference_count == 0 && ns->symbol_count == 0) {
+        ns = hashmap_delete(ctx->namespaces, ns);
+        namespace_free_((struct Namespace_ *)ns);
+    }
+}
+
+static FimoResult ctx_ns_acquire_(FimoInternalModuleContext *ctx, const char *name) {
+    FIMO_DEBUG_ASSERT(ctx && name)
+    TRACE_(ctx, "name='%s'", name)
+    if (strcmp(name, GLOBAL_NS) == 0) {
+        return FIMO_EOK;
+    }
+
+    struct Namespace_ *ns = (struct Namespace_ *)ctx_get_ns_(ctx, name);
+    if (ns == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "namespace not found, ns='%s'", name)
+        return FIMO_EINVAL;
+    }
+
+    const FimoIntOptionUSize x = fimo_usize_checked_add(ns->reference_count, 1);
+    if (!x.has_value) {
+        ERROR_(ctx, FIMO_ERANGE, "namespace reference count overflow, ns='%s'", name)
+        return FIMO_ERANGE;
+    }
+
+    ns->reference_count = x.data.value;
+    return FIMO_EOK;
+}
+
+static void ctx_ns_release_(FimoInternalModuleContext *ctx, const char *name) {
+    FIMO_DEBUG_ASSERT(ctx && name)
+    TRACE_(ctx, "name='%s'", name)
+    if (strcmp(name, GLOBAL_NS) == 0) {
+        return;
+    }
+
+    struct Namespace_ *ns = (struct Namespace_ *)ctx_get_ns_(ctx, name);
+    FIMO_DEBUG_ASSERT(ns)
+    FIMO_DEBUG_ASSERT(ns->reference_count != 0)
+    ns->reference_count--;
+
+    ctx_ns_free_if_empty_(ctx, name);
+}
+
+static FimoResult ctx_insert_symbol_(FimoInternalModuleContext *ctx, const char *name, const char *ns,
+                                     const FimoVersion version, const char *module) {
+    FIMO_DEBUG_ASSERT(ctx && name && ns && module)
+    TRACE_(ctx, "name='%s', ns='%s', module='%s'", name, ns, module)
+    if (ctx_get_symbol_(ctx, name, ns)) {
+        ERROR_(ctx, FIMO_EINVAL, "symbol already exists, name='%s', ns='%s'", name, ns)
+        return FIMO_EINVAL;
+    }
+
+    struct Symbol_ sym;
+    FimoResult error = symbol_new_(name, ns, version, module, &sym);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_SIMPLE_(ctx, error, "could not create new symbol")
+        return error;
+    }
+
+    hashmap_set(ctx->symbols, &sym);
+    if (hashmap_oom(ctx->symbols)) {
+        symbol_free_(&sym);
+        ERROR_SIMPLE_(ctx, FIMO_ENOMEM, "could not insert symbol into the context")
+        return FIMO_ENOMEM;
+    }
+
+    if (strcmp(ns, GLOBAL_NS) != 0) {
+        struct Namespace_ *ns_ = (struct Namespace_ *)ctx_get_ns_(ctx, ns);
+        if (ns_ == NULL) {
+            error = FIMO_EINVAL;
+            ERROR_(ctx, error, "missing namespace, ns='%s'", ns)
+            goto remove_symbol;
+        }
+
+        const FimoIntOptionUSize x = fimo_usize_checked_add(ns_->symbol_count, 1);
+        if (!x.has_value) {
+            error = FIMO_ERANGE;
+            ERROR_(ctx, error, "namespace symbol count overflow, ns='%s'", ns)
+            goto remove_symbol;
+        }
+
+        ns_->symbol_count = x.data.value;
+    }
+
+    return FIMO_EOK;
+
+remove_symbol: {
+    hashmap_delete(ctx->symbols, &sym);
+    symbol_free_(&sym);
+}
+
+    return error;
+}
+
+static void ctx_remove_symbol_(FimoInternalModuleContext *ctx, const char *name, const char *ns) {
+    FIMO_DEBUG_ASSERT(ctx && name && ns)
+    TRACE_(ctx, "name='%s', ns='%s'", name, ns)
+    struct Symbol_ *sym = (struct Symbol_ *)ctx_get_symbol_(ctx, name, ns);
+    FIMO_DEBUG_ASSERT(sym)
+
+    sym = (struct Symbol_ *)hashmap_delete(ctx->symbols, sym);
+    symbol_free_(sym);
+
+    if (strcmp(ns, GLOBAL_NS) != 0) {
+        struct Namespace_ *ns_ = (struct Namespace_ *)ctx_get_ns_(ctx, ns);
+        FIMO_ASSERT(ns_ && ns_->symbol_count != 0)
+        ns_->symbol_count--;
+        ctx_ns_free_if_empty_(ctx, ns);
+    }
+}
+
+static FimoResult ctx_load_set(FimoInternalModuleContext *ctx, FimoModuleLoadingSet *set) {
+    FIMO_DEBUG_ASSERT(ctx && set)
+    if (ctx->is_loading) {
+        ERROR_SIMPLE_(ctx, FIMO_EPERM, "a set is already being loaded")
+        return FIMO_EPERM;
+    }
+    FIMO_DEBUG_ASSERT(!set->is_loading)
+    ctx->is_loading = true;
+    set->is_loading = true;
+
+    bool aborted = false;
+    struct LoadingSetLoadingInfo_ loading_info;
+    set->should_recreate_map = false;
+    FimoResult error = loading_set_create_info_(set, ctx, &loading_info);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_SIMPLE_(ctx, error, "could not construct load order")
+        goto on_critical_error;
+    }
+
+    while (!loading_set_loading_info_is_empty_(&loading_info)) {
+        if (set->should_recreate_map) {
+            set->should_recreate_map = false;
+            loading_set_loading_info_free_(&loading_info);
+            error = loading_set_create_info_(set, ctx, &loading_info);
+            if (FIMO_RESULT_IS_ERROR(error)) {
+                ERROR_SIMPLE_(ctx, error, "could not construct load order")
+                goto on_critical_error;
+            }
+        }
+
+        struct LoadingSetModule_ *module = loading_set_loading_info_pop_(&loading_info);
+        // Skip the module if the loading of its group was already rolled back.
+        if (module->status != MODULE_LOAD_STATUS_UNLOADED_) {
+            continue;
+        }
+        if (aborted) {
+            WARN_(ctx, "module can not be loaded as the loading of the set was aborted, module='%s'", module->name)
+            goto skip_module;
+        }
+
+        // Recheck that all dependencies could be loaded.
+        for (FimoISize i = 0; i < (FimoISize)module->export->symbol_imports_count; i++) {
+            const FimoModuleSymbolImport *import = &module->export->symbol_imports[i];
+            const struct LoadingSetSymbol_ *symbol =
+                    loading_set_get_symbol_(set, import->name, import->ns, import->version);
+            // Skip the module if a dependency could not be loaded.
+            if (symbol != NULL) {
+                const struct LoadingSetModule_ *exporter = loading_set_get_module_(set, symbol->module);
+                FIMO_DEBUG_ASSERT(exporter)
+                if (exporter->status == MODULE_LOAD_STATUS_ERROR_) {
+                    WARN_(ctx,
+                          "module can not be loaded as there was an error during the construction of a module "
+                          "it depends on, module='%s', dependency='%s'",
+                          module->name, exporter->name)
+                    goto skip_module;
+                }
+            }
+        }
+
+        // Check that the explicit dependencies exist.
+        for (FimoISize i = 0; i < (FimoISize)module->export->modifiers_count; i++) {
+            const FimoModuleExportModifier *modifier = &module->export->modifiers[i];
+            if (modifier->key != FIMO_MODULE_EXPORT_MODIFIER_KEY_DEPENDENCY) {
+                continue;
+            }
+            const FimoModuleInfo *dependency = modifier->value;
+            FIMO_DEBUG_ASSERT(dependency && dependency->name)
+            if (ctx_get_module_(ctx, dependency->name) == NULL) {
+                WARN_(ctx,
+                      "module can not be loaded as the module specified by the dependency "
+                      "modifier does not exist, module='%s', dependency='%s'",
+                      module->name, dependency->name)
+                goto skip_module;
+            }
+        }
+
+        // Construct the module.
+        FimoModule *constructed;
+        error = fi_module_new_from_export(ctx, set, module->export, module->handle, module->group, &constructed);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            FimoResultString error_name = fimo_result_error_name(error);
+            FimoResultString error_description = fimo_result_error_description(error);
+            WARN_(ctx, "skipping module due to construction error, module='%s', error='%s:%s'", module->name,
+                  error_name.str, error_description.str)
+            ctx_queue_module_event_(ctx, &FIMO_BUS_TOPIC_MODULE_CONSTRUCTION_FAILED, module->name,
+                                    error_description.str);
+            fimo_result_string_release(error_name);
+            fimo_result_string_release(error_description);
+            fimo_result_release(error);
+            aborted = ctx->fault_policy == FIMO_MODULE_FAULT_POLICY_ABORT;
+            goto skip_module;
+        }
+
+        // Register it with the backend.
+        const struct ModuleInfo_ *constructed_info = module_info_from_module_(constructed);
+        struct ModuleInfoInner_ *constructed_info_inner = module_info_lock_(constructed_info);
+        error = ctx_add_module_(ctx, constructed_info_inner);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            ERROR_SIMPLE_(ctx, error, "could not register module with the backend")
+            fi_module_free_(constructed_info_inner, NULL, true);
+            goto free_loding_info;
+        }
+        module_info_unlock_(constructed_info_inner);
+
+        // Signal loading success. The modules of a group are only signaled, once the entire group
+        // has been loaded.
+        if (module->group == NULL) {
+            loading_set_module_signal_success(module, constructed->module_info);
+            continue;
+        }
+        loading_set_module_signal_pending_(module, constructed->module_info);
+        if (loading_set_group_is_constructed_(set, module->group)) {
+            TRACE_(ctx, "loaded module group, group='%s'", module->group)
+            loading_set_signal_group_success_(set, module->group);
+        }
+        continue;
+
+    skip_module:
+        loading_set_module_signal_error_(module);
+        if (module->group != NULL) {
+            loading_set_signal_group_error_(set, ctx, module->group);
+        }
+    }
+
+    // A group whose modules were not all loaded is rolled back.
+    loading_set_rollback_pending_groups_(set, ctx);
+    set->is_loading = false;
+    ctx->is_loading = false;
+    return FIMO_EOK;
+
+free_loding_info:
+    loading_set_loading_info_free_(&loading_info);
+on_critical_error:
+    loading_set_rollback_pending_groups_(set, ctx);
+    set->is_loading = false;
+    ctx->is_loading = false;
+    return error;
+}
+
+///////////////////////////////////////////////////////////////////////
+//// Fimo Module
+///////////////////////////////////////////////////////////////////////
+
+static FimoResult fi_module_new_pseudo_(FimoInternalModuleContext *ctx, const char *name, FimoModule **element) {
+    FIMO_DEBUG_ASSERT(ctx && name && element)
+    TRACE_(ctx, "name='%s', element='%p'", name, (void *)element)
+    struct ModuleHandle_ *handle;
+    void (*iterator)(bool (*)(const FimoModuleExport *, void *), void *) = fimo_impl_module_export_iterator;
+    FimoResult error = module_handle_new_local_(fimo_impl_module_export_iterator, *(const void **)&iterator, &handle);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_SIMPLE_(ctx, error, "could not construct module handle")
+        return error;
+    }
+
+    struct ModuleInfo_ *info;
+    error = module_info_new_(name, NULL, NULL, NULL, NULL, NULL, handle, NULL, MODULE_TYPE_PSEUDO_, &info);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_SIMPLE_(ctx, error, "could not construct module info")
+        module_handle_release_(handle);
+        return error;
+    }
+
+    fimo_internal_context_acquire(TO_CTX_(ctx));
+    FimoContext ctx_;
+    error = fimo_internal_context_to_public_ctx(TO_CTX_(ctx), &ctx_);
+    FIMO_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
+
+    *element = fimo_malloc(sizeof(**element), &error);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_SIMPLE_(ctx, error, "could not allocate module")
+        goto release_ctx;
+    }
+    info->inner.module = *element;
+
+    **element = (FimoModule){
+            .parameters = NULL,
+            .resources = NULL,
+            .imports = NULL,
+            .exports = NULL,
+            .module_info = &info->info,
+            .context = ctx_,
+            .module_data = NULL,
+    };
+
+    return FIMO_EOK;
+
+release_ctx:
+    fimo_internal_context_release(TO_CTX_(ctx));
+    module_info_release_(info, false);
+
+    return error;
+}
+
+static FimoResult fi_module_new_from_export(FimoInternalModuleContext *ctx, FimoModuleLoadingSet *set,
+                                            const FimoModuleExport *export, struct ModuleHandle_ *handle,
+                                            const char *group, FimoModule **element) {
+    FIMO_DEBUG_ASSERT(ctx && export && handle && element)
+    module_handle_acquire_(handle);
+    *element = NULL;
+
+    struct ModuleInfo_ *info;
+    FimoResult error = module_info_new_(export->name, export->description, export->author, export->license,
+                                        handle->module_path, group, handle, export, MODULE_TYPE_REGULAR_, &info);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_SIMPLE_(ctx, error, "could not construct module info")
+        module_handle_release_(handle);
+        return error;
+    }
+    struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
+
+    fimo_internal_context_acquire(TO_CTX_(ctx));
+    FimoContext ctx_;
+    error = fimo_internal_context_to_public_ctx(TO_CTX_(ctx), &ctx_);
+    FIMO_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
+
+    *element = fimo_malloc(sizeof(**element), &error);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_SIMPLE_(ctx, error, "could not allocate module")
+        goto release_ctx;
+    }
+    info->inner.module = *element;
+
+    **element = (FimoModule){
+            .parameters = NULL,
+            .resources = NULL,
+            .imports = NULL,
+            .exports = NULL,
+            .module_info = &info->info,
+            .context = ctx_,
+            .module_data = NULL,
+    };
+
+    // Init features.
+    const FimoVersion context_version =
+            FIMO_VERSION_LONG(FIMO_VERSION_MAJOR, FIMO_VERSION_MINOR, FIMO_VERSION_PATCH, FIMO_VERSION_BUILD_NUMBER);
+    for (FimoISize i = 0; i < (FimoISize)export->modifiers_count; i++) {
+        const FimoModuleExportModifier *modifier = &export->modifiers[i];
+        if (modifier->key != FIMO_MODULE_EXPORT_MODIFIER_KEY_FEATURE) {
+            continue;
+        }
+        const FimoModuleExportModifierFeature *decl = modifier->value;
+        const bool available = fimo_version_compatible(&context_version, &decl->required_version);
+        bool enabled = decl->default_enabled;
+        const struct FeatureOverride_ *override = hashmap_get(
+                ctx->feature_overrides, &(struct FeatureOverride_){.module = export->name, .feature = decl->name});
+        if (override) {
+            enabled = override->enabled;
+        }
+        if (enabled && !available) {
+            WARN_(ctx, "feature requires a newer context, disabling, module='%s', feature='%s', required='%u.%u.%u'",
+                  export->name, decl->name, decl->required_version.major, decl->required_version.minor,
+                  decl->required_version.patch)
+            enabled = false;
+        }
+        error = module_info_set_feature_(info_inner, decl, available, enabled);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            ERROR_SIMPLE_(ctx, error, "could not insert feature into the module info")
+            goto release_ctx;
+        }
+    }
+
+    // Init parameters.
+    FimoArrayList params = fimo_array_list_new();
+    for (FimoISize i = 0; i < export->parameters_count; i++) {
+        const FimoModuleParamDecl *decl = &export->parameters[i];
+        struct ParamData_ param_data = {
+                .owner = *element,
+                .type = decl->type,
+        };
+        switch (decl->type) {
+            case FIMO_MODULE_PARAM_TYPE_U8:
+                param_data.value.u8 = decl->default_value.u8;
+                break;
+            case FIMO_MODULE_PARAM_TYPE_U16:
+                param_data.value.u16 = decl->default_value.u16;
+                break;
+            case FIMO_MODULE_PARAM_TYPE_U32:
+                param_data.value.u32 = decl->default_value.u32;
+                break;
+            case FIMO_MODULE_PARAM_TYPE_U64:
+                param_data.value.u64 = decl->default_value.u64;
+                break;
+            case FIMO_MODULE_PARAM_TYPE_I8:
+                param_data.value.i8 = decl->default_value.i8;
+                break;
+            case FIMO_MODULE_PARAM_TYPE_I16:
+                param_data.value.i16 = decl->default_value.i16;
+                break;
+            case FIMO_MODULE_PARAM_TYPE_I32:
+                param_data.value.i32 = decl->default_value.i32;
+                break;
+            case FIMO_MODULE_PARAM_TYPE_I64:
+                param_data.value.i64 = decl->default_value.i64;
+                break;
+        }
+        const struct ParamOverride_ *override =
+                hashmap_get(ctx->param_overrides, &(struct ParamOverride_){.module = export->name, .param = decl->name});
+        if (override) {
+            error = param_data_init_converted_(&param_data, &override->value, override->type);
+            if (FIMO_RESULT_IS_ERROR(error)) {
+                ERROR_(ctx, error, "parameter override is not representable by the parameter type, module='%s', "
+                       "param='%s'", export->name, decl->name)
+                goto release_parameters;
+            }
+        }
+        FimoModuleParam *param;
+        error = param_new_(decl->read_access, decl->write_access, decl->setter, decl->getter, param_data, &param);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            ERROR_SIMPLE_(ctx, error, "could not initialize parameter")
+            goto release_parameters;
+        }
+        error = fimo_array_list_push(&params, sizeof(FimoModuleParam *), _Alignof(FimoModuleParam *), &param, NULL);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            ERROR_SIMPLE_(ctx, error, "could not insert parameter into the parameter table")
+            goto release_parameters;
+        }
+        error = module_info_set_param_(info_inner, decl->name, param);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            ERROR_SIMPLE_(ctx, error, "could not insert parameter into the module info")
+            goto release_parameters;
+        }
+    }
+    (*element)->parameters = params.elements;
+
+    // Init resources.
+    FimoArrayList resources = fimo_array_list_new();
+    for (FimoISize i = 0; i < export->resources_count; i++) {
+        const FimoModuleResourceDecl *resource = &export->resources[i];
+        char *resource_path;
+        error = path_join(handle->module_path, resource->path, &resource_path);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            ERROR_SIMPLE_(ctx, error, "could not construct resource path")
+            goto release_resources;
+        }
+        error = fimo_array_list_push(&resources, sizeof(const char *), _Alignof(const char *), &resource_path, NULL);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            ERROR_SIMPLE_(ctx, error, "could not insert resource into the resource table")
+            goto release_resources;
+        }
+    }
+    (*element)->resources = resources.elements;
+
+    // Init namespaces.
+    for (FimoISize i = 0; i < export->namespace_imports_count; i++) {
+        const FimoModuleNamespaceImport *import = &export->namespace_imports[i];
+        const struct Namespace_ *ns = ctx_get_ns_(ctx, import->name);
+        if (ns == NULL) {
+            ERROR_(ctx, error, "could not find namespace, ns='%s'", import->name)
+            goto release_namespaces;
+        }
+        error = module_info_set_ns_(info_inner, import->name, true);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            ERROR_SIMPLE_(ctx, error, "could not insert namespace into the module info")
+            goto release_namespaces;
+        }
+    }
+
+    // Init imports.
+    FimoArrayList imports = fimo_array_list_new();
+    for (FimoISize i = 0; i < export->symbol_imports_count; i++) {
+        const FimoModuleSymbolImport *import = &export->symbol_imports[i];
+        const struct Symbol_ *symbol = ctx_get_symbol_compatible_(ctx, import->name, import->ns, import->version);
+        if (symbol == NULL) {
+            ERROR_(ctx, error, "could not find symbol, symbol='%s', ns='%s'", import->name, import->ns)
+            goto release_imports;
+        }
+        const struct Module_ *module = ctx_get_module_(ctx, symbol->module);
+        FIMO_DEBUG_ASSERT(module);
+        const struct ModuleInfo_ *module_info = module_info_from_module_(module->module);
+        struct ModuleInfoInner_ *module_info_inner = module_info_lock_(module_info);
+        const struct ModuleInfoSymbol_ *module_info_symbol =
+                module_info_get_symbol_(module_info_inner, symbol->name, symbol->ns, symbol->version);
+        FIMO_DEBUG_ASSERT(module_info_symbol);
+        const FimoModuleRawSymbol *raw_symbol = &module_info_symbol->symbol;
+        error = fimo_array_list_push(&imports, sizeof(FimoModuleRawSymbol *), _Alignof(FimoModuleRawSymbol *),
+                                     &raw_symbol, NULL);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            module_info_unlock_(module_info_inner);
+            ERROR_SIMPLE_(ctx, error, "could not insert symbol into the import table")
+            goto release_imports;
+        }
+        if (module_info_get_dependency_(info_inner, symbol->module) == NULL) {
+            error = module_info_set_dependency_(info_inner, module->module->module_info, true);
+            if (FIMO_RESULT_IS_ERROR(error)) {
+                module_info_unlock_(module_info_inner);
+                ERROR_SIMPLE_(ctx, error, "could not insert dependency into the module info")
+                goto release_imports;
+            }
+        }
+        // Remember the imported symbol, so that its users can be queried.
+        struct ModuleInfoDependency_ *dependency =
+                (struct ModuleInfoDependency_ *)module_info_get_dependency_(info_inner, symbol->module);
+        error = module_info_dependency_add_symbol_(dependency, raw_symbol);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            module_info_unlock_(module_info_inner);
+            ERROR_SIMPLE_(ctx, error, "could not record the imported symbol")
+            goto release_imports;
+        }
+        module_info_unlock_(module_info_inner);
+    }
+    (*element)->imports = imports.elements;
+
+    // Init the module.
+    if (export->module_constructor) {
+        void *module_data = NULL;
+        module_info_unlock_(info_inner);
+        loading_set_unlock_(set);
+        ctx_unlock_(ctx);
+        error = export->module_constructor(*element, set, &module_data);
+        ctx_lock_(ctx);
+        loading_set_lock_(set);
+        module_info_lock_(info);
+        (*element)->module_data = module_data;
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            ERROR_SIMPLE_(ctx, error, "could not construct the module data")
+            goto release_imports;
+        }
+    }
+
+    // Init the static exports.
+    FimoArrayList exports = fimo_array_list_new();
+    for (FimoISize i = 0; i < export->symbol_exports_count; i++) {
+        const FimoModuleSymbolExport *symbol = &export->symbol_exports[i];
+        const struct ModuleInfoSymbol_ *info_symbol;
+        error = module_info_set_symbol_(info_inner, symbol->name, symbol->ns, symbol->version, NULL, symbol->symbol,
+                                        &info_symbol);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            ERROR_SIMPLE_(ctx, error, "could not insert symbol into the module info")
+            goto release_exports;
+        }
+        FIMO_DEBUG_ASSERT(info_symbol)
+        const FimoModuleRawSymbol *raw_symbol = &info_symbol->symbol;
+        error = fimo_array_list_push(&exports, sizeof(const FimoModuleRawSymbol *),
+                                     _Alignof(const FimoModuleRawSymbol *), &raw_symbol, NULL);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            ERROR_SIMPLE_(ctx, error, "could not insert symbol into the export table")
+            goto release_exports;
+        }
+    }
+
+    // Init the dynamic exports.
+    for (FimoISize i = 0; i < export->dynamic_symbol_exports_count; i++) {
+        const FimoModuleDynamicSymbolExport *symbol = &export->dynamic_symbol_exports[i];
+        void *sym;
+        error = symbol->constructor(*element, &sym);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            ERROR_(ctx, error, "could not construct symbol, symbol='%s', ns='%s'", symbol->name, symbol->ns)
+            goto release_exports;
+        }
+        const struct ModuleInfoSymbol_ *info_symbol;
+        error = module_info_set_symbol_(info_inner, symbol->name, symbol->ns, symbol->version, symbol->destructor, sym,
+                                        &info_symbol);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            ERROR_SIMPLE_(ctx, error, "could not insert symbol into the module info")
+            symbol->destructor(sym);
+            goto release_exports;
+        }
+        FIMO_DEBUG_ASSERT(info_symbol)
+        const FimoModuleRawSymbol *raw_symbol = &info_symbol->symbol;
+        error = fimo_array_list_push(&exports, sizeof(const FimoModuleRawSymbol *),
+                                     _Alignof(const FimoModuleRawSymbol *), &raw_symbol, NULL);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            ERROR_SIMPLE_(ctx, error, "could not insert symbol into the export table")
+            goto release_exports;
+        }
+    }
+    (*element)->exports = exports.elements;
+
+    module_info_unlock_(info_inner);
+
+    return FIMO_EOK;
+
+release_exports:
+    if ((*element)->exports == NULL) {
+        fimo_array_list_free(&exports, sizeof(const FimoModuleRawSymbol *), _Alignof(const FimoModuleRawSymbol *),
+                             NULL);
+    }
+release_imports:
+    if ((*element)->imports == NULL) {
+        fimo_array_list_free(&imports, sizeof(FimoModuleRawSymbol *), _Alignof(FimoModuleRawSymbol *), NULL);
+    }
+release_namespaces:;
+release_resources:;
+    if ((*element)->resources == NULL) {
+        fimo_array_list_free(&resources, sizeof(const char *), _Alignof(const char *), NULL);
+    }
+release_parameters:
+    if ((*element)->parameters == NULL) {
+        fimo_array_list_free(&params, sizeof(FimoModuleParam *), _Alignof(FimoModuleParam *), NULL);
+    }
+release_ctx:
+    fimo_internal_context_release(TO_CTX_(ctx));
+    module_info_unlock_(info_inner);
+    module_info_release_(info, false);
+
+    if (*element != NULL) {
+        fimo_free(*element);
+    }
+
+    return error;
+}
+
+static void fi_module_free_(struct ModuleInfoInner_ *info_inner, FimoContext *context, const bool cleanup_export) {
+    FIMO_DEBUG_ASSERT(info_inner && !module_info_is_detached_(info_inner))
+    FimoModule *module = (FimoModule *)info_inner->module;
+    const struct ModuleInfo_ *info = module_info_from_inner_(info_inner);
+    module_info_detach_(info_inner, cleanup_export);
+    module_info_unlock_(info_inner);
+    module_info_release_(info, true);
+
+    if (module->parameters) {
+        fimo_free((void *)module->parameters);
+        module->parameters = NULL;
+    }
+    if (module->resources) {
+        fimo_free((void *)module->resources);
+        module->resources = module;
+    }
+    if (module->imports) {
+        fimo_free((void *)module->imports);
+        module->imports = NULL;
+    }
+
+    if (context) {
+        *context = module->context;
+    }
+    else {
+        fimo_internal_context_release(module->context.data);
+    }
+}
+
+///////////////////////////////////////////////////////////////////////
+//// Fimo Module Info
+///////////////////////////////////////////////////////////////////////
+
+static void fi_module_info_acquire_(const FimoModuleInfo *info) {
+    FIMO_DEBUG_ASSERT(info)
+    const struct ModuleInfo_ *module_info = module_info_from_module_info_(info);
+    module_info_acquire_(module_info);
+}
+
+static void fi_module_info_release_(const FimoModuleInfo *info) {
+    FIMO_DEBUG_ASSERT(info)
+    const struct ModuleInfo_ *module_info = module_info_from_module_info_(info);
+    module_info_release_(module_info, true);
+}
+
+static bool fi_module_info_is_loaded(const FimoModuleInfo *info) {
+    FIMO_DEBUG_ASSERT(info)
+    const struct ModuleInfo_ *module_info = module_info_from_module_info_(info);
+    struct ModuleInfoInner_ *info_inner = module_info_lock_(module_info);
+    bool loaded = !module_info_is_detached_(info_inner);
+    module_info_unlock_(info_inner);
+    return loaded;
+}
+
+static FimoResult fi_module_info_lock_unload_(const FimoModuleInfo *info) {
+    FIMO_DEBUG_ASSERT(info);
+    const struct ModuleInfo_ *module_info = module_info_from_module_info_(info);
+    struct ModuleInfoInner_ *info_inner = module_info_lock_(module_info);
+    const FimoResult error = module_info_prevent_unload_(info_inner);
+    module_info_unlock_(info_inner);
+    return error;
+}
+
+static void fi_module_info_unlock_unload(const FimoModuleInfo *info) {
+    FIMO_DEBUG_ASSERT(info);
+    const struct ModuleInfo_ *module_info = module_info_from_module_info_(info);
+    struct ModuleInfoInner_ *info_inner = module_info_lock_(module_info);
+    module_info_allow_unload_(info_inner);
+    module_info_unlock_(info_inner);
+}
+
+///////////////////////////////////////////////////////////////////////
+//// Fimo Module Export
+///////////////////////////////////////////////////////////////////////
+
+static void fi_module_export_cleanup_(const FimoModuleExport *export) {
+    FIMO_DEBUG_ASSERT(export)
+
+    // If the modifiers list is invalid we do nothing.
+    if ((export->modifiers == NULL && export->modifiers_count != 0) ||
+        (export->modifiers != NULL && export->modifiers_count == 0)) {
+        return;
+    }
+
+    for (FimoISize i = 0; i < (FimoISize) export->modifiers_count; i++) {
+        const FimoModuleExportModifier *modifier = &export->modifiers[i];
+
+        // Skip invalid modifiers.
+        if (modifier->key < 0 || modifier->key >= FIMO_MODULE_EXPORT_MODIFIER_KEY_LAST) {
+            continue;
+        }
+
+        switch (modifier->key) {
+            case FIMO_MODULE_EXPORT_MODIFIER_KEY_DESTRUCTOR: {
+                const FimoModuleExportModifierDestructor *value = modifier->value;
+                if (value == NULL) {
+                    continue;
+                }
+                value->destructor(value->data);
+                break;
+            }
+            case FIMO_MODULE_EXPORT_MODIFIER_KEY_DEPENDENCY: {
+                const FimoModuleInfo *value = modifier->value;
+                if (value == NULL) {
+                    continue;
+                }
+                FIMO_MODULE_INFO_RELEASE(value);
+                break;
+            }
+            default:
+                break;
+        }
+    }
+}
+
+static bool fi_module_export_parameters_are_valid_(const FimoModuleExport *export, FimoInternalModuleContext *ctx) {
+    FIMO_DEBUG_ASSERT(export && ctx)
+    if ((export->parameters == NULL && export->parameters_count != 0) ||
+        (export->parameters != NULL && export->parameters_count == 0)) {
+        WARN_(ctx, "invalid parameters count, module='%s', parameters='%p', parameters_count='%u'", export->name,
+              (void *)export->parameters, export->parameters_count)
+        return false;
+    }
+    for (FimoISize i = 0; i < (FimoISize) export->parameters_count; i++) {
+        const FimoModuleParamDecl *param = &export->parameters[i];
+        if (param->name == NULL) {
+            WARN_(ctx, "parameter name is 'NULL', module='%s'", export->name)
+            return false;
+        }
+        if (param->type > FIMO_MODULE_PARAM_TYPE_I64) {
+            WARN_(ctx, "invalid parameter type, module='%s', parameter='%s', type='%d'", export->name, param->name,
+                  param->type)
+            return false;
+        }
+        if (param->read_access > FIMO_MODULE_PARAM_ACCESS_PRIVATE) {
+            WARN_(ctx, "invalid parameter read access, module='%s', parameter='%s', access='%d'", export->name,
+                  param->name, param->read_access)
+            return false;
+        }
+        if (param->write_access > FIMO_MODULE_PARAM_ACCESS_PRIVATE) {
+            WARN_(ctx, "invalid parameter write access, module='%s', parameter='%s', access='%d'", export->name,
+                  param->name, param->write_access)
+            return false;
+        }
+        if (param->setter == NULL) {
+            WARN_(ctx, "parameter setter is 'NULL', module='%s', parameter='%s'", export->name, param->name)
+            return false;
+        }
+        if (param->getter == NULL) {
+            WARN_(ctx, "parameter getter is 'NULL', module='%s', parameter='%s'", export->name, param->name)
+            return false;
+        }
+
+        for (FimoISize j = 0; j < i; j++) {
+            const FimoModuleParamDecl *p = &export->parameters[j];
+            if (strcmp(param->name, p->name) == 0) {
+                WARN_(ctx, "duplicate parameter, module='%s', parameter='%s'", export->name, param->name)
+                return false;
+            }
+        }
+    }
+
+    return true;
+}
+
+static bool resource_path_has_parent_component_(const char *path) {
+    FIMO_DEBUG_ASSERT(path)
+    const char *component = path;
+    for (const char *c = path;; c++) {
+        if (*c == '/' || *c == '\\' || *c == '\0') {
+            if (c - component == 2 && component[0] == '.' && component[1] == '.') {
+                return true;
+            }
+            if (*c == '\0') {
+                return false;
+            }
+            component = c + 1;
+        }
+    }
+}
+
+static bool fi_module_export_resources_are_valid_(const FimoModuleExport *export, FimoInternalModuleContext *ctx) {
+    FIMO_DEBUG_ASSERT(export && ctx)
+    if ((export->resources == NULL && export->resources_count != 0) ||
+        (export->resources != NULL && export->resources_count == 0)) {
+        WARN_(ctx, "invalid resources count, module='%s', resources='%p', resources_count='%u'", export->name,
+              (void *)export->resources, export->resources_count)
+        return false;
+    }
+    for (FimoISize i = 0; i < (FimoISize) export->resources_count; i++) {
+        const FimoModuleResourceDecl *resource = &export->resources[i];
+        if (resource->path == NULL) {
+            WARN_(ctx, "resource path is 'NULL', module='%s'", export->name)
+            return false;
+        }
+        if (resource->path[0] != '\0' && (resource->path[0] == '\\' || resource->path[0] == '/')) {
+            WARN_(ctx, "resource path begins with a slash, module='%s', resource='%s'", export->name, resource->path)
+            return false;
+        }
+        if (resource->path[0] != '\0' && resource->path[1] == ':') {
+            WARN_(ctx, "resource path begins with a drive prefix, module='%s', resource='%s'", export->name,
+                  resource->path)
+            return false;
+        }
+        if (resource_path_has_parent_component_(resource->path)) {
+            WARN_(ctx, "resource path escapes the module directory, module='%s', resource='%s'", export->name,
+                  resource->path)
+            return false;
+        }
+    }
+
+    return true;
+}
+
+static bool fi_module_export_namespaces_are_valid_(const FimoModuleExport *export, FimoInternalModuleContext *ctx) {
+    FIMO_DEBUG_ASSERT(export && ctx)
+    if ((export->namespace_imports == NULL && export->namespace_imports_count != 0) ||
+        (export->namespace_imports != NULL && export->namespace_imports_count == 0)) {
+        WARN_(ctx, "invalid namespace import count, module='%s', namespace_imports='%p', namespace_imports_count='%u'",
+              export->name, (void *)export->namespace_imports, export->namespace_imports_count)
+        return false;
+    }
+    for (FimoISize i = 0; i < (FimoISize) export->namespace_imports_count; i++) {
+        const FimoModuleNamespaceImport *ns = &export->namespace_imports[i];
+        if (ns->name == NULL) {
+            WARN_(ctx, "namespace import name is 'Null', module='%s'", export->name)
+            return false;
+        }
+    }
+
+    return true;
+}
+
+static bool fi_module_export_imports_are_valid_(const FimoModuleExport *export, FimoInternalModuleContext *ctx) {
+    FIMO_DEBUG_ASSERT(export && ctx)
+    if ((export->symbol_imports == NULL && export->symbol_imports_count != 0) ||
+        (export->symbol_imports != NULL && export->symbol_imports_count == 0)) {
+        WARN_(ctx, "invalid symbol import count, module='%s', symbol_imports='%p', symbol_imports_count='%u'",
+              export->name, (void *)export->symbol_imports, export->symbol_imports_count)
+        return false;
+    }
+    for (FimoISize i = 0; i < (FimoISize) export->symbol_imports_count; i++) {
+        const FimoModuleSymbolImport *sym = &export->symbol_imports[i];
+        if (sym->name == NULL) {
+            WARN_(ctx, "symbol import name is 'Null', module='%s'", export->name)
+            return false;
+        }
+        if (strcmp(sym->ns, GLOBAL_NS) != 0) {
+            bool found = false;
+            for (FimoISize j = 0; j < (FimoISize) export->namespace_imports_count; j++) {
+                const FimoModuleNamespaceImport *ns = &export->namespace_imports[j];
+                if (strcmp(sym->ns, ns->name) == 0) {
+                    found = true;
+                    break;
+                }
+            }
+            if (!found) {
+                WARN_(ctx, "symbol uses a namespace that was not imported, module='%s', symbol='%s', ns='%s'",
+                      export->name, sym->name, sym->ns)
+                return false;
+            }
+        }
+    }
+
+    return true;
+}
+
+static bool fi_module_export_static_exports_are_valid_(const FimoModuleExport *export, FimoInternalModuleContext *ctx) {
+    FIMO_DEBUG_ASSERT(export && ctx)
+    if ((export->symbol_exports == NULL && export->symbol_exports_count != 0) ||
+        (export->symbol_exports != NULL && export->symbol_exports_count == 0)) {
+        WARN_(ctx, "invalid symbol export count, module='%s', symbol_exports='%p', symbol_exports_count='%u'",
+              export->name, (void *)export->symbol_exports, export->symbol_exports_count)
+        return false;
+    }
+    for (FimoISize i = 0; i < (FimoISize) export->symbol_exports_count; i++) {
+        const FimoModuleSymbolExport *sym = &export->symbol_exports[i];
+        if (sym->name == NULL) {
+            WARN_(ctx, "symbol export name is 'NULL', module='%s'", export->name)
+            return false;
+        }
+        if (sym->ns == NULL) {
+            WARN_(ctx, "symbol export namespace is 'NULL', module='%s', symbol='%s'", export->name, sym->name)
+            return false;
+        }
+        if (sym->symbol == NULL) {
+            WARN_(ctx, "symbol export is 'NULL', module='%s', symbol='%s', ns='%s'", export->name, sym->name, sym->ns)
+            return false;
+        }
+        for (FimoISize j = 0; j < (FimoISize) export->symbol_imports_count; j++) {
+            const FimoModuleSymbolImport *s = &export->symbol_imports[j];
+            if (strcmp(sym->name, s->name) == 0 && strcmp(sym->ns, s->ns) == 0) {
+                WARN_(ctx, "duplicate symbol, module='%s', symbol='%s', ns='%s'", export->name, sym->name, sym->ns)
+                return false;
+            }
+        }
+        for (FimoISize j = 0; j < i; j++) {
+            const FimoModuleSymbolExport *s = &export->symbol_exports[j];
+            if (strcmp(sym->name, s->name) == 0 && strcmp(sym->ns, s->ns) == 0) {
+                WARN_(ctx, "duplicate symbol, module='%s', symbol='%s', ns='%s'", export->name, sym->name, sym->ns)
+                return false;
+            }
+        }
+    }
+
+    return true;
+}
+
+static bool fi_module_export_dynamic_exports_are_valid_(const FimoModuleExport *export,
+                                                        FimoInternalModuleContext *ctx) {
+    FIMO_DEBUG_ASSERT(export && ctx)
+    if ((export->dynamic_symbol_exports == NULL && export->dynamic_symbol_exports_count != 0) ||
+        (export->dynamic_symbol_exports != NULL && export->dynamic_symbol_exports_count == 0)) {
+        WARN_(ctx,
+              "invalid dynamic symbol export count, module='%s', dynamic_symbol_exports='%p', "
+              "dynamic_symbol_exports_count='%u'",
+              export->name, (void *)export->dynamic_symbol_exports, export->dynamic_symbol_exports_count)
+        return false;
+    }
+    for (FimoISize i = 0; i < (FimoISize) export->dynamic_symbol_exports_count; i++) {
+        const FimoModuleDynamicSymbolExport *sym = &export->dynamic_symbol_exports[i];
+        if (sym->name == NULL) {
+            WARN_(ctx, "symbol export name is 'NULL', module='%s'", export->name)
+            return false;
+        }
+        if (sym->ns == NULL) {
+            WARN_(ctx, "symbol export namespace is 'NULL', module='%s', symbol='%s'", export->name, sym->name)
+            return false;
+        }
+        if (sym->constructor == NULL) {
+            WARN_(ctx, "symbol constructor is 'NULL', module='%s', symbol='%s', ns='%s'", export->name, sym->name,
+                  sym->ns)
+            return false;
+        }
+        if (sym->destructor == NULL) {
+            WARN_(ctx, "symbol destructor is 'NULL', module='%s', symbol='%s', ns='%s'", export->name, sym->name,
+                  sym->ns)
+            return false;
+        }
+        for (FimoISize j = 0; j < (FimoISize) export->symbol_imports_count; j++) {
+            const FimoModuleSymbolImport *s = &export->symbol_imports[j];
+            if (strcmp(sym->name, s->name) == 0 && strcmp(sym->ns, s->ns) == 0) {
+                WARN_(ctx, "duplicate symbol, module='%s', symbol='%s', ns='%s'", export->name, sym->name, sym->ns)
+                return false;
+            }
+        }
+        for (FimoISize j = 0; j < (FimoISize) export->symbol_exports_count; j++) {
+            const FimoModuleSymbolExport *s = &export->symbol_exports[j];
+            if (strcmp(sym->name, s->name) == 0 && strcmp(sym->ns, s->ns) == 0) {
+                WARN_(ctx, "duplicate symbol, module='%s', symbol='%s', ns='%s'", export->name, sym->name, sym->ns)
+                return false;
+            }
+        }
+        for (FimoISize j = 0; j < i; j++) {
+            const FimoModuleDynamicSymbolExport *s = &export->dynamic_symbol_exports[j];
+            if (strcmp(sym->name, s->name) == 0 && strcmp(sym->ns, s->ns) == 0) {
+                WARN_(ctx, "duplicate symbol, module='%s', symbol='%s', ns='%s'", export->name, sym->name, sym->ns)
+                return false;
+            }
+        }
+    }
+
+    return true;
+}
+
+static bool fi_module_export_modifiers_are_valid_(const FimoModuleExport *export, FimoInternalModuleContext *ctx) {
+    FIMO_DEBUG_ASSERT(export && ctx)
+    if ((export->modifiers == NULL && export->modifiers_count != 0) ||
+        (export->modifiers != NULL && export->modifiers_count == 0)) {
+        WARN_(ctx, "invalid modifiers count, module='%s', modifiers='%p', modifiers_count='%u'", export->name,
+              (void *)export->modifiers, export->modifiers_count)
+        return false;
+    }
+    for (FimoISize i = 0; i < (FimoISize) export->modifiers_count; i++) {
+        const FimoModuleExportModifier *modifier = &export->modifiers[i];
+        switch (modifier->key) {
+            case FIMO_MODULE_EXPORT_MODIFIER_KEY_DESTRUCTOR:
+            case FIMO_MODULE_EXPORT_MODIFIER_KEY_DEPENDENCY: {
+                if (modifier->value == NULL) {
+                    WARN_(ctx, "no value set for modifier, module='%s', modifier='%d'", export->name, modifier->key)
+                    return false;
+                }
+                break;
+            }
+            case FIMO_MODULE_EXPORT_MODIFIER_KEY_FEATURE: {
+                const FimoModuleExportModifierFeature *feature = modifier->value;
+                if (feature == NULL) {
+                    WARN_(ctx, "no value set for modifier, module='%s', modifier='%d'", export->name, modifier->key)
+                    return false;
+                }
+                if (feature->name == NULL) {
+                    WARN_(ctx, "feature name is 'NULL', module='%s'", export->name)
+                    return false;
+                }
+                if (feature->stability < FIMO_MODULE_FEATURE_STABILITY_STABLE ||
+                    feature->stability > FIMO_MODULE_FEATURE_STABILITY_DEPRECATED) {
+                    WARN_(ctx, "invalid feature stability, module='%s', feature='%s', stability='%d'", export->name,
+                          feature->name, feature->stability)
+                    return false;
+                }
+                for (FimoISize j = 0; j < i; j++) {
+                    const FimoModuleExportModifier *m = &export->modifiers[j];
+                    if (m->key == FIMO_MODULE_EXPORT_MODIFIER_KEY_FEATURE &&
+                        strcmp(((const FimoModuleExportModifierFeature *)m->value)->name, feature->name) == 0) {
+                        WARN_(ctx, "duplicate feature, module='%s', feature='%s'", export->name, feature->name)
+                        return false;
+                    }
+                }
+                break;
+            }
+            default: {
+                WARN_(ctx, "unrecognized modifier key, module='%s', modifier='%d'", export->name, modifier->key)
+                return false;
+            }
+        }
+    }
+
+    return true;
+}
+
+static bool fi_module_export_is_valid_(const FimoModuleExport *export, FimoInternalModuleContext *ctx) {
+    FIMO_DEBUG_ASSERT(export && ctx)
+    _Static_assert(FIMO_MODULE_EXPORT_ABI == 1, "Unknown module abi version");
+    if (export->type != FIMO_STRUCT_TYPE_MODULE_EXPORT) {
+        WARN_(ctx, "invalid module struct type, type='%d'", export->type)
+        return false;
+    }
+    if (export->next != NULL) {
+        WARN_(ctx, "next pointer must currently be 'NULL', next='%p'", (void *)export->next)
+        return false;
+    }
+    // The layout of the remaining fields depends on the abi version, so we must not access them
+    // before the abi version is confirmed.
+    if (export->export_abi != FIMO_MODULE_EXPORT_ABI) {
+        WARN_(ctx, "incompatible module abi version, expected='%d', found='%d'", FIMO_MODULE_EXPORT_ABI,
+              export->export_abi)
+        return false;
+    }
+    const FimoVersion context_version =
+            FIMO_VERSION_LONG(FIMO_VERSION_MAJOR, FIMO_VERSION_MINOR, FIMO_VERSION_PATCH, FIMO_VERSION_BUILD_NUMBER);
+    if (!fimo_version_compatible(&context_version, &export->context_version)) {
+        WARN_(ctx, "incompatible context version, module='%s', expected='%u.%u.%u', found='%u.%u.%u'",
+              export->name ? export->name : "", context_version.major, context_version.minor, context_version.patch,
+              export->context_version.major, export->context_version.minor, export->context_version.patch)
+        return false;
+    }
+    if (export->name == NULL) {
+        WARN_SIMPLE_(ctx, "module name is 'NULL'")
+        return false;
+    }
+    if ((export->module_constructor == NULL) != (export->module_destructor == NULL)) {
+        WARN_(ctx, "module constructor must both be set or null, module='%s'", export->name)
+        return false;
+    }
+
+    if (!fi_module_export_parameters_are_valid_(export, ctx) || !fi_module_export_resources_are_valid_(export, ctx) ||
+        !fi_module_export_namespaces_are_valid_(export, ctx) || !fi_module_export_imports_are_valid_(export, ctx) ||
+        !fi_module_export_imports_are_valid_(export, ctx) || !fi_module_export_static_exports_are_valid_(export, ctx) ||
+        !fi_module_export_dynamic_exports_are_valid_(export, ctx) ||
+        !fi_module_export_modifiers_are_valid_(export, ctx)) {
+        return false;
+    }
+
+    return true;
+}
+
+///////////////////////////////////////////////////////////////////////
+//// Trampoline functions
+///////////////////////////////////////////////////////////////////////
+
+FimoResult fimo_internal_trampoline_module_pseudo_module_new(void *ctx, const FimoModule **module) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_pseudo_module_new(TO_MODULE_CTX_(ctx), module);
+}
+
+FimoResult fimo_internal_trampoline_module_pseudo_module_destroy(void *ctx, const FimoModule *module,
+                                                                 FimoContext *module_context) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_pseudo_module_destroy(TO_MODULE_CTX_(ctx), module, module_context);
+}
+
+FimoResult fimo_internal_trampoline_module_set_new(void *ctx, FimoModuleLoadingSet **set) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_set_new(TO_MODULE_CTX_(ctx), set);
+}
+
+FimoResult fimo_internal_trampoline_module_set_has_module(void *ctx, FimoModuleLoadingSet *set, const char *name,
+                                                          bool *has_module) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_set_has_module(TO_MODULE_CTX_(ctx), set, name, has_module);
+}
+
+FimoResult fimo_internal_trampoline_module_set_has_symbol(void *ctx, FimoModuleLoadingSet *set, const char *name,
+                                                          const char *ns, FimoVersion version, bool *has_symbol) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_set_has_symbol(TO_MODULE_CTX_(ctx), set, name, ns, version, has_symbol);
+}
+
+FimoResult fimo_internal_trampoline_module_set_append_callback(void *ctx, FimoModuleLoadingSet *set,
+                                                               const char *module_name,
+                                                               FimoModuleLoadingSuccessCallback on_success,
+                                                               FimoModuleLoadingErrorCallback on_error,
+                                                               void *user_data) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_set_append_callback(TO_MODULE_CTX_(ctx), set, module_name, on_success, on_error,
+                                                    user_data);
+}
+
+FimoResult fimo_internal_trampoline_module_set_append_freestanding_module(void *ctx, const FimoModule *module,
+                                                                          FimoModuleLoadingSet *set,
+                                                                          const FimoModuleExport *export) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_set_append_freestanding_module(TO_MODULE_CTX_(ctx), module, set, export);
+}
+
+FimoResult fimo_internal_trampoline_module_set_append_modules(
+        void *ctx, FimoModuleLoadingSet *set, const char *module_path, FimoModuleLoadingFilter filter,
+        void *filter_data, void (*export_iterator)(bool (*)(const FimoModuleExport *, void *), void *),
+        const void *binary_handle) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_set_append_modules(TO_MODULE_CTX_(ctx), set, module_path, filter, filter_data,
+                                                   export_iterator, binary_handle);
+}
+
+FimoResult fimo_internal_trampoline_module_set_append_group(void *ctx, FimoModuleLoadingSet *set, const char *group,
+                                                            const char *const *modules, const FimoUSize modules_count) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_set_append_group(TO_MODULE_CTX_(ctx), set, group, modules, modules_count);
+}
+
+FimoResult fimo_internal_trampoline_module_set_dismiss(void *ctx, FimoModuleLoadingSet *set) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_set_dismiss(TO_MODULE_CTX_(ctx), set);
+}
+
+FimoResult fimo_internal_trampoline_module_set_finish(void *ctx, FimoModuleLoadingSet *set) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_set_finish(TO_MODULE_CTX_(ctx), set);
+}
+
+FimoResult fimo_internal_trampoline_module_find_by_name(void *ctx, const char *name, const FimoModuleInfo **module) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_find_by_name(TO_MODULE_CTX_(ctx), name, module);
+}
+
+FimoResult fimo_internal_trampoline_module_find_by_symbol(void *ctx, const char *name, const char *ns,
+                                                          FimoVersion version, const FimoModuleInfo **module) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_find_by_symbol(TO_MODULE_CTX_(ctx), name, ns, version, module);
+}
 
-    module_info_unlock_(info_inner);
+FimoResult fimo_internal_trampoline_module_namespace_exists(void *ctx, const char *ns, bool *exists) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_namespace_exists(TO_MODULE_CTX_(ctx), ns, exists);
+}
 
-    return FIMO_EOK;
+FimoResult fimo_internal_trampoline_module_namespace_include(void *ctx, const FimoModule *module, const char *ns) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_namespace_include(TO_MODULE_CTX_(ctx), module, ns);
+}
 
-release_exports:
-    if ((*element)->exports == NULL) {
-        fimo_array_list_free(&exports, sizeof(const FimoModuleRawSymbol *), _Alignof(const FimoModuleRawSymbol *),
-                             NULL);
-    }
-release_imports:
-    if ((*element)->imports == NULL) {
-        fimo_array_list_free(&imports, sizeof(FimoModuleRawSymbol *), _Alignof(FimoModuleRawSymbol *), NULL);
-    }
-release_namespaces:;
-release_resources:;
-    if ((*element)->resources == NULL) {
-        fimo_array_list_free(&resources, sizeof(const char *), _Alignof(const char *), NULL);
-    }
-release_parameters:
-    if ((*element)->parameters == NULL) {
-        fimo_array_list_free(&params, sizeof(FimoModuleParam *), _Alignof(FimoModuleParam *), NULL);
-    }
-release_ctx:
-    fimo_internal_context_release(TO_CTX_(ctx));
-    module_info_unlock_(info_inner);
-    module_info_release_(info, false);
+FimoResult fimo_internal_trampoline_module_namespace_exclude(void *ctx, const FimoModule *module, const char *ns) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_namespace_exclude(TO_MODULE_CTX_(ctx), module, ns);
+}
 
-    if (*element != NULL) {
-        fimo_free(*element);
-    }
+FimoResult fimo_internal_trampoline_module_namespace_included(void *ctx, const FimoModule *module, const char *ns,
+                                                              bool *is_included, bool *is_static) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_namespace_included(TO_MODULE_CTX_(ctx), module, ns, is_included, is_static);
+}
 
-    return error;
+FimoResult fimo_internal_trampoline_module_acquire_dependency(void *ctx, const FimoModule *module,
+                                                              const FimoModuleInfo *dependency) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_acquire_dependency(TO_MODULE_CTX_(ctx), module, dependency);
 }
 
-static void fi_module_free_(struct ModuleInfoInner_ *info_inner, FimoContext *context) {
-    FIMO_DEBUG_ASSERT(info_inner && !module_info_is_detached_(info_inner))
-    FimoModule *module = (FimoModule *)info_inner->module;
-    const struct ModuleInfo_ *info = module_info_from_inner_(info_inner);
-    module_info_detach_(info_inner, true);
-    module_info_unlock_(info_inner);
-    module_info_release_(info, true);
+FimoResult fimo_internal_trampoline_module_relinquish_dependency(void *ctx, const FimoModule *module,
+                                                                 const FimoModuleInfo *dependency) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_relinquish_dependency(TO_MODULE_CTX_(ctx), module, dependency);
+}
 
-    if (module->parameters) {
-        fimo_free((void *)module->parameters);
-        module->parameters = NULL;
-    }
-    if (module->resources) {
-        fimo_free((void *)module->resources);
-        module->resources = module;
-    }
-    if (module->imports) {
-        fimo_free((void *)module->imports);
-        module->imports = NULL;
-    }
+FimoResult fimo_internal_trampoline_module_has_dependency(void *ctx, const FimoModule *module,
+                                                          const FimoModuleInfo *other, bool *has_dependency,
+                                                          bool *is_static) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_has_dependency(TO_MODULE_CTX_(ctx), module, other, has_dependency, is_static);
+}
 
-    if (context) {
-        *context = module->context;
-    }
-    else {
-        fimo_internal_context_release(module->context.data);
-    }
+FimoResult fimo_internal_trampoline_module_param_query(void *ctx, const char *module_name, const char *param,
+                                                       FimoModuleParamType *type, FimoModuleParamAccess *read,
+                                                       FimoModuleParamAccess *write) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_param_query(TO_MODULE_CTX_(ctx), module_name, param, type, read, write);
 }
 
-///////////////////////////////////////////////////////////////////////
-//// Fimo Module Info
-///////////////////////////////////////////////////////////////////////
+FimoResult fimo_internal_trampoline_module_param_set_public(void *ctx, const void *value, FimoModuleParamType type,
+                                                            const char *module_name, const char *param) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_param_set_public(TO_MODULE_CTX_(ctx), value, type, module_name, param);
+}
 
-static void fi_module_info_acquire_(const FimoModuleInfo *info) {
-    FIMO_DEBUG_ASSERT(info)
-    const struct ModuleInfo_ *module_info = module_info_from_module_info_(info);
-    module_info_acquire_(module_info);
+FimoResult fimo_internal_trampoline_module_param_get_public(void *ctx, void *value, FimoModuleParamType *type,
+                                                            const char *module_name, const char *param) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_param_get_public(TO_MODULE_CTX_(ctx), value, type, module_name, param);
 }
 
-static void fi_module_info_release_(const FimoModuleInfo *info) {
-    FIMO_DEBUG_ASSERT(info)
-    const struct ModuleInfo_ *module_info = module_info_from_module_info_(info);
-    module_info_release_(module_info, true);
+FimoResult fimo_internal_trampoline_module_param_set_dependency(void *ctx, const FimoModule *module, const void *value,
+                                                                FimoModuleParamType type, const char *module_name,
+                                                                const char *param) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_param_set_dependency(TO_MODULE_CTX_(ctx), module, value, type, module_name, param);
 }
 
-static bool fi_module_info_is_loaded(const FimoModuleInfo *info) {
-    FIMO_DEBUG_ASSERT(info)
-    const struct ModuleInfo_ *module_info = module_info_from_module_info_(info);
-    struct ModuleInfoInner_ *info_inner = module_info_lock_(module_info);
-    bool loaded = !module_info_is_detached_(info_inner);
-    module_info_unlock_(info_inner);
-    return loaded;
+FimoResult fimo_internal_trampoline_module_param_get_dependency(void *ctx, const FimoModule *module, void *value,
+                                                                FimoModuleParamType *type, const char *module_name,
+                                                                const char *param) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_param_get_dependency(TO_MODULE_CTX_(ctx), module, value, type, module_name, param);
 }
 
-static FimoResult fi_module_info_lock_unload_(const FimoModuleInfo *info) {
-    FIMO_DEBUG_ASSERT(info);
-    const struct ModuleInfo_ *module_info = module_info_from_module_info_(info);
-    struct ModuleInfoInner_ *info_inner = module_info_lock_(module_info);
-    const FimoResult error = module_info_prevent_unload_(info_inner);
-    module_info_unlock_(info_inner);
-    return error;
+FimoResult fimo_internal_trampoline_module_load_symbol(void *ctx, const FimoModule *module, const char *name,
+                                                       const char *ns, FimoVersion version,
+                                                       const FimoModuleRawSymbol **symbol) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_load_symbol(TO_MODULE_CTX_(ctx), module, name, ns, version, symbol);
 }
 
-static void fi_module_info_unlock_unload(const FimoModuleInfo *info) {
-    FIMO_DEBUG_ASSERT(info);
-    const struct ModuleInfo_ *module_info = module_info_from_module_info_(info);
-    struct ModuleInfoInner_ *info_inner = module_info_lock_(module_info);
-    module_info_allow_unload_(info_inner);
-    module_info_unlock_(info_inner);
+FimoResult fimo_internal_trampoline_module_unload(void *ctx, const FimoModuleInfo *module) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_unload(TO_MODULE_CTX_(ctx), module);
 }
 
-///////////////////////////////////////////////////////////////////////
-//// Fimo Module Export
-///////////////////////////////////////////////////////////////////////
+FimoResult fimo_internal_trampoline_module_param_set_private(void *ctx, const FimoModule *module, const void *value,
+                                                             FimoModuleParamType type, FimoModuleParam *param) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_param_set_private(TO_MODULE_CTX_(ctx), module, value, type, param);
+}
 
-static void fi_module_export_cleanup_(const FimoModuleExport *export) {
-    FIMO_DEBUG_ASSERT(export)
+FimoResult fimo_internal_trampoline_module_param_get_private(void *ctx, const FimoModule *module, void *value,
+                                                             FimoModuleParamType *type, const FimoModuleParam *param) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_param_get_private(TO_MODULE_CTX_(ctx), module, value, type, param);
+}
 
-    // If the modifiers list is invalid we do nothing.
-    if ((export->modifiers == NULL && export->modifiers_count != 0) ||
-        (export->modifiers != NULL && export->modifiers_count == 0)) {
-        return;
-    }
+FimoResult fimo_internal_trampoline_module_param_set_inner(void *ctx, const FimoModule *module, const void *value,
+                                                           FimoModuleParamType type, FimoModuleParamData *param) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_param_set_inner(TO_MODULE_CTX_(ctx), module, value, type, param);
+}
 
-    for (FimoISize i = 0; i < (FimoISize) export->modifiers_count; i++) {
-        const FimoModuleExportModifier *modifier = &export->modifiers[i];
+FimoResult fimo_internal_trampoline_module_get_inner(void *ctx, const FimoModule *module, void *value,
+                                                     FimoModuleParamType *type, const FimoModuleParamData *param) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_param_get_inner(TO_MODULE_CTX_(ctx), module, value, type, param);
+}
 
-        // Skip invalid modifiers.
-        if (modifier->key < 0 || modifier->key >= FIMO_MODULE_EXPORT_MODIFIER_KEY_LAST) {
-            continue;
-        }
+FimoResult fimo_internal_trampoline_module_symbol_use_counts(void *ctx, const FimoModuleInfo *module,
+                                                             FimoModuleSymbolUseCount **use_counts,
+                                                             FimoUSize *use_counts_count) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_symbol_use_counts(TO_MODULE_CTX_(ctx), module, use_counts, use_counts_count);
+}
 
-        switch (modifier->key) {
-            case FIMO_MODULE_EXPORT_MODIFIER_KEY_DESTRUCTOR: {
-                const FimoModuleExportModifierDestructor *value = modifier->value;
-                if (value == NULL) {
-                    continue;
-                }
-                value->destructor(value->data);
-                break;
-            }
-            case FIMO_MODULE_EXPORT_MODIFIER_KEY_DEPENDENCY: {
-                const FimoModuleInfo *value = modifier->value;
-                if (value == NULL) {
-                    continue;
-                }
-                FIMO_MODULE_INFO_RELEASE(value);
-                break;
-            }
-            default:
-                break;
-        }
-    }
+FimoResult fimo_internal_trampoline_module_param_list(void *ctx, const char *module_name, FimoModuleParamInfo **params,
+                                                      FimoUSize *params_count) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_param_list(TO_MODULE_CTX_(ctx), module_name, params, params_count);
 }
 
-static bool fi_module_export_parameters_are_valid_(const FimoModuleExport *export, FimoInternalModuleContext *ctx) {
-    FIMO_DEBUG_ASSERT(export && ctx)
-    if ((export->parameters == NULL && export->parameters_count != 0) ||
-        (export->parameters != NULL && export->parameters_count == 0)) {
-        WARN_(ctx, "invalid parameters count, module='%s', parameters='%p', parameters_count='%u'", export->name,
-              (void *)export->parameters, export->parameters_count)
-        return false;
-    }
-    for (FimoISize i = 0; i < (FimoISize) export->parameters_count; i++) {
-        const FimoModuleParamDecl *param = &export->parameters[i];
-        if (param->name == NULL) {
-            WARN_(ctx, "parameter name is 'NULL', module='%s'", export->name)
-            return false;
-        }
-        if (param->type > FIMO_MODULE_PARAM_TYPE_I64) {
-            WARN_(ctx, "invalid parameter type, module='%s', parameter='%s', type='%d'", export->name, param->name,
-                  param->type)
-            return false;
-        }
-        if (param->read_access > FIMO_MODULE_PARAM_ACCESS_PRIVATE) {
-            WARN_(ctx, "invalid parameter read access, module='%s', parameter='%s', access='%d'", export->name,
-                  param->name, param->read_access)
-            return false;
-        }
-        if (param->write_access > FIMO_MODULE_PARAM_ACCESS_PRIVATE) {
-            WARN_(ctx, "invalid parameter write access, module='%s', parameter='%s', access='%d'", export->name,
-                  param->name, param->write_access)
-            return false;
-        }
-        if (param->setter == NULL) {
-            WARN_(ctx, "parameter setter is 'NULL', module='%s', parameter='%s'", export->name, param->name)
-            return false;
-        }
-        if (param->getter == NULL) {
-            WARN_(ctx, "parameter getter is 'NULL', module='%s', parameter='%s'", export->name, param->name)
-            return false;
-        }
+FimoResult fimo_internal_trampoline_module_param_set_override(void *ctx, const char *module_name, const char *param,
+                                                              const void *value, FimoModuleParamType type) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_param_set_override(TO_MODULE_CTX_(ctx), module_name, param, value, type);
+}
 
-        for (FimoISize j = 0; j < i; j++) {
-            const FimoModuleParamDecl *p = &export->parameters[j];
-            if (strcmp(param->name, p->name) == 0) {
-                WARN_(ctx, "duplicate parameter, module='%s', parameter='%s'", export->name, param->name)
-                return false;
-            }
-        }
-    }
+FimoResult fimo_internal_trampoline_module_loader_register(void *ctx, const FimoModuleLoader *loader) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_loader_register(TO_MODULE_CTX_(ctx), loader);
+}
 
-    return true;
+FimoResult fimo_internal_trampoline_module_loader_unregister(void *ctx, const char *name) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_loader_unregister(TO_MODULE_CTX_(ctx), name);
 }
 
-static bool fi_module_export_resources_are_valid_(const FimoModuleExport *export, FimoInternalModuleContext *ctx) {
-    FIMO_DEBUG_ASSERT(export && ctx)
-    if ((export->resources == NULL && export->resources_count != 0) ||
-        (export->resources != NULL && export->resources_count == 0)) {
-        WARN_(ctx, "invalid resources count, module='%s', resources='%p', resources_count='%u'", export->name,
-              (void *)export->resources, export->resources_count)
-        return false;
-    }
-    for (FimoISize i = 0; i < (FimoISize) export->resources_count; i++) {
-        const FimoModuleResourceDecl *resource = &export->resources[i];
-        if (resource->path == NULL) {
-            WARN_(ctx, "resource path is 'NULL', module='%s'", export->name)
-            return false;
-        }
-        if (resource->path[0] != '\0' && (resource->path[0] == '\\' || resource->path[0] == '/')) {
-            WARN_(ctx, "resource path begins with a slash, module='%s', resource='%s'", export->name, resource->path)
-            return false;
-        }
-    }
+FimoResult fimo_internal_trampoline_module_signature_set_policy(void *ctx, const FimoModuleSignaturePolicy policy) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_signature_set_policy(TO_MODULE_CTX_(ctx), policy);
+}
 
-    return true;
+FimoResult fimo_internal_trampoline_module_signature_trust_key(void *ctx, const FimoU8 *key) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_signature_trust_key(TO_MODULE_CTX_(ctx), key);
 }
 
-static bool fi_module_export_namespaces_are_valid_(const FimoModuleExport *export, FimoInternalModuleContext *ctx) {
-    FIMO_DEBUG_ASSERT(export && ctx)
-    if ((export->namespace_imports == NULL && export->namespace_imports_count != 0) ||
-        (export->namespace_imports != NULL && export->namespace_imports_count == 0)) {
-        WARN_(ctx, "invalid namespace import count, module='%s', namespace_imports='%p', namespace_imports_count='%u'",
-              export->name, (void *)export->namespace_imports, export->namespace_imports_count)
-        return false;
-    }
-    for (FimoISize i = 0; i < (FimoISize) export->namespace_imports_count; i++) {
-        const FimoModuleNamespaceImport *ns = &export->namespace_imports[i];
-        if (ns->name == NULL) {
-            WARN_(ctx, "namespace import name is 'Null', module='%s'", export->name)
-            return false;
-        }
-    }
+FimoResult fimo_internal_trampoline_module_fault_set_policy(void *ctx, const FimoModuleFaultPolicy policy) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_fault_set_policy(TO_MODULE_CTX_(ctx), policy);
+}
 
-    return true;
+FimoResult fimo_internal_trampoline_module_feature_list(void *ctx, const char *module_name,
+                                                        FimoModuleFeatureInfo **features, FimoUSize *features_count) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_feature_list(TO_MODULE_CTX_(ctx), module_name, features, features_count);
 }
 
-static bool fi_module_export_imports_are_valid_(const FimoModuleExport *export, FimoInternalModuleContext *ctx) {
-    FIMO_DEBUG_ASSERT(export && ctx)
-    if ((export->symbol_imports == NULL && export->symbol_imports_count != 0) ||
-        (export->symbol_imports != NULL && export->symbol_imports_count == 0)) {
-        WARN_(ctx, "invalid symbol import count, module='%s', symbol_imports='%p', symbol_imports_count='%u'",
-              export->name, (void *)export->symbol_imports, export->symbol_imports_count)
-        return false;
-    }
-    for (FimoISize i = 0; i < (FimoISize) export->symbol_imports_count; i++) {
-        const FimoModuleSymbolImport *sym = &export->symbol_imports[i];
-        if (sym->name == NULL) {
-            WARN_(ctx, "symbol import name is 'Null', module='%s'", export->name)
-            return false;
-        }
-        if (strcmp(sym->ns, GLOBAL_NS) != 0) {
-            bool found = false;
-            for (FimoISize j = 0; j < (FimoISize) export->namespace_imports_count; j++) {
-                const FimoModuleNamespaceImport *ns = &export->namespace_imports[j];
-                if (strcmp(sym->ns, ns->name) == 0) {
-                    found = true;
-                    break;
-                }
-            }
-            if (!found) {
-                WARN_(ctx, "symbol uses a namespace that was not imported, module='%s', symbol='%s', ns='%s'",
-                      export->name, sym->name, sym->ns)
-                return false;
-            }
-        }
-    }
+FimoResult fimo_internal_trampoline_module_feature_set_override(void *ctx, const char *module_name,
+                                                                const char *feature, const bool *enabled) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_feature_set_override(TO_MODULE_CTX_(ctx), module_name, feature, enabled);
+}
 
-    return true;
+FimoResult fimo_internal_trampoline_module_feature_enabled(void *ctx, const FimoModule *module, const char *feature,
+                                                           bool *enabled) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_module_feature_enabled(TO_MODULE_CTX_(ctx), module, feature, enabled);
 }
 
-static bool fi_module_export_static_exports_are_valid_(const FimoModuleExport *export, FimoInternalModuleContext *ctx) {
-    FIMO_DEBUG_ASSERT(export && ctx)
-    if ((export->symbol_exports == NULL && export->symbol_exports_count != 0) ||
-        (export->symbol_exports != NULL && export->symbol_exports_count == 0)) {
-        WARN_(ctx, "invalid symbol export count, module='%s', symbol_exports='%p', symbol_exports_count='%u'",
-              export->name, (void *)export->symbol_exports, export->symbol_exports_count)
-        return false;
-    }
-    for (FimoISize i = 0; i < (FimoISize) export->symbol_exports_count; i++) {
-        const FimoModuleSymbolExport *sym = &export->symbol_exports[i];
-        if (sym->name == NULL) {
-            WARN_(ctx, "symbol export name is 'NULL', module='%s'", export->name)
-            return false;
-        }
-        if (sym->ns == NULL) {
-            WARN_(ctx, "symbol export namespace is 'NULL', module='%s', symbol='%s'", export->name, sym->name)
-            return false;
-        }
-        if (sym->symbol == NULL) {
-            WARN_(ctx, "symbol export is 'NULL', module='%s', symbol='%s', ns='%s'", export->name, sym->name, sym->ns)
-            return false;
-        }
-        for (FimoISize j = 0; j < (FimoISize) export->symbol_imports_count; j++) {
-            const FimoModuleSymbolImport *s = &export->symbol_imports[j];
-            if (strcmp(sym->name, s->name) == 0 && strcmp(sym->ns, s->ns) == 0) {
-                WARN_(ctx, "duplicate symbol, module='%s', symbol='%s', ns='%s'", export->name, sym->name, sym->ns)
-                return false;
-            }
-        }
-        for (FimoISize j = 0; j < i; j++) {
-            const FimoModuleSymbolExport *s = &export->symbol_exports[j];
-            if (strcmp(sym->name, s->name) == 0 && strcmp(sym->ns, s->ns) == 0) {
-                WARN_(ctx, "duplicate symbol, module='%s', symbol='%s', ns='%s'", export->name, sym->name, sym->ns)
-                return false;
-            }
-        }
-    }
+///////////////////////////////////////////////////////////////////////
+//// Module Subsystem API
+///////////////////////////////////////////////////////////////////////
 
-    return true;
-}
+FIMO_MUST_USE
+FimoResult fimo_internal_module_init(FimoInternalModuleContext *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    TRACE_SIMPLE_(ctx, "initializing the module subsystem")
 
-static bool fi_module_export_dynamic_exports_are_valid_(const FimoModuleExport *export,
-                                                        FimoInternalModuleContext *ctx) {
-    FIMO_DEBUG_ASSERT(export && ctx)
-    if ((export->dynamic_symbol_exports == NULL && export->dynamic_symbol_exports_count != 0) ||
-        (export->dynamic_symbol_exports != NULL && export->dynamic_symbol_exports_count == 0)) {
-        WARN_(ctx,
-              "invalid dynamic symbol export count, module='%s', dynamic_symbol_exports='%p', "
-              "dynamic_symbol_exports_count='%u'",
-              export->name, (void *)export->dynamic_symbol_exports, export->dynamic_symbol_exports_count)
-        return false;
-    }
-    for (FimoISize i = 0; i < (FimoISize) export->dynamic_symbol_exports_count; i++) {
-        const FimoModuleDynamicSymbolExport *sym = &export->dynamic_symbol_exports[i];
-        if (sym->name == NULL) {
-            WARN_(ctx, "symbol export name is 'NULL', module='%s'", export->name)
-            return false;
-        }
-        if (sym->ns == NULL) {
-            WARN_(ctx, "symbol export namespace is 'NULL', module='%s', symbol='%s'", export->name, sym->name)
-            return false;
-        }
-        if (sym->constructor == NULL) {
-            WARN_(ctx, "symbol constructor is 'NULL', module='%s', symbol='%s', ns='%s'", export->name, sym->name,
-                  sym->ns)
-            return false;
-        }
-        if (sym->destructor == NULL) {
-            WARN_(ctx, "symbol destructor is 'NULL', module='%s', symbol='%s', ns='%s'", export->name, sym->name,
-                  sym->ns)
-            return false;
-        }
-        for (FimoISize j = 0; j < (FimoISize) export->symbol_imports_count; j++) {
-            const FimoModuleSymbolImport *s = &export->symbol_imports[j];
-            if (strcmp(sym->name, s->name) == 0 && strcmp(sym->ns, s->ns) == 0) {
-                WARN_(ctx, "duplicate symbol, module='%s', symbol='%s', ns='%s'", export->name, sym->name, sym->ns)
-                return false;
-            }
-        }
-        for (FimoISize j = 0; j < (FimoISize) export->symbol_exports_count; j++) {
-            const FimoModuleSymbolExport *s = &export->symbol_exports[j];
-            if (strcmp(sym->name, s->name) == 0 && strcmp(sym->ns, s->ns) == 0) {
-                WARN_(ctx, "duplicate symbol, module='%s', symbol='%s', ns='%s'", export->name, sym->name, sym->ns)
-                return false;
-            }
-        }
-        for (FimoISize j = 0; j < i; j++) {
-            const FimoModuleDynamicSymbolExport *s = &export->dynamic_symbol_exports[j];
-            if (strcmp(sym->name, s->name) == 0 && strcmp(sym->ns, s->ns) == 0) {
-                WARN_(ctx, "duplicate symbol, module='%s', symbol='%s', ns='%s'", export->name, sym->name, sym->ns)
-                return false;
-            }
-        }
+    const FimoResult error = ctx_init_(ctx);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_SIMPLE_(ctx, error, "could not initialize the module subsystem")
+        return error;
     }
 
-    return true;
+    return FIMO_EOK;
 }
 
-static bool fi_module_export_modifiers_are_valid_(const FimoModuleExport *export, FimoInternalModuleContext *ctx) {
-    FIMO_DEBUG_ASSERT(export && ctx)
-    if ((export->modifiers == NULL && export->modifiers_count != 0) ||
-        (export->modifiers != NULL && export->modifiers_count == 0)) {
-        WARN_(ctx, "invalid modifiers count, module='%s', modifiers='%p', modifiers_count='%u'", export->name,
-              (void *)export->modifiers, export->modifiers_count)
-        return false;
-    }
-    for (FimoISize i = 0; i < (FimoISize) export->modifiers_count; i++) {
-        const FimoModuleExportModifier *modifier = &export->modifiers[i];
-        switch (modifier->key) {
-            case FIMO_MODULE_EXPORT_MODIFIER_KEY_DESTRUCTOR:
-            case FIMO_MODULE_EXPORT_MODIFIER_KEY_DEPENDENCY: {
-                if (modifier->value == NULL) {
-                    WARN_(ctx, "no value set for modifier, module='%s', modifier='%d'", export->name, modifier->key)
-                    return false;
-                }
-                break;
-            }
-            default: {
-                WARN_(ctx, "unrecognized modifier key, module='%s', modifier='%d'", export->name, modifier->key)
-                return false;
-            }
-        }
-    }
-
-    return true;
+void fimo_internal_module_destroy(FimoInternalModuleContext *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    TRACE_SIMPLE_(ctx, "destroying the module subsystem")
+    ctx_deinit_(ctx);
 }
 
-static bool fi_module_export_is_valid_(const FimoModuleExport *export, FimoInternalModuleContext *ctx) {
-    FIMO_DEBUG_ASSERT(export && ctx)
-    _Static_assert(FIMO_MODULE_EXPORT_ABI == 0, "Unknown module abi version");
-    if (export->type != FIMO_STRUCT_TYPE_MODULE_EXPORT) {
-        WARN_(ctx, "invalid module struct type, type='%d'", export->type)
-        return false;
-    }
-    if (export->next != NULL) {
-        WARN_(ctx, "next pointer must currently be 'NULL', next='%p'", (void *)export->next)
-        return false;
+FIMO_MUST_USE
+FimoResult fimo_internal_module_pseudo_module_new(FimoInternalModuleContext *ctx, const FimoModule **module) {
+    FIMO_DEBUG_ASSERT(ctx)
+    if (module == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p'", (void *)module)
+        return FIMO_EINVAL;
     }
-    if (export->export_abi != FIMO_MODULE_EXPORT_ABI) {
-        WARN_(ctx, "unknown module abi version, export_abi='%d'", export->export_abi)
-        return false;
+
+    TRACE_SIMPLE_(ctx, "new pseudo module")
+    ctx_lock_(ctx);
+    char name_buffer[32];
+    const FimoU64 num_modules = (FimoU64)hashmap_count(ctx->modules);
+
+    FIMO_PRAGMA_MSVC(warning(push))
+    FIMO_PRAGMA_MSVC(warning(disable : 4996))
+    // NOLINTNEXTLINE(clang-analyzer-security.insecureAPI.DeprecatedOrUnsafeBufferHandling)
+    snprintf(name_buffer, sizeof(name_buffer), "_pseudo_%" PRIu64, num_modules);
+    FIMO_PRAGMA_MSVC(warning(pop))
+
+    FimoModule *module_;
+    FimoResult error = fi_module_new_pseudo_(ctx, name_buffer, &module_);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_SIMPLE_(ctx, error, "could not create a new module object")
+        return error;
     }
-    if (export->name == NULL) {
-        WARN_SIMPLE_(ctx, "module name is 'NULL'")
-        return false;
+
+    const struct ModuleInfo_ *info = module_info_from_module_(module_);
+    struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
+    error = ctx_add_module_(ctx, info_inner);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_SIMPLE_(ctx, error, "could not add module to context")
+        fi_module_free_(info_inner, NULL, true);
+        return error;
     }
-    if ((export->module_constructor == NULL) != (export->module_destructor == NULL)) {
-        WARN_(ctx, "module constructor must both be set or null, module='%s'", export->name)
-        return false;
+    module_info_unlock_(info_inner);
+    ctx_unlock_(ctx);
+
+    *module = module_;
+    return FIMO_EOK;
+}
+
+FIMO_MUST_USE
+FimoResult fimo_internal_module_pseudo_module_destroy(FimoInternalModuleContext *ctx, const FimoModule *module,
+                                                      FimoContext *module_context) {
+    FIMO_DEBUG_ASSERT(ctx)
+    if (module == NULL || module_context == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', module_context='%p'", (void *)module,
+               (void *)module_context)
+        return FIMO_EINVAL;
     }
 
-    if (!fi_module_export_parameters_are_valid_(export, ctx) || !fi_module_export_resources_are_valid_(export, ctx) ||
-        !fi_module_export_namespaces_are_valid_(export, ctx) || !fi_module_export_imports_are_valid_(export, ctx) ||
-        !fi_module_export_imports_are_valid_(export, ctx) || !fi_module_export_static_exports_are_valid_(export, ctx) ||
-        !fi_module_export_dynamic_exports_are_valid_(export, ctx) ||
-        !fi_module_export_modifiers_are_valid_(export, ctx)) {
-        return false;
+    TRACE_SIMPLE_(ctx, "destroying pseudo module")
+    ctx_lock_(ctx);
+    const struct ModuleInfo_ *info = module_info_from_module_(module);
+    if (module_info_from_module_(module)->type != MODULE_TYPE_PSEUDO_) {
+        ERROR_SIMPLE_(ctx, FIMO_EINVAL, "module is not a pseudo module")
+        return FIMO_EINVAL;
     }
+    struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
 
-    return true;
-}
+    FimoResult error = ctx_remove_module_(ctx, info_inner);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        module_info_unlock_(info_inner);
+        ctx_unlock_(ctx);
+        ERROR_SIMPLE_(ctx, error, "could not remove module from context")
+        return error;
+    }
 
-///////////////////////////////////////////////////////////////////////
-//// Trampoline functions
-///////////////////////////////////////////////////////////////////////
+    fi_module_free_(info_inner, module_context, true);
 
-FimoResult fimo_internal_trampoline_module_pseudo_module_new(void *ctx, const FimoModule **module) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_pseudo_module_new(TO_MODULE_CTX_(ctx), module);
-}
+    error = ctx_cleanup_loose_modules(ctx);
+    ctx_unlock_(ctx);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_SIMPLE_(ctx, error, "could not cleanup loose modules")
+        return error;
+    }
 
-FimoResult fimo_internal_trampoline_module_pseudo_module_destroy(void *ctx, const FimoModule *module,
-                                                                 FimoContext *module_context) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_pseudo_module_destroy(TO_MODULE_CTX_(ctx), module, module_context);
+    return FIMO_EOK;
 }
 
-FimoResult fimo_internal_trampoline_module_set_new(void *ctx, FimoModuleLoadingSet **set) {
+FIMO_MUST_USE
+FimoResult fimo_internal_module_set_new(FimoInternalModuleContext *ctx, FimoModuleLoadingSet **set) {
     FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_set_new(TO_MODULE_CTX_(ctx), set);
-}
+    if (set == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, set='%p'", (void *)set)
+        return FIMO_EINVAL;
+    }
 
-FimoResult fimo_internal_trampoline_module_set_has_module(void *ctx, FimoModuleLoadingSet *set, const char *name,
-                                                          bool *has_module) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_set_has_module(TO_MODULE_CTX_(ctx), set, name, has_module);
-}
+    TRACE_SIMPLE_(ctx, "")
+    const FimoResult error = loading_set_new_(set);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_SIMPLE_(ctx, error, "could not create set")
+        return error;
+    }
 
-FimoResult fimo_internal_trampoline_module_set_has_symbol(void *ctx, FimoModuleLoadingSet *set, const char *name,
-                                                          const char *ns, FimoVersion version, bool *has_symbol) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_set_has_symbol(TO_MODULE_CTX_(ctx), set, name, ns, version, has_symbol);
+    return FIMO_EOK;
 }
 
-FimoResult fimo_internal_trampoline_module_set_append_callback(void *ctx, FimoModuleLoadingSet *set,
-                                                               const char *module_name,
-                                                               FimoModuleLoadingSuccessCallback on_success,
-                                                               FimoModuleLoadingErrorCallback on_error,
-                                                               void *user_data) {
+FIMO_MUST_USE
+FimoResult fimo_internal_module_set_has_module(FimoInternalModuleContext *ctx, FimoModuleLoadingSet *set,
+                                               const char *name, bool *has_module) {
     FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_set_append_callback(TO_MODULE_CTX_(ctx), set, module_name, on_success, on_error,
-                                                    user_data);
-}
+    if (set == NULL || name == NULL || has_module == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, set='%p', name='%p', has_module='%p'", (void *)set,
+               (void *)name, (void *)has_module)
+        return FIMO_EINVAL;
+    }
 
-FimoResult fimo_internal_trampoline_module_set_append_freestanding_module(void *ctx, const FimoModule *module,
-                                                                          FimoModuleLoadingSet *set,
-                                                                          const FimoModuleExport *export) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_set_append_freestanding_module(TO_MODULE_CTX_(ctx), module, set, export);
-}
+    TRACE_(ctx, "name='%s'", name)
+    loading_set_lock_(set);
+    const struct LoadingSetModule_ *module = loading_set_get_module_(set, name);
+    *has_module = module != NULL;
+    loading_set_unlock_(set);
 
-FimoResult fimo_internal_trampoline_module_set_append_modules(
-        void *ctx, FimoModuleLoadingSet *set, const char *module_path, FimoModuleLoadingFilter filter,
-        void *filter_data, void (*export_iterator)(bool (*)(const FimoModuleExport *, void *), void *),
-        const void *binary_handle) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_set_append_modules(TO_MODULE_CTX_(ctx), set, module_path, filter, filter_data,
-                                                   export_iterator, binary_handle);
+    return FIMO_EOK;
 }
 
-FimoResult fimo_internal_trampoline_module_set_dismiss(void *ctx, FimoModuleLoadingSet *set) {
+FIMO_MUST_USE
+FimoResult fimo_internal_module_set_has_symbol(FimoInternalModuleContext *ctx, FimoModuleLoadingSet *set,
+                                               const char *name, const char *ns, const FimoVersion version,
+                                               bool *has_symbol) {
     FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_set_dismiss(TO_MODULE_CTX_(ctx), set);
-}
+    if (set == NULL || name == NULL || ns == NULL || has_symbol == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, set='%p', name='%p', ns='%p', has_symbol='%p'", (void *)set,
+               (void *)name, (void *)ns, (void *)has_symbol)
+        return FIMO_EINVAL;
+    }
 
-FimoResult fimo_internal_trampoline_module_set_finish(void *ctx, FimoModuleLoadingSet *set) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_set_finish(TO_MODULE_CTX_(ctx), set);
-}
+    TRACE_(ctx, "name='%s', ns='%s'", name, ns)
+    loading_set_lock_(set);
+    const struct LoadingSetSymbol_ *sym = loading_set_get_symbol_(set, name, ns, version);
+    *has_symbol = sym != NULL;
+    loading_set_unlock_(set);
 
-FimoResult fimo_internal_trampoline_module_find_by_name(void *ctx, const char *name, const FimoModuleInfo **module) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_find_by_name(TO_MODULE_CTX_(ctx), name, module);
+    return FIMO_EOK;
 }
 
-FimoResult fimo_internal_trampoline_module_find_by_symbol(void *ctx, const char *name, const char *ns,
-                                                          FimoVersion version, const FimoModuleInfo **module) {
+FIMO_MUST_USE
+FimoResult fimo_internal_module_set_append_callback(FimoInternalModuleContext *ctx, FimoModuleLoadingSet *set,
+                                                    const char *module_name,
+                                                    const FimoModuleLoadingSuccessCallback on_success,
+                                                    const FimoModuleLoadingErrorCallback on_error, void *user_data) {
     FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_find_by_symbol(TO_MODULE_CTX_(ctx), name, ns, version, module);
-}
+    if (set == NULL || module_name == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, set='%p', module_name='%p'", (void *)set, (void *)module_name)
+        return FIMO_EINVAL;
+    }
 
-FimoResult fimo_internal_trampoline_module_namespace_exists(void *ctx, const char *ns, bool *exists) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_namespace_exists(TO_MODULE_CTX_(ctx), ns, exists);
-}
+    TRACE_(ctx, "module='%s'", module_name)
+    const struct LoadingSetCallback_ callback = {
+            .data = user_data,
+            .error = on_error,
+            .success = on_success,
+    };
 
-FimoResult fimo_internal_trampoline_module_namespace_include(void *ctx, const FimoModule *module, const char *ns) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_namespace_include(TO_MODULE_CTX_(ctx), module, ns);
-}
+    loading_set_lock_(set);
+    struct LoadingSetModule_ *module = (void *)loading_set_get_module_(set, module_name);
+    if (module == NULL) {
+        loading_set_unlock_(set);
+        ERROR_(ctx, FIMO_EINVAL, "module does not exist, module='%s'", module_name)
+        return FIMO_EINVAL;
+    }
 
-FimoResult fimo_internal_trampoline_module_namespace_exclude(void *ctx, const FimoModule *module, const char *ns) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_namespace_exclude(TO_MODULE_CTX_(ctx), module, ns);
-}
+    const FimoResult error = loading_set_module_append_callback_(module, callback);
+    loading_set_unlock_(set);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_SIMPLE_(ctx, error, "could not append callback")
+        return error;
+    }
 
-FimoResult fimo_internal_trampoline_module_namespace_included(void *ctx, const FimoModule *module, const char *ns,
-                                                              bool *is_included, bool *is_static) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_namespace_included(TO_MODULE_CTX_(ctx), module, ns, is_included, is_static);
+    return FIMO_EINVAL;
 }
 
-FimoResult fimo_internal_trampoline_module_acquire_dependency(void *ctx, const FimoModule *module,
-                                                              const FimoModuleInfo *dependency) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_acquire_dependency(TO_MODULE_CTX_(ctx), module, dependency);
-}
+static FimoResult add_module_(FimoInternalModuleContext *ctx, struct hashmap *symbols, struct hashmap *modules,
+                              struct hashmap *opt_symbols, struct hashmap *opt_modules, struct ModuleHandle_ *handle,
+                              const FimoModuleExport *export, const FimoModule *owner) {
+    FIMO_DEBUG_ASSERT(ctx && symbols && modules && handle && export)
+    if (hashmap_get(modules, &(struct LoadingSetModule_){.name = export->name}) ||
+        (opt_modules && hashmap_get(opt_modules, &(struct LoadingSetModule_){.name = export->name}))) {
+        ERROR_(ctx, FIMO_EINVAL, "duplicate module, module='%s'", export->name)
+        return FIMO_EINVAL;
+    }
+    for (FimoISize i = 0; i < (FimoISize) export->symbol_exports_count; i++) {
+        const FimoModuleSymbolExport *sym = &export->symbol_exports[i];
+        if (hashmap_get(symbols, &(struct LoadingSetSymbol_){.name = sym->name, .ns = sym->ns}) ||
+            (opt_symbols && hashmap_get(opt_symbols, &(struct LoadingSetSymbol_){.name = sym->name, .ns = sym->ns}))) {
+            ERROR_(ctx, FIMO_EINVAL, "duplicate symbol, symbol='%s', ns='%s'", sym->name, sym->ns)
+            return FIMO_EINVAL;
+        }
+    }
+    for (FimoISize i = 0; i < (FimoISize) export->dynamic_symbol_exports_count; i++) {
+        const FimoModuleDynamicSymbolExport *sym = &export->dynamic_symbol_exports[i];
+        if (hashmap_get(symbols, &(struct LoadingSetSymbol_){.name = sym->name, .ns = sym->ns}) ||
+            (opt_symbols && hashmap_get(opt_symbols, &(struct LoadingSetSymbol_){.name = sym->name, .ns = sym->ns}))) {
+            ERROR_(ctx, FIMO_EINVAL, "duplicate symbol, symbol='%s', ns='%s'", sym->name, sym->ns)
+            return FIMO_EINVAL;
+        }
+    }
+
+    for (FimoISize i = 0; i < (FimoISize) export->symbol_exports_count; i++) {
+        const FimoModuleSymbolExport *sym = &export->symbol_exports[i];
+        struct LoadingSetSymbol_ symbol;
+        FimoResult error = loading_set_symbol_new_(sym->name, sym->ns, sym->version, export->name, &symbol);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            ERROR_SIMPLE_(ctx, error, "could not create symbol")
+            return error;
+        }
+        hashmap_set(symbols, &symbol);
+        if (hashmap_oom(symbols)) {
+            error = FIMO_ENOMEM;
+            loading_set_symbol_free_(&symbol);
+            ERROR_SIMPLE_(ctx, error, "could not insert symbol")
+            return error;
+        }
+    }
+    for (FimoISize i = 0; i < (FimoISize) export->dynamic_symbol_exports_count; i++) {
+        const FimoModuleDynamicSymbolExport *sym = &export->dynamic_symbol_exports[i];
+        struct LoadingSetSymbol_ symbol;
+        FimoResult error = loading_set_symbol_new_(sym->name, sym->ns, sym->version, export->name, &symbol);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            ERROR_SIMPLE_(ctx, error, "could not create symbol")
+            return error;
+        }
+        hashmap_set(symbols, &symbol);
+        if (hashmap_oom(symbols)) {
+            error = FIMO_ENOMEM;
+            loading_set_symbol_free_(&symbol);
+            ERROR_SIMPLE_(ctx, error, "could not insert symbol")
+            return error;
+        }
+    }
 
-FimoResult fimo_internal_trampoline_module_relinquish_dependency(void *ctx, const FimoModule *module,
-                                                                 const FimoModuleInfo *dependency) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_relinquish_dependency(TO_MODULE_CTX_(ctx), module, dependency);
-}
+    {
+        struct LoadingSetModule_ module;
+        FimoResult error = loading_set_module_new_(export, handle, owner, &module);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            ERROR_SIMPLE_(ctx, error, "could not create module")
+            return error;
+        }
+        hashmap_set(modules, &module);
+        if (hashmap_oom(modules)) {
+            error = FIMO_ENOMEM;
+            loading_set_module_free_(&module);
+            ERROR_SIMPLE_(ctx, error, "could not insert symbol")
+            return error;
+        }
+    }
 
-FimoResult fimo_internal_trampoline_module_has_dependency(void *ctx, const FimoModule *module,
-                                                          const FimoModuleInfo *other, bool *has_dependency,
-                                                          bool *is_static) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_has_dependency(TO_MODULE_CTX_(ctx), module, other, has_dependency, is_static);
+    return FIMO_EOK;
 }
 
-FimoResult fimo_internal_trampoline_module_param_query(void *ctx, const char *module_name, const char *param,
-                                                       FimoModuleParamType *type, FimoModuleParamAccess *read,
-                                                       FimoModuleParamAccess *write) {
+FIMO_MUST_USE
+FimoResult fimo_internal_module_set_append_freestanding_module(FimoInternalModuleContext *ctx, const FimoModule *module,
+                                                               FimoModuleLoadingSet *set,
+                                                               const FimoModuleExport *export) {
     FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_param_query(TO_MODULE_CTX_(ctx), module_name, param, type, read, write);
-}
+    if (module == NULL || set == NULL || export == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', set='%p', export='%p'", (void *)module,
+               (void *)set, (void *)export)
+        return FIMO_EINVAL;
+    }
+    TRACE_SIMPLE_(ctx, "appending new freestanding module")
 
-FimoResult fimo_internal_trampoline_module_param_set_public(void *ctx, const void *value, FimoModuleParamType type,
-                                                            const char *module_name, const char *param) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_param_set_public(TO_MODULE_CTX_(ctx), value, type, module_name, param);
-}
+    // Check that the export is valid.
+    if (!fi_module_export_is_valid_(export, ctx)) {
+        fi_module_export_cleanup_(export);
+        ERROR_SIMPLE_(ctx, FIMO_EINVAL, "export is invalid");
+        return FIMO_EINVAL;
+    }
 
-FimoResult fimo_internal_trampoline_module_param_get_public(void *ctx, void *value, FimoModuleParamType *type,
-                                                            const char *module_name, const char *param) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_param_get_public(TO_MODULE_CTX_(ctx), value, type, module_name, param);
-}
+    // Inherit the same handle as the parent module.
+    const struct ModuleInfo_ *info = module_info_from_module_(module);
+    struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
+    struct ModuleHandle_ *handle = info_inner->handle;
+    module_handle_acquire_(handle);
+    module_info_unlock_(info_inner);
 
-FimoResult fimo_internal_trampoline_module_param_set_dependency(void *ctx, const FimoModule *module, const void *value,
-                                                                FimoModuleParamType type, const char *module_name,
-                                                                const char *param) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_param_set_dependency(TO_MODULE_CTX_(ctx), module, value, type, module_name, param);
-}
+    // Insert the export into the set.
+    loading_set_lock_(set);
+    FimoResult error = add_module_(ctx, set->symbols, set->modules, NULL, NULL, info_inner->handle, export, module);
+    loading_set_unlock_(set);
+    module_handle_release_(handle);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        fi_module_export_cleanup_(export);
+        ERROR_(ctx, error, "could not insert the export into the set, module='%s'", export->name);
+        return error;
+    }
 
-FimoResult fimo_internal_trampoline_module_param_get_dependency(void *ctx, const FimoModule *module, void *value,
-                                                                FimoModuleParamType *type, const char *module_name,
-                                                                const char *param) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_param_get_dependency(TO_MODULE_CTX_(ctx), module, value, type, module_name, param);
+    return FIMO_EOK;
 }
 
-FimoResult fimo_internal_trampoline_module_load_symbol(void *ctx, const FimoModule *module, const char *name,
-                                                       const char *ns, FimoVersion version,
-                                                       const FimoModuleRawSymbol **symbol) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_load_symbol(TO_MODULE_CTX_(ctx), module, name, ns, version, symbol);
-}
+struct AppendModulesData_ {
+    FimoInternalModuleContext *ctx;
+    FimoResult error;
+    FimoModuleLoadingFilter filter;
+    void *filter_data;
+    FimoArrayList exports;
+};
 
-FimoResult fimo_internal_trampoline_module_unload(void *ctx, const FimoModuleInfo *module) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_unload(TO_MODULE_CTX_(ctx), module);
-}
+static bool append_modules_iterator_(const FimoModuleExport *export, void *data) {
+    struct AppendModulesData_ *d = data;
 
-FimoResult fimo_internal_trampoline_module_param_set_private(void *ctx, const FimoModule *module, const void *value,
-                                                             FimoModuleParamType type, FimoModuleParam *param) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_param_set_private(TO_MODULE_CTX_(ctx), module, value, type, param);
-}
+    if (!fi_module_export_is_valid_(export, d->ctx)) {
+        fi_module_export_cleanup_(export);
+        return true;
+    }
 
-FimoResult fimo_internal_trampoline_module_param_get_private(void *ctx, const FimoModule *module, void *value,
-                                                             FimoModuleParamType *type, const FimoModuleParam *param) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_param_get_private(TO_MODULE_CTX_(ctx), module, value, type, param);
-}
+    if (d->filter == NULL || d->filter(export, d->filter_data)) {
+        d->error = fimo_array_list_push(&d->exports, sizeof(const FimoModuleExport *),
+                                        _Alignof(const FimoModuleExport *), &export, NULL);
+        if (FIMO_RESULT_IS_ERROR(d->error)) {
+            return false;
+        }
+    }
 
-FimoResult fimo_internal_trampoline_module_param_set_inner(void *ctx, const FimoModule *module, const void *value,
-                                                           FimoModuleParamType type, FimoModuleParamData *param) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_param_set_inner(TO_MODULE_CTX_(ctx), module, value, type, param);
+    return true;
 }
 
-FimoResult fimo_internal_trampoline_module_get_inner(void *ctx, const FimoModule *module, void *value,
-                                                     FimoModuleParamType *type, const FimoModuleParamData *param) {
-    FIMO_DEBUG_ASSERT(ctx)
-    return fimo_internal_module_param_get_inner(TO_MODULE_CTX_(ctx), module, value, type, param);
-}
+static FimoResult extract_exports_(FimoInternalModuleContext *ctx, FimoModuleLoadingSet *set,
+                                   struct ModuleHandle_ *handle, FimoArrayList exports) {
+    FIMO_ASSERT(ctx && set && handle)
+    FimoResult error;
+    struct hashmap *symbols = hashmap_new_with_allocator(
+            malloc_, realloc_, free_, sizeof(struct LoadingSetSymbol_), 0, 0, 0, (HashFn_)loading_set_symbol_hash_,
+            (CmpFn_)loading_set_symbol_cmp_, (FreeFn_)loading_set_symbol_free_, NULL);
+    if (symbols == NULL) {
+        error = FIMO_ENOMEM;
+        ERROR_SIMPLE_(ctx, error, "could not allocate symbols map")
+        goto alloc_symbols;
+    }
 
-///////////////////////////////////////////////////////////////////////
-//// Module Subsystem API
-///////////////////////////////////////////////////////////////////////
+    struct hashmap *modules = hashmap_new_with_allocator(
+            malloc_, realloc_, free_, sizeof(struct LoadingSetModule_), 0, 0, 0, (HashFn_)loading_set_module_hash_,
+            (CmpFn_)loading_set_module_cmp_, (FreeFn_)loading_set_module_free_, NULL);
+    if (modules == NULL) {
+        error = FIMO_ENOMEM;
+        ERROR_SIMPLE_(ctx, error, "could not allocate modules map")
+        goto alloc_modules;
+    }
 
-FIMO_MUST_USE
-FimoResult fimo_internal_module_init(FimoInternalModuleContext *ctx) {
-    FIMO_DEBUG_ASSERT(ctx)
-    TRACE_SIMPLE_(ctx, "initializing the module subsystem")
+    while (!fimo_array_list_is_empty(&exports)) {
+        const FimoModuleExport *export;
+        error = fimo_array_list_pop_back(&exports, sizeof(export), &export, NULL);
+        FIMO_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
 
-    const FimoResult error = ctx_init_(ctx);
-    if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not initialize the module subsystem")
-        return error;
+        error = add_module_(ctx, symbols, modules, set->symbols, set->modules, handle, export, NULL);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            ERROR_(ctx, error, "could not add export to set, module='%s'", export->name)
+            goto iterate_exports;
+        }
     }
 
-    return FIMO_EOK;
-}
+    {
+        size_t iter_idx = 0;
+        struct LoadingSetSymbol_ *iter_item = NULL;
+        while (hashmap_iter(symbols, &iter_idx, (void **)&iter_item)) {
+            iter_item = (void *)hashmap_delete(symbols, iter_item);
+            iter_idx = 0;
 
-void fimo_internal_module_destroy(FimoInternalModuleContext *ctx) {
-    FIMO_DEBUG_ASSERT(ctx)
-    TRACE_SIMPLE_(ctx, "destroying the module subsystem")
-    ctx_deinit_(ctx);
-}
+            hashmap_set(set->symbols, iter_item);
+            if (hashmap_oom(set->symbols)) {
+                loading_set_symbol_free_(iter_item);
+                error = FIMO_ENOMEM;
+                ERROR_SIMPLE_(ctx, error, "could not merge symbol maps")
+                goto transfer_symbols;
+            }
+        }
+    }
+    {
+        size_t iter_idx = 0;
+        struct LoadingSetModule_ *iter_item = NULL;
+        while (hashmap_iter(modules, &iter_idx, (void *)&iter_item)) {
+            iter_item = (void *)hashmap_delete(modules, iter_item);
+            iter_idx = 0;
 
-FIMO_MUST_USE
-FimoResult fimo_internal_module_pseudo_module_new(FimoInternalModuleContext *ctx, const FimoModule **module) {
-    FIMO_DEBUG_ASSERT(ctx)
-    if (module == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p'", (void *)module)
-        return FIMO_EINVAL;
+            hashmap_set(set->modules, iter_item);
+            if (hashmap_oom(set->modules)) {
+                loading_set_module_free_(iter_item);
+                error = FIMO_ENOMEM;
+                ERROR_SIMPLE_(ctx, error, "could not merge module maps")
+                goto transfer_modules;
+            }
+        }
     }
 
-    TRACE_SIMPLE_(ctx, "new pseudo module")
-    ctx_lock_(ctx);
-    char name_buffer[32];
-    const FimoU64 num_modules = (FimoU64)hashmap_count(ctx->modules);
+    hashmap_free(modules);
+    hashmap_free(symbols);
 
-    FIMO_PRAGMA_MSVC(warning(push))
-    FIMO_PRAGMA_MSVC(warning(disable : 4996))
-    // NOLINTNEXTLINE(clang-analyzer-security.insecureAPI.DeprecatedOrUnsafeBufferHandling)
-    snprintf(name_buffer, sizeof(name_buffer), "_pseudo_%" PRIu64, num_modules);
-    FIMO_PRAGMA_MSVC(warning(pop))
+    set->should_recreate_map = true;
+    return FIMO_EOK;
 
-    FimoModule *module_;
-    FimoResult error = fi_module_new_pseudo_(ctx, name_buffer, &module_);
-    if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not create a new module object")
-        return error;
+transfer_modules: {
+    size_t iter_idx = 0;
+    void *iter_item = NULL;
+    while (hashmap_iter(modules, &iter_idx, &iter_item)) {
+        void *mod = (void *)hashmap_delete(set->modules, iter_item);
+        if (mod) {
+            loading_set_module_free_(mod);
+        }
     }
-
-    const struct ModuleInfo_ *info = module_info_from_module_(module_);
-    struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
-    error = ctx_add_module_(ctx, info_inner);
-    if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not add module to context")
-        fi_module_free_(info_inner, NULL);
-        return error;
+}
+transfer_symbols: {
+    size_t iter_idx = 0;
+    void *iter_item = NULL;
+    while (hashmap_iter(symbols, &iter_idx, &iter_item)) {
+        void *sym = (void *)hashmap_delete(set->symbols, iter_item);
+        if (sym) {
+            loading_set_symbol_free_(sym);
+        }
     }
-    module_info_unlock_(info_inner);
-    ctx_unlock_(ctx);
-
-    *module = module_;
-    return FIMO_EOK;
+}
+iterate_exports:
+    hashmap_free(modules);
+alloc_modules:
+    hashmap_free(symbols);
+alloc_symbols:
+    return error;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_pseudo_module_destroy(FimoInternalModuleContext *ctx, const FimoModule *module,
-                                                      FimoContext *module_context) {
+FimoResult fimo_internal_module_set_append_modules(
+        FimoInternalModuleContext *ctx, FimoModuleLoadingSet *set, const char *module_path,
+        const FimoModuleLoadingFilter filter, void *filter_data,
+        void (*export_iterator)(bool (*)(const FimoModuleExport *, void *), void *), const void *binary_handle) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (module == NULL || module_context == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', module_context='%p'", (void *)module,
-               (void *)module_context)
+    if (set == NULL || export_iterator == NULL || binary_handle == NULL) {
+        ERROR_(ctx, FIMO_EINVAL,
+               "invalid null parameter, set='%p', module_path='%s', export_iterator='%p', binary_handle='%p'",
+               (void *)set, module_path, *(void **)&export_iterator, binary_handle)
         return FIMO_EINVAL;
     }
 
-    TRACE_SIMPLE_(ctx, "destroying pseudo module")
-    ctx_lock_(ctx);
-    const struct ModuleInfo_ *info = module_info_from_module_(module);
-    if (module_info_from_module_(module)->type != MODULE_TYPE_PSEUDO_) {
-        ERROR_SIMPLE_(ctx, FIMO_EINVAL, "module is not a pseudo module")
-        return FIMO_EINVAL;
+    FimoResult error;
+    struct VerifiedBinary_ binary;
+    if (module_path) {
+        error = ctx_verify_binary_(ctx, module_path, &binary);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            ERROR_SIMPLE_(ctx, error, "could not verify the binary")
+            return error;
+        }
     }
-    struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
 
-    FimoResult error = ctx_remove_module_(ctx, info_inner);
+    struct ModuleHandle_ *handle = NULL;
+    struct ModuleLoader_ *loader = module_path ? ctx_find_loader_(ctx, module_path) : NULL;
+    if (loader) {
+        TRACE_(ctx, "module_path='%s', loader='%s'", module_path, loader->name)
+        const char *load_path = binary.load_path ? binary.load_path : module_path;
+        error = module_handle_new_loader_(loader, module_path, load_path, &handle);
+        module_loader_release_(loader);
+        verified_binary_free_(&binary);
+    }
+    else if (module_path) {
+        TRACE_(ctx, "module_path='%s'", module_path)
+        const char *load_path = binary.load_path ? binary.load_path : module_path;
+        error = module_handle_new_plugin_(module_path, load_path, &handle);
+        verified_binary_free_(&binary);
+    }
+    else {
+        TRACE_SIMPLE_(ctx, "local module")
+        error = module_handle_new_local_(export_iterator, binary_handle, &handle);
+    }
     if (FIMO_RESULT_IS_ERROR(error)) {
-        module_info_unlock_(info_inner);
-        ctx_unlock_(ctx);
-        ERROR_SIMPLE_(ctx, error, "could not remove module from context")
+        ERROR_SIMPLE_(ctx, error, "could not create module handle")
         return error;
     }
 
-    fi_module_free_(info_inner, module_context);
+    struct AppendModulesData_ data = {.ctx = ctx,
+                                      .error = FIMO_EOK,
+                                      .filter = filter,
+                                      .filter_data = filter_data,
+                                      .exports = fimo_array_list_new()};
+    module_handle_iterate_exports_(handle, append_modules_iterator_, &data);
+    if (FIMO_RESULT_IS_ERROR(data.error)) {
+        error = data.error;
+        ERROR_SIMPLE_(ctx, error, "could not iterate through the module exports of the binary")
+        goto iterate_exports;
+    }
 
-    error = ctx_cleanup_loose_modules(ctx);
-    ctx_unlock_(ctx);
+    loading_set_lock_(set);
+    error = extract_exports_(ctx, set, handle, data.exports);
+    loading_set_unlock_(set);
     if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not cleanup loose modules")
-        return error;
+        ERROR_SIMPLE_(ctx, error, "could not extract the module exports")
+        goto extract_exports;
     }
 
+    fimo_array_list_free(&data.exports, sizeof(const FimoModuleExport *), _Alignof(const FimoModuleExport *), NULL);
+    module_handle_release_(handle);
+
     return FIMO_EOK;
+
+extract_exports:;
+iterate_exports:
+    fimo_array_list_free(&data.exports, sizeof(const FimoModuleExport *), _Alignof(const FimoModuleExport *), NULL);
+    module_handle_release_(handle);
+
+    return error;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_set_new(FimoInternalModuleContext *ctx, FimoModuleLoadingSet **set) {
+FimoResult fimo_internal_module_set_append_group(FimoInternalModuleContext *ctx, FimoModuleLoadingSet *set,
+                                                 const char *group, const char *const *modules,
+                                                 const FimoUSize modules_count) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (set == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, set='%p'", (void *)set)
+    if (set == NULL || group == NULL || modules == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, set='%p', group='%p', modules='%p'", (void *)set,
+               (void *)group, (void *)modules)
+        return FIMO_EINVAL;
+    }
+    if (modules_count == 0) {
+        ERROR_(ctx, FIMO_EINVAL, "the group does not contain any modules, group='%s'", group)
         return FIMO_EINVAL;
     }
 
-    TRACE_SIMPLE_(ctx, "")
-    const FimoResult error = loading_set_new_(set);
-    if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not create set")
-        return error;
+    TRACE_(ctx, "group='%s'", group)
+    loading_set_lock_(set);
+
+    // Check that the group does not already exist.
+    {
+        FimoUSize it = 0;
+        const struct LoadingSetModule_ *module = NULL;
+        while (loading_set_next_module_(set, &it, &module)) {
+            if (loading_set_module_in_group_(module, group)) {
+                loading_set_unlock_(set);
+                ERROR_(ctx, FIMO_EEXIST, "the set already contains the group, group='%s'", group)
+                return FIMO_EEXIST;
+            }
+        }
     }
 
-    return FIMO_EOK;
-}
+    // Check that all modules can be assigned to the group.
+    for (FimoUSize i = 0; i < modules_count; i++) {
+        if (modules[i] == NULL) {
+            loading_set_unlock_(set);
+            ERROR_(ctx, FIMO_EINVAL, "invalid null module name, group='%s', index='%zu'", group, (size_t)i)
+            return FIMO_EINVAL;
+        }
+        for (FimoUSize j = 0; j < i; j++) {
+            if (strcmp(modules[i], modules[j]) == 0) {
+                loading_set_unlock_(set);
+                ERROR_(ctx, FIMO_EINVAL, "duplicate module in group, group='%s', module='%s'", group, modules[i])
+                return FIMO_EINVAL;
+            }
+        }
 
-FIMO_MUST_USE
-FimoResult fimo_internal_module_set_has_module(FimoInternalModuleContext *ctx, FimoModuleLoadingSet *set,
-                                               const char *name, bool *has_module) {
-    FIMO_DEBUG_ASSERT(ctx)
-    if (set == NULL || name == NULL || has_module == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, set='%p', name='%p', has_module='%p'", (void *)set,
-               (void *)name, (void *)has_module)
-        return FIMO_EINVAL;
+        const struct LoadingSetModule_ *module = loading_set_get_module_(set, modules[i]);
+        if (module == NULL) {
+            loading_set_unlock_(set);
+            ERROR_(ctx, FIMO_ENOENT, "the set does not contain the module, group='%s', module='%s'", group,
+                   modules[i])
+            return FIMO_ENOENT;
+        }
+        if (module->status != MODULE_LOAD_STATUS_UNLOADED_) {
+            loading_set_unlock_(set);
+            ERROR_(ctx, FIMO_EPERM, "the module was already loaded, group='%s', module='%s'", group, modules[i])
+            return FIMO_EPERM;
+        }
+        if (module->group != NULL) {
+            loading_set_unlock_(set);
+            ERROR_(ctx, FIMO_EPERM, "the module is already part of a group, group='%s', module='%s', other='%s'",
+                   group, modules[i], module->group)
+            return FIMO_EPERM;
+        }
     }
 
-    TRACE_(ctx, "name='%s'", name)
-    loading_set_lock_(set);
-    const struct LoadingSetModule_ *module = loading_set_get_module_(set, name);
-    *has_module = module != NULL;
+    for (FimoUSize i = 0; i < modules_count; i++) {
+        struct LoadingSetModule_ *module = (struct LoadingSetModule_ *)loading_set_get_module_(set, modules[i]);
+        FIMO_DEBUG_ASSERT(module)
+        const FimoResult error = clone_string_(group, (char **)&module->group);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            for (FimoUSize j = 0; j < i; j++) {
+                module = (struct LoadingSetModule_ *)loading_set_get_module_(set, modules[j]);
+                fimo_free((char *)module->group);
+                module->group = NULL;
+            }
+            loading_set_unlock_(set);
+            ERROR_(ctx, error, "could not clone group name, group='%s'", group)
+            return error;
+        }
+    }
     loading_set_unlock_(set);
 
     return FIMO_EOK;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_set_has_symbol(FimoInternalModuleContext *ctx, FimoModuleLoadingSet *set,
-                                               const char *name, const char *ns, const FimoVersion version,
-                                               bool *has_symbol) {
+FimoResult fimo_internal_module_set_dismiss(FimoInternalModuleContext *ctx, FimoModuleLoadingSet *set) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (set == NULL || name == NULL || ns == NULL || has_symbol == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, set='%p', name='%p', ns='%p', has_symbol='%p'", (void *)set,
-               (void *)name, (void *)ns, (void *)has_symbol)
+    if (set == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, set='%p'", (void *)set)
         return FIMO_EINVAL;
     }
 
-    TRACE_(ctx, "name='%s', ns='%s'", name, ns)
+    TRACE_SIMPLE_(ctx, "dismissing set")
     loading_set_lock_(set);
-    const struct LoadingSetSymbol_ *sym = loading_set_get_symbol_(set, name, ns, version);
-    *has_symbol = sym != NULL;
+    if (set->is_loading) {
+        loading_set_unlock_(set);
+        ERROR_SIMPLE_(ctx, FIMO_EPERM, "set is being loaded")
+        return FIMO_EPERM;
+    }
     loading_set_unlock_(set);
+    loading_set_free_(set);
 
     return FIMO_EOK;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_set_append_callback(FimoInternalModuleContext *ctx, FimoModuleLoadingSet *set,
-                                                    const char *module_name,
-                                                    const FimoModuleLoadingSuccessCallback on_success,
-                                                    const FimoModuleLoadingErrorCallback on_error, void *user_data) {
+FimoResult fimo_internal_module_set_finish(FimoInternalModuleContext *ctx, FimoModuleLoadingSet *set) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (set == NULL || module_name == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, set='%p', module_name='%p'", (void *)set, (void *)module_name)
+    if (set == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, set='%p'", (void *)set)
         return FIMO_EINVAL;
     }
 
-    TRACE_(ctx, "module='%s'", module_name)
-    const struct LoadingSetCallback_ callback = {
-            .data = user_data,
-            .error = on_error,
-            .success = on_success,
-    };
-
+    TRACE_SIMPLE_(ctx, "loading module set")
+    ctx_lock_(ctx);
     loading_set_lock_(set);
-    struct LoadingSetModule_ *module = (void *)loading_set_get_module_(set, module_name);
-    if (module == NULL) {
-        loading_set_unlock_(set);
-        ERROR_(ctx, FIMO_EINVAL, "module does not exist, module='%s'", module_name)
-        return FIMO_EINVAL;
-    }
-
-    const FimoResult error = loading_set_module_append_callback_(module, callback);
+    const FimoResult error = ctx_load_set(ctx, set);
     loading_set_unlock_(set);
+    ctx_unlock_(ctx);
     if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not append callback")
+        ERROR_SIMPLE_(ctx, error, "could not load set")
+        loading_set_free_(set);
         return error;
     }
 
-    return FIMO_EINVAL;
-}
-
-static FimoResult add_module_(FimoInternalModuleContext *ctx, struct hashmap *symbols, struct hashmap *modules,
-                              struct hashmap *opt_symbols, struct hashmap *opt_modules, struct ModuleHandle_ *handle,
-                              const FimoModuleExport *export, const FimoModule *owner) {
-    FIMO_DEBUG_ASSERT(ctx && symbols && modules && handle && export)
-    if (hashmap_get(modules, &(struct LoadingSetModule_){.name = export->name}) ||
-        (opt_modules && hashmap_get(opt_modules, &(struct LoadingSetModule_){.name = export->name}))) {
-        ERROR_(ctx, FIMO_EINVAL, "duplicate module, module='%s'", export->name)
-        return FIMO_EINVAL;
-    }
-    for (FimoISize i = 0; i < (FimoISize) export->symbol_exports_count; i++) {
-        const FimoModuleSymbolExport *sym = &export->symbol_exports[i];
-        if (hashmap_get(symbols, &(struct LoadingSetSymbol_){.name = sym->name, .ns = sym->ns}) ||
-            (opt_symbols && hashmap_get(opt_symbols, &(struct LoadingSetSymbol_){.name = sym->name, .ns = sym->ns}))) {
-            ERROR_(ctx, FIMO_EINVAL, "duplicate symbol, symbol='%s', ns='%s'", sym->name, sym->ns)
-            return FIMO_EINVAL;
-        }
-    }
-    for (FimoISize i = 0; i < (FimoISize) export->dynamic_symbol_exports_count; i++) {
-        const FimoModuleDynamicSymbolExport *sym = &export->dynamic_symbol_exports[i];
-        if (hashmap_get(symbols, &(struct LoadingSetSymbol_){.name = sym->name, .ns = sym->ns}) ||
-            (opt_symbols && hashmap_get(opt_symbols, &(struct LoadingSetSymbol_){.name = sym->name, .ns = sym->ns}))) {
-            ERROR_(ctx, FIMO_EINVAL, "duplicate symbol, symbol='%s', ns='%s'", sym->name, sym->ns)
-            return FIMO_EINVAL;
-        }
-    }
-
-    for (FimoISize i = 0; i < (FimoISize) export->symbol_exports_count; i++) {
-        const FimoModuleSymbolExport *sym = &export->symbol_exports[i];
-        struct LoadingSetSymbol_ symbol;
-        FimoResult error = loading_set_symbol_new_(sym->name, sym->ns, sym->version, export->name, &symbol);
-        if (FIMO_RESULT_IS_ERROR(error)) {
-            ERROR_SIMPLE_(ctx, error, "could not create symbol")
-            return error;
-        }
-        hashmap_set(symbols, &symbol);
-        if (hashmap_oom(symbols)) {
-            error = FIMO_ENOMEM;
-            loading_set_symbol_free_(&symbol);
-            ERROR_SIMPLE_(ctx, error, "could not insert symbol")
-            return error;
-        }
-    }
-    for (FimoISize i = 0; i < (FimoISize) export->dynamic_symbol_exports_count; i++) {
-        const FimoModuleDynamicSymbolExport *sym = &export->dynamic_symbol_exports[i];
-        struct LoadingSetSymbol_ symbol;
-        FimoResult error = loading_set_symbol_new_(sym->name, sym->ns, sym->version, export->name, &symbol);
-        if (FIMO_RESULT_IS_ERROR(error)) {
-            ERROR_SIMPLE_(ctx, error, "could not create symbol")
-            return error;
-        }
-        hashmap_set(symbols, &symbol);
-        if (hashmap_oom(symbols)) {
-            error = FIMO_ENOMEM;
-            loading_set_symbol_free_(&symbol);
-            ERROR_SIMPLE_(ctx, error, "could not insert symbol")
-            return error;
-        }
-    }
-
-    {
-        struct LoadingSetModule_ module;
-        FimoResult error = loading_set_module_new_(export, handle, owner, &module);
-        if (FIMO_RESULT_IS_ERROR(error)) {
-            ERROR_SIMPLE_(ctx, error, "could not create module")
-            return error;
-        }
-        hashmap_set(modules, &module);
-        if (hashmap_oom(modules)) {
-            error = FIMO_ENOMEM;
-            loading_set_module_free_(&module);
-            ERROR_SIMPLE_(ctx, error, "could not insert symbol")
-            return error;
-        }
-    }
-
+    loading_set_free_(set);
     return FIMO_EOK;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_set_append_freestanding_module(FimoInternalModuleContext *ctx, const FimoModule *module,
-                                                               FimoModuleLoadingSet *set,
-                                                               const FimoModuleExport *export) {
+FimoResult fimo_internal_module_find_by_name(FimoInternalModuleContext *ctx, const char *name,
+                                             const FimoModuleInfo **module) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (module == NULL || set == NULL || export == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', set='%p', export='%p'", (void *)module,
-               (void *)set, (void *)export)
+    if (name == NULL || module == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, name='%p', module='%p'", (void *)name, (void *)module)
         return FIMO_EINVAL;
     }
-    TRACE_SIMPLE_(ctx, "appending new freestanding module")
 
-    // Check that the export is valid.
-    if (!fi_module_export_is_valid_(export, ctx)) {
-        fi_module_export_cleanup_(export);
-        ERROR_SIMPLE_(ctx, FIMO_EINVAL, "export is invalid");
+    TRACE_(ctx, "name='%s'", name)
+    ctx_lock_(ctx);
+    const struct Module_ *mod = ctx_get_module_(ctx, name);
+    if (mod == NULL) {
+        ctx_unlock_(ctx);
+        ERROR_(ctx, FIMO_EINVAL, "no module by the given name exists, module='%s'", name)
         return FIMO_EINVAL;
     }
-
-    // Inherit the same handle as the parent module.
-    const struct ModuleInfo_ *info = module_info_from_module_(module);
-    struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
-    struct ModuleHandle_ *handle = info_inner->handle;
-    module_handle_acquire_(handle);
-    module_info_unlock_(info_inner);
-
-    // Insert the export into the set.
-    loading_set_lock_(set);
-    FimoResult error = add_module_(ctx, set->symbols, set->modules, NULL, NULL, info_inner->handle, export, module);
-    loading_set_unlock_(set);
-    module_handle_release_(handle);
-    if (FIMO_RESULT_IS_ERROR(error)) {
-        fi_module_export_cleanup_(export);
-        ERROR_(ctx, error, "could not insert the export into the set, module='%s'", export->name);
-        return error;
-    }
+    *module = FIMO_MODULE_INFO_ACQUIRE(mod->module->module_info);
+    ctx_unlock_(ctx);
 
     return FIMO_EOK;
 }
 
-struct AppendModulesData_ {
-    FimoInternalModuleContext *ctx;
-    FimoResult error;
-    FimoModuleLoadingFilter filter;
-    void *filter_data;
-    FimoArrayList exports;
-};
-
-static bool append_modules_iterator_(const FimoModuleExport *export, void *data) {
-    struct AppendModulesData_ *d = data;
-
-    if (!fi_module_export_is_valid_(export, d->ctx)) {
-        fi_module_export_cleanup_(export);
-        return true;
+FIMO_MUST_USE
+FimoResult fimo_internal_module_find_by_symbol(FimoInternalModuleContext *ctx, const char *name, const char *ns,
+                                               const FimoVersion version, const FimoModuleInfo **module) {
+    FIMO_DEBUG_ASSERT(ctx)
+    if (name == NULL || ns == NULL || module == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, name='%p', ns='%p', module='%p'", (void *)name, (void *)ns,
+               (void *)module)
+        return FIMO_EINVAL;
     }
 
-    if (d->filter == NULL || d->filter(export, d->filter_data)) {
-        d->error = fimo_array_list_push(&d->exports, sizeof(const FimoModuleExport *),
-                                        _Alignof(const FimoModuleExport *), &export, NULL);
-        if (FIMO_RESULT_IS_ERROR(d->error)) {
-            return false;
-        }
+    TRACE_(ctx, "name='%s', ns='%s', version='%" PRIu32 ".%" PRIu32 ".%" PRIu32 "+%" PRIu64 "'", name, ns,
+           version.major, version.minor, version.patch, version.build)
+    ctx_lock_(ctx);
+    const struct Symbol_ *symbol = ctx_get_symbol_compatible_(ctx, name, ns, version);
+    if (symbol == NULL) {
+        ctx_unlock_(ctx);
+        ERROR_(ctx, FIMO_EINVAL,
+               "no compatible symbol was found, name='%s', ns='%s', version='%" PRIu32 ".%" PRIu32 ".%" PRIu32
+               "+%" PRIu64 "'",
+               name, ns, version.major, version.minor, version.patch, version.build)
+        return FIMO_EINVAL;
     }
 
-    return true;
+    const struct Module_ *mod = ctx_get_module_(ctx, symbol->module);
+    FIMO_DEBUG_ASSERT(mod)
+    *module = FIMO_MODULE_INFO_ACQUIRE(mod->module->module_info);
+    ctx_unlock_(ctx);
+
+    return FIMO_EOK;
 }
 
-static FimoResult extract_exports_(FimoInternalModuleContext *ctx, FimoModuleLoadingSet *set,
-                                   struct ModuleHandle_ *handle, FimoArrayList exports) {
-    FIMO_ASSERT(ctx && set && handle)
-    FimoResult error;
-    struct hashmap *symbols = hashmap_new_with_allocator(
-            malloc_, realloc_, free_, sizeof(struct LoadingSetSymbol_), 0, 0, 0, (HashFn_)loading_set_symbol_hash_,
-            (CmpFn_)loading_set_symbol_cmp_, (FreeFn_)loading_set_symbol_free_, NULL);
-    if (symbols == NULL) {
-        error = FIMO_ENOMEM;
-        ERROR_SIMPLE_(ctx, error, "could not allocate symbols map")
-        goto alloc_symbols;
+FIMO_MUST_USE
+FimoResult fimo_internal_module_namespace_exists(FimoInternalModuleContext *ctx, const char *ns, bool *exists) {
+    FIMO_DEBUG_ASSERT(ctx)
+    if (ns == NULL || exists == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, ns='%p', exists='%p'", (void *)ns, (void *)exists)
+        return FIMO_EINVAL;
     }
 
-    struct hashmap *modules = hashmap_new_with_allocator(
-            malloc_, realloc_, free_, sizeof(struct LoadingSetModule_), 0, 0, 0, (HashFn_)loading_set_module_hash_,
-            (CmpFn_)loading_set_module_cmp_, (FreeFn_)loading_set_module_free_, NULL);
-    if (modules == NULL) {
-        error = FIMO_ENOMEM;
-        ERROR_SIMPLE_(ctx, error, "could not allocate modules map")
-        goto alloc_modules;
-    }
+    TRACE_(ctx, "ns='%s'", ns)
+    ctx_lock_(ctx);
+    const struct Namespace_ *namespace = ctx_get_ns_(ctx, ns);
+    *exists = namespace != NULL;
+    ctx_unlock_(ctx);
 
-    while (!fimo_array_list_is_empty(&exports)) {
-        const FimoModuleExport *export;
-        error = fimo_array_list_pop_back(&exports, sizeof(export), &export, NULL);
-        FIMO_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
+    return FIMO_EOK;
+}
 
-        error = add_module_(ctx, symbols, modules, set->symbols, set->modules, handle, export, NULL);
-        if (FIMO_RESULT_IS_ERROR(error)) {
-            ERROR_(ctx, error, "could not add export to set, module='%s'", export->name)
-            goto iterate_exports;
-        }
+FIMO_MUST_USE
+FimoResult fimo_internal_module_namespace_include(FimoInternalModuleContext *ctx, const FimoModule *module,
+                                                  const char *ns) {
+    FIMO_DEBUG_ASSERT(ctx)
+    if (module == NULL || ns == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', ns='%p'", (void *)module, (void *)ns)
+        return FIMO_EINVAL;
     }
 
-    {
-        size_t iter_idx = 0;
-        struct LoadingSetSymbol_ *iter_item = NULL;
-        while (hashmap_iter(symbols, &iter_idx, (void **)&iter_item)) {
-            iter_item = (void *)hashmap_delete(symbols, iter_item);
-            iter_idx = 0;
+    TRACE_(ctx, "ns='%s', module='%s'", ns, module->module_info->name)
+    ctx_lock_(ctx);
+    const struct ModuleInfo_ *info = module_info_from_module_(module);
+    struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
+    const struct ModuleInfoNamespace_ *info_namespace = module_info_get_ns_(info_inner, ns);
+    if (info_namespace != NULL) {
+        module_info_unlock_(info_inner);
+        ctx_unlock_(ctx);
+        ERROR_(ctx, FIMO_EEXIST, "namespace was already included by the module, ns='%s', module='%s'", ns,
+               info->info.name)
+        return FIMO_EEXIST;
+    }
 
-            hashmap_set(set->symbols, iter_item);
-            if (hashmap_oom(set->symbols)) {
-                loading_set_symbol_free_(iter_item);
-                error = FIMO_ENOMEM;
-                ERROR_SIMPLE_(ctx, error, "could not merge symbol maps")
-                goto transfer_symbols;
-            }
-        }
+    const struct Namespace_ *namespace = ctx_get_ns_(ctx, ns);
+    if (namespace == NULL) {
+        module_info_unlock_(info_inner);
+        ctx_unlock_(ctx);
+        ERROR_(ctx, FIMO_EINVAL, "namespace does not exist, ns='%s', module='%s'", ns, info->info.name)
+        return FIMO_EINVAL;
     }
-    {
-        size_t iter_idx = 0;
-        struct LoadingSetModule_ *iter_item = NULL;
-        while (hashmap_iter(modules, &iter_idx, (void *)&iter_item)) {
-            iter_item = (void *)hashmap_delete(modules, iter_item);
-            iter_idx = 0;
 
-            hashmap_set(set->modules, iter_item);
-            if (hashmap_oom(set->modules)) {
-                loading_set_module_free_(iter_item);
-                error = FIMO_ENOMEM;
-                ERROR_SIMPLE_(ctx, error, "could not merge module maps")
-                goto transfer_modules;
-            }
-        }
+    FimoResult error = ctx_ns_acquire_(ctx, ns);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        module_info_unlock_(info_inner);
+        ctx_unlock_(ctx);
+        ERROR_(ctx, error, "could not acquire namespace, ns='%s', module='%s'", ns, info->info.name)
+        return error;
     }
 
-    hashmap_free(modules);
-    hashmap_free(symbols);
+    error = module_info_set_ns_(info_inner, ns, false);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ctx_ns_release_(ctx, ns);
+        module_info_unlock_(info_inner);
+        ctx_unlock_(ctx);
+        ERROR_(ctx, error, "could not insert namespace into the module info, ns='%s', module='%s'", ns, info->info.name)
+        return error;
+    }
+    module_info_unlock_(info_inner);
+    ctx_unlock_(ctx);
 
-    set->should_recreate_map = true;
     return FIMO_EOK;
+}
 
-transfer_modules: {
-    size_t iter_idx = 0;
-    void *iter_item = NULL;
-    while (hashmap_iter(modules, &iter_idx, &iter_item)) {
-        void *mod = (void *)hashmap_delete(set->modules, iter_item);
-        if (mod) {
-            loading_set_module_free_(mod);
-        }
+FIMO_MUST_USE
+FimoResult fimo_internal_module_namespace_exclude(FimoInternalModuleContext *ctx, const FimoModule *module,
+                                                  const char *ns) {
+    FIMO_DEBUG_ASSERT(ctx)
+    if (module == NULL || ns == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', ns='%p'", (void *)module, (void *)ns)
+        return FIMO_EINVAL;
     }
-}
-transfer_symbols: {
-    size_t iter_idx = 0;
-    void *iter_item = NULL;
-    while (hashmap_iter(symbols, &iter_idx, &iter_item)) {
-        void *sym = (void *)hashmap_delete(set->symbols, iter_item);
-        if (sym) {
-            loading_set_symbol_free_(sym);
-        }
+
+    TRACE_(ctx, "ns='%s', module='%s'", ns, module->module_info->name)
+    ctx_lock_(ctx);
+    struct ModuleInfo_ *info = (void *)module_info_from_module_(module);
+    struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
+    const struct ModuleInfoNamespace_ *info_namespace = module_info_get_ns_(info_inner, ns);
+    if (info_namespace == NULL) {
+        module_info_unlock_(info_inner);
+        ctx_unlock_(ctx);
+        ERROR_(ctx, FIMO_EEXIST, "namespace was not included by the module, ns='%s', module='%s'", ns, info->info.name)
+        return FIMO_EEXIST;
     }
-}
-iterate_exports:
-    hashmap_free(modules);
-alloc_modules:
-    hashmap_free(symbols);
-alloc_symbols:
-    return error;
+    if (info_namespace->is_static) {
+        module_info_unlock_(info_inner);
+        ctx_unlock_(ctx);
+        ERROR_(ctx, FIMO_EPERM, "can not exclude static namespace, ns='%s', module='%s'", ns, info->info.name)
+        return FIMO_EPERM;
+    }
+
+    module_info_delete_ns_(info_inner, ns);
+    ctx_ns_release_(ctx, ns);
+    module_info_unlock_(info_inner);
+    ctx_unlock_(ctx);
+
+    return FIMO_EOK;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_set_append_modules(
-        FimoInternalModuleContext *ctx, FimoModuleLoadingSet *set, const char *module_path,
-        const FimoModuleLoadingFilter filter, void *filter_data,
-        void (*export_iterator)(bool (*)(const FimoModuleExport *, void *), void *), const void *binary_handle) {
+FimoResult fimo_internal_module_namespace_included(FimoInternalModuleContext *ctx, const FimoModule *module,
+                                                   const char *ns, bool *is_included, bool *is_static) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (set == NULL || export_iterator == NULL || binary_handle == NULL) {
-        ERROR_(ctx, FIMO_EINVAL,
-               "invalid null parameter, set='%p', module_path='%s', export_iterator='%p', binary_handle='%p'",
-               (void *)set, module_path, *(void **)&export_iterator, binary_handle)
+    if (module == NULL || ns == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', ns='%p', is_included='%p', is_static='%p'",
+               (void *)module, (void *)ns, (void *)is_included, (void *)is_static)
         return FIMO_EINVAL;
     }
 
-    FimoResult error;
-    struct ModuleHandle_ *handle = NULL;
-    if (module_path) {
-        TRACE_(ctx, "module_path='%s'", module_path)
-        error = module_handle_new_plugin_(module_path, &handle);
+    TRACE_(ctx, "ns='%s', module='%s'", ns, module->module_info->name)
+    const struct ModuleInfo_ *info = module_info_from_module_(module);
+    struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
+    const struct ModuleInfoNamespace_ *info_namespace = module_info_get_ns_(info_inner, ns);
+    if (info_namespace != NULL) {
+        *is_included = true;
+        *is_static = info_namespace->is_static;
     }
     else {
-        TRACE_SIMPLE_(ctx, "local module")
-        error = module_handle_new_local_(export_iterator, binary_handle, &handle);
+        *is_included = false;
+        *is_static = false;
     }
-    if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not create module handle")
-        return error;
+    module_info_unlock_(info_inner);
+
+    return FIMO_EOK;
+}
+
+FIMO_MUST_USE
+FimoResult fimo_internal_module_acquire_dependency(FimoInternalModuleContext *ctx, const FimoModule *module,
+                                                   const FimoModuleInfo *dependency) {
+    FIMO_DEBUG_ASSERT(ctx)
+    if (module == NULL || dependency == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', dependency='%p'", (void *)module,
+               (void *)dependency)
+        return FIMO_EINVAL;
     }
 
-    struct AppendModulesData_ data = {.ctx = ctx,
-                                      .error = FIMO_EOK,
-                                      .filter = filter,
-                                      .filter_data = filter_data,
-                                      .exports = fimo_array_list_new()};
-    handle->export_iterator(append_modules_iterator_, &data);
-    if (FIMO_RESULT_IS_ERROR(data.error)) {
-        error = data.error;
-        ERROR_SIMPLE_(ctx, error, "could not iterate through the module exports of the binary")
-        goto iterate_exports;
+    const struct ModuleInfo_ *info = module_info_from_module_(module);
+    const struct ModuleInfo_ *dependency_info = module_info_from_module_info_(dependency);
+    TRACE_(ctx, "module='%s', dependency='%s'", info->info.name, dependency_info->info.name)
+    if (info == dependency_info) {
+        ERROR_(ctx, FIMO_EINVAL, "can not link module to itself, module='%s'", info->info.name)
+        return FIMO_EINVAL;
     }
 
-    loading_set_lock_(set);
-    error = extract_exports_(ctx, set, handle, data.exports);
-    loading_set_unlock_(set);
+    ctx_lock_(ctx);
+    struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
+    struct ModuleInfoInner_ *dependency_info_inner = module_info_lock_(dependency_info);
+    const FimoResult error = ctx_link_module_(ctx, info_inner, dependency_info_inner);
+    module_info_unlock_(dependency_info_inner);
+    module_info_unlock_(info_inner);
+    ctx_unlock_(ctx);
     if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not extract the module exports")
-        goto extract_exports;
+        ERROR_(ctx, error, "could not acquire dependency, module='%s', dependency='%s'", module->module_info->name,
+               dependency->name)
+        return error;
     }
 
-    fimo_array_list_free(&data.exports, sizeof(const FimoModuleExport *), _Alignof(const FimoModuleExport *), NULL);
-    module_handle_release_(handle);
-
     return FIMO_EOK;
-
-extract_exports:;
-iterate_exports:
-    fimo_array_list_free(&data.exports, sizeof(const FimoModuleExport *), _Alignof(const FimoModuleExport *), NULL);
-    module_handle_release_(handle);
-
-    return error;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_set_dismiss(FimoInternalModuleContext *ctx, FimoModuleLoadingSet *set) {
+FimoResult fimo_internal_module_relinquish_dependency(FimoInternalModuleContext *ctx, const FimoModule *module,
+                                                      const FimoModuleInfo *dependency) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (set == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, set='%p'", (void *)set)
+    if (module == NULL || dependency == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', dependency='%p'", (void *)module,
+               (void *)dependency)
         return FIMO_EINVAL;
     }
 
-    TRACE_SIMPLE_(ctx, "dismissing set")
-    loading_set_lock_(set);
-    if (set->is_loading) {
-        loading_set_unlock_(set);
-        ERROR_SIMPLE_(ctx, FIMO_EPERM, "set is being loaded")
-        return FIMO_EPERM;
+    const struct ModuleInfo_ *info = module_info_from_module_(module);
+    const struct ModuleInfo_ *dependency_info = module_info_from_module_info_(dependency);
+    TRACE_(ctx, "module='%s', dependency='%s'", module->module_info->name, dependency->name)
+    if (info == dependency_info) {
+        ERROR_(ctx, FIMO_EINVAL, "module can not relinquish itself, module='%s'", info->info.name)
+        return FIMO_EINVAL;
+    }
+
+    ctx_lock_(ctx);
+    struct ModuleInfoInner_ *inner = module_info_lock_(info);
+    struct ModuleInfoInner_ *dependency_info_inner = module_info_lock_(dependency_info);
+    const FimoResult error = ctx_unlink_module_(ctx, inner, dependency_info_inner);
+    module_info_unlock_(dependency_info_inner);
+    module_info_unlock_(inner);
+    ctx_unlock_(ctx);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_(ctx, error, "could not relinquish dependency, module='%s', dependency='%s'", module->module_info->name,
+               dependency->name)
+        return error;
     }
-    loading_set_unlock_(set);
-    loading_set_free_(set);
 
     return FIMO_EOK;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_set_finish(FimoInternalModuleContext *ctx, FimoModuleLoadingSet *set) {
+FimoResult fimo_internal_module_has_dependency(FimoInternalModuleContext *ctx, const FimoModule *module,
+                                               const FimoModuleInfo *other, bool *has_dependency, bool *is_static) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (set == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, set='%p'", (void *)set)
+    if (module == NULL || other == NULL || has_dependency == NULL || is_static == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', other='%p', has_dependency='%p', is_static='%p'",
+               (void *)module, (void *)other, (void *)has_dependency, (void *)is_static)
         return FIMO_EINVAL;
     }
 
-    TRACE_SIMPLE_(ctx, "loading module set")
-    ctx_lock_(ctx);
-    loading_set_lock_(set);
-    const FimoResult error = ctx_load_set(ctx, set);
-    loading_set_unlock_(set);
-    ctx_unlock_(ctx);
-    if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not load set")
-        loading_set_free_(set);
-        return error;
+    TRACE_(ctx, "module='%s', other='%s'", module->module_info->name, other->name)
+    const struct ModuleInfo_ *info = module_info_from_module_(module);
+    struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
+    const struct ModuleInfoDependency_ *dependency = module_info_get_dependency_(info_inner, other->name);
+    if (dependency == NULL) {
+        *has_dependency = false;
+        *is_static = false;
+    }
+    else {
+        *has_dependency = true;
+        *is_static = dependency->is_static;
     }
+    module_info_unlock_(info_inner);
 
-    loading_set_free_(set);
     return FIMO_EOK;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_find_by_name(FimoInternalModuleContext *ctx, const char *name,
-                                             const FimoModuleInfo **module) {
+FimoResult fimo_internal_module_load_symbol(FimoInternalModuleContext *ctx, const FimoModule *module, const char *name,
+                                            const char *ns, FimoVersion version, const FimoModuleRawSymbol **symbol) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (name == NULL || module == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, name='%p', module='%p'", (void *)name, (void *)module)
+    if (module == NULL || name == NULL || ns == NULL || symbol == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', name='%p', ns='%p', symbol='%p'", (void *)module,
+               (void *)name, (void *)ns, (void *)symbol)
         return FIMO_EINVAL;
     }
 
-    TRACE_(ctx, "name='%s'", name)
+    TRACE_(ctx, "module='%s', name='%s', ns='%s', version='%" PRIu32 ".%" PRIu32 ".%" PRIu32 "+%" PRIu64 "'",
+           module->module_info->name, name, ns, version.major, version.minor, version.patch, version.build)
     ctx_lock_(ctx);
-    const struct Module_ *mod = ctx_get_module_(ctx, name);
-    if (mod == NULL) {
+    const struct Symbol_ *sym = ctx_get_symbol_compatible_(ctx, name, ns, version);
+    if (sym == NULL) {
         ctx_unlock_(ctx);
-        ERROR_(ctx, FIMO_EINVAL, "no module by the given name exists, module='%s'", name)
+        ERROR_(ctx, FIMO_EINVAL,
+               "could not find a compatible symbol, module='%s', name='%s', ns='%s', version='%" PRIu32 ".%" PRIu32
+               ".%" PRIu32 "+%" PRIu64 "'",
+               module->module_info->name, name, ns, version.major, version.minor, version.patch, version.build)
         return FIMO_EINVAL;
     }
-    *module = FIMO_MODULE_INFO_ACQUIRE(mod->module->module_info);
+
+    const struct ModuleInfo_ *info = module_info_from_module_(module);
+    struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
+    const struct ModuleInfoDependency_ *dependency = module_info_get_dependency_(info_inner, sym->module);
+    if (dependency == NULL) {
+        module_info_unlock_(info_inner);
+        ctx_unlock_(ctx);
+        ERROR_(ctx, FIMO_EPERM,
+               "module exposing the symbol is not a dependency, exposed_by='%s', module='%s', name='%s', ns='%s', "
+               "version='%" PRIu32 ".%" PRIu32 ".%" PRIu32 "+%" PRIu64 "'",
+               sym->module, module->module_info->name, name, ns, version.major, version.minor, version.patch,
+               version.build)
+        return FIMO_EPERM;
+    }
+    if (module_info_get_ns_(info_inner, ns) == NULL && strcmp(ns, GLOBAL_NS) != 0) {
+        module_info_unlock_(info_inner);
+        ctx_unlock_(ctx);
+        ERROR_(ctx, FIMO_EPERM,
+               "module does not include the namespace it tried to load a symbol from, module='%s', name='%s', ns='%s', "
+               "version='%" PRIu32 ".%" PRIu32 ".%" PRIu32 "+%" PRIu64 "'",
+               module->module_info->name, name, ns, version.major, version.minor, version.patch, version.build)
+        return FIMO_EPERM;
+    }
+
+    const struct Module_ *symbol_owner = ctx_get_module_(ctx, sym->module);
+    FIMO_DEBUG_ASSERT(symbol_owner)
+    const struct ModuleInfo_ *symbol_owner_info = module_info_from_module_(symbol_owner->module);
+    struct ModuleInfoInner_ *symbol_owner_info_inner = module_info_lock_(symbol_owner_info);
+    const struct ModuleInfoSymbol_ *info_symbol = module_info_get_symbol_(symbol_owner_info_inner, name, ns, version);
+    FIMO_DEBUG_ASSERT(info_symbol);
+
+    // Remember the loaded symbol, so that its users can be queried.
+    const FimoResult error =
+            module_info_dependency_add_symbol_((struct ModuleInfoDependency_ *)dependency, &info_symbol->symbol);
+    module_info_unlock_(symbol_owner_info_inner);
+    module_info_unlock_(info_inner);
     ctx_unlock_(ctx);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_SIMPLE_(ctx, error, "could not record the loaded symbol")
+        return error;
+    }
 
+    *symbol = &info_symbol->symbol;
     return FIMO_EOK;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_find_by_symbol(FimoInternalModuleContext *ctx, const char *name, const char *ns,
-                                               const FimoVersion version, const FimoModuleInfo **module) {
+FimoResult fimo_internal_module_unload(FimoInternalModuleContext *ctx, const FimoModuleInfo *module) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (name == NULL || ns == NULL || module == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, name='%p', ns='%p', module='%p'", (void *)name, (void *)ns,
-               (void *)module)
-        return FIMO_EINVAL;
-    }
 
-    TRACE_(ctx, "name='%s', ns='%s', version='%" PRIu32 ".%" PRIu32 ".%" PRIu32 "+%" PRIu64 "'", name, ns,
-           version.major, version.minor, version.patch, version.build)
     ctx_lock_(ctx);
-    const struct Symbol_ *symbol = ctx_get_symbol_compatible_(ctx, name, ns, version);
-    if (symbol == NULL) {
-        ctx_unlock_(ctx);
-        ERROR_(ctx, FIMO_EINVAL,
-               "no compatible symbol was found, name='%s', ns='%s', version='%" PRIu32 ".%" PRIu32 ".%" PRIu32
-               "+%" PRIu64 "'",
-               name, ns, version.major, version.minor, version.patch, version.build)
-        return FIMO_EINVAL;
+    if (module != NULL) {
+        TRACE_(ctx, "unloading module, module='%s'", module->name)
+        const struct ModuleInfo_ *info = module_info_from_module_info_(module);
+        if (info->type != MODULE_TYPE_REGULAR_) {
+            ctx_unlock_(ctx);
+            ERROR_SIMPLE_(ctx, FIMO_EPERM, "can only unload regular modules")
+            return FIMO_EPERM;
+        }
+
+        // The modules of a group are unloaded together.
+        if (module->group != NULL) {
+            const FimoResult error = ctx_remove_group_(ctx, module->group);
+            if (FIMO_RESULT_IS_ERROR(error)) {
+                ctx_unlock_(ctx);
+                ERROR_SIMPLE_(ctx, error, "could not remove module group from context")
+                return error;
+            }
+            goto cleanup;
+        }
+
+        struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
+
+        const FimoResult error = ctx_remove_module_(ctx, info_inner);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            module_info_unlock_(info_inner);
+            ctx_unlock_(ctx);
+            ERROR_SIMPLE_(ctx, error, "could not remove module from context")
+            return error;
+        }
+
+        fi_module_free_(info_inner, NULL, true);
     }
 
-    const struct Module_ *mod = ctx_get_module_(ctx, symbol->module);
-    FIMO_DEBUG_ASSERT(mod)
-    *module = FIMO_MODULE_INFO_ACQUIRE(mod->module->module_info);
+cleanup:;
+    const FimoResult error = ctx_cleanup_loose_modules(ctx);
     ctx_unlock_(ctx);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_SIMPLE_(ctx, error, "could not cleanup loose modules")
+        return error;
+    }
 
     return FIMO_EOK;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_namespace_exists(FimoInternalModuleContext *ctx, const char *ns, bool *exists) {
+FimoResult fimo_internal_module_param_query(FimoInternalModuleContext *ctx, const char *module_name, const char *param,
+                                            FimoModuleParamType *type, FimoModuleParamAccess *read,
+                                            FimoModuleParamAccess *write) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (ns == NULL || exists == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, ns='%p', exists='%p'", (void *)ns, (void *)exists)
+    if (module_name == NULL || param == NULL || type == NULL || read == NULL || write == NULL) {
+        ERROR_(ctx, FIMO_EINVAL,
+               "invalid null parameter, module_name='%p', param='%p', type='%p', read='%p', write='%p'",
+               (void *)module_name, (void *)param, (void *)type, (void *)read, (void *)write)
         return FIMO_EINVAL;
     }
 
-    TRACE_(ctx, "ns='%s'", ns)
+    TRACE_(ctx, "module_name='%s', param='%s'", module_name, param)
     ctx_lock_(ctx);
-    const struct Namespace_ *namespace = ctx_get_ns_(ctx, ns);
-    *exists = namespace != NULL;
+    const struct Module_ *module = ctx_get_module_(ctx, module_name);
+    if (module == NULL) {
+        ctx_unlock_(ctx);
+        ERROR_(ctx, FIMO_EINVAL, "module does not exist, module='%s'", module_name)
+        return FIMO_EINVAL;
+    }
+
+    const struct ModuleInfo_ *module_info = module_info_from_module_(module->module);
+    struct ModuleInfoInner_ *module_info_inner = module_info_lock_(module_info);
+    const struct ModuleInfoParam_ *module_param = module_info_get_param_(module_info_inner, param);
+    if (module_param == NULL) {
+        module_info_unlock_(module_info_inner);
+        ctx_unlock_(ctx);
+        ERROR_(ctx, FIMO_EINVAL, "parameter not found, module='%s', param='%s'", module_name, param)
+        return FIMO_EINVAL;
+    }
+
+    *type = module_param->param->data.type;
+    *read = module_param->param->read;
+    *write = module_param->param->write;
+
+    module_info_unlock_(module_info_inner);
     ctx_unlock_(ctx);
 
     return FIMO_EOK;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_namespace_include(FimoInternalModuleContext *ctx, const FimoModule *module,
-                                                  const char *ns) {
+FimoResult fimo_internal_module_param_set_public(FimoInternalModuleContext *ctx, const void *value,
+                                                 const FimoModuleParamType type, const char *module_name,
+                                                 const char *param) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (module == NULL || ns == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', ns='%p'", (void *)module, (void *)ns)
+    if (value == NULL || module_name == NULL || param == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, value='%p', module_name='%p', param='%p'", (void *)value,
+               (void *)module_name, (void *)param)
         return FIMO_EINVAL;
     }
 
-    TRACE_(ctx, "ns='%s', module='%s'", ns, module->module_info->name)
+    TRACE_(ctx, "module_name='%s', param='%s'", module_name, param)
     ctx_lock_(ctx);
-    const struct ModuleInfo_ *info = module_info_from_module_(module);
-    struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
-    const struct ModuleInfoNamespace_ *info_namespace = module_info_get_ns_(info_inner, ns);
-    if (info_namespace != NULL) {
-        module_info_unlock_(info_inner);
+    const struct Module_ *module = ctx_get_module_(ctx, module_name);
+    if (module == NULL) {
         ctx_unlock_(ctx);
-        ERROR_(ctx, FIMO_EEXIST, "namespace was already included by the module, ns='%s', module='%s'", ns,
-               info->info.name)
-        return FIMO_EEXIST;
+        ERROR_(ctx, FIMO_EINVAL, "module does not exist, module='%s'", module_name)
+        return FIMO_EINVAL;
     }
 
-    const struct Namespace_ *namespace = ctx_get_ns_(ctx, ns);
-    if (namespace == NULL) {
-        module_info_unlock_(info_inner);
+    const struct ModuleInfo_ *module_info = module_info_from_module_(module->module);
+    struct ModuleInfoInner_ *module_info_inner = module_info_lock_(module_info);
+    const struct ModuleInfoParam_ *module_param = module_info_get_param_(module_info_inner, param);
+    if (module_param == NULL) {
+        module_info_unlock_(module_info_inner);
         ctx_unlock_(ctx);
-        ERROR_(ctx, FIMO_EINVAL, "namespace does not exist, ns='%s', module='%s'", ns, info->info.name)
+        ERROR_(ctx, FIMO_EINVAL, "parameter not found, module='%s', param='%s'", module_name, param)
         return FIMO_EINVAL;
     }
 
-    FimoResult error = ctx_ns_acquire_(ctx, ns);
-    if (FIMO_RESULT_IS_ERROR(error)) {
-        module_info_unlock_(info_inner);
+    if (!param_can_write_public(module_param->param)) {
+        module_info_unlock_(module_info_inner);
         ctx_unlock_(ctx);
-        ERROR_(ctx, error, "could not acquire namespace, ns='%s', module='%s'", ns, info->info.name)
-        return error;
+        ERROR_(ctx, FIMO_EPERM, "write not permitted, module='%s', param='%s'", module_name, param)
+        return FIMO_EPERM;
     }
 
-    error = module_info_set_ns_(info_inner, ns, false);
+    const FimoResult error = param_write_((FimoModuleParam *)module_param->param, module->module, value, type);
+    module_info_unlock_(module_info_inner);
+    ctx_unlock_(ctx);
     if (FIMO_RESULT_IS_ERROR(error)) {
-        ctx_ns_release_(ctx, ns);
-        module_info_unlock_(info_inner);
-        ctx_unlock_(ctx);
-        ERROR_(ctx, error, "could not insert namespace into the module info, ns='%s', module='%s'", ns, info->info.name)
+        ERROR_(ctx, error, "could not write to param, module='%s', param='%s'", module_name, param)
         return error;
     }
-    module_info_unlock_(info_inner);
-    ctx_unlock_(ctx);
 
     return FIMO_EOK;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_namespace_exclude(FimoInternalModuleContext *ctx, const FimoModule *module,
-                                                  const char *ns) {
+FimoResult fimo_internal_module_param_get_public(FimoInternalModuleContext *ctx, void *value, FimoModuleParamType *type,
+                                                 const char *module_name, const char *param) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (module == NULL || ns == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', ns='%p'", (void *)module, (void *)ns)
+    if (value == NULL || type == NULL || module_name == NULL || param == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, value='%p', type='%p', module_name='%p', param='%p'",
+               (void *)value, (void *)type, (void *)module_name, (void *)param)
         return FIMO_EINVAL;
     }
 
-    TRACE_(ctx, "ns='%s', module='%s'", ns, module->module_info->name)
+    TRACE_(ctx, "module_name='%s', param='%s'", module_name, param)
     ctx_lock_(ctx);
-    struct ModuleInfo_ *info = (void *)module_info_from_module_(module);
-    struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
-    const struct ModuleInfoNamespace_ *info_namespace = module_info_get_ns_(info_inner, ns);
-    if (info_namespace == NULL) {
-        module_info_unlock_(info_inner);
+    const struct Module_ *module = ctx_get_module_(ctx, module_name);
+    if (module == NULL) {
         ctx_unlock_(ctx);
-        ERROR_(ctx, FIMO_EEXIST, "namespace was not included by the module, ns='%s', module='%s'", ns, info->info.name)
-        return FIMO_EEXIST;
+        ERROR_(ctx, FIMO_EINVAL, "module does not exist, module='%s'", module_name)
+        return FIMO_EINVAL;
     }
-    if (info_namespace->is_static) {
-        module_info_unlock_(info_inner);
+
+    const struct ModuleInfo_ *module_info = module_info_from_module_(module->module);
+    struct ModuleInfoInner_ *module_info_inner = module_info_lock_(module_info);
+    const struct ModuleInfoParam_ *module_param = module_info_get_param_(module_info_inner, param);
+    if (module_param == NULL) {
+        module_info_unlock_(module_info_inner);
         ctx_unlock_(ctx);
-        ERROR_(ctx, FIMO_EPERM, "can not exclude static namespace, ns='%s', module='%s'", ns, info->info.name)
+        ERROR_(ctx, FIMO_EINVAL, "parameter not found, module='%s', param='%s'", module_name, param)
+        return FIMO_EINVAL;
+    }
+
+    if (!param_can_read_public(module_param->param)) {
+        module_info_unlock_(module_info_inner);
+        ctx_unlock_(ctx);
+        ERROR_(ctx, FIMO_EPERM, "read not permitted, module='%s', param='%s'", module_name, param)
         return FIMO_EPERM;
     }
 
-    module_info_delete_ns_(info_inner, ns);
-    ctx_ns_release_(ctx, ns);
-    module_info_unlock_(info_inner);
+    const FimoResult error = param_read_(module_param->param, module->module, value, type);
+    module_info_unlock_(module_info_inner);
     ctx_unlock_(ctx);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_(ctx, error, "could not read from param, module='%s', param='%s'", module_name, param)
+        return error;
+    }
 
     return FIMO_EOK;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_namespace_included(FimoInternalModuleContext *ctx, const FimoModule *module,
-                                                   const char *ns, bool *is_included, bool *is_static) {
+FimoResult fimo_internal_module_param_set_dependency(FimoInternalModuleContext *ctx, const FimoModule *module,
+                                                     const void *value, const FimoModuleParamType type,
+                                                     const char *module_name, const char *param) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (module == NULL || ns == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', ns='%p', is_included='%p', is_static='%p'",
-               (void *)module, (void *)ns, (void *)is_included, (void *)is_static)
+    if (module == NULL || value == NULL || module_name == NULL || param == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', value='%p', module_name='%p', param='%p'",
+               (void *)module, (void *)value, (void *)module_name, (void *)param)
         return FIMO_EINVAL;
     }
 
-    TRACE_(ctx, "ns='%s', module='%s'", ns, module->module_info->name)
-    const struct ModuleInfo_ *info = module_info_from_module_(module);
-    struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
-    const struct ModuleInfoNamespace_ *info_namespace = module_info_get_ns_(info_inner, ns);
-    if (info_namespace != NULL) {
-        *is_included = true;
-        *is_static = info_namespace->is_static;
-    }
-    else {
-        *is_included = false;
-        *is_static = false;
+    TRACE_(ctx, "module='%p', module_name='%s', param='%s'", (void *)module, module_name, param)
+    const struct ModuleInfo_ *caller_info = module_info_from_module_(module);
+    struct ModuleInfoInner_ *caller_info_inner = module_info_lock_(caller_info);
+    const struct ModuleInfoDependency_ *dep = module_info_get_dependency_(caller_info_inner, module_name);
+    if (dep == NULL) {
+        module_info_unlock_(caller_info_inner);
+        ERROR_(ctx, FIMO_EINVAL, "module is not a dependency, module='%s', caller='%s'", module_name,
+               module->module_info->name)
+        return FIMO_EINVAL;
     }
-    module_info_unlock_(info_inner);
-
-    return FIMO_EOK;
-}
 
-FIMO_MUST_USE
-FimoResult fimo_internal_module_acquire_dependency(FimoInternalModuleContext *ctx, const FimoModule *module,
-                                                   const FimoModuleInfo *dependency) {
-    FIMO_DEBUG_ASSERT(ctx)
-    if (module == NULL || dependency == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', dependency='%p'", (void *)module,
-               (void *)dependency)
+    const struct ModuleInfo_ *dep_info = module_info_from_module_info_(dep->info);
+    struct ModuleInfoInner_ *dep_info_inner = module_info_lock_(dep_info);
+    const struct ModuleInfoParam_ *dep_param = module_info_get_param_(dep_info_inner, param);
+    if (dep_param == NULL) {
+        module_info_unlock_(dep_info_inner);
+        module_info_unlock_(caller_info_inner);
+        ERROR_(ctx, FIMO_EINVAL, "parameter not found, module='%s', parameter='%s'", module_name, param)
         return FIMO_EINVAL;
     }
 
-    const struct ModuleInfo_ *info = module_info_from_module_(module);
-    const struct ModuleInfo_ *dependency_info = module_info_from_module_info_(dependency);
-    TRACE_(ctx, "module='%s', dependency='%s'", info->info.name, dependency_info->info.name)
-    if (info == dependency_info) {
-        ERROR_(ctx, FIMO_EINVAL, "can not link module to itself, module='%s'", info->info.name)
-        return FIMO_EINVAL;
+    if (!param_can_write_dependency(dep_param->param, caller_info_inner)) {
+        module_info_unlock_(dep_info_inner);
+        module_info_unlock_(caller_info_inner);
+        ERROR_(ctx, FIMO_EPERM, "write not permitted, caller='%s', module='%s', parameter='%s'",
+               module->module_info->name, module_name, param)
+        return FIMO_EPERM;
     }
 
-    ctx_lock_(ctx);
-    struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
-    struct ModuleInfoInner_ *dependency_info_inner = module_info_lock_(dependency_info);
-    const FimoResult error = ctx_link_module_(ctx, info_inner, dependency_info_inner);
-    module_info_unlock_(dependency_info_inner);
-    module_info_unlock_(info_inner);
-    ctx_unlock_(ctx);
+    FIMO_ASSERT(!module_info_is_detached_(dep_info_inner) && dep_info_inner->module)
+    const FimoResult error = param_write_((FimoModuleParam *)dep_param->param, dep_info_inner->module, value, type);
+    module_info_unlock_(dep_info_inner);
+    module_info_unlock_(caller_info_inner);
     if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_(ctx, error, "could not acquire dependency, module='%s', dependency='%s'", module->module_info->name,
-               dependency->name)
+        ERROR_(ctx, error, "could not write to param, module='%s', param='%s'", module_name, param)
         return error;
     }
 
@@ -4886,33 +7231,53 @@ FimoResult fimo_internal_module_acquire_dependency(FimoInternalModuleContext *ct
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_relinquish_dependency(FimoInternalModuleContext *ctx, const FimoModule *module,
-                                                      const FimoModuleInfo *dependency) {
+FimoResult fimo_internal_module_param_get_dependency(FimoInternalModuleContext *ctx, const FimoModule *module,
+                                                     void *value, FimoModuleParamType *type, const char *module_name,
+                                                     const char *param) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (module == NULL || dependency == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', dependency='%p'", (void *)module,
-               (void *)dependency)
+    if (module == NULL || value == NULL || type == NULL || module_name == NULL || param == NULL) {
+        ERROR_(ctx, FIMO_EINVAL,
+               "invalid null parameter, module='%p', value='%p', type='%p', module_name='%p', param='%p'",
+               (void *)module, value, (void *)type, (void *)module_name, (void *)param)
+        return FIMO_EINVAL;
+    }
+
+    TRACE_(ctx, "module='%p', module_name='%s', param='%s'", (void *)module, module_name, param)
+    const struct ModuleInfo_ *caller_info = module_info_from_module_(module);
+    struct ModuleInfoInner_ *caller_info_inner = module_info_lock_(caller_info);
+    const struct ModuleInfoDependency_ *dep = module_info_get_dependency_(caller_info_inner, module_name);
+    if (dep == NULL) {
+        module_info_unlock_(caller_info_inner);
+        ERROR_(ctx, FIMO_EINVAL, "module is not a dependency, module='%s', caller='%s'", module_name,
+               module->module_info->name)
+        return FIMO_EINVAL;
+    }
+
+    const struct ModuleInfo_ *dep_info = module_info_from_module_info_(dep->info);
+    struct ModuleInfoInner_ *dep_info_inner = module_info_lock_(dep_info);
+    const struct ModuleInfoParam_ *dep_param = module_info_get_param_(dep_info_inner, param);
+    if (dep_param == NULL) {
+        module_info_unlock_(dep_info_inner);
+        module_info_unlock_(caller_info_inner);
+        ERROR_(ctx, FIMO_EINVAL, "parameter not found, module='%s', parameter='%s'", module_name, param)
         return FIMO_EINVAL;
     }
 
-    const struct ModuleInfo_ *info = module_info_from_module_(module);
-    const struct ModuleInfo_ *dependency_info = module_info_from_module_info_(dependency);
-    TRACE_(ctx, "module='%s', dependency='%s'", module->module_info->name, dependency->name)
-    if (info == dependency_info) {
-        ERROR_(ctx, FIMO_EINVAL, "module can not relinquish itself, module='%s'", info->info.name)
-        return FIMO_EINVAL;
-    }
+    if (!param_can_read_dependency(dep_param->param, caller_info_inner)) {
+        module_info_unlock_(dep_info_inner);
+        module_info_unlock_(caller_info_inner);
+        ERROR_(ctx, FIMO_EPERM, "read not permitted, caller='%s', module='%s', parameter='%s'",
+               module->module_info->name, module_name, param)
+        return FIMO_EPERM;
+    }
+
+    FIMO_ASSERT(!module_info_is_detached_(dep_info_inner) && dep_info_inner->module)
+    const FimoResult error = param_read_(dep_param->param, dep_info_inner->module, value, type);
 
-    ctx_lock_(ctx);
-    struct ModuleInfoInner_ *inner = module_info_lock_(info);
-    struct ModuleInfoInner_ *dependency_info_inner = module_info_lock_(dependency_info);
-    const FimoResult error = ctx_unlink_module_(ctx, inner, dependency_info_inner);
-    module_info_unlock_(dependency_info_inner);
-    module_info_unlock_(inner);
-    ctx_unlock_(ctx);
+    module_info_unlock_(dep_info_inner);
+    module_info_unlock_(caller_info_inner);
     if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_(ctx, error, "could not relinquish dependency, module='%s', dependency='%s'", module->module_info->name,
-               dependency->name)
+        ERROR_(ctx, error, "could not read from param, module='%s', param='%s'", module_name, param)
         return error;
     }
 
@@ -4920,229 +7285,281 @@ FimoResult fimo_internal_module_relinquish_dependency(FimoInternalModuleContext
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_has_dependency(FimoInternalModuleContext *ctx, const FimoModule *module,
-                                               const FimoModuleInfo *other, bool *has_dependency, bool *is_static) {
+FimoResult fimo_internal_module_param_set_private(FimoInternalModuleContext *ctx, const FimoModule *module,
+                                                  const void *value, const FimoModuleParamType type,
+                                                  FimoModuleParam *param) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (module == NULL || other == NULL || has_dependency == NULL || is_static == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', other='%p', has_dependency='%p', is_static='%p'",
-               (void *)module, (void *)other, (void *)has_dependency, (void *)is_static)
+    if (module == NULL || value == NULL || param == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', value='%p', type='%d', param='%p'",
+               (void *)module, value, type, (void *)param)
         return FIMO_EINVAL;
     }
 
-    TRACE_(ctx, "module='%s', other='%s'", module->module_info->name, other->name)
     const struct ModuleInfo_ *info = module_info_from_module_(module);
     struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
-    const struct ModuleInfoDependency_ *dependency = module_info_get_dependency_(info_inner, other->name);
-    if (dependency == NULL) {
-        *has_dependency = false;
-        *is_static = false;
-    }
-    else {
-        *has_dependency = true;
-        *is_static = dependency->is_static;
+    TRACE_(ctx, "module='%p', param='%p', owner='%p', read='%d', write='%d', type='%d'", (void *)module, (void *)param,
+           (void *)param->data.owner, param->read, param->write, param->data.type)
+    if (!param_can_write_private(param, module)) {
+        ERROR_(ctx, FIMO_EPERM, "write not permitted, caller='%s', module='%s'", module->module_info->name,
+               param->data.owner->module_info->name)
+        return FIMO_EPERM;
     }
+
+    const FimoResult error = param_write_(param, module, value, type);
     module_info_unlock_(info_inner);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_(ctx, error, "could not write to param, module='%s'", module->module_info->name)
+        return error;
+    }
 
     return FIMO_EOK;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_load_symbol(FimoInternalModuleContext *ctx, const FimoModule *module, const char *name,
-                                            const char *ns, FimoVersion version, const FimoModuleRawSymbol **symbol) {
+FimoResult fimo_internal_module_param_get_private(FimoInternalModuleContext *ctx, const FimoModule *module, void *value,
+                                                  FimoModuleParamType *type, const FimoModuleParam *param) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (module == NULL || name == NULL || ns == NULL || symbol == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', name='%p', ns='%p', symbol='%p'", (void *)module,
-               (void *)name, (void *)ns, (void *)symbol)
-        return FIMO_EINVAL;
-    }
-
-    TRACE_(ctx, "module='%s', name='%s', ns='%s', version='%" PRIu32 ".%" PRIu32 ".%" PRIu32 "+%" PRIu64 "'",
-           module->module_info->name, name, ns, version.major, version.minor, version.patch, version.build)
-    ctx_lock_(ctx);
-    const struct Symbol_ *sym = ctx_get_symbol_compatible_(ctx, name, ns, version);
-    if (sym == NULL) {
-        ctx_unlock_(ctx);
-        ERROR_(ctx, FIMO_EINVAL,
-               "could not find a compatible symbol, module='%s', name='%s', ns='%s', version='%" PRIu32 ".%" PRIu32
-               ".%" PRIu32 "+%" PRIu64 "'",
-               module->module_info->name, name, ns, version.major, version.minor, version.patch, version.build)
+    if (module == NULL || value == NULL || type == NULL || param == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', value='%p', type='%p', param='%p'",
+               (void *)module, (void *)value, (void *)type, (void *)param)
         return FIMO_EINVAL;
     }
 
     const struct ModuleInfo_ *info = module_info_from_module_(module);
     struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
-    const struct ModuleInfoDependency_ *dependency = module_info_get_dependency_(info_inner, sym->module);
-    if (dependency == NULL) {
-        module_info_unlock_(info_inner);
-        ctx_unlock_(ctx);
-        ERROR_(ctx, FIMO_EPERM,
-               "module exposing the symbol is not a dependency, exposed_by='%s', module='%s', name='%s', ns='%s', "
-               "version='%" PRIu32 ".%" PRIu32 ".%" PRIu32 "+%" PRIu64 "'",
-               sym->module, module->module_info->name, name, ns, version.major, version.minor, version.patch,
-               version.build)
-        return FIMO_EPERM;
-    }
-    if (module_info_get_ns_(info_inner, ns) == NULL && strcmp(ns, GLOBAL_NS) != 0) {
-        module_info_unlock_(info_inner);
-        ctx_unlock_(ctx);
-        ERROR_(ctx, FIMO_EPERM,
-               "module does not include the namespace it tried to load a symbol from, module='%s', name='%s', ns='%s', "
-               "version='%" PRIu32 ".%" PRIu32 ".%" PRIu32 "+%" PRIu64 "'",
-               module->module_info->name, name, ns, version.major, version.minor, version.patch, version.build)
+    TRACE_(ctx, "module='%p', param='%p', owner='%p', read='%d', write='%d', type='%d'", (void *)module, (void *)param,
+           (void *)param->data.owner, param->read, param->write, param->data.type)
+    if (!param_can_read_private(param, module)) {
+        ERROR_(ctx, FIMO_EPERM, "read not permitted, caller='%s', module='%s'", module->module_info->name,
+               param->data.owner->module_info->name)
         return FIMO_EPERM;
     }
 
-    const struct Module_ *symbol_owner = ctx_get_module_(ctx, sym->module);
-    FIMO_DEBUG_ASSERT(symbol_owner)
-    const struct ModuleInfo_ *symbol_owner_info = module_info_from_module_(symbol_owner->module);
-    struct ModuleInfoInner_ *symbol_owner_info_inner = module_info_lock_(symbol_owner_info);
-    const struct ModuleInfoSymbol_ *info_symbol = module_info_get_symbol_(symbol_owner_info_inner, name, ns, version);
-    FIMO_DEBUG_ASSERT(info_symbol);
-    *symbol = &info_symbol->symbol;
-
-    module_info_unlock_(symbol_owner_info_inner);
+    const FimoResult error = param_read_(param, module, value, type);
     module_info_unlock_(info_inner);
-    ctx_unlock_(ctx);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ERROR_(ctx, error, "could not read from param, module='%s'", module->module_info->name)
+        return error;
+    }
 
     return FIMO_EOK;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_unload(FimoInternalModuleContext *ctx, const FimoModuleInfo *module) {
+FimoResult fimo_internal_module_param_set_inner(FimoInternalModuleContext *ctx, const FimoModule *module,
+                                                const void *value, const FimoModuleParamType type,
+                                                FimoModuleParamData *param) {
     FIMO_DEBUG_ASSERT(ctx)
+    if (module == NULL || value == NULL || param == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', value='%p', type='%d', param='%p'",
+               (void *)module, value, type, (void *)param)
+        return FIMO_EINVAL;
+    }
 
-    ctx_lock_(ctx);
-    if (module != NULL) {
-        TRACE_(ctx, "unloading module, module='%s'", module->name)
-        const struct ModuleInfo_ *info = module_info_from_module_info_(module);
-        if (info->type != MODULE_TYPE_REGULAR_) {
-            ctx_unlock_(ctx);
-            ERROR_SIMPLE_(ctx, FIMO_EPERM, "can only unload regular modules")
-            return FIMO_EPERM;
-        }
-        struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
-
-        const FimoResult error = ctx_remove_module_(ctx, info_inner);
-        if (FIMO_RESULT_IS_ERROR(error)) {
-            module_info_unlock_(info_inner);
-            ctx_unlock_(ctx);
-            ERROR_SIMPLE_(ctx, error, "could not remove module from context")
-            return error;
-        }
+    struct ParamData_ *data = (struct ParamData_ *)param;
+    TRACE_(ctx, "module='%p', param='%p', owner='%p', type='%d'", (void *)module, (void *)data, (void *)data->owner,
+           data->type)
 
-        fi_module_free_(info_inner, NULL);
+    if (!param_data_is_owner_(data, module)) {
+        ERROR_(ctx, FIMO_EPERM, "read not permitted, caller='%s', owner='%s'", module->module_info->name,
+               data->owner->module_info->name)
+        return FIMO_EPERM;
     }
 
-    const FimoResult error = ctx_cleanup_loose_modules(ctx);
-    ctx_unlock_(ctx);
-    if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_SIMPLE_(ctx, error, "could not cleanup loose modules")
-        return error;
+    if (!param_data_type_matches_(data, type)) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid parameter type, required='%d', got='%d'", data->type, type)
+        return FIMO_EINVAL;
     }
 
+    param_data_write_(data, value);
     return FIMO_EOK;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_param_query(FimoInternalModuleContext *ctx, const char *module_name, const char *param,
-                                            FimoModuleParamType *type, FimoModuleParamAccess *read,
-                                            FimoModuleParamAccess *write) {
+FimoResult fimo_internal_module_param_get_inner(FimoInternalModuleContext *ctx, const FimoModule *module, void *value,
+                                                FimoModuleParamType *type, const FimoModuleParamData *param) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (module_name == NULL || param == NULL || type == NULL || read == NULL || write == NULL) {
-        ERROR_(ctx, FIMO_EINVAL,
-               "invalid null parameter, module_name='%p', param='%p', type='%p', read='%p', write='%p'",
-               (void *)module_name, (void *)param, (void *)type, (void *)read, (void *)write)
-        return FIMO_EINVAL;
-    }
-
-    TRACE_(ctx, "module_name='%s', param='%s'", module_name, param)
-    ctx_lock_(ctx);
-    const struct Module_ *module = ctx_get_module_(ctx, module_name);
-    if (module == NULL) {
-        ctx_unlock_(ctx);
-        ERROR_(ctx, FIMO_EINVAL, "module does not exist, module='%s'", module_name)
+    if (module == NULL || value == NULL || type == NULL || param == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', value='%p', type='%p', param='%p'",
+               (void *)module, (void *)value, (void *)type, (void *)param)
         return FIMO_EINVAL;
     }
 
-    const struct ModuleInfo_ *module_info = module_info_from_module_(module->module);
-    struct ModuleInfoInner_ *module_info_inner = module_info_lock_(module_info);
-    const struct ModuleInfoParam_ *module_param = module_info_get_param_(module_info_inner, param);
-    if (module_param == NULL) {
-        module_info_unlock_(module_info_inner);
-        ctx_unlock_(ctx);
-        ERROR_(ctx, FIMO_EINVAL, "parameter not found, module='%s', param='%s'", module_name, param)
-        return FIMO_EINVAL;
+    const struct ParamData_ *data = (const struct ParamData_ *)param;
+    TRACE_(ctx, "module='%p', param='%p', owner='%p', type='%d'", (void *)module, (void *)data, (void *)data->owner,
+           data->type)
+    if (!param_data_is_owner_(data, module)) {
+        ERROR_(ctx, FIMO_EPERM, "read not permitted, caller='%s', module='%s'", module->module_info->name,
+               data->owner->module_info->name)
+        return FIMO_EPERM;
     }
 
-    *type = module_param->param->data.type;
-    *read = module_param->param->read;
-    *write = module_param->param->write;
-
-    module_info_unlock_(module_info_inner);
-    ctx_unlock_(ctx);
-
+    param_data_read_(data, value, type);
     return FIMO_EOK;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_param_set_public(FimoInternalModuleContext *ctx, const void *value,
-                                                 const FimoModuleParamType type, const char *module_name,
-                                                 const char *param) {
+FimoResult fimo_internal_module_symbol_use_counts(FimoInternalModuleContext *ctx, const FimoModuleInfo *module,
+                                                  FimoModuleSymbolUseCount **use_counts, FimoUSize *use_counts_count) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (value == NULL || module_name == NULL || param == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, value='%p', module_name='%p', param='%p'", (void *)value,
-               (void *)module_name, (void *)param)
+    if (module == NULL || use_counts == NULL || use_counts_count == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', use_counts='%p', use_counts_count='%p'",
+               (void *)module, (void *)use_counts, (void *)use_counts_count)
         return FIMO_EINVAL;
     }
 
-    TRACE_(ctx, "module_name='%s', param='%s'", module_name, param)
+    TRACE_(ctx, "module='%s'", module->name)
     ctx_lock_(ctx);
-    const struct Module_ *module = ctx_get_module_(ctx, module_name);
-    if (module == NULL) {
+    const struct ModuleInfo_ *info = module_info_from_module_info_(module);
+    struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
+    if (module_info_is_detached_(info_inner)) {
+        module_info_unlock_(info_inner);
         ctx_unlock_(ctx);
-        ERROR_(ctx, FIMO_EINVAL, "module does not exist, module='%s'", module_name)
+        ERROR_(ctx, FIMO_EINVAL, "module is not loaded, module='%s'", module->name)
         return FIMO_EINVAL;
     }
 
-    const struct ModuleInfo_ *module_info = module_info_from_module_(module->module);
-    struct ModuleInfoInner_ *module_info_inner = module_info_lock_(module_info);
-    const struct ModuleInfoParam_ *module_param = module_info_get_param_(module_info_inner, param);
-    if (module_param == NULL) {
-        module_info_unlock_(module_info_inner);
+    // The symbols of the module remain valid while the context is locked. We
+    // take a snapshot of them, so that we don't hold the lock of the module
+    // while locking its importers, which lock their own info first.
+    FimoResult error = FIMO_EOK;
+    FimoArrayList symbols = fimo_array_list_new();
+    FimoUSize strings_size = 0;
+    FimoUSize it = 0;
+    const struct ModuleInfoSymbol_ *symbol;
+    while (module_info_next_symbol_(info_inner, &it, &symbol)) {
+        error = fimo_array_list_push(&symbols, sizeof(const struct ModuleInfoSymbol_ *),
+                                     _Alignof(const struct ModuleInfoSymbol_ *), &symbol, NULL);
+        if (FIMO_RESULT_IS_ERROR(error)) {
+            module_info_unlock_(info_inner);
+            ctx_unlock_(ctx);
+            ERROR_SIMPLE_(ctx, error, "could not collect the symbols of the module")
+            goto release_symbols;
+        }
+        strings_size += strlen(symbol->name) + strlen(symbol->ns) + 2;
+    }
+    module_info_unlock_(info_inner);
+
+    const FimoUSize count = fimo_array_list_len(&symbols);
+    if (count == 0) {
         ctx_unlock_(ctx);
-        ERROR_(ctx, FIMO_EINVAL, "parameter not found, module='%s', param='%s'", module_name, param)
-        return FIMO_EINVAL;
+        *use_counts = NULL;
+        *use_counts_count = 0;
+        goto release_symbols;
     }
+    const struct ModuleInfoSymbol_ *const *symbol_list = symbols.elements;
 
-    if (!param_can_write_public(module_param->param)) {
-        module_info_unlock_(module_info_inner);
+    // Count the modules importing each symbol. The imports of a module
+    // only change while the context is locked.
+    FimoUSize *importer_counts = fimo_calloc(count * sizeof(FimoUSize), &error);
+    if (FIMO_RESULT_IS_ERROR(error)) {
         ctx_unlock_(ctx);
-        ERROR_(ctx, FIMO_EPERM, "write not permitted, module='%s', param='%s'", module_name, param)
-        return FIMO_EPERM;
+        ERROR_SIMPLE_(ctx, error, "could not allocate the importer counts")
+        goto release_symbols;
+    }
+    FimoUSize importers_size = 0;
+    it = 0;
+    const struct Module_ *importer;
+    while (hashmap_iter(ctx->modules, &it, (void **)&importer)) {
+        if (importer->module->module_info == module) {
+            continue;
+        }
+        const struct ModuleInfo_ *importer_info = module_info_from_module_(importer->module);
+        struct ModuleInfoInner_ *importer_info_inner = module_info_lock_(importer_info);
+        const struct ModuleInfoDependency_ *dependency =
+                module_info_get_dependency_(importer_info_inner, module->name);
+        bool imports_any = false;
+        for (FimoUSize i = 0; dependency != NULL && i < count; i++) {
+            if (module_info_dependency_has_symbol_(dependency, &symbol_list[i]->symbol)) {
+                importer_counts[i]++;
+                importers_size++;
+                imports_any = true;
+            }
+        }
+        module_info_unlock_(importer_info_inner);
+        if (imports_any) {
+            strings_size += strlen(importer->name) + 1;
+        }
     }
 
-    const FimoResult error = param_write_((FimoModuleParam *)module_param->param, module->module, value, type);
-    module_info_unlock_(module_info_inner);
-    ctx_unlock_(ctx);
+    // The importer lists and the strings are stored right after the entries,
+    // so that the caller only needs to free a single allocation.
+    FimoModuleSymbolUseCount *entries =
+            fimo_malloc(count * sizeof(*entries) + importers_size * sizeof(const char *) + strings_size, &error);
     if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_(ctx, error, "could not write to param, module='%s', param='%s'", module_name, param)
-        return error;
+        ctx_unlock_(ctx);
+        ERROR_SIMPLE_(ctx, error, "could not allocate the use counts")
+        goto release_importer_counts;
+    }
+
+    const char **importers = (const char **)(entries + count);
+    char *strings = (char *)(importers + importers_size);
+    for (FimoUSize i = 0; i < count; i++) {
+        symbol = symbol_list[i];
+        const FimoUSize name_len = strlen(symbol->name) + 1;
+        const FimoUSize ns_len = strlen(symbol->ns) + 1;
+        memcpy(strings, symbol->name, name_len);
+        memcpy(strings + name_len, symbol->ns, ns_len);
+        entries[i] = (FimoModuleSymbolUseCount){
+                .name = strings,
+                .ns = strings + name_len,
+                .version = symbol->version,
+                .use_count = atomic_load_explicit((_Atomic(FimoUSize) *)&symbol->symbol.lock, memory_order_relaxed),
+                .importers = importers,
+                .importers_count = 0,
+        };
+        strings += name_len + ns_len;
+        importers += importer_counts[i];
+    }
+
+    it = 0;
+    while (hashmap_iter(ctx->modules, &it, (void **)&importer)) {
+        if (importer->module->module_info == module) {
+            continue;
+        }
+        const struct ModuleInfo_ *importer_info = module_info_from_module_(importer->module);
+        struct ModuleInfoInner_ *importer_info_inner = module_info_lock_(importer_info);
+        const struct ModuleInfoDependency_ *dependency =
+                module_info_get_dependency_(importer_info_inner, module->name);
+        const char *importer_name = NULL;
+        for (FimoUSize i = 0; dependency != NULL && i < count; i++) {
+            if (!module_info_dependency_has_symbol_(dependency, &symbol_list[i]->symbol)) {
+                continue;
+            }
+            if (importer_name == NULL) {
+                const FimoUSize name_len = strlen(importer->name) + 1;
+                memcpy(strings, importer->name, name_len);
+                importer_name = strings;
+                strings += name_len;
+            }
+            ((const char **)entries[i].importers)[entries[i].importers_count++] = importer_name;
+        }
+        module_info_unlock_(importer_info_inner);
     }
+    ctx_unlock_(ctx);
 
-    return FIMO_EOK;
+    *use_counts = entries;
+    *use_counts_count = count;
+
+release_importer_counts:
+    fimo_free(importer_counts);
+release_symbols:
+    fimo_array_list_free(&symbols, sizeof(const struct ModuleInfoSymbol_ *), _Alignof(const struct ModuleInfoSymbol_ *),
+                         NULL);
+    return error;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_param_get_public(FimoInternalModuleContext *ctx, void *value, FimoModuleParamType *type,
-                                                 const char *module_name, const char *param) {
+FimoResult fimo_internal_module_param_list(FimoInternalModuleContext *ctx, const char *module_name,
+                                           FimoModuleParamInfo **params, FimoUSize *params_count) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (value == NULL || type == NULL || module_name == NULL || param == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, value='%p', type='%p', module_name='%p', param='%p'",
-               (void *)value, (void *)type, (void *)module_name, (void *)param)
+    if (module_name == NULL || params == NULL || params_count == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module_name='%p', params='%p', params_count='%p'",
+               (void *)module_name, (void *)params, (void *)params_count)
         return FIMO_EINVAL;
     }
 
-    TRACE_(ctx, "module_name='%s', param='%s'", module_name, param)
+    TRACE_(ctx, "module_name='%s'", module_name)
     ctx_lock_(ctx);
     const struct Module_ *module = ctx_get_module_(ctx, module_name);
     if (module == NULL) {
@@ -5153,248 +7570,385 @@ FimoResult fimo_internal_module_param_get_public(FimoInternalModuleContext *ctx,
 
     const struct ModuleInfo_ *module_info = module_info_from_module_(module->module);
     struct ModuleInfoInner_ *module_info_inner = module_info_lock_(module_info);
-    const struct ModuleInfoParam_ *module_param = module_info_get_param_(module_info_inner, param);
-    if (module_param == NULL) {
+
+    // The names are stored right after the entries, so that the caller
+    // only needs to free a single allocation.
+    FimoUSize count = 0;
+    FimoUSize names_size = 0;
+    FimoUSize it = 0;
+    const struct ModuleInfoParam_ *module_param;
+    while (module_info_next_param_(module_info_inner, &it, &module_param)) {
+        count++;
+        names_size += strlen(module_param->name) + 1;
+    }
+    if (count == 0) {
         module_info_unlock_(module_info_inner);
         ctx_unlock_(ctx);
-        ERROR_(ctx, FIMO_EINVAL, "parameter not found, module='%s', param='%s'", module_name, param)
-        return FIMO_EINVAL;
+        *params = NULL;
+        *params_count = 0;
+        return FIMO_EOK;
     }
 
-    if (!param_can_read_public(module_param->param)) {
+    FimoResult error = FIMO_EOK;
+    FimoModuleParamInfo *entries = fimo_malloc(count * sizeof(*entries) + names_size, &error);
+    if (FIMO_RESULT_IS_ERROR(error)) {
         module_info_unlock_(module_info_inner);
         ctx_unlock_(ctx);
-        ERROR_(ctx, FIMO_EPERM, "read not permitted, module='%s', param='%s'", module_name, param)
-        return FIMO_EPERM;
+        ERROR_SIMPLE_(ctx, error, "could not allocate the parameter infos")
+        return error;
+    }
+
+    char *names = (char *)(entries + count);
+    FimoUSize i = 0;
+    it = 0;
+    while (module_info_next_param_(module_info_inner, &it, &module_param)) {
+        const FimoUSize name_len = strlen(module_param->name) + 1;
+        memcpy(names, module_param->name, name_len);
+        entries[i++] = (FimoModuleParamInfo){
+                .name = names,
+                .type = module_param->param->data.type,
+                .read_access = module_param->param->read,
+                .write_access = module_param->param->write,
+        };
+        names += name_len;
     }
 
-    const FimoResult error = param_read_(module_param->param, module->module, value, type);
     module_info_unlock_(module_info_inner);
     ctx_unlock_(ctx);
-    if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_(ctx, error, "could not read from param, module='%s', param='%s'", module_name, param)
-        return error;
-    }
 
+    *params = entries;
+    *params_count = count;
     return FIMO_EOK;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_param_set_dependency(FimoInternalModuleContext *ctx, const FimoModule *module,
-                                                     const void *value, const FimoModuleParamType type,
-                                                     const char *module_name, const char *param) {
+FimoResult fimo_internal_module_param_set_override(FimoInternalModuleContext *ctx, const char *module_name,
+                                                   const char *param, const void *value,
+                                                   const FimoModuleParamType type) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (module == NULL || value == NULL || module_name == NULL || param == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', value='%p', module_name='%p', param='%p'",
-               (void *)module, (void *)value, (void *)module_name, (void *)param)
+    if (module_name == NULL || param == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module_name='%p', param='%p'", (void *)module_name,
+               (void *)param)
         return FIMO_EINVAL;
     }
-
-    TRACE_(ctx, "module='%p', module_name='%s', param='%s'", (void *)module, module_name, param)
-    const struct ModuleInfo_ *caller_info = module_info_from_module_(module);
-    struct ModuleInfoInner_ *caller_info_inner = module_info_lock_(caller_info);
-    const struct ModuleInfoDependency_ *dep = module_info_get_dependency_(caller_info_inner, module_name);
-    if (dep == NULL) {
-        module_info_unlock_(caller_info_inner);
-        ERROR_(ctx, FIMO_EINVAL, "module is not a dependency, module='%s', caller='%s'", module_name,
-               module->module_info->name)
+    if (type < FIMO_MODULE_PARAM_TYPE_U8 || type > FIMO_MODULE_PARAM_TYPE_I64) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid parameter type, type='%d'", type)
         return FIMO_EINVAL;
     }
 
-    const struct ModuleInfo_ *dep_info = module_info_from_module_info_(dep->info);
-    struct ModuleInfoInner_ *dep_info_inner = module_info_lock_(dep_info);
-    const struct ModuleInfoParam_ *dep_param = module_info_get_param_(dep_info_inner, param);
-    if (dep_param == NULL) {
-        module_info_unlock_(dep_info_inner);
-        module_info_unlock_(caller_info_inner);
-        ERROR_(ctx, FIMO_EINVAL, "parameter not found, module='%s', parameter='%s'", module_name, param)
-        return FIMO_EINVAL;
+    TRACE_(ctx, "module_name='%s', param='%s', value='%p', type='%d'", module_name, param, value, type)
+    ctx_lock_(ctx);
+    if (value == NULL) {
+        struct ParamOverride_ *old = (void *)hashmap_delete(
+                ctx->param_overrides, &(struct ParamOverride_){.module = module_name, .param = param});
+        if (old) {
+            param_override_free_(old);
+        }
+        ctx_unlock_(ctx);
+        return FIMO_EOK;
     }
 
-    if (!param_can_write_dependency(dep_param->param, caller_info_inner)) {
-        module_info_unlock_(dep_info_inner);
-        module_info_unlock_(caller_info_inner);
-        ERROR_(ctx, FIMO_EPERM, "write not permitted, caller='%s', module='%s', parameter='%s'",
-               module->module_info->name, module_name, param)
-        return FIMO_EPERM;
+    struct ParamOverride_ override;
+    FimoResult error = param_override_new_(module_name, param, value, type, &override);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        ctx_unlock_(ctx);
+        ERROR_SIMPLE_(ctx, error, "could not allocate the parameter override")
+        return error;
     }
 
-    FIMO_ASSERT(!module_info_is_detached_(dep_info_inner) && dep_info_inner->module)
-    const FimoResult error = param_write_((FimoModuleParam *)dep_param->param, dep_info_inner->module, value, type);
-    module_info_unlock_(dep_info_inner);
-    module_info_unlock_(caller_info_inner);
+    struct ParamOverride_ *old = (void *)hashmap_set(ctx->param_overrides, &override);
+    if (hashmap_oom(ctx->param_overrides)) {
+        ctx_unlock_(ctx);
+        param_override_free_(&override);
+        ERROR_SIMPLE_(ctx, FIMO_ENOMEM, "could not insert the parameter override")
+        return FIMO_ENOMEM;
+    }
+    if (old) {
+        param_override_free_(old);
+    }
+    ctx_unlock_(ctx);
+
+    return FIMO_EOK;
+}
+
+FIMO_MUST_USE
+FimoResult fimo_internal_module_loader_register(FimoInternalModuleContext *ctx, const FimoModuleLoader *loader) {
+    FIMO_DEBUG_ASSERT(ctx)
+    if (loader == NULL || loader->name == NULL || loader->pattern == NULL || loader->load == NULL ||
+        loader->iterate_exports == NULL || loader->unload == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid loader, loader='%p'", (void *)loader)
+        return FIMO_EINVAL;
+    }
+    if (loader->next != NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "unknown loader extension, next='%p'", loader->next)
+        return FIMO_EINVAL;
+    }
+
+    TRACE_(ctx, "loader='%s', pattern='%s'", loader->name, loader->pattern)
+    struct ModuleLoader_ *element;
+    FimoResult error = module_loader_new_(loader, &element);
     if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_(ctx, error, "could not write to param, module='%s', param='%s'", module_name, param)
+        ERROR_SIMPLE_(ctx, error, "could not allocate the loader")
         return error;
     }
 
+    ctx_loaders_lock_(ctx);
+    struct ModuleLoader_ **tail = &ctx->loaders;
+    for (; *tail; tail = &(*tail)->next) {
+        if (strcmp((*tail)->name, element->name) == 0) {
+            ctx_loaders_unlock_(ctx);
+            ERROR_(ctx, FIMO_EEXIST, "a loader with the same name already exists, loader='%s'", element->name)
+            // The caller retains the ownership of the data on error.
+            element->on_drop = NULL;
+            module_loader_release_(element);
+            return FIMO_EEXIST;
+        }
+    }
+    *tail = element;
+    ctx_loaders_unlock_(ctx);
+
     return FIMO_EOK;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_param_get_dependency(FimoInternalModuleContext *ctx, const FimoModule *module,
-                                                     void *value, FimoModuleParamType *type, const char *module_name,
-                                                     const char *param) {
+FimoResult fimo_internal_module_loader_unregister(FimoInternalModuleContext *ctx, const char *name) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (module == NULL || value == NULL || type == NULL || module_name == NULL || param == NULL) {
-        ERROR_(ctx, FIMO_EINVAL,
-               "invalid null parameter, module='%p', value='%p', type='%p', module_name='%p', param='%p'",
-               (void *)module, value, (void *)type, (void *)module_name, (void *)param)
+    if (name == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, name='%p'", (void *)name)
         return FIMO_EINVAL;
     }
 
-    TRACE_(ctx, "module='%p', module_name='%s', param='%s'", (void *)module, module_name, param)
-    const struct ModuleInfo_ *caller_info = module_info_from_module_(module);
-    struct ModuleInfoInner_ *caller_info_inner = module_info_lock_(caller_info);
-    const struct ModuleInfoDependency_ *dep = module_info_get_dependency_(caller_info_inner, module_name);
-    if (dep == NULL) {
-        module_info_unlock_(caller_info_inner);
-        ERROR_(ctx, FIMO_EINVAL, "module is not a dependency, module='%s', caller='%s'", module_name,
-               module->module_info->name)
-        return FIMO_EINVAL;
+    TRACE_(ctx, "loader='%s'", name)
+    ctx_loaders_lock_(ctx);
+    for (struct ModuleLoader_ **it = &ctx->loaders; *it; it = &(*it)->next) {
+        struct ModuleLoader_ *loader = *it;
+        if (strcmp(loader->name, name) == 0) {
+            *it = loader->next;
+            ctx_loaders_unlock_(ctx);
+            module_loader_release_(loader);
+            return FIMO_EOK;
+        }
     }
+    ctx_loaders_unlock_(ctx);
 
-    const struct ModuleInfo_ *dep_info = module_info_from_module_info_(dep->info);
-    struct ModuleInfoInner_ *dep_info_inner = module_info_lock_(dep_info);
-    const struct ModuleInfoParam_ *dep_param = module_info_get_param_(dep_info_inner, param);
-    if (dep_param == NULL) {
-        module_info_unlock_(dep_info_inner);
-        module_info_unlock_(caller_info_inner);
-        ERROR_(ctx, FIMO_EINVAL, "parameter not found, module='%s', parameter='%s'", module_name, param)
+    ERROR_(ctx, FIMO_ENOENT, "loader not found, loader='%s'", name)
+    return FIMO_ENOENT;
+}
+
+// Checks whether `policy` accepts binaries that are rejected by `current`.
+static bool signature_policy_is_weaker_(const FimoModuleSignaturePolicy policy,
+                                        const FimoModuleSignaturePolicy current) {
+    if (policy == FIMO_MODULE_SIGNATURE_POLICY_OFF) {
+        return current != FIMO_MODULE_SIGNATURE_POLICY_OFF;
+    }
+    return policy == FIMO_MODULE_SIGNATURE_POLICY_TRUST_ON_FIRST_USE &&
+           current == FIMO_MODULE_SIGNATURE_POLICY_REQUIRE;
+}
+
+FIMO_MUST_USE
+FimoResult fimo_internal_module_signature_set_policy(FimoInternalModuleContext *ctx,
+                                                     const FimoModuleSignaturePolicy policy) {
+    FIMO_DEBUG_ASSERT(ctx)
+    if (policy != FIMO_MODULE_SIGNATURE_POLICY_OFF && policy != FIMO_MODULE_SIGNATURE_POLICY_REQUIRE &&
+        policy != FIMO_MODULE_SIGNATURE_POLICY_TRUST_ON_FIRST_USE) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid policy, policy='%d'", (int)policy)
         return FIMO_EINVAL;
     }
 
-    if (!param_can_read_dependency(dep_param->param, caller_info_inner)) {
-        module_info_unlock_(dep_info_inner);
-        module_info_unlock_(caller_info_inner);
-        ERROR_(ctx, FIMO_EPERM, "read not permitted, caller='%s', module='%s', parameter='%s'",
-               module->module_info->name, module_name, param)
+    TRACE_(ctx, "policy='%d'", (int)policy)
+    ctx_signatures_lock_(ctx);
+    // Only the embedder may relax the policy, i.e., before any other binary was loaded.
+    if (ctx->binary_loaded && signature_policy_is_weaker_(policy, ctx->signature_policy)) {
+        ctx_signatures_unlock_(ctx);
+        ERROR_(ctx, FIMO_EPERM, "the policy can not be relaxed after a binary was loaded, policy='%d'", (int)policy)
         return FIMO_EPERM;
     }
+    ctx->signature_policy = policy;
+    ctx_signatures_unlock_(ctx);
 
-    FIMO_ASSERT(!module_info_is_detached_(dep_info_inner) && dep_info_inner->module)
-    const FimoResult error = param_read_(dep_param->param, dep_info_inner->module, value, type);
+    return FIMO_EOK;
+}
 
-    module_info_unlock_(dep_info_inner);
-    module_info_unlock_(caller_info_inner);
-    if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_(ctx, error, "could not read from param, module='%s', param='%s'", module_name, param)
-        return error;
+FIMO_MUST_USE
+FimoResult fimo_internal_module_fault_set_policy(FimoInternalModuleContext *ctx, const FimoModuleFaultPolicy policy) {
+    FIMO_DEBUG_ASSERT(ctx)
+    if (policy != FIMO_MODULE_FAULT_POLICY_CONTINUE && policy != FIMO_MODULE_FAULT_POLICY_ABORT) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid policy, policy='%d'", (int)policy)
+        return FIMO_EINVAL;
     }
 
+    TRACE_(ctx, "policy='%d'", (int)policy)
+    ctx_lock_(ctx);
+    ctx->fault_policy = policy;
+    ctx_unlock_(ctx);
+
     return FIMO_EOK;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_param_set_private(FimoInternalModuleContext *ctx, const FimoModule *module,
-                                                  const void *value, const FimoModuleParamType type,
-                                                  FimoModuleParam *param) {
+FimoResult fimo_internal_module_feature_list(FimoInternalModuleContext *ctx, const char *module_name,
+                                             FimoModuleFeatureInfo **features, FimoUSize *features_count) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (module == NULL || value == NULL || param == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', value='%p', type='%d', param='%p'",
-               (void *)module, value, type, (void *)param)
+    if (module_name == NULL || features == NULL || features_count == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module_name='%p', features='%p', features_count='%p'",
+               (void *)module_name, (void *)features, (void *)features_count)
         return FIMO_EINVAL;
     }
 
-    const struct ModuleInfo_ *info = module_info_from_module_(module);
-    struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
-    TRACE_(ctx, "module='%p', param='%p', owner='%p', read='%d', write='%d', type='%d'", (void *)module, (void *)param,
-           (void *)param->data.owner, param->read, param->write, param->data.type)
-    if (!param_can_write_private(param, module)) {
-        ERROR_(ctx, FIMO_EPERM, "write not permitted, caller='%s', module='%s'", module->module_info->name,
-               param->data.owner->module_info->name)
-        return FIMO_EPERM;
+    TRACE_(ctx, "module_name='%s'", module_name)
+    ctx_lock_(ctx);
+    const struct Module_ *module = ctx_get_module_(ctx, module_name);
+    if (module == NULL) {
+        ctx_unlock_(ctx);
+        ERROR_(ctx, FIMO_EINVAL, "module does not exist, module='%s'", module_name)
+        return FIMO_EINVAL;
     }
 
-    const FimoResult error = param_write_(param, module, value, type);
-    module_info_unlock_(info_inner);
+    const struct ModuleInfo_ *module_info = module_info_from_module_(module->module);
+    struct ModuleInfoInner_ *module_info_inner = module_info_lock_(module_info);
+
+    // The names are stored right after the entries, so that the caller
+    // only needs to free a single allocation.
+    FimoUSize count = 0;
+    FimoUSize names_size = 0;
+    FimoUSize it = 0;
+    const struct ModuleInfoFeature_ *module_feature;
+    while (module_info_next_feature_(module_info_inner, &it, &module_feature)) {
+        count++;
+        names_size += strlen(module_feature->name) + 1;
+    }
+    if (count == 0) {
+        module_info_unlock_(module_info_inner);
+        ctx_unlock_(ctx);
+        *features = NULL;
+        *features_count = 0;
+        return FIMO_EOK;
+    }
+
+    FimoResult error = FIMO_EOK;
+    FimoModuleFeatureInfo *entries = fimo_malloc(count * sizeof(*entries) + names_size, &error);
     if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_(ctx, error, "could not write to param, module='%s'", module->module_info->name)
+        module_info_unlock_(module_info_inner);
+        ctx_unlock_(ctx);
+        ERROR_SIMPLE_(ctx, error, "could not allocate the feature infos")
         return error;
     }
 
+    char *names = (char *)(entries + count);
+    FimoUSize i = 0;
+    it = 0;
+    while (module_info_next_feature_(module_info_inner, &it, &module_feature)) {
+        const FimoUSize name_len = strlen(module_feature->name) + 1;
+        memcpy(names, module_feature->name, name_len);
+        entries[i++] = (FimoModuleFeatureInfo){
+                .name = names,
+                .stability = module_feature->stability,
+                .default_enabled = module_feature->default_enabled,
+                .available = module_feature->available,
+                .enabled = module_feature->enabled,
+        };
+        names += name_len;
+    }
+
+    module_info_unlock_(module_info_inner);
+    ctx_unlock_(ctx);
+
+    *features = entries;
+    *features_count = count;
     return FIMO_EOK;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_param_get_private(FimoInternalModuleContext *ctx, const FimoModule *module, void *value,
-                                                  FimoModuleParamType *type, const FimoModuleParam *param) {
+FimoResult fimo_internal_module_feature_set_override(FimoInternalModuleContext *ctx, const char *module_name,
+                                                     const char *feature, const bool *enabled) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (module == NULL || value == NULL || type == NULL || param == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', value='%p', type='%p', param='%p'",
-               (void *)module, (void *)value, (void *)type, (void *)param)
+    if (module_name == NULL || feature == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module_name='%p', feature='%p'", (void *)module_name,
+               (void *)feature)
         return FIMO_EINVAL;
     }
 
-    const struct ModuleInfo_ *info = module_info_from_module_(module);
-    struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
-    TRACE_(ctx, "module='%p', param='%p', owner='%p', read='%d', write='%d', type='%d'", (void *)module, (void *)param,
-           (void *)param->data.owner, param->read, param->write, param->data.type)
-    if (!param_can_read_private(param, module)) {
-        ERROR_(ctx, FIMO_EPERM, "read not permitted, caller='%s', module='%s'", module->module_info->name,
-               param->data.owner->module_info->name)
-        return FIMO_EPERM;
+    TRACE_(ctx, "module_name='%s', feature='%s', enabled='%p'", module_name, feature, (const void *)enabled)
+    ctx_lock_(ctx);
+    if (enabled == NULL) {
+        struct FeatureOverride_ *old = (void *)hashmap_delete(
+                ctx->feature_overrides, &(struct FeatureOverride_){.module = module_name, .feature = feature});
+        if (old) {
+            feature_override_free_(old);
+        }
+        ctx_unlock_(ctx);
+        return FIMO_EOK;
     }
 
-    const FimoResult error = param_read_(param, module, value, type);
-    module_info_unlock_(info_inner);
+    struct FeatureOverride_ override;
+    FimoResult error = feature_override_new_(module_name, feature, *enabled, &override);
     if (FIMO_RESULT_IS_ERROR(error)) {
-        ERROR_(ctx, error, "could not read from param, module='%s'", module->module_info->name)
+        ctx_unlock_(ctx);
+        ERROR_SIMPLE_(ctx, error, "could not allocate the feature override")
         return error;
     }
 
+    struct FeatureOverride_ *old = (void *)hashmap_set(ctx->feature_overrides, &override);
+    if (hashmap_oom(ctx->feature_overrides)) {
+        ctx_unlock_(ctx);
+        feature_override_free_(&override);
+        ERROR_SIMPLE_(ctx, FIMO_ENOMEM, "could not insert the feature override")
+        return FIMO_ENOMEM;
+    }
+    if (old) {
+        feature_override_free_(old);
+    }
+    ctx_unlock_(ctx);
+
     return FIMO_EOK;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_param_set_inner(FimoInternalModuleContext *ctx, const FimoModule *module,
-                                                const void *value, const FimoModuleParamType type,
-                                                FimoModuleParamData *param) {
+FimoResult fimo_internal_module_feature_enabled(FimoInternalModuleContext *ctx, const FimoModule *module,
+                                                const char *feature, bool *enabled) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (module == NULL || value == NULL || param == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', value='%p', type='%d', param='%p'",
-               (void *)module, value, type, (void *)param)
+    if (module == NULL || feature == NULL || enabled == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', feature='%p', enabled='%p'", (void *)module,
+               (void *)feature, (void *)enabled)
         return FIMO_EINVAL;
     }
 
-    struct ParamData_ *data = (struct ParamData_ *)param;
-    TRACE_(ctx, "module='%p', param='%p', owner='%p', type='%d'", (void *)module, (void *)data, (void *)data->owner,
-           data->type)
-
-    if (!param_data_is_owner_(data, module)) {
-        ERROR_(ctx, FIMO_EPERM, "read not permitted, caller='%s', owner='%s'", module->module_info->name,
-               data->owner->module_info->name)
-        return FIMO_EPERM;
-    }
-
-    if (!param_data_type_matches_(data, type)) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid parameter type, required='%d', got='%d'", data->type, type)
-        return FIMO_EINVAL;
+    TRACE_(ctx, "feature='%s', module='%s'", feature, module->module_info->name)
+    const struct ModuleInfo_ *info = module_info_from_module_(module);
+    struct ModuleInfoInner_ *info_inner = module_info_lock_(info);
+    const struct ModuleInfoFeature_ *info_feature = module_info_get_feature_(info_inner, feature);
+    if (info_feature == NULL) {
+        module_info_unlock_(info_inner);
+        ERROR_(ctx, FIMO_ENOENT, "feature is not declared by the module, feature='%s', module='%s'", feature,
+               info->info.name)
+        return FIMO_ENOENT;
     }
+    *enabled = info_feature->enabled;
+    module_info_unlock_(info_inner);
 
-    param_data_write_(data, value);
     return FIMO_EOK;
 }
 
 FIMO_MUST_USE
-FimoResult fimo_internal_module_param_get_inner(FimoInternalModuleContext *ctx, const FimoModule *module, void *value,
-                                                FimoModuleParamType *type, const FimoModuleParamData *param) {
+FimoResult fimo_internal_module_signature_trust_key(FimoInternalModuleContext *ctx, const FimoU8 *key) {
     FIMO_DEBUG_ASSERT(ctx)
-    if (module == NULL || value == NULL || type == NULL || param == NULL) {
-        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, module='%p', value='%p', type='%p', param='%p'",
-               (void *)module, (void *)value, (void *)type, (void *)param)
+    if (key == NULL) {
+        ERROR_(ctx, FIMO_EINVAL, "invalid null parameter, key='%p'", (void *)key)
         return FIMO_EINVAL;
     }
 
-    const struct ParamData_ *data = (const struct ParamData_ *)param;
-    TRACE_(ctx, "module='%p', param='%p', owner='%p', type='%d'", (void *)module, (void *)data, (void *)data->owner,
-           data->type)
-    if (!param_data_is_owner_(data, module)) {
-        ERROR_(ctx, FIMO_EPERM, "read not permitted, caller='%s', module='%s'", module->module_info->name,
-               data->owner->module_info->name)
-        return FIMO_EPERM;
+    TRACE_SIMPLE_(ctx, "trusting key")
+    struct TrustedKey_ element;
+    memcpy(element.key, key, sizeof(element.key));
+
+    ctx_signatures_lock_(ctx);
+    hashmap_set(ctx->trusted_keys, &element);
+    const bool is_oom = hashmap_oom(ctx->trusted_keys);
+    ctx_signatures_unlock_(ctx);
+    if (is_oom) {
+        ERROR_SIMPLE_(ctx, FIMO_ENOMEM, "could not insert the key")
+        return FIMO_ENOMEM;
     }
 
-    param_data_read_(data, value, type);
     return FIMO_EOK;
 }
@@ -28,6 +28,8 @@
 #define BLOCKED_BIT_ 4
 #define LOCKED_BIT_ 8
 
+#define TO_CTX_(CTX) FIMO_CONTAINER_OF(CTX, FimoInternalContext, tracing)
+
 ///////////////////////////////////////////////////////////////////////
 //// Forward Declarations
 ///////////////////////////////////////////////////////////////////////
@@ -60,6 +62,10 @@ static FimoResult call_stack_create_span_(FimoTracingCallStack *call_stack, cons
 static FimoResult call_stack_destroy_span_(FimoTracingCallStack *call_stack, FimoTracingSpan *span);
 static FimoResult call_stack_emit_event_(FimoTracingCallStack *call_stack, const FimoTracingEvent *event,
                                          const FimoTracingFormat format, const void *data);
+static FimoResult call_stack_emit_event_batch_(FimoTracingCallStack *call_stack, const FimoTracingRecord *records,
+                                               FimoUSize record_count);
+static FimoUSize call_stack_span_stack_(FimoTracingCallStack *call_stack, FimoTracingSpanInfo *spans,
+                                        FimoUSize capacity);
 
 static FimoResult ctx_init_(FimoInternalTracingContext *ctx, const FimoTracingCreationConfig *options);
 static void ctx_deinit_(FimoInternalTracingContext *ctx);
@@ -75,12 +81,16 @@ static FimoResult ctx_create_span_(FimoInternalTracingContext *ctx, const FimoTr
 static FimoResult ctx_destroy_span_(FimoInternalTracingContext *ctx, FimoTracingSpan *span);
 static FimoResult ctx_emit_event_(FimoInternalTracingContext *ctx, const FimoTracingEvent *event,
                                   const FimoTracingFormat format, const void *data);
+static FimoResult ctx_emit_event_batch_(FimoInternalTracingContext *ctx, const FimoTracingRecord *records,
+                                        FimoUSize record_count);
+static FimoUSize ctx_span_stack_(FimoInternalTracingContext *ctx, FimoTracingSpanInfo *spans, FimoUSize capacity);
 static bool ctx_is_enabled_(FimoInternalTracingContext *ctx);
 static bool ctx_is_enabled_for_thread_(FimoInternalTracingContext *ctx);
 static bool ctx_would_trace_(FimoInternalTracingContext *ctx, const FimoTracingMetadata *metadata);
 static FimoResult ctx_register_thread_(FimoInternalTracingContext *ctx);
 static FimoResult ctx_unregister_thread_(FimoInternalTracingContext *ctx);
 static void ctx_flush_(FimoInternalTracingContext *ctx);
+static FimoTime ctx_now_(FimoInternalTracingContext *ctx);
 
 ///////////////////////////////////////////////////////////////////////
 //// Subscriber
@@ -93,6 +103,25 @@ static void subscriber_free_(FimoTracingSubscriber *subscriber) {
     }
 }
 
+static void subscriber_emit_event_batch_(const FimoTracingSubscriber *subscriber, const FimoTime *time, void *stack,
+                                         const FimoTracingRecord *records, const FimoUSize record_count) {
+    FIMO_DEBUG_ASSERT(subscriber && time && records)
+    for (const FimoBaseStructIn *current = subscriber->next; current != NULL; current = current->next) {
+        if (current->type == FIMO_STRUCT_TYPE_TRACING_SUBSCRIBER_BATCH_EXTENSION) {
+            const FimoTracingSubscriberBatchExtension *extension = (const FimoTracingSubscriberBatchExtension *)current;
+            extension->event_emit_batch(subscriber->ptr, time, stack, records, record_count);
+            return;
+        }
+    }
+
+    // Fall back to emitting the records one at a time.
+    for (FimoUSize i = 0; i < record_count; i++) {
+        const FimoTracingRecord *record = &records[i];
+        subscriber->vtable->event_emit(subscriber->ptr, time, stack, record->event,
+                                       record->message ? record->message : "", record->message_length);
+    }
+}
+
 ///////////////////////////////////////////////////////////////////////
 //// Call Stack Frame
 ///////////////////////////////////////////////////////////////////////
@@ -131,7 +160,7 @@ static FimoResult stack_frame_new_(FimoTracingCallStack *call_stack, const FimoT
         return error;
     }
 
-    const FimoTime current_time = fimo_time_now();
+    const FimoTime current_time = ctx_now_(call_stack->ctx);
     FimoUSize num_spans = 0;
     for (; num_spans < fimo_array_list_len(&call_stack->ctx->subscribers); num_spans++) {
         void **stack_;
@@ -206,7 +235,7 @@ cleanup:
 
 static void stack_frame_free_(struct StackFrame_ *frame) {
     FIMO_DEBUG_ASSERT(frame)
-    const FimoTime current_time = fimo_time_now();
+    const FimoTime current_time = ctx_now_(frame->call_stack->ctx);
     for (FimoUSize i = 0; i < fimo_array_list_len(&frame->call_stack->ctx->subscribers); i++) {
         void **stack_;
         FimoResult error =
@@ -257,7 +286,7 @@ static FimoResult call_stack_new_(FimoInternalTracingContext *ctx, bool bound, F
         return error;
     }
 
-    const FimoTime current_time = fimo_time_now();
+    const FimoTime current_time = ctx_now_(ctx);
     for (FimoUSize i = 0; i < fimo_array_list_len(&ctx->subscribers); i++) {
         const FimoTracingSubscriber *subscriber;
         error = fimo_array_list_get(&ctx->subscribers, i, sizeof(FimoTracingSubscriber), (const void **)&subscriber);
@@ -316,7 +345,7 @@ static void call_stack_free_(FimoTracingCallStack *call_stack, bool allow_bound)
     FIMO_DEBUG_ASSERT(call_stack && call_stack_can_destroy_(call_stack, allow_bound))
     (void)allow_bound;
 
-    const FimoTime current_time = fimo_time_now();
+    const FimoTime current_time = ctx_now_(call_stack->ctx);
     for (FimoUSize i = 0; !fimo_array_list_is_empty(&call_stack->call_stacks); i++) {
         void *stack_;
         FimoResult error = fimo_array_list_pop_front(&call_stack->call_stacks, sizeof(void *), &stack_, NULL);
@@ -424,7 +453,7 @@ static FimoResult call_stack_unblock_(FimoTracingCallStack *call_stack) {
         }
     }
 
-    const FimoTime current_time = fimo_time_now();
+    const FimoTime current_time = ctx_now_(call_stack->ctx);
     for (FimoUSize i = 0; i < fimo_array_list_len(&call_stack->ctx->subscribers); i++) {
         void **stack_;
         FimoResult error = fimo_array_list_get(&call_stack->call_stacks, i, sizeof(void *), (const void **)&stack_);
@@ -456,7 +485,7 @@ static FimoResult call_stack_suspend_(FimoTracingCallStack *call_stack, bool blo
     state |= block ? (unsigned int)BLOCKED_BIT_ : (unsigned int)0;
     atomic_store_explicit(&call_stack->state, state, memory_order_relaxed);
 
-    const FimoTime current_time = fimo_time_now();
+    const FimoTime current_time = ctx_now_(call_stack->ctx);
     for (FimoUSize i = 0; i < fimo_array_list_len(&call_stack->ctx->subscribers); i++) {
         void **stack_;
         FimoResult error = fimo_array_list_get(&call_stack->call_stacks, i, sizeof(void *), (const void **)&stack_);
@@ -484,7 +513,7 @@ static FimoResult call_stack_resume_(FimoTracingCallStack *call_stack) {
     state &= ~((unsigned int)SUSPENDED_BIT_);
     atomic_store_explicit(&call_stack->state, state, memory_order_relaxed);
 
-    const FimoTime current_time = fimo_time_now();
+    const FimoTime current_time = ctx_now_(call_stack->ctx);
     for (FimoUSize i = 0; i < fimo_array_list_len(&call_stack->ctx->subscribers); i++) {
         void **stack_;
         FimoResult error = fimo_array_list_get(&call_stack->call_stacks, i, sizeof(void *), (const void **)&stack_);
@@ -554,7 +583,7 @@ static FimoResult call_stack_emit_event_(FimoTracingCallStack *call_stack, const
         return error;
     }
 
-    const FimoTime current_time = fimo_time_now();
+    const FimoTime current_time = ctx_now_(call_stack->ctx);
     for (FimoUSize i = 0; i < fimo_array_list_len(&call_stack->ctx->subscribers); i++) {
         void **stack_;
         error = fimo_array_list_get(&call_stack->call_stacks, i, sizeof(void *), (const void **)&stack_);
@@ -570,6 +599,69 @@ static FimoResult call_stack_emit_event_(FimoTracingCallStack *call_stack, const
     return FIMO_EOK;
 }
 
+static FimoResult call_stack_emit_event_batch_(FimoTracingCallStack *call_stack, const FimoTracingRecord *records,
+                                               const FimoUSize record_count) {
+    FIMO_DEBUG_ASSERT(call_stack && records && call_stack_is_bound_(call_stack))
+    if (call_stack_is_suspended_(call_stack)) {
+        return FIMO_EPERM;
+    }
+
+    // The records are forwarded in runs of consecutive records that would be traced,
+    // so that we don't need to copy them, while preserving their order.
+    const FimoTime current_time = ctx_now_(call_stack->ctx);
+    FimoUSize run_start = 0;
+    while (run_start < record_count) {
+        if (!call_stack_would_trace_(call_stack, records[run_start].event->metadata)) {
+            run_start++;
+            continue;
+        }
+        FimoUSize run_end = run_start + 1;
+        while (run_end < record_count && call_stack_would_trace_(call_stack, records[run_end].event->metadata)) {
+            run_end++;
+        }
+
+        for (FimoUSize i = 0; i < fimo_array_list_len(&call_stack->ctx->subscribers); i++) {
+            void **stack_;
+            FimoResult error = fimo_array_list_get(&call_stack->call_stacks, i, sizeof(void *), (const void **)&stack_);
+            FIMO_DEBUG_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
+
+            const FimoTracingSubscriber *subscriber;
+            error = fimo_array_list_get(&call_stack->ctx->subscribers, i, sizeof(FimoTracingSubscriber),
+                                        (const void **)&subscriber);
+            FIMO_DEBUG_ASSERT_FALSE(FIMO_RESULT_IS_ERROR(error))
+            FIMO_RESULT_IGNORE(error);
+            subscriber_emit_event_batch_(subscriber, &current_time, *stack_, records + run_start,
+                                         run_end - run_start);
+        }
+        run_start = run_end;
+    }
+
+    return FIMO_EOK;
+}
+
+static FimoUSize call_stack_span_stack_(FimoTracingCallStack *call_stack, FimoTracingSpanInfo *spans,
+                                        const FimoUSize capacity) {
+    FIMO_DEBUG_ASSERT(call_stack && call_stack_is_bound_(call_stack))
+
+    // The message of a frame is stored in the buffer of the call stack,
+    // and ends where the message of the next frame begins.
+    FimoUSize count = 0;
+    for (const struct StackFrame_ *frame = call_stack->start_frame; frame != NULL; frame = frame->next, count++) {
+        if (count >= capacity) {
+            continue;
+        }
+        const FimoUSize message_end = frame->next ? frame->next->parent_cursor : call_stack->cursor;
+        const FimoUSize message_length = message_end - frame->parent_cursor;
+        spans[count] = (FimoTracingSpanInfo){
+                .metadata = frame->metadata,
+                .message = message_length != 0 ? call_stack->buffer + frame->parent_cursor : NULL,
+                .message_length = message_length,
+        };
+    }
+
+    return count;
+}
+
 ///////////////////////////////////////////////////////////////////////
 //// Thread Specific Data
 ///////////////////////////////////////////////////////////////////////
@@ -831,6 +923,30 @@ static FimoResult ctx_emit_event_(FimoInternalTracingContext *ctx, const FimoTra
     return call_stack_emit_event_(local_data->active, event, format, data);
 }
 
+static FimoResult ctx_emit_event_batch_(FimoInternalTracingContext *ctx, const FimoTracingRecord *records,
+                                        const FimoUSize record_count) {
+    FIMO_DEBUG_ASSERT(ctx && records)
+    if (record_count == 0 || !ctx_is_enabled_for_thread_(ctx)) {
+        return FIMO_EOK;
+    }
+
+    struct TSSData_ *local_data = tss_get(ctx->tss_data);
+    FIMO_DEBUG_ASSERT(local_data && local_data->active)
+    return call_stack_emit_event_batch_(local_data->active, records, record_count);
+}
+
+static FimoUSize ctx_span_stack_(FimoInternalTracingContext *ctx, FimoTracingSpanInfo *spans,
+                                 const FimoUSize capacity) {
+    FIMO_DEBUG_ASSERT(ctx)
+    if (!ctx_is_enabled_for_thread_(ctx)) {
+        return 0;
+    }
+
+    struct TSSData_ *local_data = tss_get(ctx->tss_data);
+    FIMO_DEBUG_ASSERT(local_data && local_data->active)
+    return call_stack_span_stack_(local_data->active, spans, capacity);
+}
+
 static bool ctx_is_enabled_(FimoInternalTracingContext *ctx) {
     FIMO_DEBUG_ASSERT(ctx)
     return !(ctx->max_level == FIMO_TRACING_LEVEL_OFF || fimo_array_list_is_empty(&ctx->subscribers));
@@ -903,6 +1019,13 @@ static void ctx_flush_(FimoInternalTracingContext *ctx) {
     }
 }
 
+static FimoTime ctx_now_(FimoInternalTracingContext *ctx) {
+    FIMO_DEBUG_ASSERT(ctx)
+    // Timestamps are taken from the clock of the context, to allow replacing it in tests.
+    FimoInternalContext *context = TO_CTX_(ctx);
+    return fimo_internal_clock_now(&context->clock);
+}
+
 ///////////////////////////////////////////////////////////////////////
 //// Trampoline functions
 ///////////////////////////////////////////////////////////////////////
@@ -977,6 +1100,18 @@ FimoResult fimo_internal_trampoline_tracing_flush(void *ctx) {
     return fimo_internal_tracing_flush(&((FimoInternalContext *)ctx)->tracing);
 }
 
+FimoResult fimo_internal_trampoline_tracing_event_emit_batch(void *ctx, const FimoTracingRecord *records,
+                                                             FimoUSize record_count) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_tracing_event_emit_batch(&((FimoInternalContext *)ctx)->tracing, records, record_count);
+}
+
+FimoResult fimo_internal_trampoline_tracing_span_stack(void *ctx, FimoTracingSpanInfo *spans, FimoUSize capacity,
+                                                       FimoUSize *span_count) {
+    FIMO_DEBUG_ASSERT(ctx)
+    return fimo_internal_tracing_span_stack(&((FimoInternalContext *)ctx)->tracing, spans, capacity, span_count);
+}
+
 ///////////////////////////////////////////////////////////////////////
 //// Tracing Subsystem API
 ///////////////////////////////////////////////////////////////////////
@@ -1126,3 +1261,29 @@ FimoResult fimo_internal_tracing_flush(FimoInternalTracingContext *ctx) {
     ctx_flush_(ctx);
     return FIMO_EOK;
 }
+
+FIMO_MUST_USE
+FimoResult fimo_internal_tracing_event_emit_batch(FimoInternalTracingContext *ctx, const FimoTracingRecord *records,
+                                                  const FimoUSize record_count) {
+    FIMO_DEBUG_ASSERT(ctx)
+    if (records == NULL && record_count != 0) {
+        return FIMO_EINVAL;
+    }
+    for (FimoUSize i = 0; i < record_count; i++) {
+        if (records[i].event == NULL || (records[i].message == NULL && records[i].message_length != 0)) {
+            return FIMO_EINVAL;
+        }
+    }
+    return ctx_emit_event_batch_(ctx, records, record_count);
+}
+
+FIMO_MUST_USE
+FimoResult fimo_internal_tracing_span_stack(FimoInternalTracingContext *ctx, FimoTracingSpanInfo *spans,
+                                            const FimoUSize capacity, FimoUSize *span_count) {
+    FIMO_DEBUG_ASSERT(ctx)
+    if ((spans == NULL && capacity != 0) || span_count == NULL) {
+        return FIMO_EINVAL;
+    }
+    *span_count = ctx_span_stack_(ctx, spans, capacity);
+    return FIMO_EOK;
+}
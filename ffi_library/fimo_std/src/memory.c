@@ -15,6 +15,45 @@
 #include <malloc.h>
 #endif // defined(_WIN32) || defined(WIN32)
 
+static _Thread_local FimoMemoryCounters *COUNTERS_ = NULL;
+
+static size_t usable_size_(void *ptr, const size_t size) {
+#if defined(_WIN32) || defined(WIN32)
+    (void)ptr;
+    return size;
+#elif __APPLE__
+    (void)size;
+    return malloc_size(ptr);
+#elif __ANDROID__
+    (void)size;
+    return malloc_usable_size(ptr);
+#elif __linux__
+    (void)size;
+    return malloc_usable_size(ptr);
+#else
+    (void)ptr;
+    return size;
+#endif // defined(_WIN32) || defined(WIN32)
+}
+
+static void free_(void *ptr, const size_t size) {
+    if (ptr == NULL) {
+        return;
+    }
+
+    FimoMemoryCounters *counters = COUNTERS_;
+    if (counters) {
+        counters->deallocations++;
+        counters->deallocated_bytes += usable_size_(ptr, size);
+    }
+
+#if defined(_WIN32) || defined(WIN32)
+    _aligned_free(ptr);
+#else
+    free(ptr);
+#endif
+}
+
 FIMO_EXPORT
 FIMO_MUST_USE
 void *fimo_malloc(const size_t size, FimoResult *error) { return fimo_malloc_sized(size, error).ptr; }
@@ -101,6 +140,12 @@ FimoMallocBuffer fimo_aligned_alloc_sized(size_t alignment, size_t size, FimoRes
     buff_size = size;
 #endif // defined(_WIN32) || defined(WIN32)
 
+    FimoMemoryCounters *counters = COUNTERS_;
+    if (counters) {
+        counters->allocations++;
+        counters->allocated_bytes += buff_size;
+    }
+
     if (error) {
         *error = FIMO_EOK;
     }
@@ -108,23 +153,20 @@ FimoMallocBuffer fimo_aligned_alloc_sized(size_t alignment, size_t size, FimoRes
 }
 
 FIMO_EXPORT
-void fimo_free(void *ptr) {
-#if defined(_WIN32) || defined(WIN32)
-    _aligned_free(ptr);
-#else
-    free(ptr);
-#endif
-}
+void fimo_free(void *ptr) { free_(ptr, 0); }
 
 FIMO_EXPORT
-void fimo_free_sized(void *ptr, const size_t size) {
-    (void)size;
-    fimo_free(ptr);
-}
+void fimo_free_sized(void *ptr, const size_t size) { free_(ptr, size); }
 
 FIMO_EXPORT
 void fimo_free_aligned_sized(void *ptr, const size_t alignment, const size_t size) {
     (void)alignment;
-    (void)size;
-    fimo_free(ptr);
+    free_(ptr, size);
+}
+
+FIMO_EXPORT
+FimoMemoryCounters *fimo_memory_swap_counters(FimoMemoryCounters *counters) {
+    FimoMemoryCounters *previous = COUNTERS_;
+    COUNTERS_ = counters;
+    return previous;
 }
@@ -122,6 +122,50 @@ FimoResult fimo_module_set_append_modules(const FimoContext context, FimoModuleL
                                                 fimo_impl_module_export_iterator, *(const void **)&iterator);
 }
 
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_set_append_group(const FimoContext context, FimoModuleLoadingSet *module_set,
+                                        const char *group, const char *const *modules,
+                                        const FimoUSize modules_count) {
+    const FimoContextVTable *vtable = context.vtable;
+    return vtable->module_v0.set_append_group(context.data, module_set, group, modules, modules_count);
+}
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_loader_register(const FimoContext context, const FimoModuleLoader *loader) {
+    const FimoContextVTable *vtable = context.vtable;
+    return vtable->module_v0.loader_register(context.data, loader);
+}
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_loader_unregister(const FimoContext context, const char *name) {
+    const FimoContextVTable *vtable = context.vtable;
+    return vtable->module_v0.loader_unregister(context.data, name);
+}
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_signature_set_policy(const FimoContext context, const FimoModuleSignaturePolicy policy) {
+    const FimoContextVTable *vtable = context.vtable;
+    return vtable->module_v0.signature_set_policy(context.data, policy);
+}
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_signature_trust_key(const FimoContext context, const FimoU8 *key) {
+    const FimoContextVTable *vtable = context.vtable;
+    return vtable->module_v0.signature_trust_key(context.data, key);
+}
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_fault_set_policy(const FimoContext context, const FimoModuleFaultPolicy policy) {
+    const FimoContextVTable *vtable = context.vtable;
+    return vtable->module_v0.fault_set_policy(context.data, policy);
+}
+
 FIMO_EXPORT
 FIMO_MUST_USE
 FimoResult fimo_module_set_dismiss(const FimoContext context, FimoModuleLoadingSet *module_set) {
@@ -238,6 +282,14 @@ FimoResult fimo_module_unload(const FimoContext context, const FimoModuleInfo *m
     return vtable->module_v0.unload(context.data, module);
 }
 
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_symbol_use_counts(const FimoContext context, const FimoModuleInfo *module,
+                                         FimoModuleSymbolUseCount **use_counts, FimoUSize *use_counts_count) {
+    const FimoContextVTable *vtable = context.vtable;
+    return vtable->module_v0.symbol_use_counts(context.data, module, use_counts, use_counts_count);
+}
+
 FIMO_EXPORT
 FIMO_MUST_USE
 FimoResult fimo_module_param_query(const FimoContext context, const char *module_name, const char *param,
@@ -247,6 +299,48 @@ FimoResult fimo_module_param_query(const FimoContext context, const char *module
     return vtable->module_v0.param_query(context.data, module_name, param, type, read, write);
 }
 
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_param_list(const FimoContext context, const char *module_name, FimoModuleParamInfo **params,
+                                  FimoUSize *params_count) {
+    const FimoContextVTable *vtable = context.vtable;
+    return vtable->module_v0.param_list(context.data, module_name, params, params_count);
+}
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_param_set_override(const FimoContext context, const char *module_name, const char *param,
+                                          const void *value, const FimoModuleParamType type) {
+    const FimoContextVTable *vtable = context.vtable;
+    return vtable->module_v0.param_set_override(context.data, module_name, param, value, type);
+}
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_feature_list(const FimoContext context, const char *module_name,
+                                   FimoModuleFeatureInfo **features, FimoUSize *features_count) {
+    const FimoContextVTable *vtable = context.vtable;
+    return vtable->module_v0.feature_list(context.data, module_name, features, features_count);
+}
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_feature_set_override(const FimoContext context, const char *module_name, const char *feature,
+                                           const bool *enabled) {
+    const FimoContextVTable *vtable = context.vtable;
+    return vtable->module_v0.feature_set_override(context.data, module_name, feature, enabled);
+}
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_module_feature_enabled(const FimoModule *module, const char *feature, bool *enabled) {
+    if (module == NULL) {
+        return FIMO_EINVAL;
+    }
+    const FimoContextVTable *vtable = module->context.vtable;
+    return vtable->module_v0.feature_enabled(module->context.data, module, feature, enabled);
+}
+
 FIMO_EXPORT
 FIMO_MUST_USE
 FimoResult fimo_module_param_set_public(const FimoContext context, const void *value, const FimoModuleParamType type,
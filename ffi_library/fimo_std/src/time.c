@@ -354,3 +354,73 @@ FimoTime fimo_time_saturating_sub(const FimoTime *time_point, const FimoDuration
     }
     return result;
 }
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoTimeMonotonic fimo_time_monotonic_now(void) {
+#if defined(_WIN32) || defined(WIN32)
+    LARGE_INTEGER frequency;
+    LARGE_INTEGER counter;
+    QueryPerformanceFrequency(&frequency);
+    QueryPerformanceCounter(&counter);
+
+    const FimoU64 ticks = (FimoU64)counter.QuadPart;
+    const FimoU64 ticks_per_sec = (FimoU64)frequency.QuadPart;
+    return (FimoTimeMonotonic){.secs = ticks / ticks_per_sec,
+                               .nanos = (FimoU32)((ticks % ticks_per_sec) * FIMO_NANOS_PER_SEC / ticks_per_sec)};
+#elif __unix__ || __APPLE__
+    struct timespec tp;
+    clock_gettime(CLOCK_MONOTONIC, &tp);
+    return (FimoTimeMonotonic){.secs = tp.tv_sec, .nanos = tp.tv_nsec};
+#endif
+}
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_time_monotonic_elapsed(const FimoTimeMonotonic *time_point, FimoDuration *elapsed) {
+    FIMO_DEBUG_ASSERT(time_point && elapsed)
+    const FimoTimeMonotonic now = fimo_time_monotonic_now();
+    return fimo_time_monotonic_duration_since(&now, time_point, elapsed);
+}
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_time_monotonic_duration_since(const FimoTimeMonotonic *time_point,
+                                              const FimoTimeMonotonic *earlier_time_point, FimoDuration *duration) {
+    FIMO_DEBUG_ASSERT(time_point && earlier_time_point && duration)
+    // The arithmetic is identical to the one of the wall clock.
+    const FimoTime lhs = {.secs = time_point->secs, .nanos = time_point->nanos};
+    const FimoTime rhs = {.secs = earlier_time_point->secs, .nanos = earlier_time_point->nanos};
+    return fimo_time_duration_since(&lhs, &rhs, duration);
+}
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_time_monotonic_add(const FimoTimeMonotonic *time_point, const FimoDuration *duration,
+                                   FimoTimeMonotonic *out) {
+    FIMO_DEBUG_ASSERT(time_point && duration && out)
+    const FimoTime lhs = {.secs = time_point->secs, .nanos = time_point->nanos};
+    FimoTime result;
+    const FimoResult error = fimo_time_add(&lhs, duration, &result);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        return error;
+    }
+
+    out->secs = result.secs;
+    out->nanos = result.nanos;
+    return FIMO_EOK;
+}
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoTimeMonotonic fimo_time_monotonic_saturating_add(const FimoTimeMonotonic *time_point,
+                                                     const FimoDuration *duration) {
+    FIMO_DEBUG_ASSERT(time_point && duration)
+    FimoTimeMonotonic result;
+    const FimoResult error = fimo_time_monotonic_add(time_point, duration, &result);
+    if (FIMO_RESULT_IS_ERROR(error)) {
+        fimo_result_release(error);
+        return FIMO_TIME_MONOTONIC_MAX;
+    }
+    return result;
+}
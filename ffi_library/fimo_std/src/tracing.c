@@ -1,6 +1,7 @@
 #include <fimo_std/tracing.h>
 
 #include <stdio.h>
+#include <string.h>
 
 #include <fimo_std/vtable.h>
 
@@ -119,6 +120,91 @@ FimoResult fimo_tracing_event_emit_custom(const FimoContext context, const FimoT
     return vtable->tracing_v0.event_emit(context.data, event, format, data);
 }
 
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_tracing_event_emit_template(const FimoContext context, const FimoTracingEvent *event) {
+    const FimoTracingMessageTemplate *template_ = fimo_tracing_event_template(event);
+    if (template_ == NULL) {
+        return FIMO_EINVAL;
+    }
+    return fimo_tracing_event_emit_custom(context, event, fimo_tracing_format_template, template_);
+}
+
+FIMO_EXPORT
+FIMO_MUST_USE
+const FimoTracingMessageTemplate *fimo_tracing_event_template(const FimoTracingEvent *event) {
+    if (event == NULL) {
+        return NULL;
+    }
+    for (const FimoBaseStructIn *current = event->next; current != NULL; current = current->next) {
+        if (current->type == FIMO_STRUCT_TYPE_TRACING_MESSAGE_TEMPLATE) {
+            return (const FimoTracingMessageTemplate *)current;
+        }
+    }
+    return NULL;
+}
+
+static const FimoTracingField *find_template_field_(const FimoTracingMessageTemplate *template_, const char *name,
+                                                    const FimoUSize name_len) {
+    for (FimoUSize i = 0; i < template_->field_count; i++) {
+        const FimoTracingField *field = &template_->fields[i];
+        if (strlen(field->name) == name_len && strncmp(field->name, name, name_len) == 0) {
+            return field;
+        }
+    }
+    return NULL;
+}
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_tracing_format_template(char *buffer, const FimoUSize buffer_size, const void *data,
+                                        FimoUSize *written_size) {
+    if (buffer == NULL || data == NULL || written_size == NULL) {
+        return FIMO_EINVAL;
+    }
+    const FimoTracingMessageTemplate *template_ = data;
+    if (template_->type != FIMO_STRUCT_TYPE_TRACING_MESSAGE_TEMPLATE || template_->message == NULL) {
+        return FIMO_EINVAL;
+    }
+
+    FimoUSize cursor = 0;
+    const char *current = template_->message;
+    while (*current != '\0' && cursor < buffer_size) {
+        if ((current[0] == '{' || current[0] == '}') && current[1] == current[0]) {
+            buffer[cursor++] = current[0];
+            current += 2;
+            continue;
+        }
+
+        if (current[0] == '{') {
+            const char *end = strchr(current, '}');
+            const FimoTracingField *field =
+                    end != NULL ? find_template_field_(template_, current + 1, (FimoUSize)(end - current - 1)) : NULL;
+            if (field != NULL) {
+                if (field->format == NULL) {
+                    return FIMO_EINVAL;
+                }
+
+                // Formatters may report the untruncated length, like `snprintf`.
+                FimoUSize field_written = 0;
+                const FimoUSize remaining = buffer_size - cursor;
+                const FimoResult error = field->format(buffer + cursor, remaining, field->data, &field_written);
+                if (FIMO_RESULT_IS_ERROR(error)) {
+                    return error;
+                }
+                cursor += field_written < remaining ? field_written : remaining;
+                current = end + 1;
+                continue;
+            }
+        }
+
+        buffer[cursor++] = *current++;
+    }
+
+    *written_size = cursor;
+    return FIMO_EOK;
+}
+
 FIMO_EXPORT
 FIMO_MUST_USE
 bool fimo_tracing_is_enabled(const FimoContext context) {
@@ -146,3 +232,19 @@ FimoResult fimo_tracing_flush(const FimoContext context) {
     const FimoContextVTable *vtable = context.vtable;
     return vtable->tracing_v0.flush(context.data);
 }
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_tracing_event_emit_batch(const FimoContext context, const FimoTracingRecord *records,
+                                         const FimoUSize record_count) {
+    const FimoContextVTable *vtable = context.vtable;
+    return vtable->tracing_v0.event_emit_batch(context.data, records, record_count);
+}
+
+FIMO_EXPORT
+FIMO_MUST_USE
+FimoResult fimo_tracing_span_stack(const FimoContext context, FimoTracingSpanInfo *spans, const FimoUSize capacity,
+                                   FimoUSize *span_count) {
+    const FimoContextVTable *vtable = context.vtable;
+    return vtable->tracing_v0.span_stack(context.data, spans, capacity, span_count);
+}
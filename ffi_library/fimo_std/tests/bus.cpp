@@ -0,0 +1,306 @@
+#include <catch2/catch_all.hpp>
+
+#include <atomic>
+#include <chrono>
+#include <cstring>
+#include <string>
+#include <thread>
+#include <vector>
+
+#include <fimo_std/bus.h>
+#include <fimo_std/module.h>
+
+static const FimoBusTopic TEST_TOPIC = {
+        .id = {0x2f, 0x6e, 0x1b, 0x0a, 0x5c, 0x47, 0x4e, 0x0d, 0x8a, 0x31, 0x77, 0xc2, 0x19, 0xe4, 0x53, 0x90},
+        .version = FIMO_VERSION(1, 2, 0),
+};
+
+struct Received {
+    std::vector<int> values;
+    std::vector<std::string> names;
+    bool dropped = false;
+};
+
+static void on_value(void *data, const FimoBusEvent *event) {
+    auto *received = static_cast<Received *>(data);
+    REQUIRE(event->payload_size == sizeof(int));
+    int value;
+    std::memcpy(&value, event->payload, sizeof(int));
+    received->values.push_back(value);
+}
+
+static void on_name(void *data, const FimoBusEvent *event) {
+    auto *received = static_cast<Received *>(data);
+    received->names.emplace_back(static_cast<const char *>(event->payload));
+}
+
+static void on_drop(void *data) { static_cast<Received *>(data)->dropped = true; }
+
+static FimoBusSubscription *subscribe(FimoContext context, FimoBusTopic topic, FimoBusDeliveryMode mode,
+                                      Received *received, void (*on_event)(void *, const FimoBusEvent *)) {
+    const FimoBusSubscriber subscriber = {
+            .next = nullptr,
+            .topic = topic,
+            .mode = mode,
+            .data = received,
+            .on_event = on_event,
+            .on_drop = on_drop,
+    };
+    FimoBusSubscription *subscription;
+    FimoResult error = fimo_bus_subscribe(context, &subscriber, &subscription);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    return subscription;
+}
+
+static void publish(FimoContext context, FimoBusTopic topic, int value) {
+    const FimoBusEvent event = {
+            .topic = &topic,
+            .payload = &value,
+            .payload_size = sizeof(value),
+    };
+    FimoResult error = fimo_bus_publish(context, &event);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+}
+
+TEST_CASE("Event delivery", "[bus]") {
+    FimoContext context;
+    FimoResult error = fimo_context_init(nullptr, &context);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    Received sync;
+    Received queued;
+    FimoBusSubscription *sync_subscription =
+            subscribe(context, TEST_TOPIC, FIMO_BUS_DELIVERY_MODE_SYNC, &sync, on_value);
+    FimoBusSubscription *queued_subscription =
+            subscribe(context, TEST_TOPIC, FIMO_BUS_DELIVERY_MODE_QUEUED, &queued, on_value);
+
+    FimoBusTopic newer = TEST_TOPIC;
+    newer.version.minor = 5;
+    FimoBusTopic older = TEST_TOPIC;
+    older.version.minor = 1;
+    FimoBusTopic other = TEST_TOPIC;
+    other.id[0] = 0;
+
+    const std::vector<int> expected = {1, 2};
+    publish(context, TEST_TOPIC, 1);
+    publish(context, newer, 2);
+    publish(context, older, 3);
+    publish(context, other, 4);
+    REQUIRE(sync.values == expected);
+    REQUIRE(queued.values.empty());
+
+    FimoUSize count;
+    error = fimo_bus_dispatch(context, queued_subscription, &count);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    REQUIRE(count == 2);
+    REQUIRE(queued.values == expected);
+
+    error = fimo_bus_dispatch(context, sync_subscription, nullptr);
+    REQUIRE(FIMO_RESULT_IS_ERROR(error));
+    fimo_result_release(error);
+
+    error = fimo_bus_unsubscribe(context, sync_subscription);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    REQUIRE(sync.dropped);
+
+    publish(context, TEST_TOPIC, 5);
+    REQUIRE(sync.values == expected);
+
+    error = fimo_bus_unsubscribe(context, queued_subscription);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    REQUIRE(queued.dropped);
+    REQUIRE(queued.values == expected);
+
+    fimo_context_release(context);
+}
+
+struct Blocking {
+    std::atomic<bool> entered = false;
+    std::atomic<bool> finished = false;
+    std::atomic<bool> dropped = false;
+};
+
+static void on_blocking(void *data, const FimoBusEvent *) {
+    auto *blocking = static_cast<Blocking *>(data);
+    blocking->entered = true;
+    std::this_thread::sleep_for(std::chrono::milliseconds(50));
+    blocking->finished = true;
+}
+
+static void on_blocking_drop(void *data) { static_cast<Blocking *>(data)->dropped = true; }
+
+TEST_CASE("Unsubscribe waits for deliveries", "[bus]") {
+    FimoContext context;
+    FimoResult error = fimo_context_init(nullptr, &context);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    for (FimoBusDeliveryMode mode: {FIMO_BUS_DELIVERY_MODE_SYNC, FIMO_BUS_DELIVERY_MODE_QUEUED}) {
+        Blocking blocking;
+        const FimoBusSubscriber subscriber = {
+                .next = nullptr,
+                .topic = TEST_TOPIC,
+                .mode = mode,
+                .data = &blocking,
+                .on_event = on_blocking,
+                .on_drop = on_blocking_drop,
+        };
+        FimoBusSubscription *subscription;
+        error = fimo_bus_subscribe(context, &subscriber, &subscription);
+        REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+        // The assertions are not thread-safe, therefore the errors are checked after the join.
+        FimoResult thread_error = FIMO_EOK;
+        std::thread thread{[&] {
+            const int value = 1;
+            const FimoBusEvent event = {
+                    .topic = &TEST_TOPIC,
+                    .payload = &value,
+                    .payload_size = sizeof(value),
+            };
+            thread_error = fimo_bus_publish(context, &event);
+            if (!FIMO_RESULT_IS_ERROR(thread_error) && mode == FIMO_BUS_DELIVERY_MODE_QUEUED) {
+                thread_error = fimo_bus_dispatch(context, subscription, nullptr);
+            }
+        }};
+        while (!blocking.entered) {
+            std::this_thread::yield();
+        }
+
+        error = fimo_bus_unsubscribe(context, subscription);
+        REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+        REQUIRE(blocking.finished);
+        REQUIRE(blocking.dropped);
+        thread.join();
+        REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(thread_error));
+    }
+
+    fimo_context_release(context);
+}
+
+struct SelfRemoving {
+    FimoContext context;
+    FimoBusSubscription *subscription;
+    bool dropped_in_handler = false;
+    bool dropped = false;
+};
+
+static void on_self_removing(void *data, const FimoBusEvent *) {
+    auto *self = static_cast<SelfRemoving *>(data);
+    FimoResult error = fimo_bus_unsubscribe(self->context, self->subscription);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    self->dropped_in_handler = self->dropped;
+}
+
+static void on_self_removing_drop(void *data) { static_cast<SelfRemoving *>(data)->dropped = true; }
+
+TEST_CASE("Unsubscribe from the event handler", "[bus]") {
+    FimoContext context;
+    FimoResult error = fimo_context_init(nullptr, &context);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    SelfRemoving self{.context = context, .subscription = nullptr};
+    const FimoBusSubscriber subscriber = {
+            .next = nullptr,
+            .topic = TEST_TOPIC,
+            .mode = FIMO_BUS_DELIVERY_MODE_SYNC,
+            .data = &self,
+            .on_event = on_self_removing,
+            .on_drop = on_self_removing_drop,
+    };
+    error = fimo_bus_subscribe(context, &subscriber, &self.subscription);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    publish(context, TEST_TOPIC, 1);
+    REQUIRE_FALSE(self.dropped_in_handler);
+    REQUIRE(self.dropped);
+
+    fimo_context_release(context);
+}
+
+TEST_CASE("Module events", "[bus]") {
+    FimoContext context;
+    FimoResult error = fimo_context_init(nullptr, &context);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    Received loaded;
+    Received unloaded;
+    FimoBusSubscription *loaded_subscription =
+            subscribe(context, FIMO_BUS_TOPIC_MODULE_LOADED, FIMO_BUS_DELIVERY_MODE_SYNC, &loaded, on_name);
+    FimoBusSubscription *unloaded_subscription =
+            subscribe(context, FIMO_BUS_TOPIC_MODULE_UNLOADED, FIMO_BUS_DELIVERY_MODE_QUEUED, &unloaded, on_name);
+
+    const FimoModule *pseudo_module;
+    error = fimo_module_pseudo_module_new(context, &pseudo_module);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    fimo_context_release(context);
+    REQUIRE(loaded.names.size() == 1);
+    REQUIRE(loaded.names[0] == pseudo_module->module_info->name);
+
+    error = fimo_module_pseudo_module_destroy(pseudo_module, &context);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    error = fimo_bus_dispatch(context, unloaded_subscription, nullptr);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    REQUIRE(unloaded.names == loaded.names);
+
+    error = fimo_bus_unsubscribe(context, loaded_subscription);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    error = fimo_bus_unsubscribe(context, unloaded_subscription);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    fimo_context_release(context);
+}
+
+struct Reentrant {
+    FimoContext context;
+    std::vector<std::string> names;
+    bool found = false;
+};
+
+static void on_reentrant(void *data, const FimoBusEvent *event) {
+    auto *reentrant = static_cast<Reentrant *>(data);
+    const char *name = static_cast<const char *>(event->payload);
+    reentrant->names.emplace_back(name);
+
+    // The module subsystem is no longer locked, therefore the module can be queried.
+    const FimoModuleInfo *info;
+    FimoResult error = fimo_module_find_by_name(reentrant->context, name, &info);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    reentrant->found = std::strcmp(info->name, name) == 0;
+    FIMO_MODULE_INFO_RELEASE(info);
+}
+
+TEST_CASE("Module event subscribers may call into the module subsystem", "[bus]") {
+    FimoContext context;
+    FimoResult error = fimo_context_init(nullptr, &context);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    Reentrant reentrant{.context = context};
+    const FimoBusSubscriber subscriber = {
+            .next = nullptr,
+            .topic = FIMO_BUS_TOPIC_MODULE_LOADED,
+            .mode = FIMO_BUS_DELIVERY_MODE_SYNC,
+            .data = &reentrant,
+            .on_event = on_reentrant,
+            .on_drop = nullptr,
+    };
+    FimoBusSubscription *subscription;
+    error = fimo_bus_subscribe(context, &subscriber, &subscription);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    const FimoModule *pseudo_module;
+    error = fimo_module_pseudo_module_new(context, &pseudo_module);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    REQUIRE(reentrant.names.size() == 1);
+    REQUIRE(reentrant.names[0] == pseudo_module->module_info->name);
+    REQUIRE(reentrant.found);
+
+    FimoContext module_context;
+    error = fimo_module_pseudo_module_destroy(pseudo_module, &module_context);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    fimo_context_release(module_context);
+
+    error = fimo_bus_unsubscribe(context, subscription);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    fimo_context_release(context);
+}
@@ -0,0 +1,88 @@
+#include <catch2/catch_all.hpp>
+
+#include <fimo_std/clock.h>
+#include <fimo_std/tracing.h>
+
+struct ManualClock {
+    FimoTime now;
+    FimoTimeMonotonic monotonic_now;
+    bool dropped = false;
+};
+
+static FimoTime manual_now(void *data) { return static_cast<ManualClock *>(data)->now; }
+
+static FimoTimeMonotonic manual_monotonic_now(void *data) { return static_cast<ManualClock *>(data)->monotonic_now; }
+
+static void manual_drop(void *data) { static_cast<ManualClock *>(data)->dropped = true; }
+
+static FimoClockConfig manual_config(ManualClock *clock) {
+    return FimoClockConfig{
+            .type = FIMO_STRUCT_TYPE_CLOCK_CONFIG,
+            .next = nullptr,
+            .data = clock,
+            .now = manual_now,
+            .monotonic_now = manual_monotonic_now,
+            .on_drop = manual_drop,
+    };
+}
+
+TEST_CASE("System clock", "[clock]") {
+    FimoContext context;
+    FimoResult error = fimo_context_init(nullptr, &context);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    const FimoTimeMonotonic first = fimo_clock_monotonic_now(context);
+    const FimoTimeMonotonic second = fimo_clock_monotonic_now(context);
+    FimoDuration elapsed;
+    error = fimo_time_monotonic_duration_since(&second, &first, &elapsed);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    const FimoTime now = fimo_clock_now(context);
+    REQUIRE(now.secs > 0);
+
+    fimo_context_release(context);
+}
+
+TEST_CASE("Custom clock", "[clock]") {
+    ManualClock clock = {
+            .now = {.secs = 1000, .nanos = 0},
+            .monotonic_now = {.secs = 5, .nanos = 0},
+    };
+    const FimoClockConfig config = manual_config(&clock);
+    const FimoBaseStructIn *options[] = {reinterpret_cast<const FimoBaseStructIn *>(&config), nullptr};
+
+    FimoContext context;
+    FimoResult error = fimo_context_init(options, &context);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    FimoTime now = fimo_clock_now(context);
+    REQUIRE(now.secs == 1000);
+    REQUIRE(now.nanos == 0);
+
+    const FimoDuration step = FIMO_MILLIS(1500);
+    clock.now = fimo_time_saturating_add(&clock.now, &step);
+    clock.monotonic_now = fimo_time_monotonic_saturating_add(&clock.monotonic_now, &step);
+    now = fimo_clock_now(context);
+    REQUIRE(now.secs == 1001);
+    REQUIRE(now.nanos == 500000000);
+    const FimoTimeMonotonic monotonic_now = fimo_clock_monotonic_now(context);
+    REQUIRE(monotonic_now.secs == 6);
+    REQUIRE(monotonic_now.nanos == 500000000);
+
+    REQUIRE_FALSE(clock.dropped);
+    fimo_context_release(context);
+    REQUIRE(clock.dropped);
+}
+
+TEST_CASE("Invalid clock", "[clock]") {
+    ManualClock clock = {};
+    FimoClockConfig config = manual_config(&clock);
+    config.monotonic_now = nullptr;
+    const FimoBaseStructIn *options[] = {reinterpret_cast<const FimoBaseStructIn *>(&config), nullptr};
+
+    FimoContext context;
+    FimoResult error = fimo_context_init(options, &context);
+    REQUIRE(FIMO_RESULT_IS_ERROR(error));
+    fimo_result_release(error);
+    REQUIRE(clock.dropped);
+}
@@ -0,0 +1,170 @@
+#include <catch2/catch_all.hpp>
+
+#include <array>
+#include <string>
+#include <string_view>
+#include <vector>
+
+#include <fimo_std/internal/ed25519.h>
+
+namespace {
+    std::vector<FimoU8> from_hex(std::string_view hex) {
+        auto nibble = [](char c) -> FimoU8 {
+            if (c >= '0' && c <= '9') {
+                return static_cast<FimoU8>(c - '0');
+            }
+            return static_cast<FimoU8>(c - 'a' + 10);
+        };
+
+        std::vector<FimoU8> bytes;
+        for (std::size_t i = 0; i + 1 < hex.size(); i += 2) {
+            bytes.push_back(static_cast<FimoU8>((nibble(hex[i]) << 4) | nibble(hex[i + 1])));
+        }
+        return bytes;
+    }
+
+    std::vector<FimoU8> sha512(std::string_view message, std::size_t chunk_size) {
+        FimoInternalSha512 hash;
+        fimo_internal_sha512_init(&hash);
+        for (std::size_t i = 0; i < message.size(); i += chunk_size) {
+            const auto chunk = message.substr(i, chunk_size);
+            fimo_internal_sha512_update(&hash, chunk.data(), chunk.size());
+        }
+        std::vector<FimoU8> digest(64);
+        fimo_internal_sha512_final(&hash, digest.data());
+        return digest;
+    }
+
+    bool verify(const std::vector<FimoU8> &public_key, const std::vector<FimoU8> &signature,
+                const std::vector<FimoU8> &message) {
+        REQUIRE(public_key.size() == FIMO_INTERNAL_ED25519_PUBLIC_KEY_SIZE);
+        REQUIRE(signature.size() == FIMO_INTERNAL_ED25519_SIGNATURE_SIZE);
+        FimoInternalEd25519Verifier verifier;
+        fimo_internal_ed25519_verify_init(&verifier, public_key.data(), signature.data());
+        fimo_internal_ed25519_verify_update(&verifier, message.data(), message.size());
+        return fimo_internal_ed25519_verify_final(&verifier);
+    }
+
+    struct SignatureVector {
+        std::string_view public_key;
+        std::string_view message;
+        std::string_view signature;
+    };
+
+    // Test vectors from RFC 8032, section 7.1.
+    constexpr std::array<SignatureVector, 3> RFC8032_VECTORS = {{
+            {
+                    "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+                    "",
+                    "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e3970"
+                    "1cf9b46bd25bf5f0595bbe24655141438e7a100b",
+            },
+            {
+                    "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+                    "72",
+                    "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613"
+                    "d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+            },
+            {
+                    "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+                    "af82",
+                    "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760"
+                    "984dc6594a7c15e9716ed28dc027beceea1ec40a",
+            },
+    }};
+} // namespace
+
+TEST_CASE("SHA-512 digests", "[ed25519]") {
+    // Test vectors from FIPS 180-4, and the NIST example values.
+    const std::string million_a(1000000, 'a');
+    const std::array<std::pair<std::string_view, std::string_view>, 4> vectors = {{
+            {"abc", "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3"
+                    "feebbd454d4423643ce80e2a9ac94fa54ca49f"},
+            {"", "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d287"
+                 "7eec2f63b931bd47417a81a538327af927da3e"},
+            {"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrs"
+             "mnopqrstnopqrstu",
+             "8e959b75dae313da8cf4f72814fc143f8f7779c6eb9f7fa17299aeadb6889018501d289e4900f7e4331b99dec4b543"
+             "3ac7d329eeb6dd26545e96e55b874be909"},
+            {million_a, "e718483d0ce769644e2e42c7bc15b4638e1f98b13b2044285632a803afa973ebde0ff244877ea60a4cb04"
+                        "32ce577c31beb009c5c2c49aa2e4eadb217ad8cc09b"},
+    }};
+
+    for (const auto &[message, digest]: vectors) {
+        // The digest must not depend on how the message is split.
+        for (const std::size_t chunk_size: {std::size_t{1}, std::size_t{7}, std::size_t{128}, std::size_t{4096}}) {
+            if (message.size() > 1000 && chunk_size == 1) {
+                continue;
+            }
+            REQUIRE(sha512(message, chunk_size) == from_hex(digest));
+        }
+    }
+}
+
+TEST_CASE("Verify valid Ed25519 signatures", "[ed25519]") {
+    for (const auto &vector: RFC8032_VECTORS) {
+        REQUIRE(verify(from_hex(vector.public_key), from_hex(vector.signature), from_hex(vector.message)));
+    }
+
+    // The message may be passed in multiple parts.
+    const auto &vector = RFC8032_VECTORS[2];
+    const auto public_key = from_hex(vector.public_key);
+    const auto signature = from_hex(vector.signature);
+    const auto message = from_hex(vector.message);
+    FimoInternalEd25519Verifier verifier;
+    fimo_internal_ed25519_verify_init(&verifier, public_key.data(), signature.data());
+    for (const auto byte: message) {
+        fimo_internal_ed25519_verify_update(&verifier, &byte, 1);
+    }
+    REQUIRE(fimo_internal_ed25519_verify_final(&verifier));
+}
+
+TEST_CASE("Reject tampered Ed25519 signatures", "[ed25519]") {
+    const auto &vector = RFC8032_VECTORS[2];
+    const auto public_key = from_hex(vector.public_key);
+    const auto signature = from_hex(vector.signature);
+    const auto message = from_hex(vector.message);
+
+    SECTION("tampered message") {
+        auto tampered = message;
+        tampered[0] ^= 0x01;
+        REQUIRE_FALSE(verify(public_key, signature, tampered));
+        tampered = message;
+        tampered.push_back(0);
+        REQUIRE_FALSE(verify(public_key, signature, tampered));
+    }
+
+    SECTION("tampered signature") {
+        for (const std::size_t byte: {std::size_t{0}, std::size_t{31}, std::size_t{32}, std::size_t{63}}) {
+            auto tampered = signature;
+            tampered[byte] ^= 0x01;
+            REQUIRE_FALSE(verify(public_key, tampered, message));
+        }
+
+        // A non-canonical scalar `s + L` must be rejected, even though it is equivalent to `s`.
+        constexpr std::array<FimoU8, 32> order = {
+                0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10,
+        };
+        auto malleable = signature;
+        unsigned carry = 0;
+        for (std::size_t i = 0; i < order.size(); i++) {
+            carry += static_cast<unsigned>(malleable[32 + i]) + order[i];
+            malleable[32 + i] = static_cast<FimoU8>(carry);
+            carry >>= 8;
+        }
+        REQUIRE(carry == 0);
+        REQUIRE_FALSE(verify(public_key, malleable, message));
+    }
+
+    SECTION("tampered public key") {
+        for (const std::size_t byte: {std::size_t{0}, std::size_t{16}, std::size_t{31}}) {
+            auto tampered = public_key;
+            tampered[byte] ^= 0x01;
+            REQUIRE_FALSE(verify(tampered, signature, message));
+        }
+
+        // The public key of another signer.
+        REQUIRE_FALSE(verify(from_hex(RFC8032_VECTORS[1].public_key), signature, message));
+    }
+}
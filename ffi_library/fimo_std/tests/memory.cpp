@@ -102,3 +102,22 @@ TEST_CASE("Allocate aligned memory", "[memory]") {
         fimo_free_aligned_sized(buffer.ptr, 256, buffer.buff_size);
     }
 }
+
+TEST_CASE("Record allocations", "[memory]") {
+    FimoResult error;
+    FimoMemoryCounters counters{};
+
+    REQUIRE(fimo_memory_swap_counters(&counters) == nullptr);
+    FimoMallocBuffer buffer = fimo_malloc_sized(64, &error);
+    REQUIRE(buffer.ptr != nullptr);
+    fimo_free(buffer.ptr);
+    REQUIRE(fimo_memory_swap_counters(nullptr) == &counters);
+
+    REQUIRE(counters.allocations == 1);
+    REQUIRE(counters.deallocations == 1);
+    REQUIRE(counters.allocated_bytes == buffer.buff_size);
+
+    void *untracked = fimo_malloc(64, &error);
+    fimo_free(untracked);
+    REQUIRE(counters.allocations == 1);
+}
@@ -1,5 +1,14 @@
 #include <catch2/catch_all.hpp>
 
+#include <algorithm>
+#include <cstring>
+#include <filesystem>
+#include <fstream>
+#include <string>
+#include <vector>
+
+#include <fimo_std/bus.h>
+#include <fimo_std/memory.h>
 #include <fimo_std/module.h>
 #include <fimo_std/tracing.h>
 
@@ -166,6 +175,50 @@ FIMO_MODULE_EXPORT_MODULE("c", nullptr, nullptr, nullptr, FIMO_MODULE_EXPORT_MOD
                           FIMO_MODULE_EXPORT_MODULE_SYMBOL_IMPORTS(c_imports),
                           FIMO_MODULE_EXPORT_MODULE_CONSTRUCTOR(c_constructor, c_destructor))
 
+static const FimoModuleExportModifierFeature d_feature_stable = {
+        .name = "stable",
+        .stability = FIMO_MODULE_FEATURE_STABILITY_STABLE,
+        .default_enabled = true,
+        .required_version = FIMO_VERSION(FIMO_VERSION_MAJOR, FIMO_VERSION_MINOR, 0),
+};
+static const FimoModuleExportModifierFeature d_feature_beta = {
+        .name = "beta",
+        .stability = FIMO_MODULE_FEATURE_STABILITY_EXPERIMENTAL,
+        .default_enabled = false,
+        .required_version = FIMO_VERSION(FIMO_VERSION_MAJOR, FIMO_VERSION_MINOR, 0),
+};
+static const FimoModuleExportModifierFeature d_feature_future = {
+        .name = "future",
+        .stability = FIMO_MODULE_FEATURE_STABILITY_EXPERIMENTAL,
+        .default_enabled = true,
+        .required_version = FIMO_VERSION(FIMO_VERSION_MAJOR + 1, 0, 0),
+};
+static FimoModuleExportModifier d_modifiers[] = {
+        {FIMO_MODULE_EXPORT_MODIFIER_KEY_FEATURE, &d_feature_stable},
+        {FIMO_MODULE_EXPORT_MODIFIER_KEY_FEATURE, &d_feature_beta},
+        {FIMO_MODULE_EXPORT_MODIFIER_KEY_FEATURE, &d_feature_future},
+};
+static bool d_beta_enabled = false;
+static FimoResult d_constructor(const FimoModule *module, FimoModuleLoadingSet *set, void **data) {
+    (void)set;
+    FimoResult error = fimo_module_feature_enabled(module, "beta", &d_beta_enabled);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    bool enabled;
+    error = fimo_module_feature_enabled(module, "missing", &enabled);
+    REQUIRE(FIMO_RESULT_IS_ERROR(error));
+    fimo_result_release(error);
+
+    *data = nullptr;
+    return FIMO_EOK;
+}
+static void d_destructor(const FimoModule *module, void *data) {
+    (void)module;
+    (void)data;
+}
+FIMO_MODULE_EXPORT_MODULE("d", nullptr, nullptr, nullptr, FIMO_MODULE_EXPORT_MODULE_MODIFIERS(d_modifiers),
+                          FIMO_MODULE_EXPORT_MODULE_CONSTRUCTOR(d_constructor, d_destructor))
+
 static bool modules_filter(const FimoModuleExport *arg0, void *arg1) {
     (void)arg0;
     (void)arg1;
@@ -248,6 +301,29 @@ TEST_CASE("Load modules", "[modules]") {
     REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
     const int *a_export_0_symbol_ptr = static_cast<const int *>(FIMO_MODULE_SYMBOL_LOCK(a_export_0_symbol));
     REQUIRE(*a_export_0_symbol_ptr == a_export_0);
+
+    FimoModuleSymbolUseCount *use_counts;
+    FimoUSize use_counts_count;
+    error = fimo_module_symbol_use_counts(pseudo_module->context, a_info, &use_counts, &use_counts_count);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    REQUIRE(use_counts_count == 2);
+    for (FimoUSize i = 0; i < use_counts_count; i++) {
+        const FimoModuleSymbolUseCount *use_count = &use_counts[i];
+        std::vector<std::string> importers{use_count->importers, use_count->importers + use_count->importers_count};
+        std::sort(importers.begin(), importers.end());
+        if (std::strcmp(use_count->name, "a_export_0") == 0) {
+            std::vector<std::string> expected{"c", pseudo_module->module_info->name};
+            std::sort(expected.begin(), expected.end());
+            REQUIRE(use_count->use_count == 1);
+            REQUIRE(importers == expected);
+        }
+        else {
+            REQUIRE(std::strcmp(use_count->name, "a_export_1") == 0);
+            REQUIRE(use_count->use_count == 0);
+            REQUIRE(importers == std::vector<std::string>{"c"});
+        }
+    }
+    fimo_free(use_counts);
     FIMO_MODULE_SYMBOL_RELEASE(a_export_0_symbol);
 
     error = fimo_module_pseudo_module_destroy(pseudo_module, &context);
@@ -258,3 +334,401 @@ TEST_CASE("Load modules", "[modules]") {
 
     fimo_context_release(context);
 }
+
+TEST_CASE("Module groups", "[modules]") {
+    FimoContext context;
+    FimoResult error = fimo_context_init(nullptr, &context);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    FimoModuleLoadingSet *set;
+    error = fimo_module_set_new(context, &set);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    error = fimo_module_set_append_modules(context, set, nullptr, modules_filter, nullptr);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    const char *group_modules[] = {"a", "b"};
+    error = fimo_module_set_append_group(context, set, "ab", group_modules, 2);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    // The group already exists.
+    const char *other_modules[] = {"c"};
+    error = fimo_module_set_append_group(context, set, "ab", other_modules, 1);
+    REQUIRE(FIMO_RESULT_IS_ERROR(error));
+    fimo_result_release(error);
+
+    // The module is already a member of a group.
+    error = fimo_module_set_append_group(context, set, "a", group_modules, 1);
+    REQUIRE(FIMO_RESULT_IS_ERROR(error));
+    fimo_result_release(error);
+
+    // The module is not contained in the set.
+    const char *missing_modules[] = {"c", "d"};
+    error = fimo_module_set_append_group(context, set, "cd", missing_modules, 2);
+    REQUIRE(FIMO_RESULT_IS_ERROR(error));
+    fimo_result_release(error);
+
+    error = fimo_module_set_finish(context, set);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    const FimoModuleInfo *a_info;
+    error = fimo_module_find_by_name(context, "a", &a_info);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    const FimoModuleInfo *b_info;
+    error = fimo_module_find_by_name(context, "b", &b_info);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    const FimoModuleInfo *c_info;
+    error = fimo_module_find_by_name(context, "c", &c_info);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    REQUIRE(std::strcmp(a_info->group, "ab") == 0);
+    REQUIRE(std::strcmp(b_info->group, "ab") == 0);
+    REQUIRE(c_info->group == nullptr);
+
+    // The members are still used by `c`.
+    error = fimo_module_unload(context, b_info);
+    REQUIRE(FIMO_RESULT_IS_ERROR(error));
+    fimo_result_release(error);
+    REQUIRE(FIMO_MODULE_INFO_IS_LOADED(a_info));
+    REQUIRE(FIMO_MODULE_INFO_IS_LOADED(b_info));
+
+    // Unloading `c` unloads the entire group, as it is no longer referenced.
+    error = fimo_module_unload(context, c_info);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    REQUIRE_FALSE(FIMO_MODULE_INFO_IS_LOADED(a_info));
+    REQUIRE_FALSE(FIMO_MODULE_INFO_IS_LOADED(b_info));
+    REQUIRE_FALSE(FIMO_MODULE_INFO_IS_LOADED(c_info));
+
+    FIMO_MODULE_INFO_RELEASE(a_info);
+    FIMO_MODULE_INFO_RELEASE(b_info);
+    FIMO_MODULE_INFO_RELEASE(c_info);
+
+    fimo_context_release(context);
+}
+
+struct LoaderState {
+    int loaded = 0;
+    int unloaded = 0;
+    bool dropped = false;
+};
+
+static FimoResult loader_load(void *data, const char *path, void **binary) {
+    auto *state = static_cast<LoaderState *>(data);
+    // Verified binaries are loaded through a path referring to the verified contents.
+    REQUIRE(std::filesystem::exists(path));
+    state->loaded++;
+    *binary = state;
+    return FIMO_EOK;
+}
+
+static void loader_iterate_exports(void *data, void *binary, bool (*f)(const FimoModuleExport *, void *),
+                                   void *f_data) {
+    REQUIRE(data == binary);
+    fimo_impl_module_export_iterator(f, f_data);
+}
+
+static void loader_unload(void *data, void *binary) {
+    REQUIRE(data == binary);
+    static_cast<LoaderState *>(data)->unloaded++;
+}
+
+static void loader_drop(void *data) { static_cast<LoaderState *>(data)->dropped = true; }
+
+static bool loader_filter(const FimoModuleExport *arg0, void *arg1) {
+    (void)arg1;
+    return std::strcmp(arg0->name, "a") == 0;
+}
+
+TEST_CASE("Custom module loaders", "[modules]") {
+    FimoContext context;
+    FimoResult error = fimo_context_init(nullptr, &context);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    const std::filesystem::path binary_path = std::filesystem::temp_directory_path() / "loader_module.fimotest";
+    std::ofstream{binary_path}.close();
+
+    LoaderState state;
+    const FimoModuleLoader loader = {
+            .next = nullptr,
+            .name = "test_loader",
+            .pattern = "*.fimo?est",
+            .data = &state,
+            .load = loader_load,
+            .iterate_exports = loader_iterate_exports,
+            .unload = loader_unload,
+            .on_drop = loader_drop,
+    };
+    error = fimo_module_loader_register(context, &loader);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    error = fimo_module_loader_register(context, &loader);
+    REQUIRE(FIMO_RESULT_IS_ERROR(error));
+    fimo_result_release(error);
+
+    FimoModuleLoadingSet *set;
+    error = fimo_module_set_new(context, &set);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    error = fimo_module_set_append_modules(context, set, binary_path.string().c_str(), loader_filter, nullptr);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    REQUIRE(state.loaded == 1);
+
+    bool has_module;
+    error = fimo_module_set_has_module(context, set, "a", &has_module);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    REQUIRE(has_module);
+    error = fimo_module_set_has_module(context, set, "b", &has_module);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    REQUIRE_FALSE(has_module);
+
+    error = fimo_module_loader_unregister(context, "test_loader");
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    REQUIRE_FALSE(state.dropped);
+
+    error = fimo_module_set_dismiss(context, set);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    REQUIRE(state.unloaded == 1);
+    REQUIRE(state.dropped);
+
+    error = fimo_module_loader_unregister(context, "test_loader");
+    REQUIRE(FIMO_RESULT_IS_ERROR(error));
+    fimo_result_release(error);
+
+    std::filesystem::remove(binary_path);
+    fimo_context_release(context);
+}
+
+// Ed25519 keys and signatures of `SIGNED_BINARY`.
+static const char SIGNED_BINARY[] = "fimo signed module\n";
+static const FimoU8 SIGNER_0_KEY[FIMO_MODULE_SIGNATURE_PUBLIC_KEY_SIZE] = {
+        0xfc, 0x4a, 0xa2, 0xd9, 0x27, 0x3b, 0xb2, 0xac, 0x5a, 0xfa, 0x6b, 0x00, 0xb3, 0x4d, 0x35, 0xfc,
+        0x43, 0x98, 0xd0, 0xb9, 0x95, 0xca, 0xd6, 0x74, 0xa6, 0x33, 0x26, 0x2b, 0x63, 0x9f, 0xf9, 0x77,
+};
+static const FimoU8 SIGNER_0_SIGNATURE[64] = {
+        0x19, 0x62, 0x56, 0x09, 0x05, 0x6c, 0x39, 0x0d, 0xb7, 0xec, 0xc4, 0x5e, 0x05, 0x37, 0xda, 0x78,
+        0xb4, 0x71, 0x5e, 0x18, 0xff, 0x0d, 0xf6, 0xca, 0x2f, 0x48, 0x49, 0xd1, 0xc4, 0x3f, 0xff, 0x18,
+        0x02, 0x81, 0x23, 0xde, 0x26, 0x1b, 0xbe, 0xcd, 0x96, 0x88, 0x63, 0x9e, 0x5b, 0xb4, 0x83, 0x3b,
+        0x39, 0x71, 0x82, 0xc0, 0xe2, 0x83, 0x2b, 0xf8, 0x4a, 0x59, 0x86, 0x4d, 0x56, 0xa7, 0xc2, 0x04,
+};
+static const FimoU8 SIGNER_1_KEY[FIMO_MODULE_SIGNATURE_PUBLIC_KEY_SIZE] = {
+        0x4f, 0xe1, 0x49, 0xf2, 0xe6, 0x6a, 0x0c, 0x35, 0x6d, 0x18, 0xcb, 0x14, 0xa4, 0x26, 0x5d, 0x55,
+        0x40, 0xd9, 0x3f, 0x8f, 0xc3, 0x20, 0xb3, 0x73, 0xf2, 0xb1, 0x77, 0x6c, 0x22, 0xa7, 0x21, 0x76,
+};
+static const FimoU8 SIGNER_1_SIGNATURE[64] = {
+        0x29, 0x57, 0x75, 0xfa, 0xb6, 0xac, 0xf9, 0xd5, 0xeb, 0xe3, 0xb8, 0x2a, 0x80, 0xd3, 0x9a, 0x0d,
+        0x06, 0x5e, 0x75, 0xa5, 0x6d, 0x86, 0xb4, 0x8e, 0xbb, 0x13, 0xba, 0x22, 0x53, 0xa0, 0xc7, 0xe3,
+        0x48, 0x34, 0x7d, 0x91, 0x6e, 0x1b, 0x20, 0x71, 0xfe, 0x3b, 0xa6, 0xef, 0x02, 0x98, 0x11, 0xdd,
+        0x98, 0xa7, 0x0c, 0xb4, 0xdc, 0xe4, 0xd4, 0x1b, 0x6e, 0x7d, 0xa3, 0xce, 0x9e, 0x7f, 0x85, 0x06,
+};
+
+static void write_signed_binary(const std::filesystem::path &path, const char *contents, const FimoU8 *key,
+                                const FimoU8 *signature) {
+    std::ofstream{path, std::ios::binary}.write(contents, static_cast<std::streamsize>(std::strlen(contents)));
+    std::filesystem::path signature_path = path;
+    signature_path += ".sig";
+    std::ofstream signature_file{signature_path, std::ios::binary};
+    signature_file.write(reinterpret_cast<const char *>(key), FIMO_MODULE_SIGNATURE_PUBLIC_KEY_SIZE);
+    signature_file.write(reinterpret_cast<const char *>(signature), 64);
+}
+
+static FimoResult append_binary(FimoContext context, const std::filesystem::path &path) {
+    FimoModuleLoadingSet *set;
+    FimoResult error = fimo_module_set_new(context, &set);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    const FimoResult result =
+            fimo_module_set_append_modules(context, set, path.string().c_str(), loader_filter, nullptr);
+    error = fimo_module_set_dismiss(context, set);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    return result;
+}
+
+static void on_verification_failed(void *data, const FimoBusEvent *event) {
+    static_cast<std::vector<std::string> *>(data)->emplace_back(static_cast<const char *>(event->payload));
+}
+
+TEST_CASE("Module signatures", "[modules]") {
+    FimoContext context;
+    FimoResult error = fimo_context_init(nullptr, &context);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    LoaderState state;
+    const FimoModuleLoader loader = {
+            .next = nullptr,
+            .name = "test_loader",
+            .pattern = "*.fimotest",
+            .data = &state,
+            .load = loader_load,
+            .iterate_exports = loader_iterate_exports,
+            .unload = loader_unload,
+            .on_drop = nullptr,
+    };
+    error = fimo_module_loader_register(context, &loader);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    std::vector<std::string> failures;
+    const FimoBusSubscriber subscriber = {
+            .next = nullptr,
+            .topic = FIMO_BUS_TOPIC_MODULE_VERIFICATION_FAILED,
+            .mode = FIMO_BUS_DELIVERY_MODE_SYNC,
+            .data = &failures,
+            .on_event = on_verification_failed,
+            .on_drop = nullptr,
+    };
+    FimoBusSubscription *subscription;
+    error = fimo_bus_subscribe(context, &subscriber, &subscription);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    const std::filesystem::path binary_path = std::filesystem::temp_directory_path() / "signed_module.fimotest";
+    std::filesystem::path signature_path = binary_path;
+    signature_path += ".sig";
+    write_signed_binary(binary_path, SIGNED_BINARY, SIGNER_0_KEY, SIGNER_0_SIGNATURE);
+
+    SECTION("Require") {
+        error = fimo_module_signature_set_policy(context, FIMO_MODULE_SIGNATURE_POLICY_REQUIRE);
+        REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+        // The signer is not trusted.
+        error = append_binary(context, binary_path);
+        REQUIRE(FIMO_RESULT_IS_ERROR(error));
+        fimo_result_release(error);
+        REQUIRE(state.loaded == 0);
+        REQUIRE(failures.size() == 1);
+        REQUIRE(failures[0] == binary_path.string());
+
+        error = fimo_module_signature_trust_key(context, SIGNER_0_KEY);
+        REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+        error = append_binary(context, binary_path);
+        REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+        REQUIRE(state.loaded == 1);
+
+        // The contents do not match the signature.
+        write_signed_binary(binary_path, "fimo tampered module\n", SIGNER_0_KEY, SIGNER_0_SIGNATURE);
+        error = append_binary(context, binary_path);
+        REQUIRE(FIMO_RESULT_IS_ERROR(error));
+        fimo_result_release(error);
+
+        // The binary is not signed.
+        std::filesystem::remove(signature_path);
+        error = append_binary(context, binary_path);
+        REQUIRE(FIMO_RESULT_IS_ERROR(error));
+        fimo_result_release(error);
+        REQUIRE(state.loaded == 1);
+        REQUIRE(failures.size() == 3);
+
+        // The policy can not be relaxed after a binary was loaded.
+        error = fimo_module_signature_set_policy(context, FIMO_MODULE_SIGNATURE_POLICY_OFF);
+        REQUIRE(FIMO_RESULT_IS_ERROR(error));
+        fimo_result_release(error);
+        error = fimo_module_signature_set_policy(context, FIMO_MODULE_SIGNATURE_POLICY_TRUST_ON_FIRST_USE);
+        REQUIRE(FIMO_RESULT_IS_ERROR(error));
+        fimo_result_release(error);
+        error = append_binary(context, binary_path);
+        REQUIRE(FIMO_RESULT_IS_ERROR(error));
+        fimo_result_release(error);
+        REQUIRE(state.loaded == 1);
+    }
+
+    SECTION("Trust on first use") {
+        error = fimo_module_signature_set_policy(context, FIMO_MODULE_SIGNATURE_POLICY_TRUST_ON_FIRST_USE);
+        REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+        error = append_binary(context, binary_path);
+        REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+        error = append_binary(context, binary_path);
+        REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+        REQUIRE(state.loaded == 2);
+
+        // The binary was signed by a different key on first use.
+        write_signed_binary(binary_path, SIGNED_BINARY, SIGNER_1_KEY, SIGNER_1_SIGNATURE);
+        error = append_binary(context, binary_path);
+        REQUIRE(FIMO_RESULT_IS_ERROR(error));
+        fimo_result_release(error);
+        REQUIRE(state.loaded == 2);
+        REQUIRE(failures.size() == 1);
+
+        // The keys are pinned to the canonical path of the binary.
+        const std::filesystem::path aliased_path =
+                binary_path.parent_path() / "." / ".." / binary_path.parent_path().filename() / binary_path.filename();
+        error = append_binary(context, aliased_path);
+        REQUIRE(FIMO_RESULT_IS_ERROR(error));
+        fimo_result_release(error);
+        REQUIRE(state.loaded == 2);
+        REQUIRE(failures.size() == 2);
+
+        error = fimo_module_signature_trust_key(context, SIGNER_1_KEY);
+        REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+        error = append_binary(context, binary_path);
+        REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+        REQUIRE(state.loaded == 3);
+    }
+
+    error = fimo_bus_unsubscribe(context, subscription);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    error = fimo_module_loader_unregister(context, "test_loader");
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    std::filesystem::remove(signature_path);
+    std::filesystem::remove(binary_path);
+    fimo_context_release(context);
+}
+
+static bool features_filter(const FimoModuleExport *arg0, void *arg1) {
+    (void)arg1;
+    return std::strcmp(arg0->name, "d") == 0;
+}
+
+TEST_CASE("Module features", "[modules]") {
+    FimoContext context;
+    FimoResult error = fimo_context_init(nullptr, &context);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    const bool enabled = true;
+    error = fimo_module_feature_set_override(context, "d", "beta", &enabled);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    error = fimo_module_feature_set_override(context, "d", "future", &enabled);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+
+    FimoModuleLoadingSet *set;
+    error = fimo_module_set_new(context, &set);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    error = fimo_module_set_append_modules(context, set, nullptr, features_filter, nullptr);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    error = fimo_module_set_finish(context, set);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    REQUIRE(d_beta_enabled);
+
+    FimoModuleFeatureInfo *features;
+    FimoUSize features_count;
+    error = fimo_module_feature_list(context, "d", &features, &features_count);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    REQUIRE(features_count == 3);
+    for (FimoUSize i = 0; i < features_count; i++) {
+        const FimoModuleFeatureInfo *feature = &features[i];
+        if (std::strcmp(feature->name, "stable") == 0) {
+            REQUIRE((feature->available && feature->enabled));
+        }
+        else if (std::strcmp(feature->name, "beta") == 0) {
+            REQUIRE(feature->stability == FIMO_MODULE_FEATURE_STABILITY_EXPERIMENTAL);
+            REQUIRE((!feature->default_enabled && feature->enabled));
+        }
+        else {
+            // The feature requires a newer context.
+            REQUIRE(std::strcmp(feature->name, "future") == 0);
+            REQUIRE((!feature->available && !feature->enabled));
+        }
+    }
+    fimo_free(features);
+
+    error = fimo_module_feature_list(context, "missing", &features, &features_count);
+    REQUIRE(FIMO_RESULT_IS_ERROR(error));
+    fimo_result_release(error);
+
+    const FimoModuleInfo *d_info;
+    error = fimo_module_find_by_name(context, "d", &d_info);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    error = fimo_module_unload(context, d_info);
+    REQUIRE_FALSE(FIMO_RESULT_IS_ERROR(error));
+    FIMO_MODULE_INFO_RELEASE(d_info);
+
+    fimo_context_release(context);
+}
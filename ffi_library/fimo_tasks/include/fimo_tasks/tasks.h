@@ -85,6 +85,30 @@ extern "C" {
 #define FI_TASKS_SET_STACK_SIZE_COMMAND(STACK_SIZE)                                                                    \
     { .type = FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_SET_STACK_SIZE, .data = {.set_stack_size = (STACK_SIZE)}, }
 
+/**
+ * Constructs a new command.
+ *
+ * @param SEMAPHORE semaphore to wait for
+ * @param VALUE value to wait for
+ */
+#define FI_TASKS_WAIT_SEMAPHORE_COMMAND(SEMAPHORE, VALUE)                                                              \
+    {                                                                                                                  \
+        .type = FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_WAIT_SEMAPHORE,                                                     \
+        .data = {.wait_semaphore = {.semaphore = (SEMAPHORE), .value = (VALUE)}},                                      \
+    }
+
+/**
+ * Constructs a new command.
+ *
+ * @param SEMAPHORE semaphore to signal
+ * @param VALUE value to signal
+ */
+#define FI_TASKS_SIGNAL_SEMAPHORE_COMMAND(SEMAPHORE, VALUE)                                                            \
+    {                                                                                                                  \
+        .type = FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_SIGNAL_SEMAPHORE,                                                   \
+        .data = {.signal_semaphore = {.semaphore = (SEMAPHORE), .value = (VALUE)}},                                    \
+    }
+
 
 /**
  * VTable of a `FiTasksContext`.
@@ -251,6 +275,518 @@ typedef struct FiTasksCommandBufferHandle FiTasksCommandBufferHandle;
  */
 typedef struct FiTasksWorkerGroupVTable FiTasksWorkerGroupVTable;
 
+/**
+ * Accumulated execution times and allocations of all tasks with
+ * the same tag.
+ */
+typedef struct FiTasksTaskTimes {
+    /**
+     * Tag of the tasks.
+     */
+    FimoUSize tag;
+    /**
+     * Number of finished tasks.
+     */
+    FimoUSize num_tasks;
+    /**
+     * Number of execution slices, i.e., the number of times
+     * a task was resumed by a worker.
+     */
+    FimoUSize num_slices;
+    /**
+     * Time spent between resuming and suspending the tasks.
+     */
+    FimoDuration wall_time;
+    /**
+     * CPU time consumed by the worker threads while executing
+     * the tasks.
+     *
+     * Is zero if the platform does not provide a per-thread
+     * CPU clock.
+     */
+    FimoDuration cpu_time;
+    /**
+     * Number of allocations performed by the tasks with the
+     * default allocator.
+     *
+     * Is zero if the allocation tracking of the worker group
+     * is disabled.
+     */
+    FimoUSize allocations;
+    /**
+     * Number of deallocations performed by the tasks with the
+     * default allocator.
+     *
+     * Is zero if the allocation tracking of the worker group
+     * is disabled.
+     */
+    FimoUSize deallocations;
+    /**
+     * Number of bytes allocated by the tasks with the default
+     * allocator.
+     *
+     * Is zero if the allocation tracking of the worker group
+     * is disabled.
+     */
+    FimoUSize allocated_bytes;
+    /**
+     * Number of bytes deallocated by the tasks with the default
+     * allocator.
+     *
+     * Is zero if the allocation tracking of the worker group
+     * is disabled.
+     */
+    FimoUSize deallocated_bytes;
+} FiTasksTaskTimes;
+
+/**
+ * Work stealing statistics of a worker group.
+ */
+typedef struct FiTasksWorkerGroupStealStats {
+    /**
+     * Number of successful steals from the global queue.
+     */
+    FimoUSize global_steals;
+    /**
+     * Number of successful steals from the queues of other
+     * workers.
+     */
+    FimoUSize peer_steals;
+    /**
+     * Total number of tasks acquired through stealing.
+     */
+    FimoUSize stolen_tasks;
+} FiTasksWorkerGroupStealStats;
+
+/**
+ * Scheduler statistics of a worker group.
+ */
+typedef struct FiTasksWorkerGroupStats {
+    /**
+     * Number of tasks spawned by the command buffers.
+     */
+    FimoUSize spawned_tasks;
+    /**
+     * Number of tasks that have run to completion.
+     */
+    FimoUSize completed_tasks;
+    /**
+     * Number of tasks that have been aborted.
+     */
+    FimoUSize aborted_tasks;
+    /**
+     * Number of tasks waiting in the global queues of the
+     * worker group.
+     */
+    FimoUSize queued_tasks;
+    /**
+     * Number of stacks currently in use by the tasks.
+     */
+    FimoUSize acquired_stacks;
+    /**
+     * Number of allocated stacks kept for reuse.
+     */
+    FimoUSize pooled_stacks;
+    /**
+     * Average time between spawning a task and the start of
+     * its execution by a worker.
+     */
+    FimoDuration average_latency;
+    /**
+     * Number of workers of the worker group, excluding the
+     * workers that are retiring.
+     */
+    FimoUSize active_workers;
+    /**
+     * Number of workers that have retired, due to a resize of
+     * the worker group.
+     */
+    FimoUSize retired_workers;
+} FiTasksWorkerGroupStats;
+
+/**
+ * Scheduler statistics of a worker of a worker group.
+ */
+typedef struct FiTasksWorkerStats {
+    /**
+     * Id of the worker.
+     */
+    FimoUSize worker;
+    /**
+     * Number of tasks waiting in the local queue of the worker.
+     */
+    FimoUSize queued_tasks;
+    /**
+     * Time spent executing tasks.
+     */
+    FimoDuration busy_time;
+    /**
+     * Time spent without executing any task since the start
+     * of the worker.
+     */
+    FimoDuration idle_time;
+} FiTasksWorkerStats;
+
+/**
+ * Usage statistics of the stacks of one size class of a worker
+ * group.
+ */
+typedef struct FiTasksStackStats {
+    /**
+     * Size of the stacks, excluding any guard page.
+     */
+    FimoUSize size;
+    /**
+     * Number of stacks currently in use by the tasks.
+     */
+    FimoUSize acquired_stacks;
+    /**
+     * Number of allocated stacks kept for reuse.
+     */
+    FimoUSize pooled_stacks;
+    /**
+     * Maximum number of stacks that were in use at the same time.
+     */
+    FimoUSize peak_acquired_stacks;
+    /**
+     * Number of tasks requesting a stack of this class that were
+     * assigned a larger stack, as the maximum residency of the
+     * class was reached.
+     */
+    FimoUSize spilled_tasks;
+    /**
+     * Maximum number of bytes used by any task executed on a stack
+     * of this class. Is `0` if the usage tracking is disabled.
+     */
+    FimoUSize peak_usage;
+} FiTasksStackStats;
+
+/**
+ * Scheduling state of a task.
+ */
+typedef enum FiTasksTaskState {
+    /**
+     * The task is waiting to be started or resumed by a worker.
+     */
+    FI_TASKS_TASK_STATE_QUEUED = 0,
+    /**
+     * The task is being executed by a worker.
+     */
+    FI_TASKS_TASK_STATE_RUNNING = 1,
+    /**
+     * The task is suspended until the event it waits on occurs.
+     */
+    FI_TASKS_TASK_STATE_BLOCKED = 2,
+    FI_TASKS_TASK_STATE_FORCE32 = 0x7FFFFFFF
+} FiTasksTaskState;
+
+/**
+ * Event a blocked task is waiting on.
+ */
+typedef enum FiTasksTaskWaitReason {
+    /**
+     * The task is not blocked.
+     */
+    FI_TASKS_TASK_WAIT_REASON_NONE = 0,
+    /**
+     * The task is sleeping until a point in time.
+     */
+    FI_TASKS_TASK_WAIT_REASON_SLEEP = 1,
+    /**
+     * The task is waiting on the completion of a command buffer.
+     */
+    FI_TASKS_TASK_WAIT_REASON_COMMAND_BUFFER = 2,
+    /**
+     * The task is parked until it is unparked, or its timeout is reached.
+     */
+    FI_TASKS_TASK_WAIT_REASON_PARKED = 3,
+    FI_TASKS_TASK_WAIT_REASON_FORCE32 = 0x7FFFFFFF
+} FiTasksTaskWaitReason;
+
+/**
+ * Snapshot of a task that has been spawned by a worker group,
+ * but has not finished yet.
+ */
+typedef struct FiTasksTaskInfo {
+    /**
+     * Id of the task.
+     */
+    FimoUSize id;
+    /**
+     * Label of the task, or `NULL`, if the task is unlabeled.
+     */
+    const char *label;
+    /**
+     * Tag of the task.
+     */
+    FimoUSize tag;
+    /**
+     * Scheduling state of the task.
+     */
+    FiTasksTaskState state;
+    /**
+     * Event the task is waiting on, if it is blocked.
+     */
+    FiTasksTaskWaitReason wait_reason;
+    /**
+     * Whether the task is bound to a worker, i.e., whether it
+     * has been started.
+     */
+    bool has_worker;
+    /**
+     * Id of the worker the task is bound to. Is only valid if
+     * `has_worker` is `true`.
+     */
+    FimoUSize worker;
+} FiTasksTaskInfo;
+
+/**
+ * Core VTable of a `FiTasksTimer`.
+ */
+typedef struct FiTasksTimerVTableV0 {
+    void (*acquire)(void *);
+    void (*release)(void *);
+    void (*cancel)(void *);
+    bool (*is_active)(void *);
+} FiTasksTimerVTableV0;
+
+/**
+ * VTable of a `FiTasksTimer`.
+ */
+typedef struct FiTasksTimerVTable {
+    FiTasksTimerVTableV0 v0;
+} FiTasksTimerVTable;
+
+/**
+ * A timer scheduled on the event loop of a worker group.
+ *
+ * A timer invokes its callback once its deadline has been reached,
+ * and, if it is periodic, at a fixed rate afterward, until it is
+ * cancelled or the worker group is closed.
+ */
+typedef struct FiTasksTimer {
+    void *data;
+    const FiTasksTimerVTable *vtable;
+} FiTasksTimer;
+
+/**
+ * Configuration of a timer.
+ */
+typedef struct FiTasksTimerConfig {
+    /**
+     * Reserved for future use.
+     * Must be `null`.
+     */
+    void *next;
+    /**
+     * Time at which the callback is invoked for the first time.
+     * A deadline in the past invokes the callback immediately.
+     */
+    FimoTime deadline;
+    /**
+     * Period of the timer. A zero duration creates a one-shot
+     * timer. Periodic timers are rescheduled relative to their
+     * previous deadline, skipping the periods missed while the
+     * event loop was busy.
+     */
+    FimoDuration period;
+    /**
+     * Function invoked by the event loop of the worker group.
+     * The function is not run inside a task, and should only
+     * perform short operations, like enqueueing a command buffer
+     * or signaling a semaphore. Must not be `null`.
+     */
+    void (*callback)(void *);
+    /**
+     * Argument of the callback.
+     */
+    void *data;
+    /**
+     * Optional function invoked with `data` once the timer has
+     * finished, i.e., once it won't invoke the callback anymore.
+     */
+    void (*cleanup)(void *);
+} FiTasksTimerConfig;
+
+/**
+ * Lifecycle events of a task.
+ */
+typedef enum FiTasksTaskEventType {
+    /**
+     * The task has been spawned by the event loop of its worker group.
+     */
+    FI_TASKS_TASK_EVENT_TYPE_SPAWNED = 0,
+    /**
+     * The task is about to be started by a worker.
+     */
+    FI_TASKS_TASK_EVENT_TYPE_STARTED = 1,
+    /**
+     * The task has suspended its execution, e.g., by yielding or
+     * waiting.
+     */
+    FI_TASKS_TASK_EVENT_TYPE_YIELDED = 2,
+    /**
+     * The task is about to be resumed by a worker.
+     */
+    FI_TASKS_TASK_EVENT_TYPE_RESUMED = 3,
+    /**
+     * The task has run to completion.
+     */
+    FI_TASKS_TASK_EVENT_TYPE_COMPLETED = 4,
+    /**
+     * The task has been aborted.
+     */
+    FI_TASKS_TASK_EVENT_TYPE_FAILED = 5,
+    FI_TASKS_TASK_EVENT_TYPE_FORCE32 = 0x7FFFFFFF
+} FiTasksTaskEventType;
+
+/**
+ * A lifecycle event of a task.
+ */
+typedef struct FiTasksTaskEvent {
+    /**
+     * Type of the event.
+     */
+    FiTasksTaskEventType type;
+    /**
+     * Id of the task.
+     */
+    FimoUSize task_id;
+    /**
+     * Id of the worker group of the task.
+     */
+    FimoUSize worker_group_id;
+    /**
+     * Whether the event was emitted by a worker. Is `false` for
+     * the `FI_TASKS_TASK_EVENT_TYPE_SPAWNED` event.
+     */
+    bool has_worker;
+    /**
+     * Id of the worker. Is only valid if `has_worker` is `true`.
+     */
+    FimoUSize worker_id;
+    /**
+     * Time at which the event occurred.
+     */
+    FimoTime time;
+} FiTasksTaskEvent;
+
+/**
+ * Core VTable of a `FiTasksTaskHook`.
+ */
+typedef struct FiTasksTaskHookVTableV0 {
+    void (*acquire)(void *);
+    void (*release)(void *);
+    void (*remove)(void *);
+    bool (*is_active)(void *);
+} FiTasksTaskHookVTableV0;
+
+/**
+ * VTable of a `FiTasksTaskHook`.
+ */
+typedef struct FiTasksTaskHookVTable {
+    FiTasksTaskHookVTableV0 v0;
+} FiTasksTaskHookVTable;
+
+/**
+ * A hook observing the lifecycle events of all tasks.
+ *
+ * A hook is invoked until it is removed, or the context is
+ * destroyed. Releasing the last reference to the hook does
+ * not remove it.
+ */
+typedef struct FiTasksTaskHook {
+    void *data;
+    const FiTasksTaskHookVTable *vtable;
+} FiTasksTaskHook;
+
+/**
+ * Configuration of a task hook.
+ */
+typedef struct FiTasksTaskHookConfig {
+    /**
+     * Reserved for future use.
+     * Must be `null`.
+     */
+    void *next;
+    /**
+     * Function invoked for each lifecycle event. The function
+     * may be invoked concurrently by the workers and the event
+     * loops of the worker groups, and is not run inside a task.
+     * It is invoked in the hot path of the scheduler and must
+     * therefore return quickly, and must not block. Must not be
+     * `null`.
+     */
+    void (*on_event)(void *, const FiTasksTaskEvent *);
+    /**
+     * Argument of the callback.
+     */
+    void *data;
+    /**
+     * Optional function invoked with `data` once the hook won't
+     * invoke the callback anymore.
+     */
+    void (*cleanup)(void *);
+} FiTasksTaskHookConfig;
+
+/**
+ * Shutdown behavior of a worker group.
+ *
+ * In every mode, the worker group stops accepting new commands,
+ * and the tasks that have already been started are run to
+ * completion.
+ */
+typedef enum FiTasksShutdownMode {
+    /**
+     * All enqueued command buffers are run to completion.
+     */
+    FI_TASKS_SHUTDOWN_MODE_GRACEFUL = 0,
+    /**
+     * The enqueued command buffers are aborted at the first command
+     * that has not been processed yet. Tasks that have already been
+     * spawned are run to completion.
+     */
+    FI_TASKS_SHUTDOWN_MODE_DRAIN = 1,
+    /**
+     * Like `FI_TASKS_SHUTDOWN_MODE_DRAIN`, but additionally aborts the
+     * spawned tasks that have not been started by a worker yet, and
+     * cancels the pending timers.
+     */
+    FI_TASKS_SHUTDOWN_MODE_IMMEDIATE = 2,
+    FI_TASKS_SHUTDOWN_MODE_FORCE32 = 0x7FFFFFFF
+} FiTasksShutdownMode;
+
+/**
+ * Configuration of a worker group shutdown.
+ */
+typedef struct FiTasksShutdownConfig {
+    /**
+     * Reserved for future use.
+     * Must be `null`.
+     */
+    void *next;
+    /**
+     * Shutdown behavior of the worker group.
+     */
+    FiTasksShutdownMode mode;
+    /**
+     * Optional function invoked with `data` once the worker group
+     * has shut down, i.e., once all tasks have finished and the
+     * workers have exited. May be invoked from an arbitrary thread,
+     * or immediately, if the group has already shut down.
+     */
+    void (*on_complete)(void *);
+    /**
+     * Argument of the `on_complete` function.
+     */
+    void *data;
+} FiTasksShutdownConfig;
+
+/**
+ * Destructor for a service of a worker group.
+ */
+typedef void (*FiTasksGroupServiceDtor)(void *);
+
 /**
  * A reference to a worker group.
  */
@@ -273,12 +809,92 @@ typedef struct FiTasksWorkerGroupVTableV0 {
     FimoResult (*workers)(void *, FimoUSize **, FimoUSize *);
     FimoResult (*stack_sizes)(void *, FimoUSize **, FimoUSize *);
     FimoResult (*enqueue_buffer)(void *, FiTasksCommandBuffer *, bool, FiTasksCommandBufferHandle *);
+    FimoResult (*task_times)(void *, FiTasksTaskTimes **, FimoUSize *);
+    FimoResult (*steal_stats)(void *, FiTasksWorkerGroupStealStats *);
+    FimoResult (*worker_affinity)(void *, FimoUSize, FimoUSize **, FimoUSize *);
+    FimoResult (*stats)(void *, FiTasksWorkerGroupStats *);
+    FimoResult (*worker_stats)(void *, FiTasksWorkerStats **, FimoUSize *);
+    FimoResult (*stack_stats)(void *, FiTasksStackStats **, FimoUSize *);
+    FimoResult (*create_timer)(void *, const FiTasksTimerConfig *, FiTasksTimer *);
+    FimoResult (*request_shutdown)(void *, const FiTasksShutdownConfig *);
+    FimoResult (*alloc)(void *, FimoUSize, FimoUSize, void **);
+    void (*free)(void *, void *, FimoUSize, FimoUSize);
+    FimoResult (*enumerate_tasks)(void *, FiTasksTaskInfo **, FimoUSize *);
+    FimoResult (*resize)(void *, FimoUSize);
+    FimoResult (*service_register)(void *, const char *, void *, FiTasksGroupServiceDtor);
+    FimoResult (*service_get)(void *, const char *, void **);
 } FiTasksWorkerGroupVTableV0;
 
 struct FiTasksWorkerGroupVTable {
     FiTasksWorkerGroupVTableV0 v0;
 };
 
+/**
+ * Execution state of an enqueued command buffer.
+ */
+typedef enum FiTasksCommandBufferState {
+    /**
+     * The command buffer is waiting on the completion of another
+     * command buffer, or on the signal of a semaphore.
+     */
+    FI_TASKS_COMMAND_BUFFER_STATE_BLOCKED = 0,
+    /**
+     * The commands of the command buffer are being executed.
+     */
+    FI_TASKS_COMMAND_BUFFER_STATE_RUNNING = 1,
+    /**
+     * All commands of the command buffer have been completed.
+     */
+    FI_TASKS_COMMAND_BUFFER_STATE_COMPLETED = 2,
+    /**
+     * The command buffer has been aborted, either due to an error
+     * in one of its commands, or due to the abortion of one of
+     * its dependencies.
+     */
+    FI_TASKS_COMMAND_BUFFER_STATE_ABORTED = 3,
+    FI_TASKS_COMMAND_BUFFER_STATE_FORCE32 = 0x7FFFFFFF
+} FiTasksCommandBufferState;
+
+/**
+ * Reason for which a command of a command buffer has not been
+ * executed successfully.
+ */
+typedef enum FiTasksCommandFailureReason {
+    /**
+     * The command failed, e.g., its task was aborted.
+     */
+    FI_TASKS_COMMAND_FAILURE_REASON_FAILED = 0,
+    /**
+     * The task was skipped, as the command buffer was aborted.
+     */
+    FI_TASKS_COMMAND_FAILURE_REASON_SKIPPED = 1,
+    /**
+     * The task was skipped due to the shutdown of the worker
+     * group.
+     */
+    FI_TASKS_COMMAND_FAILURE_REASON_SHUTDOWN = 2,
+    FI_TASKS_COMMAND_FAILURE_REASON_FORCE32 = 0x7FFFFFFF
+} FiTasksCommandFailureReason;
+
+/**
+ * Failed or skipped command of a command buffer.
+ */
+typedef struct FiTasksCommandFailure {
+    /**
+     * Index of the command.
+     */
+    FimoUSize index;
+    /**
+     * Reason of the failure.
+     */
+    FiTasksCommandFailureReason reason;
+    /**
+     * Index of the command that caused the abort of the command
+     * buffer. Is equal to `index` for failed commands.
+     */
+    FimoUSize cause;
+} FiTasksCommandFailure;
+
 /**
  * Core VTable of a `FiTasksCommandBufferHandle`.
  */
@@ -287,6 +903,8 @@ typedef struct FiTasksCommandBufferHandleVTableV0 {
     void (*release)(void *);
     FimoResult (*worker_group)(void *, FiTasksWorkerGroup *);
     FimoResult (*wait_on)(void *, bool *);
+    FiTasksCommandBufferState (*state)(void *);
+    FimoResult (*failures)(void *, FiTasksCommandFailure **, FimoUSize *);
 } FiTasksCommandBufferHandleVTableV0;
 
 /**
@@ -304,6 +922,51 @@ typedef struct FiTasksCommandBufferHandle {
     const FiTasksCommandBufferHandleVTable *vtable;
 } FiTasksCommandBufferHandle;
 
+/**
+ * Core VTable of a `FiTasksSemaphore`.
+ */
+typedef struct FiTasksSemaphoreVTableV0 {
+    void (*acquire)(void *);
+    void (*release)(void *);
+    FimoU64 (*value)(void *);
+    FimoResult (*signal)(void *, FimoU64);
+} FiTasksSemaphoreVTableV0;
+
+/**
+ * VTable of a `FiTasksSemaphore`.
+ */
+typedef struct FiTasksSemaphoreVTable {
+    FiTasksSemaphoreVTableV0 v0;
+} FiTasksSemaphoreVTable;
+
+/**
+ * A timeline semaphore.
+ *
+ * A semaphore contains a monotonically increasing counter, which
+ * can be signaled by command buffers or by the user. Command
+ * buffers can wait until the counter reaches some value, which
+ * allows expressing dependencies between command buffers of
+ * different worker groups.
+ */
+typedef struct FiTasksSemaphore {
+    void *data;
+    const FiTasksSemaphoreVTable *vtable;
+} FiTasksSemaphore;
+
+/**
+ * Semaphore operation of a command buffer.
+ */
+typedef struct FiTasksCommandBufferSemaphoreOperation {
+    /**
+     * Strong reference to the semaphore.
+     */
+    FiTasksSemaphore semaphore;
+    /**
+     * Counter value of the operation.
+     */
+    FimoU64 value;
+} FiTasksCommandBufferSemaphoreOperation;
+
 /**
  * Linked list of worker groups.
  */
@@ -340,25 +1003,161 @@ typedef struct FiTasksWorkerGroupConfigStack {
      * stacks. A value of `0` indicates that no residency number
      * should be targeted.
      */
-    FimoUSize residency_target;
+    FimoUSize residency_target;
+    /**
+     * Maximum number of stacks to have allocated at any given
+     * point in time. If more stacks are required, the tasks
+     * will be put on hold, until they can be acquired. This,
+     * may lead to a deadlock under some circumstances. A value
+     * of `0` indicates no upper limit to the number of resident
+     * stacks.
+     */
+    FimoUSize max_residency;
+    /**
+     * Enables the overflow protection of the stack. May require
+     * the allocation of an additional guard page for each stack.
+     * Enabling this option may marginally increase the allocation
+     * time for each stack, but is advised, as a task may otherwise
+     * overwrite foreign memory.
+     */
+    bool enable_stack_overflow_protection;
+    /**
+     * Enables the tracking of the maximum number of bytes used by
+     * the tasks. The stacks are filled with a known pattern when
+     * they are allocated, and scanned each time a task releases
+     * them. This increases the cost of allocating and releasing a
+     * stack, and commits the entire stack memory.
+     */
+    bool enable_usage_tracking;
+    /**
+     * Allows the tasks to be assigned a stack of a larger size
+     * class, instead of being put on hold, when the maximum
+     * residency is reached.
+     */
+    bool enable_spilling;
+} FiTasksWorkerGroupConfigStack;
+
+/**
+ * Policy for pinning the workers of a worker group to the cpus
+ * of the system.
+ */
+typedef enum FiTasksWorkerAffinityPolicy {
+    /**
+     * Workers are not pinned, unless an explicit cpu set is
+     * specified for the worker.
+     */
+    FI_TASKS_WORKER_AFFINITY_POLICY_NONE = 0,
+    /**
+     * Workers are pinned to a single cpu each, distributing them
+     * evenly over the available cpus.
+     */
+    FI_TASKS_WORKER_AFFINITY_POLICY_SPREAD = 1,
+    /**
+     * Workers are pinned to a single cpu each, packing them onto
+     * adjacent cpus.
+     */
+    FI_TASKS_WORKER_AFFINITY_POLICY_COMPACT = 2,
+    FI_TASKS_WORKER_AFFINITY_POLICY_FORCE32 = 0x7FFFFFFF
+} FiTasksWorkerAffinityPolicy;
+
+/**
+ * Set of cpus a worker is allowed to run on.
+ */
+typedef struct FiTasksWorkerCpuSet {
+    /**
+     * Array of cpu indices.
+     */
+    const FimoUSize *cpus;
+    /**
+     * Number of cpus in the set.
+     */
+    FimoUSize num_cpus;
+} FiTasksWorkerCpuSet;
+
+/**
+ * Affinity configuration of the workers of a worker group.
+ *
+ * The affinity is applied by each worker thread when it is
+ * started. Pinning workers is currently only supported on
+ * Linux. On other platforms, requesting any affinity fails
+ * with `FIMO_ENOTSUP`.
+ */
+typedef struct FiTasksWorkerGroupConfigAffinity {
+    /**
+     * Reserved for future use.
+     * Must be `null`.
+     */
+    void *next;
+    /**
+     * Policy used to pin the workers without an explicit cpu set.
+     */
+    FiTasksWorkerAffinityPolicy policy;
+    /**
+     * Restricts the cpus available to the policy to the ones of
+     * the given NUMA node. A negative value does not restrict
+     * the cpus.
+     */
+    FimoISize numa_node;
+    /**
+     * Explicit cpu sets of the workers. The i-th set applies to
+     * the i-th worker and takes precedence over the policy. An
+     * empty set falls back to the policy. May be `null`, if
+     * `num_worker_cpus` is `0`.
+     */
+    const FiTasksWorkerCpuSet *worker_cpus;
+    /**
+     * Number of explicit cpu sets. Must not exceed the number of
+     * workers.
+     */
+    FimoUSize num_worker_cpus;
+} FiTasksWorkerGroupConfigAffinity;
+
+/**
+ * Allocation hooks of a worker group.
+ *
+ * The hooks serve the allocations whose lifetime is bound to the
+ * worker group, i.e., the allocations performed through the
+ * `alloc` function of the worker group, which can be used for
+ * command buffers and the private data of the tasks. They allow
+ * serving the allocations from an arena bound to the NUMA node of
+ * the worker group. Small allocations are rounded up to a size
+ * class, and freed blocks are cached by the worker group, so the
+ * hooks are invoked with the size and alignment of the class. The
+ * hooks may be invoked from any thread.
+ */
+typedef struct FiTasksWorkerGroupConfigAllocator {
+    /**
+     * Reserved for future use.
+     * Must be `null`.
+     */
+    void *next;
+    /**
+     * Custom data passed to the hooks.
+     */
+    void *data;
+    /**
+     * Allocates a block of memory of the given size and alignment.
+     * The last argument is the NUMA node of the worker group, or
+     * a negative value, if the worker group is not bound to a NUMA
+     * node. Returns `null` on failure.
+     *
+     * Must not be `null`.
+     */
+    void *(*alloc)(void *data, FimoUSize size, FimoUSize alignment, FimoISize numa_node);
     /**
-     * Maximum number of stacks to have allocated at any given
-     * point in time. If more stacks are required, the tasks
-     * will be put on hold, until they can be acquired. This,
-     * may lead to a deadlock under some circumstances. A value
-     * of `0` indicates no upper limit to the number of resident
-     * stacks.
+     * Frees a block of memory allocated with `alloc`, along with
+     * the size and alignment it was allocated with.
+     *
+     * Must not be `null`.
      */
-    FimoUSize max_residency;
+    void (*free)(void *data, void *ptr, FimoUSize size, FimoUSize alignment);
     /**
-     * Enables the overflow protection of the stack. May require
-     * the allocation of an additional guard page for each stack.
-     * Enabling this option may marginally increase the allocation
-     * time for each stack, but is advised, as a task may otherwise
-     * overwrite foreign memory.
+     * Invoked once the worker group is destroyed, after all blocks
+     * have been freed. Is not invoked, if the creation of the
+     * worker group fails. May be `null`.
      */
-    bool enable_stack_overflow_protection;
-} FiTasksWorkerGroupConfigStack;
+    void (*on_drop)(void *data);
+} FiTasksWorkerGroupConfigAllocator;
 
 /**
  * Configuration structure for the creation of worker groups.
@@ -395,6 +1194,18 @@ typedef struct FiTasksWorkerGroupConfig {
      * present in the system.
      */
     FimoUSize number_of_workers;
+    /**
+     * Maximum number of tasks that an idle worker steals at once
+     * from the global queue or from the queue of another worker.
+     * A value of `0` indicates to use the default batch size of
+     * the runtime.
+     */
+    FimoUSize steal_batch_size;
+    /**
+     * Affinity of the worker threads. A value of `null` does not
+     * pin the workers to any cpu.
+     */
+    const FiTasksWorkerGroupConfigAffinity *affinity;
     /**
      * Indicates whether to make a reference to the new worker
      * group queryable through the task context. Specifying this
@@ -402,8 +1213,83 @@ typedef struct FiTasksWorkerGroupConfig {
      * group through its id.
      */
     bool is_queryable;
+    /**
+     * Indicates whether to record the allocations performed by
+     * the tasks with the default allocator. The allocations are
+     * recorded by installing per-task counters on the worker,
+     * each time a task is resumed, and are reported by the
+     * `task_times` function. Enabling this option adds a small
+     * overhead to each allocation.
+     */
+    bool track_allocations;
+    /**
+     * Execution budget of a task, each time it is resumed by a
+     * worker. Once the budget is exhausted, the task yields at
+     * the next call to `checkpoint`. A duration of `0` uses the
+     * default budget of the runtime.
+     */
+    FimoDuration time_slice;
+    /**
+     * Allocation hooks of the worker group. A value of `null` uses
+     * the default allocator of the fimo library.
+     */
+    const FiTasksWorkerGroupConfigAllocator *allocator;
 } FiTasksWorkerGroupConfig;
 
+/**
+ * Scheduling priority of a task.
+ *
+ * Tasks with a higher priority are dispatched before tasks
+ * with a lower priority. To prevent starvation, the runtime
+ * periodically dispatches tasks of lower priorities, that
+ * have been passed over for too long.
+ */
+typedef enum FiTasksTaskPriority {
+    /**
+     * Background work, which may be delayed.
+     */
+    FI_TASKS_TASK_PRIORITY_LOW = 0,
+    /**
+     * Default priority of a task.
+     */
+    FI_TASKS_TASK_PRIORITY_NORMAL = 1,
+    /**
+     * Work that should be dispatched before the normal tasks.
+     */
+    FI_TASKS_TASK_PRIORITY_HIGH = 2,
+    /**
+     * Latency critical work.
+     */
+    FI_TASKS_TASK_PRIORITY_CRITICAL = 3,
+    FI_TASKS_TASK_PRIORITY_FORCE32 = 0x7FFFFFFF
+} FiTasksTaskPriority;
+
+/**
+ * Reaction of a command buffer to the failure of one of its
+ * tasks.
+ */
+typedef enum FiTasksFailurePolicy {
+    /**
+     * Aborts the command buffer. The commands that have not been
+     * processed yet are skipped, while the tasks that are already
+     * running are run to completion.
+     */
+    FI_TASKS_FAILURE_POLICY_ABORT_ALL = 0,
+    /**
+     * Aborts the command buffer once it reaches a command that
+     * depends on the failed task, i.e., a barrier, a semaphore
+     * signal, or the end of the buffer. The commands preceding
+     * it are processed as usual.
+     */
+    FI_TASKS_FAILURE_POLICY_ABORT_DEPENDENTS = 1,
+    /**
+     * Ignores the failure of the task. The command buffer
+     * continues processing its commands.
+     */
+    FI_TASKS_FAILURE_POLICY_CONTINUE = 2,
+    FI_TASKS_FAILURE_POLICY_FORCE32 = 0x7FFFFFFF
+} FiTasksFailurePolicy;
+
 /**
  * Type for an entry in a command buffer.
  */
@@ -441,6 +1327,43 @@ typedef enum FiTasksCommandBufferEntryType {
      * tasks.
      */
     FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_SET_STACK_SIZE = 5,
+    /**
+     * Assigns a tag to the following tasks.
+     *
+     * The runtime aggregates the execution times of all
+     * tasks with the same tag. A tag of `0` marks the
+     * tasks as untagged.
+     */
+    FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_SET_TAG = 6,
+    /**
+     * Assigns a scheduling priority to the following tasks.
+     *
+     * Tasks are spawned with the
+     * `FI_TASKS_TASK_PRIORITY_NORMAL` priority by default.
+     */
+    FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_SET_PRIORITY = 7,
+    /**
+     * A semaphore synchronization command.
+     *
+     * Synchronizes the following commands with the counter of
+     * a semaphore reaching the specified value.
+     */
+    FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_WAIT_SEMAPHORE = 8,
+    /**
+     * Signals a semaphore.
+     *
+     * Sets the counter of the semaphore to the specified value,
+     * once all preceding commands have been completed. The value
+     * must be greater than the current value of the counter.
+     */
+    FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_SIGNAL_SEMAPHORE = 9,
+    /**
+     * Assigns a failure policy to the following tasks.
+     *
+     * Tasks are spawned with the
+     * `FI_TASKS_FAILURE_POLICY_ABORT_ALL` policy by default.
+     */
+    FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_SET_FAILURE_POLICY = 10,
     FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_FORCE32 = 0x7FFFFFFF
 } FiTasksCommandBufferEntryType;
 
@@ -472,6 +1395,26 @@ typedef union FiTasksCommandBufferEntryData {
      * Stack size.
      */
     FimoUSize set_stack_size;
+    /**
+     * Task tag.
+     */
+    FimoUSize set_tag;
+    /**
+     * Task priority.
+     */
+    FiTasksTaskPriority set_priority;
+    /**
+     * Failure policy.
+     */
+    FiTasksFailurePolicy set_failure_policy;
+    /**
+     * Semaphore and value to wait for.
+     */
+    FiTasksCommandBufferSemaphoreOperation wait_semaphore;
+    /**
+     * Semaphore and value to signal.
+     */
+    FiTasksCommandBufferSemaphoreOperation signal_semaphore;
 } FiTasksCommandBufferEntryData;
 
 struct FiTasksCommandBufferEntry {
@@ -620,6 +1563,12 @@ typedef struct FiTasksVTableV0 {
                                 FiTasksUnparkResult *);
     FimoResult (*unpark_filter)(void *, const void *, FiTasksUnparkFilterOp (*)(void *, const void *), void *,
                                const void *(*)(void *, FiTasksUnparkResult), void *, FiTasksUnparkResult *);
+    FimoResult (*task_priority)(void *, FiTasksTaskPriority *);
+    FimoResult (*create_semaphore)(void *, FimoU64, FiTasksSemaphore *);
+    FimoResult (*spawn_blocking)(void *, void (*)(void *), void *);
+    FimoResult (*sleep_until)(void *, FimoTime);
+    FimoResult (*register_task_hook)(void *, const FiTasksTaskHookConfig *, FiTasksTaskHook *);
+    FimoResult (*time_slice_exhausted)(void *, bool *);
 } FiTasksVTableV0;
 
 struct FiTasksVTable {
@@ -627,160 +1576,442 @@ struct FiTasksVTable {
 };
 
 /**
- * Returns the unique id of the worker group.
+ * Returns the unique id of the worker group.
+ *
+ * @param grp worker group
+ *
+ * @return Worker group id
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS FimoUSize fi_tasks_worker_group_id(FiTasksWorkerGroup grp) {
+    return grp.vtable->v0.id(grp.data);
+}
+
+/**
+ * Acquires a strong reference to the worker group.
+ *
+ * @param grp worker group
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS void fi_tasks_worker_group_acquire(FiTasksWorkerGroup grp) {
+    grp.vtable->v0.acquire(grp.data);
+}
+
+/**
+ * Releases a strong reference to the worker group.
+ *
+ * @param grp worker group
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS void fi_tasks_worker_group_release(FiTasksWorkerGroup grp) {
+    grp.vtable->v0.release(grp.data);
+}
+
+/**
+ * Checks whether the worker group is open to receive new commands.
+ *
+ * @param grp worker group
+ *
+ * @return `true` if new commands can be enqueued to the worker group.
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS bool fi_tasks_worker_group_is_open(FiTasksWorkerGroup grp) {
+    return grp.vtable->v0.is_open(grp.data);
+}
+
+/**
+ * Checks whether the current thread is a worker thread of the group.
+ *
+ * @param grp worker group
+ *
+ * @return `true` if the current thread is a worker thread of the group.
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS bool fi_tasks_worker_group_is_worker(FiTasksWorkerGroup grp) {
+    return grp.vtable->v0.is_worker(grp.data);
+}
+
+/**
+ * Returns the name of the worker.
+ *
+ * The returned reference is guaranteed to be valid for as long as the
+ * caller owns a strong reference to the worker group.
+ *
+ * @param grp worker group
+ *
+ * @return Worker group name
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS const char *fi_tasks_worker_group_name(FiTasksWorkerGroup grp) {
+    return grp.vtable->v0.name(grp.data);
+}
+
+/**
+ * Requests that the worker group stops accepting new commands.
+ *
+ * If successful, the worker group will close its side of the
+ * channel and stop accepting new commands. The currently enqueued
+ * commands will be run to completion.
+ *
+ * @param grp worker group
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_worker_group_request_close(FiTasksWorkerGroup grp) {
+    return grp.vtable->v0.request_close(grp.data);
+}
+
+/**
+ * Fetches a list of worker ids available in the worker group.
+ *
+ * If the `workers` parameter is `null`, this function will only
+ * return the number of workers in the group without allocating
+ * any memory. Otherwise, `workers` will be set to point to an
+ * array allocated by `fimo_malloc` and must be deallocated by
+ * the caller.
+ *
+ * @param grp worker group
+ * @param workers array of workers ids
+ * @param count worker count
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_worker_group_workers(FiTasksWorkerGroup grp, FimoUSize **workers,
+                                                                  FimoUSize *count) {
+    return grp.vtable->v0.workers(grp.data, workers, count);
+}
+
+/**
+ * Fetches a list of stack sizes available in the worker group.
+ *
+ * When spawning new tasks, they will be assigned one stack which
+ * matches the requirements specified in the commands.
+ *
+ * If the `stack_sizes` parameter is `null`, this function will
+ * only return the number of stack sizes in the group without
+ * allocating any memory. Otherwise, `workers` will be set to
+ * point to an array allocated by `fimo_malloc` and must be
+ * deallocated by the caller.
+ *
+ * @param grp worker group
+ * @param stack_sizes array of stack sizes
+ * @param count worker count
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_worker_group_stack_sizes(FiTasksWorkerGroup grp, FimoUSize **stack_sizes,
+                                                                      FimoUSize *count) {
+    return grp.vtable->v0.stack_sizes(grp.data, stack_sizes, count);
+}
+
+/**
+ * Enqueues a command buffer to the scheduler of the worker group.
+ *
+ * On success, the command buffer `buffer` will be owned by the
+ * worker group, which will start executing the contained commands
+ * in parallel. The buffer must remain alive until the worker group
+ * relinquishes its ownership, which happens after the completion
+ * and detaching of the buffer. The caller may specify whether to
+ * enqueue the buffer in a detached state by setting the `detached`
+ * parameter to `true`, in which case `handle` will be set to `null`.
+ *
+ * @param grp worker group
+ * @param buffer command buffer to enqueue
+ * @param detached whether to enqueue the buffer in a detached state
+ * @param handle resulting handle to the enqueued buffer
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_worker_group_enqueue_buffer(FiTasksWorkerGroup grp,
+                                                                         FiTasksCommandBuffer *buffer, bool detached,
+                                                                         FiTasksCommandBufferHandle *handle) {
+    return grp.vtable->v0.enqueue_buffer(grp.data, buffer, detached, handle);
+}
+
+/**
+ * Fetches the accumulated execution times of the tasks spawned
+ * in the worker group, grouped by their tag.
+ *
+ * Only the tasks that have already finished are accounted for.
+ * On success, `times` will be set to point to an array allocated
+ * by `fimo_malloc` and must be deallocated by the caller.
+ *
+ * @param grp worker group
+ * @param times array of accumulated task times
+ * @param count number of tags
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_worker_group_task_times(FiTasksWorkerGroup grp, FiTasksTaskTimes **times,
+                                                                     FimoUSize *count) {
+    return grp.vtable->v0.task_times(grp.data, times, count);
+}
+
+/**
+ * Fetches the work stealing statistics of the worker group.
+ *
+ * @param grp worker group
+ * @param stats resulting statistics
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_worker_group_steal_stats(FiTasksWorkerGroup grp,
+                                                                      FiTasksWorkerGroupStealStats *stats) {
+    return grp.vtable->v0.steal_stats(grp.data, stats);
+}
+
+/**
+ * Fetches the effective cpu affinity of a worker of the worker group.
+ *
+ * On success, `cpus` will be set to point to an array allocated
+ * by `fimo_malloc` containing the sorted indices of the cpus the
+ * worker is allowed to run on, and must be deallocated by the
+ * caller. On platforms where the affinity can not be queried,
+ * the function fails with `FIMO_ENOTSUP`.
+ *
+ * @param grp worker group
+ * @param worker worker id
+ * @param cpus array of cpu indices
+ * @param count number of cpus
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_worker_group_worker_affinity(FiTasksWorkerGroup grp, FimoUSize worker,
+                                                                          FimoUSize **cpus, FimoUSize *count) {
+    return grp.vtable->v0.worker_affinity(grp.data, worker, cpus, count);
+}
+
+/**
+ * Fetches the scheduler statistics of the worker group.
  *
  * @param grp worker group
+ * @param stats resulting statistics
  *
- * @return Worker group id
+ * @return Status code.
  */
 FIMO_MUST_USE
-static FIMO_INLINE_ALWAYS FimoUSize fi_tasks_worker_group_id(FiTasksWorkerGroup grp) {
-    return grp.vtable->v0.id(grp.data);
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_worker_group_stats(FiTasksWorkerGroup grp,
+                                                                FiTasksWorkerGroupStats *stats) {
+    return grp.vtable->v0.stats(grp.data, stats);
 }
 
 /**
- * Acquires a strong reference to the worker group.
+ * Fetches the scheduler statistics of the workers of the worker
+ * group.
+ *
+ * On success, `stats` will be set to point to an array allocated
+ * by `fimo_malloc`, sorted by the worker id, and must be
+ * deallocated by the caller.
  *
  * @param grp worker group
+ * @param stats array of worker statistics
+ * @param count number of workers
+ *
+ * @return Status code.
  */
 FIMO_MUST_USE
-static FIMO_INLINE_ALWAYS void fi_tasks_worker_group_acquire(FiTasksWorkerGroup grp) {
-    grp.vtable->v0.acquire(grp.data);
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_worker_group_worker_stats(FiTasksWorkerGroup grp,
+                                                                       FiTasksWorkerStats **stats, FimoUSize *count) {
+    return grp.vtable->v0.worker_stats(grp.data, stats, count);
 }
 
 /**
- * Releases a strong reference to the worker group.
+ * Fetches the usage statistics of the stacks of the worker group.
+ *
+ * On success, `stats` will be set to point to an array allocated
+ * by `fimo_malloc`, containing one entry for each stack size,
+ * sorted by the size, and must be deallocated by the caller.
  *
  * @param grp worker group
+ * @param stats array of stack statistics
+ * @param count number of stack sizes
+ *
+ * @return Status code.
  */
 FIMO_MUST_USE
-static FIMO_INLINE_ALWAYS void fi_tasks_worker_group_release(FiTasksWorkerGroup grp) {
-    grp.vtable->v0.release(grp.data);
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_worker_group_stack_stats(FiTasksWorkerGroup grp,
+                                                                      FiTasksStackStats **stats, FimoUSize *count) {
+    return grp.vtable->v0.stack_stats(grp.data, stats, count);
 }
 
 /**
- * Checks whether the worker group is open to receive new commands.
+ * Schedules a timer on the event loop of the worker group.
+ *
+ * The callback of the timer is invoked by the event loop of the
+ * worker group once the deadline has been reached. Periodic timers
+ * are invoked until they are cancelled, or until the worker group
+ * is closed. The timer does not keep the worker group alive.
  *
  * @param grp worker group
+ * @param cfg timer configuration
+ * @param timer resulting timer
  *
- * @return `true` if new commands can be enqueued to the worker group.
+ * @return Status code.
  */
 FIMO_MUST_USE
-static FIMO_INLINE_ALWAYS bool fi_tasks_worker_group_is_open(FiTasksWorkerGroup grp) {
-    return grp.vtable->v0.is_open(grp.data);
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_worker_group_create_timer(FiTasksWorkerGroup grp,
+                                                                       const FiTasksTimerConfig *cfg,
+                                                                       FiTasksTimer *timer) {
+    return grp.vtable->v0.create_timer(grp.data, cfg, timer);
 }
 
 /**
- * Checks whether the current thread is a worker thread of the group.
+ * Requests the shutdown of the worker group.
+ *
+ * Like `fi_tasks_worker_group_request_close`, the worker group
+ * stops accepting new commands. The handling of the enqueued
+ * commands depends on the requested mode. A shutdown may be
+ * requested multiple times, in which case the most aggressive of
+ * the requested modes is applied. If successful, the `on_complete`
+ * function is invoked exactly once, otherwise it is not invoked.
  *
  * @param grp worker group
+ * @param cfg shutdown configuration
  *
- * @return `true` if the current thread is a worker thread of the group.
+ * @return Status code.
  */
 FIMO_MUST_USE
-static FIMO_INLINE_ALWAYS bool fi_tasks_worker_group_is_worker(FiTasksWorkerGroup grp) {
-    return grp.vtable->v0.is_worker(grp.data);
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_worker_group_request_shutdown(FiTasksWorkerGroup grp,
+                                                                           const FiTasksShutdownConfig *cfg) {
+    return grp.vtable->v0.request_shutdown(grp.data, cfg);
 }
 
 /**
- * Returns the name of the worker.
+ * Allocates memory from the allocator of the worker group.
  *
- * The returned reference is guaranteed to be valid for as long as the
- * caller owns a strong reference to the worker group.
+ * The memory is allocated with the allocation hooks of the worker
+ * group, or with the default allocator, if the worker group does
+ * not specify any hooks. The memory must be freed with
+ * `fi_tasks_worker_group_free`, before the last reference to the
+ * worker group is released.
  *
  * @param grp worker group
+ * @param size size of the allocation, must not be `0`
+ * @param alignment alignment of the allocation, must be a power of two
+ * @param ptr resulting allocation
  *
- * @return Worker group name
+ * @return Status code.
  */
 FIMO_MUST_USE
-static FIMO_INLINE_ALWAYS const char *fi_tasks_worker_group_name(FiTasksWorkerGroup grp) {
-    return grp.vtable->v0.name(grp.data);
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_worker_group_alloc(FiTasksWorkerGroup grp, FimoUSize size,
+                                                                FimoUSize alignment, void **ptr) {
+    return grp.vtable->v0.alloc(grp.data, size, alignment, ptr);
 }
 
 /**
- * Requests that the worker group stops accepting new commands.
+ * Frees memory allocated with `fi_tasks_worker_group_alloc`.
  *
- * If successful, the worker group will close its side of the
- * channel and stop accepting new commands. The currently enqueued
- * commands will be run to completion.
+ * @param grp worker group
+ * @param ptr allocation to free
+ * @param size size of the allocation
+ * @param alignment alignment of the allocation
+ */
+static FIMO_INLINE_ALWAYS void fi_tasks_worker_group_free(FiTasksWorkerGroup grp, void *ptr, FimoUSize size,
+                                                          FimoUSize alignment) {
+    grp.vtable->v0.free(grp.data, ptr, size, alignment);
+}
+
+/**
+ * Enumerates the tasks of the worker group that have not finished yet.
+ *
+ * Tasks that are waiting for the allocation of a stack have not
+ * been spawned yet, and are therefore not included. On success,
+ * `infos` will be set to point to an array allocated by
+ * `fimo_malloc`, sorted by the task id, and must be deallocated
+ * by the caller. The labels of the tasks are stored in the same
+ * allocation. If the group has no tasks, `infos` is set to `NULL`.
  *
  * @param grp worker group
+ * @param infos array of task snapshots
+ * @param count number of tasks
  *
  * @return Status code.
  */
 FIMO_MUST_USE
-static FIMO_INLINE_ALWAYS FimoResult fi_tasks_worker_group_request_close(FiTasksWorkerGroup grp) {
-    return grp.vtable->v0.request_close(grp.data);
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_worker_group_enumerate_tasks(FiTasksWorkerGroup grp,
+                                                                          FiTasksTaskInfo **infos,
+                                                                          FimoUSize *count) {
+    return grp.vtable->v0.enumerate_tasks(grp.data, infos, count);
 }
 
 /**
- * Fetches a list of worker ids available in the worker group.
+ * Changes the number of workers of the worker group.
  *
- * If the `workers` parameter is `null`, this function will only
- * return the number of workers in the group without allocating
- * any memory. Otherwise, `workers` will be set to point to an
- * array allocated by `fimo_malloc` and must be deallocated by
- * the caller.
+ * The request is processed asynchronously by the worker group.
+ * Additional workers are spawned immediately, and start stealing
+ * tasks from the queues of the group. Superfluous workers, starting
+ * with the most recently spawned ones, retire gracefully: a retiring
+ * worker moves the tasks it has not started yet to the global queue,
+ * stops accepting new tasks and exits once the tasks it has already
+ * started have finished. Retiring workers can no longer be selected
+ * by the commands of a command buffer. The workers spawned by a
+ * resize are not pinned to any cpus, and are assigned new ids.
  *
  * @param grp worker group
- * @param workers array of workers ids
- * @param count worker count
+ * @param num_workers new number of workers
  *
  * @return Status code.
  */
 FIMO_MUST_USE
-static FIMO_INLINE_ALWAYS FimoResult fi_tasks_worker_group_workers(FiTasksWorkerGroup grp, FimoUSize **workers,
-                                                                  FimoUSize *count) {
-    return grp.vtable->v0.workers(grp.data, workers, count);
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_worker_group_resize(FiTasksWorkerGroup grp, FimoUSize num_workers) {
+    return grp.vtable->v0.resize(grp.data, num_workers);
 }
 
 /**
- * Fetches a list of stack sizes available in the worker group.
+ * Registers a service with the worker group.
  *
- * When spawning new tasks, they will be assigned one stack which
- * matches the requirements specified in the commands.
+ * A service is an object shared by all tasks of the worker group,
+ * which is identified by a unique name. Once registered, the service
+ * is owned by the worker group, and can be acquired by any task of
+ * the group through `fi_tasks_worker_group_service_get`. The services
+ * are destroyed once the worker group has shut down, i.e., after all
+ * of its tasks have completed, in the reverse order of their
+ * registration. The destructor is invoked on an unspecified thread,
+ * and may not call into the worker group. On failure, the ownership
+ * of the service remains with the caller.
  *
- * If the `stack_sizes` parameter is `null`, this function will
- * only return the number of stack sizes in the group without
- * allocating any memory. Otherwise, `workers` will be set to
- * point to an array allocated by `fimo_malloc` and must be
- * deallocated by the caller.
+ * Fails if a service with the same name is already registered, or
+ * if the services of the worker group have already been destroyed.
  *
  * @param grp worker group
- * @param stack_sizes array of stack sizes
- * @param count worker count
+ * @param name unique name of the service
+ * @param service pointer to the service
+ * @param dtor optional destructor function
  *
  * @return Status code.
  */
 FIMO_MUST_USE
-static FIMO_INLINE_ALWAYS FimoResult fi_tasks_worker_group_stack_sizes(FiTasksWorkerGroup grp, FimoUSize **stack_sizes,
-                                                                      FimoUSize *count) {
-    return grp.vtable->v0.stack_sizes(grp.data, stack_sizes, count);
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_worker_group_service_register(FiTasksWorkerGroup grp, const char *name,
+                                                                           void *service,
+                                                                           FiTasksGroupServiceDtor dtor) {
+    return grp.vtable->v0.service_register(grp.data, name, service, dtor);
 }
 
 /**
- * Enqueues a command buffer to the scheduler of the worker group.
+ * Acquires a service registered with the worker group.
  *
- * On success, the command buffer `buffer` will be owned by the
- * worker group, which will start executing the contained commands
- * in parallel. The buffer must remain alive until the worker group
- * relinquishes its ownership, which happens after the completion
- * and detaching of the buffer. The caller may specify whether to
- * enqueue the buffer in a detached state by setting the `detached`
- * parameter to `true`, in which case `handle` will be set to `null`.
+ * The service remains valid until the worker group has shut down,
+ * and can therefore be used for the duration of any task of the
+ * worker group. Fails if no service with the name `name` is
+ * registered.
  *
  * @param grp worker group
- * @param buffer command buffer to enqueue
- * @param detached whether to enqueue the buffer in a detached state
- * @param handle resulting handle to the enqueued buffer
+ * @param name name of the service
+ * @param service pointer to the service
  *
  * @return Status code.
  */
 FIMO_MUST_USE
-static FIMO_INLINE_ALWAYS FimoResult fi_tasks_worker_group_enqueue_buffer(FiTasksWorkerGroup grp,
-                                                                         FiTasksCommandBuffer *buffer, bool detached,
-                                                                         FiTasksCommandBufferHandle *handle) {
-    return grp.vtable->v0.enqueue_buffer(grp.data, buffer, detached, handle);
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_worker_group_service_get(FiTasksWorkerGroup grp, const char *name,
+                                                                      void **service) {
+    return grp.vtable->v0.service_get(grp.data, name, service);
 }
 
 /**
@@ -824,7 +2055,7 @@ static FIMO_INLINE_ALWAYS FimoResult fi_tasks_command_buffer_worker_group(FiTask
  * If provided, `error` is set to whether the command buffer could
  * be executed completely.
  *
- * May only be called in a task running in the same worker group.
+ * May only be called in a task.
  *
  * @param handle handle to the command buffer
  * @param error optional success status of the command buffer
@@ -836,6 +2067,169 @@ static FIMO_INLINE_ALWAYS FimoResult fi_tasks_command_buffer_wait_on(FiTasksComm
     return handle.vtable->v0.wait_on(handle.data, error);
 }
 
+/**
+ * Returns the execution state of the command buffer.
+ *
+ * A command buffer is reported as aborted, if it, or any of the
+ * command buffers it depends on, has been aborted.
+ *
+ * @param handle buffer handle
+ *
+ * @return Execution state.
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS FiTasksCommandBufferState fi_tasks_command_buffer_state(FiTasksCommandBufferHandle handle) {
+    return handle.vtable->v0.state(handle.data);
+}
+
+/**
+ * Fetches the commands of the command buffer that have failed or
+ * have been skipped so far.
+ *
+ * On success, `failures` will be set to point to an array
+ * allocated by `fimo_malloc`, sorted by the index of the command,
+ * and must be deallocated by the caller. If no command failed,
+ * `failures` is set to `NULL`.
+ *
+ * @param handle buffer handle
+ * @param failures array of failed commands
+ * @param count number of failed commands
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_command_buffer_failures(FiTasksCommandBufferHandle handle,
+                                                                     FiTasksCommandFailure **failures,
+                                                                     FimoUSize *count) {
+    return handle.vtable->v0.failures(handle.data, failures, count);
+}
+
+/**
+ * Acquires a strong reference to the semaphore.
+ *
+ * @param semaphore semaphore
+ */
+static FIMO_INLINE_ALWAYS void fi_tasks_semaphore_acquire(FiTasksSemaphore semaphore) {
+    semaphore.vtable->v0.acquire(semaphore.data);
+}
+
+/**
+ * Releases a strong reference to the semaphore.
+ *
+ * @param semaphore semaphore
+ */
+static FIMO_INLINE_ALWAYS void fi_tasks_semaphore_release(FiTasksSemaphore semaphore) {
+    semaphore.vtable->v0.release(semaphore.data);
+}
+
+/**
+ * Returns the current counter value of the semaphore.
+ *
+ * @param semaphore semaphore
+ *
+ * @return Counter value.
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS FimoU64 fi_tasks_semaphore_value(FiTasksSemaphore semaphore) {
+    return semaphore.vtable->v0.value(semaphore.data);
+}
+
+/**
+ * Sets the counter of the semaphore to `value`.
+ *
+ * Resumes all command buffers waiting on a value less than or
+ * equal to `value`. The value must be greater than the current
+ * counter value.
+ *
+ * @param semaphore semaphore
+ * @param value new counter value
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_semaphore_signal(FiTasksSemaphore semaphore, FimoU64 value) {
+    return semaphore.vtable->v0.signal(semaphore.data, value);
+}
+
+/**
+ * Acquires a strong reference to the timer.
+ *
+ * @param timer timer
+ */
+static FIMO_INLINE_ALWAYS void fi_tasks_timer_acquire(FiTasksTimer timer) { timer.vtable->v0.acquire(timer.data); }
+
+/**
+ * Releases a strong reference to the timer.
+ *
+ * Releasing the last reference does not cancel the timer.
+ *
+ * @param timer timer
+ */
+static FIMO_INLINE_ALWAYS void fi_tasks_timer_release(FiTasksTimer timer) { timer.vtable->v0.release(timer.data); }
+
+/**
+ * Cancels the timer.
+ *
+ * After this call the callback of the timer won't be invoked anymore,
+ * but an invocation started before the call may still be running.
+ * May be called from inside the callback.
+ *
+ * @param timer timer
+ */
+static FIMO_INLINE_ALWAYS void fi_tasks_timer_cancel(FiTasksTimer timer) { timer.vtable->v0.cancel(timer.data); }
+
+/**
+ * Returns whether the timer may still invoke its callback.
+ *
+ * @param timer timer
+ *
+ * @return `true` if the timer is active.
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS bool fi_tasks_timer_is_active(FiTasksTimer timer) {
+    return timer.vtable->v0.is_active(timer.data);
+}
+
+/**
+ * Acquires a strong reference to the task hook.
+ *
+ * @param hook task hook
+ */
+static FIMO_INLINE_ALWAYS void fi_tasks_task_hook_acquire(FiTasksTaskHook hook) { hook.vtable->v0.acquire(hook.data); }
+
+/**
+ * Releases a strong reference to the task hook.
+ *
+ * Releasing the last reference does not remove the hook.
+ *
+ * @param hook task hook
+ */
+static FIMO_INLINE_ALWAYS void fi_tasks_task_hook_release(FiTasksTaskHook hook) { hook.vtable->v0.release(hook.data); }
+
+/**
+ * Removes the task hook.
+ *
+ * After this call the callback of the hook won't be invoked
+ * for new events anymore, but an invocation started before
+ * the call may still be running. May be called from inside
+ * the callback.
+ *
+ * @param hook task hook
+ */
+static FIMO_INLINE_ALWAYS void fi_tasks_task_hook_remove(FiTasksTaskHook hook) { hook.vtable->v0.remove(hook.data); }
+
+/**
+ * Returns whether the task hook is still registered.
+ *
+ * @param hook task hook
+ *
+ * @return `true` if the hook is active.
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS bool fi_tasks_task_hook_is_active(FiTasksTaskHook hook) {
+    return hook.vtable->v0.is_active(hook.data);
+}
+
 /**
  * Returns whether the current thread is a worker thread,
  * managed by some worker group the context owns.
@@ -865,6 +2259,100 @@ static FIMO_INLINE_ALWAYS FimoResult fi_tasks_ctx_task_id(FiTasksContext ctx, Fi
     return ctx.vtable->v0.task_id(ctx.data, id);
 }
 
+/**
+ * Returns the scheduling priority of the current task.
+ *
+ * May only be called in a task.
+ *
+ * @param ctx context
+ * @param priority task priority
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_ctx_task_priority(FiTasksContext ctx, FiTasksTaskPriority *priority) {
+    return ctx.vtable->v0.task_priority(ctx.data, priority);
+}
+
+/**
+ * Creates a new timeline semaphore.
+ *
+ * @param ctx context
+ * @param initial_value initial counter value
+ * @param semaphore resulting semaphore
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_ctx_create_semaphore(FiTasksContext ctx, FimoU64 initial_value,
+                                                                  FiTasksSemaphore *semaphore) {
+    return ctx.vtable->v0.create_semaphore(ctx.data, initial_value, semaphore);
+}
+
+/**
+ * Runs a function on the blocking thread pool.
+ *
+ * The blocking thread pool consists of a dynamically sized set of
+ * threads, which are not part of any worker group, and is intended
+ * for long-running synchronous operations, like file io, which would
+ * otherwise block a worker. The function is invoked exactly once with
+ * `data` as its argument. Since the function is not run inside a task,
+ * it must not call any function that may only be called in a task.
+ * Completion of the function must be signaled by the function itself,
+ * e.g., with a semaphore.
+ *
+ * May be called from any thread.
+ *
+ * @param ctx context
+ * @param func function to invoke
+ * @param data argument of the function
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_ctx_spawn_blocking(FiTasksContext ctx, void (*func)(void *),
+                                                                void *data) {
+    return ctx.vtable->v0.spawn_blocking(ctx.data, func, data);
+}
+
+/**
+ * Puts the current task to sleep until the specified time.
+ *
+ * The task is resumed by the event loop of its worker group once
+ * the time has been reached. A time in the past returns immediately.
+ * May only be called in a task.
+ *
+ * @param ctx context
+ * @param time wakeup time
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_ctx_sleep_until(FiTasksContext ctx, FimoTime time) {
+    return ctx.vtable->v0.sleep_until(ctx.data, time);
+}
+
+/**
+ * Registers a hook observing the lifecycle events of all tasks.
+ *
+ * The callback of the hook is invoked when a task is spawned,
+ * started, yielded, resumed, completed or aborted, until the hook
+ * is removed. While no hook is registered, the events are not
+ * constructed. May be called from any thread.
+ *
+ * @param ctx context
+ * @param cfg hook configuration
+ * @param hook resulting hook
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_ctx_register_task_hook(FiTasksContext ctx,
+                                                                    const FiTasksTaskHookConfig *cfg,
+                                                                    FiTasksTaskHook *hook) {
+    return ctx.vtable->v0.register_task_hook(ctx.data, cfg, hook);
+}
+
 /**
  * Returns the id of the current worker.
  *
@@ -981,6 +2469,46 @@ static FIMO_INLINE_ALWAYS FimoResult fi_tasks_ctx_create_worker_group(FiTasksCon
 FIMO_MUST_USE
 static FIMO_INLINE_ALWAYS FimoResult fi_tasks_ctx_yield(FiTasksContext ctx) { return ctx.vtable->v0.yield(ctx.data); }
 
+/**
+ * Checks whether the current task has exhausted its time slice.
+ *
+ * Each call is recorded as a checkpoint of the task. Unlike
+ * `fi_tasks_ctx_checkpoint`, the task is not yielded.
+ * May only be called in a task.
+ *
+ * @param ctx context
+ * @param exhausted whether the time slice is exhausted
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_ctx_time_slice_exhausted(FiTasksContext ctx, bool *exhausted) {
+    return ctx.vtable->v0.time_slice_exhausted(ctx.data, exhausted);
+}
+
+/**
+ * Yields the current task, if it has exhausted its time slice.
+ *
+ * Each time a task is resumed by a worker, it is assigned a time
+ * slice, whose length is configured by the `time_slice` option of
+ * its worker group. Long-running tasks should call this function
+ * periodically, to allow other tasks to be scheduled.
+ * May only be called in a task.
+ *
+ * @param ctx context
+ *
+ * @return Status code.
+ */
+FIMO_MUST_USE
+static FIMO_INLINE_ALWAYS FimoResult fi_tasks_ctx_checkpoint(FiTasksContext ctx) {
+    bool exhausted;
+    const FimoResult error = fi_tasks_ctx_time_slice_exhausted(ctx, &exhausted);
+    if (FIMO_RESULT_IS_ERROR(error) || !exhausted) {
+        return error;
+    }
+    return fi_tasks_ctx_yield(ctx);
+}
+
 /**
  * Aborts the current task.
  *
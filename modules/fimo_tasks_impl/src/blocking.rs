@@ -0,0 +1,197 @@
+use fimo_std::{context::Context as StdContext, error::Error, tracing::ThreadAccess};
+use std::{
+    collections::VecDeque,
+    ffi::c_void,
+    fmt::{Debug, Formatter},
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    time::Duration,
+};
+
+/// Time after which an idle thread of the pool exits.
+const KEEP_ALIVE: Duration = Duration::from_secs(10);
+
+/// Function queued on the blocking pool.
+struct Job {
+    func: unsafe extern "C" fn(*mut c_void),
+    data: *mut c_void,
+}
+
+// Safety: The caller of `spawn_blocking` guarantees that the job may be run on another thread.
+unsafe impl Send for Job {}
+
+impl Job {
+    fn run(self) {
+        // Safety: The caller of `spawn_blocking` guarantees that the function may be called with
+        // the provided data.
+        unsafe { (self.func)(self.data) }
+    }
+}
+
+/// Dynamically sized thread pool for long-running synchronous operations.
+///
+/// Threads are spawned on demand, whenever a job is queued and no idle thread is available, up to
+/// the maximum number of threads passed to [`BlockingPool::spawn`]. Idle threads exit after
+/// [`KEEP_ALIVE`] has elapsed without a new job.
+pub struct BlockingPool {
+    context: StdContext,
+    state: Mutex<PoolState>,
+    job_available: Condvar,
+    thread_exited: Condvar,
+}
+
+struct PoolState {
+    closed: bool,
+    jobs: VecDeque<Job>,
+    idle_threads: usize,
+    num_threads: usize,
+    next_thread_id: usize,
+}
+
+impl BlockingPool {
+    pub fn new(context: StdContext) -> Arc<Self> {
+        Arc::new(Self {
+            context,
+            state: Mutex::new(PoolState {
+                closed: false,
+                jobs: VecDeque::new(),
+                idle_threads: 0,
+                num_threads: 0,
+                next_thread_id: 0,
+            }),
+            job_available: Condvar::new(),
+            thread_exited: Condvar::new(),
+        })
+    }
+
+    /// Queues `func` to be invoked with `data` on a thread of the pool.
+    ///
+    /// # Safety
+    ///
+    /// `func` must be safe to invoke with `data` on another thread.
+    pub unsafe fn spawn(
+        self: &Arc<Self>,
+        max_threads: usize,
+        func: unsafe extern "C" fn(*mut c_void),
+        data: *mut c_void,
+    ) -> Result<(), Error> {
+        let mut state = self.state();
+        if state.closed {
+            fimo_std::emit_error!(*self.context, "blocking pool is already closed");
+            return Err(Error::EPERM);
+        }
+        state.jobs.push_back(Job { func, data });
+
+        // Wake an idle thread, if one is available for the new job.
+        if state.jobs.len() <= state.idle_threads {
+            fimo_std::emit_trace!(*self.context, "waking idle thread");
+            self.job_available.notify_one();
+            return Ok(());
+        }
+        if state.num_threads >= max_threads.max(1) {
+            fimo_std::emit_trace!(
+                *self.context,
+                "maximum number of threads reached, queueing job"
+            );
+            return Ok(());
+        }
+
+        let id = state.next_thread_id;
+        fimo_std::emit_trace!(*self.context, "spawning blocking thread {id}");
+        let this = self.clone();
+        let result = std::thread::Builder::new()
+            .name(format!("fimo_tasks blocking {id}"))
+            .spawn(move || this.run_thread());
+        match result {
+            Ok(_) => {
+                state.next_thread_id += 1;
+                state.num_threads += 1;
+                Ok(())
+            }
+            // The job is run by one of the existing threads.
+            Err(e) if state.num_threads != 0 => {
+                fimo_std::emit_warn!(*self.context, "could not spawn blocking thread: {e}");
+                Ok(())
+            }
+            Err(e) => {
+                fimo_std::emit_error!(*self.context, "could not spawn blocking thread: {e}");
+                state.jobs.pop_back();
+                Err(Error::new(e))
+            }
+        }
+    }
+
+    /// Closes the pool and waits until all queued jobs are completed.
+    pub fn shutdown(&self) {
+        let _span = fimo_std::span_trace!(*self.context, "");
+        let mut state = self.state();
+        assert!(!state.closed, "blocking pool has already been closed");
+        state.closed = true;
+        self.job_available.notify_all();
+
+        fimo_std::emit_trace!(*self.context, "waiting for blocking threads to exit");
+        while state.num_threads != 0 {
+            state = self
+                .thread_exited
+                .wait(state)
+                .expect("could not lock blocking pool");
+        }
+    }
+
+    fn run_thread(&self) {
+        fimo_std::panic::abort_on_panic(|| {
+            let _access = ThreadAccess::new(&self.context).expect("could not register thread");
+            let _span = fimo_std::span_trace!(*self.context, "blocking thread");
+
+            // Log panics through the tracing subsystem before aborting.
+            fimo_std::panic::with_panic_context(*self.context, |_| {
+                while let Some(job) = self.next_job() {
+                    job.run();
+                }
+            });
+        });
+
+        let mut state = self.state();
+        state.num_threads -= 1;
+        self.thread_exited.notify_all();
+    }
+
+    fn next_job(&self) -> Option<Job> {
+        let mut state = self.state();
+        loop {
+            if let Some(job) = state.jobs.pop_front() {
+                return Some(job);
+            }
+            if state.closed {
+                return None;
+            }
+
+            state.idle_threads += 1;
+            let (guard, timeout) = self
+                .job_available
+                .wait_timeout(state, KEEP_ALIVE)
+                .expect("could not lock blocking pool");
+            state = guard;
+            state.idle_threads -= 1;
+            if timeout.timed_out() && state.jobs.is_empty() {
+                fimo_std::emit_trace!(*self.context, "exiting idle blocking thread");
+                return None;
+            }
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, PoolState> {
+        self.state.lock().expect("could not lock blocking pool")
+    }
+}
+
+impl Debug for BlockingPool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let state = self.state();
+        f.debug_struct("BlockingPool")
+            .field("closed", &state.closed)
+            .field("queued_jobs", &state.jobs.len())
+            .field("idle_threads", &state.idle_threads)
+            .field("num_threads", &state.num_threads)
+            .finish_non_exhaustive()
+    }
+}
@@ -2,13 +2,21 @@ use std::{ffi::CStr, num::NonZeroUsize, sync::Arc};
 
 use crate::{
     module_export::{TasksModule, TasksModuleToken},
+    semaphore::{SemaphoreFFI, SemaphoreImpl},
+    task_hooks::{TaskHookFFI, TaskHookImpl},
     worker_group::{
-        self, worker_thread::with_worker_context_lock, WorkerGroupFFI, WorkerGroupImpl,
+        self,
+        affinity::{AffinityPolicy, AffinityRequest},
+        allocator::AllocatorHooks,
+        worker_thread::with_worker_context_lock,
+        WorkerGroupFFI, WorkerGroupImpl,
     },
     WorkerGroupQuery,
 };
-use fimo_std::{bindings as std_bindings, error::Error, ffi::FFITransferable, module::Module};
-use fimo_tasks::{bindings, TaskId, WorkerGroupId, WorkerId};
+use fimo_std::{
+    bindings as std_bindings, error::Error, ffi::FFITransferable, module::Module, time::Time,
+};
+use fimo_tasks::{bindings, TaskId, TaskPriority, WorkerGroupId, WorkerId};
 
 #[derive(Debug)]
 pub struct ContextImpl;
@@ -31,6 +39,18 @@ impl ContextImpl {
         .flatten()
     }
 
+    pub fn task_priority(&self, module: TasksModule<'_>) -> Result<TaskPriority, Error> {
+        let _span = fimo_std::span_trace!(module.context(), "self: {self:?}");
+        with_worker_context_lock(|worker| match &worker.current_task {
+            None => {
+                fimo_std::emit_error!(module.context(), "no task registered for current worker");
+                Err(Error::from_string(c"task not owned by worker"))
+            }
+            Some(t) => Ok(t.priority()),
+        })
+        .flatten()
+    }
+
     pub fn worker_id(&self, module: TasksModule<'_>) -> Result<WorkerId, Error> {
         let _span = fimo_std::span_trace!(module.context(), "self: {self:?}");
         with_worker_context_lock(|worker| worker.id)
@@ -68,6 +88,7 @@ impl ContextImpl {
         Ok(groups)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn create_worker_group(
         &self,
         module: TasksModule<'_>,
@@ -75,12 +96,19 @@ impl ContextImpl {
         stacks: &[bindings::FiTasksWorkerGroupConfigStack],
         default_stack_index: usize,
         number_of_workers: Option<NonZeroUsize>,
+        steal_batch_size: Option<NonZeroUsize>,
+        affinity: Option<AffinityRequest>,
         is_queryable: bool,
+        track_allocations: bool,
+        time_slice: Option<std::time::Duration>,
+        allocator: Option<AllocatorHooks>,
     ) -> Result<Arc<WorkerGroupImpl>, Error> {
         let _span = fimo_std::span_trace!(
             module.context(),
             "self: {self:?}, name: {name:?}, number of workers: {number_of_workers:?}, \
-            is queryable: {is_queryable:?}"
+            steal batch size: {steal_batch_size:?}, affinity: {affinity:?}, \
+            is queryable: {is_queryable:?}, track allocations: {track_allocations:?}, \
+            time slice: {time_slice:?}, allocator: {allocator:?}"
         );
         let runtime = module.data().shared_runtime();
         runtime.spawn_worker_group(
@@ -88,7 +116,12 @@ impl ContextImpl {
             stacks,
             default_stack_index,
             number_of_workers,
+            steal_batch_size,
+            affinity,
             is_queryable,
+            track_allocations,
+            time_slice,
+            allocator,
         )
     }
 
@@ -98,6 +131,11 @@ impl ContextImpl {
         worker_group::worker_thread::yield_now()
     }
 
+    /// Is called frequently by long-running tasks, and is therefore not traced.
+    pub fn time_slice_exhausted(&self) -> Result<bool, Error> {
+        worker_group::worker_thread::time_slice_exhausted()
+    }
+
     /// # Safety
     ///
     /// Aborting a task does not unwind the stack, possibly resulting in broken invariants. This
@@ -111,7 +149,7 @@ impl ContextImpl {
         fimo_std::emit_trace!(module.context(), "aborting task");
 
         // Safety: The caller ensures that it is safe.
-        unsafe { worker_group::worker_thread::abort_task(error) }
+        unsafe { worker_group::worker_thread::abort_task(error, None) }
     }
 
     pub fn sleep_for(
@@ -127,6 +165,13 @@ impl ContextImpl {
         worker_group::worker_thread::wait_until(until)
     }
 
+    pub fn sleep_until(&self, module: TasksModule<'_>, time: Time) -> Result<(), Error> {
+        let _span = fimo_std::span_trace!(module.context(), "self: {self:?}, time: {time:?}");
+        let until = worker_group::timer::instant_from_time(module.context(), time);
+        fimo_std::emit_trace!(module.context(), "sleeping task until {until:?}");
+        worker_group::worker_thread::wait_until(until)
+    }
+
     pub fn tss_set(
         &self,
         module: TasksModule<'_>,
@@ -193,6 +238,162 @@ impl ContextImpl {
         })
         .flatten()
     }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn park_conditionally(
+        &self,
+        module: TasksModule<'_>,
+        key: *const std::ffi::c_void,
+        validate: impl FnOnce() -> bool,
+        before_sleep: impl FnOnce(),
+        timed_out: impl FnOnce(*const std::ffi::c_void, bool),
+        park_token: *const std::ffi::c_void,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<bindings::FiTasksParkResult, Error> {
+        let _span = fimo_std::span_trace!(
+            module.context(),
+            "self: {self:?}, key: {key:?}, park_token: {park_token:?}, timeout: {timeout:?}"
+        );
+        fimo_std::emit_trace!(module.context(), "parking task");
+        let deadline = timeout.map(|timeout| std::time::Instant::now() + timeout);
+        let runtime = module.data().shared_runtime();
+        runtime.parking_lot().park(
+            key.addr(),
+            validate,
+            before_sleep,
+            |key, was_last| timed_out(std::ptr::without_provenance(key), was_last),
+            park_token,
+            deadline,
+        )
+    }
+
+    pub fn unpark_one(
+        &self,
+        module: TasksModule<'_>,
+        key: *const std::ffi::c_void,
+        callback: impl FnOnce(bindings::FiTasksUnparkResult) -> *const std::ffi::c_void,
+    ) -> bindings::FiTasksUnparkResult {
+        let _span = fimo_std::span_trace!(module.context(), "self: {self:?}, key: {key:?}");
+        let runtime = module.data().shared_runtime();
+        let result = runtime.parking_lot().unpark_one(key.addr(), callback);
+        fimo_std::emit_trace!(module.context(), "unparked tasks: {result:?}");
+        result
+    }
+
+    pub fn unpark_all(
+        &self,
+        module: TasksModule<'_>,
+        key: *const std::ffi::c_void,
+        unpark_token: *const std::ffi::c_void,
+    ) -> usize {
+        let _span = fimo_std::span_trace!(
+            module.context(),
+            "self: {self:?}, key: {key:?}, unpark_token: {unpark_token:?}"
+        );
+        let runtime = module.data().shared_runtime();
+        let unparked = runtime.parking_lot().unpark_all(key.addr(), unpark_token);
+        fimo_std::emit_trace!(module.context(), "unparked tasks: {unparked:?}");
+        unparked
+    }
+
+    pub fn unpark_requeue(
+        &self,
+        module: TasksModule<'_>,
+        key_from: *const std::ffi::c_void,
+        key_to: *const std::ffi::c_void,
+        validate: impl FnOnce() -> bindings::FiTasksRequeueOp,
+        callback: impl FnOnce(
+            bindings::FiTasksRequeueOp,
+            bindings::FiTasksUnparkResult,
+        ) -> *const std::ffi::c_void,
+    ) -> bindings::FiTasksUnparkResult {
+        let _span = fimo_std::span_trace!(
+            module.context(),
+            "self: {self:?}, key_from: {key_from:?}, key_to: {key_to:?}"
+        );
+        let runtime = module.data().shared_runtime();
+        let result = runtime.parking_lot().unpark_requeue(
+            key_from.addr(),
+            key_to.addr(),
+            validate,
+            callback,
+        );
+        fimo_std::emit_trace!(module.context(), "unparked tasks: {result:?}");
+        result
+    }
+
+    pub fn unpark_filter(
+        &self,
+        module: TasksModule<'_>,
+        key: *const std::ffi::c_void,
+        filter: impl FnMut(*const std::ffi::c_void) -> bindings::FiTasksUnparkFilterOp,
+        callback: impl FnOnce(bindings::FiTasksUnparkResult) -> *const std::ffi::c_void,
+    ) -> bindings::FiTasksUnparkResult {
+        let _span = fimo_std::span_trace!(module.context(), "self: {self:?}, key: {key:?}");
+        let runtime = module.data().shared_runtime();
+        let result = runtime
+            .parking_lot()
+            .unpark_filter(key.addr(), filter, callback);
+        fimo_std::emit_trace!(module.context(), "unparked tasks: {result:?}");
+        result
+    }
+
+    pub fn create_semaphore(
+        &self,
+        module: TasksModule<'_>,
+        initial_value: u64,
+    ) -> Arc<SemaphoreImpl> {
+        let _span = fimo_std::span_trace!(
+            module.context(),
+            "self: {self:?}, initial_value: {initial_value:?}"
+        );
+        let semaphore = SemaphoreImpl::new(initial_value);
+        fimo_std::emit_trace!(module.context(), "created semaphore: {semaphore:?}");
+        semaphore
+    }
+
+    /// # Safety
+    ///
+    /// `func` must be safe to invoke with `data` on another thread.
+    pub unsafe fn spawn_blocking(
+        &self,
+        module: TasksModule<'_>,
+        func: unsafe extern "C" fn(*mut std::ffi::c_void),
+        data: *mut std::ffi::c_void,
+    ) -> Result<(), Error> {
+        let _span = fimo_std::span_trace!(
+            module.context(),
+            "self: {self:?}, func: {func:?}, data: {data:?}"
+        );
+        let max_threads = module.parameters().max_blocking_threads().read(&module)? as usize;
+        let runtime = module.data().shared_runtime();
+        fimo_std::emit_trace!(module.context(), "queueing blocking job");
+
+        // Safety: Is guaranteed by the caller.
+        unsafe { runtime.blocking_pool().spawn(max_threads, func, data) }
+    }
+
+    /// # Safety
+    ///
+    /// `on_event` and `cleanup` must be safe to invoke with `data` from any thread.
+    pub unsafe fn register_task_hook(
+        &self,
+        module: TasksModule<'_>,
+        on_event: unsafe extern "C" fn(*mut std::ffi::c_void, *const bindings::FiTasksTaskEvent),
+        data: *mut std::ffi::c_void,
+        cleanup: Option<unsafe extern "C" fn(*mut std::ffi::c_void)>,
+    ) -> Arc<TaskHookImpl> {
+        let _span = fimo_std::span_trace!(
+            module.context(),
+            "self: {self:?}, on_event: {on_event:?}, data: {data:?}, cleanup: {cleanup:?}"
+        );
+        let runtime = module.data().shared_runtime();
+
+        // Safety: Is guaranteed by the caller.
+        let hook = unsafe { runtime.task_hooks().register(on_event, data, cleanup) };
+        fimo_std::emit_trace!(module.context(), "registered task hook: {hook:?}");
+        hook
+    }
 }
 
 impl ContextImpl {
@@ -218,6 +419,12 @@ impl ContextImpl {
                 unpark_all: Some(ContextImpl::unpark_all_ffi),
                 unpark_requeue: Some(ContextImpl::unpark_requeue_ffi),
                 unpark_filter: Some(ContextImpl::unpark_filter_ffi),
+                task_priority: Some(ContextImpl::task_priority_ffi),
+                create_semaphore: Some(ContextImpl::create_semaphore_ffi),
+                spawn_blocking: Some(ContextImpl::spawn_blocking_ffi),
+                sleep_until: Some(ContextImpl::sleep_until_ffi),
+                register_task_hook: Some(ContextImpl::register_task_hook_ffi),
+                time_slice_exhausted: Some(ContextImpl::time_slice_exhausted_ffi),
             },
         };
 
@@ -260,6 +467,29 @@ impl ContextImpl {
         .into_ffi()
     }
 
+    unsafe extern "C" fn task_priority_ffi(
+        _this: *mut std::ffi::c_void,
+        priority: *mut bindings::FiTasksTaskPriority,
+    ) -> std_bindings::FimoResult {
+        fimo_std::panic::catch_unwind(|| {
+            // Safety: Is safe since we are calling it from an exported symbol.
+            unsafe {
+                TasksModuleToken::with_current_unlocked(|module| {
+                    let _span = fimo_std::span_trace!(module.context(), "priority: {priority:?}");
+                    if priority.is_null() {
+                        fimo_std::emit_error!(module.context(), "`priority` is null");
+                        return Err(Error::EINVAL);
+                    }
+                    priority.write(Self.task_priority(module)?.into());
+                    Ok(())
+                })
+            }
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
+    }
+
     unsafe extern "C" fn worker_id_ffi(
         _this: *mut std::ffi::c_void,
         id: *mut usize,
@@ -421,7 +651,92 @@ impl ContextImpl {
                         cfg.default_stack_index
                     };
                     let number_of_workers = NonZeroUsize::new(cfg.number_of_workers);
+                    let steal_batch_size = NonZeroUsize::new(cfg.steal_batch_size);
+                    let affinity = if cfg.affinity.is_null() {
+                        None
+                    } else {
+                        let affinity = &*cfg.affinity;
+                        if !affinity.next.is_null() {
+                            fimo_std::emit_error!(
+                                module.context(),
+                                "`cfg.affinity.next` is not null"
+                            );
+                            return Err(Error::EINVAL);
+                        }
+
+                        use bindings::FiTasksWorkerAffinityPolicy as Policy;
+                        let policy = match affinity.policy {
+                            Policy::FI_TASKS_WORKER_AFFINITY_POLICY_NONE => AffinityPolicy::None,
+                            Policy::FI_TASKS_WORKER_AFFINITY_POLICY_SPREAD => {
+                                AffinityPolicy::Spread
+                            }
+                            Policy::FI_TASKS_WORKER_AFFINITY_POLICY_COMPACT => {
+                                AffinityPolicy::Compact
+                            }
+                            policy => {
+                                fimo_std::emit_error!(
+                                    module.context(),
+                                    "unknown affinity policy {policy:?}"
+                                );
+                                return Err(Error::EINVAL);
+                            }
+                        };
+                        let worker_cpus = if affinity.num_worker_cpus == 0 {
+                            &[]
+                        } else {
+                            if affinity.worker_cpus.is_null() {
+                                fimo_std::emit_error!(
+                                    module.context(),
+                                    "`cfg.affinity.worker_cpus` is null"
+                                );
+                                return Err(Error::EINVAL);
+                            }
+                            std::slice::from_raw_parts(
+                                affinity.worker_cpus,
+                                affinity.num_worker_cpus,
+                            )
+                        };
+                        let mut cpus = Vec::with_capacity(worker_cpus.len());
+                        for set in worker_cpus {
+                            if set.num_cpus == 0 {
+                                cpus.push(Box::default());
+                                continue;
+                            }
+                            if set.cpus.is_null() {
+                                fimo_std::emit_error!(
+                                    module.context(),
+                                    "cpu set of `cfg.affinity.worker_cpus` is null"
+                                );
+                                return Err(Error::EINVAL);
+                            }
+                            cpus.push(std::slice::from_raw_parts(set.cpus, set.num_cpus).into());
+                        }
+
+                        Some(AffinityRequest {
+                            policy,
+                            numa_node: usize::try_from(affinity.numa_node).ok(),
+                            worker_cpus: cpus,
+                        })
+                    };
                     let is_queryable = cfg.is_queryable;
+                    let track_allocations = cfg.track_allocations;
+                    let time_slice =
+                        std::time::Duration::new(cfg.time_slice.secs, cfg.time_slice.nanos);
+                    let time_slice = (!time_slice.is_zero()).then_some(time_slice);
+                    let allocator = if cfg.allocator.is_null() {
+                        None
+                    } else {
+                        match AllocatorHooks::from_config(&*cfg.allocator) {
+                            Ok(hooks) => Some(hooks),
+                            Err(e) => {
+                                fimo_std::emit_error!(
+                                    module.context(),
+                                    "invalid `cfg.allocator`, error: {e:?}"
+                                );
+                                return Err(e);
+                            }
+                        }
+                    };
 
                     if cfg.name.is_null() {
                         fimo_std::emit_error!(module.context(), "`cfg.next` is not null");
@@ -440,7 +755,12 @@ impl ContextImpl {
                             stacks,
                             default_stack_index,
                             number_of_workers,
+                            steal_batch_size,
+                            affinity,
                             is_queryable,
+                            track_allocations,
+                            time_slice,
+                            allocator,
                         )
                         .map(|g| WorkerGroupFFI(g).into_ffi())?,
                     );
@@ -468,6 +788,26 @@ impl ContextImpl {
         .into_ffi()
     }
 
+    unsafe extern "C" fn time_slice_exhausted_ffi(
+        _this: *mut std::ffi::c_void,
+        exhausted: *mut bool,
+    ) -> std_bindings::FimoResult {
+        // The module is not accessed by the checkpoint, and remains loaded while a worker is
+        // executing a task.
+        fimo_std::panic::catch_unwind(|| {
+            if exhausted.is_null() {
+                return Err(Error::EINVAL);
+            }
+            let value = Self.time_slice_exhausted()?;
+            // Safety: The pointer is not null, and is valid for writes by contract.
+            unsafe { exhausted.write(value) };
+            Ok(())
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
+    }
+
     unsafe extern "C" fn abort_ffi(
         _this: *mut std::ffi::c_void,
         error: *mut std::ffi::c_void,
@@ -574,86 +914,324 @@ impl ContextImpl {
 
     unsafe extern "C" fn park_conditionally_ffi(
         _this: *mut std::ffi::c_void,
-        _key: *const std::ffi::c_void,
-        _validate: Option<unsafe extern "C" fn(*mut std::ffi::c_void) -> bool>,
-        _validate_data: *mut std::ffi::c_void,
-        _before_sleep: Option<unsafe extern "C" fn(*mut std::ffi::c_void)>,
-        _before_sleep_data: *mut std::ffi::c_void,
-        _timed_out: Option<
+        key: *const std::ffi::c_void,
+        validate: Option<unsafe extern "C" fn(*mut std::ffi::c_void) -> bool>,
+        validate_data: *mut std::ffi::c_void,
+        before_sleep: Option<unsafe extern "C" fn(*mut std::ffi::c_void)>,
+        before_sleep_data: *mut std::ffi::c_void,
+        timed_out: Option<
             unsafe extern "C" fn(*mut std::ffi::c_void, *const std::ffi::c_void, bool),
         >,
-        _timed_out_data: *mut std::ffi::c_void,
-        _park_token: *const std::ffi::c_void,
-        _timeout: *const std_bindings::FimoDuration,
-        _result: *mut bindings::FiTasksParkResult,
+        timed_out_data: *mut std::ffi::c_void,
+        park_token: *const std::ffi::c_void,
+        timeout: *const std_bindings::FimoDuration,
+        result: *mut bindings::FiTasksParkResult,
     ) -> std_bindings::FimoResult {
-        <Error>::ENOSYS.into_error()
+        fimo_std::panic::catch_unwind(|| {
+            // Safety: Is safe since we are calling it from an exported symbol. The callbacks
+            // are invoked with the data provided by the caller.
+            unsafe {
+                TasksModuleToken::with_current_unlocked(|module| {
+                    let _span = fimo_std::span_trace!(
+                        module.context(),
+                        "key: {key:?}, park_token: {park_token:?}, timeout: {timeout:?}"
+                    );
+                    if result.is_null() {
+                        fimo_std::emit_error!(module.context(), "`result` is null");
+                        return Err(Error::EINVAL);
+                    }
+                    let timeout = timeout
+                        .as_ref()
+                        .map(|timeout| std::time::Duration::new(timeout.secs, timeout.nanos));
+                    let park_result = Self.park_conditionally(
+                        module,
+                        key,
+                        || validate.is_none_or(|f| f(validate_data)),
+                        || {
+                            if let Some(f) = before_sleep {
+                                f(before_sleep_data);
+                            }
+                        },
+                        |key, was_last| {
+                            if let Some(f) = timed_out {
+                                f(timed_out_data, key, was_last);
+                            }
+                        },
+                        park_token,
+                        timeout,
+                    )?;
+                    result.write(park_result);
+                    Ok(())
+                })
+            }
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
     }
 
     unsafe extern "C" fn unpark_one_ffi(
         _this: *mut std::ffi::c_void,
-        _key: *const std::ffi::c_void,
-        _callback: Option<
+        key: *const std::ffi::c_void,
+        callback: Option<
             unsafe extern "C" fn(
                 *mut std::ffi::c_void,
                 bindings::FiTasksUnparkResult,
             ) -> *const std::ffi::c_void,
         >,
-        _callback_data: *mut std::ffi::c_void,
-        _result: *mut bindings::FiTasksUnparkResult,
+        callback_data: *mut std::ffi::c_void,
+        result: *mut bindings::FiTasksUnparkResult,
     ) -> std_bindings::FimoResult {
-        <Error>::ENOSYS.into_error()
+        fimo_std::panic::catch_unwind(|| {
+            // Safety: Is safe since we are calling it from an exported symbol. The callback is
+            // invoked with the data provided by the caller.
+            unsafe {
+                TasksModuleToken::with_current_unlocked(|module| {
+                    let _span = fimo_std::span_trace!(module.context(), "key: {key:?}");
+                    if result.is_null() {
+                        fimo_std::emit_error!(module.context(), "`result` is null");
+                        return Err(Error::EINVAL);
+                    }
+                    let unpark_result = Self.unpark_one(module, key, |r| {
+                        callback.map_or(std::ptr::null(), |f| f(callback_data, r))
+                    });
+                    result.write(unpark_result);
+                    Ok(())
+                })
+            }
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
     }
 
     unsafe extern "C" fn unpark_all_ffi(
         _this: *mut std::ffi::c_void,
-        _key: *const std::ffi::c_void,
-        _unpark_token: *const std::ffi::c_void,
-        _unparked_tasks: *mut usize,
+        key: *const std::ffi::c_void,
+        unpark_token: *const std::ffi::c_void,
+        unparked_tasks: *mut usize,
     ) -> std_bindings::FimoResult {
-        <Error>::ENOSYS.into_error()
+        fimo_std::panic::catch_unwind(|| {
+            // Safety: Is safe since we are calling it from an exported symbol.
+            unsafe {
+                TasksModuleToken::with_current_unlocked(|module| {
+                    let _span = fimo_std::span_trace!(
+                        module.context(),
+                        "key: {key:?}, unpark_token: {unpark_token:?}"
+                    );
+                    if unparked_tasks.is_null() {
+                        fimo_std::emit_error!(module.context(), "`unparked_tasks` is null");
+                        return Err(Error::EINVAL);
+                    }
+                    unparked_tasks.write(Self.unpark_all(module, key, unpark_token));
+                    Ok(())
+                })
+            }
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
     }
 
     unsafe extern "C" fn unpark_requeue_ffi(
         _this: *mut std::ffi::c_void,
-        _key_from: *const std::ffi::c_void,
-        _key_to: *const std::ffi::c_void,
-        _validate: Option<
-            unsafe extern "C" fn(*mut std::ffi::c_void) -> bindings::FiTasksRequeueOp,
-        >,
-        _validate_data: *mut std::ffi::c_void,
-        _callback: Option<
+        key_from: *const std::ffi::c_void,
+        key_to: *const std::ffi::c_void,
+        validate: Option<unsafe extern "C" fn(*mut std::ffi::c_void) -> bindings::FiTasksRequeueOp>,
+        validate_data: *mut std::ffi::c_void,
+        callback: Option<
             unsafe extern "C" fn(
                 *mut std::ffi::c_void,
                 bindings::FiTasksRequeueOp,
                 bindings::FiTasksUnparkResult,
             ) -> *const std::ffi::c_void,
         >,
-        _callback_data: *mut std::ffi::c_void,
-        _result: *mut bindings::FiTasksUnparkResult,
+        callback_data: *mut std::ffi::c_void,
+        result: *mut bindings::FiTasksUnparkResult,
     ) -> std_bindings::FimoResult {
-        <Error>::ENOSYS.into_error()
+        fimo_std::panic::catch_unwind(|| {
+            // Safety: Is safe since we are calling it from an exported symbol. The callbacks
+            // are invoked with the data provided by the caller.
+            unsafe {
+                TasksModuleToken::with_current_unlocked(|module| {
+                    let _span = fimo_std::span_trace!(
+                        module.context(),
+                        "key_from: {key_from:?}, key_to: {key_to:?}"
+                    );
+                    if result.is_null() {
+                        fimo_std::emit_error!(module.context(), "`result` is null");
+                        return Err(Error::EINVAL);
+                    }
+                    let Some(validate) = validate else {
+                        fimo_std::emit_error!(module.context(), "`validate` is null");
+                        return Err(Error::EINVAL);
+                    };
+                    let unpark_result = Self.unpark_requeue(
+                        module,
+                        key_from,
+                        key_to,
+                        || validate(validate_data),
+                        |op, r| callback.map_or(std::ptr::null(), |f| f(callback_data, op, r)),
+                    );
+                    result.write(unpark_result);
+                    Ok(())
+                })
+            }
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
     }
 
     unsafe extern "C" fn unpark_filter_ffi(
         _this: *mut std::ffi::c_void,
-        _key: *const std::ffi::c_void,
-        _filter: Option<
+        key: *const std::ffi::c_void,
+        filter: Option<
             unsafe extern "C" fn(
                 *mut std::ffi::c_void,
                 *const std::ffi::c_void,
             ) -> bindings::FiTasksUnparkFilterOp,
         >,
-        _filter_data: *mut std::ffi::c_void,
-        _callback: Option<
+        filter_data: *mut std::ffi::c_void,
+        callback: Option<
             unsafe extern "C" fn(
                 *mut std::ffi::c_void,
                 bindings::FiTasksUnparkResult,
             ) -> *const std::ffi::c_void,
         >,
-        _callback_data: *mut std::ffi::c_void,
-        _result: *mut bindings::FiTasksUnparkResult,
+        callback_data: *mut std::ffi::c_void,
+        result: *mut bindings::FiTasksUnparkResult,
     ) -> std_bindings::FimoResult {
-        <Error>::ENOSYS.into_error()
+        fimo_std::panic::catch_unwind(|| {
+            // Safety: Is safe since we are calling it from an exported symbol. The callbacks
+            // are invoked with the data provided by the caller.
+            unsafe {
+                TasksModuleToken::with_current_unlocked(|module| {
+                    let _span = fimo_std::span_trace!(module.context(), "key: {key:?}");
+                    if result.is_null() {
+                        fimo_std::emit_error!(module.context(), "`result` is null");
+                        return Err(Error::EINVAL);
+                    }
+                    let Some(filter) = filter else {
+                        fimo_std::emit_error!(module.context(), "`filter` is null");
+                        return Err(Error::EINVAL);
+                    };
+                    let unpark_result = Self.unpark_filter(
+                        module,
+                        key,
+                        |token| filter(filter_data, token),
+                        |r| callback.map_or(std::ptr::null(), |f| f(callback_data, r)),
+                    );
+                    result.write(unpark_result);
+                    Ok(())
+                })
+            }
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
+    }
+
+    unsafe extern "C" fn create_semaphore_ffi(
+        _this: *mut std::ffi::c_void,
+        initial_value: u64,
+        semaphore: *mut bindings::FiTasksSemaphore,
+    ) -> std_bindings::FimoResult {
+        fimo_std::panic::catch_unwind(|| {
+            // Safety: Is safe since we are calling it from an exported symbol.
+            unsafe {
+                TasksModuleToken::with_current_unlocked(|module| {
+                    if semaphore.is_null() {
+                        fimo_std::emit_error!(module.context(), "`semaphore` is null");
+                        return Err(Error::EINVAL);
+                    }
+                    semaphore.write(
+                        SemaphoreFFI(Self.create_semaphore(module, initial_value)).into_ffi(),
+                    );
+                    Ok(())
+                })
+            }
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
+    }
+
+    unsafe extern "C" fn spawn_blocking_ffi(
+        _this: *mut std::ffi::c_void,
+        func: Option<unsafe extern "C" fn(*mut std::ffi::c_void)>,
+        data: *mut std::ffi::c_void,
+    ) -> std_bindings::FimoResult {
+        fimo_std::panic::catch_unwind(|| {
+            // Safety: Is safe since we are calling it from an exported symbol.
+            unsafe {
+                TasksModuleToken::with_current_unlocked(|module| {
+                    let Some(func) = func else {
+                        fimo_std::emit_error!(module.context(), "`func` is null");
+                        return Err(Error::EINVAL);
+                    };
+                    Self.spawn_blocking(module, func, data)
+                })
+            }
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
+    }
+
+    unsafe extern "C" fn sleep_until_ffi(
+        _this: *mut std::ffi::c_void,
+        time: std_bindings::FimoTime,
+    ) -> std_bindings::FimoResult {
+        fimo_std::panic::catch_unwind(|| {
+            // Safety: Is safe since we are calling it from an exported symbol.
+            unsafe {
+                TasksModuleToken::with_current_unlocked(|module| {
+                    let _span = fimo_std::span_trace!(module.context(), "time: {time:?}");
+                    Self.sleep_until(module, Time::from_ffi(time))
+                })
+            }
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
+    }
+
+    unsafe extern "C" fn register_task_hook_ffi(
+        _this: *mut std::ffi::c_void,
+        config: *const bindings::FiTasksTaskHookConfig,
+        hook: *mut bindings::FiTasksTaskHook,
+    ) -> std_bindings::FimoResult {
+        fimo_std::panic::catch_unwind(|| {
+            // Safety: Is safe since we are calling it from an exported symbol.
+            unsafe {
+                TasksModuleToken::with_current_unlocked(|module| {
+                    if config.is_null() || hook.is_null() {
+                        fimo_std::emit_error!(module.context(), "`config` or `hook` is null");
+                        return Err(Error::EINVAL);
+                    }
+                    let bindings::FiTasksTaskHookConfig {
+                        next,
+                        on_event,
+                        data,
+                        cleanup,
+                    } = config.read();
+                    if !next.is_null() {
+                        fimo_std::emit_error!(module.context(), "`config.next` is not null");
+                        return Err(Error::EINVAL);
+                    }
+                    let Some(on_event) = on_event else {
+                        fimo_std::emit_error!(module.context(), "`config.on_event` is null");
+                        return Err(Error::EINVAL);
+                    };
+                    let hook_impl = Self.register_task_hook(module, on_event, data, cleanup);
+                    hook.write(TaskHookFFI(hook_impl).into_ffi());
+                    Ok(())
+                })
+            }
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
     }
 }
@@ -14,6 +14,13 @@
 //!
 //! - `default_stack_size: u32` (public, dependency, `default = 512KB`): Default stack size in
 //!   bytes.
+//! - `deadlock_detection: u8` (public, dependency, `default = 0`): Enables the detection of cyclic
+//!   waits between tasks and command buffers for the worker groups created afterward, if set to a
+//!   non-zero value. Detected deadlocks are reported through the tracing subsystem, together with
+//!   the backtraces of the sites spawning the involved tasks. Waits on the task-aware
+//!   synchronization primitives, e.g. semaphores, are not tracked.
+//! - `max_blocking_threads: u32` (public, dependency, `default = 512`): Maximum number of threads
+//!   of the blocking thread pool.
 //!
 //! ## Imported symbols:
 //!
@@ -30,7 +37,10 @@
 #![feature(thread_local)]
 
 use crate::{
+    blocking::BlockingPool,
     module_export::TasksModule,
+    parking_lot::ParkingLot,
+    task_hooks::TaskHooks,
     worker_group::{WorkerGroupFFI, WorkerGroupImpl},
 };
 use crossbeam_channel::{Receiver, Sender};
@@ -51,8 +61,14 @@ use std::{
     num::NonZeroUsize,
     sync::{Arc, RwLock},
     thread::JoinHandle,
+    time::Duration,
+};
+use worker_group::{
+    affinity::{self, AffinityRequest},
+    allocator::{AllocatorHooks, GroupAllocator},
+    event_loop::stack_manager::StackDescriptor,
+    worker_thread::{DEFAULT_STEAL_BATCH_SIZE, DEFAULT_TIME_SLICE},
 };
-use worker_group::event_loop::stack_manager::StackDescriptor;
 
 // We are currently building each module in separate dynamic library.
 // If we decide to support static linking in the future this should be
@@ -60,8 +76,12 @@ use worker_group::event_loop::stack_manager::StackDescriptor;
 #[global_allocator]
 static GLOBAL: FimoAllocator = FimoAllocator;
 
+mod blocking;
 mod context;
 mod module_export;
+mod parking_lot;
+mod semaphore;
+mod task_hooks;
 mod worker_group;
 
 #[derive(Debug)]
@@ -142,6 +162,9 @@ struct RuntimeShared {
     context: StdContext,
     sx: Sender<RuntimeMessage>,
     worker_group_manager: RwLock<WorkerGroupManager>,
+    blocking_pool: Arc<BlockingPool>,
+    task_hooks: Arc<TaskHooks>,
+    parking_lot: ParkingLot,
 }
 
 impl RuntimeShared {
@@ -150,6 +173,9 @@ impl RuntimeShared {
             context: module.context().to_context(),
             sx,
             worker_group_manager: RwLock::new(WorkerGroupManager::new()),
+            blocking_pool: BlockingPool::new(module.context().to_context()),
+            task_hooks: TaskHooks::new(module.context().to_context()),
+            parking_lot: ParkingLot::new(),
         })
     }
 
@@ -172,6 +198,13 @@ impl RuntimeShared {
             }
         }
 
+        // Tasks of the closed worker groups may still be waiting on the blocking jobs.
+        fimo_std::emit_trace!(*self.context, "shutting down blocking pool");
+        self.blocking_pool.shutdown();
+
+        fimo_std::emit_trace!(*self.context, "removing task hooks");
+        self.task_hooks.clear();
+
         fimo_std::emit_trace!(*self.context, "shutting down inner thread");
         self.send_runtime_message(RuntimeMessage::Exit);
     }
@@ -196,18 +229,26 @@ impl RuntimeShared {
         guard.find_by_id(group_id)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn spawn_worker_group(
         self: &Arc<Self>,
         name: &CStr,
         stacks: &[bindings::FiTasksWorkerGroupConfigStack],
         default_stack_index: usize,
         number_of_workers: Option<NonZeroUsize>,
+        steal_batch_size: Option<NonZeroUsize>,
+        affinity: Option<AffinityRequest>,
         is_queryable: bool,
+        track_allocations: bool,
+        time_slice: Option<Duration>,
+        allocator: Option<AllocatorHooks>,
     ) -> Result<Arc<WorkerGroupImpl>, Error> {
         let _span = fimo_std::span_trace!(
             *self.context,
             "self: {self:?}, name: {name:?}, number of workers: {number_of_workers:?}, \
-            is queryable: {is_queryable:?}"
+            steal batch size: {steal_batch_size:?}, affinity: {affinity:?}, \
+            is queryable: {is_queryable:?}, track allocations: {track_allocations:?}, \
+            time slice: {time_slice:?}, allocator: {allocator:?}"
         );
         if stacks.is_empty() {
             fimo_std::emit_error!(*self.context, "stacks is empty");
@@ -222,6 +263,9 @@ impl RuntimeShared {
         let default_stack_size_config = TasksModuleToken::with_current(|module| {
             module.parameters().default_stack_size().read(&**module)
         })? as usize;
+        let detect_deadlocks = TasksModuleToken::with_current(|module| {
+            module.parameters().deadlock_detection().read(&**module)
+        })? != 0;
         let min_stack_size = ::context::stack::Stack::min_size();
         let max_stack_size = ::context::stack::Stack::max_size();
 
@@ -235,6 +279,8 @@ impl RuntimeShared {
                 residency_target,
                 max_residency,
                 enable_stack_overflow_protection,
+                enable_usage_tracking,
+                enable_spilling,
             } = *stack;
             if !next.is_null() {
                 fimo_std::emit_error!(*self.context, "`stack.next` is not null");
@@ -280,6 +326,8 @@ impl RuntimeShared {
                 target_allocated,
                 max_allocated,
                 overflow_protection: enable_stack_overflow_protection,
+                usage_tracking: enable_usage_tracking,
+                spilling: enable_spilling,
             };
 
             match stacks_.binary_search_by_key(&size, |s| s.min_size) {
@@ -307,6 +355,51 @@ impl RuntimeShared {
             }
             workers
         };
+        let steal_batch_size = steal_batch_size.map_or(DEFAULT_STEAL_BATCH_SIZE, |x| x.get());
+        let time_slice = time_slice.unwrap_or(DEFAULT_TIME_SLICE);
+
+        // Determine the cpus of each worker.
+        let numa_node = affinity.as_ref().and_then(|request| request.numa_node);
+        let worker_cpus = match affinity {
+            None => vec![None; number_of_workers.get()],
+            Some(request) => {
+                if !affinity::is_supported() {
+                    fimo_std::emit_error!(
+                        *self.context,
+                        "pinning workers is not supported on the current platform"
+                    );
+                    return Err(Error::ENOTSUP);
+                }
+                if request.worker_cpus.len() > number_of_workers.get() {
+                    fimo_std::emit_error!(
+                        *self.context,
+                        "specified {} cpu sets for {number_of_workers} workers",
+                        request.worker_cpus.len()
+                    );
+                    return Err(Error::EINVAL);
+                }
+                if let Some(cpu) = request
+                    .worker_cpus
+                    .iter()
+                    .flatten()
+                    .find(|&&cpu| !affinity::is_valid_cpu(cpu))
+                {
+                    fimo_std::emit_error!(*self.context, "invalid cpu index {cpu}");
+                    return Err(Error::EINVAL);
+                }
+
+                match request.resolve(number_of_workers.get()) {
+                    Ok(cpus) => cpus,
+                    Err(e) => {
+                        fimo_std::emit_error!(
+                            *self.context,
+                            "could not resolve the affinity of the workers, error: {e}"
+                        );
+                        return Err(e);
+                    }
+                }
+            }
+        };
 
         {
             fimo_std::emit_trace!(*self.context, "requesting a new worker group");
@@ -315,13 +408,32 @@ impl RuntimeShared {
                 name.to_owned(),
                 is_queryable,
                 number_of_workers.get(),
+                steal_batch_size,
+                worker_cpus,
+                detect_deadlocks,
+                track_allocations,
+                time_slice,
                 default_stack_size,
                 stacks_,
+                allocator,
+                numa_node,
                 self,
             )
         }
     }
 
+    fn blocking_pool(&self) -> &Arc<BlockingPool> {
+        &self.blocking_pool
+    }
+
+    fn task_hooks(&self) -> &Arc<TaskHooks> {
+        &self.task_hooks
+    }
+
+    fn parking_lot(&self) -> &ParkingLot {
+        &self.parking_lot
+    }
+
     fn shutdown_worker_group(&self, group_id: WorkerGroupId) {
         let _span = fimo_std::span_trace!(*self.context, "group_id: {group_id:?}");
         {
@@ -370,20 +482,32 @@ impl WorkerGroupManager {
         self.closed
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn spawn_new(
         &mut self,
         name: CString,
         visible: bool,
         num_workers: usize,
+        steal_batch_size: usize,
+        worker_cpus: Vec<Option<Box<[usize]>>>,
+        detect_deadlocks: bool,
+        track_allocations: bool,
+        time_slice: Duration,
         default_stack_size: usize,
         stacks: Vec<StackDescriptor>,
+        allocator: Option<AllocatorHooks>,
+        numa_node: Option<usize>,
         runtime: &Arc<RuntimeShared>,
     ) -> Result<Arc<WorkerGroupImpl>, Error> {
         let ctx = *runtime.context;
         let _span = fimo_std::span_trace!(
             ctx,
             "this: {self:?}, name: {name:?}, visible: {visible:?}, num_workers: {num_workers:?}, \
-            default_stack_size: {default_stack_size:?}, stacks: {stacks:?}, runtime: {runtime:?}"
+            steal_batch_size: {steal_batch_size:?}, worker_cpus: {worker_cpus:?}, \
+            detect_deadlocks: {detect_deadlocks:?}, track_allocations: {track_allocations:?}, \
+            time_slice: {time_slice:?}, default_stack_size: {default_stack_size:?}, \
+            stacks: {stacks:?}, allocator: {allocator:?}, numa_node: {numa_node:?}, \
+            runtime: {runtime:?}"
         );
 
         if self.closed {
@@ -397,14 +521,23 @@ impl WorkerGroupManager {
             return Err(Error::E2BIG);
         }
 
+        // The allocator takes ownership of the hooks, so it may only be constructed once the
+        // creation of the group can no longer fail.
+        let allocator = GroupAllocator::new(allocator, numa_node);
         let group = WorkerGroupImpl::new(
             ctx,
             id,
             name,
             visible,
             num_workers,
+            steal_batch_size,
+            worker_cpus,
+            detect_deadlocks,
+            track_allocations,
+            time_slice,
             default_stack_size,
             stacks,
+            allocator,
             runtime.clone(),
         );
         self.groups.insert(id, group.clone());
@@ -548,7 +681,10 @@ impl RuntimeInner {
                 let _access = ThreadAccess::new(&context).expect("could not register thread");
                 let _span = fimo_std::span_trace!(*context, "tasks runtime event loop");
 
-                this.process_messages(&context);
+                // Log panics through the tracing subsystem before aborting.
+                fimo_std::panic::with_panic_context(*context, |context| {
+                    this.process_messages(context);
+                });
             });
         });
         (sx, thread)
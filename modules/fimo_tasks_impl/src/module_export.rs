@@ -17,6 +17,16 @@ export_module! {
                 read_group: public,
                 write_group: dependency,
             },
+            deadlock_detection: {
+                default: u8(0),
+                read_group: public,
+                write_group: dependency,
+            },
+            max_blocking_threads: {
+                default: u32(512),
+                read_group: public,
+                write_group: dependency,
+            },
         },
         resources: {},
         namespaces: [],
@@ -0,0 +1,364 @@
+//! Queues of parked tasks.
+//!
+//! A task is parked in the queue associated with an address, and is suspended until it is
+//! unparked by another task or thread, or until its timeout is reached. The queues are shared
+//! between all worker groups, so that the synchronization primitives built on top of them can be
+//! used by the tasks of multiple groups.
+
+use crate::worker_group::worker_thread::{self, AssertSend};
+use fimo_std::error::Error;
+use fimo_tasks::{bindings, TaskId};
+use rustc_hash::FxHashMap;
+use std::{
+    collections::VecDeque,
+    ffi::c_void,
+    fmt::{Debug, Formatter},
+    sync::{Arc, Mutex, MutexGuard},
+    time::Instant,
+};
+
+type Waker = Box<dyn FnOnce() + Send>;
+
+#[derive(Debug, Default)]
+pub struct ParkingLot {
+    queues: Mutex<FxHashMap<usize, VecDeque<ParkedTask>>>,
+}
+
+#[derive(Debug)]
+struct ParkedTask {
+    handle: Arc<ParkHandle>,
+    token: AssertSend<*const c_void>,
+}
+
+impl ParkingLot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn queues(&self) -> MutexGuard<'_, FxHashMap<usize, VecDeque<ParkedTask>>> {
+        self.queues.lock().expect("could not lock parking lot")
+    }
+
+    /// Parks the current task in the queue associated with `key`.
+    ///
+    /// `validate` and `timed_out` are called while the queues are locked, while `before_sleep`
+    /// is called after the task has been enqueued and the queues have been unlocked.
+    pub fn park(
+        &self,
+        key: usize,
+        validate: impl FnOnce() -> bool,
+        before_sleep: impl FnOnce(),
+        timed_out: impl FnOnce(usize, bool),
+        token: *const c_void,
+        deadline: Option<Instant>,
+    ) -> Result<bindings::FiTasksParkResult, Error> {
+        let task = worker_thread::current_task_id()?;
+        let handle = Arc::new(ParkHandle::new(task, key));
+        {
+            let mut queues = self.queues();
+            if !validate() {
+                return Ok(bindings::FiTasksParkResult {
+                    type_: bindings::FiTasksParkResultType::FI_TASKS_PARK_RESULT_TYPE_INVALID,
+                    data: std::ptr::null(),
+                });
+            }
+            queues.entry(key).or_default().push_back(ParkedTask {
+                handle: handle.clone(),
+                token: AssertSend(token),
+            });
+        }
+
+        before_sleep();
+        let suspended = worker_thread::park(handle.clone(), deadline);
+
+        // The task is either unparked, in which case it was removed from the queue, or it timed
+        // out, in which case it is still enqueued.
+        let mut queues = self.queues();
+        let key = {
+            let state = handle.state();
+            if let Some(AssertSend(token)) = state.unpark_token {
+                return Ok(bindings::FiTasksParkResult {
+                    type_: bindings::FiTasksParkResultType::FI_TASKS_PARK_RESULT_TYPE_UNPARKED,
+                    data: token,
+                });
+            }
+            state.key
+        };
+        let queue = queues.get_mut(&key).expect("parked task not found");
+        queue.retain(|parked| !Arc::ptr_eq(&parked.handle, &handle));
+        let was_last = queue.is_empty();
+        if was_last {
+            queues.remove(&key);
+        }
+
+        // The task was never suspended, if the request failed.
+        suspended?;
+        timed_out(key, was_last);
+        Ok(bindings::FiTasksParkResult {
+            type_: bindings::FiTasksParkResultType::FI_TASKS_PARK_RESULT_TYPE_TIMED_OUT,
+            data: std::ptr::null(),
+        })
+    }
+
+    /// Unparks the first task of the queue associated with `key`.
+    pub fn unpark_one(
+        &self,
+        key: usize,
+        callback: impl FnOnce(bindings::FiTasksUnparkResult) -> *const c_void,
+    ) -> bindings::FiTasksUnparkResult {
+        let mut result = empty_unpark_result();
+        let unparked = {
+            let mut queues = self.queues();
+            let unparked = queues.get_mut(&key).and_then(VecDeque::pop_front);
+            if unparked.is_some() {
+                result.unparked_tasks = 1;
+                result.has_more_tasks = queues.get(&key).is_some_and(|q| !q.is_empty());
+            }
+            remove_if_empty(&mut queues, key);
+
+            let token = callback(result);
+            unparked.map(|parked| parked.handle.set_unpark_token(token))
+        };
+
+        // The tasks are woken after releasing the lock, as it requires sending a message.
+        if let Some(handle) = unparked {
+            handle.wake();
+        }
+        result
+    }
+
+    /// Unparks all tasks of the queue associated with `key`.
+    pub fn unpark_all(&self, key: usize, token: *const c_void) -> usize {
+        let unparked = {
+            let mut queues = self.queues();
+            queues
+                .remove(&key)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|parked| parked.handle.set_unpark_token(token))
+                .collect::<Vec<_>>()
+        };
+
+        let num_unparked = unparked.len();
+        for handle in unparked {
+            handle.wake();
+        }
+        num_unparked
+    }
+
+    /// Removes the tasks of the queue associated with `key_from`, unparking or requeueing them
+    /// onto the queue associated with `key_to`, depending on the operation returned by
+    /// `validate`.
+    pub fn unpark_requeue(
+        &self,
+        key_from: usize,
+        key_to: usize,
+        validate: impl FnOnce() -> bindings::FiTasksRequeueOp,
+        callback: impl FnOnce(
+            bindings::FiTasksRequeueOp,
+            bindings::FiTasksUnparkResult,
+        ) -> *const c_void,
+    ) -> bindings::FiTasksUnparkResult {
+        use bindings::FiTasksRequeueOp as Op;
+
+        let mut result = empty_unpark_result();
+        let unparked = {
+            let mut queues = self.queues();
+            let op = validate();
+            if op == Op::FI_TASKS_REQUEUE_OP_ABORT {
+                return result;
+            }
+
+            let mut from = queues.remove(&key_from).unwrap_or_default();
+            let unparked = match op {
+                Op::FI_TASKS_REQUEUE_OP_UNPARK_ONE_REQUEUE_REST
+                | Op::FI_TASKS_REQUEUE_OP_UNPARK_ONE => from.pop_front(),
+                _ => None,
+            };
+            let requeued = match op {
+                Op::FI_TASKS_REQUEUE_OP_UNPARK_ONE_REQUEUE_REST
+                | Op::FI_TASKS_REQUEUE_OP_REQUEUE_ALL => std::mem::take(&mut from),
+                Op::FI_TASKS_REQUEUE_OP_REQUEUE_ONE => from.pop_front().into_iter().collect(),
+                _ => VecDeque::new(),
+            };
+            if !from.is_empty() {
+                queues.insert(key_from, from);
+            }
+
+            result.unparked_tasks = usize::from(unparked.is_some());
+            result.requeued_tasks = requeued.len();
+            if !requeued.is_empty() {
+                let to = queues.entry(key_to).or_default();
+                for parked in requeued {
+                    parked.handle.state().key = key_to;
+                    to.push_back(parked);
+                }
+                result.has_more_tasks = true;
+            } else {
+                result.has_more_tasks = unparked.is_some() && queues.contains_key(&key_from);
+            }
+
+            let token = callback(op, result);
+            unparked.map(|parked| parked.handle.set_unpark_token(token))
+        };
+
+        if let Some(handle) = unparked {
+            handle.wake();
+        }
+        result
+    }
+
+    /// Unparks the tasks of the queue associated with `key`, that are selected by `filter`.
+    pub fn unpark_filter(
+        &self,
+        key: usize,
+        mut filter: impl FnMut(*const c_void) -> bindings::FiTasksUnparkFilterOp,
+        callback: impl FnOnce(bindings::FiTasksUnparkResult) -> *const c_void,
+    ) -> bindings::FiTasksUnparkResult {
+        use bindings::FiTasksUnparkFilterOp as Op;
+
+        let mut result = empty_unpark_result();
+        let unparked = {
+            let mut queues = self.queues();
+            let mut unparked = Vec::new();
+            if let Some(queue) = queues.get_mut(&key) {
+                let mut remaining = VecDeque::with_capacity(queue.len());
+                while let Some(parked) = queue.pop_front() {
+                    match filter(parked.token.0) {
+                        Op::FI_TASKS_UNPARK_FILTER_OP_UNPARK => unparked.push(parked),
+                        Op::FI_TASKS_UNPARK_FILTER_OP_STOP => {
+                            remaining.push_back(parked);
+                            break;
+                        }
+                        _ => remaining.push_back(parked),
+                    }
+                }
+                remaining.append(queue);
+                *queue = remaining;
+            }
+            remove_if_empty(&mut queues, key);
+
+            result.unparked_tasks = unparked.len();
+            result.has_more_tasks = !unparked.is_empty() && queues.contains_key(&key);
+            let token = callback(result);
+            unparked
+                .into_iter()
+                .map(|parked| parked.handle.set_unpark_token(token))
+                .collect::<Vec<_>>()
+        };
+
+        for handle in unparked {
+            handle.wake();
+        }
+        result
+    }
+}
+
+fn empty_unpark_result() -> bindings::FiTasksUnparkResult {
+    bindings::FiTasksUnparkResult {
+        unparked_tasks: 0,
+        requeued_tasks: 0,
+        has_more_tasks: false,
+        be_fair: false,
+    }
+}
+
+fn remove_if_empty(queues: &mut FxHashMap<usize, VecDeque<ParkedTask>>, key: usize) {
+    if queues.get(&key).is_some_and(VecDeque::is_empty) {
+        queues.remove(&key);
+    }
+}
+
+/// State of a parked task, shared between the parking lot and the event loop of its group.
+///
+/// A parked task is woken exactly once, either by an unpark operation, or by its timeout.
+pub struct ParkHandle {
+    task: TaskId,
+    state: Mutex<ParkState>,
+}
+
+struct ParkState {
+    key: usize,
+    unpark_token: Option<AssertSend<*const c_void>>,
+    woken: bool,
+    waker: Option<Waker>,
+}
+
+impl ParkHandle {
+    fn new(task: TaskId, key: usize) -> Self {
+        Self {
+            task,
+            state: Mutex::new(ParkState {
+                key,
+                unpark_token: None,
+                woken: false,
+                waker: None,
+            }),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, ParkState> {
+        self.state.lock().expect("could not lock park handle")
+    }
+
+    /// Returns whether the task has already been woken.
+    pub fn is_woken(&self) -> bool {
+        self.state().woken
+    }
+
+    /// Registers the function waking the suspended task.
+    ///
+    /// Returns `false` if the task has already been woken, in which case it must be resumed by
+    /// the caller.
+    pub fn register_waker(&self, waker: impl FnOnce() + Send + 'static) -> bool {
+        let mut state = self.state();
+        if state.woken {
+            false
+        } else {
+            state.waker = Some(Box::new(waker));
+            true
+        }
+    }
+
+    /// Marks the task as woken by its timeout.
+    ///
+    /// Returns the task, if it was not woken before.
+    pub fn try_time_out(&self) -> Option<TaskId> {
+        let mut state = self.state();
+        if state.woken {
+            None
+        } else {
+            state.woken = true;
+            state.waker = None;
+            Some(self.task)
+        }
+    }
+
+    /// Passes the token to the task. Must be called while the queues are locked.
+    fn set_unpark_token(self: Arc<Self>, token: *const c_void) -> Arc<Self> {
+        self.state().unpark_token = Some(AssertSend(token));
+        self
+    }
+
+    fn wake(&self) {
+        let waker = {
+            let mut state = self.state();
+            if state.woken {
+                return;
+            }
+            state.woken = true;
+            state.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker();
+        }
+    }
+}
+
+impl Debug for ParkHandle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParkHandle")
+            .field("task", &self.task)
+            .finish_non_exhaustive()
+    }
+}
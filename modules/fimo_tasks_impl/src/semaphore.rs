@@ -0,0 +1,166 @@
+use fimo_std::{
+    bindings as std_bindings,
+    error::Error,
+    ffi::{FFISharable, FFITransferable},
+};
+use fimo_tasks::bindings;
+use std::{
+    fmt::{Debug, Formatter},
+    sync::{Arc, Mutex},
+};
+
+type Listener = Box<dyn FnOnce() + Send>;
+
+/// Timeline semaphore shared between the worker groups.
+pub struct SemaphoreImpl {
+    state: Mutex<SemaphoreState>,
+}
+
+struct SemaphoreState {
+    value: u64,
+    listeners: Vec<(u64, Listener)>,
+}
+
+impl SemaphoreImpl {
+    pub fn new(initial_value: u64) -> Arc<Self> {
+        Arc::new(Self {
+            state: Mutex::new(SemaphoreState {
+                value: initial_value,
+                listeners: Vec::new(),
+            }),
+        })
+    }
+
+    pub fn value(&self) -> u64 {
+        let state = self.state.lock().expect("could not lock semaphore");
+        state.value
+    }
+
+    /// Sets the counter to `value` and invokes the listeners waiting on a smaller or equal value.
+    pub fn signal(&self, value: u64) -> Result<(), Error> {
+        let ready = {
+            let mut state = self.state.lock().expect("could not lock semaphore");
+            if value <= state.value {
+                return Err(Error::EINVAL);
+            }
+            state.value = value;
+
+            let (ready, pending) = std::mem::take(&mut state.listeners)
+                .into_iter()
+                .partition::<Vec<_>, _>(|(target, _)| *target <= value);
+            state.listeners = pending;
+            ready
+        };
+
+        // The listeners are invoked without holding the lock, as they may access the semaphore.
+        for (_, listener) in ready {
+            listener();
+        }
+        Ok(())
+    }
+
+    /// Invokes `f` once the counter reaches `value`.
+    ///
+    /// If the counter has already reached the value, `f` is invoked immediately.
+    pub fn on_reached(&self, value: u64, f: impl FnOnce() + Send + 'static) {
+        let mut state = self.state.lock().expect("could not lock semaphore");
+        if state.value >= value {
+            drop(state);
+            f();
+        } else {
+            state.listeners.push((value, Box::new(f)));
+        }
+    }
+}
+
+impl Debug for SemaphoreImpl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SemaphoreImpl")
+            .field("value", &self.value())
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct SemaphoreFFI(pub Arc<SemaphoreImpl>);
+
+impl SemaphoreFFI {
+    const VTABLE: &bindings::FiTasksSemaphoreVTable = &bindings::FiTasksSemaphoreVTable {
+        v0: bindings::FiTasksSemaphoreVTableV0 {
+            acquire: Some(Self::acquire),
+            release: Some(Self::release),
+            value: Some(Self::value),
+            signal: Some(Self::signal),
+        },
+    };
+
+    unsafe extern "C" fn acquire(this: *mut std::ffi::c_void) {
+        fimo_std::panic::abort_on_panic(|| {
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+
+            // Safety: Is always in an Arc.
+            unsafe { Arc::increment_strong_count(this) };
+        });
+    }
+
+    unsafe extern "C" fn release(this: *mut std::ffi::c_void) {
+        fimo_std::panic::abort_on_panic(|| {
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+
+            // Safety: Is always in an Arc.
+            unsafe { Arc::decrement_strong_count(this) };
+        });
+    }
+
+    unsafe extern "C" fn value(this: *mut std::ffi::c_void) -> u64 {
+        fimo_std::panic::abort_on_panic(|| {
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+            this.value()
+        })
+    }
+
+    unsafe extern "C" fn signal(
+        this: *mut std::ffi::c_void,
+        value: u64,
+    ) -> std_bindings::FimoResult {
+        fimo_std::panic::catch_unwind(|| {
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+            this.signal(value)
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
+    }
+}
+
+impl FFISharable<*mut std::ffi::c_void> for SemaphoreFFI {
+    type BorrowedView<'a> = &'a SemaphoreImpl;
+
+    fn share_to_ffi(&self) -> *mut std::ffi::c_void {
+        Arc::as_ptr(&self.0).cast_mut().cast()
+    }
+
+    unsafe fn borrow_from_ffi<'a>(ffi: *mut std::ffi::c_void) -> Self::BorrowedView<'a> {
+        // Safety: Is sound if `ffi` is the result of `Self::share_to_ffi`.
+        unsafe { &*ffi.cast_const().cast() }
+    }
+}
+
+impl FFITransferable<bindings::FiTasksSemaphore> for SemaphoreFFI {
+    fn into_ffi(self) -> bindings::FiTasksSemaphore {
+        bindings::FiTasksSemaphore {
+            data: Arc::into_raw(self.0).cast_mut().cast(),
+            vtable: Self::VTABLE,
+        }
+    }
+
+    unsafe fn from_ffi(ffi: bindings::FiTasksSemaphore) -> Self {
+        // Safety: Is always in an `Arc`.
+        unsafe { Self(Arc::from_raw(ffi.data.cast_const().cast())) }
+    }
+}
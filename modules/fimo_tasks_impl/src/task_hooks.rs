@@ -0,0 +1,253 @@
+use fimo_std::{
+    context::Context as StdContext,
+    ffi::{FFISharable, FFITransferable},
+    time::ClockSubsystem,
+};
+use fimo_tasks::{bindings, TaskId, WorkerGroupId, WorkerId};
+use std::{
+    ffi::c_void,
+    fmt::{Debug, Formatter},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, RwLock, Weak,
+    },
+};
+
+/// Registry of the hooks observing the lifecycle of the tasks.
+pub struct TaskHooks {
+    context: StdContext,
+    count: AtomicUsize,
+    hooks: RwLock<Arc<[Arc<TaskHookImpl>]>>,
+}
+
+impl TaskHooks {
+    pub fn new(context: StdContext) -> Arc<Self> {
+        Arc::new(Self {
+            context,
+            count: AtomicUsize::new(0),
+            hooks: RwLock::new(Arc::new([])),
+        })
+    }
+
+    /// # Safety
+    ///
+    /// `on_event` and `cleanup` must be safe to invoke with `data` from any thread.
+    pub unsafe fn register(
+        self: &Arc<Self>,
+        on_event: unsafe extern "C" fn(*mut c_void, *const bindings::FiTasksTaskEvent),
+        data: *mut c_void,
+        cleanup: Option<unsafe extern "C" fn(*mut c_void)>,
+    ) -> Arc<TaskHookImpl> {
+        let hook = Arc::new(TaskHookImpl {
+            active: AtomicBool::new(true),
+            registry: Arc::downgrade(self),
+            callback: Callback {
+                on_event,
+                data,
+                cleanup,
+            },
+        });
+
+        let mut guard = self.hooks.write().expect("could not lock task hooks");
+        *guard = guard.iter().cloned().chain([hook.clone()]).collect();
+        self.count.store(guard.len(), Ordering::Release);
+        hook
+    }
+
+    fn remove(&self, hook: &TaskHookImpl) {
+        let mut guard = self.hooks.write().expect("could not lock task hooks");
+        *guard = guard
+            .iter()
+            .filter(|x| !std::ptr::eq(Arc::as_ptr(x), hook))
+            .cloned()
+            .collect();
+        self.count.store(guard.len(), Ordering::Release);
+    }
+
+    /// Removes all hooks.
+    pub fn clear(&self) {
+        let hooks = {
+            let mut guard = self.hooks.write().expect("could not lock task hooks");
+            self.count.store(0, Ordering::Release);
+            std::mem::replace(&mut *guard, Arc::new([]))
+        };
+        for hook in hooks.iter() {
+            hook.active.store(false, Ordering::Release);
+        }
+    }
+
+    /// Notifies the registered hooks of an event.
+    ///
+    /// Does not construct the event, if no hook is registered.
+    pub fn emit(
+        &self,
+        event_type: bindings::FiTasksTaskEventType,
+        task: TaskId,
+        group: WorkerGroupId,
+        worker: Option<WorkerId>,
+    ) {
+        if self.count.load(Ordering::Acquire) == 0 {
+            return;
+        }
+
+        // The hooks are invoked without holding the lock, as they may remove themselves.
+        let hooks = self
+            .hooks
+            .read()
+            .expect("could not lock task hooks")
+            .clone();
+        let event = bindings::FiTasksTaskEvent {
+            type_: event_type,
+            task_id: task.0,
+            worker_group_id: group.0,
+            has_worker: worker.is_some(),
+            worker_id: worker.map_or(0, |x| x.0),
+            time: self.context.now().into_ffi(),
+        };
+        for hook in hooks.iter().filter(|x| x.is_active()) {
+            // Safety: Is guaranteed by the creator of the hook.
+            unsafe { (hook.callback.on_event)(hook.callback.data, &event) };
+        }
+    }
+}
+
+impl Debug for TaskHooks {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskHooks")
+            .field("count", &self.count)
+            .finish_non_exhaustive()
+    }
+}
+
+struct Callback {
+    on_event: unsafe extern "C" fn(*mut c_void, *const bindings::FiTasksTaskEvent),
+    data: *mut c_void,
+    cleanup: Option<unsafe extern "C" fn(*mut c_void)>,
+}
+
+// Safety: The creator of the hook guarantees that the callback may be invoked from any thread.
+unsafe impl Send for Callback {}
+
+// Safety: The creator of the hook guarantees that the callback may be invoked from any thread.
+unsafe impl Sync for Callback {}
+
+impl Drop for Callback {
+    fn drop(&mut self) {
+        if let Some(cleanup) = self.cleanup {
+            // Safety: The creator of the hook guarantees that the function is safe to call.
+            unsafe { cleanup(self.data) }
+        }
+    }
+}
+
+/// Hook registered in the [`TaskHooks`] registry.
+///
+/// The callback is cleaned up once the hook has been removed, and all references to it have been
+/// released.
+pub struct TaskHookImpl {
+    active: AtomicBool,
+    registry: Weak<TaskHooks>,
+    callback: Callback,
+}
+
+impl TaskHookImpl {
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::Acquire)
+    }
+
+    /// Removes the hook from its registry.
+    pub fn remove(&self) {
+        if !self.active.swap(false, Ordering::AcqRel) {
+            return;
+        }
+        if let Some(registry) = self.registry.upgrade() {
+            registry.remove(self);
+        }
+    }
+}
+
+impl Debug for TaskHookImpl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskHookImpl")
+            .field("active", &self.active)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct TaskHookFFI(pub Arc<TaskHookImpl>);
+
+impl TaskHookFFI {
+    const VTABLE: &'static bindings::FiTasksTaskHookVTable = &bindings::FiTasksTaskHookVTable {
+        v0: bindings::FiTasksTaskHookVTableV0 {
+            acquire: Some(Self::acquire),
+            release: Some(Self::release),
+            remove: Some(Self::remove),
+            is_active: Some(Self::is_active),
+        },
+    };
+
+    unsafe extern "C" fn acquire(this: *mut c_void) {
+        fimo_std::panic::abort_on_panic(|| {
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+
+            // Safety: Is always in an Arc.
+            unsafe { Arc::increment_strong_count(this) };
+        });
+    }
+
+    unsafe extern "C" fn release(this: *mut c_void) {
+        fimo_std::panic::abort_on_panic(|| {
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+
+            // Safety: Is always in an Arc.
+            unsafe { Arc::decrement_strong_count(this) };
+        });
+    }
+
+    unsafe extern "C" fn remove(this: *mut c_void) {
+        fimo_std::panic::abort_on_panic(|| {
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+            this.remove();
+        });
+    }
+
+    unsafe extern "C" fn is_active(this: *mut c_void) -> bool {
+        fimo_std::panic::abort_on_panic(|| {
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+            this.is_active()
+        })
+    }
+}
+
+impl FFISharable<*mut c_void> for TaskHookFFI {
+    type BorrowedView<'a> = &'a TaskHookImpl;
+
+    fn share_to_ffi(&self) -> *mut c_void {
+        Arc::as_ptr(&self.0).cast_mut().cast()
+    }
+
+    unsafe fn borrow_from_ffi<'a>(ffi: *mut c_void) -> Self::BorrowedView<'a> {
+        // Safety: Is sound if `ffi` is the result of `Self::share_to_ffi`.
+        unsafe { &*ffi.cast_const().cast() }
+    }
+}
+
+impl FFITransferable<bindings::FiTasksTaskHook> for TaskHookFFI {
+    fn into_ffi(self) -> bindings::FiTasksTaskHook {
+        bindings::FiTasksTaskHook {
+            data: Arc::into_raw(self.0).cast_mut().cast(),
+            vtable: Self::VTABLE,
+        }
+    }
+
+    unsafe fn from_ffi(ffi: bindings::FiTasksTaskHook) -> Self {
+        // Safety: Is always in an `Arc`.
+        unsafe { Self(Arc::from_raw(ffi.data.cast_const().cast())) }
+    }
+}
@@ -1,27 +1,66 @@
-use crate::{worker_group::worker_thread::with_worker_context_lock, RuntimeShared};
+use crate::{
+    task_hooks::TaskHooks, worker_group::worker_thread::with_worker_context_lock, RuntimeShared,
+};
+use affinity::AffinityTable;
+use allocator::GroupAllocator;
 use command_buffer::{CommandBufferHandleFFI, CommandBufferHandleImpl};
 use event_loop::{stack_manager::StackDescriptor, EventLoopHandle};
 use fimo_std::{
     error::Error,
     ffi::{FFISharable, FFITransferable},
+    time::Time,
 };
-use fimo_tasks::{bindings, WorkerGroupId};
+use fimo_tasks::{bindings, WorkerGroupId, WorkerId};
+use services::ServiceRegistry;
+use shutdown::{ShutdownListener, ShutdownMode};
+use stats::GroupStats;
 use std::{
     ffi::{CStr, CString},
     fmt::Debug,
-    sync::{Arc, RwLock},
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+    time::{Duration, Instant},
 };
-
+use task_registry::TaskRegistry;
+use task_times::TaskTimesTable;
+use timer::{TimerFFI, TimerImpl};
+use worker_thread::StealStats;
+
+pub mod affinity;
+pub mod allocator;
+mod cleanup;
 pub mod command_buffer;
 pub mod event_loop;
+mod services;
+pub mod shutdown;
+mod stack_overflow;
+mod stats;
 mod task;
+mod task_registry;
+mod task_times;
+pub mod timer;
 pub mod worker_thread;
 
 pub struct WorkerGroupImpl {
     id: WorkerGroupId,
     name: CString,
     visible: bool,
+    steal_batch_size: usize,
+    detect_deadlocks: bool,
+    track_allocations: bool,
+    time_slice: Duration,
     event_loop: RwLock<Option<event_loop::EventLoopHandle>>,
+    task_times: TaskTimesTable,
+    steal_stats: StealStats,
+    stats: GroupStats,
+    tasks: TaskRegistry,
+    affinity: AffinityTable,
+    abort_pending_tasks: AtomicBool,
+    allocator: GroupAllocator,
+    services: ServiceRegistry,
     runtime: Arc<RuntimeShared>,
 }
 
@@ -33,21 +72,42 @@ impl WorkerGroupImpl {
         name: CString,
         visible: bool,
         num_workers: usize,
+        steal_batch_size: usize,
+        worker_cpus: Vec<Option<Box<[usize]>>>,
+        detect_deadlocks: bool,
+        track_allocations: bool,
+        time_slice: Duration,
         default_stack_size: usize,
         stacks: Vec<StackDescriptor>,
+        allocator: GroupAllocator,
         runtime: Arc<RuntimeShared>,
     ) -> Arc<Self> {
         let _span = fimo_std::span_trace!(
             ctx,
             "id: {id:?}, name: {name:?}, visible: {visible:?}, num_workers: {num_workers:?}, \
-            default_stack_size: {default_stack_size:?}, stacks: {stacks:?}, runtime: {runtime:?}"
+            steal_batch_size: {steal_batch_size:?}, worker_cpus: {worker_cpus:?}, \
+            detect_deadlocks: {detect_deadlocks:?}, track_allocations: {track_allocations:?}, \
+            time_slice: {time_slice:?}, default_stack_size: {default_stack_size:?}, \
+            stacks: {stacks:?}, allocator: {allocator:?}, runtime: {runtime:?}"
         );
         fimo_std::emit_trace!(ctx, "constructing worker group");
         let this = Arc::new(Self {
             id,
             name,
             visible,
+            steal_batch_size,
+            detect_deadlocks,
+            track_allocations,
+            time_slice,
             event_loop: RwLock::new(None),
+            task_times: Default::default(),
+            steal_stats: Default::default(),
+            stats: Default::default(),
+            tasks: Default::default(),
+            affinity: Default::default(),
+            abort_pending_tasks: AtomicBool::new(false),
+            allocator,
+            services: Default::default(),
             runtime,
         });
 
@@ -60,6 +120,7 @@ impl WorkerGroupImpl {
                 ctx,
                 this.clone(),
                 num_workers,
+                worker_cpus,
                 default_stack_size,
                 stacks,
             ));
@@ -80,6 +141,64 @@ impl WorkerGroupImpl {
         self.visible
     }
 
+    pub fn steal_batch_size(&self) -> usize {
+        self.steal_batch_size
+    }
+
+    pub fn detect_deadlocks(&self) -> bool {
+        self.detect_deadlocks
+    }
+
+    pub fn track_allocations(&self) -> bool {
+        self.track_allocations
+    }
+
+    pub fn time_slice(&self) -> Duration {
+        self.time_slice
+    }
+
+    pub fn task_times(&self) -> &TaskTimesTable {
+        &self.task_times
+    }
+
+    pub fn steal_stats(&self) -> &StealStats {
+        &self.steal_stats
+    }
+
+    pub fn stats(&self) -> &GroupStats {
+        &self.stats
+    }
+
+    pub fn tasks(&self) -> &TaskRegistry {
+        &self.tasks
+    }
+
+    pub fn task_hooks(&self) -> &TaskHooks {
+        self.runtime.task_hooks()
+    }
+
+    pub fn affinity(&self) -> &AffinityTable {
+        &self.affinity
+    }
+
+    pub fn allocator(&self) -> &GroupAllocator {
+        &self.allocator
+    }
+
+    pub fn services(&self) -> &ServiceRegistry {
+        &self.services
+    }
+
+    /// Returns whether the workers abort the tasks they have not started yet.
+    pub fn aborts_pending_tasks(&self) -> bool {
+        self.abort_pending_tasks.load(Ordering::Acquire)
+    }
+
+    /// Instructs the workers to abort the tasks they have not started yet.
+    pub fn abort_pending_tasks(&self) {
+        self.abort_pending_tasks.store(true, Ordering::Release);
+    }
+
     pub fn is_open(&self) -> bool {
         let guard = self
             .event_loop
@@ -97,12 +216,20 @@ impl WorkerGroupImpl {
     }
 
     pub fn request_close(&self) -> Result<(), Error> {
+        self.request_shutdown(ShutdownMode::Graceful, None)
+    }
+
+    pub fn request_shutdown(
+        &self,
+        mode: ShutdownMode,
+        listener: Option<ShutdownListener>,
+    ) -> Result<(), Error> {
         let guard = self
             .event_loop
             .read()
             .expect("failed to lock event loop handle");
         if let Some(handle) = guard.as_ref() {
-            handle.request_close()?;
+            handle.request_shutdown(mode, listener)?;
             self.runtime.shutdown_worker_group(self.id());
         }
         Ok(())
@@ -119,6 +246,29 @@ impl WorkerGroupImpl {
         unsafe { CommandBufferHandleImpl::new(self, buffer) }
     }
 
+    pub fn add_timer(&self, deadline: Instant, timer: Arc<TimerImpl>) -> Result<(), Error> {
+        let guard = self
+            .event_loop
+            .read()
+            .expect("failed to lock event loop handle");
+        match guard.as_ref() {
+            Some(handle) => handle.add_timer(deadline, timer),
+            None => Err(Error::EINVAL),
+        }
+    }
+
+    /// Requests to change the number of workers of the group.
+    pub fn resize(&self, num_workers: NonZeroUsize) -> Result<(), Error> {
+        let guard = self
+            .event_loop
+            .read()
+            .expect("failed to lock event loop handle");
+        match guard.as_ref() {
+            Some(handle) => handle.resize(num_workers),
+            None => Err(Error::EINVAL),
+        }
+    }
+
     pub fn wait_for_close(&self) {
         self.request_close()
             .expect("could not request to close the event loop");
@@ -139,6 +289,10 @@ impl Debug for WorkerGroupImpl {
             .field("id", &self.id)
             .field("name", &self.name)
             .field("visible", &self.visible)
+            .field("steal_batch_size", &self.steal_batch_size)
+            .field("detect_deadlocks", &self.detect_deadlocks)
+            .field("track_allocations", &self.track_allocations)
+            .field("time_slice", &self.time_slice)
             .field("event_loop", &self.event_loop)
             .finish_non_exhaustive()
     }
@@ -162,6 +316,20 @@ impl WorkerGroupFFI {
                 workers: Some(Self::workers),
                 stack_sizes: Some(Self::stack_sizes),
                 enqueue_buffer: Some(Self::enqueue_buffer),
+                task_times: Some(Self::task_times),
+                steal_stats: Some(Self::steal_stats),
+                worker_affinity: Some(Self::worker_affinity),
+                stats: Some(Self::stats),
+                worker_stats: Some(Self::worker_stats),
+                stack_stats: Some(Self::stack_stats),
+                create_timer: Some(Self::create_timer),
+                request_shutdown: Some(Self::request_shutdown),
+                alloc: Some(Self::alloc),
+                free: Some(Self::free),
+                enumerate_tasks: Some(Self::enumerate_tasks),
+                resize: Some(Self::resize),
+                service_register: Some(Self::service_register),
+                service_get: Some(Self::service_get),
             },
         };
 
@@ -285,6 +453,377 @@ impl WorkerGroupFFI {
         .flatten()
         .into_ffi()
     }
+
+    unsafe extern "C" fn task_times(
+        this: *mut std::ffi::c_void,
+        times: *mut *mut bindings::FiTasksTaskTimes,
+        count: *mut usize,
+    ) -> fimo_std::bindings::FimoResult {
+        fimo_std::panic::catch_unwind(|| {
+            if this.is_null() || times.is_null() || count.is_null() {
+                return Err(Error::EINVAL);
+            }
+
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+            let snapshot = this.task_times().snapshot();
+            let len = snapshot.len();
+
+            // Safety: We assume that the pointers can be dereferenced.
+            unsafe {
+                times.write(Box::into_raw(snapshot).cast());
+                count.write(len);
+            }
+            Ok(())
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
+    }
+
+    unsafe extern "C" fn steal_stats(
+        this: *mut std::ffi::c_void,
+        stats: *mut bindings::FiTasksWorkerGroupStealStats,
+    ) -> fimo_std::bindings::FimoResult {
+        fimo_std::panic::catch_unwind(|| {
+            if this.is_null() || stats.is_null() {
+                return Err(Error::EINVAL);
+            }
+
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+
+            // Safety: We assume that the pointer can be dereferenced.
+            unsafe { stats.write(this.steal_stats().snapshot()) };
+            Ok(())
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
+    }
+
+    unsafe extern "C" fn worker_affinity(
+        this: *mut std::ffi::c_void,
+        worker: usize,
+        cpus: *mut *mut usize,
+        count: *mut usize,
+    ) -> fimo_std::bindings::FimoResult {
+        fimo_std::panic::catch_unwind(|| {
+            if this.is_null() || cpus.is_null() || count.is_null() {
+                return Err(Error::EINVAL);
+            }
+
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+            let affinity = this.affinity().worker_affinity(WorkerId(worker))?;
+            let len = affinity.len();
+
+            // Safety: We assume that the pointers can be dereferenced.
+            unsafe {
+                cpus.write(Box::into_raw(affinity).cast());
+                count.write(len);
+            }
+            Ok(())
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
+    }
+
+    unsafe extern "C" fn stats(
+        this: *mut std::ffi::c_void,
+        stats: *mut bindings::FiTasksWorkerGroupStats,
+    ) -> fimo_std::bindings::FimoResult {
+        fimo_std::panic::catch_unwind(|| {
+            if this.is_null() || stats.is_null() {
+                return Err(Error::EINVAL);
+            }
+
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+
+            // Safety: We assume that the pointer can be dereferenced.
+            unsafe { stats.write(this.stats().snapshot()) };
+            Ok(())
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
+    }
+
+    unsafe extern "C" fn worker_stats(
+        this: *mut std::ffi::c_void,
+        stats: *mut *mut bindings::FiTasksWorkerStats,
+        count: *mut usize,
+    ) -> fimo_std::bindings::FimoResult {
+        fimo_std::panic::catch_unwind(|| {
+            if this.is_null() || stats.is_null() || count.is_null() {
+                return Err(Error::EINVAL);
+            }
+
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+            let snapshot = this.stats().worker_snapshot();
+            let len = snapshot.len();
+
+            // Safety: We assume that the pointers can be dereferenced.
+            unsafe {
+                stats.write(Box::into_raw(snapshot).cast());
+                count.write(len);
+            }
+            Ok(())
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
+    }
+
+    unsafe extern "C" fn stack_stats(
+        this: *mut std::ffi::c_void,
+        stats: *mut *mut bindings::FiTasksStackStats,
+        count: *mut usize,
+    ) -> fimo_std::bindings::FimoResult {
+        fimo_std::panic::catch_unwind(|| {
+            if this.is_null() || stats.is_null() || count.is_null() {
+                return Err(Error::EINVAL);
+            }
+
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+            let snapshot = this.stats().stack_snapshot();
+            let len = snapshot.len();
+
+            // Safety: We assume that the pointers can be dereferenced.
+            unsafe {
+                stats.write(Box::into_raw(snapshot).cast());
+                count.write(len);
+            }
+            Ok(())
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
+    }
+
+    unsafe extern "C" fn create_timer(
+        this: *mut std::ffi::c_void,
+        config: *const bindings::FiTasksTimerConfig,
+        timer: *mut bindings::FiTasksTimer,
+    ) -> fimo_std::bindings::FimoResult {
+        fimo_std::panic::catch_unwind(|| {
+            if this.is_null() || config.is_null() || timer.is_null() {
+                return Err(Error::EINVAL);
+            }
+
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+            // Safety: We assume that the pointer can be dereferenced.
+            let bindings::FiTasksTimerConfig {
+                next,
+                deadline,
+                period,
+                callback,
+                data,
+                cleanup,
+            } = unsafe { config.read() };
+            if !next.is_null() {
+                return Err(Error::EINVAL);
+            }
+            let Some(callback) = callback else {
+                return Err(Error::EINVAL);
+            };
+
+            let deadline = timer::instant_from_time(
+                *this.runtime.context,
+                // Safety: The time is passed by value.
+                unsafe { Time::from_ffi(deadline) },
+            );
+            let period = std::time::Duration::new(period.secs, period.nanos);
+            let period = (!period.is_zero()).then_some(period);
+
+            // Safety: We assume that the callback is safe to invoke from the event loop.
+            let timer_impl = unsafe { TimerImpl::new(period, callback, data, cleanup) };
+            if let Err(e) = this.add_timer(deadline, timer_impl.clone()) {
+                // The caller retains the ownership of the data on failure.
+                timer_impl.discard();
+                return Err(e);
+            }
+
+            // Safety: Again, we assume that the pointer can be dereferenced.
+            unsafe { timer.write(TimerFFI(timer_impl).into_ffi()) };
+            Ok(())
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
+    }
+
+    unsafe extern "C" fn request_shutdown(
+        this: *mut std::ffi::c_void,
+        config: *const bindings::FiTasksShutdownConfig,
+    ) -> fimo_std::bindings::FimoResult {
+        fimo_std::panic::catch_unwind(|| {
+            if this.is_null() || config.is_null() {
+                return Err(Error::EINVAL);
+            }
+
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+            // Safety: We assume that the pointer can be dereferenced.
+            let bindings::FiTasksShutdownConfig {
+                next,
+                mode,
+                on_complete,
+                data,
+            } = unsafe { config.read() };
+            if !next.is_null() {
+                return Err(Error::EINVAL);
+            }
+            let mode = ShutdownMode::try_from(mode)?;
+
+            // Safety: We assume that the function is safe to invoke from any thread.
+            let listener = on_complete.map(|f| unsafe { ShutdownListener::new(f, data) });
+            this.request_shutdown(mode, listener)
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
+    }
+
+    unsafe extern "C" fn alloc(
+        this: *mut std::ffi::c_void,
+        size: usize,
+        alignment: usize,
+        ptr: *mut *mut std::ffi::c_void,
+    ) -> fimo_std::bindings::FimoResult {
+        fimo_std::panic::catch_unwind(|| {
+            if this.is_null() || ptr.is_null() {
+                return Err(Error::EINVAL);
+            }
+
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+            let block = this.allocator().allocate(size, alignment)?;
+
+            // Safety: We assume that the pointer can be dereferenced.
+            unsafe { ptr.write(block.as_ptr().cast()) };
+            Ok(())
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
+    }
+
+    unsafe extern "C" fn free(
+        this: *mut std::ffi::c_void,
+        ptr: *mut std::ffi::c_void,
+        size: usize,
+        alignment: usize,
+    ) {
+        fimo_std::panic::abort_on_panic(|| {
+            let Some(ptr) = std::ptr::NonNull::new(ptr.cast()) else {
+                return;
+            };
+
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+            // Safety: The caller ensures that the block was allocated by the worker group.
+            unsafe { this.allocator().deallocate(ptr, size, alignment) };
+        })
+    }
+
+    unsafe extern "C" fn enumerate_tasks(
+        this: *mut std::ffi::c_void,
+        infos: *mut *mut bindings::FiTasksTaskInfo,
+        count: *mut usize,
+    ) -> fimo_std::bindings::FimoResult {
+        fimo_std::panic::catch_unwind(|| {
+            if this.is_null() || infos.is_null() || count.is_null() {
+                return Err(Error::EINVAL);
+            }
+
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+            let (snapshot, len) = this.tasks().snapshot();
+
+            // Safety: We assume that the pointers can be dereferenced.
+            unsafe {
+                infos.write(snapshot);
+                count.write(len);
+            }
+            Ok(())
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
+    }
+
+    unsafe extern "C" fn resize(
+        this: *mut std::ffi::c_void,
+        num_workers: usize,
+    ) -> fimo_std::bindings::FimoResult {
+        fimo_std::panic::catch_unwind(|| {
+            let Some(num_workers) = NonZeroUsize::new(num_workers) else {
+                return Err(Error::EINVAL);
+            };
+            if this.is_null() {
+                return Err(Error::EINVAL);
+            }
+
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+            this.resize(num_workers)
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
+    }
+
+    unsafe extern "C" fn service_register(
+        this: *mut std::ffi::c_void,
+        name: *const std::ffi::c_char,
+        service: *mut std::ffi::c_void,
+        dtor: bindings::FiTasksGroupServiceDtor,
+    ) -> fimo_std::bindings::FimoResult {
+        fimo_std::panic::catch_unwind(|| {
+            if this.is_null() || name.is_null() {
+                return Err(Error::EINVAL);
+            }
+
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+            // Safety: We assume that the name is a valid string.
+            let name = unsafe { CStr::from_ptr(name) };
+            this.services().register(name, service, dtor)
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
+    }
+
+    unsafe extern "C" fn service_get(
+        this: *mut std::ffi::c_void,
+        name: *const std::ffi::c_char,
+        service: *mut *mut std::ffi::c_void,
+    ) -> fimo_std::bindings::FimoResult {
+        fimo_std::panic::catch_unwind(|| {
+            if this.is_null() || name.is_null() || service.is_null() {
+                return Err(Error::EINVAL);
+            }
+
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+            // Safety: We assume that the name is a valid string.
+            let name = unsafe { CStr::from_ptr(name) };
+            let data = this.services().get(name)?;
+            // Safety: We checked that the pointer is not null.
+            unsafe { service.write(data) };
+            Ok(())
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
+    }
 }
 
 impl FFISharable<*mut std::ffi::c_void> for WorkerGroupFFI {
@@ -0,0 +1,240 @@
+use fimo_std::error::Error;
+use fimo_tasks::WorkerId;
+use rustc_hash::FxHashMap;
+use std::{sync::Mutex, thread::JoinHandle};
+
+/// Policy for pinning the workers without an explicit cpu set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AffinityPolicy {
+    None,
+    Spread,
+    Compact,
+}
+
+/// Affinity requested for the workers of a worker group.
+#[derive(Debug, Clone)]
+pub struct AffinityRequest {
+    pub policy: AffinityPolicy,
+    pub numa_node: Option<usize>,
+    pub worker_cpus: Vec<Box<[usize]>>,
+}
+
+impl AffinityRequest {
+    /// Resolves the cpu set of each worker.
+    ///
+    /// Workers mapped to `None` are not pinned.
+    pub fn resolve(&self, num_workers: usize) -> Result<Vec<Option<Box<[usize]>>>, Error> {
+        let allowed = sys::available_cpus()?;
+        let available = match self.numa_node {
+            None => allowed,
+            Some(node) => sys::numa_node_cpus(node)?
+                .into_iter()
+                .filter(|cpu| allowed.contains(cpu))
+                .collect(),
+        };
+        if available.is_empty() {
+            return Err(Error::from_string(
+                c"no cpus available for the worker group",
+            ));
+        }
+
+        let num_available = available.len();
+        let cpus = (0..num_workers)
+            .map(|i| {
+                if let Some(cpus) = self.worker_cpus.get(i).filter(|x| !x.is_empty()) {
+                    return Some(cpus.clone());
+                }
+
+                match self.policy {
+                    AffinityPolicy::None if self.numa_node.is_none() => None,
+                    AffinityPolicy::None => Some(available.clone().into_boxed_slice()),
+                    AffinityPolicy::Compact => Some(Box::new([available[i % num_available]])),
+                    AffinityPolicy::Spread => {
+                        // Leave the largest possible gap between the cpus of two workers.
+                        let index = if num_workers >= num_available {
+                            i % num_available
+                        } else {
+                            i * num_available / num_workers
+                        };
+                        Some(Box::new([available[index]]))
+                    }
+                }
+            })
+            .collect();
+        Ok(cpus)
+    }
+}
+
+/// Effective cpu affinity of the workers of a worker group.
+#[derive(Debug, Default)]
+pub struct AffinityTable {
+    workers: Mutex<FxHashMap<WorkerId, Box<[usize]>>>,
+}
+
+impl AffinityTable {
+    /// Pins the thread of the worker to the cpus and records its effective affinity.
+    pub fn pin_worker(
+        &self,
+        worker: WorkerId,
+        thread: &JoinHandle<()>,
+        cpus: Option<&[usize]>,
+    ) -> Result<(), Error> {
+        if !sys::IS_SUPPORTED && cpus.is_none() {
+            return Ok(());
+        }
+        if let Some(cpus) = cpus {
+            sys::set_thread_affinity(thread, cpus)?;
+        }
+
+        let effective = sys::thread_affinity(thread)?;
+        let mut workers = self.workers.lock().expect("could not lock affinity table");
+        workers.insert(worker, effective);
+        Ok(())
+    }
+
+    /// Removes the affinity of a worker that has exited.
+    pub fn remove_worker(&self, worker: WorkerId) {
+        let mut workers = self.workers.lock().expect("could not lock affinity table");
+        workers.remove(&worker);
+    }
+
+    /// Returns the sorted cpu indices the worker is allowed to run on.
+    pub fn worker_affinity(&self, worker: WorkerId) -> Result<Box<[usize]>, Error> {
+        if !sys::IS_SUPPORTED {
+            return Err(Error::ENOTSUP);
+        }
+
+        let workers = self.workers.lock().expect("could not lock affinity table");
+        workers.get(&worker).cloned().ok_or(Error::EINVAL)
+    }
+}
+
+/// Returns whether the given cpu index can be used in an affinity mask.
+pub fn is_valid_cpu(cpu: usize) -> bool {
+    sys::IS_SUPPORTED && cpu < sys::MAX_CPUS
+}
+
+/// Returns whether pinning the workers is supported on the current platform.
+pub fn is_supported() -> bool {
+    sys::IS_SUPPORTED
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use fimo_std::error::Error;
+    use std::{os::unix::thread::JoinHandleExt, thread::JoinHandle};
+
+    pub const IS_SUPPORTED: bool = true;
+    pub const MAX_CPUS: usize = libc::CPU_SETSIZE as usize;
+
+    fn to_cpu_set(cpus: &[usize]) -> libc::cpu_set_t {
+        // Safety: An all zero cpu set is valid.
+        let mut set = unsafe { std::mem::zeroed::<libc::cpu_set_t>() };
+        for &cpu in cpus {
+            libc::CPU_SET(cpu, &mut set);
+        }
+        set
+    }
+
+    fn from_cpu_set(set: &libc::cpu_set_t) -> Box<[usize]> {
+        (0..MAX_CPUS)
+            .filter(|&cpu| libc::CPU_ISSET(cpu, set))
+            .collect()
+    }
+
+    pub fn available_cpus() -> Result<Vec<usize>, Error> {
+        // Safety: An all zero cpu set is valid.
+        let mut set = unsafe { std::mem::zeroed::<libc::cpu_set_t>() };
+        // Safety: The pointer is valid for writes.
+        let result =
+            unsafe { libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut set) };
+        if result != 0 {
+            return Err(Error::from_errno(
+                std::io::Error::last_os_error()
+                    .raw_os_error()
+                    .unwrap_or(libc::EINVAL),
+            ));
+        }
+        Ok(from_cpu_set(&set).into_vec())
+    }
+
+    pub fn numa_node_cpus(node: usize) -> Result<Vec<usize>, Error> {
+        let path = format!("/sys/devices/system/node/node{node}/cpulist");
+        let list = std::fs::read_to_string(path).map_err(|e| match e.raw_os_error() {
+            Some(errno) => Error::from_errno(errno),
+            None => Error::new(e),
+        })?;
+
+        // The list has the form `0-3,8-11`.
+        let mut cpus = Vec::new();
+        for range in list.trim().split(',').filter(|x| !x.is_empty()) {
+            let parse = |x: &str| {
+                x.parse::<usize>()
+                    .map_err(|e| Error::new(format!("invalid NUMA node cpu list: {e}")))
+            };
+            match range.split_once('-') {
+                None => cpus.push(parse(range)?),
+                Some((start, end)) => cpus.extend(parse(start)?..=parse(end)?),
+            }
+        }
+        Ok(cpus)
+    }
+
+    pub fn set_thread_affinity(thread: &JoinHandle<()>, cpus: &[usize]) -> Result<(), Error> {
+        let set = to_cpu_set(cpus);
+        // Safety: The thread is alive, as we own its join handle.
+        let result = unsafe {
+            libc::pthread_setaffinity_np(
+                thread.as_pthread_t(),
+                std::mem::size_of::<libc::cpu_set_t>(),
+                &set,
+            )
+        };
+        if result != 0 {
+            return Err(Error::from_errno(result));
+        }
+        Ok(())
+    }
+
+    pub fn thread_affinity(thread: &JoinHandle<()>) -> Result<Box<[usize]>, Error> {
+        // Safety: An all zero cpu set is valid.
+        let mut set = unsafe { std::mem::zeroed::<libc::cpu_set_t>() };
+        // Safety: The thread is alive, as we own its join handle.
+        let result = unsafe {
+            libc::pthread_getaffinity_np(
+                thread.as_pthread_t(),
+                std::mem::size_of::<libc::cpu_set_t>(),
+                &mut set,
+            )
+        };
+        if result != 0 {
+            return Err(Error::from_errno(result));
+        }
+        Ok(from_cpu_set(&set))
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod sys {
+    use fimo_std::error::Error;
+    use std::thread::JoinHandle;
+
+    pub const IS_SUPPORTED: bool = false;
+    pub const MAX_CPUS: usize = 0;
+
+    pub fn available_cpus() -> Result<Vec<usize>, Error> {
+        Err(Error::ENOTSUP)
+    }
+
+    pub fn numa_node_cpus(_node: usize) -> Result<Vec<usize>, Error> {
+        Err(Error::ENOTSUP)
+    }
+
+    pub fn set_thread_affinity(_thread: &JoinHandle<()>, _cpus: &[usize]) -> Result<(), Error> {
+        Err(Error::ENOTSUP)
+    }
+
+    pub fn thread_affinity(_thread: &JoinHandle<()>) -> Result<Box<[usize]>, Error> {
+        Err(Error::ENOTSUP)
+    }
+}
@@ -0,0 +1,174 @@
+use fimo_std::{allocator::PoolAllocator, bindings as std_bindings, error::Error};
+use fimo_tasks::bindings;
+use std::{
+    alloc::{AllocError, Allocator, Layout},
+    ptr::NonNull,
+};
+
+/// Allocation hooks provided at the creation of a worker group.
+#[derive(Debug, Clone, Copy)]
+pub struct AllocatorHooks {
+    data: *mut std::ffi::c_void,
+    alloc:
+        unsafe extern "C" fn(*mut std::ffi::c_void, usize, usize, isize) -> *mut std::ffi::c_void,
+    free: unsafe extern "C" fn(*mut std::ffi::c_void, *mut std::ffi::c_void, usize, usize),
+    on_drop: Option<unsafe extern "C" fn(*mut std::ffi::c_void)>,
+}
+
+impl AllocatorHooks {
+    /// Reads the hooks from the configuration of a worker group.
+    ///
+    /// # Safety
+    ///
+    /// The hooks must be safe to invoke from any thread, until `on_drop` is invoked.
+    pub unsafe fn from_config(
+        config: &bindings::FiTasksWorkerGroupConfigAllocator,
+    ) -> Result<Self, Error> {
+        let bindings::FiTasksWorkerGroupConfigAllocator {
+            next,
+            data,
+            alloc,
+            free,
+            on_drop,
+        } = *config;
+        if !next.is_null() {
+            return Err(Error::EINVAL);
+        }
+        let (Some(alloc), Some(free)) = (alloc, free) else {
+            return Err(Error::EINVAL);
+        };
+
+        Ok(Self {
+            data,
+            alloc,
+            free,
+            on_drop,
+        })
+    }
+}
+
+// Safety: The hooks are required to be thread-safe.
+unsafe impl Send for AllocatorHooks {}
+
+// Safety: The hooks are required to be thread-safe.
+unsafe impl Sync for AllocatorHooks {}
+
+/// Allocator of a worker group.
+///
+/// Small allocations are served from the size classes of a [`PoolAllocator`], which caches the
+/// freed blocks for reuse until the allocator is dropped. The blocks are allocated with the hooks
+/// of the worker group, or with the fimo allocator, if the worker group does not specify any
+/// hooks.
+#[derive(Debug)]
+pub struct GroupAllocator {
+    pool: PoolAllocator<HookAllocator>,
+}
+
+impl GroupAllocator {
+    pub fn new(hooks: Option<AllocatorHooks>, numa_node: Option<usize>) -> Self {
+        Self {
+            pool: PoolAllocator::new_in(HookAllocator { hooks, numa_node }),
+        }
+    }
+
+    /// Allocates a block of memory.
+    pub fn allocate(&self, size: usize, alignment: usize) -> Result<NonNull<u8>, Error> {
+        if size == 0 {
+            return Err(Error::EINVAL);
+        }
+        let layout = Layout::from_size_align(size, alignment).map_err(Error::new)?;
+        let block = self
+            .pool
+            .allocate(layout)
+            .map_err(|AllocError| Error::ENOMEM)?;
+        Ok(block.cast())
+    }
+
+    /// Frees a block of memory.
+    ///
+    /// # Safety
+    ///
+    /// The block must have been allocated by the allocator with the same size and alignment.
+    pub unsafe fn deallocate(&self, ptr: NonNull<u8>, size: usize, alignment: usize) {
+        // Safety: Ensured by the caller.
+        unsafe {
+            let layout = Layout::from_size_align_unchecked(size, alignment);
+            self.pool.deallocate(ptr, layout);
+        }
+    }
+}
+
+/// Backing allocator of the pool of a worker group.
+///
+/// Releases the hooks once it is dropped, i.e., after the pool has returned its cached blocks.
+#[derive(Debug)]
+struct HookAllocator {
+    hooks: Option<AllocatorHooks>,
+    numa_node: Option<usize>,
+}
+
+// Safety: The blocks are allocated by the hooks or by the fimo allocator, with the requested
+// layout.
+unsafe impl Allocator for HookAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let ptr = match &self.hooks {
+            Some(hooks) => {
+                let numa_node = self
+                    .numa_node
+                    .map_or(-1, |x| isize::try_from(x).unwrap_or(isize::MAX));
+                // Safety: The hooks are valid until they are dropped.
+                unsafe { (hooks.alloc)(hooks.data, layout.size(), layout.align(), numa_node) }
+            }
+            None => {
+                // The allocation failed, if the returned pointer is null, so we don't need to
+                // request the error.
+                // Safety: FFI call is safe.
+                unsafe {
+                    std_bindings::fimo_aligned_alloc(
+                        layout.align(),
+                        layout.size(),
+                        core::ptr::null_mut(),
+                    )
+                }
+            }
+        };
+        let ptr = NonNull::new(ptr.cast::<u8>()).ok_or(AllocError)?;
+        Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        match &self.hooks {
+            // Safety: Ensured by the caller.
+            Some(hooks) => unsafe {
+                (hooks.free)(
+                    hooks.data,
+                    ptr.as_ptr().cast(),
+                    layout.size(),
+                    layout.align(),
+                )
+            },
+            // Safety: Ensured by the caller.
+            None => unsafe {
+                std_bindings::fimo_free_aligned_sized(
+                    ptr.as_ptr().cast(),
+                    layout.align(),
+                    layout.size(),
+                )
+            },
+        }
+    }
+}
+
+impl Drop for HookAllocator {
+    fn drop(&mut self) {
+        if let Some(AllocatorHooks {
+            data,
+            on_drop: Some(on_drop),
+            ..
+        }) = self.hooks
+        {
+            // Safety: The pool has freed all cached blocks, so we can release the hooks.
+            unsafe { on_drop(data) };
+        }
+    }
+}
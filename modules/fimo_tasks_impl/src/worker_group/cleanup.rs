@@ -0,0 +1,113 @@
+use fimo_std::error::Error;
+use fimo_tasks::TaskId;
+use std::{
+    any::Any,
+    panic::AssertUnwindSafe,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Size of the stack used by each worker to clean up aborted tasks.
+const CLEANUP_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Maximum time a worker may spend cleaning up a task, before it is reported by the watchdog.
+pub const CLEANUP_TIME_LIMIT: Duration = Duration::from_millis(100);
+
+type CleanupJob<'a> = &'a mut dyn FnMut();
+
+/// Dedicated stack of a worker, used to run the cleanup of aborted tasks.
+///
+/// The stack of an aborted task may be small or already exhausted, so we don't run any
+/// destructors on it.
+#[derive(Debug)]
+pub struct CleanupStack(context::stack::ProtectedFixedSizeStack);
+
+impl CleanupStack {
+    pub fn new() -> Result<Self, Error> {
+        let size = CLEANUP_STACK_SIZE.clamp(
+            context::stack::Stack::min_size(),
+            context::stack::Stack::max_size(),
+        );
+        let stack = context::stack::ProtectedFixedSizeStack::new(size).map_err(Error::new)?;
+        Ok(Self(stack))
+    }
+
+    /// Runs the function on the cleanup stack, returning the payload of a panic, if any.
+    pub fn run(&self, f: impl FnOnce()) -> Result<(), Box<dyn Any + Send>> {
+        extern "C" fn cleanup_start(t: context::Transfer) -> ! {
+            let context::Transfer { context, data } = t;
+            {
+                // Safety: We are passed a pointer to the job.
+                let job =
+                    unsafe { &mut *std::ptr::with_exposed_provenance_mut::<CleanupJob<'_>>(data) };
+                job();
+            }
+
+            // Switch back to the caller. The context is discarded afterward, so we never return
+            // from this call.
+            // Safety: The caller is suspended and waiting for our completion.
+            unsafe { context.resume(0) };
+            std::process::abort();
+        }
+
+        let mut f = Some(f);
+        let mut result = None;
+        {
+            let mut job = || {
+                let f = f.take().expect("cleanup job already executed");
+                result = Some(std::panic::catch_unwind(AssertUnwindSafe(f)));
+            };
+            let mut job: CleanupJob<'_> = &mut job;
+
+            // Safety: The stack outlives the context, which is discarded after the job completes.
+            let context = unsafe { context::Context::new(&self.0, cleanup_start) };
+            // Safety: The job remains valid until the context switches back to us.
+            unsafe { context.resume(std::ptr::from_mut(&mut job).expose_provenance()) };
+        }
+
+        result.expect("cleanup job was not executed")
+    }
+}
+
+/// Watchdog tracking the time spent by a worker cleaning up a task.
+#[derive(Debug, Default)]
+pub struct CleanupWatchdog(Mutex<Option<CleanupInfo>>);
+
+#[derive(Debug)]
+struct CleanupInfo {
+    task: TaskId,
+    start: Instant,
+    reported: bool,
+}
+
+impl CleanupWatchdog {
+    pub fn start(&self, task: TaskId) {
+        let mut guard = self.0.lock().expect("could not lock watchdog");
+        *guard = Some(CleanupInfo {
+            task,
+            start: Instant::now(),
+            reported: false,
+        });
+    }
+
+    /// Stops the watchdog, returning the time spent in the cleanup.
+    pub fn stop(&self) -> Duration {
+        let mut guard = self.0.lock().expect("could not lock watchdog");
+        let info = guard.take().expect("watchdog not started");
+        info.start.elapsed()
+    }
+
+    /// Returns the task and the elapsed time, if the cleanup exceeded the time limit and has not
+    /// been reported yet.
+    pub fn check_overdue(&self) -> Option<(TaskId, Duration)> {
+        let mut guard = self.0.lock().expect("could not lock watchdog");
+        let info = guard.as_mut()?;
+        let elapsed = info.start.elapsed();
+        if info.reported || elapsed < CLEANUP_TIME_LIMIT {
+            return None;
+        }
+
+        info.reported = true;
+        Some((info.task, elapsed))
+    }
+}
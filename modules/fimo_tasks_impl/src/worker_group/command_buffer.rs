@@ -1,5 +1,6 @@
 use crate::{
     module_export::TasksModule,
+    semaphore::SemaphoreImpl,
     worker_group::{
         task::RawTask, worker_thread::wait_on_command_buffer, WorkerGroupFFI, WorkerGroupImpl,
     },
@@ -11,28 +12,34 @@ use fimo_std::{
     module::Module,
 };
 use fimo_tasks::{
-    bindings::{self, FiTasksCommandBufferEntryType},
-    TaskId, WorkerId,
+    bindings::{self, FiTasksCommandBufferEntryType, FiTasksCommandBufferState},
+    FailurePolicy, TaskId, TaskPriority, TaskTag, WorkerId,
 };
 use rustc_hash::FxHashMap;
 use std::{
+    backtrace::Backtrace,
     collections::VecDeque,
     ffi::CStr,
     fmt::{Debug, Formatter},
     num::NonZeroUsize,
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc, Weak,
+        Arc, Mutex, Weak,
     },
 };
 
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub struct CommandBufferId(pub usize);
 
+type CompletionListener = Box<dyn FnOnce() + Send>;
+
 pub struct CommandBufferHandleImpl {
     id: CommandBufferId,
     status: AtomicBool,
     completed: AtomicBool,
+    blocked: AtomicBool,
+    listeners: Mutex<Vec<CompletionListener>>,
+    failures: Mutex<Vec<bindings::FiTasksCommandFailure>>,
     group: Weak<WorkerGroupImpl>,
 }
 
@@ -65,21 +72,92 @@ impl CommandBufferHandleImpl {
         self.completion_status().is_some()
     }
 
+    /// Returns whether the command buffer was aborted, if it has finished executing.
     pub fn completion_status(&self) -> Option<bool> {
         if self.completed.load(Ordering::Acquire) {
-            Some(self.completed.load(Ordering::Relaxed))
+            Some(self.status.load(Ordering::Relaxed))
         } else {
             None
         }
     }
 
+    pub fn state(&self) -> FiTasksCommandBufferState {
+        match self.completion_status() {
+            Some(false) => FiTasksCommandBufferState::FI_TASKS_COMMAND_BUFFER_STATE_COMPLETED,
+            Some(true) => FiTasksCommandBufferState::FI_TASKS_COMMAND_BUFFER_STATE_ABORTED,
+            None if self.blocked.load(Ordering::Relaxed) => {
+                FiTasksCommandBufferState::FI_TASKS_COMMAND_BUFFER_STATE_BLOCKED
+            }
+            None => FiTasksCommandBufferState::FI_TASKS_COMMAND_BUFFER_STATE_RUNNING,
+        }
+    }
+
+    fn set_blocked(&self, blocked: bool) {
+        self.blocked.store(blocked, Ordering::Relaxed);
+    }
+
+    fn record_failure(
+        &self,
+        index: usize,
+        reason: bindings::FiTasksCommandFailureReason,
+        cause: usize,
+    ) {
+        let mut failures = self.failures.lock().expect("could not lock failures");
+        failures.push(bindings::FiTasksCommandFailure {
+            index,
+            reason,
+            cause,
+        });
+    }
+
+    /// Returns the failed and skipped commands, sorted by their index.
+    pub fn failures(&self) -> Box<[bindings::FiTasksCommandFailure]> {
+        let mut failures: Box<[_]> = self
+            .failures
+            .lock()
+            .expect("could not lock failures")
+            .as_slice()
+            .into();
+        failures.sort_by_key(|x| x.index);
+        failures
+    }
+
+    /// Invokes `f` once the command buffer has finished executing.
+    ///
+    /// If the command buffer has already finished, `f` is invoked immediately. Since the
+    /// listeners may be invoked from the event loop of another worker group, they should only
+    /// forward the notification.
+    pub fn on_completion(&self, f: impl FnOnce() + Send + 'static) {
+        let mut listeners = self
+            .listeners
+            .lock()
+            .expect("could not lock completion listeners");
+        if self.is_completed() {
+            drop(listeners);
+            f();
+        } else {
+            listeners.push(Box::new(f));
+        }
+    }
+
     /// # Safety
     ///
     /// May only be called once after the completion/abortion of the command buffer.
     pub unsafe fn mark_completed(&self, aborted: bool) {
-        self.status.store(aborted, Ordering::Relaxed);
-        let completed = self.completed.swap(true, Ordering::Release);
-        debug_assert!(!completed);
+        let listeners = {
+            let mut listeners = self
+                .listeners
+                .lock()
+                .expect("could not lock completion listeners");
+            self.status.store(aborted, Ordering::Relaxed);
+            let completed = self.completed.swap(true, Ordering::Release);
+            debug_assert!(!completed);
+            std::mem::take(&mut *listeners)
+        };
+
+        for listener in listeners {
+            listener();
+        }
     }
 
     pub fn worker_group(&self) -> Result<Arc<WorkerGroupImpl>, Error> {
@@ -105,6 +183,7 @@ impl Debug for CommandBufferHandleImpl {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("CommandBufferHandleImpl")
             .field("completion_status", &self.completion_status())
+            .field("state", &self.state())
             .finish_non_exhaustive()
     }
 }
@@ -121,6 +200,8 @@ impl CommandBufferHandleFFI {
                 release: Some(Self::release),
                 worker_group: Some(Self::worker_group),
                 wait_on: Some(Self::wait_on),
+                state: Some(Self::state),
+                failures: Some(Self::failures),
             },
         };
 
@@ -191,6 +272,46 @@ impl CommandBufferHandleFFI {
         .flatten()
         .into_ffi()
     }
+
+    unsafe extern "C" fn state(this: *mut std::ffi::c_void) -> FiTasksCommandBufferState {
+        fimo_std::panic::abort_on_panic(|| {
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+            this.state()
+        })
+    }
+
+    unsafe extern "C" fn failures(
+        this: *mut std::ffi::c_void,
+        failures: *mut *mut bindings::FiTasksCommandFailure,
+        count: *mut usize,
+    ) -> std_bindings::FimoResult {
+        fimo_std::panic::catch_unwind(|| {
+            if this.is_null() || failures.is_null() || count.is_null() {
+                return Err(Error::EINVAL);
+            }
+
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+            let snapshot = this.failures();
+            let len = snapshot.len();
+            let snapshot = if len == 0 {
+                std::ptr::null_mut()
+            } else {
+                Box::into_raw(snapshot).cast()
+            };
+
+            // Safety: We assume that the pointers can be dereferenced.
+            unsafe {
+                failures.write(snapshot);
+                count.write(len);
+            }
+            Ok(())
+        })
+        .map_err(Into::into)
+        .flatten()
+        .into_ffi()
+    }
 }
 
 impl FFISharable<*mut std::ffi::c_void> for CommandBufferHandleFFI {
@@ -227,9 +348,19 @@ pub struct CommandBufferImpl {
     buffer: CommandBufferIterator,
     wait_reason: WaitReason,
     waiters: VecDeque<Waiter>,
-    blocked_tasks: FxHashMap<TaskId, (usize, Option<WorkerId>, RawTask)>,
+    blocked_tasks: FxHashMap<TaskId, (usize, Option<WorkerId>, TaskTag, TaskPriority, RawTask)>,
     worker: Option<WorkerId>,
     stack_size: Option<NonZeroUsize>,
+    tag: TaskTag,
+    priority: TaskPriority,
+    failure_policy: FailurePolicy,
+    /// Failure policies of the spawned tasks, by the index of their command. Tasks with the
+    /// default policy are not included.
+    task_policies: FxHashMap<usize, FailurePolicy>,
+    /// Index of a failed task, whose failure aborts the buffer at the next dependent command.
+    deferred_abort: Option<usize>,
+    /// Backtrace of the thread enqueueing the buffer, i.e., where its tasks were spawned from.
+    spawn_backtrace: Option<Arc<Backtrace>>,
 }
 
 impl CommandBufferImpl {
@@ -244,6 +375,9 @@ impl CommandBufferImpl {
             id: CommandBufferId(0),
             status: AtomicBool::new(false),
             completed: AtomicBool::new(false),
+            blocked: AtomicBool::new(false),
+            listeners: Mutex::new(Vec::new()),
+            failures: Mutex::new(Vec::new()),
             group: Arc::downgrade(group),
         });
         let id = CommandBufferId(Arc::as_ptr(&handle).addr());
@@ -258,6 +392,14 @@ impl CommandBufferImpl {
             blocked_tasks: Default::default(),
             worker: None,
             stack_size: None,
+            tag: TaskTag::UNTAGGED,
+            priority: TaskPriority::Normal,
+            failure_policy: FailurePolicy::AbortAll,
+            task_policies: Default::default(),
+            deferred_abort: None,
+            spawn_backtrace: group
+                .detect_deadlocks()
+                .then(|| Arc::new(Backtrace::force_capture())),
         }
     }
 
@@ -265,6 +407,16 @@ impl CommandBufferImpl {
         &self.handle
     }
 
+    pub fn label(&self) -> &CStr {
+        self.buffer.buffer.label()
+    }
+
+    /// Returns the backtrace of the site spawning the tasks of the buffer, if the deadlock
+    /// detection is enabled.
+    pub fn spawn_backtrace(&self) -> Option<Arc<Backtrace>> {
+        self.spawn_backtrace.clone()
+    }
+
     pub fn worker(&self) -> Option<WorkerId> {
         self.worker
     }
@@ -273,39 +425,129 @@ impl CommandBufferImpl {
         self.stack_size
     }
 
+    pub fn tag(&self) -> TaskTag {
+        self.tag
+    }
+
+    pub fn priority(&self) -> TaskPriority {
+        self.priority
+    }
+
     #[allow(dead_code)]
-    pub fn mark_task_as_blocked(&mut self, index: usize, worker: Option<WorkerId>, task: RawTask) {
+    pub fn mark_task_as_blocked(
+        &mut self,
+        index: usize,
+        worker: Option<WorkerId>,
+        tag: TaskTag,
+        priority: TaskPriority,
+        task: RawTask,
+    ) {
         let id = task.id();
-        let old = self.blocked_tasks.insert(id, (index, worker, task));
+        let old = self
+            .blocked_tasks
+            .insert(id, (index, worker, tag, priority, task));
         assert!(old.is_none(), "task marked as blocked multiple times");
     }
 
     pub fn mark_task_as_unblocked(
         &mut self,
         task_id: TaskId,
-    ) -> (usize, Option<WorkerId>, RawTask) {
+    ) -> (usize, Option<WorkerId>, TaskTag, TaskPriority, RawTask) {
         self.blocked_tasks.remove(&task_id).expect("task not found")
     }
 
     pub fn mark_task_as_completed(
         &mut self,
-        _module: &TasksModule<'_>,
-        _index: usize,
+        module: &TasksModule<'_>,
+        index: usize,
         _task: RawTask,
     ) {
         self.num_enqueued_tasks -= 1;
-        if self.num_enqueued_tasks == 0 && self.buffer.is_done() {
-            // Safety: Is only called once.
-            unsafe {
-                self.buffer.mark_completed();
-                self.handle.mark_completed(false);
-            }
-        }
+        self.task_policies.remove(&index);
+
+        // The buffer may have been aborted while the task was running.
+        self.complete_if_done(module);
     }
 
     pub fn mark_task_as_aborted(&mut self, module: &TasksModule<'_>, index: usize, _task: RawTask) {
         self.num_enqueued_tasks -= 1;
-        self.abort(module, index);
+        let policy = self.task_policies.remove(&index).unwrap_or_default();
+
+        // The failure of a task that was still running while the buffer was aborted does not
+        // require a decision.
+        if self.handle.completion_status().is_some() {
+            self.handle.record_failure(
+                index,
+                bindings::FiTasksCommandFailureReason::FI_TASKS_COMMAND_FAILURE_REASON_FAILED,
+                index,
+            );
+            return;
+        }
+
+        match policy {
+            FailurePolicy::AbortAll => {
+                fimo_std::emit_debug!(
+                    module.context(),
+                    "Task at command {index} of command buffer {:?} failed, aborting the command \
+                    buffer, policy: {policy:?}",
+                    self.buffer.buffer.label()
+                );
+                self.fail(module, index);
+            }
+            FailurePolicy::AbortDependents => {
+                fimo_std::emit_debug!(
+                    module.context(),
+                    "Task at command {index} of command buffer {:?} failed, aborting the command \
+                    buffer at the next dependent command, policy: {policy:?}",
+                    self.buffer.buffer.label()
+                );
+                self.handle.record_failure(
+                    index,
+                    bindings::FiTasksCommandFailureReason::FI_TASKS_COMMAND_FAILURE_REASON_FAILED,
+                    index,
+                );
+                self.deferred_abort.get_or_insert(index);
+                self.complete_if_done(module);
+            }
+            FailurePolicy::Continue => {
+                fimo_std::emit_debug!(
+                    module.context(),
+                    "Task at command {index} of command buffer {:?} failed, continuing, \
+                    policy: {policy:?}",
+                    self.buffer.buffer.label()
+                );
+                self.handle.record_failure(
+                    index,
+                    bindings::FiTasksCommandFailureReason::FI_TASKS_COMMAND_FAILURE_REASON_FAILED,
+                    index,
+                );
+                self.complete_if_done(module);
+            }
+        }
+    }
+
+    /// Completes the buffer, if all commands have been processed and all tasks have finished.
+    ///
+    /// The buffer is aborted instead, if the failure of a task was deferred.
+    fn complete_if_done(&mut self, module: &TasksModule<'_>) {
+        if self.num_enqueued_tasks != 0
+            || !self.buffer.is_done()
+            || !matches!(self.wait_reason, WaitReason::None)
+            || self.handle.completion_status().is_some()
+        {
+            return;
+        }
+
+        match self.deferred_abort {
+            Some(cause) => {
+                self.abort_deferred(module, cause);
+            }
+            // Safety: Is only called once.
+            None => unsafe {
+                self.buffer.mark_completed();
+                self.handle.mark_completed(false);
+            },
+        }
     }
 
     pub fn register_waiter(&mut self, waiter: Waiter) {
@@ -344,6 +586,9 @@ impl CommandBufferImpl {
                     return CommandBufferEventLoopCommand::Waiting;
                 }
                 self.wait_reason = WaitReason::None;
+                if let Some(cause) = self.deferred_abort {
+                    return self.abort_deferred(module, cause);
+                }
             }
             WaitReason::CommandBuffer {
                 index,
@@ -355,23 +600,52 @@ impl CommandBufferImpl {
                         return CommandBufferEventLoopCommand::Waiting;
                     }
                     // Propagate the abort to the current buffer.
-                    Some(false) => {
+                    Some(true) => {
                         let index = *index;
                         self.wait_reason = WaitReason::None;
-                        return self.abort(module, index);
+                        self.handle.set_blocked(false);
+                        return self.fail(module, index);
                     }
                     // The command buffer is done.
-                    Some(true) => {
+                    Some(false) => {
                         self.wait_reason = WaitReason::None;
+                        self.handle.set_blocked(false);
                     }
                 }
             }
+            WaitReason::Semaphore { semaphore, value } => {
+                if semaphore.value() < *value {
+                    return CommandBufferEventLoopCommand::Waiting;
+                }
+                self.wait_reason = WaitReason::None;
+                self.handle.set_blocked(false);
+            }
+            WaitReason::Signal {
+                index,
+                semaphore,
+                value,
+            } => {
+                if self.num_enqueued_tasks != 0 {
+                    return CommandBufferEventLoopCommand::Waiting;
+                }
+                let (index, semaphore, value) = (*index, semaphore.clone(), *value);
+                self.wait_reason = WaitReason::None;
+                if let Some(cause) = self.deferred_abort {
+                    return self.abort_deferred(module, cause);
+                }
+                if !self.signal_semaphore(module, &semaphore, value) {
+                    return self.fail(module, index);
+                }
+            }
         }
 
         // Process all commands we can.
         for (idx, command) in &mut self.buffer {
             match command {
                 Command::SpawnTask(task) => {
+                    if self.failure_policy != FailurePolicy::default() {
+                        self.task_policies.insert(idx, self.failure_policy);
+                    }
                     self.num_enqueued_tasks += 1;
                     return CommandBufferEventLoopCommand::SpawnTask(idx, task);
                 }
@@ -381,34 +655,66 @@ impl CommandBufferImpl {
                         self.wait_reason = WaitReason::Barrier;
                         return CommandBufferEventLoopCommand::Waiting;
                     }
+                    if let Some(cause) = self.deferred_abort {
+                        return self.abort_deferred(module, cause);
+                    }
                 }
                 Command::WaitCommandBuffer(command_buffer) => {
                     if !check_command_buffer(&command_buffer) {
                         drop(command_buffer);
-                        return self.abort(module, idx);
+                        return self.fail(module, idx);
                     }
                     match command_buffer.completion_status() {
                         // Wait for the command buffer.
                         None => {
-                            let id = command_buffer.id();
                             self.wait_reason = WaitReason::CommandBuffer {
                                 index: idx,
-                                command_buffer,
+                                command_buffer: command_buffer.clone(),
                             };
-                            return CommandBufferEventLoopCommand::WaitCommandBuffer(id);
+                            self.handle.set_blocked(true);
+                            return CommandBufferEventLoopCommand::WaitCommandBuffer(
+                                command_buffer,
+                            );
                         }
                         // Propagate the abort to the current buffer.
-                        Some(false) => {
+                        Some(true) => {
                             drop(command_buffer);
-                            return self.abort(module, idx);
+                            return self.fail(module, idx);
                         }
                         // The command buffer is done.
-                        Some(true) => {}
+                        Some(false) => {}
+                    }
+                }
+                Command::WaitSemaphore(semaphore, value) => {
+                    if semaphore.value() < value {
+                        self.wait_reason = WaitReason::Semaphore {
+                            semaphore: semaphore.clone(),
+                            value,
+                        };
+                        self.handle.set_blocked(true);
+                        return CommandBufferEventLoopCommand::WaitSemaphore(semaphore, value);
+                    }
+                }
+                Command::SignalSemaphore(semaphore, value) => {
+                    // Wait until all tasks have been completed.
+                    if self.num_enqueued_tasks != 0 {
+                        self.wait_reason = WaitReason::Signal {
+                            index: idx,
+                            semaphore,
+                            value,
+                        };
+                        return CommandBufferEventLoopCommand::Waiting;
+                    }
+                    if let Some(cause) = self.deferred_abort {
+                        return self.abort_deferred(module, cause);
+                    }
+                    if !self.signal_semaphore(module, &semaphore, value) {
+                        return self.fail(module, idx);
                     }
                 }
                 Command::SetWorker(worker) => {
                     if !check_worker(worker) {
-                        return self.abort(module, idx);
+                        return self.fail(module, idx);
                     }
                     self.worker = Some(worker);
                 }
@@ -417,22 +723,35 @@ impl CommandBufferImpl {
                 }
                 Command::SetStackSize(stack_size) => {
                     if !check_stack_size(stack_size) {
-                        return self.abort(module, idx);
+                        return self.fail(module, idx);
                     }
                     self.stack_size = stack_size;
                 }
+                Command::SetTag(tag) => {
+                    self.tag = tag;
+                }
+                Command::SetPriority(priority) => {
+                    self.priority = priority;
+                }
+                Command::SetFailurePolicy(policy) => {
+                    self.failure_policy = policy;
+                }
                 Command::Unknown => {
                     fimo_std::emit_error!(
                         module.context(),
                         "Unknown command at index {idx} for command buffer {:?}",
                         self.buffer.buffer.label()
                     );
-                    return self.abort(module, idx);
+                    return self.fail(module, idx);
                 }
             }
         }
 
         if self.num_enqueued_tasks == 0 {
+            if let Some(cause) = self.deferred_abort {
+                return self.abort_deferred(module, cause);
+            }
+
             // Safety: Is only called once.
             unsafe {
                 self.buffer.mark_completed();
@@ -446,6 +765,26 @@ impl CommandBufferImpl {
         }
     }
 
+    fn signal_semaphore(
+        &self,
+        module: &TasksModule<'_>,
+        semaphore: &SemaphoreImpl,
+        value: u64,
+    ) -> bool {
+        match semaphore.signal(value) {
+            Ok(_) => true,
+            Err(e) => {
+                fimo_std::emit_error!(
+                    module.context(),
+                    "could not signal semaphore {semaphore:?} with value {value} from command \
+                    buffer {:?}, error: {e}",
+                    self.buffer.buffer.label()
+                );
+                false
+            }
+        }
+    }
+
     pub fn abort(
         &mut self,
         module: &TasksModule<'_>,
@@ -464,9 +803,76 @@ impl CommandBufferImpl {
             "Aborting command buffer {:?} due to an error while processing command {cause}",
             self.buffer.buffer.label()
         );
+        self.abort_unchecked(
+            cause,
+            bindings::FiTasksCommandFailureReason::FI_TASKS_COMMAND_FAILURE_REASON_SKIPPED,
+        )
+    }
 
-        for (_, (_, _, mut task)) in self.blocked_tasks.drain() {
+    /// Aborts the command buffer due to the failure of the command at index `index`.
+    fn fail(&mut self, module: &TasksModule<'_>, index: usize) -> CommandBufferEventLoopCommand {
+        if self.handle.completion_status().is_none() {
+            self.handle.record_failure(
+                index,
+                bindings::FiTasksCommandFailureReason::FI_TASKS_COMMAND_FAILURE_REASON_FAILED,
+                index,
+            );
+        }
+        self.abort(module, index)
+    }
+
+    /// Aborts the command buffer due to the deferred failure of the task at index `cause`.
+    fn abort_deferred(
+        &mut self,
+        module: &TasksModule<'_>,
+        cause: usize,
+    ) -> CommandBufferEventLoopCommand {
+        fimo_std::emit_debug!(
+            module.context(),
+            "Aborting command buffer {:?} at command {} due to the failure of the task at \
+            command {cause}",
+            self.buffer.buffer.label(),
+            self.buffer.index
+        );
+        self.abort_unchecked(
+            cause,
+            bindings::FiTasksCommandFailureReason::FI_TASKS_COMMAND_FAILURE_REASON_SKIPPED,
+        )
+    }
+
+    /// Aborts the command buffer at the first command that has not been processed yet, due to
+    /// the shutdown of the worker group.
+    ///
+    /// The spawned tasks are run to completion. Returns whether the buffer was aborted, which is
+    /// not the case if it has already finished processing its commands.
+    pub fn abort_for_shutdown(&mut self, module: &TasksModule<'_>) -> bool {
+        if self.handle.completion_status().is_some()
+            || (self.buffer.is_done() && self.blocked_tasks.is_empty())
+        {
+            return false;
+        }
+
+        let cause = self.buffer.index;
+        fimo_std::emit_debug!(
+            module.context(),
+            "Aborting command buffer {:?} at command {cause} due to the worker group shutdown",
+            self.buffer.buffer.label()
+        );
+        self.abort_unchecked(
+            cause,
+            bindings::FiTasksCommandFailureReason::FI_TASKS_COMMAND_FAILURE_REASON_SHUTDOWN,
+        );
+        true
+    }
+
+    fn abort_unchecked(
+        &mut self,
+        cause: usize,
+        reason: bindings::FiTasksCommandFailureReason,
+    ) -> CommandBufferEventLoopCommand {
+        for (_, (index, _, _, _, mut task)) in self.blocked_tasks.drain() {
             self.num_enqueued_tasks -= 1;
+            self.handle.record_failure(index, reason, cause);
             // Safety: The task is being aborted.
             unsafe {
                 task.run_abortion_handler(std::ptr::null_mut());
@@ -474,7 +880,9 @@ impl CommandBufferImpl {
             }
         }
 
-        self.buffer.abort(cause);
+        let handle = &self.handle;
+        self.buffer
+            .abort(cause, |index| handle.record_failure(index, reason, cause));
 
         // Safety: Is only called once.
         unsafe { self.handle.mark_completed(true) };
@@ -508,6 +916,15 @@ enum WaitReason {
         index: usize,
         command_buffer: Arc<CommandBufferHandleImpl>,
     },
+    Semaphore {
+        semaphore: Arc<SemaphoreImpl>,
+        value: u64,
+    },
+    Signal {
+        index: usize,
+        semaphore: Arc<SemaphoreImpl>,
+        value: u64,
+    },
 }
 
 #[derive(Debug)]
@@ -516,13 +933,13 @@ pub enum CommandBufferEventLoopCommand {
     Processed,
     Completed,
     SpawnTask(usize, RawTask),
-    WaitCommandBuffer(CommandBufferId),
+    WaitCommandBuffer(Arc<CommandBufferHandleImpl>),
+    WaitSemaphore(Arc<SemaphoreImpl>, u64),
 }
 
 #[derive(Debug, Clone)]
 pub enum Waiter {
     Task(TaskId),
-    CommandBuffer(Arc<CommandBufferHandleImpl>),
 }
 
 #[derive(Debug)]
@@ -555,11 +972,13 @@ impl CommandBufferIterator {
         self.index == self.num_commands
     }
 
-    fn abort(&mut self, cause: usize) {
+    /// Aborts the remaining commands, invoking `on_skip` with the index of each skipped task.
+    fn abort(&mut self, cause: usize, mut on_skip: impl FnMut(usize)) {
         debug_assert_eq!(self.state, CommandBufferState::Running);
         debug_assert!(cause <= self.num_commands);
-        for (_, command) in self.by_ref() {
+        for (index, command) in self.by_ref() {
             if let Command::SpawnTask(mut t) = command {
+                on_skip(index);
                 // Safety:
                 unsafe {
                     t.run_abortion_handler(std::ptr::null_mut());
@@ -638,6 +1057,49 @@ impl Iterator for CommandBufferIterator {
                 };
                 Command::SetStackSize(NonZeroUsize::new(stack_size))
             }
+            FiTasksCommandBufferEntryType::FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_SET_TAG => {
+                // Safety: We checked the tag of the union.
+                let tag = unsafe {
+                    TaskTag(*command.data.set_tag)
+                };
+                Command::SetTag(tag)
+            }
+            FiTasksCommandBufferEntryType::FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_WAIT_SEMAPHORE => {
+                // Safety: We checked the tag of the union.
+                let (semaphore, value) = unsafe {
+                    let operation = &command.data.wait_semaphore;
+                    (Arc::from_raw(operation.semaphore.data.cast_const().cast::<SemaphoreImpl>()), operation.value)
+                };
+                Command::WaitSemaphore(semaphore, value)
+            }
+            FiTasksCommandBufferEntryType::FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_SIGNAL_SEMAPHORE => {
+                // Safety: We checked the tag of the union.
+                let (semaphore, value) = unsafe {
+                    let operation = &command.data.signal_semaphore;
+                    (Arc::from_raw(operation.semaphore.data.cast_const().cast::<SemaphoreImpl>()), operation.value)
+                };
+                Command::SignalSemaphore(semaphore, value)
+            }
+            FiTasksCommandBufferEntryType::FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_SET_PRIORITY => {
+                // Safety: We checked the tag of the union.
+                let priority = unsafe {
+                    *command.data.set_priority
+                };
+                match TaskPriority::try_from(priority) {
+                    Ok(priority) => Command::SetPriority(priority),
+                    Err(_) => Command::Unknown,
+                }
+            }
+            FiTasksCommandBufferEntryType::FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_SET_FAILURE_POLICY => {
+                // Safety: We checked the tag of the union.
+                let policy = unsafe {
+                    *command.data.set_failure_policy
+                };
+                match FailurePolicy::try_from(policy) {
+                    Ok(policy) => Command::SetFailurePolicy(policy),
+                    Err(_) => Command::Unknown,
+                }
+            }
             _ => Command::Unknown,
         };
 
@@ -663,9 +1125,14 @@ enum Command {
     SpawnTask(RawTask),
     WaitBarrier,
     WaitCommandBuffer(Arc<CommandBufferHandleImpl>),
+    WaitSemaphore(Arc<SemaphoreImpl>, u64),
+    SignalSemaphore(Arc<SemaphoreImpl>, u64),
     SetWorker(WorkerId),
     EnableAllWorkers,
     SetStackSize(Option<NonZeroUsize>),
+    SetTag(TaskTag),
+    SetPriority(TaskPriority),
+    SetFailurePolicy(FailurePolicy),
     Unknown,
 }
 
@@ -6,7 +6,9 @@ use crate::{
             CommandBufferImpl, Waiter,
         },
         event_loop::stack_manager::StackDescriptor,
+        shutdown::{ShutdownListener, ShutdownMode},
         task::EnqueuedTask,
+        timer::TimerImpl,
         worker_thread::{
             TaskRequest, TaskResponse, WorkerBootstrapper, WorkerHandle, WorkerRequest,
             WorkerResponse, WorkerSyncInfo,
@@ -16,7 +18,7 @@ use crate::{
 };
 use crossbeam_channel::{select, Receiver, Sender, TrySendError};
 use fimo_std::{error::Error, module::Module};
-use fimo_tasks::{TaskId, WorkerId};
+use fimo_tasks::{bindings, TaskId, WorkerId};
 use rustc_hash::FxHashMap;
 use std::{
     fmt::{Debug, Formatter},
@@ -28,19 +30,23 @@ use std::{
 
 pub mod stack_manager;
 pub mod time_out;
+mod timer_wheel;
+mod wait_graph;
 
 #[derive(Debug)]
 pub enum OuterRequest {
-    Close,
+    Shutdown(ShutdownMode, Option<ShutdownListener>),
     EnqueueCommandBuffer(CommandBufferImpl),
+    AddTimer(Instant, Arc<TimerImpl>),
+    Resize(NonZeroUsize),
 }
 
 #[derive(Debug)]
 pub enum InnerRequest {
-    #[allow(dead_code)]
     UnblockTask(TaskId),
     UnblockCommandBuffer(Arc<CommandBufferHandleImpl>),
     WorkerRequest(WorkerRequest),
+    WorkerRetired(WorkerId),
 }
 
 pub struct EventLoopHandle {
@@ -61,12 +67,13 @@ impl EventLoopHandle {
         ctx: fimo_std::context::ContextView<'_>,
         group: Arc<WorkerGroupImpl>,
         num_workers: usize,
+        worker_cpus: Vec<Option<Box<[usize]>>>,
         default_stack_size: usize,
         stacks: Vec<StackDescriptor>,
     ) -> Self {
         let _span = fimo_std::span_trace!(
             ctx,
-            "group: {group:?}, num_workers: {num_workers:?}, \
+            "group: {group:?}, num_workers: {num_workers:?}, worker_cpus: {worker_cpus:?}, \
             default_stack_size: {default_stack_size:?}, stacks: {stacks:?}"
         );
         fimo_std::emit_trace!(ctx, "spawning event loop");
@@ -103,6 +110,7 @@ impl EventLoopHandle {
                                     &module,
                                     group,
                                     num_workers,
+                                    worker_cpus,
                                     default_stack_size,
                                     stacks,
                                     outer_rx,
@@ -155,25 +163,46 @@ impl EventLoopHandle {
     }
 
     pub fn request_close(&self) -> Result<(), Error> {
+        self.request_shutdown(ShutdownMode::Graceful, None)
+    }
+
+    pub fn request_shutdown(
+        &self,
+        mode: ShutdownMode,
+        listener: Option<ShutdownListener>,
+    ) -> Result<(), Error> {
         // Acquire the `RwLock` with `write` permissions, such that no messages are sent while we
-        // try to send the close message.
-        let mut status = self
-            .connection_status
-            .write()
-            .map_err(|_e| <Error>::ECANCELED)?;
+        // try to send the shutdown message.
+        let Ok(mut status) = self.connection_status.write() else {
+            if let Some(listener) = listener {
+                listener.dismiss();
+            }
+            return Err(<Error>::ECANCELED);
+        };
 
-        // If the channel is already closed we can return.
-        if *status == ConnectionStatus::Closed {
+        // If the channel is already closed, a shutdown message is only needed to escalate the
+        // mode, or to register the listener.
+        if *status == ConnectionStatus::Closed
+            && mode == ShutdownMode::Graceful
+            && listener.is_none()
+        {
             return Ok(());
         }
 
-        // Send the message.
-        self.outer_requests
-            .try_send(OuterRequest::Close)
-            .map_err(|e| match e {
-                TrySendError::Full(_) => <Error>::ECOMM,
-                TrySendError::Disconnected(_) => Error::ECONNABORTED,
-            })?;
+        // Send the message. The event loop keeps receiving the shutdown messages after the channel
+        // has been closed. If it has already exited, the listener is invoked once it is dropped.
+        match self
+            .outer_requests
+            .try_send(OuterRequest::Shutdown(mode, listener))
+        {
+            Ok(_) | Err(TrySendError::Disconnected(_)) => {}
+            Err(TrySendError::Full(request)) => {
+                if let OuterRequest::Shutdown(_, Some(listener)) = request {
+                    listener.dismiss();
+                }
+                return Err(<Error>::ECOMM);
+            }
+        }
 
         // Change the status, so that other threads don't keep sending new messages while the
         // channel is still open.
@@ -209,6 +238,55 @@ impl EventLoopHandle {
         Ok(handle)
     }
 
+    pub(in super::super::worker_group) fn add_timer(
+        &self,
+        deadline: Instant,
+        timer: Arc<TimerImpl>,
+    ) -> Result<(), Error> {
+        // Acquire the lock, such that it can not be closed in the meantime.
+        let status = self
+            .connection_status
+            .read()
+            .map_err(|_e| <Error>::ECANCELED)?;
+
+        // If the channel is already closed we can return.
+        if *status == ConnectionStatus::Closed {
+            return Err(<Error>::ECANCELED);
+        }
+
+        // Send the message.
+        self.outer_requests
+            .try_send(OuterRequest::AddTimer(deadline, timer))
+            .map_err(|e| match e {
+                TrySendError::Full(_) => <Error>::ECOMM,
+                TrySendError::Disconnected(_) => <Error>::ECONNABORTED,
+            })
+    }
+
+    pub(in super::super::worker_group) fn resize(
+        &self,
+        num_workers: NonZeroUsize,
+    ) -> Result<(), Error> {
+        // Acquire the lock, such that it can not be closed in the meantime.
+        let status = self
+            .connection_status
+            .read()
+            .map_err(|_e| <Error>::ECANCELED)?;
+
+        // If the channel is already closed we can return.
+        if *status == ConnectionStatus::Closed {
+            return Err(<Error>::ECANCELED);
+        }
+
+        // Send the message.
+        self.outer_requests
+            .try_send(OuterRequest::Resize(num_workers))
+            .map_err(|e| match e {
+                TrySendError::Full(_) => <Error>::ECOMM,
+                TrySendError::Disconnected(_) => <Error>::ECONNABORTED,
+            })
+    }
+
     pub fn wait_for_close(&self) {
         let handle = {
             let mut guard = self.handle.lock().expect("could not lock thread handle");
@@ -241,7 +319,8 @@ impl Drop for EventLoopHandle {
 #[derive(Debug)]
 struct EventLoop {
     is_closed: bool,
-    next_timeout: Instant,
+    shutdown_mode: ShutdownMode,
+    shutdown_listeners: Vec<ShutdownListener>,
     group: Arc<WorkerGroupImpl>,
     stack_manager: stack_manager::StackManager,
     public_messages: Receiver<OuterRequest>,
@@ -249,9 +328,17 @@ struct EventLoop {
     private_messages_sender: Sender<InnerRequest>,
     worker_shared: Arc<WorkerSyncInfo>,
     workers: FxHashMap<WorkerId, WorkerHandle>,
+    next_worker_id: usize,
     blocked_tasks: FxHashMap<TaskId, BlockedTask>,
     handles: FxHashMap<CommandBufferId, CommandBufferImpl>,
-    timeouts: Vec<time_out::TimeOut>,
+    timers: timer_wheel::TimerWheel<TimerEntry>,
+    wait_graph: Option<wait_graph::WaitGraph>,
+}
+
+#[derive(Debug)]
+enum TimerEntry {
+    TimeOut(time_out::TimeOutHandle),
+    Timer(Instant, Arc<TimerImpl>),
 }
 
 #[derive(Debug)]
@@ -263,7 +350,7 @@ enum BlockedTask {
         task: EnqueuedTask,
         buffer: Arc<CommandBufferHandleImpl>,
     },
-    #[allow(dead_code)]
+    /// The task is parked in the parking lot.
     External {
         task: EnqueuedTask,
     },
@@ -271,12 +358,57 @@ enum BlockedTask {
 
 // Outer requests.
 impl EventLoop {
-    fn on_close(&mut self, module: &TasksModule<'_>) {
-        fimo_std::emit_trace!(module.context(), "closing queue");
-        self.is_closed = true;
+    fn on_shutdown(
+        &mut self,
+        module: &TasksModule<'_>,
+        mode: ShutdownMode,
+        listener: Option<ShutdownListener>,
+    ) {
+        if !self.is_closed || mode > self.shutdown_mode {
+            fimo_std::emit_debug!(
+                module.context(),
+                "shutting down worker group {:?}, mode: {mode:?}",
+                self.group.name()
+            );
+        }
+        self.shutdown_listeners.extend(listener);
+
+        let previous_mode = self.shutdown_mode;
+        self.shutdown_mode = self.shutdown_mode.max(mode);
+        if !self.is_closed {
+            fimo_std::emit_trace!(module.context(), "closing queue");
+            self.is_closed = true;
+        } else if previous_mode == self.shutdown_mode {
+            return;
+        }
+
+        if self.shutdown_mode >= ShutdownMode::Drain {
+            // Stop processing the enqueued command buffers.
+            let command_buffers: Vec<_> = self.handles.keys().copied().collect();
+            for id in command_buffers {
+                let command_buffer = self.handles.get_mut(&id).expect("command buffer not found");
+                if command_buffer.abort_for_shutdown(module) {
+                    self.process_command_buffer_commands(module, id);
+                }
+            }
+        }
+
+        if self.shutdown_mode == ShutdownMode::Immediate {
+            // Skip the tasks that were not started yet, and cancel the timers.
+            self.group.abort_pending_tasks();
+            for entry in self.timers.iter() {
+                if let TimerEntry::Timer(_, timer) = entry {
+                    timer.cancel();
+                }
+            }
+        }
     }
 
-    fn on_enqueue_command_buffer(&mut self, module: &TasksModule<'_>, buffer: CommandBufferImpl) {
+    fn on_enqueue_command_buffer(
+        &mut self,
+        module: &TasksModule<'_>,
+        mut buffer: CommandBufferImpl,
+    ) {
         fimo_std::emit_trace!(module.context(), "enqueueing command buffer: {buffer:?}");
         let id = buffer.handle().id();
         if self.handles.contains_key(&id) {
@@ -284,15 +416,63 @@ impl EventLoop {
                 module.context(),
                 "command buffer is already enqueued, buffer: {buffer:?}"
             );
-            let mut buffer = buffer;
             buffer.abort(module, usize::MAX);
             return;
         }
 
+        if let Some(graph) = &mut self.wait_graph {
+            graph.register_buffer(id, buffer.label());
+        }
         self.handles.insert(id, buffer);
         self.worker_shared.notify_command_buffer_enqueued();
         self.process_command_buffer_commands(module, id);
     }
+
+    fn on_add_timer(&mut self, module: &TasksModule<'_>, deadline: Instant, timer: Arc<TimerImpl>) {
+        fimo_std::emit_trace!(
+            module.context(),
+            "adding timer: {timer:?}, deadline: {deadline:?}"
+        );
+        self.timers
+            .insert(deadline, TimerEntry::Timer(deadline, timer));
+    }
+
+    fn on_resize(&mut self, module: &TasksModule<'_>, num_workers: NonZeroUsize) {
+        // The workers are joined once the group is closed.
+        if self.is_closed {
+            fimo_std::emit_debug!(
+                module.context(),
+                "ignoring resize of closed worker group {:?}",
+                self.group.name()
+            );
+            return;
+        }
+
+        let mut active: Vec<_> = self
+            .workers
+            .iter()
+            .filter(|(_, w)| !w.is_retiring())
+            .map(|(id, _)| *id)
+            .collect();
+        let num_workers = num_workers.get();
+        fimo_std::emit_info!(
+            module.context(),
+            "resizing worker group {:?} from {} to {num_workers} workers",
+            self.group.name(),
+            active.len()
+        );
+
+        // Retire the most recently spawned workers first.
+        active.sort_unstable();
+        for id in active.iter().skip(num_workers) {
+            fimo_std::emit_debug!(module.context(), "retiring worker {id:?}");
+            self.workers[id].retire();
+        }
+
+        for _ in active.len()..num_workers {
+            self.spawn_worker(module);
+        }
+    }
 }
 
 // Inner requests.
@@ -305,7 +485,7 @@ impl EventLoop {
             TaskRequest::Complete => {
                 self.finish_task(module, task, false);
             }
-            TaskRequest::Abort(_) => {
+            TaskRequest::Abort(..) => {
                 self.finish_task(module, task, true);
             }
             TaskRequest::Yield => {
@@ -313,14 +493,29 @@ impl EventLoop {
             }
             TaskRequest::WaitUntil(time) => {
                 // Insert the timeout into our timeout queue.
-                let timeout =
-                    time_out::TimeOut::new(time, time_out::TimeOutHandle::Internal(task.id()));
-                self.add_timeout(module, timeout);
+                let handle = time_out::TimeOutHandle::Internal(task.id());
+                self.add_timeout(module, time, handle);
                 self.blocked_tasks
                     .insert(task.id(), BlockedTask::WaitTimeout { task });
             }
             TaskRequest::WaitOnCommandBuffer(handle) => {
                 match handle.completion_status() {
+                    None if !self.owns_command_buffer(&handle) => {
+                        // The buffer is managed by the event loop of another worker group, which
+                        // notifies us through a message.
+                        let task_id = task.id();
+                        let sender = self.private_messages_sender.clone();
+                        handle.on_completion(move || {
+                            let _ = sender.send(InnerRequest::UnblockTask(task_id));
+                        });
+                        self.blocked_tasks.insert(
+                            task_id,
+                            BlockedTask::WaitCommandBuffer {
+                                task,
+                                buffer: handle,
+                            },
+                        );
+                    }
                     None => {
                         // Add the task to the block list.
                         let state = self
@@ -328,6 +523,17 @@ impl EventLoop {
                             .get_mut(&handle.id())
                             .expect("command buffer does not exist");
                         state.register_waiter(Waiter::Task(task.id()));
+                        if let Some(graph) = &mut self.wait_graph {
+                            if let Some(cycle) =
+                                graph.add_task_wait(task.id(), task.buffer_id(), handle.id())
+                            {
+                                fimo_std::emit_error!(
+                                    module.context(),
+                                    "deadlock detected in worker group {:?}:\n{cycle}",
+                                    self.group.name()
+                                );
+                            }
+                        }
                         self.blocked_tasks.insert(
                             task.id(),
                             BlockedTask::WaitCommandBuffer {
@@ -343,6 +549,7 @@ impl EventLoop {
                         call_stack
                             .unblock()
                             .expect("could not unblock task call stack");
+                        task.entry().set_queued();
 
                         // Enqueue the task.
                         let worker_id = task.worker();
@@ -354,6 +561,38 @@ impl EventLoop {
                     }
                 }
             }
+            TaskRequest::Park(handle, deadline) => {
+                // The task may be unparked from any thread, which notifies us through a message.
+                let task_id = task.id();
+                let sender = self.private_messages_sender.clone();
+                let registered = handle.register_waker(move || {
+                    let _ = sender.send(InnerRequest::UnblockTask(task_id));
+                });
+                if registered {
+                    if let Some(deadline) = deadline {
+                        let handle = time_out::TimeOutHandle::External(handle);
+                        self.add_timeout(module, deadline, handle);
+                    }
+                    self.blocked_tasks
+                        .insert(task_id, BlockedTask::External { task });
+                    return;
+                }
+
+                // The task was unparked in the meantime.
+                let mut task = task;
+                let call_stack = task.peek_call_stack();
+                call_stack
+                    .unblock()
+                    .expect("could not unblock task call stack");
+                task.entry().set_queued();
+
+                let worker_id = task.worker();
+                let worker = &self.workers[&worker_id];
+                worker.push_local_response(WorkerResponse {
+                    task,
+                    response: TaskResponse::Park,
+                });
+            }
         }
     }
 
@@ -372,6 +611,7 @@ impl EventLoop {
                 call_stack
                     .unblock()
                     .expect("could not unblock task call stack");
+                task.entry().set_queued();
 
                 let worker_id = task.worker();
                 let worker = &self.workers[&worker_id];
@@ -384,12 +624,16 @@ impl EventLoop {
                 if time_out {
                     panic!("time outs are not supported while waiting on command buffer, task: {task:?}, command buffer: {buffer:?}");
                 }
+                if let Some(graph) = &mut self.wait_graph {
+                    graph.remove_task_wait(task.id());
+                }
 
                 // Unblock the call stack.
                 let call_stack = task.peek_call_stack();
                 call_stack
                     .unblock()
                     .expect("could not unblock task call stack");
+                task.entry().set_queued();
 
                 let aborted = buffer
                     .completion_status()
@@ -401,11 +645,34 @@ impl EventLoop {
                     response: TaskResponse::WaitOnCommandBuffer(aborted),
                 });
             }
-            #[allow(clippy::unimplemented)]
-            BlockedTask::External { .. } => unimplemented!(),
+            BlockedTask::External { mut task } => {
+                // Unblock the call stack.
+                let call_stack = task.peek_call_stack();
+                call_stack
+                    .unblock()
+                    .expect("could not unblock task call stack");
+                task.entry().set_queued();
+
+                let worker_id = task.worker();
+                let worker = &self.workers[&worker_id];
+                worker.push_local_response(WorkerResponse {
+                    task,
+                    response: TaskResponse::Park,
+                });
+            }
         }
     }
 
+    fn on_worker_retired(&mut self, module: &TasksModule<'_>, worker: WorkerId) {
+        fimo_std::emit_trace!(module.context(), "joining retired worker {worker:?}");
+
+        let mut handle = self.workers.remove(&worker).expect("worker not found");
+        handle.wait_for_exit();
+        self.worker_shared.remove_worker(worker);
+        self.group.affinity().remove_worker(worker);
+        self.group.stats().record_retired_worker();
+    }
+
     fn on_unblock_command_buffer(
         &mut self,
         module: &TasksModule<'_>,
@@ -416,6 +683,10 @@ impl EventLoop {
             "unblocking command buffer: {command_buffer:?}",
         );
 
+        if let Some(graph) = &mut self.wait_graph {
+            graph.remove_buffer_wait(command_buffer.id());
+        }
+
         // Check if it was aborted.
         if command_buffer.is_completed() {
             return;
@@ -428,6 +699,13 @@ impl EventLoop {
 
 // Task management.
 impl EventLoop {
+    fn owns_command_buffer(&self, handle: &CommandBufferHandleImpl) -> bool {
+        std::ptr::eq(
+            handle.worker_group_weak().as_ptr(),
+            Arc::as_ptr(&self.group),
+        )
+    }
+
     fn enqueue_task(
         &mut self,
         module: &TasksModule<'_>,
@@ -439,8 +717,20 @@ impl EventLoop {
             "enqueueing task: {task:?}, worker: {worker:?}"
         );
 
+        self.group.tasks().insert(task.id(), task.entry().clone());
+        if let Some(graph) = &mut self.wait_graph {
+            let backtrace = self
+                .handles
+                .get(&task.buffer_id())
+                .and_then(|buffer| buffer.spawn_backtrace());
+            graph.register_task(task.id(), backtrace);
+        }
+
+        // The worker may have been retired after it was selected by the command buffer.
+        let worker = worker
+            .and_then(|worker| self.workers.get(&worker))
+            .filter(|worker| !worker.is_retiring());
         if let Some(worker) = worker {
-            let worker = self.workers.get(&worker).expect("worker not found");
             worker.push_local_response(WorkerResponse {
                 task,
                 response: TaskResponse::Start,
@@ -459,6 +749,10 @@ impl EventLoop {
             "finishing task: {task:?}, aborted: {aborted:?}"
         );
 
+        self.group.tasks().remove(task.id());
+        if let Some(graph) = &mut self.wait_graph {
+            graph.remove_task(task.id());
+        }
         let (_, buffer_id, index, task, stack) = task.into_raw_parts();
 
         // Release the stack.
@@ -472,8 +766,11 @@ impl EventLoop {
                 .handles
                 .get_mut(&buffer_handle.id())
                 .expect("command buffer not found");
-            let (index, worker, task) = command_buffer.mark_task_as_unblocked(task_id);
-            let task = EnqueuedTask::new(module, task_id, buffer_id, index, task, stack);
+            let (index, worker, tag, priority, task) =
+                command_buffer.mark_task_as_unblocked(task_id);
+            let task = EnqueuedTask::new(
+                module, task_id, buffer_id, index, tag, priority, task, stack,
+            );
             self.enqueue_task(module, task, worker);
         }
 
@@ -483,6 +780,8 @@ impl EventLoop {
             .get_mut(&buffer_id)
             .expect("command buffer not found");
 
+        // Record the task before the waiters of the command buffer can be notified.
+        self.group.stats().record_finish(aborted);
         if aborted {
             command_buffer.mark_task_as_aborted(module, index, task);
         } else {
@@ -512,19 +811,10 @@ impl EventLoop {
                 return false;
             }
 
-            let group = handle.worker_group_weak().as_ptr();
-            if !std::ptr::eq(group, Arc::as_ptr(&self.group)) {
-                fimo_std::emit_error!(
-                    module.context(),
-                    "command buffer handle does not belong to the same worker group"
-                );
-                return false;
-            }
-
             true
         };
         let check_worker = |worker| {
-            if !self.workers.contains_key(&worker) {
+            if !self.workers.get(&worker).is_some_and(|w| !w.is_retiring()) {
                 fimo_std::emit_error!(
                     module.context(),
                     "specified worker {worker:?} does not exist"
@@ -561,25 +851,21 @@ impl EventLoop {
                     "command buffer completed: {command_buffer:?}"
                 );
 
-                // Wake all waiters of the command buffer and clean up.
+                // Wake all waiters of the command buffer and clean up. Waiting command buffers
+                // are notified by the completion listeners of the handle.
                 let waiters = command_buffer.take_waiters();
                 for waiter in waiters {
                     match waiter {
                         Waiter::Task(task) => {
                             self.on_unblock_task(module, task, false);
                         }
-                        // Avoid recursive call by sending a message back to ourselves.
-                        Waiter::CommandBuffer(handle) => {
-                            if !handle.is_completed() {
-                                self.private_messages_sender
-                                    .send(InnerRequest::UnblockCommandBuffer(handle))
-                                    .unwrap();
-                            }
-                        }
                     }
                 }
 
                 self.handles.remove(&command_buffer_id);
+                if let Some(graph) = &mut self.wait_graph {
+                    graph.remove_buffer(command_buffer_id);
+                }
                 self.worker_shared.notify_command_buffer_completed();
             }
             CommandBufferEventLoopCommand::SpawnTask(index, task) => {
@@ -587,17 +873,19 @@ impl EventLoop {
                     module.context(),
                     "spawning task, command buffer: {command_buffer:?}, task: {task:?}"
                 );
+                self.group.stats().record_spawn();
+                self.group.task_hooks().emit(
+                    bindings::FiTasksTaskEventType::FI_TASKS_TASK_EVENT_TYPE_SPAWNED,
+                    task.id(),
+                    self.group.id(),
+                    None,
+                );
 
                 // Try to allocate a stack that is large enough to execute the task.
                 let stack_size = command_buffer.stack_size();
                 let stack_size =
                     stack_size.map_or(self.stack_manager.default_stack_size(), |x| x.get());
-                let allocator = self
-                    .stack_manager
-                    .allocator_by_size_mut(stack_size)
-                    .expect("allocator not found");
-
-                let stack = match allocator.acquire_stack() {
+                let stack = match self.stack_manager.acquire_stack(stack_size) {
                     Ok(Some(stack)) => stack,
                     Ok(None) => {
                         // If we aren't successful due to reaching the maximum number of
@@ -606,7 +894,10 @@ impl EventLoop {
                             module.context(),
                             "maximum number of allowed stacks reached for size {stack_size}"
                         );
-                        allocator.register_waiter(command_buffer.handle().clone(), task.id());
+                        self.stack_manager
+                            .allocator_by_size_mut(stack_size)
+                            .expect("allocator not found")
+                            .register_waiter(command_buffer.handle().clone(), task.id());
                         return;
                     }
                     Err(e) => {
@@ -621,25 +912,49 @@ impl EventLoop {
                 let task_id = task.id();
                 let buffer_id = command_buffer.handle().id();
                 let worker = command_buffer.worker();
-                let task = EnqueuedTask::new(module, task_id, buffer_id, index, task, stack);
+                let tag = command_buffer.tag();
+                let priority = command_buffer.priority();
+                let task = EnqueuedTask::new(
+                    module, task_id, buffer_id, index, tag, priority, task, stack,
+                );
                 self.enqueue_task(module, task, worker);
             }
-            CommandBufferEventLoopCommand::WaitCommandBuffer(buffer_id) => {
+            CommandBufferEventLoopCommand::WaitCommandBuffer(buffer) => {
                 fimo_std::emit_trace!(
                     module.context(),
-                    "waiting on command buffer, command buffer: {command_buffer:?}, wait on: {buffer_id:?}"
+                    "waiting on command buffer, command buffer: {command_buffer:?}, wait on: {buffer:?}"
                 );
 
-                let buffer = self
-                    .handles
-                    .get_mut(&buffer_id)
-                    .expect("command buffer not found");
-                debug_assert!(
-                    !buffer.handle().is_completed(),
-                    "buffer has already finished executing"
+                // The buffer may be managed by the event loop of another worker group, therefore
+                // we always resume the waiting buffer through a message.
+                let waiter = command_buffer.handle().clone();
+                let sender = self.private_messages_sender.clone();
+                buffer.on_completion(move || {
+                    let _ = sender.send(InnerRequest::UnblockCommandBuffer(waiter));
+                });
+
+                if let Some(graph) = &mut self.wait_graph {
+                    if let Some(cycle) = graph.add_buffer_wait(command_buffer_id, buffer.id()) {
+                        fimo_std::emit_error!(
+                            module.context(),
+                            "deadlock detected in worker group {:?}:\n{cycle}",
+                            self.group.name()
+                        );
+                    }
+                }
+            }
+            CommandBufferEventLoopCommand::WaitSemaphore(semaphore, value) => {
+                fimo_std::emit_trace!(
+                    module.context(),
+                    "waiting on semaphore, command buffer: {command_buffer:?}, semaphore: \
+                    {semaphore:?}, value: {value:?}"
                 );
 
-                buffer.register_waiter(Waiter::CommandBuffer(buffer.handle().clone()));
+                let waiter = command_buffer.handle().clone();
+                let sender = self.private_messages_sender.clone();
+                semaphore.on_reached(value, move || {
+                    let _ = sender.send(InnerRequest::UnblockCommandBuffer(waiter));
+                });
             }
         }
     }
@@ -649,9 +964,10 @@ impl EventLoop {
 impl EventLoop {
     #[allow(clippy::too_many_arguments)]
     fn new(
-        _module: &TasksModule<'_>,
+        module: &TasksModule<'_>,
         group: Arc<WorkerGroupImpl>,
         num_workers: usize,
+        worker_cpus: Vec<Option<Box<[usize]>>>,
         default_stack_size: usize,
         stacks: Vec<StackDescriptor>,
         outer_receiver: Receiver<OuterRequest>,
@@ -659,14 +975,19 @@ impl EventLoop {
         inner_receiver: Receiver<InnerRequest>,
     ) -> Self {
         let is_closed = false;
-        let next_timeout = Instant::now();
+        let shutdown_mode = ShutdownMode::Graceful;
+        let shutdown_listeners = Vec::new();
         let stack_manager = stack_manager::StackManager::new(default_stack_size, stacks);
+        group.stats().register_stacks(stack_manager.class_stats());
         let public_messages = outer_receiver;
         let private_messages = inner_receiver;
         let private_messages_sender = inner_sender;
         let blocked_tasks = FxHashMap::default();
         let handles = FxHashMap::default();
-        let timeouts = Vec::default();
+        let timers = timer_wheel::TimerWheel::new();
+        let wait_graph = group
+            .detect_deadlocks()
+            .then(wait_graph::WaitGraph::default);
 
         // Bootstrap for the worker threads.
         let worker_bootstrappers = (0..num_workers)
@@ -675,13 +996,23 @@ impl EventLoop {
                 WorkerBootstrapper::new(id, group.clone(), private_messages_sender.clone())
             })
             .collect::<Vec<_>>();
-        let (worker_threads, queue_stealers): (Vec<_>, Vec<_>) = worker_bootstrappers
-            .iter()
-            .map(|w| w.bootstrap_data())
-            .unzip();
-        let queue_stealers = queue_stealers.into_boxed_slice();
-        let worker_threads = worker_threads.into_boxed_slice();
-        let worker_shared = Arc::new(WorkerSyncInfo::new(queue_stealers, worker_threads));
+
+        // Pin the workers before they start executing any tasks.
+        for (worker, cpus) in worker_bootstrappers.iter().zip(worker_cpus) {
+            if let Err(e) = worker.pin(group.affinity(), cpus.as_deref()) {
+                fimo_std::emit_warn!(
+                    module.context(),
+                    "could not set the affinity of the worker {:?} to {cpus:?}, error: {e}",
+                    worker.id()
+                );
+            }
+        }
+
+        let worker_shared = Arc::new(WorkerSyncInfo::default());
+        for worker in &worker_bootstrappers {
+            worker_shared.add_worker(worker.id(), worker.info().clone());
+        }
+        group.stats().register_queues(worker_shared.clone());
 
         // Start the worker threads.
         let workers = worker_bootstrappers
@@ -691,7 +1022,8 @@ impl EventLoop {
 
         Self {
             is_closed,
-            next_timeout,
+            shutdown_mode,
+            shutdown_listeners,
             group,
             stack_manager,
             public_messages,
@@ -699,22 +1031,46 @@ impl EventLoop {
             private_messages_sender,
             worker_shared,
             workers,
+            next_worker_id: num_workers,
             blocked_tasks,
             handles,
-            timeouts,
+            timers,
+            wait_graph,
         }
     }
 
+    /// Spawns a new worker, which is not pinned to any cpus.
+    fn spawn_worker(&mut self, module: &TasksModule<'_>) {
+        let id = WorkerId(self.next_worker_id);
+        self.next_worker_id += 1;
+        fimo_std::emit_debug!(module.context(), "spawning worker {id:?}");
+
+        let worker =
+            WorkerBootstrapper::new(id, self.group.clone(), self.private_messages_sender.clone());
+        if let Err(e) = worker.pin(self.group.affinity(), None) {
+            fimo_std::emit_warn!(
+                module.context(),
+                "could not query the affinity of the worker {id:?}, error: {e}"
+            );
+        }
+        self.worker_shared.add_worker(id, worker.info().clone());
+
+        let (id, handle) = worker.start(self.worker_shared.clone());
+        self.workers.insert(id, handle);
+    }
+
     fn can_join(&self) -> bool {
         self.is_closed && self.handles.is_empty()
     }
 
     fn handle_outer_request(&mut self, module: &TasksModule<'_>, msg: OuterRequest) {
         match msg {
-            OuterRequest::Close => self.on_close(module),
+            OuterRequest::Shutdown(mode, listener) => self.on_shutdown(module, mode, listener),
             OuterRequest::EnqueueCommandBuffer(buffer) => {
                 self.on_enqueue_command_buffer(module, buffer);
             }
+            OuterRequest::AddTimer(deadline, timer) => self.on_add_timer(module, deadline, timer),
+            OuterRequest::Resize(num_workers) => self.on_resize(module, num_workers),
         }
     }
 
@@ -725,49 +1081,45 @@ impl EventLoop {
                 self.on_unblock_command_buffer(module, command_buffer);
             }
             InnerRequest::UnblockTask(task) => self.on_unblock_task(module, task, false),
+            InnerRequest::WorkerRetired(worker) => self.on_worker_retired(module, worker),
         }
     }
 
     fn handle_timeouts(&mut self, module: &TasksModule<'_>) {
         let now = Instant::now();
-
-        // Loop over all timeout instances.
-        // The array is sorted such that `t[i].time >= t[i+1].time`.
-        loop {
-            // If there are no more instances we are done.
-            let timeout = self.timeouts.last();
-            if timeout.is_none() {
-                break;
-            }
-            let time = timeout.unwrap().peek_time();
-
-            // Stop if the timeout has not passed yet.
-            if now < time {
-                self.next_timeout = time.min(self.next_timeout);
-                break;
-            }
-
-            // Remove the instance now that we know that the timeout time has passed.
-            let handle = self.timeouts.pop().unwrap().consume();
-
-            // Some handles are shared outside the event loop, e.g. synchronization operations
-            // between multiple event loops. In those cases we have to ensure that the task is not
-            // enqueued multiple times due to race conditions.
-            if let Some(task) = handle.try_consume() {
-                // Now that consuming the handle was successful, we can wake the task back up.
-                self.on_unblock_task(module, task, true);
+        for entry in self.timers.expire(now) {
+            match entry {
+                TimerEntry::TimeOut(handle) => {
+                    // Some handles are shared outside the event loop, e.g. synchronization
+                    // operations between multiple event loops. In those cases we have to ensure
+                    // that the task is not enqueued multiple times due to race conditions.
+                    if let Some(task) = handle.try_consume() {
+                        // Now that consuming the handle was successful, we can wake the task
+                        // back up.
+                        self.on_unblock_task(module, task, true);
+                    }
+                }
+                TimerEntry::Timer(deadline, timer) => {
+                    fimo_std::emit_trace!(module.context(), "firing timer: {timer:?}");
+                    if let Some(next) = timer.fire(deadline) {
+                        self.timers.insert(next, TimerEntry::Timer(next, timer));
+                    }
+                }
             }
         }
     }
 
-    fn add_timeout(&mut self, module: &TasksModule<'_>, timeout: time_out::TimeOut) {
-        fimo_std::emit_trace!(module.context(), "adding time out, time out: {timeout:?}");
-
-        let insertion_position = self
-            .timeouts
-            .binary_search_by_key(&timeout.peek_time(), |t| t.peek_time())
-            .unwrap_or_else(|pos| pos);
-        self.timeouts.insert(insertion_position, timeout);
+    fn add_timeout(
+        &mut self,
+        module: &TasksModule<'_>,
+        time: Instant,
+        handle: time_out::TimeOutHandle,
+    ) {
+        fimo_std::emit_trace!(
+            module.context(),
+            "adding time out, time: {time:?}, handle: {handle:?}"
+        );
+        self.timers.insert(time, TimerEntry::TimeOut(handle));
     }
 
     fn handle_request(&mut self, module: &TasksModule<'_>) {
@@ -777,9 +1129,11 @@ impl EventLoop {
         // Compute the maximum timeout depending on the next requested timeout.
         let now = Instant::now();
         let timeout = self
-            .next_timeout
-            .checked_duration_since(now)
-            .unwrap_or(MIN_TIMEOUT)
+            .timers
+            .next_deadline()
+            .map_or(MAX_TIMEOUT, |deadline| {
+                deadline.checked_duration_since(now).unwrap_or(MIN_TIMEOUT)
+            })
             .min(MAX_TIMEOUT);
 
         enum Request {
@@ -812,6 +1166,14 @@ impl EventLoop {
             fimo_std::emit_trace!(module.context(), "starting event loop");
             while !self.can_join() {
                 self.handle_request(module);
+                self.worker_shared.check_cleanup_watchdogs(module);
+            }
+
+            // The remaining timers won't be invoked anymore.
+            for entry in self.timers.drain() {
+                if let TimerEntry::Timer(_, timer) = entry {
+                    timer.cancel();
+                }
             }
 
             fimo_std::emit_trace!(module.context(), "joining worker threads");
@@ -819,6 +1181,26 @@ impl EventLoop {
                 worker.join();
             }
             fimo_std::emit_trace!(module.context(), "worker threads joined");
+
+            // No task can access the services anymore.
+            for name in self.group.services().destroy() {
+                fimo_std::emit_trace!(module.context(), "destroyed service {name:?}");
+            }
+
+            // Notify the listeners, including the ones of the requests that were not handled.
+            // Requests arriving after this point are dropped together with the event loop, which
+            // also notifies their listeners.
+            fimo_std::emit_debug!(
+                module.context(),
+                "worker group {:?} shut down",
+                self.group.name()
+            );
+            for request in self.public_messages.try_iter() {
+                if let OuterRequest::Shutdown(_, listener) = request {
+                    self.shutdown_listeners.extend(listener);
+                }
+            }
+            self.shutdown_listeners.clear();
         });
     }
 }
@@ -1,5 +1,6 @@
 use crate::worker_group::{
     command_buffer::CommandBufferHandleImpl,
+    stats::StackStats,
     task::{AcquiredStack, StackMemory},
 };
 use fimo_std::error::Error;
@@ -13,8 +14,13 @@ pub struct StackDescriptor {
     pub target_allocated: usize,
     pub max_allocated: usize,
     pub overflow_protection: bool,
+    pub usage_tracking: bool,
+    pub spilling: bool,
 }
 
+/// Byte pattern used to fill the stacks, whose usage is tracked.
+const STACK_PATTERN: u8 = 0xA5;
+
 #[derive(Debug)]
 pub struct StackManager {
     default_stack_size: usize,
@@ -36,6 +42,8 @@ impl StackManager {
                     target_allocated,
                     max_allocated,
                     overflow_protection,
+                    usage_tracking,
+                    spilling,
                 } = stack;
 
                 StackAllocator::new(
@@ -45,6 +53,8 @@ impl StackManager {
                     target_allocated,
                     max_allocated,
                     overflow_protection,
+                    usage_tracking,
+                    spilling,
                 )
             })
             .collect();
@@ -64,28 +74,55 @@ impl StackManager {
     }
 
     pub fn allocator_by_size(&self, size: usize) -> Option<&StackAllocator> {
-        let idx = match self
-            .allocators
-            .binary_search_by_key(&size, |alloc| alloc.size)
-        {
-            Ok(idx) | Err(idx) => idx,
-        };
-        self.allocators.get(idx)
+        self.allocators.get(self.class_index(size))
     }
 
     pub fn allocator_by_size_mut(&mut self, size: usize) -> Option<&mut StackAllocator> {
-        let idx = match self
-            .allocators
-            .binary_search_by_key(&size, |alloc| alloc.size)
-        {
-            Ok(idx) | Err(idx) => idx,
-        };
+        let idx = self.class_index(size);
         self.allocators.get_mut(idx)
     }
 
     pub fn allocator_by_id_mut(&mut self, id: usize) -> Option<&mut StackAllocator> {
         self.allocators.get_mut(id)
     }
+
+    /// Returns the statistics of the size classes, sorted by the stack size.
+    pub fn class_stats(&self) -> Box<[Arc<StackStats>]> {
+        self.allocators.iter().map(|x| x.stats.clone()).collect()
+    }
+
+    /// Acquires a stack of at least `size` bytes.
+    ///
+    /// If the size class has reached its maximum residency, and allows spilling, the stack is
+    /// acquired from the smallest larger class that has not reached its own maximum. Returns
+    /// `None` if no stack could be acquired.
+    pub fn acquire_stack(&mut self, size: usize) -> Result<Option<AcquiredStack>, Error> {
+        let idx = self.class_index(size);
+        let allocator = self.allocators.get_mut(idx).expect("allocator not found");
+        if let Some(stack) = allocator.acquire_stack()? {
+            return Ok(Some(stack));
+        }
+        if !allocator.spilling {
+            return Ok(None);
+        }
+
+        for larger in &mut self.allocators[idx + 1..] {
+            if let Some(stack) = larger.acquire_stack()? {
+                self.allocators[idx].stats.record_spill();
+                return Ok(Some(stack));
+            }
+        }
+        Ok(None)
+    }
+
+    fn class_index(&self, size: usize) -> usize {
+        match self
+            .allocators
+            .binary_search_by_key(&size, |alloc| alloc.size)
+        {
+            Ok(idx) | Err(idx) => idx,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -93,14 +130,18 @@ pub struct StackAllocator {
     id: usize,
     size: usize,
     protected: bool,
+    usage_tracking: bool,
+    spilling: bool,
     num_acquired: usize,
     max_num_allocated: usize,
     deallocation_threshold: usize,
     free_list: Vec<StackMemory>,
     waiting_tasks: VecDeque<(Arc<CommandBufferHandleImpl>, TaskId)>,
+    stats: Arc<StackStats>,
 }
 
 impl StackAllocator {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         id: usize,
         size: usize,
@@ -108,16 +149,21 @@ impl StackAllocator {
         target_allocated: usize,
         max_allocated: usize,
         overflow_protection: bool,
+        usage_tracking: bool,
+        spilling: bool,
     ) -> Self {
         let mut this = Self {
             id,
             size,
             protected: overflow_protection,
+            usage_tracking,
+            spilling,
             num_acquired: 0,
             max_num_allocated: max_allocated,
             deallocation_threshold: target_allocated,
             free_list: vec![],
             waiting_tasks: Default::default(),
+            stats: Arc::new(StackStats::new(size)),
         };
 
         // Preallocate stacks.
@@ -139,6 +185,7 @@ impl StackAllocator {
 
         if let Some(memory) = self.free_list.pop() {
             self.num_acquired += 1;
+            self.publish_residency();
             return Ok(Some(AcquiredStack::new(self.id, memory)));
         }
 
@@ -150,8 +197,15 @@ impl StackAllocator {
             let stack = context::stack::FixedSizeStack::new(self.size).map_err(Error::new)?;
             StackMemory::Unprotected(stack)
         };
+        if self.usage_tracking {
+            // Safety: The stack is not in use.
+            unsafe {
+                std::ptr::write_bytes(stack.bottom().cast::<u8>(), STACK_PATTERN, stack.len())
+            };
+        }
 
         self.num_acquired += 1;
+        self.publish_residency();
         Ok(Some(AcquiredStack::new(self.id, stack)))
     }
 
@@ -159,6 +213,20 @@ impl StackAllocator {
         let (id, memory) = stack.into_raw_parts();
         debug_assert!(id == self.id);
 
+        if self.usage_tracking {
+            // The stack is filled with the pattern only once, when it is allocated, so the
+            // untouched part is the one not used by any of the tasks that were executed on it.
+            // Safety: The stack is not in use anymore.
+            let memory = unsafe {
+                std::slice::from_raw_parts(memory.bottom().cast_const().cast::<u8>(), memory.len())
+            };
+            let untouched = memory
+                .iter()
+                .position(|&x| x != STACK_PATTERN)
+                .unwrap_or(memory.len());
+            self.stats.record_usage(memory.len() - untouched);
+        }
+
         let num_allocated = self.num_acquired + self.free_list.len();
         if num_allocated <= self.deallocation_threshold {
             self.free_list.push(memory);
@@ -167,6 +235,12 @@ impl StackAllocator {
         }
 
         self.num_acquired -= 1;
+        self.publish_residency();
+    }
+
+    fn publish_residency(&self) {
+        self.stats
+            .set_residency(self.num_acquired, self.free_list.len());
     }
 
     pub fn register_waiter(&mut self, command_buffer: Arc<CommandBufferHandleImpl>, task: TaskId) {
@@ -0,0 +1,130 @@
+use std::time::{Duration, Instant};
+
+/// Resolution of the timer wheel.
+const TICK: Duration = Duration::from_millis(1);
+
+/// Number of slots of the timer wheel.
+const NUM_SLOTS: usize = 512;
+
+/// Hashed timing wheel.
+///
+/// Each entry is assigned to the slot of the tick containing its deadline. Deadlines lying more
+/// than one rotation in the future share the slot with the nearer ones, and are skipped until the
+/// wheel reaches their rotation. Insertions are constant time, while expiring the entries only
+/// visits the slots of the elapsed ticks.
+#[derive(Debug)]
+pub(super) struct TimerWheel<T> {
+    start: Instant,
+    current_tick: u64,
+    slots: Box<[Vec<(u64, T)>]>,
+    len: usize,
+}
+
+impl<T> TimerWheel<T> {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            current_tick: 0,
+            slots: (0..NUM_SLOTS).map(|_| Vec::new()).collect(),
+            len: 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts an entry expiring at `deadline`.
+    ///
+    /// Deadlines in the past expire at the next call to [`TimerWheel::expire`].
+    pub fn insert(&mut self, deadline: Instant, value: T) {
+        let tick = self.tick_of(deadline, true).max(self.current_tick);
+        self.slots[slot_of(tick)].push((tick, value));
+        self.len += 1;
+    }
+
+    /// Returns the deadline of the entry expiring next.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        if self.is_empty() {
+            return None;
+        }
+
+        // Try to find an entry in the current rotation.
+        for tick in self.current_tick..self.current_tick + NUM_SLOTS as u64 {
+            if self.slots[slot_of(tick)].iter().any(|(t, _)| *t == tick) {
+                return Some(self.instant_of(tick));
+            }
+        }
+
+        let tick = self.slots.iter().flatten().map(|(t, _)| *t).min()?;
+        Some(self.instant_of(tick))
+    }
+
+    /// Removes all entries whose deadline is not after `now`.
+    ///
+    /// The entries are returned in the order of their deadlines.
+    pub fn expire(&mut self, now: Instant) -> Vec<T> {
+        let now_tick = self.tick_of(now, false);
+        if now_tick < self.current_tick {
+            return Vec::new();
+        }
+        if self.is_empty() {
+            self.current_tick = now_tick + 1;
+            return Vec::new();
+        }
+
+        let mut expired = Vec::new();
+        let num_ticks = (now_tick - self.current_tick + 1).min(NUM_SLOTS as u64);
+        for tick in self.current_tick..self.current_tick + num_ticks {
+            let slot = &mut self.slots[slot_of(tick)];
+            let mut i = 0;
+            while i < slot.len() {
+                if slot[i].0 <= now_tick {
+                    expired.push(slot.swap_remove(i));
+                } else {
+                    i += 1;
+                }
+            }
+        }
+        self.current_tick = now_tick + 1;
+        self.len -= expired.len();
+
+        expired.sort_by_key(|(tick, _)| *tick);
+        expired.into_iter().map(|(_, value)| value).collect()
+    }
+
+    /// Returns an iterator over all entries, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.slots.iter().flatten().map(|(_, value)| value)
+    }
+
+    /// Removes all entries.
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.len = 0;
+        self.slots
+            .iter_mut()
+            .flat_map(|slot| slot.drain(..))
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the tick containing `instant`, rounding up to the next tick if requested.
+    fn tick_of(&self, instant: Instant, round_up: bool) -> u64 {
+        let elapsed = instant.saturating_duration_since(self.start).as_nanos();
+        let tick = if round_up {
+            elapsed.div_ceil(TICK.as_nanos())
+        } else {
+            elapsed / TICK.as_nanos()
+        };
+        u64::try_from(tick).unwrap_or(u64::MAX)
+    }
+
+    fn instant_of(&self, tick: u64) -> Instant {
+        let nanos = u128::from(tick) * TICK.as_nanos();
+        let nanos = u64::try_from(nanos).unwrap_or(u64::MAX);
+        self.start + Duration::from_nanos(nanos)
+    }
+}
+
+fn slot_of(tick: u64) -> usize {
+    (tick % NUM_SLOTS as u64) as usize
+}
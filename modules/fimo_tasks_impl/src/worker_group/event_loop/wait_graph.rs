@@ -0,0 +1,284 @@
+use crate::worker_group::command_buffer::CommandBufferId;
+use fimo_tasks::TaskId;
+use rustc_hash::FxHashMap;
+use std::{
+    backtrace::Backtrace,
+    collections::VecDeque,
+    ffi::{CStr, CString},
+    fmt::Write,
+    sync::Arc,
+};
+
+/// Tracker of the waits-for relations between the tasks and command buffers of a worker group.
+///
+/// A command buffer can only complete once all of its tasks have completed. A task waiting on a
+/// command buffer, whose completion transitively depends on the task itself, can therefore never
+/// be resumed. The graph detects such a cycle as soon as the edge closing it is inserted.
+///
+/// Only the waits on command buffers are tracked. Waits on the task-aware synchronization
+/// primitives, like semaphores or the primitives built on top of the parking lot, are not
+/// recorded, as their owners are not known to the event loop. Cycles involving such a wait are
+/// therefore not detected. The same applies to cycles spanning multiple worker groups, as each
+/// group tracks only the waits of its own command buffers.
+#[derive(Debug, Default)]
+pub(super) struct WaitGraph {
+    buffers: FxHashMap<CommandBufferId, CString>,
+    tasks: FxHashMap<TaskId, Option<Arc<Backtrace>>>,
+    task_waits: FxHashMap<TaskId, TaskWait>,
+    buffer_waits: FxHashMap<CommandBufferId, CommandBufferId>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TaskWait {
+    buffer: CommandBufferId,
+    waits_on: CommandBufferId,
+}
+
+/// A waits-for edge, where `waiter` can not make progress until `waits_on` completes.
+///
+/// If `task` is set, the edge is caused by the task of `waiter` blocking on `waits_on`, otherwise
+/// by a wait command of `waiter`.
+#[derive(Debug, Clone, Copy)]
+struct WaitEdge {
+    waiter: CommandBufferId,
+    task: Option<TaskId>,
+    waits_on: CommandBufferId,
+}
+
+impl WaitGraph {
+    pub fn register_buffer(&mut self, buffer: CommandBufferId, label: &CStr) {
+        self.buffers.insert(buffer, label.to_owned());
+    }
+
+    pub fn remove_buffer(&mut self, buffer: CommandBufferId) {
+        self.buffers.remove(&buffer);
+        self.buffer_waits
+            .retain(|&waiter, &mut waits_on| waiter != buffer && waits_on != buffer);
+        self.task_waits
+            .retain(|_, wait| wait.buffer != buffer && wait.waits_on != buffer);
+    }
+
+    /// Registers a spawned task, together with the backtrace of the site that spawned it.
+    pub fn register_task(&mut self, task: TaskId, spawn_backtrace: Option<Arc<Backtrace>>) {
+        self.tasks.insert(task, spawn_backtrace);
+    }
+
+    pub fn remove_task(&mut self, task: TaskId) {
+        self.tasks.remove(&task);
+        self.task_waits.remove(&task);
+    }
+
+    /// Records that the task of `buffer` waits on `waits_on`.
+    ///
+    /// Returns a description of the deadlock, if the wait closes a cycle.
+    pub fn add_task_wait(
+        &mut self,
+        task: TaskId,
+        buffer: CommandBufferId,
+        waits_on: CommandBufferId,
+    ) -> Option<String> {
+        self.task_waits.insert(task, TaskWait { buffer, waits_on });
+        let edge = WaitEdge {
+            waiter: buffer,
+            task: Some(task),
+            waits_on,
+        };
+        self.find_cycle(edge).map(|cycle| self.describe(&cycle))
+    }
+
+    pub fn remove_task_wait(&mut self, task: TaskId) {
+        self.task_waits.remove(&task);
+    }
+
+    /// Records that `buffer` waits on `waits_on`.
+    ///
+    /// Returns a description of the deadlock, if the wait closes a cycle.
+    pub fn add_buffer_wait(
+        &mut self,
+        buffer: CommandBufferId,
+        waits_on: CommandBufferId,
+    ) -> Option<String> {
+        self.buffer_waits.insert(buffer, waits_on);
+        let edge = WaitEdge {
+            waiter: buffer,
+            task: None,
+            waits_on,
+        };
+        self.find_cycle(edge).map(|cycle| self.describe(&cycle))
+    }
+
+    pub fn remove_buffer_wait(&mut self, buffer: CommandBufferId) {
+        self.buffer_waits.remove(&buffer);
+    }
+
+    fn edges_from(&self, buffer: CommandBufferId) -> impl Iterator<Item = WaitEdge> + '_ {
+        let buffer_wait = self.buffer_waits.get(&buffer).map(|&waits_on| WaitEdge {
+            waiter: buffer,
+            task: None,
+            waits_on,
+        });
+        let task_waits = self
+            .task_waits
+            .iter()
+            .filter(move |(_, wait)| wait.buffer == buffer)
+            .map(|(&task, wait)| WaitEdge {
+                waiter: wait.buffer,
+                task: Some(task),
+                waits_on: wait.waits_on,
+            });
+        buffer_wait.into_iter().chain(task_waits)
+    }
+
+    /// Searches for a path from the end of `closing` back to its start.
+    ///
+    /// Returns the edges of the cycle starting with `closing`.
+    fn find_cycle(&self, closing: WaitEdge) -> Option<Vec<WaitEdge>> {
+        let start = closing.waits_on;
+        let mut incoming = FxHashMap::<CommandBufferId, WaitEdge>::default();
+        let mut queue = VecDeque::from([start]);
+        while let Some(buffer) = queue.pop_front() {
+            if buffer == closing.waiter {
+                let mut cycle = vec![];
+                let mut current = buffer;
+                while current != start {
+                    let edge = incoming[&current];
+                    cycle.push(edge);
+                    current = edge.waiter;
+                }
+                cycle.push(closing);
+                cycle.reverse();
+                return Some(cycle);
+            }
+
+            for edge in self.edges_from(buffer) {
+                if edge.waits_on != start && !incoming.contains_key(&edge.waits_on) {
+                    incoming.insert(edge.waits_on, edge);
+                    queue.push_back(edge.waits_on);
+                }
+            }
+        }
+
+        None
+    }
+
+    fn describe(&self, cycle: &[WaitEdge]) -> String {
+        let label = |buffer: CommandBufferId| {
+            self.buffers
+                .get(&buffer)
+                .map_or(c"unknown", |label| label.as_c_str())
+        };
+
+        let mut description = String::new();
+        for edge in cycle {
+            let _ = match edge.task {
+                Some(task) => writeln!(
+                    description,
+                    "task {task:?} of command buffer {:?} ({:?}) waits on command buffer {:?} ({:?})",
+                    label(edge.waiter),
+                    edge.waiter,
+                    label(edge.waits_on),
+                    edge.waits_on
+                ),
+                None => writeln!(
+                    description,
+                    "command buffer {:?} ({:?}) waits on command buffer {:?} ({:?})",
+                    label(edge.waiter),
+                    edge.waiter,
+                    label(edge.waits_on),
+                    edge.waits_on
+                ),
+            };
+        }
+
+        for task in cycle.iter().filter_map(|edge| edge.task) {
+            let Some(Some(backtrace)) = self.tasks.get(&task) else {
+                continue;
+            };
+            let _ = writeln!(description, "task {task:?} was spawned at:\n{backtrace}");
+        }
+
+        description
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: CommandBufferId = CommandBufferId(1);
+    const B: CommandBufferId = CommandBufferId(2);
+    const C: CommandBufferId = CommandBufferId(3);
+
+    fn graph() -> WaitGraph {
+        let mut graph = WaitGraph::default();
+        graph.register_buffer(A, c"a");
+        graph.register_buffer(B, c"b");
+        graph.register_buffer(C, c"c");
+        graph
+    }
+
+    #[test]
+    fn cycle() {
+        let mut graph = graph();
+        graph.register_task(TaskId(0), Some(Arc::new(Backtrace::force_capture())));
+        graph.register_task(TaskId(1), None);
+        assert!(graph.add_task_wait(TaskId(0), A, B).is_none());
+        assert!(graph.add_buffer_wait(B, C).is_none());
+
+        let cycle = graph
+            .add_task_wait(TaskId(1), C, A)
+            .expect("the cycle was not detected");
+        assert!(cycle.contains("task TaskId(1) of command buffer \"c\""));
+        assert!(cycle.contains("command buffer \"b\" (CommandBufferId(2)) waits on"));
+        assert!(cycle.contains("task TaskId(0) of command buffer \"a\""));
+        assert!(cycle.contains("task TaskId(0) was spawned at:"));
+        assert!(!cycle.contains("task TaskId(1) was spawned at:"));
+    }
+
+    #[test]
+    fn self_wait() {
+        let mut graph = graph();
+        graph.register_task(TaskId(0), None);
+        assert!(graph.add_task_wait(TaskId(0), A, A).is_some());
+    }
+
+    #[test]
+    fn no_cycle() {
+        let mut graph = graph();
+        graph.register_task(TaskId(0), None);
+        graph.register_task(TaskId(1), None);
+        assert!(graph.add_task_wait(TaskId(0), A, B).is_none());
+        assert!(graph.add_buffer_wait(B, C).is_none());
+        assert!(graph.add_task_wait(TaskId(1), A, C).is_none());
+
+        // Once the wait is resolved, the reversed edge does not close a cycle.
+        graph.remove_task_wait(TaskId(0));
+        graph.remove_task_wait(TaskId(1));
+        assert!(graph.add_buffer_wait(C, A).is_none());
+
+        // Removed buffers and tasks no longer contribute edges.
+        graph.remove_buffer(C);
+        graph.register_task(TaskId(2), None);
+        assert!(graph.add_task_wait(TaskId(2), A, B).is_none());
+        graph.remove_task(TaskId(2));
+        assert!(graph.add_buffer_wait(B, A).is_none());
+    }
+
+    #[test]
+    fn cross_group() {
+        // The buffer is owned by the event loop of another worker group, and is therefore not
+        // registered with the graph, nor are any of its waits.
+        const FOREIGN: CommandBufferId = CommandBufferId(4);
+
+        let mut graph = graph();
+        graph.register_task(TaskId(0), None);
+        assert!(graph.add_buffer_wait(A, FOREIGN).is_none());
+        assert!(graph.add_task_wait(TaskId(0), B, A).is_none());
+        assert!(graph.add_buffer_wait(C, B).is_none());
+
+        // The edge closing the cycle is recorded by the graph of the other group.
+        let mut other = WaitGraph::default();
+        other.register_buffer(FOREIGN, c"foreign");
+        assert!(other.add_buffer_wait(FOREIGN, C).is_none());
+    }
+}
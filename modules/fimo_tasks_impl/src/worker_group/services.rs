@@ -0,0 +1,95 @@
+use fimo_std::error::Error;
+use fimo_tasks::bindings;
+use std::{
+    ffi::{c_void, CStr, CString},
+    sync::Mutex,
+};
+
+/// Services registered with a worker group.
+///
+/// The services are destroyed once the group has shut down, in the reverse order of their
+/// registration. Afterward, no new services can be registered.
+#[derive(Debug, Default)]
+pub struct ServiceRegistry(Mutex<ServiceRegistryInner>);
+
+#[derive(Debug, Default)]
+struct ServiceRegistryInner {
+    services: Vec<Service>,
+    destroyed: bool,
+}
+
+#[derive(Debug)]
+struct Service {
+    name: CString,
+    data: *mut c_void,
+    dtor: bindings::FiTasksGroupServiceDtor,
+}
+
+// Safety: Services must be shareable between the workers of the group.
+unsafe impl Send for Service {}
+
+impl ServiceRegistry {
+    /// Registers a new service.
+    ///
+    /// On failure, the ownership of the service remains with the caller.
+    pub fn register(
+        &self,
+        name: &CStr,
+        data: *mut c_void,
+        dtor: bindings::FiTasksGroupServiceDtor,
+    ) -> Result<(), Error> {
+        let mut inner = self.0.lock().expect("could not lock service registry");
+        if inner.destroyed {
+            return Err(Error::ECANCELED);
+        }
+        if inner.services.iter().any(|s| s.name.as_c_str() == name) {
+            return Err(Error::EEXIST);
+        }
+
+        inner.services.push(Service {
+            name: name.into(),
+            data,
+            dtor,
+        });
+        Ok(())
+    }
+
+    /// Returns the service registered with the name `name`.
+    pub fn get(&self, name: &CStr) -> Result<*mut c_void, Error> {
+        let inner = self.0.lock().expect("could not lock service registry");
+        inner
+            .services
+            .iter()
+            .find(|s| s.name.as_c_str() == name)
+            .map(|s| s.data)
+            .ok_or(Error::ENOENT)
+    }
+
+    /// Destroys all services, in the reverse order of their registration.
+    ///
+    /// Must only be called once no task of the group can access the services anymore.
+    pub fn destroy(&self) -> Vec<CString> {
+        let services = {
+            let mut inner = self.0.lock().expect("could not lock service registry");
+            inner.destroyed = true;
+            std::mem::take(&mut inner.services)
+        };
+
+        // The destructors are invoked without holding the lock.
+        let mut names = Vec::with_capacity(services.len());
+        for service in services.into_iter().rev() {
+            if let Some(dtor) = service.dtor {
+                // Safety: The service is owned by the registry.
+                unsafe { dtor(service.data) };
+            }
+            names.push(service.name);
+        }
+        names
+    }
+}
+
+impl Drop for ServiceRegistry {
+    fn drop(&mut self) {
+        self.destroy();
+    }
+}
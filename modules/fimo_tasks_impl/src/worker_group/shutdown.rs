@@ -0,0 +1,80 @@
+use fimo_std::error::Error;
+use fimo_tasks::bindings;
+use std::{
+    ffi::c_void,
+    fmt::{Debug, Formatter},
+};
+
+/// Shutdown behavior of a worker group.
+///
+/// The modes are ordered by their aggressiveness.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum ShutdownMode {
+    /// Runs all enqueued command buffers to completion.
+    Graceful,
+    /// Aborts the enqueued command buffers at their first unprocessed command.
+    Drain,
+    /// Like [`ShutdownMode::Drain`], but also aborts the tasks that were not started yet and
+    /// cancels the timers.
+    Immediate,
+}
+
+impl TryFrom<bindings::FiTasksShutdownMode> for ShutdownMode {
+    type Error = Error;
+
+    fn try_from(value: bindings::FiTasksShutdownMode) -> Result<Self, Self::Error> {
+        match value {
+            bindings::FiTasksShutdownMode::FI_TASKS_SHUTDOWN_MODE_GRACEFUL => Ok(Self::Graceful),
+            bindings::FiTasksShutdownMode::FI_TASKS_SHUTDOWN_MODE_DRAIN => Ok(Self::Drain),
+            bindings::FiTasksShutdownMode::FI_TASKS_SHUTDOWN_MODE_IMMEDIATE => Ok(Self::Immediate),
+            _ => Err(Error::EINVAL),
+        }
+    }
+}
+
+/// Callback notified once a worker group has shut down.
+///
+/// The callback is invoked when the listener is dropped, which happens once the event loop of the
+/// worker group has exited. Dropping the listener on any other path, e.g., when the request could
+/// not be delivered to an event loop that has already exited, also invokes it.
+pub struct ShutdownListener {
+    func: Option<unsafe extern "C" fn(*mut c_void)>,
+    data: *mut c_void,
+}
+
+impl ShutdownListener {
+    /// # Safety
+    ///
+    /// `func` must be safe to invoke with `data` from any thread.
+    pub unsafe fn new(func: unsafe extern "C" fn(*mut c_void), data: *mut c_void) -> Self {
+        Self {
+            func: Some(func),
+            data,
+        }
+    }
+
+    /// Drops the listener without invoking its callback.
+    pub fn dismiss(mut self) {
+        self.func = None;
+    }
+}
+
+// Safety: The creator of the listener guarantees that the callback may be invoked from any thread.
+unsafe impl Send for ShutdownListener {}
+
+impl Debug for ShutdownListener {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShutdownListener")
+            .field("data", &self.data)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for ShutdownListener {
+    fn drop(&mut self) {
+        if let Some(func) = self.func.take() {
+            // Safety: The creator of the listener guarantees that the function is safe to call.
+            unsafe { func(self.data) }
+        }
+    }
+}
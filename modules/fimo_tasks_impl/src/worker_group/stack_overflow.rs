@@ -0,0 +1,357 @@
+use crate::worker_group::task::StackMemory;
+use fimo_std::error::Error;
+use fimo_tasks::TaskId;
+use std::ffi::{c_char, CStr};
+
+/// Stack of the task executed by the current worker thread.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(not(unix), allow(dead_code))]
+struct ActiveStack {
+    guard_start: usize,
+    guard_end: usize,
+    stack_size: usize,
+    task: TaskId,
+    group: *const c_char,
+}
+
+/// Reporter of stack overflows of the tasks executed by a worker thread.
+///
+/// An overflow is detected when a task accesses the guard page of its stack. The handler then
+/// prints the task and the size of its stack, before the process is terminated. Accesses skipping
+/// over the guard page, and stacks without an overflow protection, can not be detected.
+#[derive(Debug)]
+pub struct OverflowHandler(sys::ThreadHandler);
+
+impl OverflowHandler {
+    /// Installs the handler for the current thread.
+    pub fn new() -> Result<Self, Error> {
+        sys::ThreadHandler::new().map(Self)
+    }
+
+    /// Marks the stack as the one in use by the current thread.
+    pub fn enter(&self, task: TaskId, group: &CStr, stack: &StackMemory) {
+        if !matches!(stack, StackMemory::Protected(_)) {
+            return;
+        }
+
+        // The guard page is located right below the usable part of the stack.
+        let bottom = stack.bottom() as usize;
+        let stack = ActiveStack {
+            guard_start: bottom - sys::page_size(),
+            guard_end: bottom,
+            stack_size: stack.len(),
+            task,
+            group: group.as_ptr(),
+        };
+        self.0.set_active_stack(Some(stack));
+    }
+
+    /// Unmarks the stack in use by the current thread.
+    pub fn exit(&self) {
+        self.0.set_active_stack(None);
+    }
+}
+
+#[cfg(unix)]
+mod sys {
+    use super::ActiveStack;
+    use fimo_std::error::Error;
+    use std::{
+        cell::{Cell, UnsafeCell},
+        ffi::CStr,
+        fmt::Write,
+        mem::MaybeUninit,
+        sync::Mutex,
+    };
+
+    const SIGNALS: [libc::c_int; 2] = [libc::SIGSEGV, libc::SIGBUS];
+    const ALT_STACK_SIZE: usize = 64 * 1024;
+
+    #[thread_local]
+    static ACTIVE_STACK: Cell<Option<ActiveStack>> = Cell::new(None);
+
+    struct PreviousActions(UnsafeCell<[MaybeUninit<libc::sigaction>; SIGNALS.len()]>);
+
+    // Safety: The actions are only written while the handler is not installed.
+    unsafe impl Sync for PreviousActions {}
+
+    static PREVIOUS_ACTIONS: PreviousActions = PreviousActions(UnsafeCell::new(
+        [const { MaybeUninit::uninit() }; SIGNALS.len()],
+    ));
+    static NUM_THREADS: Mutex<usize> = Mutex::new(0);
+
+    pub fn page_size() -> usize {
+        // Safety: Is always safe to call.
+        unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+    }
+
+    #[derive(Debug)]
+    pub struct ThreadHandler {
+        alt_stack: Option<Box<[u8]>>,
+    }
+
+    impl ThreadHandler {
+        pub fn new() -> Result<Self, Error> {
+            let alt_stack = setup_alt_stack()?;
+            let mut num_threads = NUM_THREADS.lock().expect("could not lock handler count");
+            if *num_threads == 0 {
+                // Safety: We hold the lock, and the handler is not installed.
+                if let Err(e) = unsafe { install_handler() } {
+                    drop(num_threads);
+                    disable_alt_stack(alt_stack);
+                    return Err(e);
+                }
+            }
+            *num_threads += 1;
+
+            Ok(Self { alt_stack })
+        }
+
+        pub fn set_active_stack(&self, stack: Option<ActiveStack>) {
+            ACTIVE_STACK.set(stack);
+        }
+    }
+
+    impl Drop for ThreadHandler {
+        fn drop(&mut self) {
+            ACTIVE_STACK.set(None);
+
+            let mut num_threads = NUM_THREADS.lock().expect("could not lock handler count");
+            *num_threads -= 1;
+            if *num_threads == 0 {
+                // Safety: We hold the lock, and are the last thread using the handler.
+                unsafe { uninstall_handler() };
+            }
+            drop(num_threads);
+
+            disable_alt_stack(self.alt_stack.take());
+        }
+    }
+
+    /// Sets up a stack for the signal handler, if the thread does not already have one.
+    fn setup_alt_stack() -> Result<Option<Box<[u8]>>, Error> {
+        let mut current = MaybeUninit::<libc::stack_t>::uninit();
+        // Safety: The pointer is valid for writes.
+        if unsafe { libc::sigaltstack(std::ptr::null(), current.as_mut_ptr()) } != 0 {
+            return Err(last_error());
+        }
+        // Safety: Has been initialized by `sigaltstack`.
+        let current = unsafe { current.assume_init() };
+        if current.ss_flags & libc::SS_DISABLE == 0 {
+            return Ok(None);
+        }
+
+        let mut memory = vec![0u8; ALT_STACK_SIZE].into_boxed_slice();
+        let stack = libc::stack_t {
+            ss_sp: memory.as_mut_ptr().cast(),
+            ss_flags: 0,
+            ss_size: memory.len(),
+        };
+        // Safety: The memory outlives the registration, as it is disabled before being freed.
+        if unsafe { libc::sigaltstack(&stack, std::ptr::null_mut()) } != 0 {
+            return Err(last_error());
+        }
+        Ok(Some(memory))
+    }
+
+    fn disable_alt_stack(memory: Option<Box<[u8]>>) {
+        let Some(memory) = memory else {
+            return;
+        };
+
+        let stack = libc::stack_t {
+            ss_sp: std::ptr::null_mut(),
+            ss_flags: libc::SS_DISABLE,
+            ss_size: ALT_STACK_SIZE,
+        };
+        // Safety: The stack is not in use, as we are not executing the signal handler.
+        unsafe { libc::sigaltstack(&stack, std::ptr::null_mut()) };
+        drop(memory);
+    }
+
+    /// # Safety
+    ///
+    /// Must be called while holding the lock of `NUM_THREADS`.
+    unsafe fn install_handler() -> Result<(), Error> {
+        // Safety: The handler is not installed, so no one reads the previous actions.
+        let previous = unsafe { &mut *PREVIOUS_ACTIONS.0.get() };
+        for (i, &signal) in SIGNALS.iter().enumerate() {
+            // Safety: An all zero `sigaction` is valid.
+            let mut action = unsafe { std::mem::zeroed::<libc::sigaction>() };
+            action.sa_sigaction = handler_address();
+            action.sa_flags = libc::SA_SIGINFO | libc::SA_ONSTACK;
+            // Safety: The pointers are valid.
+            if unsafe { libc::sigaction(signal, &action, previous[i].as_mut_ptr()) } != 0 {
+                let error = last_error();
+                // Safety: The actions of the preceding signals have been replaced.
+                unsafe { restore_previous(&SIGNALS[..i]) };
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+
+    /// # Safety
+    ///
+    /// Must be called while holding the lock of `NUM_THREADS`.
+    unsafe fn uninstall_handler() {
+        // Safety: Is ensured by the caller.
+        unsafe { restore_previous(&SIGNALS) };
+    }
+
+    /// # Safety
+    ///
+    /// The previous actions of the signals must have been recorded.
+    unsafe fn restore_previous(signals: &[libc::c_int]) {
+        // Safety: Is ensured by the caller.
+        let previous = unsafe { &*PREVIOUS_ACTIONS.0.get() };
+        for (i, &signal) in signals.iter().enumerate() {
+            // If another handler was installed after ours we keep it, as we can not restore the
+            // state before ours without also removing it.
+            let mut current = MaybeUninit::<libc::sigaction>::uninit();
+            // Safety: The pointer is valid for writes.
+            unsafe { libc::sigaction(signal, std::ptr::null(), current.as_mut_ptr()) };
+            // Safety: Has been initialized by `sigaction`.
+            if unsafe { current.assume_init_ref() }.sa_sigaction != handler_address() {
+                continue;
+            }
+
+            // Safety: The action has been recorded during the installation.
+            unsafe { libc::sigaction(signal, previous[i].as_ptr(), std::ptr::null_mut()) };
+        }
+    }
+
+    fn handler_address() -> libc::sighandler_t {
+        type Handler = unsafe extern "C" fn(libc::c_int, *mut libc::siginfo_t, *mut libc::c_void);
+        handle_signal as Handler as libc::sighandler_t
+    }
+
+    unsafe extern "C" fn handle_signal(
+        signal: libc::c_int,
+        info: *mut libc::siginfo_t,
+        context: *mut libc::c_void,
+    ) {
+        // Safety: The pointer is provided by the system.
+        let address = unsafe { fault_address(info) };
+        if let Some(stack) = ACTIVE_STACK.get() {
+            if (stack.guard_start..stack.guard_end).contains(&address) {
+                report_overflow(&stack);
+
+                // Returning with the default action re-raises the signal, which terminates the
+                // process.
+                reset_to_default(signal);
+                return;
+            }
+        }
+
+        // Forward the signal to the previous handler.
+        let index = SIGNALS.iter().position(|&x| x == signal).unwrap_or(0);
+        // Safety: The previous actions are not modified while the handler is installed.
+        let previous = unsafe { (*PREVIOUS_ACTIONS.0.get())[index].assume_init_ref() };
+        match previous.sa_sigaction {
+            libc::SIG_DFL | libc::SIG_IGN => reset_to_default(signal),
+            action if previous.sa_flags & libc::SA_SIGINFO != 0 => {
+                // Safety: The handler was registered with the `SA_SIGINFO` flag.
+                let action = unsafe {
+                    std::mem::transmute::<
+                        usize,
+                        extern "C" fn(libc::c_int, *mut libc::siginfo_t, *mut libc::c_void),
+                    >(action)
+                };
+                action(signal, info, context);
+            }
+            action => {
+                // Safety: The handler was registered without the `SA_SIGINFO` flag.
+                let action =
+                    unsafe { std::mem::transmute::<usize, extern "C" fn(libc::c_int)>(action) };
+                action(signal);
+            }
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    unsafe fn fault_address(info: *mut libc::siginfo_t) -> usize {
+        // Safety: Is ensured by the caller.
+        unsafe { (*info).si_addr() as usize }
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    unsafe fn fault_address(info: *mut libc::siginfo_t) -> usize {
+        // Safety: Is ensured by the caller.
+        unsafe { (*info).si_addr as usize }
+    }
+
+    fn reset_to_default(signal: libc::c_int) {
+        // Safety: An all zero `sigaction` is valid.
+        let mut action = unsafe { std::mem::zeroed::<libc::sigaction>() };
+        action.sa_sigaction = libc::SIG_DFL;
+        // Safety: The pointer is valid.
+        unsafe { libc::sigaction(signal, &action, std::ptr::null_mut()) };
+    }
+
+    /// Writes the report without allocating, as we are running inside a signal handler.
+    fn report_overflow(stack: &ActiveStack) {
+        struct Message {
+            buffer: [u8; 512],
+            len: usize,
+        }
+
+        impl Write for Message {
+            fn write_str(&mut self, s: &str) -> std::fmt::Result {
+                let len = s.len().min(self.buffer.len() - self.len);
+                self.buffer[self.len..self.len + len].copy_from_slice(&s.as_bytes()[..len]);
+                self.len += len;
+                Ok(())
+            }
+        }
+
+        // Safety: The name is owned by the worker group, which outlives its workers.
+        let group = unsafe { CStr::from_ptr(stack.group) };
+        let mut message = Message {
+            buffer: [0; 512],
+            len: 0,
+        };
+        let _ = writeln!(
+            message,
+            "fimo_tasks: task {:?} of worker group {:?} has overflowed its stack of {} bytes",
+            stack.task, group, stack.stack_size
+        );
+        // Safety: The buffer is valid for reads.
+        unsafe {
+            libc::write(
+                libc::STDERR_FILENO,
+                message.buffer.as_ptr().cast(),
+                message.len,
+            )
+        };
+    }
+
+    fn last_error() -> Error {
+        Error::from_errno(
+            std::io::Error::last_os_error()
+                .raw_os_error()
+                .unwrap_or(libc::EINVAL),
+        )
+    }
+}
+
+#[cfg(not(unix))]
+mod sys {
+    use super::ActiveStack;
+    use fimo_std::error::Error;
+
+    pub fn page_size() -> usize {
+        0
+    }
+
+    #[derive(Debug)]
+    pub struct ThreadHandler;
+
+    impl ThreadHandler {
+        pub fn new() -> Result<Self, Error> {
+            Ok(Self)
+        }
+
+        pub fn set_active_stack(&self, _stack: Option<ActiveStack>) {}
+    }
+}
@@ -0,0 +1,301 @@
+use crate::worker_group::{task_times::to_ffi_duration, worker_thread::WorkerSyncInfo};
+use fimo_tasks::{bindings, WorkerId};
+use std::{
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, OnceLock,
+    },
+    time::{Duration, Instant},
+};
+
+/// Scheduler statistics of a worker group.
+///
+/// The counters are updated with relaxed atomics, so a snapshot may be slightly inconsistent while
+/// the group is executing tasks.
+#[derive(Debug, Default)]
+pub struct GroupStats {
+    spawned_tasks: AtomicUsize,
+    completed_tasks: AtomicUsize,
+    aborted_tasks: AtomicUsize,
+    started_tasks: AtomicUsize,
+    latency_nanos: AtomicU64,
+    retired_workers: AtomicUsize,
+    queues: OnceLock<Arc<WorkerSyncInfo>>,
+    stacks: OnceLock<Box<[Arc<StackStats>]>>,
+}
+
+impl GroupStats {
+    /// Makes the task queues of the workers available to the snapshots.
+    pub fn register_queues(&self, queues: Arc<WorkerSyncInfo>) {
+        self.queues
+            .set(queues)
+            .expect("the queues should only be registered once");
+    }
+
+    /// Makes the statistics of the stack size classes available to the snapshots.
+    pub fn register_stacks(&self, stacks: Box<[Arc<StackStats>]>) {
+        self.stacks
+            .set(stacks)
+            .expect("the stacks should only be registered once");
+    }
+
+    pub fn record_spawn(&self) {
+        self.spawned_tasks.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_finish(&self, aborted: bool) {
+        if aborted {
+            self.aborted_tasks.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.completed_tasks.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Records the time between spawning a task and the start of its execution.
+    pub fn record_start(&self, latency: Duration) {
+        let nanos = u64::try_from(latency.as_nanos()).unwrap_or(u64::MAX);
+        self.started_tasks.fetch_add(1, Ordering::Relaxed);
+        self.latency_nanos.fetch_add(nanos, Ordering::Relaxed);
+    }
+
+    /// Records the exit of a worker that was retired by a resize of the group.
+    pub fn record_retired_worker(&self) {
+        self.retired_workers.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> bindings::FiTasksWorkerGroupStats {
+        let started_tasks = self.started_tasks.load(Ordering::Relaxed);
+        let average_latency = match started_tasks {
+            0 => Duration::ZERO,
+            n => Duration::from_nanos(self.latency_nanos.load(Ordering::Relaxed) / n as u64),
+        };
+        let stacks = self.stacks.get().map_or(&[][..], |x| &x[..]);
+
+        bindings::FiTasksWorkerGroupStats {
+            spawned_tasks: self.spawned_tasks.load(Ordering::Relaxed),
+            completed_tasks: self.completed_tasks.load(Ordering::Relaxed),
+            aborted_tasks: self.aborted_tasks.load(Ordering::Relaxed),
+            queued_tasks: self.queues.get().map_or(0, |x| x.global_queue_len()),
+            acquired_stacks: stacks
+                .iter()
+                .map(|x| x.acquired.load(Ordering::Relaxed))
+                .sum(),
+            pooled_stacks: stacks
+                .iter()
+                .map(|x| x.pooled.load(Ordering::Relaxed))
+                .sum(),
+            average_latency: to_ffi_duration(average_latency),
+            active_workers: self.queues.get().map_or(0, |x| x.num_active_workers()),
+            retired_workers: self.retired_workers.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns a snapshot of the statistics of the workers, sorted by the worker id.
+    ///
+    /// Includes the workers that are retiring, but not the ones that have already exited.
+    pub fn worker_snapshot(&self) -> Box<[bindings::FiTasksWorkerStats]> {
+        let Some(queues) = self.queues.get() else {
+            return Box::default();
+        };
+        queues
+            .workers()
+            .into_iter()
+            .map(|(id, worker)| worker.stats().snapshot(id, worker.queue_len()))
+            .collect()
+    }
+
+    /// Returns a snapshot of the statistics of the stack size classes, sorted by the size.
+    pub fn stack_snapshot(&self) -> Box<[bindings::FiTasksStackStats]> {
+        self.stacks
+            .get()
+            .map_or(&[][..], |x| &x[..])
+            .iter()
+            .map(|x| x.snapshot())
+            .collect()
+    }
+}
+
+/// Scheduler statistics of a worker.
+#[derive(Debug)]
+pub struct WorkerStats {
+    started: Instant,
+    busy_nanos: AtomicU64,
+}
+
+impl WorkerStats {
+    /// Records the time the worker spent executing a task.
+    pub fn record_busy(&self, duration: Duration) {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        self.busy_nanos.fetch_add(nanos, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self, worker: WorkerId, queued_tasks: usize) -> bindings::FiTasksWorkerStats {
+        let busy_time = Duration::from_nanos(self.busy_nanos.load(Ordering::Relaxed));
+        let idle_time = self.started.elapsed().saturating_sub(busy_time);
+        bindings::FiTasksWorkerStats {
+            worker: worker.0,
+            queued_tasks,
+            busy_time: to_ffi_duration(busy_time),
+            idle_time: to_ffi_duration(idle_time),
+        }
+    }
+}
+
+impl Default for WorkerStats {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            busy_nanos: AtomicU64::new(0),
+        }
+    }
+}
+
+/// Usage statistics of the stacks of one size class.
+#[derive(Debug)]
+pub struct StackStats {
+    size: usize,
+    acquired: AtomicUsize,
+    pooled: AtomicUsize,
+    peak_acquired: AtomicUsize,
+    spilled_tasks: AtomicUsize,
+    peak_usage: AtomicUsize,
+}
+
+impl StackStats {
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            acquired: AtomicUsize::new(0),
+            pooled: AtomicUsize::new(0),
+            peak_acquired: AtomicUsize::new(0),
+            spilled_tasks: AtomicUsize::new(0),
+            peak_usage: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn set_residency(&self, acquired: usize, pooled: usize) {
+        self.acquired.store(acquired, Ordering::Relaxed);
+        self.pooled.store(pooled, Ordering::Relaxed);
+        self.peak_acquired.fetch_max(acquired, Ordering::Relaxed);
+    }
+
+    /// Records that a task was assigned a stack of a larger size class.
+    pub fn record_spill(&self) {
+        self.spilled_tasks.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records the number of bytes used by the tasks executed on a stack.
+    pub fn record_usage(&self, used: usize) {
+        self.peak_usage.fetch_max(used, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> bindings::FiTasksStackStats {
+        bindings::FiTasksStackStats {
+            size: self.size,
+            acquired_stacks: self.acquired.load(Ordering::Relaxed),
+            pooled_stacks: self.pooled.load(Ordering::Relaxed),
+            peak_acquired_stacks: self.peak_acquired.load(Ordering::Relaxed),
+            spilled_tasks: self.spilled_tasks.load(Ordering::Relaxed),
+            peak_usage: self.peak_usage.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn duration(duration: fimo_std::bindings::FimoDuration) -> Duration {
+        Duration::new(duration.secs, duration.nanos)
+    }
+
+    #[test]
+    fn task_counters() {
+        let stats = GroupStats::default();
+        for _ in 0..3 {
+            stats.record_spawn();
+        }
+        stats.record_finish(false);
+        stats.record_finish(true);
+        stats.record_finish(false);
+        stats.record_retired_worker();
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.spawned_tasks, 3);
+        assert_eq!(snapshot.completed_tasks, 2);
+        assert_eq!(snapshot.aborted_tasks, 1);
+        assert_eq!(snapshot.retired_workers, 1);
+        assert_eq!(snapshot.queued_tasks, 0);
+        assert_eq!(snapshot.active_workers, 0);
+        assert!(stats.worker_snapshot().is_empty());
+    }
+
+    #[test]
+    fn average_latency() {
+        let stats = GroupStats::default();
+        assert_eq!(duration(stats.snapshot().average_latency), Duration::ZERO);
+
+        stats.record_start(Duration::from_millis(10));
+        stats.record_start(Duration::from_millis(30));
+        assert_eq!(
+            duration(stats.snapshot().average_latency),
+            Duration::from_millis(20)
+        );
+    }
+
+    #[test]
+    fn stack_residency() {
+        let stats = GroupStats::default();
+        let stacks = [
+            Arc::new(StackStats::new(4096)),
+            Arc::new(StackStats::new(8192)),
+        ];
+        stats.register_stacks(stacks.iter().cloned().collect());
+
+        stacks[0].set_residency(2, 1);
+        stacks[1].set_residency(1, 3);
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.acquired_stacks, 3);
+        assert_eq!(snapshot.pooled_stacks, 4);
+
+        stacks[0].set_residency(0, 3);
+        stacks[0].record_spill();
+        stacks[0].record_usage(1024);
+        stacks[0].record_usage(512);
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.acquired_stacks, 1);
+        assert_eq!(snapshot.pooled_stacks, 6);
+
+        let stack_snapshot = stats.stack_snapshot();
+        assert_eq!(stack_snapshot.len(), 2);
+        assert_eq!(stack_snapshot[0].size, 4096);
+        assert_eq!(stack_snapshot[0].acquired_stacks, 0);
+        assert_eq!(stack_snapshot[0].peak_acquired_stacks, 2);
+        assert_eq!(stack_snapshot[0].spilled_tasks, 1);
+        assert_eq!(stack_snapshot[0].peak_usage, 1024);
+        assert_eq!(stack_snapshot[1].size, 8192);
+        assert_eq!(stack_snapshot[1].peak_acquired_stacks, 1);
+    }
+
+    #[test]
+    fn worker_busy_and_idle_time() {
+        let stats = WorkerStats::default();
+        stats.record_busy(Duration::from_millis(5));
+        stats.record_busy(Duration::from_millis(5));
+        std::thread::sleep(Duration::from_millis(20));
+
+        let snapshot = stats.snapshot(WorkerId(1), 2);
+        assert_eq!(snapshot.worker, 1);
+        assert_eq!(snapshot.queued_tasks, 2);
+        assert_eq!(duration(snapshot.busy_time), Duration::from_millis(10));
+        assert!(duration(snapshot.idle_time) >= Duration::from_millis(10));
+        assert!(
+            stats.started.elapsed() >= duration(snapshot.busy_time) + duration(snapshot.idle_time)
+        );
+
+        // The idle time can not become negative.
+        stats.record_busy(Duration::from_secs(3600));
+        let snapshot = stats.snapshot(WorkerId(1), 0);
+        assert_eq!(duration(snapshot.idle_time), Duration::ZERO);
+    }
+}
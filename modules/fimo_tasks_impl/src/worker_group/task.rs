@@ -2,33 +2,46 @@ use crate::{
     module_export::{TasksModule, TasksModuleToken},
     worker_group::{
         command_buffer::CommandBufferId,
+        task_registry::TaskEntry,
+        task_times::{SliceTimer, TaskTimes},
         worker_thread::{abort_task, complete_task, with_worker_context_lock},
     },
 };
-use fimo_std::{error::Error, ffi::FFISharable, module::Module, tracing::CallStack};
-use fimo_tasks::{TaskId, WorkerId};
+use fimo_std::{
+    allocator::AllocationCounters, error::Error, ffi::FFISharable, module::Module,
+    tracing::CallStack,
+};
+use fimo_tasks::{TaskId, TaskPriority, TaskTag, WorkerId};
 use rustc_hash::FxHashMap;
-use std::{mem::ManuallyDrop, ops::Deref};
+use std::{ffi::CStr, mem::ManuallyDrop, ops::Deref, sync::Arc, time::Instant};
 
 #[derive(Debug)]
 pub struct EnqueuedTask {
     id: TaskId,
     buffer_id: CommandBufferId,
     index: usize,
+    tag: TaskTag,
+    priority: TaskPriority,
     task: RawTask,
     stack: AcquiredStack,
+    times: TaskTimes,
+    spawned: Instant,
     worker: Option<WorkerId>,
     local_data: Option<LocalData>,
     resume_context: Option<context::Context>,
     call_stack: Option<CallStack>,
+    entry: Arc<TaskEntry>,
 }
 
 impl EnqueuedTask {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         module: &TasksModule<'_>,
         id: TaskId,
         buffer_id: CommandBufferId,
         index: usize,
+        tag: TaskTag,
+        priority: TaskPriority,
         task: RawTask,
         stack: AcquiredStack,
     ) -> Self {
@@ -61,8 +74,10 @@ impl EnqueuedTask {
                 })) {
                     // Safety: The task is complete.
                     Ok(_) => unsafe { complete_task().unwrap() },
-                    // Safety: The task was aborted.
-                    Err(_) => unsafe { abort_task(std::ptr::null_mut()).unwrap() },
+                    // Safety: The task was aborted. The panic payload is dropped by the worker on
+                    // its cleanup stack, as its destructor may require more stack space than is
+                    // left.
+                    Err(e) => unsafe { abort_task(std::ptr::null_mut(), Some(e)).unwrap() },
                 }
             });
 
@@ -74,17 +89,23 @@ impl EnqueuedTask {
         let resume_context = unsafe { context::Context::new(stack.memory(), task_start) };
         let call_stack =
             CallStack::new(&module.context()).expect("could not create task call stack");
+        let entry = Arc::new(TaskEntry::new(task.label(), tag));
 
         Self {
             id,
             buffer_id,
             index,
+            tag,
+            priority,
             task,
             stack,
+            times: TaskTimes::default(),
+            spawned: Instant::now(),
             worker: None,
             local_data: Some(local_data),
             resume_context: Some(resume_context),
             call_stack: Some(call_stack),
+            entry,
         }
     }
 
@@ -106,6 +127,7 @@ impl EnqueuedTask {
             let index = this.index;
             let task = this.task;
             let stack = std::ptr::from_ref(&this.stack).read();
+            drop(std::ptr::from_ref(&this.entry).read());
             (id, buffer_id, index, task, stack)
         }
     }
@@ -114,6 +136,44 @@ impl EnqueuedTask {
         self.id
     }
 
+    pub fn buffer_id(&self) -> CommandBufferId {
+        self.buffer_id
+    }
+
+    pub fn tag(&self) -> TaskTag {
+        self.tag
+    }
+
+    pub fn priority(&self) -> TaskPriority {
+        self.priority
+    }
+
+    /// Returns the debug information of the task, as shared with the task registry.
+    pub fn entry(&self) -> &Arc<TaskEntry> {
+        &self.entry
+    }
+
+    pub fn times(&self) -> &TaskTimes {
+        &self.times
+    }
+
+    pub fn stack(&self) -> &AcquiredStack {
+        &self.stack
+    }
+
+    /// Returns the instant at which the task was spawned by the event loop.
+    pub fn spawned(&self) -> Instant {
+        self.spawned
+    }
+
+    pub fn record_slice(&mut self, timer: SliceTimer) {
+        self.times.record_slice(timer);
+    }
+
+    pub fn record_allocations(&mut self, counters: AllocationCounters) {
+        self.times.record_allocations(counters);
+    }
+
     pub fn worker(&self) -> WorkerId {
         self.worker.expect("task not bound to a worker")
     }
@@ -415,6 +475,17 @@ impl RawTask {
         TaskId(self.0.addr())
     }
 
+    pub fn label(&self) -> Option<&CStr> {
+        // Safety: A `RawTask` works like a `Box`. We own the buffer.
+        let task = unsafe { &*self.0 };
+        if task.label.is_null() {
+            None
+        } else {
+            // Safety: The label must live until the completion of the task.
+            unsafe { Some(CStr::from_ptr(task.label)) }
+        }
+    }
+
     fn task_mut(&mut self) -> &mut fimo_tasks::bindings::FiTasksTask {
         // Safety: A `RawTask` works like a `Box`. We own the buffer.
         unsafe { &mut *self.0 }
@@ -0,0 +1,190 @@
+use fimo_tasks::{bindings, TaskId, TaskTag, WorkerId};
+use rustc_hash::FxHashMap;
+use std::{
+    alloc::Layout,
+    ffi::{CStr, CString},
+    sync::{
+        atomic::{AtomicU8, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// Registry of the spawned tasks of a worker group that have not finished yet.
+///
+/// The entries are shared with the control blocks of the tasks, so that the workers can update the
+/// state of a task without locking the registry.
+#[derive(Debug, Default)]
+pub struct TaskRegistry {
+    tasks: Mutex<FxHashMap<TaskId, Arc<TaskEntry>>>,
+}
+
+impl TaskRegistry {
+    pub fn insert(&self, id: TaskId, entry: Arc<TaskEntry>) {
+        let mut tasks = self.tasks.lock().expect("could not lock task registry");
+        tasks.insert(id, entry);
+    }
+
+    pub fn remove(&self, id: TaskId) {
+        let mut tasks = self.tasks.lock().expect("could not lock task registry");
+        tasks.remove(&id);
+    }
+
+    /// Returns a snapshot of the registered tasks, sorted by their id.
+    ///
+    /// The snapshot is allocated as a single block, containing the array of the entries followed
+    /// by their labels, so that it can be released by a single call to `fimo_free`. Returns a null
+    /// pointer if no task is registered.
+    pub fn snapshot(&self) -> (*mut bindings::FiTasksTaskInfo, usize) {
+        let mut tasks: Vec<_> = {
+            let tasks = self.tasks.lock().expect("could not lock task registry");
+            tasks
+                .iter()
+                .map(|(id, entry)| (*id, entry.clone()))
+                .collect()
+        };
+        if tasks.is_empty() {
+            return (std::ptr::null_mut(), 0);
+        }
+        tasks.sort_unstable_by_key(|(id, _)| *id);
+
+        let labels_len: usize = tasks
+            .iter()
+            .filter_map(|(_, entry)| entry.label.as_ref())
+            .map(|label| label.as_bytes_with_nul().len())
+            .sum();
+        let (layout, labels_offset) = Layout::array::<bindings::FiTasksTaskInfo>(tasks.len())
+            .and_then(|infos| infos.extend(Layout::array::<u8>(labels_len)?))
+            .expect("task snapshot too large");
+
+        // Safety: The layout has a non-zero size, as it contains at least one entry.
+        let block = unsafe { std::alloc::alloc(layout) };
+        if block.is_null() {
+            std::alloc::handle_alloc_error(layout);
+        }
+
+        let infos = block.cast::<bindings::FiTasksTaskInfo>();
+        // Safety: The labels are stored after the entries.
+        let mut label_ptr = unsafe { block.add(labels_offset) };
+        for (i, (id, entry)) in tasks.iter().enumerate() {
+            let label = match &entry.label {
+                None => std::ptr::null(),
+                Some(label) => {
+                    let bytes = label.as_bytes_with_nul();
+                    // Safety: The block has enough space for all labels.
+                    unsafe {
+                        std::ptr::copy_nonoverlapping(bytes.as_ptr(), label_ptr, bytes.len());
+                        let ptr = label_ptr;
+                        label_ptr = label_ptr.add(bytes.len());
+                        ptr.cast_const().cast()
+                    }
+                }
+            };
+
+            let (state, wait_reason) = entry.state();
+            let worker = entry.worker();
+            // Safety: The block has enough space for all entries.
+            unsafe {
+                infos.add(i).write(bindings::FiTasksTaskInfo {
+                    id: id.0,
+                    label,
+                    tag: entry.tag.0,
+                    state,
+                    wait_reason,
+                    has_worker: worker.is_some(),
+                    worker: worker.map_or(0, |w| w.0),
+                });
+            }
+        }
+
+        (infos, tasks.len())
+    }
+}
+
+/// Debug information of a task.
+#[derive(Debug)]
+pub struct TaskEntry {
+    label: Option<CString>,
+    tag: TaskTag,
+    state: AtomicU8,
+    worker: AtomicUsize,
+}
+
+impl TaskEntry {
+    const QUEUED: u8 = 0;
+    const RUNNING: u8 = 1;
+    const SLEEPING: u8 = 2;
+    const WAITING_ON_COMMAND_BUFFER: u8 = 3;
+    const PARKED: u8 = 4;
+
+    const NO_WORKER: usize = usize::MAX;
+
+    pub fn new(label: Option<&CStr>, tag: TaskTag) -> Self {
+        Self {
+            label: label.map(CStr::to_owned),
+            tag,
+            state: AtomicU8::new(Self::QUEUED),
+            worker: AtomicUsize::new(Self::NO_WORKER),
+        }
+    }
+
+    /// Marks the task as waiting to be started or resumed.
+    pub fn set_queued(&self) {
+        self.state.store(Self::QUEUED, Ordering::Relaxed);
+    }
+
+    /// Marks the task as being executed by the worker.
+    pub fn set_running(&self, worker: WorkerId) {
+        self.worker.store(worker.0, Ordering::Relaxed);
+        self.state.store(Self::RUNNING, Ordering::Relaxed);
+    }
+
+    /// Marks the task as sleeping until a point in time.
+    pub fn set_sleeping(&self) {
+        self.state.store(Self::SLEEPING, Ordering::Relaxed);
+    }
+
+    /// Marks the task as waiting on the completion of a command buffer.
+    pub fn set_waiting_on_command_buffer(&self) {
+        self.state
+            .store(Self::WAITING_ON_COMMAND_BUFFER, Ordering::Relaxed);
+    }
+
+    /// Marks the task as parked in the parking lot.
+    pub fn set_parked(&self) {
+        self.state.store(Self::PARKED, Ordering::Relaxed);
+    }
+
+    fn state(&self) -> (bindings::FiTasksTaskState, bindings::FiTasksTaskWaitReason) {
+        use bindings::{FiTasksTaskState as State, FiTasksTaskWaitReason as Reason};
+        match self.state.load(Ordering::Relaxed) {
+            Self::QUEUED => (
+                State::FI_TASKS_TASK_STATE_QUEUED,
+                Reason::FI_TASKS_TASK_WAIT_REASON_NONE,
+            ),
+            Self::RUNNING => (
+                State::FI_TASKS_TASK_STATE_RUNNING,
+                Reason::FI_TASKS_TASK_WAIT_REASON_NONE,
+            ),
+            Self::SLEEPING => (
+                State::FI_TASKS_TASK_STATE_BLOCKED,
+                Reason::FI_TASKS_TASK_WAIT_REASON_SLEEP,
+            ),
+            Self::WAITING_ON_COMMAND_BUFFER => (
+                State::FI_TASKS_TASK_STATE_BLOCKED,
+                Reason::FI_TASKS_TASK_WAIT_REASON_COMMAND_BUFFER,
+            ),
+            Self::PARKED => (
+                State::FI_TASKS_TASK_STATE_BLOCKED,
+                Reason::FI_TASKS_TASK_WAIT_REASON_PARKED,
+            ),
+            _ => unreachable!("invalid task state"),
+        }
+    }
+
+    fn worker(&self) -> Option<WorkerId> {
+        match self.worker.load(Ordering::Relaxed) {
+            Self::NO_WORKER => None,
+            worker => Some(WorkerId(worker)),
+        }
+    }
+}
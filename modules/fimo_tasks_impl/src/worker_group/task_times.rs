@@ -0,0 +1,139 @@
+use fimo_std::{allocator::AllocationCounters, ffi::FFITransferable};
+use fimo_tasks::{bindings, TaskTag};
+use rustc_hash::FxHashMap;
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Execution times and allocations of a single task.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TaskTimes {
+    num_slices: usize,
+    wall_time: Duration,
+    cpu_time: Duration,
+    allocations: AllocationCounters,
+}
+
+impl TaskTimes {
+    pub fn wall_time(&self) -> Duration {
+        self.wall_time
+    }
+
+    pub fn cpu_time(&self) -> Duration {
+        self.cpu_time
+    }
+
+    pub fn allocations(&self) -> &AllocationCounters {
+        &self.allocations
+    }
+
+    /// Accounts the allocations recorded during an execution slice to the task.
+    pub fn record_allocations(&mut self, counters: AllocationCounters) {
+        self.allocations += counters;
+    }
+
+    /// Accounts the times measured by the timer to the task.
+    pub fn record_slice(&mut self, timer: SliceTimer) {
+        let wall_time = timer.wall.elapsed();
+        let cpu_time = match (timer.cpu, thread_cpu_time()) {
+            (Some(start), Some(end)) => end.saturating_sub(start),
+            _ => Duration::ZERO,
+        };
+
+        self.num_slices += 1;
+        self.wall_time += wall_time;
+        self.cpu_time += cpu_time;
+    }
+}
+
+/// Timer for measuring a single execution slice of a task.
+///
+/// Must be started and stopped on the same thread, as the cpu time is
+/// measured with the clock of the current thread.
+#[derive(Debug)]
+pub struct SliceTimer {
+    wall: Instant,
+    cpu: Option<Duration>,
+}
+
+impl SliceTimer {
+    pub fn start() -> Self {
+        Self {
+            wall: Instant::now(),
+            cpu: thread_cpu_time(),
+        }
+    }
+}
+
+/// Accumulated task times of a worker group, grouped by the tags of the tasks.
+#[derive(Debug, Default)]
+pub struct TaskTimesTable {
+    tags: Mutex<FxHashMap<TaskTag, TagTimes>>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct TagTimes {
+    num_tasks: usize,
+    times: TaskTimes,
+}
+
+impl TaskTimesTable {
+    /// Accounts the times of a finished task to its tag.
+    pub fn record(&self, tag: TaskTag, times: &TaskTimes) {
+        let mut tags = self.tags.lock().expect("could not lock task times");
+        let entry = tags.entry(tag).or_default();
+        entry.num_tasks += 1;
+        entry.times.num_slices += times.num_slices;
+        entry.times.wall_time += times.wall_time;
+        entry.times.cpu_time += times.cpu_time;
+        entry.times.allocations += times.allocations;
+    }
+
+    /// Returns a snapshot of the accumulated times, sorted by the tag.
+    pub fn snapshot(&self) -> Box<[bindings::FiTasksTaskTimes]> {
+        let tags = self.tags.lock().expect("could not lock task times");
+        let mut times = tags
+            .iter()
+            .map(|(tag, times)| {
+                let allocations = &times.times.allocations;
+                bindings::FiTasksTaskTimes {
+                    tag: tag.0,
+                    num_tasks: times.num_tasks,
+                    num_slices: times.times.num_slices,
+                    wall_time: to_ffi_duration(times.times.wall_time),
+                    cpu_time: to_ffi_duration(times.times.cpu_time),
+                    allocations: allocations.allocations(),
+                    deallocations: allocations.deallocations(),
+                    allocated_bytes: allocations.allocated_bytes(),
+                    deallocated_bytes: allocations.deallocated_bytes(),
+                }
+            })
+            .collect::<Vec<_>>();
+        times.sort_unstable_by_key(|x| x.tag);
+        times.into_boxed_slice()
+    }
+}
+
+pub fn to_ffi_duration(duration: Duration) -> fimo_std::bindings::FimoDuration {
+    fimo_std::time::Duration::new(duration.as_secs(), duration.subsec_nanos()).into_ffi()
+}
+
+#[cfg(unix)]
+fn thread_cpu_time() -> Option<Duration> {
+    let mut time = std::mem::MaybeUninit::<libc::timespec>::uninit();
+    // Safety: The pointer is valid for writes.
+    let result = unsafe { libc::clock_gettime(libc::CLOCK_THREAD_CPUTIME_ID, time.as_mut_ptr()) };
+    if result != 0 {
+        return None;
+    }
+
+    // Safety: The call succeeded, so the value has been initialized.
+    let time = unsafe { time.assume_init() };
+    Some(Duration::new(time.tv_sec as u64, time.tv_nsec as u32))
+}
+
+#[cfg(not(unix))]
+fn thread_cpu_time() -> Option<Duration> {
+    None
+}
@@ -0,0 +1,223 @@
+use fimo_std::{
+    context::ContextView,
+    ffi::{FFISharable, FFITransferable},
+    time::{ClockSubsystem, Time},
+};
+use fimo_tasks::bindings;
+use std::{
+    ffi::c_void,
+    fmt::{Debug, Formatter},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, TryLockError,
+    },
+    time::{Duration, Instant},
+};
+
+struct Callback {
+    func: unsafe extern "C" fn(*mut c_void),
+    data: *mut c_void,
+    cleanup: Option<unsafe extern "C" fn(*mut c_void)>,
+}
+
+// Safety: The creator of the timer guarantees that the callback may be invoked from the event loop.
+unsafe impl Send for Callback {}
+
+impl Drop for Callback {
+    fn drop(&mut self) {
+        if let Some(cleanup) = self.cleanup {
+            // Safety: The creator of the timer guarantees that the function is safe to call.
+            unsafe { cleanup(self.data) }
+        }
+    }
+}
+
+/// Timer scheduled on the event loop of a worker group.
+pub struct TimerImpl {
+    period: Option<Duration>,
+    cancelled: AtomicBool,
+    callback: Mutex<Option<Callback>>,
+}
+
+impl TimerImpl {
+    /// # Safety
+    ///
+    /// `func` and `cleanup` must be safe to invoke with `data` on the event loop thread.
+    pub unsafe fn new(
+        period: Option<Duration>,
+        func: unsafe extern "C" fn(*mut c_void),
+        data: *mut c_void,
+        cleanup: Option<unsafe extern "C" fn(*mut c_void)>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            period,
+            cancelled: AtomicBool::new(false),
+            callback: Mutex::new(Some(Callback {
+                func,
+                data,
+                cleanup,
+            })),
+        })
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Prevents further invocations of the callback.
+    ///
+    /// If the callback is not running, it is cleaned up immediately, otherwise it is cleaned up by
+    /// the event loop after the invocation.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+        let callback = match self.callback.try_lock() {
+            Ok(mut guard) => guard.take(),
+            Err(TryLockError::WouldBlock) => None,
+            Err(TryLockError::Poisoned(_)) => panic!("could not lock timer"),
+        };
+        drop(callback);
+    }
+
+    /// Drops the callback without invoking its cleanup function.
+    pub fn discard(&self) {
+        self.cancelled.store(true, Ordering::Release);
+        let mut guard = self.callback.lock().expect("could not lock timer");
+        if let Some(mut callback) = guard.take() {
+            callback.cleanup = None;
+        }
+    }
+
+    /// Invokes the callback and returns the next deadline of the timer.
+    pub fn fire(&self, deadline: Instant) -> Option<Instant> {
+        let mut guard = self.callback.lock().expect("could not lock timer");
+        let Some(callback) = guard.as_ref() else {
+            return None;
+        };
+        if !self.is_active() {
+            guard.take();
+            return None;
+        }
+
+        // Safety: Is guaranteed by the creator of the timer.
+        unsafe { (callback.func)(callback.data) };
+
+        // The timer may have been cancelled by the callback.
+        match self.period {
+            Some(period) if self.is_active() => {
+                // Skip the periods missed while the event loop was busy.
+                let now = Instant::now();
+                let mut next = deadline + period;
+                if next <= now {
+                    let missed = (now - next).as_nanos() / period.as_nanos() + 1;
+                    let missed = u32::try_from(missed).unwrap_or(u32::MAX);
+                    next += period.saturating_mul(missed);
+                }
+                Some(next)
+            }
+            _ => {
+                self.cancelled.store(true, Ordering::Release);
+                guard.take();
+                None
+            }
+        }
+    }
+}
+
+impl Debug for TimerImpl {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimerImpl")
+            .field("period", &self.period)
+            .field("cancelled", &self.cancelled)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Converts a wall clock time to the corresponding instant of the monotonic clock.
+///
+/// The remaining time is measured with the clock of the context, while the instant itself is
+/// always relative to the monotonic clock of the system.
+pub fn instant_from_time(context: ContextView<'_>, time: Time) -> Instant {
+    let now = Instant::now();
+    match time.duration_since(&context.now()) {
+        Ok(duration) => now + Duration::new(duration.as_secs(), duration.subsec_nanos()),
+        Err(_) => now,
+    }
+}
+
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct TimerFFI(pub Arc<TimerImpl>);
+
+impl TimerFFI {
+    const VTABLE: &'static bindings::FiTasksTimerVTable = &bindings::FiTasksTimerVTable {
+        v0: bindings::FiTasksTimerVTableV0 {
+            acquire: Some(Self::acquire),
+            release: Some(Self::release),
+            cancel: Some(Self::cancel),
+            is_active: Some(Self::is_active),
+        },
+    };
+
+    unsafe extern "C" fn acquire(this: *mut c_void) {
+        fimo_std::panic::abort_on_panic(|| {
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+
+            // Safety: Is always in an Arc.
+            unsafe { Arc::increment_strong_count(this) };
+        });
+    }
+
+    unsafe extern "C" fn release(this: *mut c_void) {
+        fimo_std::panic::abort_on_panic(|| {
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+
+            // Safety: Is always in an Arc.
+            unsafe { Arc::decrement_strong_count(this) };
+        });
+    }
+
+    unsafe extern "C" fn cancel(this: *mut c_void) {
+        fimo_std::panic::abort_on_panic(|| {
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+            this.cancel();
+        });
+    }
+
+    unsafe extern "C" fn is_active(this: *mut c_void) -> bool {
+        fimo_std::panic::abort_on_panic(|| {
+            // Safety: Must be ensured by the caller.
+            let this = unsafe { Self::borrow_from_ffi(this) };
+            this.is_active()
+        })
+    }
+}
+
+impl FFISharable<*mut c_void> for TimerFFI {
+    type BorrowedView<'a> = &'a TimerImpl;
+
+    fn share_to_ffi(&self) -> *mut c_void {
+        Arc::as_ptr(&self.0).cast_mut().cast()
+    }
+
+    unsafe fn borrow_from_ffi<'a>(ffi: *mut c_void) -> Self::BorrowedView<'a> {
+        // Safety: Is sound if `ffi` is the result of `Self::share_to_ffi`.
+        unsafe { &*ffi.cast_const().cast() }
+    }
+}
+
+impl FFITransferable<bindings::FiTasksTimer> for TimerFFI {
+    fn into_ffi(self) -> bindings::FiTasksTimer {
+        bindings::FiTasksTimer {
+            data: Arc::into_raw(self.0).cast_mut().cast(),
+            vtable: Self::VTABLE,
+        }
+    }
+
+    unsafe fn from_ffi(ffi: bindings::FiTasksTimer) -> Self {
+        // Safety: Is always in an `Arc`.
+        unsafe { Self(Arc::from_raw(ffi.data.cast_const().cast())) }
+    }
+}
@@ -1,34 +1,75 @@
 use crate::{
     module_export::{TasksModule, TasksModuleToken},
+    parking_lot::ParkHandle,
     worker_group::{
-        command_buffer::CommandBufferHandleImpl, event_loop::InnerRequest, task::EnqueuedTask,
+        affinity::AffinityTable,
+        cleanup::{CleanupStack, CleanupWatchdog, CLEANUP_TIME_LIMIT},
+        command_buffer::CommandBufferHandleImpl,
+        event_loop::InnerRequest,
+        stack_overflow::OverflowHandler,
+        stats::WorkerStats,
+        task::EnqueuedTask,
+        task_times::SliceTimer,
         WorkerGroupImpl,
     },
 };
 use crossbeam_channel::{Receiver, Sender};
-use crossbeam_deque::{Injector, Stealer, Worker};
-use fimo_std::{error::Error, module::Module, tracing, tracing::ThreadAccess};
-use fimo_tasks::WorkerId;
+use crossbeam_deque::{Injector, Steal, Stealer, Worker};
+use fimo_std::{
+    allocator::AllocationCounters, error::Error, module::Module, tracing, tracing::ThreadAccess,
+};
+use fimo_tasks::{bindings, TaskId, TaskPriority, WorkerId};
 use std::{
-    cell::{RefCell, RefMut},
+    any::Any,
+    cell::{Cell, RefCell, RefMut},
+    ffi::CStr,
     fmt::Debug,
     mem::MaybeUninit,
     sync::{
         atomic::{AtomicBool, AtomicUsize, Ordering},
-        Arc,
+        Arc, RwLock,
     },
     thread::{JoinHandle, Thread},
-    time::Instant,
+    time::{Duration, Instant},
+};
+
+/// Default maximum number of tasks stolen at once by an idle worker.
+pub const DEFAULT_STEAL_BATCH_SIZE: usize = 32;
+
+/// Default execution budget of a task, each time it is resumed.
+pub const DEFAULT_TIME_SLICE: Duration = Duration::from_millis(10);
+
+/// Number of distinct task priorities.
+const NUM_PRIORITIES: usize = 4;
+
+/// Number of times a non-empty priority queue may be passed over in favor of a higher priority,
+/// before it is served regardless of the pending higher priority tasks.
+const AGING_THRESHOLD: usize = 16;
+
+/// Maximum length of the thread names displayed by the native debuggers, excluding the nul
+/// terminator.
+const MAX_THREAD_NAME_LEN: usize = if cfg!(any(target_os = "linux", target_os = "android")) {
+    15
+} else if cfg!(target_vendor = "apple") {
+    63
+} else {
+    usize::MAX
 };
 
 #[thread_local]
 static WORKER_THREAD: WorkerContextLock = WorkerContextLock::new();
 
+/// Time slice of the task executed by the current worker.
+///
+/// Is kept outside of the [`WorkerContext`], so that checkpoints don't need to lock it.
+#[thread_local]
+static CURRENT_SLICE: Cell<Option<TimeSlice>> = Cell::new(None);
+
 #[derive(Debug)]
 pub struct WorkerBootstrapper {
     id: WorkerId,
-    latch: Sender<Arc<WorkerSyncInfo>>,
-    stealer: Stealer<WorkerResponse>,
+    latch: Sender<(Arc<WorkerSyncInfo>, Arc<WorkerInfo>)>,
+    info: Arc<WorkerInfo>,
     join_handle: JoinHandle<()>,
     bound_tasks_sender: Sender<WorkerResponse>,
 }
@@ -44,18 +85,19 @@ impl WorkerBootstrapper {
         let (sx, rx) = crossbeam_channel::unbounded();
         let (latch_sx, latch_rx) = crossbeam_channel::bounded(1);
 
-        let name = format!("{:?} Worker: {id:?}", group.name);
+        let name = worker_thread_name(group.name(), id);
         let join_handle = std::thread::Builder::new()
             .name(name)
             .spawn({
                 let sx = sx.clone();
                 move || {
                     // Wait for the sync object.
-                    let sync = latch_rx.recv().expect("no signal received");
+                    let (sync, info) = latch_rx.recv().expect("no signal received");
 
                     let worker = WorkerThread {
                         id,
                         sync,
+                        info,
                         group,
                         event_loop_sender,
                         bound_tasks_sender: sx,
@@ -67,28 +109,45 @@ impl WorkerBootstrapper {
             })
             .expect("could not create worker thread");
 
+        let info = Arc::new(WorkerInfo {
+            thread: join_handle.thread().clone(),
+            stealer,
+            cleanup_watchdog: Default::default(),
+            stats: Default::default(),
+            retiring: AtomicBool::new(false),
+        });
         Self {
             id,
             latch: latch_sx,
-            stealer,
+            info,
             join_handle,
             bound_tasks_sender: sx,
         }
     }
 
-    pub fn bootstrap_data(&self) -> (Thread, Stealer<WorkerResponse>) {
-        let thread = self.join_handle.thread().clone();
-        let stealer = self.stealer.clone();
-        (thread, stealer)
+    pub fn id(&self) -> WorkerId {
+        self.id
+    }
+
+    /// Pins the worker thread to the cpus and records its effective affinity.
+    pub fn pin(&self, affinity: &AffinityTable, cpus: Option<&[usize]>) -> Result<(), Error> {
+        affinity.pin_worker(self.id, &self.join_handle, cpus)
+    }
+
+    pub fn info(&self) -> &Arc<WorkerInfo> {
+        &self.info
     }
 
     pub fn start(self, sync: Arc<WorkerSyncInfo>) -> (WorkerId, WorkerHandle) {
-        self.latch.send(sync.clone()).expect("can not send signal");
+        self.latch
+            .send((sync.clone(), self.info.clone()))
+            .expect("can not send signal");
 
         (
             self.id,
             WorkerHandle {
                 sync,
+                info: self.info,
                 bound_tasks_sender: self.bound_tasks_sender,
                 join_handle: Some(self.join_handle),
             },
@@ -96,9 +155,27 @@ impl WorkerBootstrapper {
     }
 }
 
+/// Builds the name of the thread of a worker, consisting of the name of the group and the index of
+/// the worker.
+///
+/// The name of the group is shortened, such that the index remains visible on platforms that
+/// truncate the names of the threads.
+fn worker_thread_name(group: &CStr, id: WorkerId) -> String {
+    let group = group.to_string_lossy();
+    let index = format!("/{}", id.0);
+    let mut len = group
+        .len()
+        .min(MAX_THREAD_NAME_LEN.saturating_sub(index.len()));
+    while !group.is_char_boundary(len) {
+        len -= 1;
+    }
+    format!("{}{index}", &group[..len])
+}
+
 #[derive(Debug)]
 pub struct WorkerHandle {
     sync: Arc<WorkerSyncInfo>,
+    info: Arc<WorkerInfo>,
     bound_tasks_sender: Sender<WorkerResponse>,
     join_handle: Option<JoinHandle<()>>,
 }
@@ -115,9 +192,29 @@ impl WorkerHandle {
         }
     }
 
+    /// Requests the worker to retire.
+    ///
+    /// The worker hands over the tasks it has not started yet, and exits once all tasks it has
+    /// started are finished, notifying the event loop with [`InnerRequest::WorkerRetired`].
+    pub fn retire(&self) {
+        self.info.retiring.store(true, Ordering::Release);
+        if let Some(handle) = &self.join_handle {
+            handle.thread().unpark();
+        }
+    }
+
+    pub fn is_retiring(&self) -> bool {
+        self.info.is_retiring()
+    }
+
     pub fn join(&mut self) {
         // Notify all workers to stop executing tasks.
         self.sync.request_join();
+        self.wait_for_exit();
+    }
+
+    /// Waits for the thread of the worker to exit.
+    pub fn wait_for_exit(&mut self) {
         let handle = self.join_handle.take().expect("handle already joined");
 
         // Wake the worker so that we don't deadlock.
@@ -143,10 +240,39 @@ pub struct WorkerContext {
     pub resume_context: Option<context::Context>,
 }
 
+/// Execution budget of a task, assigned each time it is resumed.
+#[derive(Debug, Clone, Copy)]
+struct TimeSlice {
+    /// Instant at which the task must yield, or `None` if the slice does not end.
+    deadline: Option<Instant>,
+    /// Instant of the last checkpoint, or of the start of the slice.
+    last_checkpoint: Instant,
+    /// Longest time the task ran without reaching a checkpoint.
+    longest_interval: Duration,
+}
+
+impl TimeSlice {
+    fn start(time_slice: Duration) -> Self {
+        let now = Instant::now();
+        Self {
+            deadline: now.checked_add(time_slice),
+            last_checkpoint: now,
+            longest_interval: Duration::ZERO,
+        }
+    }
+
+    fn checkpoint(&mut self, now: Instant) {
+        let interval = now.saturating_duration_since(self.last_checkpoint);
+        self.longest_interval = self.longest_interval.max(interval);
+        self.last_checkpoint = now;
+    }
+}
+
 #[derive(Debug)]
 struct WorkerThread {
     id: WorkerId,
     sync: Arc<WorkerSyncInfo>,
+    info: Arc<WorkerInfo>,
     group: Arc<WorkerGroupImpl>,
     event_loop_sender: Sender<InnerRequest>,
     bound_tasks_sender: Sender<WorkerResponse>,
@@ -175,10 +301,14 @@ unsafe impl<T> Send for AssertSend<T> {}
 #[derive(Debug)]
 pub enum TaskRequest {
     Complete,
-    Abort(AssertSend<*mut std::ffi::c_void>),
+    Abort(
+        AssertSend<*mut std::ffi::c_void>,
+        Option<Box<dyn Any + Send + 'static>>,
+    ),
     Yield,
     WaitUntil(Instant),
     WaitOnCommandBuffer(Arc<CommandBufferHandleImpl>),
+    Park(Arc<ParkHandle>, Option<Instant>),
 }
 
 #[derive(Debug)]
@@ -191,37 +321,105 @@ pub enum TaskResponse {
     Yield,
     WaitUntil,
     WaitOnCommandBuffer(bool),
+    Park,
 }
 
+/// State of a worker, shared with the event loop and the other workers.
 #[derive(Debug)]
+pub struct WorkerInfo {
+    thread: Thread,
+    stealer: Stealer<WorkerResponse>,
+    cleanup_watchdog: CleanupWatchdog,
+    stats: WorkerStats,
+    retiring: AtomicBool,
+}
+
+impl WorkerInfo {
+    pub fn stats(&self) -> &WorkerStats {
+        &self.stats
+    }
+
+    /// Returns the number of tasks in the local queue of the worker.
+    pub fn queue_len(&self) -> usize {
+        self.stealer.len()
+    }
+
+    /// Returns whether the worker has been requested to retire.
+    pub fn is_retiring(&self) -> bool {
+        self.retiring.load(Ordering::Acquire)
+    }
+}
+
+#[derive(Debug, Default)]
 pub struct WorkerSyncInfo {
     join_requested: AtomicBool,
     enqueued_command_buffers: AtomicUsize,
-    global_queue: Injector<WorkerResponse>,
-    queue_stealers: Box<[Stealer<WorkerResponse>]>,
-    worker_threads: Box<[Thread]>,
+    global_queues: [Injector<WorkerResponse>; NUM_PRIORITIES],
+    passed_over: [AtomicUsize; NUM_PRIORITIES],
+    /// Workers of the group, indexed by their id. The slots of the retired workers are empty.
+    workers: RwLock<Vec<Option<Arc<WorkerInfo>>>>,
 }
 
 impl WorkerSyncInfo {
-    pub fn new(
-        queue_stealers: Box<[Stealer<WorkerResponse>]>,
-        worker_threads: Box<[Thread]>,
-    ) -> Self {
-        Self {
-            join_requested: AtomicBool::new(false),
-            enqueued_command_buffers: AtomicUsize::new(0),
-            global_queue: Injector::new(),
-            queue_stealers,
-            worker_threads,
+    /// Makes the worker visible to the other workers.
+    pub fn add_worker(&self, id: WorkerId, info: Arc<WorkerInfo>) {
+        let mut workers = self.workers.write().expect("could not lock workers");
+        if workers.len() <= id.0 {
+            workers.resize(id.0 + 1, None);
         }
+        assert!(workers[id.0].is_none(), "worker id reused");
+        workers[id.0] = Some(info);
+    }
+
+    /// Removes a worker that has exited.
+    pub fn remove_worker(&self, id: WorkerId) {
+        let mut workers = self.workers.write().expect("could not lock workers");
+        workers[id.0] = None;
+    }
+
+    /// Returns the workers of the group, sorted by their id.
+    pub fn workers(&self) -> Vec<(WorkerId, Arc<WorkerInfo>)> {
+        let workers = self.workers.read().expect("could not lock workers");
+        workers
+            .iter()
+            .enumerate()
+            .filter_map(|(id, info)| Some((WorkerId(id), info.clone()?)))
+            .collect()
+    }
+
+    /// Returns the number of workers that are not retiring.
+    pub fn num_active_workers(&self) -> usize {
+        let workers = self.workers.read().expect("could not lock workers");
+        workers
+            .iter()
+            .flatten()
+            .filter(|info| !info.is_retiring())
+            .count()
     }
 
     pub fn push_global_response(&self, worker_response: WorkerResponse) {
-        self.global_queue.push(worker_response);
+        let priority = worker_response.task.priority() as usize;
+        self.global_queues[priority].push(worker_response);
 
         // Wake all worker threads.
-        for thread in &self.worker_threads {
-            thread.unpark();
+        let workers = self.workers.read().expect("could not lock workers");
+        for info in workers.iter().flatten() {
+            info.thread.unpark();
+        }
+    }
+
+    /// Moves the tasks of the local queue of a worker, and its deferred task, to the global
+    /// queues.
+    fn hand_over_local_queue(
+        &self,
+        local: &Worker<WorkerResponse>,
+        deferred: &mut Option<WorkerResponse>,
+    ) {
+        if let Some(worker_response) = deferred.take() {
+            self.push_global_response(worker_response);
+        }
+        while let Some(worker_response) = local.pop() {
+            self.push_global_response(worker_response);
         }
     }
 
@@ -239,26 +437,95 @@ impl WorkerSyncInfo {
             .fetch_sub(1, Ordering::Release);
     }
 
+    /// Returns the number of tasks in the global queues.
+    pub fn global_queue_len(&self) -> usize {
+        self.global_queues.iter().map(|x| x.len()).sum()
+    }
+
+    /// Reports all workers which exceeded the time limit while cleaning up a task.
+    pub fn check_cleanup_watchdogs(&self, module: &TasksModule<'_>) {
+        let workers = self.workers.read().expect("could not lock workers");
+        for (i, info) in workers.iter().enumerate() {
+            let Some(info) = info else { continue };
+            if let Some((task, elapsed)) = info.cleanup_watchdog.check_overdue() {
+                fimo_std::emit_warn!(
+                    module.context(),
+                    "worker {i} is cleaning up task {task:?} for {elapsed:?}, \
+                    exceeding the limit of {CLEANUP_TIME_LIMIT:?}"
+                );
+            }
+        }
+    }
+
     fn can_join(&self) -> bool {
         self.join_requested.load(Ordering::Acquire)
             && self.enqueued_command_buffers.load(Ordering::Acquire) == 0
     }
 
-    fn dequeue_task(&self, local: &Worker<WorkerResponse>) -> Option<WorkerResponse> {
-        // Pop a task from the local queue, if not empty.
-        let task = local.pop().or_else(|| {
+    /// Dequeues the next task to execute.
+    ///
+    /// A task popped from the local queue, which is passed over in favor of a task with a higher
+    /// priority, is stored in `deferred`, as the queue only allows pushing to its back. It is
+    /// dequeued before the remaining tasks of the local queue, to preserve their order.
+    fn dequeue_task(
+        &self,
+        id: WorkerId,
+        group: &WorkerGroupImpl,
+        local: &Worker<WorkerResponse>,
+        deferred: &mut Option<WorkerResponse>,
+    ) -> Option<WorkerResponse> {
+        let batch_size = group.steal_batch_size();
+        let stats = group.steal_stats();
+
+        // Pop a task from the local queue, unless there are tasks with a higher priority waiting in
+        // the global queue.
+        let task = match deferred.take().or_else(|| local.pop()) {
+            Some(task) if !self.has_higher_priority_tasks(task.task.priority()) => Some(task),
+            task => {
+                *deferred = task;
+                None
+            }
+        };
+
+        let task = task.or_else(|| {
             // Otherwise, we need to look for a task elsewhere.
             std::iter::repeat_with(|| {
+                // The other workers may steal from the local queue concurrently, so the number of
+                // moved tasks is only an estimate. The popped task is always counted.
+                let queued = local.len();
+
                 // Try stealing a batch of tasks from the global queue.
-                self.global_queue
-                    .steal_batch_and_pop(local)
-                    // Or try stealing a task from one of the other threads.
-                    .or_else(|| self.queue_stealers.iter().map(|s| s.steal()).collect())
+                let global = self.steal_global(local, batch_size);
+                if global.is_success() {
+                    stats.record_global_steal(local.len().saturating_sub(queued) + 1);
+                    return global;
+                }
+
+                // Or try stealing a batch of tasks from one of the other threads.
+                let peer = global.or_else(|| {
+                    let workers = self.workers.read().expect("could not lock workers");
+                    workers
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| *i != id.0)
+                        .filter_map(|(_, info)| info.as_ref())
+                        .map(|info| {
+                            info.stealer
+                                .steal_batch_with_limit_and_pop(local, batch_size)
+                        })
+                        .collect()
+                });
+                if peer.is_success() {
+                    stats.record_peer_steal(local.len().saturating_sub(queued) + 1);
+                }
+                peer
             })
             // Loop while no task was stolen and any steal operation needs to be retried.
             .find(|s| !s.is_retry())
             // Extract the stolen task, if there is one.
             .and_then(|s| s.success())
+            // Fall back to the tasks of the local queue.
+            .or_else(|| deferred.take().or_else(|| local.pop()))
         });
 
         // Park the thread if there were no tasks.
@@ -271,6 +538,63 @@ impl WorkerSyncInfo {
             None
         }
     }
+
+    fn has_higher_priority_tasks(&self, priority: TaskPriority) -> bool {
+        self.global_queues[priority as usize + 1..]
+            .iter()
+            .any(|queue| !queue.is_empty())
+    }
+
+    /// Steals a batch of tasks from the global queue, preferring the higher priorities.
+    ///
+    /// Each time a priority is passed over while it has pending tasks, it ages. Once it has been
+    /// passed over [`AGING_THRESHOLD`] times, it is served before all other priorities.
+    fn steal_global(
+        &self,
+        local: &Worker<WorkerResponse>,
+        batch_size: usize,
+    ) -> Steal<WorkerResponse> {
+        // Serve the priorities which have been passed over for too long.
+        for (queue, passed_over) in self.global_queues.iter().zip(&self.passed_over) {
+            if passed_over.load(Ordering::Relaxed) < AGING_THRESHOLD {
+                continue;
+            }
+            match queue.steal_batch_with_limit_and_pop(local, batch_size) {
+                Steal::Success(task) => {
+                    passed_over.store(0, Ordering::Relaxed);
+                    return Steal::Success(task);
+                }
+                Steal::Empty => passed_over.store(0, Ordering::Relaxed),
+                Steal::Retry => {}
+            }
+        }
+
+        // Otherwise, serve the highest priority with pending tasks.
+        let mut retry = false;
+        for (priority, queue) in self.global_queues.iter().enumerate().rev() {
+            match queue.steal_batch_with_limit_and_pop(local, batch_size) {
+                Steal::Success(task) => {
+                    self.passed_over[priority].store(0, Ordering::Relaxed);
+                    for (queue, passed_over) in
+                        self.global_queues[..priority].iter().zip(&self.passed_over)
+                    {
+                        if !queue.is_empty() {
+                            passed_over.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                    return Steal::Success(task);
+                }
+                Steal::Retry => retry = true,
+                Steal::Empty => {}
+            }
+        }
+
+        if retry {
+            Steal::Retry
+        } else {
+            Steal::Empty
+        }
+    }
 }
 
 impl Drop for WorkerSyncInfo {
@@ -279,6 +603,34 @@ impl Drop for WorkerSyncInfo {
     }
 }
 
+/// Work stealing statistics of a worker group.
+#[derive(Debug, Default)]
+pub struct StealStats {
+    global_steals: AtomicUsize,
+    peer_steals: AtomicUsize,
+    stolen_tasks: AtomicUsize,
+}
+
+impl StealStats {
+    fn record_global_steal(&self, num_tasks: usize) {
+        self.global_steals.fetch_add(1, Ordering::Relaxed);
+        self.stolen_tasks.fetch_add(num_tasks, Ordering::Relaxed);
+    }
+
+    fn record_peer_steal(&self, num_tasks: usize) {
+        self.peer_steals.fetch_add(1, Ordering::Relaxed);
+        self.stolen_tasks.fetch_add(num_tasks, Ordering::Relaxed);
+    }
+
+    pub fn snapshot(&self) -> bindings::FiTasksWorkerGroupStealStats {
+        bindings::FiTasksWorkerGroupStealStats {
+            global_steals: self.global_steals.load(Ordering::Relaxed),
+            peer_steals: self.peer_steals.load(Ordering::Relaxed),
+            stolen_tasks: self.stolen_tasks.load(Ordering::Relaxed),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct WorkerContextLock(RefCell<Option<WorkerContext>>);
 
@@ -317,6 +669,12 @@ pub fn with_worker_context_lock<R>(f: impl FnOnce(&mut WorkerContext) -> R) -> R
     Ok(f(&mut worker))
 }
 
+/// Returns the id of the task executed by the current worker.
+pub fn current_task_id() -> Result<TaskId, Error> {
+    with_worker_context_lock(|worker| worker.current_task.as_ref().map(|task| task.id()))?
+        .ok_or(Error::EPERM)
+}
+
 /// # Safety
 ///
 /// Should not be used directly.
@@ -350,12 +708,19 @@ pub unsafe fn complete_task() -> Result<std::convert::Infallible, Error> {
     }
 }
 
+/// Aborts the current task.
+///
+/// The optional panic payload is dropped by the worker on its cleanup stack.
+///
 /// # Safety
 ///
 /// May only be called upon abortion of a task.
-pub unsafe fn abort_task(error: *mut std::ffi::c_void) -> Result<std::convert::Infallible, Error> {
+pub unsafe fn abort_task(
+    error: *mut std::ffi::c_void,
+    payload: Option<Box<dyn Any + Send + 'static>>,
+) -> Result<std::convert::Infallible, Error> {
     // Safety: Ensured by the caller.
-    let response = unsafe { send_worker_request(TaskRequest::Abort(AssertSend(error)))? };
+    let response = unsafe { send_worker_request(TaskRequest::Abort(AssertSend(error), payload))? };
     match response {
         TaskResponse::Abort(x) => Ok(x),
         _ => unreachable!("should not happen"),
@@ -371,6 +736,18 @@ pub fn yield_now() -> Result<(), Error> {
     }
 }
 
+/// Records a checkpoint of the current task, and returns whether it has exhausted its time slice.
+pub fn time_slice_exhausted() -> Result<bool, Error> {
+    let Some(mut slice) = CURRENT_SLICE.get() else {
+        return Err(Error::EPERM);
+    };
+    let now = Instant::now();
+    slice.checkpoint(now);
+    CURRENT_SLICE.set(Some(slice));
+
+    Ok(slice.deadline.is_some_and(|deadline| now >= deadline))
+}
+
 pub fn wait_until(instant: Instant) -> Result<(), Error> {
     // Safety: Is always safe.
     let response = unsafe { send_worker_request(TaskRequest::WaitUntil(instant))? };
@@ -383,21 +760,6 @@ pub fn wait_until(instant: Instant) -> Result<(), Error> {
 pub fn wait_on_command_buffer(
     handle: Arc<CommandBufferHandleImpl>,
 ) -> Result<bool, (Error, Arc<CommandBufferHandleImpl>)> {
-    // Check that the handle belongs to the same worker group.
-    match with_worker_context_lock(|worker| {
-        let group = handle.worker_group_weak();
-        if !std::ptr::eq(Arc::as_ptr(&worker.group), group.as_ptr()) {
-            Err(Error::EPERM)
-        } else {
-            Ok(())
-        }
-    })
-    .flatten()
-    {
-        Ok(_) => {}
-        Err(e) => return Err((e, handle)),
-    }
-
     // Safety: Is always safe.
     let response = unsafe { send_worker_request(TaskRequest::WaitOnCommandBuffer(handle.clone())) };
     match response {
@@ -407,12 +769,23 @@ pub fn wait_on_command_buffer(
     }
 }
 
+/// Suspends the current task until the handle is woken.
+pub fn park(handle: Arc<ParkHandle>, deadline: Option<Instant>) -> Result<(), Error> {
+    // Safety: Is always safe.
+    let response = unsafe { send_worker_request(TaskRequest::Park(handle, deadline))? };
+    match response {
+        TaskResponse::Park => Ok(()),
+        _ => unreachable!("should not happen"),
+    }
+}
+
 fn worker_event_loop(data: WorkerThread) {
     // Safety: While the event loop is running, the task can not be unloaded.
     unsafe {
         let WorkerThread {
             id,
             sync,
+            info,
             group,
             event_loop_sender,
             bound_tasks_sender,
@@ -429,6 +802,13 @@ fn worker_event_loop(data: WorkerThread) {
             let _span =
                 fimo_std::span_trace!(module.context(), "worker event loop, worker: {id:?}");
 
+            // Allocate the stack for cleaning up aborted tasks.
+            let cleanup_stack = CleanupStack::new().expect("could not allocate cleanup stack");
+
+            // Report the stack overflows of the tasks.
+            let overflow_handler =
+                OverflowHandler::new().expect("could not install stack overflow handler");
+
             // Initialize the shared worker data.
             let shared = WorkerContext {
                 id,
@@ -439,22 +819,86 @@ fn worker_event_loop(data: WorkerThread) {
             // Safety: We are the event loop and are going to uninitialize it.
             WORKER_THREAD.init(shared);
 
+            // Task of the local queue, which was passed over in favor of a task with a higher
+            // priority.
+            let mut deferred_task = None;
+
+            // Number of started tasks, which are bound to the worker until they finish.
+            let mut num_bound_tasks = 0usize;
+            let mut retired = false;
+
             // Loop until we must join.
             while !sync.can_join() {
+                // A retiring worker hands over the tasks it has not started, and exits once all
+                // bound tasks have finished.
+                let retiring = info.is_retiring();
+                if retiring {
+                    sync.hand_over_local_queue(&local_queue, &mut deferred_task);
+                    if num_bound_tasks == 0 {
+                        retired = true;
+                        break;
+                    }
+                }
+
                 // First handle the bound tasks.
                 let WorkerResponse { mut task, response } = match bound_tasks.try_recv() {
                     Ok(task) => task,
+                    // Wait until one of the bound tasks is resumed.
+                    Err(_) if retiring => {
+                        std::thread::park();
+                        continue;
+                    }
                     Err(_) => {
                         // If we don't own any tasks we try to dequeue one.
-                        match sync.dequeue_task(&local_queue) {
+                        match sync.dequeue_task(id, &group, &local_queue, &mut deferred_task) {
                             None => continue,
                             Some(task) => task,
                         }
                     }
                 };
 
+                // Tasks assigned to the worker before it was asked to retire are executed by
+                // the remaining workers.
+                if retiring && matches!(response, TaskResponse::Start) {
+                    sync.push_global_response(WorkerResponse { task, response });
+                    continue;
+                }
+
+                // Abort the tasks that were not started yet, if the group is being shut down
+                // immediately.
+                if matches!(response, TaskResponse::Start) && group.aborts_pending_tasks() {
+                    fimo_std::emit_trace!(
+                        module.context(),
+                        "aborting pending task {:?} due to the shutdown of the worker group",
+                        task.id()
+                    );
+
+                    // Lock the context so that the callbacks can not call into the context.
+                    with_worker_context_lock(|_| {
+                        // Safety: The task was never started and the context is locked.
+                        unsafe { task.run_abort(std::ptr::null_mut()) };
+                    })
+                    .unwrap();
+                    group.task_hooks().emit(
+                        bindings::FiTasksTaskEventType::FI_TASKS_TASK_EVENT_TYPE_FAILED,
+                        task.id(),
+                        group.id(),
+                        Some(id),
+                    );
+
+                    // Notify the main event loop.
+                    let request = TaskRequest::Abort(AssertSend(std::ptr::null_mut()), None);
+                    event_loop_sender
+                        .send(InnerRequest::WorkerRequest(WorkerRequest { task, request }))
+                        .expect("event loop queue should be open");
+                    continue;
+                }
+
                 // Retrieve the context of the task.
                 let context = task.take_resume_context();
+                if matches!(response, TaskResponse::Start) {
+                    num_bound_tasks += 1;
+                }
 
                 // Switch the call stack.
                 tracing::CallStack::suspend_current(&module.context(), false)
@@ -467,13 +911,34 @@ fn worker_event_loop(data: WorkerThread) {
                     .expect("could not resume task call stack");
 
                 // Set the task as active.
+                let task_id = task.id();
+                let task_spawned = task.spawned();
+                overflow_handler.enter(task.id(), group.name(), task.stack().memory());
+                task.entry().set_running(id);
                 with_worker_context_lock(|worker| worker.current_task = Some(task)).unwrap();
+                CURRENT_SLICE.set(Some(TimeSlice::start(group.time_slice())));
 
                 // Jump into the task.
+                let event_type = if matches!(response, TaskResponse::Start) {
+                    group.stats().record_start(task_spawned.elapsed());
+                    bindings::FiTasksTaskEventType::FI_TASKS_TASK_EVENT_TYPE_STARTED
+                } else {
+                    bindings::FiTasksTaskEventType::FI_TASKS_TASK_EVENT_TYPE_RESUMED
+                };
+                group
+                    .task_hooks()
+                    .emit(event_type, task_id, group.id(), Some(id));
                 let response = MaybeUninit::new(response);
+                let timer = SliceTimer::start();
+                let busy_since = Instant::now();
+                let mut allocations = AllocationCounters::new();
                 // Safety: We ensure that everything is set up properly.
-                let context::Transfer { context, data } =
-                    context.resume(response.as_ptr().expose_provenance());
+                let resume = || context.resume(response.as_ptr().expose_provenance());
+                let context::Transfer { context, data } = if group.track_allocations() {
+                    allocations.record(resume)
+                } else {
+                    resume()
+                };
 
                 // Safety: We are passed ownership to a `TaskRequest` instance.
                 let request = std::ptr::with_exposed_provenance::<TaskRequest>(data).read();
@@ -481,13 +946,47 @@ fn worker_event_loop(data: WorkerThread) {
                 // Set the task as inactive.
                 let mut task =
                     with_worker_context_lock(|worker| worker.current_task.take().unwrap()).unwrap();
+                let mut slice = CURRENT_SLICE.take().expect("time slice not started");
+                overflow_handler.exit();
+                let busy = busy_since.elapsed();
+                info.stats().record_busy(busy);
+
+                // Report the tasks that monopolize the worker.
+                slice.checkpoint(Instant::now());
+                if cfg!(debug_assertions) && slice.longest_interval > group.time_slice() {
+                    fimo_std::emit_warn!(
+                        module.context(),
+                        "task exceeded its time slice without reaching a checkpoint, \
+                        id: {task_id:?}, tag: {:?}, interval: {:?}, time_slice: {:?}",
+                        task.tag(),
+                        slice.longest_interval,
+                        group.time_slice()
+                    );
+                }
+                task.record_slice(timer);
+                task.record_allocations(allocations);
                 task.set_resume_context(context);
 
+                let event_type = match request {
+                    TaskRequest::Complete => {
+                        bindings::FiTasksTaskEventType::FI_TASKS_TASK_EVENT_TYPE_COMPLETED
+                    }
+                    TaskRequest::Abort(..) => {
+                        bindings::FiTasksTaskEventType::FI_TASKS_TASK_EVENT_TYPE_FAILED
+                    }
+                    _ => bindings::FiTasksTaskEventType::FI_TASKS_TASK_EVENT_TYPE_YIELDED,
+                };
+                group
+                    .task_hooks()
+                    .emit(event_type, task_id, group.id(), Some(id));
+
                 // Process the request.
                 match request {
                     TaskRequest::Complete => {
                         // Switch back to the event loop call stack.
                         swap_call_stack(module, &mut task, call_stack, false);
+                        record_task_times(module, &group, &task);
+                        num_bound_tasks -= 1;
 
                         // Lock the context so that the callbacks can not call into the context.
                         with_worker_context_lock(|_| {
@@ -501,18 +1000,46 @@ fn worker_event_loop(data: WorkerThread) {
                             .send(InnerRequest::WorkerRequest(WorkerRequest { task, request }))
                             .expect("event loop queue should be open");
                     }
-                    TaskRequest::Abort(AssertSend(error)) => {
+                    TaskRequest::Abort(AssertSend(error), payload) => {
                         // Switch back to the event loop call stack.
                         swap_call_stack(module, &mut task, call_stack, false);
-
-                        // Lock the context so that the callbacks can not call into the context.
-                        with_worker_context_lock(|_| {
-                            // Safety: The task has been aborted and the context is locked.
-                            task.run_abort(error);
-                        })
-                        .unwrap();
+                        record_task_times(module, &group, &task);
+                        num_bound_tasks -= 1;
+
+                        // Run the destructors on the cleanup stack, as the stack of the task may
+                        // not have enough space left.
+                        let watchdog = &info.cleanup_watchdog;
+                        watchdog.start(task.id());
+                        let result = cleanup_stack.run(|| {
+                            drop(payload);
+
+                            // Lock the context so that the callbacks can not call into the context.
+                            with_worker_context_lock(|_| {
+                                // Safety: The task has been aborted and the context is locked.
+                                task.run_abort(error);
+                            })
+                            .unwrap();
+                        });
+                        let elapsed = watchdog.stop();
+                        if elapsed > CLEANUP_TIME_LIMIT {
+                            fimo_std::emit_warn!(
+                                module.context(),
+                                "cleanup of task {:?} took {elapsed:?}, \
+                                exceeding the limit of {CLEANUP_TIME_LIMIT:?}",
+                                task.id()
+                            );
+                        }
+                        if let Err(e) = result {
+                            fimo_std::emit_error!(
+                                module.context(),
+                                "cleanup of task {:?} panicked",
+                                task.id()
+                            );
+                            std::panic::resume_unwind(e);
+                        }
 
                         // Notify the main event loop.
+                        let request = TaskRequest::Abort(AssertSend(error), None);
                         event_loop_sender
                             .send(InnerRequest::WorkerRequest(WorkerRequest { task, request }))
                             .expect("event loop queue should be open");
@@ -520,6 +1047,7 @@ fn worker_event_loop(data: WorkerThread) {
                     TaskRequest::Yield => {
                         // Switch back to the event loop call stack.
                         swap_call_stack(module, &mut task, call_stack, false);
+                        task.entry().set_queued();
 
                         // Push the task onto our task queue.
                         bound_tasks_sender
@@ -534,6 +1062,7 @@ fn worker_event_loop(data: WorkerThread) {
                         if Instant::now() >= timeout {
                             // Switch back to the event loop call stack.
                             swap_call_stack(module, &mut task, call_stack, false);
+                            task.entry().set_queued();
 
                             // Push the task onto our task queue.
                             bound_tasks_sender
@@ -547,6 +1076,7 @@ fn worker_event_loop(data: WorkerThread) {
 
                         // Switch back to the event loop call stack.
                         swap_call_stack(module, &mut task, call_stack, true);
+                        task.entry().set_sleeping();
 
                         // Otherwise we notify the event loop.
                         event_loop_sender
@@ -561,6 +1091,7 @@ fn worker_event_loop(data: WorkerThread) {
                         if let Some(aborted) = handle.completion_status() {
                             // Switch back to the event loop call stack.
                             swap_call_stack(module, &mut task, call_stack, false);
+                            task.entry().set_queued();
 
                             // Push the task onto our task queue.
                             bound_tasks_sender
@@ -574,6 +1105,7 @@ fn worker_event_loop(data: WorkerThread) {
 
                         // Switch back to the event loop call stack.
                         swap_call_stack(module, &mut task, call_stack, true);
+                        task.entry().set_waiting_on_command_buffer();
 
                         // Otherwise we notify the event loop.
                         event_loop_sender
@@ -583,16 +1115,88 @@ fn worker_event_loop(data: WorkerThread) {
                             }))
                             .expect("event loop queue should be open");
                     }
+                    TaskRequest::Park(handle, deadline) => {
+                        // If the task was already unparked we can enqueue it.
+                        if handle.is_woken() {
+                            // Switch back to the event loop call stack.
+                            swap_call_stack(module, &mut task, call_stack, false);
+                            task.entry().set_queued();
+
+                            // Push the task onto our task queue.
+                            bound_tasks_sender
+                                .send(WorkerResponse {
+                                    task,
+                                    response: TaskResponse::Park,
+                                })
+                                .unwrap();
+                            continue;
+                        }
+
+                        // Switch back to the event loop call stack.
+                        swap_call_stack(module, &mut task, call_stack, true);
+                        task.entry().set_parked();
+
+                        // Otherwise we notify the event loop, which waits for the task to be
+                        // woken.
+                        event_loop_sender
+                            .send(InnerRequest::WorkerRequest(WorkerRequest {
+                                task,
+                                request: TaskRequest::Park(handle, deadline),
+                            }))
+                            .expect("event loop queue should be open");
+                    }
                 }
             }
 
             // Drop the shared worker data.
             // Safety: We are the event loop.
             drop(WORKER_THREAD.uninit());
+
+            if retired {
+                // Hand over the tasks that were assigned to the worker in the meantime.
+                for worker_response in bound_tasks.try_iter() {
+                    sync.push_global_response(worker_response);
+                }
+
+                fimo_std::emit_debug!(
+                    module.context(),
+                    "worker {id:?} of worker group {:?} retired",
+                    group.name()
+                );
+                event_loop_sender
+                    .send(InnerRequest::WorkerRetired(id))
+                    .expect("event loop queue should be open");
+            }
         });
     }
 }
 
+fn record_task_times(module: TasksModule<'_>, group: &WorkerGroupImpl, task: &EnqueuedTask) {
+    let tag = task.tag();
+    let times = task.times();
+    fimo_std::emit_trace!(
+        module.context(),
+        "task finished, id: {:?}, tag: {tag:?}, wall_time: {:?}, cpu_time: {:?}",
+        task.id(),
+        times.wall_time(),
+        times.cpu_time()
+    );
+    if group.track_allocations() {
+        let allocations = times.allocations();
+        fimo_std::emit_trace!(
+            module.context(),
+            "task allocations, id: {:?}, tag: {tag:?}, allocations: {}, deallocations: {}, \
+            allocated_bytes: {}, deallocated_bytes: {}",
+            task.id(),
+            allocations.allocations(),
+            allocations.deallocations(),
+            allocations.allocated_bytes(),
+            allocations.deallocated_bytes()
+        );
+    }
+    group.task_times().record(tag, times);
+}
+
 fn swap_call_stack(
     module: TasksModule<'_>,
     task: &mut EnqueuedTask,
@@ -613,6 +613,12 @@ class FimoVersion(c.Structure):
     ]
 
 
+FIMO_VERSION_MAJOR = 0
+FIMO_VERSION_MINOR = 1
+FIMO_VERSION_PATCH = 0
+FIMO_VERSION_BUILD_NUMBER = 0
+
+
 _fimo_version_parse_str = _lib.fimo_version_parse_str
 _fimo_version_parse_str.argtypes = [c.c_char_p, c.c_size_t, c.POINTER(FimoVersion)]
 _fimo_version_parse_str.restype = FimoResult
@@ -1234,6 +1240,7 @@ class FimoStructType(c.c_int):
     FIMO_STRUCT_TYPE_TRACING_SUBSCRIBER = 5
     FIMO_STRUCT_TYPE_MODULE_EXPORT = 6
     FIMO_STRUCT_TYPE_MODULE_INFO = 7
+    FIMO_STRUCT_TYPE_TRACING_MESSAGE_TEMPLATE = 8
 
 
 class FimoBaseStructIn(c.Structure):
@@ -1453,6 +1460,33 @@ It is not an error to format only a part of the message.
 """
 
 
+class FimoTracingField(c.Structure):
+    """A named field of a message template."""
+
+    _fields_ = [
+        ("name", c.c_char_p),
+        ("format", FimoTracingFormat),
+        ("data", c.c_void_p),
+    ]
+
+
+class FimoTracingMessageTemplate(c.Structure):
+    """Un-interpolated message of an event.
+
+    A template is a message containing placeholders of the form `{name}`,
+    which are replaced by the value of the field with the same name. The
+    template is attached to an event through its `next` pointer.
+    """
+
+    _fields_ = [
+        ("type", FimoStructType),
+        ("next", c.POINTER(FimoBaseStructIn)),
+        ("message", c.c_char_p),
+        ("fields", c.POINTER(FimoTracingField)),
+        ("field_count", FimoUSize),
+    ]
+
+
 class FimoTracingSubscriberVTable(c.Structure):
     """VTable of a tracing subscriber.
 
@@ -1942,6 +1976,77 @@ def fimo_tracing_event_emit_custom(
     return _fimo_tracing_event_emit_custom(context, event, format, data)
 
 
+_fimo_tracing_event_emit_template = _lib.fimo_tracing_event_emit_template
+_fimo_tracing_event_emit_template.argtypes = [FimoContext, c.POINTER(FimoTracingEvent)]
+_fimo_tracing_event_emit_template.restype = FimoResult
+
+
+def fimo_tracing_event_emit_template(
+    context: FimoContext, event: Ref[FimoTracingEvent]
+) -> FimoResult:
+    """Emits a new event with a message template.
+
+    The `next` pointer of the event must point to a `FimoTracingMessageTemplate`.
+    The fields are only formatted if the event is not filtered out by the
+    subsystem.
+
+    :param context: the context
+    :param event: the event to emit
+
+    :return: Status code.
+    """
+    return _fimo_tracing_event_emit_template(context, event)
+
+
+_fimo_tracing_event_template = _lib.fimo_tracing_event_template
+_fimo_tracing_event_template.argtypes = [c.POINTER(FimoTracingEvent)]
+_fimo_tracing_event_template.restype = c.POINTER(FimoTracingMessageTemplate)
+
+
+def fimo_tracing_event_template(
+    event: Ref[FimoTracingEvent],
+) -> Pointer[FimoTracingMessageTemplate]:
+    """Returns the message template attached to an event.
+
+    :param event: the event
+
+    :return: The template, or `NULL` if the event has no template.
+    """
+    return _fimo_tracing_event_template(event)
+
+
+_fimo_tracing_format_template = _lib.fimo_tracing_format_template
+_fimo_tracing_format_template.argtypes = [
+    c.POINTER(c.c_char),
+    FimoUSize,
+    c.c_void_p,
+    c.POINTER(FimoUSize),
+]
+_fimo_tracing_format_template.restype = FimoResult
+
+
+def fimo_tracing_format_template(
+    buffer: Pointer[c.c_char],
+    buffer_size: FimoUSize,
+    data: c.c_void_p,
+    written_size: Pointer[FimoUSize],
+) -> FimoResult:
+    """Formatter for message templates.
+
+    Renders the `FimoTracingMessageTemplate` pointed to by `data` into the
+    buffer, by replacing each placeholder with the formatted value of the
+    field.
+
+    :param buffer: destination buffer
+    :param buffer_size: destination buffer size
+    :param data: template to render
+    :param written_size: number of written bytes
+
+    :return: Status code.
+    """
+    return _fimo_tracing_format_template(buffer, buffer_size, data, written_size)
+
+
 _fimo_tracing_is_enabled = _lib.fimo_tracing_is_enabled
 _fimo_tracing_is_enabled.argtypes = [FimoContext]
 _fimo_tracing_is_enabled.restype = c.c_bool
@@ -2201,6 +2306,17 @@ class FimoModuleParamDecl(c.Structure):
     ]
 
 
+class FimoModuleParamInfo(c.Structure):
+    """Information of a module parameter."""
+
+    _fields_ = [
+        ("name", c.c_char_p),
+        ("type", FimoModuleParamType),
+        ("read_access", FimoModuleParamAccess),
+        ("write_access", FimoModuleParamAccess),
+    ]
+
+
 class FimoModuleResourceDecl(c.Structure):
     """Declaration of a module resource."""
 
@@ -2262,6 +2378,9 @@ class FimoModuleExportModifierDestructor(c.Structure):
     _fields_ = [("data", c.c_void_p), ("destructor", c.CFUNCTYPE(None, c.c_void_p))]
 
 
+FIMO_MODULE_EXPORT_ABI = 1
+
+
 class FimoModuleExport(c.Structure):
     """Declaration of a module export."""
 
@@ -2269,6 +2388,7 @@ class FimoModuleExport(c.Structure):
         ("type", FimoStructType),
         ("next", c.POINTER(FimoBaseStructIn)),
         ("export_abi", FimoI32),
+        ("context_version", FimoVersion),
         ("name", c.c_char_p),
         ("description", c.c_char_p),
         ("author", c.c_char_p),
@@ -2298,6 +2418,19 @@ class FimoModuleRawSymbol(c.Structure):
     _fields_ = [("data", c.c_void_p), ("lock", FimoUSize)]
 
 
+class FimoModuleSymbolUseCount(c.Structure):
+    """Number of uses of a symbol exported by a module."""
+
+    _fields_ = [
+        ("name", c.c_char_p),
+        ("ns", c.c_char_p),
+        ("version", FimoVersion),
+        ("use_count", FimoUSize),
+        ("importers", c.POINTER(c.c_char_p)),
+        ("importers_count", FimoUSize),
+    ]
+
+
 _fimo_impl_module_symbol_is_used = _lib.fimo_impl_module_symbol_is_used
 _fimo_impl_module_symbol_is_used.argtypes = [c.POINTER(FimoUSize)]
 _fimo_impl_module_symbol_is_used.restype = c.c_bool
@@ -2754,6 +2887,37 @@ class FimoModuleVTableV0(c.Structure):
                 c.POINTER(FimoModuleParamData),
             ),
         ),
+        (
+            "symbol_use_counts",
+            c.CFUNCTYPE(
+                FimoResult,
+                c.c_void_p,
+                c.POINTER(FimoModuleInfo),
+                c.POINTER(c.POINTER(FimoModuleSymbolUseCount)),
+                c.POINTER(FimoUSize),
+            ),
+        ),
+        (
+            "param_list",
+            c.CFUNCTYPE(
+                FimoResult,
+                c.c_void_p,
+                c.c_char_p,
+                c.POINTER(c.POINTER(FimoModuleParamInfo)),
+                c.POINTER(FimoUSize),
+            ),
+        ),
+        (
+            "param_set_override",
+            c.CFUNCTYPE(
+                FimoResult,
+                c.c_void_p,
+                c.c_char_p,
+                c.c_char_p,
+                c.c_void_p,
+                FimoModuleParamType,
+            ),
+        ),
     ]
 
 
@@ -3442,6 +3606,40 @@ def fimo_module_unload(context: FimoContext, module: Ref[FimoModuleInfo]) -> Fim
     return _fimo_module_unload(context, module)
 
 
+_fimo_module_symbol_use_counts = _lib.fimo_module_symbol_use_counts
+_fimo_module_symbol_use_counts.argtypes = [
+    FimoContext,
+    c.POINTER(FimoModuleInfo),
+    c.POINTER(c.POINTER(FimoModuleSymbolUseCount)),
+    c.POINTER(FimoUSize),
+]
+_fimo_module_symbol_use_counts.restype = FimoResult
+
+
+def fimo_module_symbol_use_counts(
+    context: FimoContext,
+    module: Ref[FimoModuleInfo],
+    use_counts: PtrRef[FimoModuleSymbolUseCount],
+    use_counts_count: Ref[FimoUSize],
+) -> FimoResult:
+    """Queries the number of uses of the symbols exported by a module.
+
+    A symbol is in use while it is locked. The counts are only a snapshot,
+    and may change concurrently. The entries, and the strings they reference,
+    are stored in a single allocation, which must be freed with `fimo_free`.
+    The modules using the symbols of `module` are the modules that depend on
+    it. This function fails, if `module` is not loaded.
+
+    :param context: the context
+    :param module: module exporting the symbols
+    :param use_counts: resulting array of use counts
+    :param use_counts_count: number of entries in `use_counts`
+
+    :return: Status code.
+    """
+    return _fimo_module_symbol_use_counts(context, module, use_counts, use_counts_count)
+
+
 _fimo_module_param_query = _lib.fimo_module_param_query
 _fimo_module_param_query.argtypes = [
     FimoContext,
@@ -3480,6 +3678,78 @@ def fimo_module_param_query(
     return _fimo_module_param_query(context, module_name, param, type, read, write)
 
 
+_fimo_module_param_list = _lib.fimo_module_param_list
+_fimo_module_param_list.argtypes = [
+    FimoContext,
+    c.c_char_p,
+    c.POINTER(c.POINTER(FimoModuleParamInfo)),
+    c.POINTER(FimoUSize),
+]
+_fimo_module_param_list.restype = FimoResult
+
+
+def fimo_module_param_list(
+    context: FimoContext,
+    module_name: c.c_char_p,
+    params: PtrRef[FimoModuleParamInfo],
+    params_count: Ref[FimoUSize],
+) -> FimoResult:
+    """Enumerates the parameters of a module.
+
+    Queries the name, the datatype, the read access, and the write access
+    of each parameter of the module. The entries, and the strings they
+    reference, are stored in a single allocation, which must be freed with
+    `fimo_free`. This function fails, if the module can not be found.
+
+    :param context: context
+    :param module_name: name of the module containing the parameters
+    :param params: resulting array of parameter infos
+    :param params_count: number of entries in `params`
+
+    :return: Status code.
+    """
+    return _fimo_module_param_list(context, module_name, params, params_count)
+
+
+_fimo_module_param_set_override = _lib.fimo_module_param_set_override
+_fimo_module_param_set_override.argtypes = [
+    FimoContext,
+    c.c_char_p,
+    c.c_char_p,
+    c.c_void_p,
+    FimoModuleParamType,
+]
+_fimo_module_param_set_override.restype = FimoResult
+
+
+def fimo_module_param_set_override(
+    context: FimoContext,
+    module_name: c.c_char_p,
+    param: c.c_char_p,
+    value: c.c_void_p,
+    type: FimoModuleParamType,
+) -> FimoResult:
+    """Overrides the initial value of a module parameter.
+
+    Instead of the default value of its declaration, the parameter will
+    be initialized with `value`, when the module is loaded. The override
+    is only applied to modules loaded after this call, and takes effect
+    before the constructor of the module is called. The value is converted
+    to the type of the parameter, and loading the module fails, if it is
+    not representable by that type. Setting `value` to `NULL` removes the
+    override.
+
+    :param context: context
+    :param module_name: name of the module containing the parameter
+    :param param: name of the parameter
+    :param value: initial value of the parameter
+    :param type: type of the value
+
+    :return: Status code.
+    """
+    return _fimo_module_param_set_override(context, module_name, param, value, type)
+
+
 _fimo_module_param_set_public = _lib.fimo_module_param_set_public
 _fimo_module_param_set_public.argtypes = [
     FimoContext,
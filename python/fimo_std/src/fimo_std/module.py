@@ -1715,6 +1715,12 @@ class ModuleExport(
         assert isinstance(value, int)
         return value
 
+    def context_version(self) -> Version:
+        """Fetches the version of the context the module was built against."""
+        value = self.ffi.contents.context_version
+        assert isinstance(value, _ffi.FimoVersion)
+        return Version.transfer_from_ffi(value)
+
     def name(self) -> str:
         """Fetches the name of the module declaration."""
         value = self.ffi.contents.name
@@ -1843,6 +1849,7 @@ class ModuleExport(
 
     def __repr__(self) -> str:
         export_abi = self.export_abi()
+        context_version = self.context_version()
         name = self.name()
         description = self.description()
         author = self.author()
@@ -1856,7 +1863,7 @@ class ModuleExport(
         module_constructor = self.module_constructor()
         module_destructor = self.module_destructor()
         return (
-            f"ModuleExport({export_abi=!r}, {name=!r}, {description=!r}, "
+            f"ModuleExport({export_abi=!r}, {context_version=!r}, {name=!r}, {description=!r}, "
             f"{author=!r}, {license=!r}, {parameters=!r}, {resources=!r}, {imported_namespaces=!r}, "
             f"{imported_symbols=!r}, {exported_symbols=!r}, {exported_dynamic_symbols=!r}, "
             f"{module_constructor=!r}, {module_destructor=!r}, ...)"
@@ -2790,7 +2797,13 @@ def export_module(
     export = _ffi.FimoModuleExport()
     export.type = _ffi.FimoStructType.FIMO_STRUCT_TYPE_MODULE_EXPORT
     export.next = c.POINTER(_ffi.FimoBaseStructIn)()
-    export.export_abi = _ffi.FimoI32(0)
+    export.export_abi = _ffi.FimoI32(_ffi.FIMO_MODULE_EXPORT_ABI)
+    export.context_version = _ffi.FimoVersion(
+        _ffi.FIMO_VERSION_MAJOR,
+        _ffi.FIMO_VERSION_MINOR,
+        _ffi.FIMO_VERSION_PATCH,
+        _ffi.FIMO_VERSION_BUILD_NUMBER,
+    )
     export.name = name_ffi
     export.description = description_ffi
     export.author = author_ffi
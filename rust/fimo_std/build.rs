@@ -1,9 +1,14 @@
 use std::{env, path::PathBuf};
 
 fn main() {
+    let static_modules = env::var_os("CARGO_FEATURE_STATIC_MODULES").is_some();
     let library = cmake::Config::new("ffi")
         .configure_arg("-DFIMO_TEST_BINDINGS:BOOL=OFF")
         .configure_arg("-DFIMO_INSTALL_BINDINGS:BOOL=ON")
+        .define(
+            "FIMO_STD_STATIC_MODULES",
+            if static_modules { "ON" } else { "OFF" },
+        )
         .build();
     println!("cargo:rustc-link-search=native={}/lib", library.display());
     println!("cargo:rustc-link-lib=static=fimo_std");
@@ -1,7 +1,11 @@
 //! Fimo memory allocator.
 
 use alloc::alloc::handle_alloc_error;
-use core::alloc::{AllocError, Allocator, GlobalAlloc, Layout};
+use core::{
+    alloc::{AllocError, Allocator, GlobalAlloc, Layout},
+    ptr::NonNull,
+};
+use std::sync::Mutex;
 
 use crate::{bindings, error::to_result_indirect};
 
@@ -94,11 +98,316 @@ unsafe impl Allocator for FimoAllocator {
     }
 }
 
+/// Allocation counters of the [`FimoAllocator`].
+///
+/// Records the allocations and deallocations performed by a thread through the allocation
+/// functions of the fimo library, while the counters are installed with [`record`]. Only the
+/// allocations of the current instance of the library are recorded, i.e., allocations of a module
+/// linking its own copy of the library are not accounted for.
+///
+/// [`record`]: AllocationCounters::record
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api)]
+/// use fimo_std::allocator::{AllocationCounters, FimoAllocator};
+///
+/// let mut counters = AllocationCounters::new();
+/// let x = counters.record(|| Box::new_in(5, FimoAllocator));
+/// assert_eq!(counters.allocations(), 1);
+/// assert!(counters.allocated_bytes() >= core::mem::size_of::<i32>());
+///
+/// drop(x);
+/// assert_eq!(counters.deallocations(), 0);
+/// ```
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub struct AllocationCounters(bindings::FimoMemoryCounters);
+
+impl AllocationCounters {
+    /// Constructs new zeroed `AllocationCounters`.
+    pub const fn new() -> Self {
+        Self(bindings::FimoMemoryCounters {
+            allocations: 0,
+            deallocations: 0,
+            allocated_bytes: 0,
+            deallocated_bytes: 0,
+        })
+    }
+
+    /// Returns the number of successful allocations.
+    pub fn allocations(&self) -> usize {
+        self.0.allocations
+    }
+
+    /// Returns the number of deallocations.
+    pub fn deallocations(&self) -> usize {
+        self.0.deallocations
+    }
+
+    /// Returns the number of allocated bytes.
+    pub fn allocated_bytes(&self) -> usize {
+        self.0.allocated_bytes
+    }
+
+    /// Returns the number of deallocated bytes.
+    ///
+    /// May be lower than the exact number, on platforms where the size of an allocation can not be
+    /// queried.
+    pub fn deallocated_bytes(&self) -> usize {
+        self.0.deallocated_bytes
+    }
+
+    /// Records the allocations performed by the calling thread while running `f`.
+    ///
+    /// The previously installed counters are suspended while `f` runs, and are reinstalled
+    /// afterward, even if `f` panics.
+    pub fn record<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        struct Restore(*mut bindings::FimoMemoryCounters);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                // Safety: The counters were installed before ours, so they are still alive.
+                unsafe { bindings::fimo_memory_swap_counters(self.0) };
+            }
+        }
+
+        // Safety: The counters are uninstalled by the guard, before the borrow ends.
+        let _guard = Restore(unsafe { bindings::fimo_memory_swap_counters(&mut self.0) });
+        f()
+    }
+}
+
+impl Default for AllocationCounters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::ops::AddAssign for AllocationCounters {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0.allocations += rhs.0.allocations;
+        self.0.deallocations += rhs.0.deallocations;
+        self.0.allocated_bytes += rhs.0.allocated_bytes;
+        self.0.deallocated_bytes += rhs.0.deallocated_bytes;
+    }
+}
+
+impl core::fmt::Debug for AllocationCounters {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AllocationCounters")
+            .field("allocations", &self.allocations())
+            .field("deallocations", &self.deallocations())
+            .field("allocated_bytes", &self.allocated_bytes())
+            .field("deallocated_bytes", &self.deallocated_bytes())
+            .finish()
+    }
+}
+
+/// Size-classed pool allocator for small objects.
+///
+/// Allocations that fit into one of the size classes of the pool are served from a per-class free
+/// list, which is refilled by returning blocks to the pool upon deallocation. Larger allocations,
+/// or allocations with an alignment requirement exceeding the one of the size class, are forwarded
+/// to the backing allocator. The pool is meant for subsystems that box many small closures, e.g.
+/// callbacks created during the registration phase, and must be opted into explicitly by passing a
+/// reference to the pool as the allocator.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api)]
+/// use fimo_std::allocator::PoolAllocator;
+///
+/// static POOL: PoolAllocator = PoolAllocator::new();
+///
+/// let callback = Box::new_in(|x: usize| x + 1, &POOL);
+/// assert_eq!(callback(1), 2);
+/// ```
+#[derive(Debug)]
+pub struct PoolAllocator<A: Allocator = FimoAllocator> {
+    allocator: A,
+    classes: [SizeClass; POOL_SIZE_CLASSES.len()],
+}
+
+const POOL_SIZE_CLASSES: [usize; 5] = [16, 32, 64, 128, 256];
+const POOL_MAX_ALIGNMENT: usize = 16;
+
+impl PoolAllocator {
+    /// Constructs a new empty `PoolAllocator` backed by the [`FimoAllocator`].
+    pub const fn new() -> Self {
+        Self::new_in(FimoAllocator)
+    }
+}
+
+impl<A: Allocator> PoolAllocator<A> {
+    /// Sizes, in bytes, of the blocks managed by the pool.
+    pub const SIZE_CLASSES: [usize; 5] = POOL_SIZE_CLASSES;
+
+    /// Maximum alignment supported by the size classes.
+    pub const MAX_ALIGNMENT: usize = POOL_MAX_ALIGNMENT;
+
+    /// Maximum number of unused blocks cached by each size class.
+    pub const MAX_CACHED_BLOCKS: usize = 256;
+
+    /// Constructs a new empty `PoolAllocator` backed by a custom allocator.
+    pub const fn new_in(allocator: A) -> Self {
+        Self {
+            allocator,
+            classes: [
+                SizeClass::new(Self::SIZE_CLASSES[0]),
+                SizeClass::new(Self::SIZE_CLASSES[1]),
+                SizeClass::new(Self::SIZE_CLASSES[2]),
+                SizeClass::new(Self::SIZE_CLASSES[3]),
+                SizeClass::new(Self::SIZE_CLASSES[4]),
+            ],
+        }
+    }
+
+    /// Returns a reference to the backing allocator.
+    pub fn allocator(&self) -> &A {
+        &self.allocator
+    }
+
+    /// Returns the number of unused blocks currently cached by the pool.
+    pub fn cached_blocks(&self) -> usize {
+        self.classes
+            .iter()
+            .map(|class| class.free.lock().map_or(0, |list| list.len))
+            .sum()
+    }
+
+    /// Releases all cached blocks back to the backing allocator.
+    pub fn trim(&self) {
+        for class in &self.classes {
+            let mut list = class.free.lock().unwrap_or_else(|e| e.into_inner());
+            while let Some(block) = list.pop() {
+                // Safety: All blocks in the free list were allocated with the class layout.
+                unsafe { self.allocator.deallocate(block, class.layout()) };
+            }
+        }
+    }
+
+    fn size_class(&self, layout: Layout) -> Option<&SizeClass> {
+        if layout.size() == 0 || layout.align() > Self::MAX_ALIGNMENT {
+            return None;
+        }
+        self.classes
+            .iter()
+            .find(|class| layout.size() <= class.size && layout.align() <= class.align())
+    }
+}
+
+impl Default for PoolAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Allocator> Drop for PoolAllocator<A> {
+    fn drop(&mut self) {
+        self.trim();
+    }
+}
+
+// Safety: Blocks are either served from the free lists, which only contain blocks allocated with
+// the layout of the size class, or by the backing allocator.
+unsafe impl<A: Allocator> Allocator for PoolAllocator<A> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let Some(class) = self.size_class(layout) else {
+            return self.allocator.allocate(layout);
+        };
+
+        let block = {
+            let mut list = class.free.lock().unwrap_or_else(|e| e.into_inner());
+            list.pop()
+        };
+        let block = match block {
+            Some(block) => block,
+            None => self.allocator.allocate(class.layout())?.cast::<u8>(),
+        };
+        Ok(NonNull::slice_from_raw_parts(block, class.size))
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        let Some(class) = self.size_class(layout) else {
+            // Safety: The block was allocated by the backing allocator.
+            unsafe { self.allocator.deallocate(ptr, layout) };
+            return;
+        };
+
+        let mut list = class.free.lock().unwrap_or_else(|e| e.into_inner());
+        if list.len < Self::MAX_CACHED_BLOCKS {
+            // Safety: The block is at least as large and aligned as a pointer.
+            unsafe { list.push(ptr) };
+        } else {
+            drop(list);
+            // Safety: The block was allocated with the layout of the size class.
+            unsafe { self.allocator.deallocate(ptr, class.layout()) };
+        }
+    }
+}
+
+#[derive(Debug)]
+struct SizeClass {
+    size: usize,
+    free: Mutex<FreeList>,
+}
+
+impl SizeClass {
+    const fn new(size: usize) -> Self {
+        Self {
+            size,
+            free: Mutex::new(FreeList { head: None, len: 0 }),
+        }
+    }
+
+    fn align(&self) -> usize {
+        self.size.min(POOL_MAX_ALIGNMENT)
+    }
+
+    fn layout(&self) -> Layout {
+        // Safety: The size is a power of two and the alignment is not larger than the size.
+        unsafe { Layout::from_size_align_unchecked(self.size, self.align()) }
+    }
+}
+
+/// Intrusive singly linked list of unused blocks.
+#[derive(Debug)]
+struct FreeList {
+    head: Option<NonNull<u8>>,
+    len: usize,
+}
+
+impl FreeList {
+    fn pop(&mut self) -> Option<NonNull<u8>> {
+        let block = self.head?;
+        // Safety: Each block in the list stores the pointer to the next block.
+        self.head = unsafe { block.cast::<Option<NonNull<u8>>>().read() };
+        self.len -= 1;
+        Some(block)
+    }
+
+    /// # Safety
+    ///
+    /// The block must be valid for writes of a pointer and be suitably aligned.
+    unsafe fn push(&mut self, block: NonNull<u8>) {
+        // Safety: Ensured by the caller.
+        unsafe { block.cast::<Option<NonNull<u8>>>().write(self.head) };
+        self.head = Some(block);
+        self.len += 1;
+    }
+}
+
+// Safety: The list owns the blocks it points to.
+unsafe impl Send for FreeList {}
+
 #[cfg(test)]
 mod tests {
     use core::hint::black_box;
 
-    use crate::allocator::FimoAllocator;
+    use crate::allocator::{FimoAllocator, PoolAllocator};
+    use std::alloc::Global;
 
     #[test]
     fn allocator() {
@@ -109,4 +418,26 @@ mod tests {
         x.extend_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
         assert_eq!(*x, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
     }
+
+    #[test]
+    fn pool_allocator() {
+        let pool = PoolAllocator::new_in(Global);
+
+        let x = Box::new_in(55_u64, &pool);
+        drop(x);
+        assert_eq!(pool.cached_blocks(), 1);
+
+        let y = Box::new_in(7_u64, &pool);
+        assert_eq!(*y, 7);
+        assert_eq!(pool.cached_blocks(), 0);
+        drop(y);
+
+        // Blocks larger than the largest size class bypass the pool.
+        let z = Box::new_in([0_u8; 1024], &pool);
+        drop(z);
+        assert_eq!(pool.cached_blocks(), 1);
+
+        pool.trim();
+        assert_eq!(pool.cached_blocks(), 0);
+    }
 }
@@ -0,0 +1,633 @@
+//! Bootstrapping of an engine instance from a declarative profile.
+
+use alloc::{boxed::Box, ffi::CString, format, string::String, vec::Vec};
+use core::{cell::RefCell, ffi::CStr, fmt, pin::Pin};
+use std::path::{Path, PathBuf};
+
+use crate::{
+    allocator::FimoAllocator,
+    context::{Context, ContextBuilder},
+    error::Error,
+    module::{
+        LoadingFilterRequest, LoadingSet, LoadingSetRequest, LoadingStatus, Module, ModuleExport,
+        ModuleInfo, ModuleSubsystem, ParameterConfig, ParameterValue, PseudoModule,
+    },
+    tracing::{Config, ThreadAccess},
+    version::Version,
+};
+
+/// A symbol required by a [`Profile`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolRequirement {
+    name: CString,
+    namespace: CString,
+    version: Version,
+}
+
+impl SymbolRequirement {
+    /// Constructs a new `SymbolRequirement`.
+    ///
+    /// The requirement is satisfied by a symbol with the same name and namespace, whose version is
+    /// [compatible](Version::compatible) with `version`.
+    pub fn new(name: &CStr, namespace: &CStr, version: Version) -> Self {
+        Self {
+            name: name.into(),
+            namespace: namespace.into(),
+            version,
+        }
+    }
+
+    /// Returns the name of the symbol.
+    pub fn name(&self) -> &CStr {
+        &self.name
+    }
+
+    /// Returns the namespace of the symbol.
+    pub fn namespace(&self) -> &CStr {
+        &self.namespace
+    }
+
+    /// Returns the minimum version of the symbol.
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    fn matches(&self, name: &CStr, namespace: &CStr) -> bool {
+        self.name.as_c_str() == name && self.namespace.as_c_str() == namespace
+    }
+}
+
+impl fmt::Display for SymbolRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}::{} ({})",
+            self.namespace.to_string_lossy(),
+            self.name.to_string_lossy(),
+            self.version
+        )
+    }
+}
+
+/// Error returned by [`Profile::bootstrap`].
+///
+/// Each variant identifies the step, and if applicable, the requirement that failed.
+#[derive(Debug)]
+pub enum BootstrapError {
+    /// The context could not be constructed.
+    Context(Error),
+    /// The parameter overrides could not be registered.
+    Parameters(Error),
+    /// A module directory or binary could not be read.
+    ///
+    /// A path of `None` refers to the current binary.
+    Discovery { path: Option<PathBuf>, error: Error },
+    /// Two binaries export a module with the same name.
+    DuplicateModule {
+        name: CString,
+        path: Option<PathBuf>,
+    },
+    /// No discovered binary exports a required module.
+    MissingModule { name: CString },
+    /// No discovered module exports a required symbol.
+    MissingSymbol { symbol: SymbolRequirement },
+    /// The discovered module exporting a required symbol provides an incompatible version.
+    IncompatibleSymbol {
+        symbol: SymbolRequirement,
+        module: CString,
+        version: Version,
+    },
+    /// A required module, or the module exporting a required symbol, failed to load.
+    ///
+    /// Contains the first import of the module, that no loaded module exports, if any.
+    ModuleFailed {
+        name: CString,
+        unresolved: Option<SymbolRequirement>,
+    },
+    /// The loading of the modules failed.
+    Loading(Error),
+    /// The handle to the loaded modules could not be constructed.
+    Initialization(Error),
+}
+
+impl fmt::Display for BootstrapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let binary = |path: &Option<PathBuf>| match path {
+            Some(path) => format!("`{}`", path.display()),
+            None => String::from("the current binary"),
+        };
+
+        match self {
+            BootstrapError::Context(e) => write!(f, "could not construct the context: {e}"),
+            BootstrapError::Parameters(e) => {
+                write!(f, "could not register the parameter overrides: {e}")
+            }
+            BootstrapError::Discovery { path, error } => {
+                write!(
+                    f,
+                    "could not discover the modules of {}: {error}",
+                    binary(path)
+                )
+            }
+            BootstrapError::DuplicateModule { name, path } => write!(
+                f,
+                "module `{}` of {} has already been discovered",
+                name.to_string_lossy(),
+                binary(path)
+            ),
+            BootstrapError::MissingModule { name } => {
+                write!(f, "required module `{}` not found", name.to_string_lossy())
+            }
+            BootstrapError::MissingSymbol { symbol } => {
+                write!(f, "required symbol {symbol} not found")
+            }
+            BootstrapError::IncompatibleSymbol {
+                symbol,
+                module,
+                version,
+            } => write!(
+                f,
+                "required symbol {symbol} is only exported with version {version} by module `{}`",
+                module.to_string_lossy()
+            ),
+            BootstrapError::ModuleFailed { name, unresolved } => {
+                write!(f, "module `{}` failed to load", name.to_string_lossy())?;
+                if let Some(symbol) = unresolved {
+                    write!(f, ", import {symbol} is not exported by any loaded module")?;
+                }
+                Ok(())
+            }
+            BootstrapError::Loading(e) => write!(f, "could not load the modules: {e}"),
+            BootstrapError::Initialization(e) => {
+                write!(f, "could not initialize the engine handle: {e}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BootstrapError {}
+
+impl From<BootstrapError> for Error {
+    fn from(value: BootstrapError) -> Self {
+        Error::new(value)
+    }
+}
+
+/// Declarative description of an engine instance.
+///
+/// A profile lists where to find the modules, which modules and symbols are required, the
+/// configuration of the tracing subsystem, and the overrides of the module parameters.
+/// [`Profile::bootstrap`] then performs the discovery, resolution, loading and initialization of
+/// the modules in order, and returns an [`Engine`] handle keeping the required modules loaded.
+///
+/// # Examples
+///
+/// ```no_run
+/// use fimo_std::{
+///     bootstrap::Profile,
+///     module::ParameterValue,
+///     tracing::{default_subscriber, Config, Level},
+///     version::Version,
+/// };
+///
+/// let engine = Profile::new()
+///     .with_tracing_config(Config::new(None, Some(Level::Info), [default_subscriber()]))
+///     .with_module_dir("modules")
+///     .with_parameter(c"fimo_tasks_impl", c"max_blocking_threads", ParameterValue::U32(64))
+///     .require_module(c"fimo_tasks_impl")
+///     .require_symbol(c"context", c"fimo_tasks", Version::new(0, 1, 0))
+///     .bootstrap()?;
+///
+/// let context = engine.shutdown()?;
+/// # drop(context);
+/// # Ok::<(), fimo_std::error::Error>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct Profile<const N: usize = 0> {
+    tracing: Option<Pin<Box<Config<N>, FimoAllocator>>>,
+    module_dirs: Vec<PathBuf>,
+    local_modules: bool,
+    modules: Vec<CString>,
+    symbols: Vec<SymbolRequirement>,
+    parameters: ParameterConfig,
+}
+
+impl Profile {
+    /// Constructs an empty profile.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<const N: usize> Profile<N> {
+    /// Sets the config of the tracing subsystem.
+    pub fn with_tracing_config<const M: usize>(
+        self,
+        config: Pin<Box<Config<M>, FimoAllocator>>,
+    ) -> Profile<M> {
+        Profile {
+            tracing: Some(config),
+            module_dirs: self.module_dirs,
+            local_modules: self.local_modules,
+            modules: self.modules,
+            symbols: self.symbols,
+            parameters: self.parameters,
+        }
+    }
+
+    /// Adds a directory to search for module binaries.
+    ///
+    /// The binaries in the directory are found with [`module_binaries`].
+    pub fn with_module_dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.module_dirs.push(path.into());
+        self
+    }
+
+    /// Adds the modules exported by the current binary.
+    pub fn with_local_modules(mut self) -> Self {
+        self.local_modules = true;
+        self
+    }
+
+    /// Sets the initial value of a module parameter.
+    pub fn with_parameter(
+        mut self,
+        module: &CStr,
+        parameter: &CStr,
+        value: ParameterValue,
+    ) -> Self {
+        self.parameters.set(module, parameter, value);
+        self
+    }
+
+    /// Sets the initial values of the module parameters.
+    ///
+    /// Replaces the values set previously.
+    pub fn with_parameter_config(mut self, config: ParameterConfig) -> Self {
+        self.parameters = config;
+        self
+    }
+
+    /// Requires that a module with the name `name` is loaded.
+    pub fn require_module(mut self, name: &CStr) -> Self {
+        if !self.modules.iter().any(|x| x.as_c_str() == name) {
+            self.modules.push(name.into());
+        }
+        self
+    }
+
+    /// Requires that a symbol compatible with `version` is exported by a loaded module.
+    pub fn require_symbol(mut self, name: &CStr, namespace: &CStr, version: Version) -> Self {
+        self.symbols
+            .push(SymbolRequirement::new(name, namespace, version));
+        self
+    }
+
+    /// Constructs the context and loads the modules of the profile.
+    ///
+    /// The bootstrapping is performed in the following steps:
+    ///
+    /// 1. The context is constructed and the parameter overrides are registered.
+    /// 2. The module binaries are discovered and added to a [`LoadingSet`].
+    /// 3. The required modules and symbols are resolved against the modules of the set. The set is
+    ///    dismissed without loading any module if a requirement can not be satisfied.
+    /// 4. The modules are loaded. A module failing to load is reported together with its first
+    ///    import that is not exported by any loaded module.
+    /// 5. An [`Engine`] handle is constructed, which depends on the required modules and on the
+    ///    modules exporting the required symbols.
+    ///
+    /// The calling thread is registered with the tracing subsystem while the modules are loaded.
+    pub fn bootstrap(mut self) -> Result<Engine, BootstrapError> {
+        let context = match self.tracing.take() {
+            Some(config) => <ContextBuilder>::new().with_tracing_config(config).build(),
+            None => Context::new(),
+        }
+        .map_err(BootstrapError::Context)?;
+        self.parameters
+            .apply(&*context)
+            .map_err(BootstrapError::Parameters)?;
+
+        let mut binaries = Vec::new();
+        if self.local_modules {
+            binaries.push(None);
+        }
+        for dir in &self.module_dirs {
+            let paths = module_binaries(dir).map_err(|error| BootstrapError::Discovery {
+                path: Some(dir.clone()),
+                error,
+            })?;
+            binaries.extend(paths.into_iter().map(Some));
+        }
+
+        let access = ThreadAccess::new(&context).map_err(BootstrapError::Context)?;
+        let mut discovered = Vec::new();
+        let failed = RefCell::new(Vec::new());
+        let mut error = None;
+        let result = LoadingSet::with_loading_set(&*context, |ctx, set| {
+            for path in &binaries {
+                if let Err(e) = append_binary(ctx, set, path.as_deref(), &mut discovered) {
+                    error = Some(e);
+                    return Ok(LoadingSetRequest::Dismiss);
+                }
+            }
+            for module in &discovered {
+                set.append_callback(ctx, &module.name, |status| {
+                    if let LoadingStatus::Error { export } = status {
+                        failed.borrow_mut().push(CString::from(export.name()));
+                    }
+                })?;
+            }
+
+            if let Err(e) = self.resolve(&discovered) {
+                error = Some(e);
+                return Ok(LoadingSetRequest::Dismiss);
+            }
+            Ok(LoadingSetRequest::Load)
+        });
+        if let Some(e) = error {
+            return Err(e);
+        }
+
+        let failed = failed.into_inner();
+        let module_failed = |name: &CStr| {
+            let module = discovered.iter().find(|x| x.name.as_c_str() == name);
+            let unresolved = module.and_then(|module| {
+                module
+                    .imports
+                    .iter()
+                    .find(|x| {
+                        ModuleInfo::find_by_symbol(&*context, &x.name, &x.namespace, x.version)
+                            .is_err()
+                    })
+                    .cloned()
+            });
+            BootstrapError::ModuleFailed {
+                name: name.into(),
+                unresolved,
+            }
+        };
+        if let Err(e) = result {
+            return Err(match failed.first() {
+                Some(name) => module_failed(name),
+                None => BootstrapError::Loading(e),
+            });
+        }
+
+        let mut modules: Vec<ModuleInfo> = Vec::new();
+        for name in &self.modules {
+            let info = ModuleInfo::find_by_name(&*context, name).map_err(|e| {
+                if failed.contains(name) {
+                    module_failed(name)
+                } else {
+                    BootstrapError::Loading(e)
+                }
+            })?;
+            modules.push(info);
+        }
+        for symbol in &self.symbols {
+            let info = ModuleInfo::find_by_symbol(
+                &*context,
+                &symbol.name,
+                &symbol.namespace,
+                symbol.version,
+            )
+            .map_err(|e| {
+                let provider = discovered.iter().find(|x| {
+                    x.exports
+                        .iter()
+                        .any(|x| x.matches(&symbol.name, &symbol.namespace))
+                });
+                match provider {
+                    Some(provider) if failed.contains(&provider.name) => {
+                        module_failed(&provider.name)
+                    }
+                    _ => BootstrapError::Loading(e),
+                }
+            })?;
+            if !modules.iter().any(|x| x.name() == info.name()) {
+                modules.push(info);
+            }
+        }
+
+        let module = PseudoModule::new(&*context).map_err(BootstrapError::Initialization)?;
+        for info in &modules {
+            module
+                .acquire_dependency(info)
+                .map_err(BootstrapError::Initialization)?;
+        }
+        drop(access);
+
+        Ok(Engine {
+            modules,
+            module,
+            context,
+        })
+    }
+
+    /// Checks that the requirements of the profile are satisfied by the discovered modules.
+    fn resolve(&self, discovered: &[DiscoveredModule]) -> Result<(), BootstrapError> {
+        for name in &self.modules {
+            if !discovered.iter().any(|x| &x.name == name) {
+                return Err(BootstrapError::MissingModule { name: name.clone() });
+            }
+        }
+
+        for symbol in &self.symbols {
+            let candidates = discovered
+                .iter()
+                .flat_map(|module| module.exports.iter().map(move |x| (module, x)))
+                .filter(|(_, x)| x.matches(&symbol.name, &symbol.namespace));
+
+            let mut incompatible = None;
+            let mut found = false;
+            for (module, export) in candidates {
+                if export.version.compatible(&symbol.version) {
+                    found = true;
+                    break;
+                }
+                incompatible.get_or_insert((module, export.version));
+            }
+            if found {
+                continue;
+            }
+
+            return Err(match incompatible {
+                Some((module, version)) => BootstrapError::IncompatibleSymbol {
+                    symbol: symbol.clone(),
+                    module: module.name.clone(),
+                    version,
+                },
+                None => BootstrapError::MissingSymbol {
+                    symbol: symbol.clone(),
+                },
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Handle to an engine instance constructed by [`Profile::bootstrap`].
+///
+/// The handle owns a [`PseudoModule`], which depends on the required modules of the profile, and
+/// on the modules exporting the required symbols, preventing them from being unloaded.
+#[derive(Debug)]
+pub struct Engine {
+    modules: Vec<ModuleInfo>,
+    module: PseudoModule,
+    context: Context,
+}
+
+impl Engine {
+    /// Returns the context of the engine.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    /// Returns the pseudo module of the engine.
+    ///
+    /// The module can be used to load the symbols of its dependencies.
+    pub fn module(&self) -> &PseudoModule {
+        &self.module
+    }
+
+    /// Returns the modules the engine depends on.
+    pub fn modules(&self) -> &[ModuleInfo] {
+        &self.modules
+    }
+
+    /// Releases the modules of the engine and returns the context.
+    ///
+    /// Modules that are not depended on by other modules are unloaded.
+    pub fn shutdown(self) -> Result<Context, Error> {
+        let Self {
+            modules,
+            module,
+            context,
+        } = self;
+        drop(modules);
+        drop(module.destroy()?);
+        Ok(context)
+    }
+}
+
+/// Module found while discovering the binaries of a [`Profile`].
+#[derive(Debug)]
+struct DiscoveredModule {
+    name: CString,
+    exports: Vec<SymbolRequirement>,
+    imports: Vec<SymbolRequirement>,
+}
+
+impl DiscoveredModule {
+    fn new(export: &ModuleExport<'_>) -> Self {
+        let exports = export
+            .exported_symbols()
+            .iter()
+            .map(|x| SymbolRequirement::new(x.name(), x.namespace(), x.version()))
+            .chain(
+                export
+                    .exported_dynamic_symbols()
+                    .iter()
+                    .map(|x| SymbolRequirement::new(x.name(), x.namespace(), x.version())),
+            )
+            .collect();
+        let imports = export
+            .imported_symbols()
+            .iter()
+            .map(|x| SymbolRequirement::new(x.name(), x.namespace(), x.version()))
+            .collect();
+        Self {
+            name: export.name().into(),
+            exports,
+            imports,
+        }
+    }
+}
+
+/// Returns the paths of the module binaries contained in the directory `dir`.
+///
+/// The binaries are the files with the platform specific extension of a dynamic library, sorted
+/// by their path. Subdirectories are not searched.
+pub fn module_binaries(dir: impl AsRef<Path>) -> Result<Vec<PathBuf>, Error> {
+    let mut binaries = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(Error::new)? {
+        let path = entry.map_err(Error::new)?.path();
+        if path.is_file()
+            && path
+                .extension()
+                .is_some_and(|x| x == std::env::consts::DLL_EXTENSION)
+        {
+            binaries.push(path);
+        }
+    }
+    binaries.sort();
+    Ok(binaries)
+}
+
+/// Invokes `f` with each module exported by a binary, without loading the modules.
+///
+/// The binary is opened to enumerate its exports, but none of its modules is constructed. If
+/// `path` is `None`, the modules of the current binary are enumerated.
+pub fn inspect_binary(
+    ctx: &impl ModuleSubsystem,
+    path: Option<&Path>,
+    mut f: impl FnMut(ModuleExport<'_>),
+) -> crate::error::Result {
+    let path = path.map(binary_path).transpose()?;
+    LoadingSet::with_loading_set(ctx, |ctx, set| {
+        set.append_modules(ctx, path.as_deref(), |export| {
+            f(export);
+            LoadingFilterRequest::Skip
+        })?;
+        Ok(LoadingSetRequest::Dismiss)
+    })
+}
+
+fn binary_path(path: &Path) -> Result<CString, Error> {
+    let path = path
+        .to_str()
+        .ok_or_else(|| Error::new("the path is not valid unicode"))?;
+    CString::new(path).map_err(Error::new)
+}
+
+/// Adds the modules of a binary to the set.
+fn append_binary(
+    ctx: &impl ModuleSubsystem,
+    set: &LoadingSet<'_>,
+    path: Option<&Path>,
+    discovered: &mut Vec<DiscoveredModule>,
+) -> Result<(), BootstrapError> {
+    let discovery_error = |error: Error| BootstrapError::Discovery {
+        path: path.map(Into::into),
+        error,
+    };
+
+    let path_str = path.map(binary_path).transpose().map_err(discovery_error)?;
+
+    let mut duplicate = None;
+    let mut modules = Vec::new();
+    set.append_modules(ctx, path_str.as_deref(), |export| {
+        let name = export.name();
+        if discovered
+            .iter()
+            .chain(&modules)
+            .any(|x| x.name.as_c_str() == name)
+        {
+            duplicate.get_or_insert_with(|| CString::from(name));
+            return LoadingFilterRequest::Skip;
+        }
+        modules.push(DiscoveredModule::new(&export));
+        LoadingFilterRequest::Load
+    })
+    .map_err(discovery_error)?;
+
+    if let Some(name) = duplicate {
+        return Err(BootstrapError::DuplicateModule {
+            name,
+            path: path.map(Into::into),
+        });
+    }
+    discovered.extend(modules);
+    Ok(())
+}
@@ -0,0 +1,411 @@
+//! Event bus subsystem.
+
+use alloc::boxed::Box;
+use core::{
+    ffi::{c_void, CStr},
+    marker::PhantomData,
+    mem::MaybeUninit,
+};
+
+use crate::{
+    bindings,
+    context::{private::SealedContext, ContextView},
+    error,
+    error::{to_result_indirect, to_result_indirect_in_place, Error},
+    ffi::{FFISharable, FFITransferable},
+    version::Version,
+};
+
+/// UUID of a [`Topic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TopicId(pub [u8; 16]);
+
+/// Topic of the events published on the bus.
+///
+/// An event is delivered to a subscriber, if the ids of the topics match, and the [`Version`] of
+/// the published topic is [compatible](Version::compatible) with the one of the subscriber. A
+/// compatible version of a topic may only append new fields to the end of its payload.
+pub trait Topic: 'static {
+    /// UUID of the topic.
+    const ID: TopicId;
+
+    /// Version of the topic.
+    const VERSION: Version;
+
+    /// Type of the payload.
+    type Payload: ?Sized;
+
+    /// Marshals the payload into bytes.
+    ///
+    /// Queued events are copied and delivered at a later point in time, therefore the bytes must
+    /// not contain pointers to data owned by the publisher.
+    fn encode(payload: &Self::Payload) -> &[u8];
+
+    /// Unmarshals the payload from the bytes of an event.
+    ///
+    /// Events whose payload can not be decoded are not delivered to the subscriber.
+    fn decode(bytes: &[u8]) -> Option<&Self::Payload>;
+}
+
+/// Topic of the events published after a module has been loaded.
+///
+/// The payload consists of the name of the module. Synchronous subscribers are invoked after the
+/// module subsystem has been unlocked, and may call back into it.
+#[derive(Debug)]
+pub struct ModuleLoaded;
+
+impl Topic for ModuleLoaded {
+    // Must match the definition of `FIMO_BUS_TOPIC_MODULE_LOADED`.
+    const ID: TopicId = TopicId([
+        0xa8, 0x02, 0xcb, 0xc8, 0xc0, 0xbf, 0x48, 0x84, 0xa6, 0x4c, 0xfc, 0xf1, 0x73, 0x7f, 0x5f,
+        0x81,
+    ]);
+    const VERSION: Version = Version::new(0, 1, 0);
+    type Payload = CStr;
+
+    fn encode(payload: &Self::Payload) -> &[u8] {
+        payload.to_bytes_with_nul()
+    }
+
+    fn decode(bytes: &[u8]) -> Option<&Self::Payload> {
+        CStr::from_bytes_until_nul(bytes).ok()
+    }
+}
+
+/// Topic of the events published after a module has been unloaded.
+///
+/// The payload consists of the name of the module. Synchronous subscribers are invoked after the
+/// module subsystem has been unlocked, and may call back into it.
+#[derive(Debug)]
+pub struct ModuleUnloaded;
+
+impl Topic for ModuleUnloaded {
+    // Must match the definition of `FIMO_BUS_TOPIC_MODULE_UNLOADED`.
+    const ID: TopicId = TopicId([
+        0xbe, 0x44, 0xe4, 0xaf, 0x72, 0xda, 0x49, 0x2e, 0x9d, 0x9b, 0x3f, 0xe4, 0x52, 0x6a, 0xa7,
+        0x43,
+    ]);
+    const VERSION: Version = Version::new(0, 1, 0);
+    type Payload = CStr;
+
+    fn encode(payload: &Self::Payload) -> &[u8] {
+        payload.to_bytes_with_nul()
+    }
+
+    fn decode(bytes: &[u8]) -> Option<&Self::Payload> {
+        CStr::from_bytes_until_nul(bytes).ok()
+    }
+}
+
+/// Topic of the events published after a module binary has failed the verification of its
+/// signature.
+///
+/// The payload consists of the path of the binary.
+#[derive(Debug)]
+pub struct ModuleVerificationFailed;
+
+impl Topic for ModuleVerificationFailed {
+    // Must match the definition of `FIMO_BUS_TOPIC_MODULE_VERIFICATION_FAILED`.
+    const ID: TopicId = TopicId([
+        0x5d, 0x17, 0x8e, 0x3a, 0x0b, 0x64, 0x4f, 0xc1, 0x92, 0x2e, 0x71, 0xd8, 0x4a, 0x06, 0xbb,
+        0x39,
+    ]);
+    const VERSION: Version = Version::new(0, 1, 0);
+    type Payload = CStr;
+
+    fn encode(payload: &Self::Payload) -> &[u8] {
+        payload.to_bytes_with_nul()
+    }
+
+    fn decode(bytes: &[u8]) -> Option<&Self::Payload> {
+        CStr::from_bytes_until_nul(bytes).ok()
+    }
+}
+
+/// Topic of the events published after the construction of a module has failed.
+///
+/// The construction fails if the constructor of the module returns an error or panics. Synchronous
+/// subscribers are invoked after the module subsystem has been unlocked, and may call back into it.
+#[derive(Debug)]
+pub struct ModuleConstructionFailed;
+
+impl Topic for ModuleConstructionFailed {
+    // Must match the definition of `FIMO_BUS_TOPIC_MODULE_CONSTRUCTION_FAILED`.
+    const ID: TopicId = TopicId([
+        0x3c, 0x91, 0x0f, 0x6e, 0xd4, 0x27, 0x4b, 0x58, 0x8a, 0xe3, 0x15, 0x7c, 0x62, 0xb9, 0x40,
+        0xd2,
+    ]);
+    const VERSION: Version = Version::new(0, 1, 0);
+    type Payload = ModuleFault;
+
+    fn encode(payload: &Self::Payload) -> &[u8] {
+        &payload.0
+    }
+
+    fn decode(bytes: &[u8]) -> Option<&Self::Payload> {
+        let module = CStr::from_bytes_until_nul(bytes).ok()?;
+        CStr::from_bytes_until_nul(&bytes[module.to_bytes_with_nul().len()..]).ok()?;
+
+        // Safety: `ModuleFault` is a transparent wrapper over a slice.
+        Some(unsafe { &*(core::ptr::from_ref(bytes) as *const ModuleFault) })
+    }
+}
+
+/// Report of a failed module construction.
+///
+/// Payload of the [`ModuleConstructionFailed`] topic.
+#[repr(transparent)]
+pub struct ModuleFault([u8]);
+
+impl ModuleFault {
+    /// Name of the module.
+    pub fn module(&self) -> &CStr {
+        CStr::from_bytes_until_nul(&self.0).expect("the payload is validated on construction")
+    }
+
+    /// Description of the error returned by the constructor, or of the panic message.
+    pub fn error(&self) -> &CStr {
+        let start = self.module().to_bytes_with_nul().len();
+        CStr::from_bytes_until_nul(&self.0[start..])
+            .expect("the payload is validated on construction")
+    }
+}
+
+impl core::fmt::Debug for ModuleFault {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ModuleFault")
+            .field("module", &self.module())
+            .field("error", &self.error())
+            .finish()
+    }
+}
+
+/// Delivery mode of a [`Subscription`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeliveryMode {
+    /// Events are delivered synchronously on the publishing thread.
+    #[default]
+    Sync,
+    /// Events are queued, and are delivered once [`Subscription::dispatch`] is called.
+    Queued,
+}
+
+impl From<DeliveryMode> for bindings::FimoBusDeliveryMode {
+    fn from(value: DeliveryMode) -> Self {
+        match value {
+            DeliveryMode::Sync => Self::FIMO_BUS_DELIVERY_MODE_SYNC,
+            DeliveryMode::Queued => Self::FIMO_BUS_DELIVERY_MODE_QUEUED,
+        }
+    }
+}
+
+/// Definition of the bus subsystem.
+pub trait BusSubsystem: SealedContext {
+    /// Publishes an event on the bus.
+    ///
+    /// Synchronous subscribers are invoked before this function returns, while queued subscribers
+    /// receive a copy of the event.
+    fn publish<T: Topic>(&self, payload: &T::Payload) -> error::Result;
+
+    /// Subscribes to a topic of the bus.
+    ///
+    /// The subscription receives all events published to a compatible topic, until the returned
+    /// guard is dropped.
+    fn subscribe<T, F>(&self, mode: DeliveryMode, f: F) -> Result<Subscription<'_, T>, Error>
+    where
+        T: Topic,
+        F: Fn(&T::Payload) + Send + Sync + 'static;
+}
+
+impl<C> BusSubsystem for C
+where
+    C: SealedContext,
+{
+    fn publish<T: Topic>(&self, payload: &T::Payload) -> error::Result {
+        let topic = ffi_topic::<T>();
+        let bytes = T::encode(payload);
+        let event = bindings::FimoBusEvent {
+            topic: &topic,
+            payload: bytes.as_ptr().cast(),
+            payload_size: bytes.len(),
+        };
+
+        // Safety: FFI call is safe.
+        unsafe {
+            to_result_indirect(|error| {
+                *error = bindings::fimo_bus_publish(self.share_to_ffi(), &event);
+            })
+        }
+    }
+
+    fn subscribe<T, F>(&self, mode: DeliveryMode, f: F) -> Result<Subscription<'_, T>, Error>
+    where
+        T: Topic,
+        F: Fn(&T::Payload) + Send + Sync + 'static,
+    {
+        unsafe extern "C" fn on_event<T: Topic, F: Fn(&T::Payload)>(
+            data: *mut c_void,
+            event: *const bindings::FimoBusEvent,
+        ) {
+            crate::panic::abort_on_panic(|| {
+                // Safety: The bus only invokes the function with valid events.
+                let (f, bytes) = unsafe {
+                    let f = &*data.cast_const().cast::<F>();
+                    let event = &*event;
+                    let bytes = if event.payload_size == 0 {
+                        &[][..]
+                    } else {
+                        core::slice::from_raw_parts(event.payload.cast::<u8>(), event.payload_size)
+                    };
+                    (f, bytes)
+                };
+                if let Some(payload) = T::decode(bytes) {
+                    f(payload);
+                }
+            });
+        }
+
+        unsafe extern "C" fn on_drop<F>(data: *mut c_void) {
+            // Safety: The function is invoked once, after the last delivery.
+            drop(unsafe { Box::from_raw(data.cast::<F>()) });
+        }
+
+        let data = Box::into_raw(Box::new(f));
+        let subscriber = bindings::FimoBusSubscriber {
+            next: core::ptr::null(),
+            topic: ffi_topic::<T>(),
+            mode: mode.into(),
+            data: data.cast(),
+            on_event: Some(on_event::<T, F>),
+            on_drop: Some(on_drop::<F>),
+        };
+
+        // Safety: FFI call is safe.
+        let subscription = unsafe {
+            to_result_indirect_in_place(|error, subscription| {
+                *error = bindings::fimo_bus_subscribe(
+                    self.share_to_ffi(),
+                    &subscriber,
+                    subscription.as_mut_ptr(),
+                );
+            })
+        };
+        match subscription {
+            Ok(subscription) => Ok(Subscription {
+                // Safety: The view is only used while `self` is borrowed.
+                context: unsafe { ContextView::from_ffi(self.share_to_ffi()) },
+                subscription,
+                _phantom: PhantomData,
+            }),
+            Err(e) => {
+                // Safety: The subscriber is not used on failure, so we still own the data.
+                drop(unsafe { Box::from_raw(data) });
+                Err(e)
+            }
+        }
+    }
+}
+
+fn ffi_topic<T: Topic>() -> bindings::FimoBusTopic {
+    bindings::FimoBusTopic {
+        id: T::ID.0,
+        version: T::VERSION.into_ffi(),
+    }
+}
+
+/// RAII guard of a subscription to a [`Topic`].
+///
+/// Dropping the guard removes the subscription from the bus, and discards its queued events.
+/// The drop waits until the events that are being delivered by other threads have been handled.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::{
+///     bus::{BusSubsystem, DeliveryMode, Topic, TopicId},
+///     context::Context,
+///     version::Version,
+/// };
+/// use std::sync::{
+///     atomic::{AtomicUsize, Ordering},
+///     Arc,
+/// };
+///
+/// struct Ping;
+///
+/// impl Topic for Ping {
+///     const ID: TopicId = TopicId([1; 16]);
+///     const VERSION: Version = Version::new(1, 0, 0);
+///     type Payload = [u8];
+///
+///     fn encode(payload: &[u8]) -> &[u8] {
+///         payload
+///     }
+///
+///     fn decode(bytes: &[u8]) -> Option<&[u8]> {
+///         Some(bytes)
+///     }
+/// }
+///
+/// let context = Context::new().expect("could not create context");
+/// let received = Arc::new(AtomicUsize::new(0));
+/// let subscription = context
+///     .subscribe::<Ping, _>(DeliveryMode::Queued, {
+///         let received = received.clone();
+///         move |payload| {
+///             received.fetch_add(payload.len(), Ordering::Relaxed);
+///         }
+///     })
+///     .expect("could not subscribe");
+///
+/// context.publish::<Ping>(b"ping").expect("could not publish");
+/// assert_eq!(received.load(Ordering::Relaxed), 0);
+/// assert_eq!(subscription.dispatch().expect("could not dispatch"), 1);
+/// assert_eq!(received.load(Ordering::Relaxed), 4);
+/// ```
+#[derive(Debug)]
+pub struct Subscription<'a, T: Topic> {
+    context: ContextView<'a>,
+    subscription: *mut bindings::FimoBusSubscription,
+    _phantom: PhantomData<fn(&T::Payload)>,
+}
+
+impl<T: Topic> Subscription<'_, T> {
+    /// Delivers the queued events of the subscription on the calling thread.
+    ///
+    /// Returns the number of delivered events. Fails if the subscription does not use the
+    /// [`DeliveryMode::Queued`] mode.
+    pub fn dispatch(&self) -> Result<usize, Error> {
+        let mut count = MaybeUninit::uninit();
+        // Safety: FFI call is safe.
+        unsafe {
+            to_result_indirect(|error| {
+                *error = bindings::fimo_bus_dispatch(
+                    self.context.share_to_ffi(),
+                    self.subscription,
+                    count.as_mut_ptr(),
+                );
+            })?;
+            Ok(count.assume_init())
+        }
+    }
+}
+
+// Safety: The subscription is thread-safe, and the callback is `Send + Sync`.
+unsafe impl<T: Topic> Send for Subscription<'_, T> {}
+
+// Safety: The subscription is thread-safe, and the callback is `Send + Sync`.
+unsafe impl<T: Topic> Sync for Subscription<'_, T> {}
+
+impl<T: Topic> Drop for Subscription<'_, T> {
+    fn drop(&mut self) {
+        // Safety: FFI call is safe.
+        let result = unsafe {
+            to_result_indirect(|error| {
+                *error =
+                    bindings::fimo_bus_unsubscribe(self.context.share_to_ffi(), self.subscription);
+            })
+        };
+        result.expect("could not remove the subscription");
+    }
+}
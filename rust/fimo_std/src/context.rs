@@ -150,16 +150,33 @@ impl FFITransferable<bindings::FimoContext> for Context {
     }
 }
 
+/// Preset configuration of a [`ContextBuilder`].
+///
+/// See [`ContextBuilder::with_profile`] for the options set by each profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Profile {
+    /// Verbose configuration for local development.
+    Development,
+    /// Quiet configuration for deployed binaries.
+    Release,
+    /// Deterministic configuration for tests and tools without a terminal.
+    Headless,
+}
+
 /// A builder for a [`Context`].
 #[derive(Debug, Default)]
 pub struct ContextBuilder<const N: usize = 0> {
     tracing: Option<Pin<Box<crate::tracing::Config<N>, FimoAllocator>>>,
+    clock: Option<crate::time::ClockConfig>,
 }
 
 impl<const N: usize> ContextBuilder<N> {
     /// Constructs a new builder.
     pub fn new() -> ContextBuilder<0> {
-        ContextBuilder { tracing: None }
+        ContextBuilder {
+            tracing: None,
+            clock: None,
+        }
     }
 
     /// Adds a config for the tracing subsystem.
@@ -169,19 +186,96 @@ impl<const N: usize> ContextBuilder<N> {
     ) -> ContextBuilder<M> {
         ContextBuilder {
             tracing: Some(config),
+            clock: self.clock,
+        }
+    }
+
+    /// Configures the builder according to a [`Profile`].
+    ///
+    /// Replaces the tracing config, and for [`Profile::Headless`] also the clock, of the builder:
+    ///
+    /// - [`Profile::Development`]: Writes all messages up to [`Level::Trace`] to the console.
+    /// - [`Profile::Release`]: Writes all messages up to [`Level::Warn`] to `stderr`, without
+    ///   colors.
+    /// - [`Profile::Headless`]: Disables all messages, and uses a
+    ///   [`MockClock`](crate::time::MockClock) starting at the
+    ///   [`UNIX_EPOCH`](crate::time::Time::UNIX_EPOCH).
+    ///
+    /// Each option may be overridden by calling the respective method after this one.
+    ///
+    /// [`Level::Trace`]: crate::tracing::Level::Trace
+    /// [`Level::Warn`]: crate::tracing::Level::Warn
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fimo_std::{
+    ///     context::{ContextBuilder, Profile},
+    ///     time::{ClockSubsystem, SystemClock, Time},
+    /// };
+    ///
+    /// let context = <ContextBuilder>::new()
+    ///     .with_profile(Profile::Headless)
+    ///     .build()
+    ///     .expect("could not create context");
+    /// assert_eq!(context.now(), Time::UNIX_EPOCH);
+    ///
+    /// let context = <ContextBuilder>::new()
+    ///     .with_profile(Profile::Headless)
+    ///     .with_clock(SystemClock)
+    ///     .build()
+    ///     .expect("could not create context");
+    /// assert_ne!(context.now(), Time::UNIX_EPOCH);
+    /// ```
+    pub fn with_profile(self, profile: Profile) -> ContextBuilder<1> {
+        use crate::tracing::{
+            ColorMode, Config, ConsoleStream, ConsoleSubscriber, Level, OpaqueSubscriber,
+        };
+
+        let (level, subscriber) = match profile {
+            Profile::Development => (Level::Trace, ConsoleSubscriber::new()),
+            Profile::Release => (
+                Level::Warn,
+                ConsoleSubscriber::new()
+                    .with_stream(ConsoleStream::Stderr)
+                    .with_color_mode(ColorMode::Never),
+            ),
+            Profile::Headless => (Level::Off, ConsoleSubscriber::new()),
+        };
+        let subscriber = OpaqueSubscriber::from_box(Box::new(subscriber));
+        let this = self.with_tracing_config(Config::new(None, Some(level), [subscriber]));
+
+        match profile {
+            Profile::Headless => {
+                this.with_clock(crate::time::MockClock::new(crate::time::Time::UNIX_EPOCH))
+            }
+            Profile::Development | Profile::Release => this,
+        }
+    }
+
+    /// Replaces the default [`SystemClock`](crate::time::SystemClock) of the context.
+    pub fn with_clock(self, clock: impl crate::time::Clock) -> Self {
+        ContextBuilder {
+            tracing: self.tracing,
+            clock: Some(crate::time::ClockConfig::new(clock)),
         }
     }
 
     /// Builds the context.
     pub fn build(self) -> Result<Context, crate::error::Error> {
         let tracing = ManuallyDrop::new(self.tracing);
+        let clock = ManuallyDrop::new(self.clock);
 
         let mut counter = 0;
-        let mut options: [*const bindings::FimoBaseStructIn; 2] = [core::ptr::null(); 2];
+        let mut options: [*const bindings::FimoBaseStructIn; 3] = [core::ptr::null(); 3];
         if let Some(tracing) = &*tracing {
             options[counter] = tracing.as_ffi_option_ptr();
             counter += 1;
         }
+        if let Some(clock) = &*clock {
+            options[counter] = clock.as_ffi_option_ptr();
+            counter += 1;
+        }
 
         if counter == 0 {
             Context::new()
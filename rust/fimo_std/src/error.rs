@@ -1,6 +1,10 @@
 //! Fimo error codes.
 
-use crate::{bindings, ffi::FFITransferable};
+use crate::{
+    bindings,
+    ffi::FFITransferable,
+    tracing::{TracedError, TracingSubsystem},
+};
 use std::{
     ffi::{CStr, CString},
     fmt,
@@ -293,6 +297,17 @@ impl Error {
         let error = <Error>::new_error(value).into_error();
         Self(error, PhantomData)
     }
+
+    /// Creates an [`Error`] from an arbitrary value, annotated with the spans entered on the
+    /// active call stack.
+    ///
+    /// See [`TracedError`] for more details.
+    pub fn new_traced(
+        ctx: &impl TracingSubsystem,
+        value: impl fmt::Display + fmt::Debug + 'static,
+    ) -> Self {
+        Self::new(TracedError::new(ctx, value))
+    }
 }
 
 impl Error<dyn Send> {
@@ -434,6 +449,69 @@ impl fmt::Display for Error {
     }
 }
 
+impl std::error::Error for Error {}
+
+/// Formats an error together with the chain of its sources.
+///
+/// The error is printed first, followed by each of its [sources](std::error::Error::source) on a
+/// separate line. Allows for reporting errors uniformly, e.g., through the tracing subsystem with
+/// [`emit_error_chain`](crate::emit_error_chain).
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::error::{Error, ErrorChain};
+/// use std::fmt;
+///
+/// #[derive(Debug)]
+/// struct LoadError(Error);
+///
+/// impl fmt::Display for LoadError {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "could not load the module")
+///     }
+/// }
+///
+/// impl std::error::Error for LoadError {
+///     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+///         Some(&self.0)
+///     }
+/// }
+///
+/// let error = LoadError(Error::new("symbol not found"));
+/// assert_eq!(
+///     ErrorChain::new(&error).to_string(),
+///     "could not load the module\n\nCaused by:\n    0: symbol not found"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ErrorChain<'a>(&'a (dyn std::error::Error + 'static));
+
+impl<'a> ErrorChain<'a> {
+    /// Constructs a new `ErrorChain` starting at `error`.
+    pub fn new(error: &'a (dyn std::error::Error + 'static)) -> Self {
+        Self(error)
+    }
+}
+
+impl fmt::Display for ErrorChain<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)?;
+
+        let mut source = self.0.source();
+        if source.is_some() {
+            write!(f, "\n\nCaused by:")?;
+        }
+        let mut i = 0;
+        while let Some(error) = source {
+            write!(f, "\n    {i}: {error}")?;
+            source = error.source();
+            i += 1;
+        }
+        Ok(())
+    }
+}
+
 impl<T: ?Sized> Drop for Error<T> {
     fn drop(&mut self) {
         let vtable = self.vtable();
@@ -0,0 +1,138 @@
+use alloc::boxed::Box;
+use core::{ffi::c_void, marker::PhantomData, mem::MaybeUninit};
+
+/// Virtual function table of a [`FFIIterator`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct FFIIteratorVTable<T> {
+    /// Writes the next item into the second argument, and returns whether an item was written.
+    pub next: unsafe extern "C" fn(*mut c_void, *mut MaybeUninit<T>) -> bool,
+    /// Destroys the iterator.
+    pub drop: unsafe extern "C" fn(*mut c_void),
+}
+
+impl<T> Clone for FFIIteratorVTable<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for FFIIteratorVTable<T> {}
+
+/// FFI-safe owning iterator.
+///
+/// Allows an interface to stream a sequence of items to its caller, without collecting them
+/// first, or requiring that the caller and the callee agree on the layout of a collection. The
+/// iterator is consumed with the [`Iterator`] trait, and destroyed when dropped, which also drops
+/// the remaining items. The items must be FFI-safe themselves.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::ffi::FFIIterator;
+///
+/// let iter = FFIIterator::new((1..=3).map(|x| x * 2));
+/// assert_eq!(iter.collect::<Vec<i32>>(), [2, 4, 6]);
+/// ```
+#[repr(C)]
+#[derive(Debug)]
+pub struct FFIIterator<'a, T> {
+    data: *mut c_void,
+    vtable: FFIIteratorVTable<T>,
+    _phantom: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> FFIIterator<'a, T> {
+    /// Constructs a new `FFIIterator` from a Rust iterator.
+    pub fn new<I>(iter: I) -> Self
+    where
+        I: Iterator<Item = T> + Send + 'a,
+    {
+        let data = Box::into_raw(Box::new(iter));
+        Self {
+            data: data.cast(),
+            vtable: IterVTable::<I>::VTABLE,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Constructs a new `FFIIterator` from its raw parts.
+    ///
+    /// # Safety
+    ///
+    /// The functions of `vtable` must be safe to call with `data` from any thread, for the
+    /// lifetime `'a`, and the caller must own `data`.
+    pub unsafe fn from_raw_parts(data: *mut c_void, vtable: FFIIteratorVTable<T>) -> Self {
+        Self {
+            data,
+            vtable,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Decomposes the `FFIIterator` into its raw parts.
+    ///
+    /// The caller becomes responsible for destroying the iterator with the `drop` function of the
+    /// returned vtable.
+    pub fn into_raw_parts(self) -> (*mut c_void, FFIIteratorVTable<T>) {
+        let this = core::mem::ManuallyDrop::new(self);
+        (this.data, this.vtable)
+    }
+}
+
+impl<T> Iterator for FFIIterator<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut item = MaybeUninit::uninit();
+        // Safety: The vtable is valid for the data.
+        unsafe {
+            if (self.vtable.next)(self.data, &mut item) {
+                Some(item.assume_init())
+            } else {
+                None
+            }
+        }
+    }
+}
+
+// Safety: The iterator is required to be `Send`.
+unsafe impl<T: Send> Send for FFIIterator<'_, T> {}
+
+impl<T> Drop for FFIIterator<'_, T> {
+    fn drop(&mut self) {
+        // Safety: We own the iterator.
+        unsafe { (self.vtable.drop)(self.data) }
+    }
+}
+
+struct IterVTable<I>(PhantomData<I>);
+
+impl<I: Iterator> IterVTable<I> {
+    const VTABLE: FFIIteratorVTable<I::Item> = FFIIteratorVTable {
+        next: Self::next,
+        drop: Self::drop,
+    };
+
+    unsafe extern "C" fn next(data: *mut c_void, item: *mut MaybeUninit<I::Item>) -> bool {
+        crate::panic::abort_on_panic(|| {
+            // Safety: The data is an owned `I`.
+            let iter = unsafe { &mut *data.cast::<I>() };
+            match iter.next() {
+                Some(value) => {
+                    // Safety: The caller provides a valid item pointer.
+                    unsafe { (*item).write(value) };
+                    true
+                }
+                None => false,
+            }
+        })
+    }
+
+    unsafe extern "C" fn drop(data: *mut c_void) {
+        crate::panic::abort_on_panic(|| {
+            // Safety: The data is an owned `I`.
+            drop(unsafe { Box::from_raw(data.cast::<I>()) });
+        });
+    }
+}
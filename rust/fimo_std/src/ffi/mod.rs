@@ -1,5 +1,9 @@
 //! FFI helpers.
 
+mod iter;
+
+pub use iter::*;
+
 /// Used to transfer ownership to and from a ffi interface.
 ///
 /// The ownership of a type is transferred by calling [`Self::into_ffi`] and
@@ -18,10 +18,14 @@ pub use paste;
 pub mod allocator;
 pub mod array_list;
 pub mod bindings;
+pub mod bootstrap;
+pub mod bus;
 pub mod context;
 pub mod error;
 pub mod ffi;
 pub mod graph;
+#[cfg(any(unix, windows))]
+pub mod mmap;
 pub mod module;
 pub mod panic;
 pub mod refcount;
@@ -0,0 +1,434 @@
+//! Memory-mapped files and shared memory.
+//!
+//! Provides read-only and copy-on-write mappings of files, and anonymous shared memory, which can
+//! be opened by other processes through its name. The mapped memory is accessed through
+//! [`View`]s and [`ViewMut`]s, which check the bounds and alignment of all typed accesses.
+use core::ops::{Deref, DerefMut};
+use std::{fs::File, io};
+
+#[cfg(unix)]
+mod unix;
+mod view;
+#[cfg(windows)]
+mod windows;
+
+#[cfg(unix)]
+use unix as sys;
+#[cfg(windows)]
+use windows as sys;
+
+pub use view::*;
+
+/// Access mode of a mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Access {
+    ReadOnly,
+    CopyOnWrite,
+}
+
+/// Mapped region of memory.
+///
+/// The mapping starts at an offset aligned to the allocation granularity of the system, while
+/// `ptr` points to the first requested byte.
+#[derive(Debug)]
+struct RawMap {
+    base: *mut u8,
+    base_len: usize,
+    ptr: *mut u8,
+    len: usize,
+}
+
+impl RawMap {
+    fn empty() -> Self {
+        Self {
+            base: core::ptr::null_mut(),
+            base_len: 0,
+            ptr: core::ptr::NonNull::dangling().as_ptr(),
+            len: 0,
+        }
+    }
+
+    /// Maps a range of a file.
+    ///
+    /// Fails if the range extends past the end of the file, as accessing the mapped memory past
+    /// the end would raise a signal, e.g., `SIGBUS` on unix.
+    ///
+    /// # Safety
+    ///
+    /// The range of the file must not be modified or truncated while it is mapped.
+    unsafe fn file(file: &File, offset: u64, len: usize, access: Access) -> io::Result<Self> {
+        let file_len = file.metadata()?.len();
+        let end = u64::try_from(len)
+            .ok()
+            .and_then(|len| offset.checked_add(len))
+            .ok_or_else(too_large)?;
+        if end > file_len {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "mapping extends past the end of the file",
+            ));
+        }
+        if len == 0 {
+            return Ok(Self::empty());
+        }
+
+        let granularity = sys::allocation_granularity() as u64;
+        let delta = (offset % granularity) as usize;
+        let base_len = len.checked_add(delta).ok_or_else(too_large)?;
+        // Safety: Ensured by the caller.
+        let base = unsafe { sys::map_file(file, offset - delta as u64, base_len, access)? };
+        Ok(Self {
+            base,
+            base_len,
+            // Safety: The mapping contains `delta + len` bytes.
+            ptr: unsafe { base.add(delta) },
+            len,
+        })
+    }
+
+    /// Maps the whole file.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated while it is mapped.
+    unsafe fn whole_file(file: &File, access: Access) -> io::Result<Self> {
+        let len = usize::try_from(file.metadata()?.len())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        // Safety: Ensured by the caller.
+        unsafe { Self::file(file, 0, len, access) }
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        // Safety: The mapping is valid for `len` bytes.
+        unsafe { core::slice::from_raw_parts(self.ptr, self.len) }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        // Safety: The mapping is valid for `len` bytes.
+        unsafe { core::slice::from_raw_parts_mut(self.ptr, self.len) }
+    }
+}
+
+impl Drop for RawMap {
+    fn drop(&mut self) {
+        if self.base_len != 0 {
+            // Safety: We own the mapping.
+            unsafe { sys::unmap(self.base, self.base_len) };
+        }
+    }
+}
+
+// Safety: The mapping is not bound to a thread.
+unsafe impl Send for RawMap {}
+
+// Safety: Mutable accesses require a mutable reference.
+unsafe impl Sync for RawMap {}
+
+fn too_large() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, "mapping too large")
+}
+
+/// Read-only memory mapping of a file.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::mmap::Mmap;
+/// use std::{fs::File, io::Write};
+///
+/// let path = std::env::temp_dir().join("fimo_std_mmap_doctest.bin");
+/// File::create(&path)?.write_all(&[1, 0, 0, 0, 2, 0, 0, 0])?;
+///
+/// let file = File::open(&path)?;
+/// // Safety: The file is not modified while it is mapped.
+/// let map = unsafe { Mmap::map(&file)? };
+/// assert_eq!(map.len(), 8);
+/// assert_eq!(map.view().read::<u32>(4).map(u32::from_le), Some(2));
+/// assert_eq!(map.view().read::<u32>(6), None);
+///
+/// // Safety: The file is not modified while it is mapped.
+/// let range = unsafe { Mmap::map_range(&file, 4, 4)? };
+/// assert_eq!(&*range, &[2, 0, 0, 0]);
+/// # drop((map, range));
+/// # std::fs::remove_file(&path)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Mmap(RawMap);
+
+impl Mmap {
+    /// Maps the whole file.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated, e.g., by another process, while it is mapped.
+    pub unsafe fn map(file: &File) -> io::Result<Self> {
+        // Safety: Ensured by the caller.
+        unsafe { RawMap::whole_file(file, Access::ReadOnly).map(Self) }
+    }
+
+    /// Maps `len` bytes of the file, starting at `offset`.
+    ///
+    /// # Safety
+    ///
+    /// The range of the file must not be modified or truncated, e.g., by another process, while
+    /// it is mapped.
+    pub unsafe fn map_range(file: &File, offset: u64, len: usize) -> io::Result<Self> {
+        // Safety: Ensured by the caller.
+        unsafe { RawMap::file(file, offset, len, Access::ReadOnly).map(Self) }
+    }
+
+    /// Returns a view of the mapped memory.
+    pub fn view(&self) -> View<'_> {
+        View::new(self)
+    }
+}
+
+impl Deref for Mmap {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_slice()
+    }
+}
+
+/// Copy-on-write memory mapping of a file.
+///
+/// Writes to the mapping are private to the mapping, and are not carried through to the file.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::mmap::CowMmap;
+/// use std::{fs::File, io::Write};
+///
+/// let path = std::env::temp_dir().join("fimo_std_cow_mmap_doctest.bin");
+/// File::create(&path)?.write_all(&[0; 16])?;
+///
+/// let file = File::open(&path)?;
+/// // Safety: The file is not modified while it is mapped.
+/// let mut map = unsafe { CowMmap::map(&file)? };
+/// map.view_mut().write(8, 42u64).unwrap();
+/// assert_eq!(map.view().get::<u64>(8), Some(&42));
+/// assert_eq!(std::fs::read(&path)?, [0; 16]);
+/// # drop(map);
+/// # std::fs::remove_file(&path)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct CowMmap(RawMap);
+
+impl CowMmap {
+    /// Maps the whole file.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated, e.g., by another process, while it is mapped.
+    pub unsafe fn map(file: &File) -> io::Result<Self> {
+        // Safety: Ensured by the caller.
+        unsafe { RawMap::whole_file(file, Access::CopyOnWrite).map(Self) }
+    }
+
+    /// Maps `len` bytes of the file, starting at `offset`.
+    ///
+    /// # Safety
+    ///
+    /// The range of the file must not be modified or truncated, e.g., by another process, while
+    /// it is mapped.
+    pub unsafe fn map_range(file: &File, offset: u64, len: usize) -> io::Result<Self> {
+        // Safety: Ensured by the caller.
+        unsafe { RawMap::file(file, offset, len, Access::CopyOnWrite).map(Self) }
+    }
+
+    /// Returns a view of the mapped memory.
+    pub fn view(&self) -> View<'_> {
+        View::new(self)
+    }
+
+    /// Returns a mutable view of the mapped memory.
+    pub fn view_mut(&mut self) -> ViewMut<'_> {
+        ViewMut::new(self)
+    }
+}
+
+impl Deref for CowMmap {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_slice()
+    }
+}
+
+impl DerefMut for CowMmap {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0.as_mut_slice()
+    }
+}
+
+/// Named region of shared memory.
+///
+/// The region is created by one process with [`SharedMemory::create`], and can then be mapped by
+/// other processes by passing the same name to [`SharedMemory::open`]. The memory is initially
+/// zeroed, and remains valid until the last mapping is dropped. On unix, the name is removed once
+/// the creating instance is dropped, while on Windows, it is removed with the last mapping.
+///
+/// Since the memory can be modified by other processes at any time, it can only be accessed
+/// through raw pointers, or through views, whose construction is unsafe.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::mmap::SharedMemory;
+///
+/// let name = format!("fimo_std_shm_doctest_{}", std::process::id());
+/// let owner = SharedMemory::create(&name, 4096)?;
+/// let peer = SharedMemory::open(&name)?;
+/// assert!(peer.len() >= owner.len());
+///
+/// // Safety: The memory is not accessed concurrently.
+/// unsafe { owner.view_mut().write(0, 0xF1F0u32).unwrap() };
+/// // Safety: The memory is not accessed concurrently.
+/// assert_eq!(unsafe { peer.view().read::<u32>(0) }, Some(0xF1F0));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct SharedMemory {
+    map: RawMap,
+    name: String,
+    owner: bool,
+}
+
+impl SharedMemory {
+    /// Creates a new region of shared memory with the given name and length.
+    ///
+    /// The name must not be empty, and must not contain slashes, backslashes or nul bytes. Fails
+    /// if a region with the same name already exists.
+    pub fn create(name: &str, len: usize) -> io::Result<Self> {
+        validate_name(name)?;
+        if len == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "shared memory must not be empty",
+            ));
+        }
+
+        let base = sys::create_shared(name, len)?;
+        Ok(Self {
+            map: RawMap {
+                base,
+                base_len: len,
+                ptr: base,
+                len,
+            },
+            name: name.into(),
+            owner: true,
+        })
+    }
+
+    /// Opens an existing region of shared memory.
+    ///
+    /// Depending on the system, the length of the opened region may be rounded up to a multiple
+    /// of the page size.
+    pub fn open(name: &str) -> io::Result<Self> {
+        validate_name(name)?;
+        let (base, len) = sys::open_shared(name)?;
+        Ok(Self {
+            map: RawMap {
+                base,
+                base_len: len,
+                ptr: base,
+                len,
+            },
+            name: name.into(),
+            owner: false,
+        })
+    }
+
+    /// Returns the name of the region.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the length of the mapped region in bytes.
+    pub fn len(&self) -> usize {
+        self.map.len
+    }
+
+    /// Returns whether the mapped region is empty.
+    pub fn is_empty(&self) -> bool {
+        self.map.len == 0
+    }
+
+    /// Returns a pointer to the start of the mapped region.
+    pub fn as_ptr(&self) -> *mut u8 {
+        self.map.ptr
+    }
+
+    /// Returns a view of the mapped region.
+    ///
+    /// # Safety
+    ///
+    /// The region must not be modified, e.g., by another process, while the view is alive.
+    pub unsafe fn view(&self) -> View<'_> {
+        View::new(self.map.as_slice())
+    }
+
+    /// Returns a mutable view of the mapped region.
+    ///
+    /// # Safety
+    ///
+    /// The region must not be accessed by any other view or process, while the view is alive.
+    pub unsafe fn view_mut(&self) -> ViewMut<'_> {
+        // Safety: Ensured by the caller.
+        let bytes = unsafe { core::slice::from_raw_parts_mut(self.map.ptr, self.map.len) };
+        ViewMut::new(bytes)
+    }
+}
+
+impl Drop for SharedMemory {
+    fn drop(&mut self) {
+        if self.owner {
+            sys::unlink_shared(&self.name);
+        }
+    }
+}
+
+fn validate_name(name: &str) -> io::Result<()> {
+    if name.is_empty() || name.contains(['/', '\\', '\0']) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "invalid shared memory name",
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn map_past_end() -> io::Result<()> {
+        let path = std::env::temp_dir().join(format!("fimo_std_mmap_eof_{}", std::process::id()));
+        File::create(&path)?.write_all(&[0; 16])?;
+        let file = File::open(&path)?;
+
+        // Safety: The file is not modified while it is mapped.
+        let map = unsafe { Mmap::map_range(&file, 8, 8)? };
+        assert_eq!(&*map, &[0; 8]);
+        drop(map);
+
+        for (offset, len) in [(8, 9), (16, 1), (17, 0), (u64::MAX, 1)] {
+            // Safety: The file is not modified while it is mapped.
+            let error = unsafe { Mmap::map_range(&file, offset, len) }.unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+            // Safety: The file is not modified while it is mapped.
+            let error = unsafe { CowMmap::map_range(&file, offset, len) }.unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        }
+
+        drop(file);
+        std::fs::remove_file(&path)
+    }
+}
@@ -0,0 +1,132 @@
+use super::Access;
+use std::{
+    ffi::CString,
+    fs::File,
+    io,
+    os::fd::{AsRawFd, FromRawFd, OwnedFd},
+};
+
+pub fn allocation_granularity() -> usize {
+    // Safety: FFI call is safe.
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    usize::try_from(page_size).unwrap_or(4096)
+}
+
+/// Maps `len` bytes of a file, starting at the page aligned `offset`.
+///
+/// # Safety
+///
+/// The range of the file must not be modified or truncated while it is mapped.
+pub unsafe fn map_file(
+    file: &File,
+    offset: u64,
+    len: usize,
+    access: Access,
+) -> io::Result<*mut u8> {
+    let (prot, flags) = match access {
+        Access::ReadOnly => (libc::PROT_READ, libc::MAP_SHARED),
+        Access::CopyOnWrite => (libc::PROT_READ | libc::PROT_WRITE, libc::MAP_PRIVATE),
+    };
+    let offset = libc::off_t::try_from(offset)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    map(len, prot, flags, file.as_raw_fd(), offset)
+}
+
+/// Unmaps a mapping.
+///
+/// # Safety
+///
+/// The mapping must have been created by this module, and must not be used afterward.
+pub unsafe fn unmap(ptr: *mut u8, len: usize) {
+    // Safety: Ensured by the caller.
+    unsafe { libc::munmap(ptr.cast(), len) };
+}
+
+pub fn create_shared(name: &str, len: usize) -> io::Result<*mut u8> {
+    let name = shm_name(name)?;
+    let fd = shm_open(&name, libc::O_CREAT | libc::O_EXCL | libc::O_RDWR)?;
+    let result = libc::off_t::try_from(len)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+        .and_then(|size| {
+            // Safety: The descriptor is valid.
+            if unsafe { libc::ftruncate(fd.as_raw_fd(), size) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            map(
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                fd.as_raw_fd(),
+                0,
+            )
+        });
+    if result.is_err() {
+        // Safety: The name is a valid string.
+        unsafe { libc::shm_unlink(name.as_ptr()) };
+    }
+    result
+}
+
+pub fn open_shared(name: &str) -> io::Result<(*mut u8, usize)> {
+    let name = shm_name(name)?;
+    let fd = shm_open(&name, libc::O_RDWR)?;
+    // Safety: `stat` is valid when zeroed.
+    let mut stat: libc::stat = unsafe { core::mem::zeroed() };
+    // Safety: The descriptor and the buffer are valid.
+    if unsafe { libc::fstat(fd.as_raw_fd(), &mut stat) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let len =
+        usize::try_from(stat.st_size).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    if len == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "shared memory is empty",
+        ));
+    }
+    let ptr = map(
+        len,
+        libc::PROT_READ | libc::PROT_WRITE,
+        libc::MAP_SHARED,
+        fd.as_raw_fd(),
+        0,
+    )?;
+    Ok((ptr, len))
+}
+
+pub fn unlink_shared(name: &str) {
+    if let Ok(name) = shm_name(name) {
+        // Safety: The name is a valid string.
+        unsafe { libc::shm_unlink(name.as_ptr()) };
+    }
+}
+
+fn shm_name(name: &str) -> io::Result<CString> {
+    // Fails with `InvalidInput` if the name contains a nul byte.
+    CString::new(format!("/{name}")).map_err(io::Error::from)
+}
+
+fn shm_open(name: &CString, flags: libc::c_int) -> io::Result<OwnedFd> {
+    // Safety: The name is a valid string.
+    let fd = unsafe { libc::shm_open(name.as_ptr(), flags, 0o600) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // Safety: We own the descriptor.
+    Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+}
+
+fn map(
+    len: usize,
+    prot: libc::c_int,
+    flags: libc::c_int,
+    fd: libc::c_int,
+    offset: libc::off_t,
+) -> io::Result<*mut u8> {
+    // Safety: A new mapping does not alias any existing memory.
+    let ptr = unsafe { libc::mmap(core::ptr::null_mut(), len, prot, flags, fd, offset) };
+    if ptr == libc::MAP_FAILED {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(ptr.cast())
+}
@@ -0,0 +1,197 @@
+use core::{
+    mem::{align_of, size_of},
+    ops::Range,
+};
+
+/// Marker for types which are valid for any bit pattern.
+///
+/// Values of the types can be read from and written to arbitrary memory through a [`View`] or
+/// [`ViewMut`].
+///
+/// # Safety
+///
+/// Any bit pattern must be a valid value of the type, and the type must not contain any padding
+/// bytes.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($ty:ty),*) => {
+        $(
+            // Safety: Primitive numbers are valid for any bit pattern.
+            unsafe impl Pod for $ty {}
+        )*
+    };
+}
+
+impl_pod!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+// Safety: Arrays of `Pod` types contain no padding.
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+/// Returns the byte range of `count` values of type `T`, starting at `offset`.
+fn range_of<T>(len: usize, offset: usize, count: usize) -> Option<Range<usize>> {
+    let size = size_of::<T>().checked_mul(count)?;
+    let end = offset.checked_add(size)?;
+    (end <= len).then_some(offset..end)
+}
+
+/// Bounds-checked view of a region of memory.
+#[derive(Debug, Clone, Copy)]
+pub struct View<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> View<'a> {
+    /// Constructs a new view of the bytes.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    /// Returns the length of the view in bytes.
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Returns whether the view is empty.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns the bytes of the view.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// Returns a view of `len` bytes, starting at `offset`.
+    ///
+    /// Returns `None` if the range is out of bounds.
+    pub fn subview(&self, offset: usize, len: usize) -> Option<View<'a>> {
+        let range = range_of::<u8>(self.len(), offset, len)?;
+        Some(View::new(&self.bytes[range]))
+    }
+
+    /// Reads a value at `offset`, which need not be aligned.
+    ///
+    /// Returns `None` if the value is out of bounds.
+    pub fn read<T: Pod>(&self, offset: usize) -> Option<T> {
+        let range = range_of::<T>(self.len(), offset, 1)?;
+        // Safety: The range is in bounds, and `T` is valid for any bit pattern.
+        unsafe { Some(self.bytes[range].as_ptr().cast::<T>().read_unaligned()) }
+    }
+
+    /// Returns a reference to the value at `offset`.
+    ///
+    /// Returns `None` if the value is out of bounds, or if `offset` is not suitably aligned.
+    pub fn get<T: Pod>(&self, offset: usize) -> Option<&'a T> {
+        self.get_slice(offset, 1).map(|x| &x[0])
+    }
+
+    /// Returns a slice of `count` values, starting at `offset`.
+    ///
+    /// Returns `None` if the slice is out of bounds, or if `offset` is not suitably aligned.
+    pub fn get_slice<T: Pod>(&self, offset: usize, count: usize) -> Option<&'a [T]> {
+        let range = range_of::<T>(self.len(), offset, count)?;
+        let ptr = self.bytes[range].as_ptr().cast::<T>();
+        if ptr.addr() % align_of::<T>() != 0 {
+            return None;
+        }
+        // Safety: The range is in bounds and aligned, and `T` is valid for any bit pattern.
+        unsafe { Some(core::slice::from_raw_parts(ptr, count)) }
+    }
+}
+
+/// Bounds-checked mutable view of a region of memory.
+#[derive(Debug)]
+pub struct ViewMut<'a> {
+    bytes: &'a mut [u8],
+}
+
+impl<'a> ViewMut<'a> {
+    /// Constructs a new mutable view of the bytes.
+    pub fn new(bytes: &'a mut [u8]) -> Self {
+        Self { bytes }
+    }
+
+    /// Returns the length of the view in bytes.
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Returns whether the view is empty.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns an immutable view of the same memory.
+    pub fn as_view(&self) -> View<'_> {
+        View::new(self.bytes)
+    }
+
+    /// Returns the bytes of the view.
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        self.bytes
+    }
+
+    /// Consumes the view, returning the bytes of the view.
+    pub fn into_bytes(self) -> &'a mut [u8] {
+        self.bytes
+    }
+
+    /// Returns a mutable view of `len` bytes, starting at `offset`.
+    ///
+    /// Returns `None` if the range is out of bounds.
+    pub fn subview_mut(&mut self, offset: usize, len: usize) -> Option<ViewMut<'_>> {
+        let range = range_of::<u8>(self.len(), offset, len)?;
+        Some(ViewMut::new(&mut self.bytes[range]))
+    }
+
+    /// Reads a value at `offset`, which need not be aligned.
+    ///
+    /// Returns `None` if the value is out of bounds.
+    pub fn read<T: Pod>(&self, offset: usize) -> Option<T> {
+        self.as_view().read(offset)
+    }
+
+    /// Writes a value at `offset`, which need not be aligned.
+    ///
+    /// Returns `None` if the value is out of bounds.
+    pub fn write<T: Pod>(&mut self, offset: usize, value: T) -> Option<()> {
+        let range = range_of::<T>(self.len(), offset, 1)?;
+        // Safety: The range is in bounds, and `T` contains no padding.
+        unsafe {
+            self.bytes[range]
+                .as_mut_ptr()
+                .cast::<T>()
+                .write_unaligned(value)
+        };
+        Some(())
+    }
+
+    /// Returns a reference to the value at `offset`.
+    ///
+    /// Returns `None` if the value is out of bounds, or if `offset` is not suitably aligned.
+    pub fn get<T: Pod>(&self, offset: usize) -> Option<&T> {
+        self.as_view().get(offset)
+    }
+
+    /// Returns a mutable reference to the value at `offset`.
+    ///
+    /// Returns `None` if the value is out of bounds, or if `offset` is not suitably aligned.
+    pub fn get_mut<T: Pod>(&mut self, offset: usize) -> Option<&mut T> {
+        self.get_slice_mut(offset, 1).map(|x| &mut x[0])
+    }
+
+    /// Returns a mutable slice of `count` values, starting at `offset`.
+    ///
+    /// Returns `None` if the slice is out of bounds, or if `offset` is not suitably aligned.
+    pub fn get_slice_mut<T: Pod>(&mut self, offset: usize, count: usize) -> Option<&mut [T]> {
+        let range = range_of::<T>(self.len(), offset, count)?;
+        let ptr = self.bytes[range].as_mut_ptr().cast::<T>();
+        if ptr.addr() % align_of::<T>() != 0 {
+            return None;
+        }
+        // Safety: The range is in bounds and aligned, and `T` is valid for any bit pattern and
+        // contains no padding.
+        unsafe { Some(core::slice::from_raw_parts_mut(ptr, count)) }
+    }
+}
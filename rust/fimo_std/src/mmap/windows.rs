@@ -0,0 +1,218 @@
+use super::Access;
+use core::ffi::c_void;
+use std::{fs::File, io, os::windows::io::AsRawHandle};
+
+const PAGE_READONLY: u32 = 0x02;
+const PAGE_READWRITE: u32 = 0x04;
+const PAGE_WRITECOPY: u32 = 0x08;
+const FILE_MAP_COPY: u32 = 0x01;
+const FILE_MAP_READ: u32 = 0x04;
+const FILE_MAP_ALL_ACCESS: u32 = 0x000F_001F;
+const ERROR_ALREADY_EXISTS: i32 = 183;
+
+#[repr(C)]
+#[allow(dead_code)]
+struct SystemInfo {
+    processor_architecture: u16,
+    reserved: u16,
+    page_size: u32,
+    minimum_application_address: *mut c_void,
+    maximum_application_address: *mut c_void,
+    active_processor_mask: usize,
+    number_of_processors: u32,
+    processor_type: u32,
+    allocation_granularity: u32,
+    processor_level: u16,
+    processor_revision: u16,
+}
+
+#[repr(C)]
+#[allow(dead_code)]
+struct MemoryBasicInformation {
+    base_address: *mut c_void,
+    allocation_base: *mut c_void,
+    allocation_protect: u32,
+    #[cfg(target_pointer_width = "64")]
+    partition_id: u16,
+    region_size: usize,
+    state: u32,
+    protect: u32,
+    type_: u32,
+}
+
+#[link(name = "kernel32")]
+extern "system" {
+    fn GetSystemInfo(info: *mut SystemInfo);
+    fn CreateFileMappingW(
+        file: *mut c_void,
+        attributes: *const c_void,
+        protect: u32,
+        maximum_size_high: u32,
+        maximum_size_low: u32,
+        name: *const u16,
+    ) -> *mut c_void;
+    fn OpenFileMappingW(desired_access: u32, inherit_handle: i32, name: *const u16) -> *mut c_void;
+    fn MapViewOfFile(
+        mapping: *mut c_void,
+        desired_access: u32,
+        offset_high: u32,
+        offset_low: u32,
+        len: usize,
+    ) -> *mut c_void;
+    fn UnmapViewOfFile(base: *const c_void) -> i32;
+    fn VirtualQuery(
+        address: *const c_void,
+        buffer: *mut MemoryBasicInformation,
+        len: usize,
+    ) -> usize;
+    fn CloseHandle(handle: *mut c_void) -> i32;
+}
+
+pub fn allocation_granularity() -> usize {
+    // Safety: `SystemInfo` is valid when zeroed.
+    let mut info: SystemInfo = unsafe { core::mem::zeroed() };
+    // Safety: The buffer is valid.
+    unsafe { GetSystemInfo(&mut info) };
+    info.allocation_granularity as usize
+}
+
+/// Maps `len` bytes of a file, starting at the aligned `offset`.
+///
+/// # Safety
+///
+/// The range of the file must not be modified or truncated while it is mapped.
+pub unsafe fn map_file(
+    file: &File,
+    offset: u64,
+    len: usize,
+    access: Access,
+) -> io::Result<*mut u8> {
+    let (protect, desired_access) = match access {
+        Access::ReadOnly => (PAGE_READONLY, FILE_MAP_READ),
+        Access::CopyOnWrite => (PAGE_WRITECOPY, FILE_MAP_COPY),
+    };
+
+    // Safety: The file handle is valid.
+    let mapping = unsafe {
+        CreateFileMappingW(
+            file.as_raw_handle().cast(),
+            core::ptr::null(),
+            protect,
+            0,
+            0,
+            core::ptr::null(),
+        )
+    };
+    if mapping.is_null() {
+        return Err(io::Error::last_os_error());
+    }
+    // The view keeps the mapping object alive.
+    let view = map_view(mapping, desired_access, offset, len);
+    // Safety: We own the handle.
+    unsafe { CloseHandle(mapping) };
+    view
+}
+
+/// Unmaps a mapping.
+///
+/// # Safety
+///
+/// The mapping must have been created by this module, and must not be used afterward.
+pub unsafe fn unmap(ptr: *mut u8, _len: usize) {
+    // Safety: Ensured by the caller.
+    unsafe { UnmapViewOfFile(ptr.cast_const().cast()) };
+}
+
+pub fn create_shared(name: &str, len: usize) -> io::Result<*mut u8> {
+    let name = mapping_name(name);
+    let len_u64 = len as u64;
+    // Safety: The name is a valid string.
+    let mapping = unsafe {
+        CreateFileMappingW(
+            -1isize as *mut c_void,
+            core::ptr::null(),
+            PAGE_READWRITE,
+            (len_u64 >> 32) as u32,
+            len_u64 as u32,
+            name.as_ptr(),
+        )
+    };
+    if mapping.is_null() {
+        return Err(io::Error::last_os_error());
+    }
+    let error = io::Error::last_os_error();
+    if error.raw_os_error() == Some(ERROR_ALREADY_EXISTS) {
+        // Safety: We own the handle.
+        unsafe { CloseHandle(mapping) };
+        return Err(io::Error::from(io::ErrorKind::AlreadyExists));
+    }
+
+    let view = map_view(mapping, FILE_MAP_ALL_ACCESS, 0, len);
+    // Safety: We own the handle.
+    unsafe { CloseHandle(mapping) };
+    view
+}
+
+pub fn open_shared(name: &str) -> io::Result<(*mut u8, usize)> {
+    let name = mapping_name(name);
+    // Safety: The name is a valid string.
+    let mapping = unsafe { OpenFileMappingW(FILE_MAP_ALL_ACCESS, 0, name.as_ptr()) };
+    if mapping.is_null() {
+        return Err(io::Error::last_os_error());
+    }
+    let view = map_view(mapping, FILE_MAP_ALL_ACCESS, 0, 0);
+    // Safety: We own the handle.
+    unsafe { CloseHandle(mapping) };
+    let view = view?;
+
+    // Safety: `MemoryBasicInformation` is valid when zeroed.
+    let mut info: MemoryBasicInformation = unsafe { core::mem::zeroed() };
+    // Safety: The buffer is valid.
+    let written = unsafe {
+        VirtualQuery(
+            view.cast_const().cast(),
+            &mut info,
+            core::mem::size_of::<MemoryBasicInformation>(),
+        )
+    };
+    if written == 0 {
+        let error = io::Error::last_os_error();
+        // Safety: We own the view.
+        unsafe { UnmapViewOfFile(view.cast_const().cast()) };
+        return Err(error);
+    }
+    Ok((view, info.region_size))
+}
+
+pub fn unlink_shared(_name: &str) {
+    // Named mappings are removed with their last handle.
+}
+
+fn mapping_name(name: &str) -> Vec<u16> {
+    format!("Local\\{name}")
+        .encode_utf16()
+        .chain(core::iter::once(0))
+        .collect()
+}
+
+fn map_view(
+    mapping: *mut c_void,
+    desired_access: u32,
+    offset: u64,
+    len: usize,
+) -> io::Result<*mut u8> {
+    // Safety: The mapping handle is valid.
+    let view = unsafe {
+        MapViewOfFile(
+            mapping,
+            desired_access,
+            (offset >> 32) as u32,
+            offset as u32,
+            len,
+        )
+    };
+    if view.is_null() {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(view.cast())
+}
@@ -4,21 +4,34 @@ use alloc::boxed::Box;
 use core::{ffi::CStr, mem::MaybeUninit};
 
 use crate::{
+    allocator::FimoAllocator,
     bindings,
     context::private::SealedContext,
-    error::{to_result_indirect_in_place, Error},
+    error,
+    error::{to_result_indirect, to_result_indirect_in_place, Error},
+    ffi::FFISharable,
 };
 
+mod config;
+mod feature;
+mod loader;
 mod loading_set;
 mod module_export;
 mod module_info;
 mod parameter;
+mod plan;
+mod signature;
 mod symbol;
 
+pub use config::*;
+pub use feature::*;
+pub use loader::*;
 pub use loading_set::*;
 pub use module_export::*;
 pub use module_info::*;
 pub use parameter::*;
+pub use plan::*;
+pub use signature::*;
 pub use symbol::*;
 
 /// Definition of the module subsystem.
@@ -27,6 +40,184 @@ pub trait ModuleSubsystem: SealedContext {
     ///
     /// A namespace exists, if at least one loaded module exports one symbol in said namespace.
     fn namespace_exists(&self, namespace: &CStr) -> Result<bool, Error>;
+
+    /// Queries the number of uses of the symbols exported by a module.
+    ///
+    /// The counts are only a snapshot, and may change concurrently. Each entry also lists the
+    /// modules that imported or loaded the symbol, see [`SymbolUseCount::importers`]. Fails if
+    /// `module` is not loaded.
+    fn symbol_use_counts(
+        &self,
+        module: ModuleInfoView<'_>,
+    ) -> Result<Box<[SymbolUseCount], FimoAllocator>, Error>;
+
+    /// Queries the features declared by a loaded module.
+    ///
+    /// Returns the resolved state of each feature, as seen by the module during its construction.
+    /// Fails if no module with the name `module` is loaded.
+    fn module_features(&self, module: &CStr) -> Result<Box<[FeatureEntry], FimoAllocator>, Error>;
+
+    /// Overrides whether a feature of a module is enabled.
+    ///
+    /// The override is applied to all modules with the name `module` that are loaded afterward,
+    /// and takes precedence over the default of the feature. Features requiring a newer version of
+    /// the context remain disabled. Passing `None` removes the override.
+    fn set_feature_override(
+        &self,
+        module: &CStr,
+        feature: &CStr,
+        enabled: Option<bool>,
+    ) -> error::Result;
+
+    /// Registers a loader for module binaries of a custom format.
+    ///
+    /// The loader is selected by [`LoadingSet::append_modules`], if `pattern` matches the file
+    /// name of the binary. Registered loaders are tried in the order of their registration, and
+    /// binaries not matched by any loader are opened by the native loader. The loader is
+    /// unregistered once the returned guard is dropped. Fails if a loader with the same `name`
+    /// is already registered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fimo_std::{
+    ///     context::Context,
+    ///     error::Error,
+    ///     module::{
+    ///         LoadingFilterRequest, LoadingSet, LoadingSetRequest, ModuleExport, ModuleLoader,
+    ///         ModuleSubsystem,
+    ///     },
+    /// };
+    /// use std::{
+    ///     ffi::{CStr, CString},
+    ///     sync::atomic::{AtomicUsize, Ordering},
+    /// };
+    ///
+    /// static LOADED: AtomicUsize = AtomicUsize::new(0);
+    ///
+    /// struct EmptyLoader;
+    ///
+    /// impl ModuleLoader for EmptyLoader {
+    ///     type Binary = ();
+    ///
+    ///     fn load(&self, _path: &CStr) -> Result<Self::Binary, Error> {
+    ///         LOADED.fetch_add(1, Ordering::Relaxed);
+    ///         Ok(())
+    ///     }
+    ///
+    ///     fn exports<'a>(&self, _binary: &'a ()) -> impl Iterator<Item = ModuleExport<'a>> {
+    ///         std::iter::empty()
+    ///     }
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("register_loader.fimo-empty");
+    /// std::fs::write(&path, b"").unwrap();
+    /// let path = CString::new(path.to_str().unwrap()).unwrap();
+    ///
+    /// let context = Context::new().expect("could not create context");
+    /// let _registration = context
+    ///     .register_loader(c"empty", c"*.fimo-empty", EmptyLoader)
+    ///     .expect("could not register the loader");
+    ///
+    /// LoadingSet::with_loading_set(&*context, |ctx, set| {
+    ///     set.append_modules(ctx, Some(&path), |_| LoadingFilterRequest::Load)?;
+    ///     Ok(LoadingSetRequest::Dismiss)
+    /// })
+    /// .expect("could not append the modules");
+    /// assert_eq!(LOADED.load(Ordering::Relaxed), 1);
+    /// ```
+    fn register_loader<L: ModuleLoader>(
+        &self,
+        name: &CStr,
+        pattern: &CStr,
+        loader: L,
+    ) -> Result<LoaderRegistration<'_>, Error>;
+
+    /// Sets the verification policy of the signatures of module binaries.
+    ///
+    /// The policy applies to all future calls to [`LoadingSet::append_modules`] with a path. The
+    /// modules of the current binary are never verified. Once a binary has been loaded, the
+    /// policy can only be made stricter, so that it can only be relaxed by the embedder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fimo_std::{
+    ///     bus::{BusSubsystem, DeliveryMode, ModuleVerificationFailed},
+    ///     context::Context,
+    ///     error::Error,
+    ///     module::{
+    ///         LoadingFilterRequest, LoadingSet, LoadingSetRequest, ModuleExport, ModuleLoader,
+    ///         ModuleSubsystem, SignaturePolicy,
+    ///     },
+    /// };
+    /// use std::{
+    ///     ffi::{CStr, CString},
+    ///     sync::{Arc, Mutex},
+    /// };
+    ///
+    /// struct EmptyLoader;
+    ///
+    /// impl ModuleLoader for EmptyLoader {
+    ///     type Binary = ();
+    ///
+    ///     fn load(&self, _path: &CStr) -> Result<Self::Binary, Error> {
+    ///         Ok(())
+    ///     }
+    ///
+    ///     fn exports<'a>(&self, _binary: &'a ()) -> impl Iterator<Item = ModuleExport<'a>> {
+    ///         std::iter::empty()
+    ///     }
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("set_signature_policy.fimo-empty");
+    /// std::fs::write(&path, b"").unwrap();
+    /// let path = CString::new(path.to_str().unwrap()).unwrap();
+    ///
+    /// let context = Context::new().expect("could not create context");
+    /// let _registration = context
+    ///     .register_loader(c"empty", c"*.fimo-empty", EmptyLoader)
+    ///     .expect("could not register the loader");
+    /// let failures = Arc::new(Mutex::new(Vec::new()));
+    /// let _subscription = context
+    ///     .subscribe::<ModuleVerificationFailed, _>(DeliveryMode::Sync, {
+    ///         let failures = failures.clone();
+    ///         move |path| failures.lock().unwrap().push(path.to_owned())
+    ///     })
+    ///     .expect("could not subscribe");
+    ///
+    /// // The binary is not signed.
+    /// context
+    ///     .set_signature_policy(SignaturePolicy::Require)
+    ///     .expect("could not set the policy");
+    /// let result = LoadingSet::with_loading_set(&*context, |ctx, set| {
+    ///     set.append_modules(ctx, Some(&path), |_| LoadingFilterRequest::Load)?;
+    ///     Ok(LoadingSetRequest::Dismiss)
+    /// });
+    /// assert!(result.is_err());
+    /// assert_eq!(*failures.lock().unwrap(), [path]);
+    /// ```
+    fn set_signature_policy(&self, policy: SignaturePolicy) -> error::Result;
+
+    /// Adds a public key to the set of trusted keys.
+    ///
+    /// Binaries signed by a trusted key pass the verification with every [`SignaturePolicy`].
+    fn trust_signature_key(&self, key: &[u8; SIGNATURE_PUBLIC_KEY_SIZE]) -> error::Result;
+
+    /// Sets the policy for handling modules that fail to be constructed.
+    ///
+    /// The policy applies to all loading sets finished afterward, e.g., with
+    /// [`LoadingSet::with_loading_set`]. A panic in the constructor of a module is caught, and is
+    /// handled like an error returned by it.
+    fn set_fault_policy(&self, policy: FaultPolicy) -> error::Result;
+
+    /// Computes the plan for loading the modules of the binaries at `module_paths`, without
+    /// loading them.
+    ///
+    /// See [`LoadPlan::new`] for more details.
+    fn plan_load<T>(&self, module_paths: &[Option<&CStr>], filter: T) -> Result<LoadPlan, Error>
+    where
+        T: FnMut(ModuleExport<'_>) -> LoadingFilterRequest;
 }
 
 impl<T> ModuleSubsystem for T
@@ -45,6 +236,132 @@ where
             })
         }
     }
+
+    fn symbol_use_counts(
+        &self,
+        module: ModuleInfoView<'_>,
+    ) -> Result<Box<[SymbolUseCount], FimoAllocator>, Error> {
+        let mut count = 0;
+        // Safety: Either we get an error, or we initialize the use counts.
+        let use_counts = unsafe {
+            to_result_indirect_in_place(|error, use_counts| {
+                *error = bindings::fimo_module_symbol_use_counts(
+                    self.share_to_ffi(),
+                    module.share_to_ffi(),
+                    use_counts.as_mut_ptr(),
+                    &mut count,
+                );
+            })
+        }?;
+
+        if use_counts.is_null() {
+            return Ok(Box::new_in([], FimoAllocator));
+        }
+
+        // We can cast the pointer to an `SymbolUseCount` pointer, since the two types have the
+        // same layout.
+        let use_counts = use_counts.cast::<SymbolUseCount>();
+        let use_counts = core::ptr::slice_from_raw_parts_mut(use_counts, count);
+
+        // Safety: According to the API, the slice has been allocated with the fimo allocator,
+        // therefore we are allowed to construct a box with the same allocator.
+        unsafe { Ok(Box::from_raw_in(use_counts, FimoAllocator)) }
+    }
+
+    fn module_features(&self, module: &CStr) -> Result<Box<[FeatureEntry], FimoAllocator>, Error> {
+        let mut count = 0;
+        // Safety: Either we get an error, or we initialize the features.
+        let features = unsafe {
+            to_result_indirect_in_place(|error, features| {
+                *error = bindings::fimo_module_feature_list(
+                    self.share_to_ffi(),
+                    module.as_ptr(),
+                    features.as_mut_ptr(),
+                    &mut count,
+                );
+            })
+        }?;
+
+        if features.is_null() {
+            return Ok(Box::new_in([], FimoAllocator));
+        }
+
+        // We can cast the pointer to a `FeatureEntry` pointer, since the two types have the same
+        // layout.
+        let features = features.cast::<FeatureEntry>();
+        let features = core::ptr::slice_from_raw_parts_mut(features, count);
+
+        // Safety: According to the API, the slice has been allocated with the fimo allocator,
+        // therefore we are allowed to construct a box with the same allocator.
+        unsafe { Ok(Box::from_raw_in(features, FimoAllocator)) }
+    }
+
+    fn set_feature_override(
+        &self,
+        module: &CStr,
+        feature: &CStr,
+        enabled: Option<bool>,
+    ) -> error::Result {
+        let enabled = enabled
+            .as_ref()
+            .map_or(core::ptr::null(), core::ptr::from_ref);
+        // Safety: FFI call is safe.
+        unsafe {
+            to_result_indirect(|error| {
+                *error = bindings::fimo_module_feature_set_override(
+                    self.share_to_ffi(),
+                    module.as_ptr(),
+                    feature.as_ptr(),
+                    enabled,
+                );
+            })
+        }
+    }
+
+    fn register_loader<L: ModuleLoader>(
+        &self,
+        name: &CStr,
+        pattern: &CStr,
+        loader: L,
+    ) -> Result<LoaderRegistration<'_>, Error> {
+        loader::register_loader(self, name, pattern, loader)
+    }
+
+    fn set_signature_policy(&self, policy: SignaturePolicy) -> error::Result {
+        // Safety: FFI call is safe.
+        unsafe {
+            to_result_indirect(|error| {
+                *error =
+                    bindings::fimo_module_signature_set_policy(self.share_to_ffi(), policy.into());
+            })
+        }
+    }
+
+    fn trust_signature_key(&self, key: &[u8; SIGNATURE_PUBLIC_KEY_SIZE]) -> error::Result {
+        // Safety: FFI call is safe.
+        unsafe {
+            to_result_indirect(|error| {
+                *error =
+                    bindings::fimo_module_signature_trust_key(self.share_to_ffi(), key.as_ptr());
+            })
+        }
+    }
+
+    fn set_fault_policy(&self, policy: FaultPolicy) -> error::Result {
+        // Safety: FFI call is safe.
+        unsafe {
+            to_result_indirect(|error| {
+                *error = bindings::fimo_module_fault_set_policy(self.share_to_ffi(), policy.into());
+            })
+        }
+    }
+
+    fn plan_load<T>(&self, module_paths: &[Option<&CStr>], filter: T) -> Result<LoadPlan, Error>
+    where
+        T: FnMut(ModuleExport<'_>) -> LoadingFilterRequest,
+    {
+        LoadPlan::new(self, module_paths, filter)
+    }
 }
 
 /// A handle to a module that is being constructed.
@@ -149,6 +466,7 @@ macro_rules! export_module {
             $(license: $license:literal,)?
             $(parameters: { $($param_block:tt)* },)?
             $(resources: { $($res_block:tt)* },)?
+            $(features: { $($features_block:tt)* },)?
             $(namespaces: [ $($ns_block:tt)* ],)?
             $(imports: { $($imports_block:tt)* },)?
             $(exports: { $($exports_block:tt)* },)?
@@ -278,6 +596,9 @@ macro_rules! export_module {
                 let (resources, resources_count) = $crate::export_module_private_resources!(
                     ptr { $($($res_block)*)? }
                 );
+                let (modifiers, modifiers_count) = $crate::export_module_private_features!(
+                    ptr { $($($features_block)*)? }
+                );
                 let (namespace_imports, namespace_imports_count) = $crate::export_module_private_ns!(
                     ptr [ $($($ns_block)*)? ]
                 );
@@ -302,6 +623,12 @@ macro_rules! export_module {
                     next: core::ptr::null(),
                     export_abi: $crate::bindings::FIMO_MODULE_EXPORT_ABI
                         as $crate::bindings::FimoI32,
+                    context_version: $crate::bindings::FimoVersion {
+                        major: $crate::bindings::FIMO_VERSION_MAJOR,
+                        minor: $crate::bindings::FIMO_VERSION_MINOR,
+                        patch: $crate::bindings::FIMO_VERSION_PATCH,
+                        build: $crate::bindings::FIMO_VERSION_BUILD_NUMBER as u64,
+                    },
                     name,
                     description,
                     author,
@@ -318,8 +645,8 @@ macro_rules! export_module {
                     symbol_exports_count,
                     dynamic_symbol_exports,
                     dynamic_symbol_exports_count,
-                    modifiers: core::ptr::null(),
-                    modifiers_count: 0,
+                    modifiers,
+                    modifiers_count,
                     module_constructor,
                     module_destructor,
                 }
@@ -477,7 +804,7 @@ macro_rules! export_module_private_parameter {
             value: *mut core::ffi::c_void,
             type_: *mut $crate::bindings::FimoModuleParamType,
             data: *const $crate::bindings::FimoModuleParamData,
-        ) {
+        ) -> $crate::bindings::FimoResult {
             // Safety:
             unsafe {
                 $crate::module::c_ffi::get_param::<$mod_ident<'_>, _>(module, value, type_, data, $x)
@@ -486,16 +813,16 @@ macro_rules! export_module_private_parameter {
 
         Some(getter as _)
     }};
-    (setter $mod_ident:ident;) => {
+    (setter $mod_ident:ident; ;) => {
         Some($crate::bindings::fimo_module_param_set_inner as _)
     };
-    (setter $mod_ident:ident; $x:ident) => {{
+    (setter $mod_ident:ident; $x:ident;) => {{
         extern "C" fn setter(
             module: *const $crate::bindings::FimoModule,
             value: *const core::ffi::c_void,
             type_: $crate::bindings::FimoModuleParamType,
             data: *mut $crate::bindings::FimoModuleParamData,
-        ) {
+        ) -> $crate::bindings::FimoResult {
             // Safety:
             unsafe {
                 $crate::module::c_ffi::set_param::<$mod_ident<'_>, _>(module, value, type_, data, $x)
@@ -504,12 +831,33 @@ macro_rules! export_module_private_parameter {
 
         Some(setter as _)
     }};
+    (setter $mod_ident:ident; ; $on_change:ident) => {{
+        extern "C" fn setter(
+            module: *const $crate::bindings::FimoModule,
+            value: *const core::ffi::c_void,
+            type_: $crate::bindings::FimoModuleParamType,
+            data: *mut $crate::bindings::FimoModuleParamData,
+        ) -> $crate::bindings::FimoResult {
+            // Safety:
+            unsafe {
+                $crate::module::c_ffi::set_param_and_notify::<$mod_ident<'_>, _>(
+                    module, value, type_, data, $on_change,
+                )
+            }
+        }
+
+        Some(setter as _)
+    }};
+    (setter $mod_ident:ident; $x:ident; $on_change:ident) => {
+        core::compile_error!("a parameter with a custom `setter` can not specify `on_change`")
+    };
     ($mod_ident:ident; $( $name:ident: {
         default: $default_ty:ident ( $default:literal ),
         $(read_group: $read:ident,)?
         $(write_group: $write:ident,)?
         $(getter: $getter:ident,)?
         $(setter: $setter:ident,)?
+        $(on_change: $on_change:ident,)?
         $(override: $param_ty:ty,)?
     }),* $(,)?) => {
         &[
@@ -518,7 +866,7 @@ macro_rules! export_module_private_parameter {
                     type_: $crate::export_module_private_parameter!(default_type $default_ty),
                     read_access: $crate::export_module_private_parameter!(group $($read)?),
                     write_access: $crate::export_module_private_parameter!(group $($write)?),
-                    setter: $crate::export_module_private_parameter!(setter $mod_ident; $($setter)?),
+                    setter: $crate::export_module_private_parameter!(setter $mod_ident; $($setter)?; $($on_change)?),
                     getter: $crate::export_module_private_parameter!(getter $mod_ident; $($getter)?),
                     name: {
                         let x: &'static str = core::concat!(core::stringify!($name), '\0');
@@ -535,6 +883,7 @@ macro_rules! export_module_private_parameter {
         $(write_group: $write:ident,)?
         $(getter: $getter:ident,)?
         $(setter: $setter:ident,)?
+        $(on_change: $on_change:ident,)?
         $(override: $param_ty:ty,)?
     }),* $(,)?) => {
         $crate::paste::paste! {
@@ -699,6 +1048,7 @@ macro_rules! export_module_private_imports {
             impl [<$mod_ident Imports>] {
                 $(
                     #[doc = "Fetches the `" $name "` import symbol"]
+                    #[track_caller]
                     pub fn $name(&self) -> $crate::module::SymbolGuard<
                         '_, '_, <$import as $crate::module::SymbolItem>::Type
                     > {
@@ -857,6 +1207,7 @@ macro_rules! export_module_private_exports {
             impl [<$mod_ident Exports>] {
                 $(
                     #[doc = "Fetches the `" $s_name "` import symbol"]
+                    #[track_caller]
                     pub fn $s_name(&self) -> $crate::module::SymbolGuard<
                         '_, '_, <$s_export as $crate::module::SymbolItem>::Type
                     > {
@@ -865,6 +1216,7 @@ macro_rules! export_module_private_exports {
                 )*
                 $(
                     #[doc = "Fetches the `" $d_name "` import symbol"]
+                    #[track_caller]
                     pub fn $d_name(&self) -> $crate::module::SymbolGuard<
                         '_, '_, <<$d_export as $crate::module::DynamicExport<$mod_ident<'_>>>::Item as $crate::module::SymbolItem>::Type
                     > {
@@ -972,7 +1324,7 @@ pub mod c_ffi {
         module: *const bindings::FimoModule,
         value: *mut core::ffi::c_void,
         type_: *mut bindings::FimoModuleParamType,
-        data: *mut bindings::FimoModuleParamData,
+        data: *const bindings::FimoModuleParamData,
         f: F,
     ) -> bindings::FimoResult
     where
@@ -1096,6 +1448,36 @@ pub mod c_ffi {
         .into_ffi()
     }
 
+    pub unsafe fn set_param_and_notify<T, F>(
+        module: *const bindings::FimoModule,
+        value: *const core::ffi::c_void,
+        type_: bindings::FimoModuleParamType,
+        data: *mut bindings::FimoModuleParamData,
+        f: F,
+    ) -> bindings::FimoResult
+    where
+        T: Module,
+        F: FnOnce(&T, ParameterValue),
+    {
+        // Safety: The pointers are provided by the module subsystem.
+        let result = unsafe {
+            error::to_result(bindings::fimo_module_param_set_inner(
+                module, value, type_, data,
+            ))
+        };
+        if let Err(e) = result {
+            return e.into_ffi();
+        }
+
+        // Safety: The value has been written, and the pointers are still valid.
+        unsafe {
+            set_param::<T, _>(module, value, type_, data, |module, value, _| {
+                f(module, value);
+                Ok(())
+            })
+        }
+    }
+
     pub unsafe fn construct_dynamic_symbol<T, S>(
         module: *const bindings::FimoModule,
         symbol: *mut *mut core::ffi::c_void,
@@ -1174,7 +1556,7 @@ pub mod c_ffi {
         T: Module,
         C: ModuleConstructor<T>,
     {
-        crate::panic::abort_on_panic(|| {
+        let result = crate::panic::catch_unwind(|| {
             // Safety: See above
             unsafe {
                 let module = PreModule::<T>::from_ffi(module);
@@ -1185,6 +1567,16 @@ pub mod c_ffi {
                 });
             }
         });
+
+        // The destructor can not report an error, so we only log it. The remaining state of the
+        // module is leaked.
+        if let Err(e) = result.map_err(Error::from) {
+            crate::panic::abort_on_panic(|| {
+                // Safety: The module is valid until the destructor returns.
+                let module = unsafe { PreModule::<T>::from_ffi(module) };
+                crate::emit_error!(module.context(), "module destructor panicked: {e}");
+            });
+        }
     }
 }
 
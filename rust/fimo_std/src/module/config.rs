@@ -0,0 +1,975 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    ffi::CString,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{ffi::CStr, fmt::Write};
+use std::{
+    io::Write as _,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{self, Error},
+    version::Version,
+};
+
+use super::{ModuleSubsystem, ParameterAccess, ParameterInfo, ParameterValue};
+
+/// Name of the table containing the settings layout versions of the modules.
+const VERSIONS_TABLE: &str = "$versions";
+
+/// Name of the table containing the feature overrides of the modules.
+const FEATURES_TABLE: &str = "$features";
+
+/// Prefix of the environment variables read by [`ParameterConfig::load_env`].
+pub const PARAMETER_ENV_PREFIX: &str = "FIMO_PARAM_";
+
+/// Prefix of the environment variables overriding features, read by
+/// [`ParameterConfig::load_env`].
+pub const FEATURE_ENV_PREFIX: &str = "FIMO_FEATURE_";
+
+/// Origin of a configured parameter value.
+///
+/// The sources are ordered by their precedence, i.e., a value from the environment replaces the
+/// value from a file, and is replaced by an explicitly set value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ConfigSource {
+    File,
+    Environment,
+    Explicit,
+}
+
+impl core::fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ConfigSource::File => write!(f, "file"),
+            ConfigSource::Environment => write!(f, "environment"),
+            ConfigSource::Explicit => write!(f, "explicit"),
+        }
+    }
+}
+
+/// Initial values of module parameters.
+///
+/// Collects the values of the parameters from configuration files, the environment, and explicit
+/// calls, and overrides the default values of the parameters, when the modules are loaded. The
+/// values are validated against the declared type of the parameter once the module is loaded,
+/// which fails if the value is not representable by the type.
+///
+/// Configuration files map `module.parameter` to an integer or boolean value. Files ending with
+/// `.json` contain a JSON object, either with a nested object for each module, or with the
+/// dotted names as keys. All other files are parsed as TOML, with a table for each module:
+///
+/// ```toml
+/// # Keys outside of a table name the module and the parameter.
+/// fimo_tasks.max_workers = 8
+///
+/// [fimo_tasks]
+/// stack_tracking = true
+/// ```
+///
+/// The environment variable `FIMO_PARAM_<module>__<parameter>` sets the value of `parameter` of
+/// `module`.
+///
+/// Additionally, the configuration may enable or disable the features of the modules. They are
+/// listed in the `$features` table, or in the `$features` object of a JSON file, and can be
+/// overridden with the environment variable `FIMO_FEATURE_<module>__<feature>`:
+///
+/// ```toml
+/// ["$features"]
+/// fimo_tasks.stack_tracking = false
+/// ```
+///
+/// The configuration can also be persisted with [`ParameterConfig::save`], e.g., to restore the
+/// values of the parameters captured with [`ParameterConfig::snapshot`] at the next startup.
+/// Persisted files additionally record the version of the settings layout of each module in the
+/// `$versions` table, which allows the modules to upgrade the values of an older layout with
+/// [`ParameterConfig::migrate`]:
+///
+/// ```toml
+/// ["$versions"]
+/// fimo_tasks = "0.2.0"
+///
+/// [fimo_tasks]
+/// max_workers = 8
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParameterConfig {
+    entries: BTreeMap<(CString, CString), (ConfigSource, ParameterValue)>,
+    versions: BTreeMap<CString, Version>,
+    features: BTreeMap<(CString, CString), (ConfigSource, bool)>,
+    excluded: BTreeSet<(CString, Option<CString>)>,
+}
+
+impl ParameterConfig {
+    /// Constructs an empty configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the values of the configuration file at `path`.
+    ///
+    /// The format is chosen by the extension of the file.
+    pub fn load_file(&mut self, path: impl AsRef<Path>) -> error::Result {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path).map_err(Error::new)?;
+        if path.extension().is_some_and(|x| x == "json") {
+            self.load_json(&source)
+        } else {
+            self.load_toml(&source)
+        }
+    }
+
+    /// Loads the values of a TOML configuration.
+    ///
+    /// Only tables, integers and booleans are supported.
+    pub fn load_toml(&mut self, source: &str) -> error::Result {
+        let mut table: Option<String> = None;
+        for (i, line) in source.lines().enumerate() {
+            let line = strip_toml_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            let line_error = |msg: &str| Error::new(format!("line {}: {msg}", i + 1));
+
+            if let Some(header) = line.strip_prefix('[') {
+                let header = header
+                    .strip_suffix(']')
+                    .ok_or_else(|| line_error("unterminated table header"))?;
+                let mut keys = parse_toml_key(header).map_err(|e| line_error(&e))?;
+                if keys.len() != 1 {
+                    return Err(line_error("expected the name of a module"));
+                }
+                table = keys.pop();
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| line_error("expected a key-value pair"))?;
+            let mut keys = parse_toml_key(key).map_err(|e| line_error(&e))?;
+            if table.as_deref() == Some(VERSIONS_TABLE) {
+                let version = value
+                    .trim()
+                    .strip_prefix('"')
+                    .and_then(|x| x.strip_suffix('"'))
+                    .and_then(Version::parse)
+                    .ok_or_else(|| line_error("expected a version string"))?;
+                match &*keys {
+                    [module] => self.insert_version(module, version)?,
+                    _ => return Err(line_error("expected the name of a module")),
+                }
+                continue;
+            }
+            if table.as_deref() == Some(FEATURES_TABLE) {
+                let enabled = parse_toml_bool(value.trim()).map_err(|e| line_error(&e))?;
+                match &*keys {
+                    [module, feature] => {
+                        self.insert_feature(ConfigSource::File, module, feature, enabled)?;
+                    }
+                    _ => return Err(line_error("expected a `module.feature` key")),
+                }
+                continue;
+            }
+            let value = parse_toml_value(value.trim()).map_err(|e| line_error(&e))?;
+            match (&table, keys.len()) {
+                (Some(module), 1) => self.insert(ConfigSource::File, module, &keys[0], value)?,
+                (None, 2) => {
+                    let parameter = keys.pop().unwrap();
+                    let module = keys.pop().unwrap();
+                    self.insert(ConfigSource::File, &module, &parameter, value)?;
+                }
+                _ => return Err(line_error("expected a `module.parameter` key")),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Loads the values of a JSON configuration.
+    pub fn load_json(&mut self, source: &str) -> error::Result {
+        let mut parser = JsonParser {
+            source: source.as_bytes(),
+            pos: 0,
+        };
+        let root = parser.parse_document().map_err(Error::new)?;
+        let JsonValue::Object(entries) = root else {
+            return Err(Error::new("expected a JSON object"));
+        };
+
+        for (key, value) in entries {
+            match value {
+                JsonValue::Object(versions) if key == VERSIONS_TABLE => {
+                    for (module, version) in versions {
+                        let version = match version {
+                            JsonValue::String(x) => Version::parse(&x),
+                            _ => None,
+                        };
+                        let version = version.ok_or_else(|| {
+                            Error::new(format!("invalid version of module `{module}`"))
+                        })?;
+                        self.insert_version(&module, version)?;
+                    }
+                }
+                JsonValue::Object(modules) if key == FEATURES_TABLE => {
+                    for (module, features) in modules {
+                        let JsonValue::Object(features) = features else {
+                            return Err(Error::new(format!(
+                                "expected an object of the features of module `{module}`"
+                            )));
+                        };
+                        for (feature, enabled) in features {
+                            let JsonValue::Bool(enabled) = enabled else {
+                                return Err(Error::new(format!(
+                                    "expected a boolean value for feature `{module}.{feature}`"
+                                )));
+                            };
+                            self.insert_feature(ConfigSource::File, &module, &feature, enabled)?;
+                        }
+                    }
+                }
+                JsonValue::Object(params) => {
+                    for (parameter, value) in params {
+                        let value = value.into_parameter_value().map_err(|e| {
+                            Error::new(format!("parameter `{key}.{parameter}`: {e}"))
+                        })?;
+                        self.insert(ConfigSource::File, &key, &parameter, value)?;
+                    }
+                }
+                value => {
+                    let (module, parameter) = key
+                        .split_once('.')
+                        .ok_or_else(|| Error::new(format!("invalid parameter key `{key}`")))?;
+                    let value = value
+                        .into_parameter_value()
+                        .map_err(|e| Error::new(format!("parameter `{key}`: {e}")))?;
+                    self.insert(ConfigSource::File, module, parameter, value)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Loads the values of the environment variables of the current process.
+    ///
+    /// The variables are named `FIMO_PARAM_<module>__<parameter>`, or
+    /// `FIMO_FEATURE_<module>__<feature>` for features, and are case-sensitive.
+    pub fn load_env(&mut self) -> error::Result {
+        for (key, value) in std::env::vars_os() {
+            let Some(key) = key.to_str() else {
+                continue;
+            };
+            if let Some(name) = key.strip_prefix(FEATURE_ENV_PREFIX) {
+                let (module, feature) = name
+                    .split_once("__")
+                    .ok_or_else(|| Error::new(format!("invalid feature variable `{key}`")))?;
+                let value = value
+                    .to_str()
+                    .ok_or_else(|| Error::new(format!("variable `{key}` is not valid unicode")))?;
+                let enabled = parse_toml_bool(value.trim())
+                    .map_err(|e| Error::new(format!("variable `{key}`: {e}")))?;
+                self.insert_feature(ConfigSource::Environment, module, feature, enabled)?;
+                continue;
+            }
+            let Some(name) = key.strip_prefix(PARAMETER_ENV_PREFIX) else {
+                continue;
+            };
+            let (module, parameter) = name
+                .split_once("__")
+                .ok_or_else(|| Error::new(format!("invalid parameter variable `{key}`")))?;
+            let value = value
+                .to_str()
+                .ok_or_else(|| Error::new(format!("variable `{key}` is not valid unicode")))?;
+            let value = parse_toml_value(value.trim())
+                .map_err(|e| Error::new(format!("variable `{key}`: {e}")))?;
+            self.insert(ConfigSource::Environment, module, parameter, value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Sets the value of a parameter, replacing the values from all other sources.
+    pub fn set(&mut self, module: &CStr, parameter: &CStr, value: ParameterValue) {
+        self.entries.insert(
+            (module.into(), parameter.into()),
+            (ConfigSource::Explicit, value),
+        );
+    }
+
+    /// Returns the configured value of a parameter, and its source.
+    pub fn get(&self, module: &CStr, parameter: &CStr) -> Option<(ConfigSource, ParameterValue)> {
+        self.entries
+            .get(&(CString::from(module), CString::from(parameter)))
+            .copied()
+    }
+
+    /// Returns an iterator over the module, parameter name, source and value of each entry.
+    ///
+    /// The entries are sorted by the module and parameter names.
+    pub fn iter(&self) -> impl Iterator<Item = (&CStr, &CStr, ConfigSource, ParameterValue)> + '_ {
+        self.entries
+            .iter()
+            .map(|((module, parameter), (source, value))| {
+                (module.as_c_str(), parameter.as_c_str(), *source, *value)
+            })
+    }
+
+    /// Enables or disables a feature, replacing the values from all other sources.
+    pub fn set_feature(&mut self, module: &CStr, feature: &CStr, enabled: bool) {
+        self.features.insert(
+            (module.into(), feature.into()),
+            (ConfigSource::Explicit, enabled),
+        );
+    }
+
+    /// Returns whether a feature is configured to be enabled, and the source of the value.
+    pub fn feature(&self, module: &CStr, feature: &CStr) -> Option<(ConfigSource, bool)> {
+        self.features
+            .get(&(CString::from(module), CString::from(feature)))
+            .copied()
+    }
+
+    /// Returns an iterator over the module, feature name, source and state of each feature.
+    ///
+    /// The features are sorted by the module and feature names.
+    pub fn features(&self) -> impl Iterator<Item = (&CStr, &CStr, ConfigSource, bool)> + '_ {
+        self.features
+            .iter()
+            .map(|((module, feature), (source, enabled))| {
+                (module.as_c_str(), feature.as_c_str(), *source, *enabled)
+            })
+    }
+
+    /// Excludes a module, or a single parameter of a module, from the persisted configuration.
+    ///
+    /// The excluded values, e.g., secrets or ephemeral values, are neither captured by
+    /// [`ParameterConfig::snapshot`], nor written by [`ParameterConfig::save`]. Passing `None` as
+    /// the `parameter` excludes all parameters of the module.
+    pub fn exclude(&mut self, module: &CStr, parameter: Option<&CStr>) {
+        self.excluded
+            .insert((module.into(), parameter.map(Into::into)));
+    }
+
+    /// Returns whether the value of a parameter is persisted.
+    pub fn is_persisted(&self, module: &CStr, parameter: &CStr) -> bool {
+        let module = CString::from(module);
+        !self.excluded.contains(&(module.clone(), None))
+            && !self.excluded.contains(&(module, Some(parameter.into())))
+    }
+
+    /// Captures the current values of the parameters of a loaded module.
+    ///
+    /// Only the parameters with public read access, which are not excluded from the persisted
+    /// configuration, are captured. The values replace the values from all other sources.
+    pub fn snapshot(&mut self, ctx: &impl ModuleSubsystem, module: &CStr) -> error::Result {
+        for parameter in ParameterInfo::list(ctx, module)?.iter() {
+            let name = parameter.name();
+            if parameter.info().read_access() != ParameterAccess::Public
+                || !self.is_persisted(module, name)
+            {
+                continue;
+            }
+            let value = ParameterValue::read_public(ctx, module, name)?;
+            self.set(module, name, value);
+        }
+        Ok(())
+    }
+
+    /// Returns the recorded version of the settings layout of a module.
+    pub fn version(&self, module: &CStr) -> Option<Version> {
+        self.versions.get(module).copied()
+    }
+
+    /// Upgrades the values of a module to the settings layout `version`.
+    ///
+    /// If the recorded version of the module is older than `version`, `f` is invoked with the
+    /// recorded version and the values of the module, which it may modify, add to, or remove
+    /// from. Added values are treated as if they were loaded from a file. Afterwards, `version`
+    /// is recorded as the version of the module. Modules without a recorded version are assumed
+    /// to already use the layout `version`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fimo_std::{module::{ParameterConfig, ParameterValue}, version::Version};
+    ///
+    /// let mut config = ParameterConfig::new();
+    /// config
+    ///     .load_toml("[\"$versions\"]\nmy_module = \"0.1.0\"\n[my_module]\nthreads = 4")
+    ///     .expect("could not load the configuration");
+    ///
+    /// // Version 0.2.0 renamed `threads` to `workers`.
+    /// config
+    ///     .migrate(c"my_module", Version::new(0, 2, 0), |_, values| {
+    ///         if let Some(value) = values.remove(c"threads") {
+    ///             values.insert(c"workers".into(), value);
+    ///         }
+    ///         Ok(())
+    ///     })
+    ///     .expect("could not migrate the configuration");
+    ///
+    /// assert_eq!(config.version(c"my_module"), Some(Version::new(0, 2, 0)));
+    /// assert!(config.get(c"my_module", c"threads").is_none());
+    /// assert_eq!(
+    ///     config.get(c"my_module", c"workers").map(|(_, x)| x),
+    ///     Some(ParameterValue::U64(4))
+    /// );
+    /// ```
+    pub fn migrate(
+        &mut self,
+        module: &CStr,
+        version: Version,
+        f: impl FnOnce(Version, &mut BTreeMap<CString, ParameterValue>) -> error::Result,
+    ) -> error::Result {
+        let module = CString::from(module);
+        if let Some(recorded) = self.versions.get(&module).copied() {
+            if recorded < version {
+                let keys = self
+                    .entries
+                    .keys()
+                    .filter(|(m, _)| *m == module)
+                    .cloned()
+                    .collect::<Vec<_>>();
+                let mut sources = BTreeMap::new();
+                let mut values = BTreeMap::new();
+                for key in keys {
+                    let (source, value) = self.entries.remove(&key).unwrap();
+                    sources.insert(key.1.clone(), source);
+                    values.insert(key.1, value);
+                }
+
+                let result = f(recorded, &mut values);
+                for (parameter, value) in values {
+                    let source = sources.get(&parameter).copied();
+                    let source = source.unwrap_or(ConfigSource::File);
+                    self.entries
+                        .insert((module.clone(), parameter), (source, value));
+                }
+                result?;
+            }
+        }
+        self.versions.insert(module, version);
+        Ok(())
+    }
+
+    /// Writes the persisted values to the file at `path`.
+    ///
+    /// The format is chosen by the extension of the file, like with
+    /// [`ParameterConfig::load_file`]. The file is replaced atomically, i.e., the configuration
+    /// is first written to a temporary file next to it, which is then renamed to `path`. The
+    /// excluded values are skipped.
+    pub fn save(&self, path: impl AsRef<Path>) -> error::Result {
+        let path = path.as_ref();
+        let mut persisted = self.clone();
+        persisted
+            .entries
+            .retain(|(module, parameter), _| self.is_persisted(module, parameter));
+        persisted.excluded.clear();
+
+        let contents = if path.extension().is_some_and(|x| x == "json") {
+            persisted.to_json()
+        } else {
+            persisted.to_string()
+        };
+
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        let temp = PathBuf::from(temp);
+        let result = std::fs::File::create(&temp)
+            .and_then(|mut file| {
+                file.write_all(contents.as_bytes())?;
+                file.sync_all()
+            })
+            .and_then(|_| std::fs::rename(&temp, path));
+        if result.is_err() {
+            let _ = std::fs::remove_file(&temp);
+        }
+        result.map_err(Error::new)
+    }
+
+    /// Registers the values as the initial values of the parameters, and as the overrides of
+    /// the features.
+    ///
+    /// The values only apply to the modules loaded after this call. Parameters and features,
+    /// which are not declared by their module, are ignored.
+    pub fn apply(&self, ctx: &impl ModuleSubsystem) -> error::Result {
+        for (module, parameter, _, value) in self.iter() {
+            ParameterValue::set_override(Some(value), ctx, module, parameter)?;
+        }
+        for (module, feature, _, enabled) in self.features() {
+            ctx.set_feature_override(module, feature, Some(enabled))?;
+        }
+        Ok(())
+    }
+
+    fn insert_version(&mut self, module: &str, version: Version) -> error::Result {
+        let module = CString::new(module).map_err(Error::new)?;
+        self.versions.insert(module, version);
+        Ok(())
+    }
+
+    fn insert_feature(
+        &mut self,
+        source: ConfigSource,
+        module: &str,
+        feature: &str,
+        enabled: bool,
+    ) -> error::Result {
+        let module = CString::new(module).map_err(Error::new)?;
+        let feature = CString::new(feature).map_err(Error::new)?;
+        match self.features.get(&(module.clone(), feature.clone())) {
+            Some((old, _)) if *old > source => {}
+            _ => {
+                self.features.insert((module, feature), (source, enabled));
+            }
+        }
+        Ok(())
+    }
+
+    fn to_json(&self) -> String {
+        let mut out = String::from("{");
+        let mut first = true;
+        let mut separator = |out: &mut String| {
+            out.push_str(if first { "\n  " } else { ",\n  " });
+            first = false;
+        };
+
+        if !self.versions.is_empty() {
+            separator(&mut out);
+            out.push_str(&format_json_string(VERSIONS_TABLE.as_bytes()));
+            out.push_str(": {");
+            for (i, (module, version)) in self.versions.iter().enumerate() {
+                if i != 0 {
+                    out.push(',');
+                }
+                out.push_str("\n    ");
+                out.push_str(&format_json_string(module.to_bytes()));
+                out.push_str(&format!(": \"{}\"", format_version(version)));
+            }
+            out.push_str("\n  }");
+        }
+
+        if !self.features.is_empty() {
+            separator(&mut out);
+            out.push_str(&format_json_string(FEATURES_TABLE.as_bytes()));
+            out.push_str(": {");
+            let mut current: Option<&CStr> = None;
+            for (module, feature, _, enabled) in self.features() {
+                if current != Some(module) {
+                    if current.is_some() {
+                        out.push_str("\n    },");
+                    }
+                    out.push_str("\n    ");
+                    out.push_str(&format_json_string(module.to_bytes()));
+                    out.push_str(": {");
+                    current = Some(module);
+                } else {
+                    out.push(',');
+                }
+                out.push_str("\n      ");
+                out.push_str(&format_json_string(feature.to_bytes()));
+                out.push_str(&format!(": {enabled}"));
+            }
+            out.push_str("\n    }\n  }");
+        }
+
+        let mut current: Option<&CStr> = None;
+        for (module, parameter, _, value) in self.iter() {
+            if current != Some(module) {
+                if current.is_some() {
+                    out.push_str("\n  }");
+                }
+                separator(&mut out);
+                out.push_str(&format_json_string(module.to_bytes()));
+                out.push_str(": {");
+                current = Some(module);
+            } else {
+                out.push(',');
+            }
+            out.push_str("\n    ");
+            out.push_str(&format_json_string(parameter.to_bytes()));
+            out.push_str(&format!(": {value}"));
+        }
+        if current.is_some() {
+            out.push_str("\n  }");
+        }
+
+        out.push_str("\n}\n");
+        out
+    }
+
+    fn insert(
+        &mut self,
+        source: ConfigSource,
+        module: &str,
+        parameter: &str,
+        value: ParameterValue,
+    ) -> error::Result {
+        let module = CString::new(module).map_err(Error::new)?;
+        let parameter = CString::new(parameter).map_err(Error::new)?;
+        match self.entries.get(&(module.clone(), parameter.clone())) {
+            Some((old, _)) if *old > source => {}
+            _ => {
+                self.entries.insert((module, parameter), (source, value));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl core::fmt::Display for ParameterConfig {
+    /// Formats the configuration as TOML, annotated with the source of each value.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if !self.versions.is_empty() {
+            writeln!(f, "[\"{VERSIONS_TABLE}\"]")?;
+            for (module, version) in &self.versions {
+                writeln!(
+                    f,
+                    "{} = \"{}\"",
+                    format_toml_key(module),
+                    format_version(version)
+                )?;
+            }
+        }
+
+        if !self.features.is_empty() {
+            if !self.versions.is_empty() {
+                f.write_char('\n')?;
+            }
+            writeln!(f, "[\"{FEATURES_TABLE}\"]")?;
+            for (module, feature, source, enabled) in self.features() {
+                writeln!(
+                    f,
+                    "{}.{} = {enabled} # {source}",
+                    format_toml_key(module),
+                    format_toml_key(feature)
+                )?;
+            }
+        }
+
+        let mut current: Option<&CStr> = None;
+        for (module, parameter, source, value) in self.iter() {
+            if current != Some(module) {
+                if current.is_some() || !self.versions.is_empty() || !self.features.is_empty() {
+                    f.write_char('\n')?;
+                }
+                writeln!(f, "[{}]", format_toml_key(module))?;
+                current = Some(module);
+            }
+
+            let value = match value {
+                ParameterValue::U8(x) => x.to_string(),
+                ParameterValue::U16(x) => x.to_string(),
+                ParameterValue::U32(x) => x.to_string(),
+                ParameterValue::U64(x) => x.to_string(),
+                ParameterValue::I8(x) => x.to_string(),
+                ParameterValue::I16(x) => x.to_string(),
+                ParameterValue::I32(x) => x.to_string(),
+                ParameterValue::I64(x) => x.to_string(),
+            };
+            writeln!(f, "{} = {value} # {source}", format_toml_key(parameter))?;
+        }
+        Ok(())
+    }
+}
+
+fn integer_to_value(value: i128) -> Result<ParameterValue, String> {
+    if value < 0 {
+        i64::try_from(value)
+            .map(ParameterValue::I64)
+            .map_err(|e| format!("integer `{value}` is out of range: {e}"))
+    } else {
+        u64::try_from(value)
+            .map(ParameterValue::U64)
+            .map_err(|e| format!("integer `{value}` is out of range: {e}"))
+    }
+}
+
+fn format_toml_key(key: &CStr) -> String {
+    let key = key.to_string_lossy();
+    if !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        key.into_owned()
+    } else {
+        format!("{key:?}")
+    }
+}
+
+fn format_version(version: &Version) -> String {
+    // Omit the build number, unless it is set.
+    if version.0.build == 0 {
+        version.to_version_string().as_str().into()
+    } else {
+        version.to_version_string_long().as_str().into()
+    }
+}
+
+fn format_json_string(value: &[u8]) -> String {
+    let mut out = String::from("\"");
+    for c in String::from_utf8_lossy(value).chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn strip_toml_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn parse_toml_key(key: &str) -> Result<Vec<String>, String> {
+    let mut keys = Vec::new();
+    let mut rest = key.trim();
+    loop {
+        let part;
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let end = quoted
+                .find('"')
+                .ok_or_else(|| format!("unterminated key `{key}`"))?;
+            part = &quoted[..end];
+            rest = quoted[end + 1..].trim_start();
+        } else {
+            let end = rest.find('.').unwrap_or(rest.len());
+            part = rest[..end].trim();
+            rest = &rest[end..];
+            if part.is_empty()
+                || !part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                return Err(format!("invalid key `{key}`"));
+            }
+        }
+        keys.push(part.into());
+
+        match rest.strip_prefix('.') {
+            Some(x) => rest = x.trim_start(),
+            None if rest.is_empty() => return Ok(keys),
+            None => return Err(format!("invalid key `{key}`")),
+        }
+    }
+}
+
+fn parse_toml_bool(value: &str) -> Result<bool, String> {
+    match value {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        _ => Err(format!("expected a boolean value, found `{value}`")),
+    }
+}
+
+fn parse_toml_value(value: &str) -> Result<ParameterValue, String> {
+    match value {
+        "true" => return Ok(ParameterValue::U8(1)),
+        "false" => return Ok(ParameterValue::U8(0)),
+        _ => {}
+    }
+
+    let (negative, digits) = match value.as_bytes().first() {
+        Some(b'-') => (true, &value[1..]),
+        Some(b'+') => (false, &value[1..]),
+        _ => (false, value),
+    };
+    let (radix, digits) = match digits.get(..2) {
+        Some("0x") => (16, &digits[2..]),
+        Some("0o") => (8, &digits[2..]),
+        Some("0b") => (2, &digits[2..]),
+        _ => (10, digits),
+    };
+    if digits.is_empty() || digits.starts_with('_') || digits.ends_with('_') {
+        return Err(format!("invalid value `{value}`"));
+    }
+
+    let digits: String = digits.chars().filter(|&c| c != '_').collect();
+    let magnitude =
+        u64::from_str_radix(&digits, radix).map_err(|e| format!("invalid value `{value}`: {e}"))?;
+    let value = if negative {
+        -i128::from(magnitude)
+    } else {
+        i128::from(magnitude)
+    };
+    integer_to_value(value)
+}
+
+enum JsonValue {
+    Object(Vec<(String, JsonValue)>),
+    String(String),
+    Integer(i128),
+    Bool(bool),
+}
+
+impl JsonValue {
+    fn into_parameter_value(self) -> Result<ParameterValue, String> {
+        match self {
+            JsonValue::Object(_) | JsonValue::String(_) => {
+                Err("expected an integer or boolean value".into())
+            }
+            JsonValue::Integer(x) => integer_to_value(x),
+            JsonValue::Bool(x) => Ok(ParameterValue::U8(x.into())),
+        }
+    }
+}
+
+struct JsonParser<'a> {
+    source: &'a [u8],
+    pos: usize,
+}
+
+impl JsonParser<'_> {
+    fn parse_document(&mut self) -> Result<JsonValue, String> {
+        let value = self.parse_value()?;
+        self.skip_whitespace();
+        if self.pos != self.source.len() {
+            return Err(self.error("trailing characters"));
+        }
+        Ok(value)
+    }
+
+    fn error(&self, msg: &str) -> String {
+        format!("offset {}: {msg}", self.pos)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.source.get(self.pos) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), String> {
+        self.skip_whitespace();
+        if self.source.get(self.pos) != Some(&c) {
+            return Err(self.error(&format!("expected `{}`", c as char)));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn parse_value(&mut self) -> Result<JsonValue, String> {
+        self.skip_whitespace();
+        let rest = &self.source[self.pos..];
+        match rest.first() {
+            Some(b'{') => self.parse_object(),
+            Some(b'"') => self.parse_string().map(JsonValue::String),
+            Some(b'-' | b'0'..=b'9') => self.parse_integer(),
+            _ if rest.starts_with(b"true") => {
+                self.pos += 4;
+                Ok(JsonValue::Bool(true))
+            }
+            _ if rest.starts_with(b"false") => {
+                self.pos += 5;
+                Ok(JsonValue::Bool(false))
+            }
+            _ => Err(self.error("expected an object, string, integer or boolean")),
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<JsonValue, String> {
+        self.expect(b'{')?;
+        let mut entries = Vec::new();
+        self.skip_whitespace();
+        if self.source.get(self.pos) == Some(&b'}') {
+            self.pos += 1;
+            return Ok(JsonValue::Object(entries));
+        }
+
+        loop {
+            let key = self.parse_string()?;
+            self.expect(b':')?;
+            let value = self.parse_value()?;
+            entries.push((key, value));
+
+            self.skip_whitespace();
+            match self.source.get(self.pos) {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Object(entries));
+                }
+                _ => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        self.expect(b'"')?;
+        let mut bytes = Vec::new();
+        loop {
+            let Some(&c) = self.source.get(self.pos) else {
+                return Err(self.error("unterminated string"));
+            };
+            self.pos += 1;
+            match c {
+                b'"' => break,
+                b'\\' => {
+                    let escaped = match self.source.get(self.pos) {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            let code = self
+                                .source
+                                .get(self.pos + 1..self.pos + 5)
+                                .and_then(|x| core::str::from_utf8(x).ok())
+                                .and_then(|x| u32::from_str_radix(x, 16).ok())
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| self.error("invalid unicode escape"))?;
+                            self.pos += 4;
+                            code
+                        }
+                        _ => return Err(self.error("invalid escape sequence")),
+                    };
+                    self.pos += 1;
+                    let mut buffer = [0; 4];
+                    bytes.extend_from_slice(escaped.encode_utf8(&mut buffer).as_bytes());
+                }
+                c => bytes.push(c),
+            }
+        }
+        String::from_utf8(bytes)
+            .map_err(|e| self.error(&format!("string is not valid unicode: {e}")))
+    }
+
+    fn parse_integer(&mut self) -> Result<JsonValue, String> {
+        let start = self.pos;
+        if self.source.get(self.pos) == Some(&b'-') {
+            self.pos += 1;
+        }
+        while let Some(b'0'..=b'9') = self.source.get(self.pos) {
+            self.pos += 1;
+        }
+        if let Some(b'.' | b'e' | b'E') = self.source.get(self.pos) {
+            return Err(self.error("expected an integer"));
+        }
+
+        core::str::from_utf8(&self.source[start..self.pos])
+            .ok()
+            .and_then(|x| x.parse::<i128>().ok())
+            .map(JsonValue::Integer)
+            .ok_or_else(|| self.error("invalid integer"))
+    }
+}
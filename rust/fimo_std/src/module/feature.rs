@@ -0,0 +1,253 @@
+use core::ffi::CStr;
+
+use crate::{bindings, error::Error, ffi::FFITransferable, version::Version};
+
+/// Stability of a module feature.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FeatureStability {
+    /// The feature is stable.
+    #[default]
+    Stable,
+    /// The feature may change or be removed in future versions of the module.
+    Experimental,
+    /// The feature will be removed in a future version of the module.
+    Deprecated,
+}
+
+impl core::fmt::Display for FeatureStability {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FeatureStability::Stable => write!(f, "Stable"),
+            FeatureStability::Experimental => write!(f, "Experimental"),
+            FeatureStability::Deprecated => write!(f, "Deprecated"),
+        }
+    }
+}
+
+impl TryFrom<bindings::FimoModuleFeatureStability> for FeatureStability {
+    type Error = Error;
+
+    fn try_from(value: bindings::FimoModuleFeatureStability) -> Result<Self, Self::Error> {
+        match value {
+            bindings::FimoModuleFeatureStability::FIMO_MODULE_FEATURE_STABILITY_STABLE => {
+                Ok(FeatureStability::Stable)
+            }
+            bindings::FimoModuleFeatureStability::FIMO_MODULE_FEATURE_STABILITY_EXPERIMENTAL => {
+                Ok(FeatureStability::Experimental)
+            }
+            bindings::FimoModuleFeatureStability::FIMO_MODULE_FEATURE_STABILITY_DEPRECATED => {
+                Ok(FeatureStability::Deprecated)
+            }
+            _ => Err(Error::EINVAL),
+        }
+    }
+}
+
+impl From<FeatureStability> for bindings::FimoModuleFeatureStability {
+    fn from(value: FeatureStability) -> Self {
+        match value {
+            FeatureStability::Stable => Self::FIMO_MODULE_FEATURE_STABILITY_STABLE,
+            FeatureStability::Experimental => Self::FIMO_MODULE_FEATURE_STABILITY_EXPERIMENTAL,
+            FeatureStability::Deprecated => Self::FIMO_MODULE_FEATURE_STABILITY_DEPRECATED,
+        }
+    }
+}
+
+impl FFITransferable<bindings::FimoModuleFeatureStability> for FeatureStability {
+    fn into_ffi(self) -> bindings::FimoModuleFeatureStability {
+        self.into()
+    }
+
+    unsafe fn from_ffi(ffi: bindings::FimoModuleFeatureStability) -> Self {
+        ffi.try_into().expect("expected known enum value")
+    }
+}
+
+/// Declaration of a module feature.
+#[repr(transparent)]
+pub struct FeatureDeclaration(bindings::FimoModuleExportModifierFeature);
+
+impl FeatureDeclaration {
+    /// Fetches the name of the feature.
+    pub fn name(&self) -> &CStr {
+        // Safety: The name is a valid string.
+        unsafe { CStr::from_ptr(self.0.name) }
+    }
+
+    /// Fetches the stability of the feature.
+    pub fn stability(&self) -> FeatureStability {
+        self.0
+            .stability
+            .try_into()
+            .expect("expected known enum value")
+    }
+
+    /// Returns whether the feature is enabled by default.
+    pub fn default_enabled(&self) -> bool {
+        self.0.default_enabled
+    }
+
+    /// Fetches the minimum version of the context required by the feature.
+    pub fn required_version(&self) -> Version {
+        // Safety: The version is valid.
+        unsafe { Version::from_ffi(self.0.required_version) }
+    }
+}
+
+// Safety: `FimoModuleExportModifierFeature` is always `Send + Sync`.
+unsafe impl Send for FeatureDeclaration {}
+
+// Safety: `FimoModuleExportModifierFeature` is always `Send + Sync`.
+unsafe impl Sync for FeatureDeclaration {}
+
+impl core::fmt::Debug for FeatureDeclaration {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FeatureDeclaration")
+            .field("name", &self.name())
+            .field("stability", &self.stability())
+            .field("default_enabled", &self.default_enabled())
+            .field("required_version", &self.required_version())
+            .finish()
+    }
+}
+
+impl core::fmt::Display for FeatureDeclaration {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "feature: {:?}, stability: {}, default: {}, requires: {}",
+            self.name(),
+            self.stability(),
+            self.default_enabled(),
+            self.required_version()
+        )
+    }
+}
+
+/// Name and resolved state of a feature of a loaded module.
+#[repr(transparent)]
+pub struct FeatureEntry(bindings::FimoModuleFeatureInfo);
+
+impl FeatureEntry {
+    /// Name of the feature.
+    pub fn name(&self) -> &CStr {
+        // Safety: The name is a valid string.
+        unsafe { CStr::from_ptr(self.0.name) }
+    }
+
+    /// Fetches the stability of the feature.
+    pub fn stability(&self) -> FeatureStability {
+        self.0
+            .stability
+            .try_into()
+            .expect("invalid feature stability")
+    }
+
+    /// Returns whether the feature is enabled by default.
+    pub fn default_enabled(&self) -> bool {
+        self.0.default_enabled
+    }
+
+    /// Returns whether the context satisfies the version required by the feature.
+    pub fn is_available(&self) -> bool {
+        self.0.available
+    }
+
+    /// Returns whether the feature is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.0.enabled
+    }
+}
+
+// Safety: The name is owned by the allocation containing the `FeatureEntry`.
+unsafe impl Send for FeatureEntry {}
+
+// Safety: The name is owned by the allocation containing the `FeatureEntry`.
+unsafe impl Sync for FeatureEntry {}
+
+impl core::fmt::Debug for FeatureEntry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FeatureEntry")
+            .field("name", &self.name())
+            .field("stability", &self.stability())
+            .field("default_enabled", &self.default_enabled())
+            .field("available", &self.is_available())
+            .field("enabled", &self.is_enabled())
+            .finish()
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! export_module_private_features {
+    (ptr { $($block:tt)* }) => {{
+        const X: &[$crate::bindings::FimoModuleExportModifier] =
+            $crate::export_module_private_features!($($block)*);
+        if X.is_empty() {
+            (core::ptr::null(), 0)
+        } else {
+            (X.as_ptr(), X.len() as u32)
+        }
+    }};
+    (stability) => {
+        $crate::bindings::FimoModuleFeatureStability::FIMO_MODULE_FEATURE_STABILITY_STABLE
+    };
+    (stability stable) => {
+        $crate::bindings::FimoModuleFeatureStability::FIMO_MODULE_FEATURE_STABILITY_STABLE
+    };
+    (stability experimental) => {
+        $crate::bindings::FimoModuleFeatureStability::FIMO_MODULE_FEATURE_STABILITY_EXPERIMENTAL
+    };
+    (stability deprecated) => {
+        $crate::bindings::FimoModuleFeatureStability::FIMO_MODULE_FEATURE_STABILITY_DEPRECATED
+    };
+    (requires) => {
+        $crate::bindings::FimoVersion {
+            major: $crate::bindings::FIMO_VERSION_MAJOR,
+            minor: $crate::bindings::FIMO_VERSION_MINOR,
+            patch: 0,
+            build: 0,
+        }
+    };
+    (requires $major:literal, $minor:literal, $patch:literal) => {
+        $crate::bindings::FimoVersion {
+            major: $major,
+            minor: $minor,
+            patch: $patch,
+            build: 0,
+        }
+    };
+    ($(
+        $name:ident: {
+            default: $default:literal
+            $(, stability: $stability:ident)?
+            $(, requires: ($major:literal, $minor:literal, $patch:literal))?
+            $(,)?
+        }
+    ),* $(,)?) => {
+        &[
+            $(
+                $crate::bindings::FimoModuleExportModifier {
+                    key: $crate::bindings::FimoModuleExportModifierKey::FIMO_MODULE_EXPORT_MODIFIER_KEY_FEATURE,
+                    value: {
+                        const FEATURE: $crate::bindings::FimoModuleExportModifierFeature =
+                            $crate::bindings::FimoModuleExportModifierFeature {
+                                name: {
+                                    let x: &'static str = core::concat!(core::stringify!($name), '\0');
+                                    x.as_ptr().cast()
+                                },
+                                stability: $crate::export_module_private_features!(
+                                    stability $($stability)?
+                                ),
+                                default_enabled: $default,
+                                required_version: $crate::export_module_private_features!(
+                                    requires $($major, $minor, $patch)?
+                                ),
+                            };
+                        core::ptr::from_ref(&FEATURE).cast()
+                    },
+                }
+            ),*
+        ]
+    };
+}
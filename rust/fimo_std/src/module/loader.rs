@@ -0,0 +1,162 @@
+use alloc::{boxed::Box, ffi::CString};
+use core::ffi::{c_void, CStr};
+
+use crate::{
+    bindings,
+    context::ContextView,
+    error::{to_result_indirect, Error},
+    ffi::{FFISharable, FFITransferable},
+};
+
+use super::{ModuleExport, ModuleSubsystem};
+
+/// Loader for module binaries of a custom format.
+///
+/// Loaders allow loading modules from artifacts that can not be opened by the native loader,
+/// e.g., modules compiled to an intermediate representation, or modules described by scripts. A
+/// loader is registered with [`ModuleSubsystem::register_loader`], and is selected by
+/// [`LoadingSet::append_modules`](super::LoadingSet::append_modules), if its pattern matches the
+/// file name of the binary. The pattern may contain the wildcards `*`, matching any sequence of
+/// characters, and `?`, matching a single character.
+pub trait ModuleLoader: Send + Sync + 'static {
+    /// Handle to an open binary.
+    ///
+    /// The binary is closed by dropping the handle, once no module of the binary is loaded.
+    type Binary: Send + Sync + 'static;
+
+    /// Opens the binary at `path`.
+    ///
+    /// If the signature of the binary was verified, `path` refers to the verified contents, and
+    /// may differ from the path of the binary.
+    fn load(&self, path: &CStr) -> Result<Self::Binary, Error>;
+
+    /// Returns the modules exported by an open binary.
+    ///
+    /// The exports are borrowed from the binary, and remain in use until it is closed.
+    fn exports<'a>(&self, binary: &'a Self::Binary) -> impl Iterator<Item = ModuleExport<'a>>;
+}
+
+pub(super) fn register_loader<'a, L: ModuleLoader>(
+    ctx: &'a impl ModuleSubsystem,
+    name: &CStr,
+    pattern: &CStr,
+    loader: L,
+) -> Result<LoaderRegistration<'a>, Error> {
+    unsafe extern "C" fn load<L: ModuleLoader>(
+        data: *mut c_void,
+        path: *const core::ffi::c_char,
+        binary: *mut *mut c_void,
+    ) -> bindings::FimoResult {
+        let result = crate::panic::catch_unwind(|| {
+            // Safety: The loader only invokes the function with valid paths.
+            let (loader, path) = unsafe { (&*data.cast_const().cast::<L>(), CStr::from_ptr(path)) };
+            loader.load(path)
+        });
+        match result {
+            Ok(Ok(x)) => {
+                // Safety: `binary` is valid for writes.
+                unsafe { core::ptr::write(binary, Box::into_raw(Box::new(x)).cast()) };
+                Result::<_, Error>::Ok(()).into_ffi()
+            }
+            Ok(Err(e)) => e.into_error(),
+            Err(e) => e.into_error(),
+        }
+    }
+
+    unsafe extern "C" fn iterate_exports<L: ModuleLoader>(
+        data: *mut c_void,
+        binary: *mut c_void,
+        f: Option<unsafe extern "C" fn(*const bindings::FimoModuleExport, *mut c_void) -> bool>,
+        f_data: *mut c_void,
+    ) {
+        crate::panic::abort_on_panic(|| {
+            // Safety: The binary was opened by the same loader.
+            let (loader, binary) = unsafe {
+                (
+                    &*data.cast_const().cast::<L>(),
+                    &*binary.cast_const().cast::<L::Binary>(),
+                )
+            };
+            let f = f.expect("the export inspector must not be null");
+            for export in loader.exports(binary) {
+                // Safety: The export is valid until the binary is closed.
+                if unsafe { !f(export.share_to_ffi(), f_data) } {
+                    break;
+                }
+            }
+        });
+    }
+
+    unsafe extern "C" fn unload<L: ModuleLoader>(_data: *mut c_void, binary: *mut c_void) {
+        // Safety: The function is invoked once, after the last use of the binary.
+        drop(unsafe { Box::from_raw(binary.cast::<L::Binary>()) });
+    }
+
+    unsafe extern "C" fn on_drop<L>(data: *mut c_void) {
+        // Safety: The function is invoked once, after the last binary has been closed.
+        drop(unsafe { Box::from_raw(data.cast::<L>()) });
+    }
+
+    let data = Box::into_raw(Box::new(loader));
+    let ffi_loader = bindings::FimoModuleLoader {
+        next: core::ptr::null(),
+        name: name.as_ptr(),
+        pattern: pattern.as_ptr(),
+        data: data.cast(),
+        load: Some(load::<L>),
+        iterate_exports: Some(iterate_exports::<L>),
+        unload: Some(unload::<L>),
+        on_drop: Some(on_drop::<L>),
+    };
+
+    // Safety: FFI call is safe.
+    let result = unsafe {
+        to_result_indirect(|error| {
+            *error = bindings::fimo_module_loader_register(ctx.share_to_ffi(), &ffi_loader);
+        })
+    };
+    match result {
+        Ok(_) => Ok(LoaderRegistration {
+            // Safety: The view is only used while `ctx` is borrowed.
+            context: unsafe { ContextView::from_ffi(ctx.share_to_ffi()) },
+            name: name.into(),
+        }),
+        Err(e) => {
+            // Safety: The loader is not used on failure, so we still own the data.
+            drop(unsafe { Box::from_raw(data) });
+            Err(e)
+        }
+    }
+}
+
+/// RAII guard of a registered [`ModuleLoader`].
+///
+/// Dropping the guard unregisters the loader. Binaries that are already open keep the loader
+/// alive, until they are closed.
+#[derive(Debug)]
+pub struct LoaderRegistration<'a> {
+    context: ContextView<'a>,
+    name: CString,
+}
+
+impl LoaderRegistration<'_> {
+    /// Returns the name of the loader.
+    pub fn name(&self) -> &CStr {
+        &self.name
+    }
+}
+
+impl Drop for LoaderRegistration<'_> {
+    fn drop(&mut self) {
+        // Safety: FFI call is safe.
+        let result = unsafe {
+            to_result_indirect(|error| {
+                *error = bindings::fimo_module_loader_unregister(
+                    self.context.share_to_ffi(),
+                    self.name.as_ptr(),
+                );
+            })
+        };
+        result.expect("could not unregister the loader");
+    }
+}
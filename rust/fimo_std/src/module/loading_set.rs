@@ -30,6 +30,29 @@ pub enum LoadingSetRequest {
     Dismiss,
 }
 
+/// Policy for handling modules that fail to be constructed.
+///
+/// Each failure is published on the bus with the
+/// [`ModuleConstructionFailed`](crate::bus::ModuleConstructionFailed) topic. The modules depending
+/// on a failed module are never loaded.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FaultPolicy {
+    /// The remaining modules of the loading set continue to be loaded.
+    #[default]
+    Continue,
+    /// The remaining modules of the loading set are skipped.
+    Abort,
+}
+
+impl From<FaultPolicy> for bindings::FimoModuleFaultPolicy {
+    fn from(value: FaultPolicy) -> Self {
+        match value {
+            FaultPolicy::Continue => Self::FIMO_MODULE_FAULT_POLICY_CONTINUE,
+            FaultPolicy::Abort => Self::FIMO_MODULE_FAULT_POLICY_ABORT,
+        }
+    }
+}
+
 /// A set of modules that should be loaded.
 #[repr(transparent)]
 #[derive(Debug, Clone, Copy)]
@@ -232,6 +255,14 @@ impl<'a> LoadingSet<'a> {
     /// an error, if it does not export any modules. The necessary symbols are set-up automatically,
     /// if the binary was linked with the fimo library. In case of an error, no modules are appended
     /// to the set.
+    ///
+    /// If the file name of the binary is matched by a loader registered with
+    /// [`ModuleSubsystem::register_loader`], the binary is opened with said loader instead of the
+    /// native loader. With the `static_modules` feature, the native loader only supports the
+    /// modules of the current binary, and passing a path not matched by a registered loader
+    /// results in an [`Error::ENOTSUP`]. Depending on the policy set with
+    /// [`ModuleSubsystem::set_signature_policy`], the signature of the binary is verified before
+    /// it is opened.
     pub fn append_modules<T>(
         &self,
         ctx: &impl ModuleSubsystem,
@@ -273,6 +304,39 @@ impl<'a> LoadingSet<'a> {
             })
         }
     }
+
+    /// Groups modules of the module set.
+    ///
+    /// Marks the modules `modules`, which must already be contained in the set, as members of the
+    /// module group `group`. The members of a group are loaded atomically, i.e., either all of
+    /// them are loaded, or none of them. If the construction of a member fails, the already
+    /// constructed members are unloaded again, and the callbacks of all members are invoked with
+    /// an error. The success callbacks of the members are deferred until the entire group is
+    /// constructed, and modules depending on a member are loaded after the entire group. The name
+    /// of a group must be unique among the groups of the set and of the loaded modules, and a
+    /// module may be a member of at most one group. Once loaded, the members of a group can only
+    /// be unloaded together, see [`ModuleInfo::unload`].
+    pub fn append_group(
+        &self,
+        ctx: &impl ModuleSubsystem,
+        group: &CStr,
+        modules: &[&CStr],
+    ) -> error::Result {
+        let modules = modules.iter().map(|x| x.as_ptr()).collect::<Vec<_>>();
+
+        // Safety: FFI call is safe.
+        unsafe {
+            to_result_indirect(|error| {
+                *error = bindings::fimo_module_set_append_group(
+                    ctx.share_to_ffi(),
+                    self.share_to_ffi(),
+                    group.as_ptr(),
+                    modules.as_ptr(),
+                    modules.len(),
+                );
+            })
+        }
+    }
 }
 
 // Safety: `FimoModuleLoadingSet` is always `Send + Sync`.
@@ -6,7 +6,7 @@ use crate::{
     version::Version,
 };
 
-use super::{ParameterAccess, ParameterType, ParameterValue};
+use super::{FeatureDeclaration, ParameterAccess, ParameterType, ParameterValue};
 
 /// Declaration of a module parameter.
 #[repr(transparent)]
@@ -104,6 +104,8 @@ pub struct ResourceDecl(bindings::FimoModuleResourceDecl);
 
 impl ResourceDecl {
     /// Fetches the path of the resource.
+    ///
+    /// The path is relative to the module directory, and may not escape it.
     pub fn path(&self) -> &CStr {
         // Safety: The value is always a valid string.
         unsafe { CStr::from_ptr(self.0.path) }
@@ -394,8 +396,13 @@ pub struct Modifier(bindings::FimoModuleExportModifier);
 impl Modifier {
     /// Fetches the value of the modifier.
     pub fn value(&self) -> ModifierValue<'_> {
-        #[allow(clippy::match_single_binding)]
         match self.0.key {
+            bindings::FimoModuleExportModifierKey::FIMO_MODULE_EXPORT_MODIFIER_KEY_FEATURE => {
+                // Safety: The value of a feature modifier is a `FimoModuleExportModifierFeature`,
+                // which has the same layout as a `FeatureDeclaration`.
+                let feature = unsafe { &*self.0.value.cast::<FeatureDeclaration>() };
+                ModifierValue::Feature(feature)
+            }
             _ => ModifierValue::Unknown(PhantomData),
         }
     }
@@ -433,12 +440,14 @@ impl FFITransferable<bindings::FimoModuleExportModifier> for Modifier {
 #[non_exhaustive]
 #[derive(Debug, Copy, Clone)]
 pub enum ModifierValue<'a> {
+    Feature(&'a FeatureDeclaration),
     Unknown(PhantomData<&'a ()>),
 }
 
 impl core::fmt::Display for ModifierValue<'_> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
+            ModifierValue::Feature(feature) => write!(f, "{feature}"),
             ModifierValue::Unknown(_) => {
                 write!(f, "Unknown modifier")
             }
@@ -455,6 +464,12 @@ impl ModuleExport<'_> {
     /// Export abi of the module.
     pub const EXPORT_ABI: i32 = bindings::FIMO_MODULE_EXPORT_ABI as i32;
 
+    /// Version of the context the module was built against.
+    pub fn context_version(&self) -> Version {
+        // Safety: The version is a plain value.
+        unsafe { Version::from_ffi(self.0.context_version) }
+    }
+
     /// Fetches the name of the module declaration.
     pub fn name(&self) -> &CStr {
         // Safety: The value is always a valid string.
@@ -591,6 +606,7 @@ impl core::fmt::Debug for ModuleExport<'_> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("ModuleExport")
             .field("export_abi", &Self::EXPORT_ABI)
+            .field("context_version", &self.context_version())
             .field("name", &self.name())
             .field("description", &self.description())
             .field("author", &self.author())
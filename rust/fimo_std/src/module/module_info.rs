@@ -5,16 +5,18 @@ use core::{
     mem::ManuallyDrop,
     ops::Deref,
 };
+use std::path::{Component, Path, PathBuf};
 
 use crate::{
     bindings,
     context::{Context, ContextView},
     error::{self, to_result, to_result_indirect, to_result_indirect_in_place, Error},
     ffi::{FFISharable, FFITransferable},
+    tracing::ModuleChannels,
     version::Version,
 };
 
-use super::{ModuleSubsystem, NamespaceItem, NoState, Symbol, SymbolItem};
+use super::{ModuleSubsystem, NamespaceItem, NoState, ScopedSymbol, Symbol, SymbolItem};
 
 /// View of a `ModuleInfo`.
 #[derive(Copy, Clone)]
@@ -50,6 +52,68 @@ impl ModuleInfoView<'_> {
         // Safety: The module path is a valid string.
         unsafe { CStr::from_ptr(self.0.module_path) }
     }
+
+    /// Name of the module group containing the module.
+    pub fn group(&self) -> Option<&CStr> {
+        // Safety: The string is valid or null.
+        unsafe { self.0.group.as_ref().map(|x| CStr::from_ptr(x)) }
+    }
+}
+
+impl<'a> ModuleInfoView<'a> {
+    /// Installation directory of the module.
+    ///
+    /// Equivalent to [`module_path`](Self::module_path), converted to a [`Path`].
+    pub fn data_dir(&self) -> &'a Path {
+        // Safety: The module path is a valid string.
+        let path = unsafe { CStr::from_ptr(self.0.module_path) };
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            Path::new(std::ffi::OsStr::from_bytes(path.to_bytes()))
+        }
+        #[cfg(not(unix))]
+        {
+            Path::new(path.to_str().expect("module paths are encoded as UTF-8"))
+        }
+    }
+
+    /// Resolves a path relative to the installation directory of the module.
+    ///
+    /// The path may only consist of normal components, i.e., absolute paths, prefixes and `..`
+    /// components are rejected with [`Error::EINVAL`]. If the resolved path exists, its symbolic
+    /// links are followed and the result must still be contained in the
+    /// [`data_dir`](Self::data_dir) of the module, otherwise [`Error::EACCES`] is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fimo_std::{error::Error, module::ModuleInfoView};
+    ///
+    /// fn load_shader(info: ModuleInfoView<'_>) -> Result<Vec<u8>, Error> {
+    ///     let path = info.resource_path("shaders/main.spv")?;
+    ///     std::fs::read(path).map_err(Error::new)
+    /// }
+    /// ```
+    pub fn resource_path(&self, relative: impl AsRef<Path>) -> Result<PathBuf, Error> {
+        let relative = relative.as_ref();
+        if !relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+        {
+            return Err(Error::EINVAL);
+        }
+
+        let root = self.data_dir();
+        let path = root.join(relative);
+        if let Ok(canonical) = path.canonicalize() {
+            let root = root.canonicalize().map_err(Error::new)?;
+            if !canonical.starts_with(root) {
+                return Err(Error::EACCES);
+            }
+        }
+        Ok(path)
+    }
 }
 
 impl ModuleInfoView<'_> {
@@ -100,7 +164,9 @@ impl ModuleInfoView<'_> {
     ///
     /// If successful, this function unloads the module. To succeed, the module no other module may
     /// depend on the module. This function automatically unloads cleans up unreferenced modules,
-    /// except if they are a pseudo module.
+    /// except if they are a pseudo module. If the module is a member of a module group, all
+    /// members of the group are unloaded together, in which case no module outside of the group
+    /// may depend on a member.
     pub fn unload(&self, ctx: &impl ModuleSubsystem) -> error::Result {
         // Safety: The ffi call is safe.
         unsafe {
@@ -175,6 +241,7 @@ impl core::fmt::Debug for ModuleInfoView<'_> {
             .field("author", &self.author())
             .field("license", &self.license())
             .field("module_path", &self.module_path())
+            .field("group", &self.group())
             .finish_non_exhaustive()
     }
 }
@@ -375,6 +442,46 @@ pub unsafe trait Module:
     /// Fetches the context of the module.
     fn context(&self) -> ContextView<'_>;
 
+    /// Fetches the namespace of the tracing channels of the module.
+    ///
+    /// See [`ModuleChannels`] for more details.
+    fn channels(&self) -> ModuleChannels<'_> {
+        ModuleChannels::new(self.context(), &self.module_info())
+    }
+
+    /// Installation directory of the module.
+    ///
+    /// See [`ModuleInfoView::data_dir`].
+    fn data_dir(&self) -> &Path {
+        self.module_info().data_dir()
+    }
+
+    /// Resolves a path relative to the installation directory of the module.
+    ///
+    /// See [`ModuleInfoView::resource_path`].
+    fn resource_path(&self, relative: impl AsRef<Path>) -> Result<PathBuf, Error> {
+        self.module_info().resource_path(relative)
+    }
+
+    /// Checks whether a feature of the module is enabled.
+    ///
+    /// The state of the features is resolved before the module is constructed, and does not
+    /// change while the module is loaded. It can therefore already be queried from the
+    /// [`PreModule`](super::PreModule) passed to the constructor. Fails if the module does not
+    /// declare the feature.
+    fn feature_enabled(&self, feature: &CStr) -> Result<bool, Error> {
+        // Safety: Either we get an error, or we initialize the value.
+        unsafe {
+            to_result_indirect_in_place(|error, enabled| {
+                *error = bindings::fimo_module_feature_enabled(
+                    self.share_to_ffi(),
+                    feature.as_ptr(),
+                    enabled.as_mut_ptr(),
+                );
+            })
+        }
+    }
+
     /// Fetches the data of the module.
     fn data(&self) -> &Self::Data;
 
@@ -440,6 +547,45 @@ pub unsafe trait Module:
         unsafe { self.load_symbol_unchecked(T::NAME, T::Namespace::NAME, T::VERSION) }
     }
 
+    /// Loads a symbol, acquiring the module exporting it as a dependency if required.
+    ///
+    /// Searches for the loaded module exporting a symbol compatible with the requested version,
+    /// acquires it as a dependency and includes the namespace of the symbol, unless the module
+    /// already depends on them, and then loads the symbol. See
+    /// [`declare_dependencies`](crate::declare_dependencies) for acquiring multiple symbols at
+    /// once.
+    fn acquire_symbol<T: SymbolItem>(&self) -> Result<Symbol<'_, T::Type>, Error> {
+        let namespace = T::Namespace::NAME;
+        let exporter = ModuleInfo::find_by_symbol(&self.context(), T::NAME, namespace, T::VERSION)?;
+        if self.has_dependency(&exporter)? == DependencyType::NoDependency {
+            self.acquire_dependency(&exporter)?;
+        }
+
+        if !namespace.is_empty()
+            && self.has_namespace_dependency(namespace)? == DependencyType::NoDependency
+        {
+            self.include_namespace(namespace)?;
+        }
+
+        self.load_symbol::<T>()
+    }
+
+    /// Acquires a symbol for the lifetime of the returned guard.
+    ///
+    /// Like [`Module::acquire_symbol`], but relinquishes the dependency and the namespace acquired
+    /// for the symbol once the guard is dropped. See [`ScopedSymbol`] for more info.
+    fn acquire_scoped_symbol<T: SymbolItem>(&self) -> Result<ScopedSymbol<'_, T::Type>, Error> {
+        ScopedSymbol::acquire::<Self, T>(self)
+    }
+
+    /// Invokes a closure with a symbol, releasing it afterward.
+    ///
+    /// The symbol is acquired with [`Module::acquire_scoped_symbol`] for the duration of the call.
+    fn with_symbol<T: SymbolItem, R>(&self, f: impl FnOnce(&T::Type) -> R) -> Result<R, Error> {
+        let symbol = self.acquire_scoped_symbol::<T>()?;
+        Ok(f(&symbol))
+    }
+
     /// Loads a symbol from the module subsystem.
     ///
     /// The caller can query the backend for a symbol of a loaded module. This is useful for loading
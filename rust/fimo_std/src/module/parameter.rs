@@ -1,8 +1,10 @@
+use alloc::boxed::Box;
 use core::{cell::UnsafeCell, ffi::CStr, marker::PhantomData, ops::Deref};
 
 use crate::{
+    allocator::FimoAllocator,
     bindings,
-    error::{self, to_result_indirect, Error},
+    error::{self, to_result_indirect, to_result_indirect_in_place, Error},
     ffi::{FFISharable, FFITransferable},
 };
 
@@ -395,6 +397,47 @@ impl ParameterValue {
         }
     }
 
+    /// Overrides the initial value of a module parameter.
+    ///
+    /// The parameter is initialized with `value` instead of its default value, when the module is
+    /// loaded after this call. The value is converted to the type of the parameter, and loading
+    /// the module fails, if it is not representable by that type. Passing `None` removes the
+    /// override.
+    pub fn set_override(
+        value: Option<Self>,
+        ctx: &impl ModuleSubsystem,
+        module: &CStr,
+        parameter: &CStr,
+    ) -> error::Result {
+        let (value, type_) = match value {
+            None => (None, ParameterType::U8),
+            Some(ParameterValue::U8(x)) => (Some(ValueTypes { u8_: x }), ParameterType::U8),
+            Some(ParameterValue::U16(x)) => (Some(ValueTypes { u16_: x }), ParameterType::U16),
+            Some(ParameterValue::U32(x)) => (Some(ValueTypes { u32_: x }), ParameterType::U32),
+            Some(ParameterValue::U64(x)) => (Some(ValueTypes { u64_: x }), ParameterType::U64),
+            Some(ParameterValue::I8(x)) => (Some(ValueTypes { i8_: x }), ParameterType::I8),
+            Some(ParameterValue::I16(x)) => (Some(ValueTypes { i16_: x }), ParameterType::I16),
+            Some(ParameterValue::I32(x)) => (Some(ValueTypes { i32_: x }), ParameterType::I32),
+            Some(ParameterValue::I64(x)) => (Some(ValueTypes { i64_: x }), ParameterType::I64),
+        };
+        let value = value
+            .as_ref()
+            .map_or(core::ptr::null(), |x| core::ptr::from_ref(x).cast());
+
+        // Safety: The ffi call is safe.
+        unsafe {
+            to_result_indirect(|error| {
+                *error = bindings::fimo_module_param_set_override(
+                    ctx.share_to_ffi(),
+                    module.as_ptr(),
+                    parameter.as_ptr(),
+                    value,
+                    type_.into_ffi(),
+                );
+            })
+        }
+    }
+
     /// Writes a module parameter.
     pub fn write_private(
         self,
@@ -510,6 +553,38 @@ impl ParameterInfo {
         Ok(Self { type_, read, write })
     }
 
+    /// Enumerates the parameters of a module.
+    pub fn list(
+        ctx: &impl ModuleSubsystem,
+        module: &CStr,
+    ) -> Result<Box<[ParameterEntry], FimoAllocator>, Error> {
+        let mut count = 0;
+        // Safety: Either we get an error, or we initialize the parameters.
+        let params = unsafe {
+            to_result_indirect_in_place(|error, params| {
+                *error = bindings::fimo_module_param_list(
+                    ctx.share_to_ffi(),
+                    module.as_ptr(),
+                    params.as_mut_ptr(),
+                    &mut count,
+                );
+            })
+        }?;
+
+        if params.is_null() {
+            return Ok(Box::new_in([], FimoAllocator));
+        }
+
+        // We can cast the pointer to an `ParameterEntry` pointer, since the two types have the
+        // same layout.
+        let params = params.cast::<ParameterEntry>();
+        let params = core::ptr::slice_from_raw_parts_mut(params, count);
+
+        // Safety: According to the API, the slice has been allocated with the fimo allocator,
+        // therefore we are allowed to construct a box with the same allocator.
+        unsafe { Ok(Box::from_raw_in(params, FimoAllocator)) }
+    }
+
     /// Fetches the type of the parameter.
     pub fn parameter_type(&self) -> ParameterType {
         self.type_
@@ -522,7 +597,43 @@ impl ParameterInfo {
 
     /// Fetches the access group specifier for the write permission.
     pub fn write_access(&self) -> ParameterAccess {
-        self.read
+        self.write
+    }
+}
+
+/// Name and info of a parameter of a module.
+#[repr(transparent)]
+pub struct ParameterEntry(bindings::FimoModuleParamInfo);
+
+impl ParameterEntry {
+    /// Name of the parameter.
+    pub fn name(&self) -> &CStr {
+        // Safety: The name is a valid string.
+        unsafe { CStr::from_ptr(self.0.name) }
+    }
+
+    /// Fetches the info of the parameter.
+    pub fn info(&self) -> ParameterInfo {
+        ParameterInfo {
+            type_: TryFrom::try_from(self.0.type_).expect("invalid parameter type"),
+            read: TryFrom::try_from(self.0.read_access).expect("invalid read access"),
+            write: TryFrom::try_from(self.0.write_access).expect("invalid write access"),
+        }
+    }
+}
+
+// Safety: The name is owned by the allocation containing the `ParameterEntry`.
+unsafe impl Send for ParameterEntry {}
+
+// Safety: The name is owned by the allocation containing the `ParameterEntry`.
+unsafe impl Sync for ParameterEntry {}
+
+impl core::fmt::Debug for ParameterEntry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ParameterEntry")
+            .field("name", &self.name())
+            .field("info", &self.info())
+            .finish()
     }
 }
 
@@ -0,0 +1,470 @@
+use alloc::{collections::BTreeMap, ffi::CString, vec::Vec};
+use core::{
+    ffi::CStr,
+    fmt::{Display, Formatter},
+};
+
+use crate::{error::Error, version::Version};
+
+use super::{
+    LoadingFilterRequest, LoadingSet, LoadingSetRequest, ModuleExport, ModuleInfo, ModuleSubsystem,
+};
+
+/// Symbol referenced by a [`LoadPlan`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedSymbol {
+    name: CString,
+    namespace: CString,
+    version: Version,
+}
+
+impl PlannedSymbol {
+    /// Returns the name of the symbol.
+    pub fn name(&self) -> &CStr {
+        &self.name
+    }
+
+    /// Returns the namespace of the symbol.
+    pub fn namespace(&self) -> &CStr {
+        &self.namespace
+    }
+
+    /// Returns the version of the symbol.
+    ///
+    /// For an imported symbol, this is the minimum version required by the importer.
+    pub fn version(&self) -> Version {
+        self.version
+    }
+}
+
+impl Display for PlannedSymbol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if self.namespace.is_empty() {
+            write!(f, "`{}`", self.name.to_string_lossy())
+        } else {
+            write!(
+                f,
+                "`{}::{}`",
+                self.namespace.to_string_lossy(),
+                self.name.to_string_lossy()
+            )
+        }
+    }
+}
+
+/// Module scheduled to be loaded by a [`LoadPlan`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedModule {
+    name: CString,
+    path: Option<CString>,
+    dependencies: Vec<CString>,
+    exports: Vec<PlannedSymbol>,
+}
+
+impl PlannedModule {
+    /// Returns the name of the module.
+    pub fn name(&self) -> &CStr {
+        &self.name
+    }
+
+    /// Returns the path of the binary exporting the module, or `None` for the current binary.
+    pub fn path(&self) -> Option<&CStr> {
+        self.path.as_deref()
+    }
+
+    /// Returns the names of the planned modules, which provide the imported symbols of the module.
+    ///
+    /// The dependencies on loaded modules are not listed.
+    pub fn dependencies(&self) -> impl Iterator<Item = &CStr> {
+        self.dependencies.iter().map(|x| &**x)
+    }
+
+    /// Returns the symbols exported by the module.
+    pub fn exports(&self) -> &[PlannedSymbol] {
+        &self.exports
+    }
+}
+
+/// Reason for a module of a [`LoadPlan`] not being loadable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Conflict {
+    /// A module with the same name is loaded, or has been appended before.
+    DuplicateModule { module: CString, loaded: bool },
+    /// A symbol exported by the module is already exported by another module.
+    DuplicateSymbol {
+        module: CString,
+        symbol: PlannedSymbol,
+        exporter: CString,
+    },
+    /// An imported symbol is not provided by any module.
+    ///
+    /// If a module exports the symbol with an incompatible version, it is listed as the
+    /// `candidate`.
+    MissingSymbol {
+        module: CString,
+        symbol: PlannedSymbol,
+        candidate: Option<(CString, Version)>,
+    },
+    /// An imported namespace does not contain any symbol.
+    MissingNamespace { module: CString, namespace: CString },
+    /// A module providing an imported symbol can not be loaded.
+    MissingDependency {
+        module: CString,
+        dependency: CString,
+    },
+    /// The modules depend on each other.
+    Cycle { modules: Vec<CString> },
+}
+
+impl Conflict {
+    /// Returns the modules affected by the conflict.
+    pub fn modules(&self) -> impl Iterator<Item = &CStr> {
+        let modules = match self {
+            Conflict::DuplicateModule { module, .. }
+            | Conflict::DuplicateSymbol { module, .. }
+            | Conflict::MissingSymbol { module, .. }
+            | Conflict::MissingNamespace { module, .. }
+            | Conflict::MissingDependency { module, .. } => core::slice::from_ref(module),
+            Conflict::Cycle { modules } => modules.as_slice(),
+        };
+        modules.iter().map(|x| &**x)
+    }
+}
+
+impl Display for Conflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Conflict::DuplicateModule { module, loaded } => {
+                let location = if *loaded { "loaded" } else { "appended" };
+                write!(
+                    f,
+                    "module `{}` is already {location}",
+                    module.to_string_lossy()
+                )
+            }
+            Conflict::DuplicateSymbol {
+                module,
+                symbol,
+                exporter,
+            } => write!(
+                f,
+                "module `{}` exports symbol {symbol}, which is already exported by module `{}`",
+                module.to_string_lossy(),
+                exporter.to_string_lossy()
+            ),
+            Conflict::MissingSymbol {
+                module,
+                symbol,
+                candidate,
+            } => {
+                write!(
+                    f,
+                    "module `{}` requires symbol {symbol} compatible with v{}",
+                    module.to_string_lossy(),
+                    symbol.version
+                )?;
+                match candidate {
+                    Some((exporter, version)) => write!(
+                        f,
+                        ", but module `{}` only provides v{version}",
+                        exporter.to_string_lossy()
+                    ),
+                    None => write!(f, ", but no module provides it"),
+                }
+            }
+            Conflict::MissingNamespace { module, namespace } => write!(
+                f,
+                "module `{}` imports namespace `{}`, which contains no symbols",
+                module.to_string_lossy(),
+                namespace.to_string_lossy()
+            ),
+            Conflict::MissingDependency { module, dependency } => write!(
+                f,
+                "module `{}` depends on module `{}`, which can not be loaded",
+                module.to_string_lossy(),
+                dependency.to_string_lossy()
+            ),
+            Conflict::Cycle { modules } => {
+                write!(f, "modules ")?;
+                for (i, module) in modules.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "`{}`", module.to_string_lossy())?;
+                }
+                write!(f, " depend on each other")
+            }
+        }
+    }
+}
+
+/// Result of a dry run of the loading of a set of modules.
+///
+/// The plan is computed by [`LoadPlan::new`] or [`ModuleSubsystem::plan_load`], without loading
+/// any modules, and consists of the modules that can be loaded, in the order they would be
+/// loaded, along with the conflicts which prevent the remaining modules from being loaded. The
+/// plan reflects the state of the module subsystem at the time it was computed.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::{
+///     context::Context,
+///     module::{LoadingFilterRequest, ModuleSubsystem},
+/// };
+///
+/// let context = Context::new().expect("could not create context");
+/// let plan = context
+///     .plan_load(&[None], |_| LoadingFilterRequest::Load)
+///     .expect("could not plan the loading");
+/// for conflict in plan.conflicts() {
+///     println!("{conflict}");
+/// }
+/// for module in plan.steps() {
+///     println!("load `{}`", module.name().to_string_lossy());
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadPlan {
+    steps: Vec<PlannedModule>,
+    conflicts: Vec<Conflict>,
+}
+
+impl LoadPlan {
+    /// Computes the plan for loading the modules exported by the binaries at `module_paths`.
+    ///
+    /// The binaries are opened like by [`LoadingSet::append_modules`], and the modules are
+    /// selected with the `filter`, but are never constructed. A path of `None` refers to the
+    /// current binary.
+    pub fn new<T>(
+        ctx: &impl ModuleSubsystem,
+        module_paths: &[Option<&CStr>],
+        mut filter: T,
+    ) -> Result<Self, Error>
+    where
+        T: FnMut(ModuleExport<'_>) -> LoadingFilterRequest,
+    {
+        let mut candidates = Vec::new();
+        LoadingSet::with_loading_set(ctx, |ctx, set| {
+            for &path in module_paths {
+                set.append_modules(ctx, path, |export| {
+                    if filter(export) == LoadingFilterRequest::Load {
+                        candidates.push(Candidate::new(path, export));
+                    }
+                    LoadingFilterRequest::Skip
+                })?;
+            }
+            Ok(LoadingSetRequest::Dismiss)
+        })?;
+
+        Ok(Self::resolve(ctx, candidates))
+    }
+
+    /// Returns whether all selected modules can be loaded.
+    pub fn is_feasible(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+
+    /// Returns the modules that can be loaded, in the order they would be loaded.
+    pub fn steps(&self) -> &[PlannedModule] {
+        &self.steps
+    }
+
+    /// Returns the conflicts preventing the remaining modules from being loaded.
+    pub fn conflicts(&self) -> &[Conflict] {
+        &self.conflicts
+    }
+
+    fn resolve(ctx: &impl ModuleSubsystem, candidates: Vec<Candidate>) -> Self {
+        let mut conflicts = Vec::new();
+
+        // Reject the modules whose name or exports collide with the ones of other modules.
+        let mut modules = Vec::<Candidate>::new();
+        let mut exporters = BTreeMap::<(CString, CString), (usize, Version)>::new();
+        'candidates: for candidate in candidates {
+            let name = &candidate.module.name;
+            if ModuleInfo::find_by_name(ctx, name).is_ok() {
+                conflicts.push(Conflict::DuplicateModule {
+                    module: name.clone(),
+                    loaded: true,
+                });
+                continue;
+            }
+            if modules.iter().any(|x| x.module.name == *name) {
+                conflicts.push(Conflict::DuplicateModule {
+                    module: name.clone(),
+                    loaded: false,
+                });
+                continue;
+            }
+            for symbol in &candidate.module.exports {
+                let key = (symbol.name.clone(), symbol.namespace.clone());
+                let exporter = match exporters.get(&key) {
+                    Some(&(i, _)) => Some(modules[i].module.name.clone()),
+                    None => ModuleInfo::find_by_symbol(
+                        ctx,
+                        &symbol.name,
+                        &symbol.namespace,
+                        symbol.version,
+                    )
+                    .ok()
+                    .map(|x| x.name().into()),
+                };
+                if let Some(exporter) = exporter {
+                    conflicts.push(Conflict::DuplicateSymbol {
+                        module: name.clone(),
+                        symbol: symbol.clone(),
+                        exporter,
+                    });
+                    continue 'candidates;
+                }
+            }
+            for symbol in &candidate.module.exports {
+                let key = (symbol.name.clone(), symbol.namespace.clone());
+                exporters.insert(key, (modules.len(), symbol.version));
+            }
+            modules.push(candidate);
+        }
+
+        // Resolve the imports of the remaining modules.
+        let mut rejected = alloc::vec![false; modules.len()];
+        let mut dependencies = alloc::vec![Vec::new(); modules.len()];
+        for (i, candidate) in modules.iter().enumerate() {
+            let name = &candidate.module.name;
+            for symbol in &candidate.imports {
+                let key = (symbol.name.clone(), symbol.namespace.clone());
+                match exporters.get(&key) {
+                    Some(&(exporter, version)) if version.compatible(&symbol.version) => {
+                        if !dependencies[i].contains(&exporter) {
+                            dependencies[i].push(exporter);
+                        }
+                    }
+                    _ if ModuleInfo::find_by_symbol(
+                        ctx,
+                        &symbol.name,
+                        &symbol.namespace,
+                        symbol.version,
+                    )
+                    .is_ok() => {}
+                    exporter => {
+                        rejected[i] = true;
+                        conflicts.push(Conflict::MissingSymbol {
+                            module: name.clone(),
+                            symbol: symbol.clone(),
+                            candidate: exporter
+                                .map(|&(j, version)| (modules[j].module.name.clone(), version)),
+                        });
+                    }
+                }
+            }
+            for namespace in &candidate.namespaces {
+                let exported = exporters.keys().any(|(_, ns)| ns == namespace);
+                if !exported && !ctx.namespace_exists(namespace).unwrap_or(false) {
+                    rejected[i] = true;
+                    conflicts.push(Conflict::MissingNamespace {
+                        module: name.clone(),
+                        namespace: namespace.clone(),
+                    });
+                }
+            }
+        }
+
+        // Reject the modules depending on rejected modules.
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for i in 0..modules.len() {
+                if rejected[i] {
+                    continue;
+                }
+                if let Some(&dependency) = dependencies[i].iter().find(|&&j| rejected[j]) {
+                    rejected[i] = true;
+                    changed = true;
+                    conflicts.push(Conflict::MissingDependency {
+                        module: modules[i].module.name.clone(),
+                        dependency: modules[dependency].module.name.clone(),
+                    });
+                }
+            }
+        }
+
+        // Order the modules, such that each module is loaded after its dependencies.
+        let mut steps = Vec::new();
+        let mut scheduled = rejected.clone();
+        loop {
+            let ready = (0..modules.len()).find(|&i| {
+                !scheduled[i]
+                    && dependencies[i]
+                        .iter()
+                        .all(|&j| scheduled[j] && !rejected[j])
+            });
+            match ready {
+                Some(i) => {
+                    scheduled[i] = true;
+                    let mut module = modules[i].module.clone();
+                    module.dependencies = dependencies[i]
+                        .iter()
+                        .map(|&j| modules[j].module.name.clone())
+                        .collect();
+                    steps.push(module);
+                }
+                None => break,
+            }
+        }
+        let cycle = (0..modules.len())
+            .filter(|&i| !scheduled[i])
+            .map(|i| modules[i].module.name.clone())
+            .collect::<Vec<_>>();
+        if !cycle.is_empty() {
+            conflicts.push(Conflict::Cycle { modules: cycle });
+        }
+
+        Self { steps, conflicts }
+    }
+}
+
+/// Owned copy of the relevant parts of a [`ModuleExport`].
+struct Candidate {
+    module: PlannedModule,
+    imports: Vec<PlannedSymbol>,
+    namespaces: Vec<CString>,
+}
+
+impl Candidate {
+    fn new(path: Option<&CStr>, export: ModuleExport<'_>) -> Self {
+        let exports = export
+            .exported_symbols()
+            .iter()
+            .map(|x| (x.name(), x.namespace(), x.version()))
+            .chain(
+                export
+                    .exported_dynamic_symbols()
+                    .iter()
+                    .map(|x| (x.name(), x.namespace(), x.version())),
+            );
+        let symbol = |(name, namespace, version): (&CStr, &CStr, Version)| PlannedSymbol {
+            name: name.into(),
+            namespace: namespace.into(),
+            version,
+        };
+
+        Self {
+            module: PlannedModule {
+                name: export.name().into(),
+                path: path.map(Into::into),
+                dependencies: Vec::new(),
+                exports: exports.map(symbol).collect(),
+            },
+            imports: export
+                .imported_symbols()
+                .iter()
+                .map(|x| symbol((x.name(), x.namespace(), x.version())))
+                .collect(),
+            namespaces: export
+                .imported_namespaces()
+                .iter()
+                .map(|x| x.name().into())
+                .collect(),
+        }
+    }
+}
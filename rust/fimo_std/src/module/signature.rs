@@ -0,0 +1,36 @@
+use crate::bindings;
+
+/// Size of the public key of a module signature in bytes.
+pub const SIGNATURE_PUBLIC_KEY_SIZE: usize =
+    bindings::FIMO_MODULE_SIGNATURE_PUBLIC_KEY_SIZE as usize;
+
+/// Verification policy of the signatures of module binaries.
+///
+/// The signature of a binary is stored in a detached file at `<path>.sig`, consisting of the
+/// Ed25519 public key of the signer, followed by the Ed25519 signature of the contents of the
+/// binary. Binaries failing the verification are not loaded, and each failure is published on
+/// the bus with the [`ModuleVerificationFailed`](crate::bus::ModuleVerificationFailed) topic.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignaturePolicy {
+    /// Signatures are not verified.
+    #[default]
+    Off,
+    /// Binaries must be signed by a trusted key.
+    Require,
+    /// Binaries must be signed by a trusted key, or by the key that signed the first binary
+    /// loaded from the same canonical path. The keys are pinned for the lifetime of the context,
+    /// and are not persisted.
+    TrustOnFirstUse,
+}
+
+impl From<SignaturePolicy> for bindings::FimoModuleSignaturePolicy {
+    fn from(value: SignaturePolicy) -> Self {
+        match value {
+            SignaturePolicy::Off => Self::FIMO_MODULE_SIGNATURE_POLICY_OFF,
+            SignaturePolicy::Require => Self::FIMO_MODULE_SIGNATURE_POLICY_REQUIRE,
+            SignaturePolicy::TrustOnFirstUse => {
+                Self::FIMO_MODULE_SIGNATURE_POLICY_TRUST_ON_FIRST_USE
+            }
+        }
+    }
+}
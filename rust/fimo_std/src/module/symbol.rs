@@ -1,9 +1,24 @@
-use core::{ffi::CStr, marker::PhantomData, ops::Deref, sync::atomic};
+use alloc::{boxed::Box, format, vec::Vec};
+use core::{
+    cell::RefCell,
+    ffi::CStr,
+    fmt::Write,
+    marker::PhantomData,
+    ops::Deref,
+    panic::Location,
+    sync::atomic::{self, AtomicBool, AtomicUsize},
+};
+use std::{
+    collections::BTreeSet,
+    ffi::CString,
+    sync::{Mutex, RwLock},
+};
 
 use crate::{
     bindings,
     error::Error,
-    module::{GenericModule, Module},
+    ffi::FFISharable,
+    module::{DependencyType, GenericModule, Module, ModuleInfo, ModuleInfoView, OpaqueModule},
     version::Version,
 };
 
@@ -16,8 +31,21 @@ impl<'a, T> Symbol<'a, T> {
     /// Locks the symbol for use.
     ///
     /// The same symbol may be locked multiple times, without
-    /// introducing any deadlock.
+    /// introducing any deadlock. The guard records the caller as the
+    /// site that locked the symbol, see [`held_symbols`].
+    #[track_caller]
     pub fn lock(&self) -> SymbolGuard<'_, 'a, T> {
+        self.raw_lock();
+        let location = Location::caller();
+        let id = HeldSymbol::register(core::any::type_name::<T>(), location);
+        SymbolGuard {
+            symbol: self,
+            id,
+            location,
+        }
+    }
+
+    fn raw_lock(&self) {
         // Safety: it is sound.
         let count = unsafe { &(*self.0).lock };
 
@@ -25,8 +53,6 @@ impl<'a, T> Symbol<'a, T> {
         if old_count >= (isize::MAX as usize) {
             unreachable!()
         }
-
-        SymbolGuard(self)
     }
 
     fn unlock(&self) {
@@ -57,27 +83,531 @@ impl<T> crate::ffi::FFITransferable<*const bindings::FimoModuleRawSymbol> for Sy
 }
 
 /// A reference to a locked symbol.
-#[repr(transparent)]
-pub struct SymbolGuard<'sym, 'a, T>(&'sym Symbol<'a, T>);
+///
+/// While it is alive, the guard is listed by [`held_symbols`] on the thread that locked the
+/// symbol, together with the site that locked it. A symbol can not be unloaded while it is locked,
+/// therefore the guards should not be held across suspension points, like the yields of a task.
+/// The task runtime reports the guards held across those points with [`SuspendedSymbols`].
+pub struct SymbolGuard<'sym, 'a, T> {
+    symbol: &'sym Symbol<'a, T>,
+    id: usize,
+    location: &'static Location<'static>,
+}
+
+impl<T> SymbolGuard<'_, '_, T> {
+    /// Returns the site that locked the symbol.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+}
 
 impl<T> Deref for SymbolGuard<'_, '_, T> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
         // Safety: We hold a reference to a `T`.
-        unsafe { &*(*self.0 .0).data.get().cast::<T>() }
+        unsafe { &*(*self.symbol.0).data.get().cast::<T>() }
     }
 }
 
 impl<T> Clone for SymbolGuard<'_, '_, T> {
+    #[track_caller]
     fn clone(&self) -> Self {
-        self.0.lock()
+        self.symbol.lock()
     }
 }
 
 impl<T> Drop for SymbolGuard<'_, '_, T> {
     fn drop(&mut self) {
-        self.0.unlock();
+        HeldSymbol::unregister(self.id);
+        self.symbol.unlock();
+    }
+}
+
+impl<T> core::fmt::Debug for SymbolGuard<'_, '_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SymbolGuard")
+            .field("symbol", &core::any::type_name::<T>())
+            .field("location", &self.location)
+            .finish()
+    }
+}
+
+/// A symbol locked by a [`SymbolGuard`].
+#[derive(Debug, Clone, Copy)]
+pub struct HeldSymbol {
+    id: usize,
+    type_name: &'static str,
+    location: &'static Location<'static>,
+}
+
+impl HeldSymbol {
+    /// Returns the name of the type of the symbol.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns the site that locked the symbol.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    fn register(type_name: &'static str, location: &'static Location<'static>) -> usize {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        let id = NEXT_ID.fetch_add(1, atomic::Ordering::Relaxed);
+        HELD_SYMBOLS.borrow_mut().push(Self {
+            id,
+            type_name,
+            location,
+        });
+        id
+    }
+
+    fn unregister(id: usize) {
+        let mut held = HELD_SYMBOLS.borrow_mut();
+        if let Some(position) = held.iter().rposition(|x| x.id == id) {
+            held.remove(position);
+            return;
+        }
+        drop(held);
+
+        // The guard was either moved to another thread, or it is owned by a suspended task.
+        let mut suspended = SUSPENDED_SYMBOLS.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(position) = suspended.iter().position(|x| x.id == id) {
+            suspended.swap_remove(position);
+        } else {
+            RELEASED_SYMBOLS
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(id);
+            HAS_RELEASED_SYMBOLS.store(true, atomic::Ordering::Release);
+        }
+    }
+}
+
+impl core::fmt::Display for HeldSymbol {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "`{}` locked at {}", self.type_name, self.location)
+    }
+}
+
+/// Lists the symbols locked by the current thread through a [`SymbolGuard`].
+///
+/// Only the guards acquired through the current instance of this crate are listed, i.e., the
+/// guards of the modules linked against it.
+pub fn held_symbols() -> Vec<HeldSymbol> {
+    prune_released_symbols();
+    HELD_SYMBOLS.borrow().clone()
+}
+
+/// Symbols locked by a task, while it is suspended.
+///
+/// A suspended task may be resumed by a different thread, which must then take over the list of
+/// the locked symbols. The runtime detaches the symbols from the thread with
+/// [`SuspendedSymbols::suspend`], before suspending the task, and attaches them to the resuming
+/// thread with [`SuspendedSymbols::resume`].
+#[derive(Debug)]
+pub struct SuspendedSymbols(Vec<HeldSymbol>);
+
+impl SuspendedSymbols {
+    /// Detaches the symbols locked by the current thread.
+    pub fn suspend() -> Self {
+        prune_released_symbols();
+        let symbols = core::mem::take(&mut *HELD_SYMBOLS.borrow_mut());
+        if !symbols.is_empty() {
+            SUSPENDED_SYMBOLS
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .extend_from_slice(&symbols);
+        }
+        Self(symbols)
+    }
+
+    /// Attaches the symbols, that were not released in the meantime, to the current thread.
+    pub fn resume(self) {
+        if self.0.is_empty() {
+            return;
+        }
+
+        let mut suspended = SUSPENDED_SYMBOLS.lock().unwrap_or_else(|e| e.into_inner());
+        let mut held = HELD_SYMBOLS.borrow_mut();
+        for symbol in self.0 {
+            if let Some(position) = suspended.iter().position(|x| x.id == symbol.id) {
+                suspended.swap_remove(position);
+                held.push(symbol);
+            }
+        }
+    }
+
+    /// Returns the detached symbols.
+    pub fn symbols(&self) -> &[HeldSymbol] {
+        &self.0
+    }
+
+    /// Reports that the task was suspended by `operation` while holding the symbols.
+    ///
+    /// Does nothing, if the task does not hold any symbols. The report is forwarded to the hook
+    /// set with [`set_held_symbols_hook`], or emitted as a warning through the tracing subsystem
+    /// of the current panic context otherwise.
+    pub fn report(&self, operation: &str) {
+        if self.0.is_empty() {
+            return;
+        }
+
+        let hook = HELD_SYMBOLS_HOOK.read().unwrap_or_else(|e| e.into_inner());
+        match &*hook {
+            Some(hook) => hook(operation, &self.0),
+            None => crate::panic::with_current_context(|context| {
+                let Some(context) = context else {
+                    return;
+                };
+                let mut message = format!(
+                    "{operation} while holding {} locked symbol(s):",
+                    self.0.len()
+                );
+                for symbol in &self.0 {
+                    let _ = write!(message, "\n    {symbol}");
+                }
+                crate::emit_warn!(context, "{message}");
+            }),
+        }
+    }
+}
+
+/// Function reporting the symbols held across a suspension point, see [`SuspendedSymbols`].
+pub type HeldSymbolsHook = Box<dyn Fn(&str, &[HeldSymbol]) + Send + Sync>;
+
+/// Replaces the hook reporting the symbols held across a suspension point, returning the previous
+/// hook.
+///
+/// The hook is called with the name of the operation that suspended the task, and the symbols it
+/// holds. By default, the symbols are reported as a warning through the tracing subsystem of the
+/// panic context of the thread (see [`with_panic_context`](crate::panic::with_panic_context)).
+/// Without a hook, nothing is reported on threads without a panic context.
+pub fn set_held_symbols_hook(hook: Option<HeldSymbolsHook>) -> Option<HeldSymbolsHook> {
+    let mut current = HELD_SYMBOLS_HOOK.write().unwrap_or_else(|e| e.into_inner());
+    core::mem::replace(&mut *current, hook)
+}
+
+#[thread_local]
+static HELD_SYMBOLS: RefCell<Vec<HeldSymbol>> = RefCell::new(Vec::new());
+
+// Symbols held by the suspended tasks.
+static SUSPENDED_SYMBOLS: Mutex<Vec<HeldSymbol>> = Mutex::new(Vec::new());
+
+// Guards that were dropped on a thread other than the one holding their entry.
+static RELEASED_SYMBOLS: Mutex<BTreeSet<usize>> = Mutex::new(BTreeSet::new());
+static HAS_RELEASED_SYMBOLS: AtomicBool = AtomicBool::new(false);
+
+static HELD_SYMBOLS_HOOK: RwLock<Option<HeldSymbolsHook>> = RwLock::new(None);
+
+fn prune_released_symbols() {
+    if !HAS_RELEASED_SYMBOLS.load(atomic::Ordering::Acquire) {
+        return;
+    }
+
+    let mut released = RELEASED_SYMBOLS.lock().unwrap_or_else(|e| e.into_inner());
+    HELD_SYMBOLS
+        .borrow_mut()
+        .retain(|x| !released.remove(&x.id));
+    HAS_RELEASED_SYMBOLS.store(!released.is_empty(), atomic::Ordering::Release);
+}
+
+/// Number of uses of a symbol exported by a module.
+///
+/// A symbol is in use while it is locked with [`Symbol::lock`].
+#[repr(transparent)]
+pub struct SymbolUseCount(bindings::FimoModuleSymbolUseCount);
+
+impl SymbolUseCount {
+    /// Name of the symbol.
+    pub fn name(&self) -> &CStr {
+        // Safety: The name is a valid string.
+        unsafe { CStr::from_ptr(self.0.name) }
+    }
+
+    /// Namespace of the symbol.
+    pub fn namespace(&self) -> &CStr {
+        // Safety: The namespace is a valid string.
+        unsafe { CStr::from_ptr(self.0.ns) }
+    }
+
+    /// Version of the symbol.
+    pub fn version(&self) -> Version {
+        Version(self.0.version)
+    }
+
+    /// Number of times the symbol was locked at the time of the query.
+    pub fn use_count(&self) -> usize {
+        self.0.use_count
+    }
+
+    /// Names of the modules that imported or loaded the symbol at the time of the query.
+    ///
+    /// Only these modules are able to lock the symbol.
+    pub fn importers(&self) -> impl ExactSizeIterator<Item = &CStr> + '_ {
+        let importers: &[*const core::ffi::c_char] = if self.0.importers_count == 0 {
+            &[]
+        } else {
+            // Safety: The array contains `importers_count` entries.
+            unsafe { core::slice::from_raw_parts(self.0.importers, self.0.importers_count) }
+        };
+        importers.iter().map(|&importer| {
+            // Safety: The importer names are valid strings.
+            unsafe { CStr::from_ptr(importer) }
+        })
+    }
+}
+
+// Safety: The strings and the importers are owned by the allocation containing the
+// `SymbolUseCount`.
+unsafe impl Send for SymbolUseCount {}
+
+// Safety: The strings and the importers are owned by the allocation containing the
+// `SymbolUseCount`.
+unsafe impl Sync for SymbolUseCount {}
+
+impl core::fmt::Debug for SymbolUseCount {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SymbolUseCount")
+            .field("name", &self.name())
+            .field("namespace", &self.namespace())
+            .field("version", &self.version())
+            .field("use_count", &self.use_count())
+            .field("importers", &self.importers().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// A symbol acquired for the lifetime of the guard.
+///
+/// Acquiring a scoped symbol acquires the module exporting the symbol as a dependency and includes
+/// the namespace of the symbol, unless the module already depends on them, and locks the symbol.
+/// Dropping the guard unlocks the symbol, and relinquishes the dependency and the namespace once no
+/// other scoped symbol of the module relies on them. Unlike with [`Module::acquire_symbol`], the
+/// exporting module is therefore not kept alive until the module is unloaded.
+///
+/// The guards are recorded together with the module that acquired them, until they are dropped.
+/// The outstanding guards can be listed with [`scoped_symbols`], e.g., for finding leaked guards
+/// that prevent the unloading of a module.
+pub struct ScopedSymbol<'a, T> {
+    id: usize,
+    module: OpaqueModule<'a>,
+    exporter: ModuleInfo,
+    symbol: Symbol<'a, T>,
+}
+
+impl<'a, T> ScopedSymbol<'a, T> {
+    pub(crate) fn acquire<M, I>(module: &'a M) -> Result<Self, Error>
+    where
+        M: Module + ?Sized,
+        I: SymbolItem<Type = T>,
+    {
+        // Safety: The view does not outlive the module.
+        let module = unsafe { OpaqueModule::borrow_from_ffi(module.share_to_ffi()) };
+        let namespace = I::Namespace::NAME;
+
+        // Keep the registry locked, so that the ownership of the dependencies can not change.
+        let mut registry = SCOPED_SYMBOLS.lock().unwrap_or_else(|e| e.into_inner());
+        let exporter =
+            ModuleInfo::find_by_symbol(&module.context(), I::NAME, namespace, I::VERSION)?;
+        let owns_dependency = module.has_dependency(&exporter)? == DependencyType::NoDependency;
+        if owns_dependency {
+            module.acquire_dependency(&exporter)?;
+        }
+
+        let mut owns_namespace = false;
+        let symbol = (|| {
+            if !namespace.is_empty()
+                && module.has_namespace_dependency(namespace)? == DependencyType::NoDependency
+            {
+                module.include_namespace(namespace)?;
+                owns_namespace = true;
+            }
+            module.load_symbol::<I>()
+        })();
+        let symbol = match symbol {
+            Ok(symbol) => Symbol(symbol.0, PhantomData),
+            Err(e) => {
+                // Safety: The symbol was not loaded.
+                unsafe {
+                    release(
+                        module,
+                        *exporter,
+                        namespace,
+                        owns_dependency,
+                        owns_namespace,
+                    )
+                };
+                return Err(e);
+            }
+        };
+        symbol.raw_lock();
+
+        let id = registry.next_id;
+        registry.next_id += 1;
+        registry.entries.push(ScopedSymbolEntry {
+            id,
+            module: module.share_to_ffi() as usize,
+            exporter: exporter.share_to_ffi() as usize,
+            owns_dependency,
+            owns_namespace,
+            info: ScopedSymbolInfo {
+                module: module.module_info().name().to_owned(),
+                exporter: exporter.name().to_owned(),
+                name: I::NAME,
+                namespace,
+                version: I::VERSION,
+            },
+        });
+        drop(registry);
+
+        Ok(Self {
+            id,
+            module,
+            exporter,
+            symbol,
+        })
+    }
+
+    /// Returns the info of the module exporting the symbol.
+    pub fn exporter(&self) -> &ModuleInfoView<'static> {
+        &self.exporter
+    }
+}
+
+impl<T> Deref for ScopedSymbol<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        // Safety: The symbol is locked while the guard is alive.
+        unsafe { &*(*self.symbol.0).data.get().cast::<T>() }
+    }
+}
+
+// Safety: A `ScopedSymbol` is essentially a `&'a T`.
+unsafe impl<T> Send for ScopedSymbol<'_, T> where T: Sync {}
+
+// Safety: A `ScopedSymbol` is essentially a `&'a T`.
+unsafe impl<T> Sync for ScopedSymbol<'_, T> where T: Sync {}
+
+impl<T> core::fmt::Debug for ScopedSymbol<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ScopedSymbol")
+            .field("module", &self.module.module_info().name())
+            .field("exporter", &self.exporter.name())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Drop for ScopedSymbol<'_, T> {
+    fn drop(&mut self) {
+        self.symbol.unlock();
+
+        let mut registry = SCOPED_SYMBOLS.lock().unwrap_or_else(|e| e.into_inner());
+        let position = registry
+            .entries
+            .iter()
+            .position(|x| x.id == self.id)
+            .expect("scoped symbol is not registered");
+        let entry = registry.entries.swap_remove(position);
+
+        // Hand the ownership of the dependencies over to another guard of the module, if they
+        // still rely on them.
+        let mut owns_dependency = entry.owns_dependency;
+        if let Some(other) = registry
+            .entries
+            .iter_mut()
+            .find(|x| x.module == entry.module && x.exporter == entry.exporter)
+        {
+            other.owns_dependency |= owns_dependency;
+            owns_dependency = false;
+        }
+        let mut owns_namespace = entry.owns_namespace;
+        if let Some(other) = registry
+            .entries
+            .iter_mut()
+            .find(|x| x.module == entry.module && x.info.namespace == entry.info.namespace)
+        {
+            other.owns_namespace |= owns_namespace;
+            owns_namespace = false;
+        }
+
+        // Safety: The symbol is no longer used, and no other guard relies on the dependencies.
+        unsafe {
+            release(
+                self.module,
+                *self.exporter,
+                entry.info.namespace,
+                owns_dependency,
+                owns_namespace,
+            )
+        };
+    }
+}
+
+/// Info of an outstanding [`ScopedSymbol`].
+#[derive(Debug, Clone)]
+pub struct ScopedSymbolInfo {
+    /// Name of the module that acquired the symbol.
+    pub module: CString,
+    /// Name of the module exporting the symbol.
+    pub exporter: CString,
+    /// Name of the symbol.
+    pub name: &'static CStr,
+    /// Namespace of the symbol.
+    pub namespace: &'static CStr,
+    /// Version of the symbol.
+    pub version: Version,
+}
+
+/// Lists the outstanding [`ScopedSymbol`]s.
+///
+/// Only the guards acquired through the current instance of this crate are listed, i.e., the
+/// guards of the modules linked against it.
+pub fn scoped_symbols() -> Vec<ScopedSymbolInfo> {
+    let registry = SCOPED_SYMBOLS.lock().unwrap_or_else(|e| e.into_inner());
+    registry.entries.iter().map(|x| x.info.clone()).collect()
+}
+
+static SCOPED_SYMBOLS: Mutex<ScopedSymbolRegistry> = Mutex::new(ScopedSymbolRegistry {
+    next_id: 0,
+    entries: Vec::new(),
+});
+
+struct ScopedSymbolRegistry {
+    next_id: usize,
+    entries: Vec<ScopedSymbolEntry>,
+}
+
+struct ScopedSymbolEntry {
+    id: usize,
+    module: usize,
+    exporter: usize,
+    owns_dependency: bool,
+    owns_namespace: bool,
+    info: ScopedSymbolInfo,
+}
+
+/// # Safety
+///
+/// The symbols of the dependency, or of the namespace, must no longer be used, if they are
+/// relinquished.
+unsafe fn release(
+    module: OpaqueModule<'_>,
+    exporter: ModuleInfoView<'_>,
+    namespace: &CStr,
+    relinquish_dependency: bool,
+    exclude_namespace: bool,
+) {
+    // Errors are ignored, as the module may have already relinquished them manually.
+    if exclude_namespace {
+        // Safety: Ensured by the caller.
+        let _ = unsafe { module.exclude_namespace(namespace) };
+    }
+    if relinquish_dependency {
+        // Safety: Ensured by the caller.
+        let _ = unsafe { module.remove_dependency(exporter) };
     }
 }
 
@@ -248,3 +778,84 @@ macro_rules! declare_items_private {
         $crate::declare_items_private!(namespace $ns_type $($tt)*);
     };
 }
+
+/// Declares a set of symbols that are acquired together from the module subsystem.
+///
+/// Generates a struct containing one loaded [`Symbol`] per field, with an accessor locking the
+/// symbol. The constructor `acquire` loads each symbol with [`Module::acquire_symbol`], which
+/// searches for a module exporting a compatible version of the symbol and acquires it, and the
+/// namespace of the symbol, as a dependency.
+///
+/// [`Module::acquire_symbol`]: crate::module::Module::acquire_symbol
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::{
+///     declare_dependencies, declare_items,
+///     error::Error,
+///     module::Module,
+/// };
+///
+/// declare_items! {
+///     extern answer @ (1, 0, 0): i32;
+///
+///     mod my_ns {
+///         extern question @ (0, 2, 0): &'static str;
+///     }
+/// }
+///
+/// declare_dependencies! {
+///     /// Symbols required by the module.
+///     pub struct Dependencies {
+///         answer: Answer,
+///         question: my_ns::Question,
+///     }
+/// }
+///
+/// fn print_answer(module: &impl Module) -> Result<(), Error> {
+///     let deps = Dependencies::acquire(module)?;
+///     println!("{}: {}", *deps.question(), *deps.answer());
+///     Ok(())
+/// }
+/// ```
+#[macro_export]
+macro_rules! declare_dependencies {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            $($field:ident: $item:path),* $(,)?
+        }
+    ) => {
+        $crate::paste::paste! {
+            $(#[$attr])*
+            $vis struct $name<'a> {
+                $(
+                    $field: $crate::module::Symbol<'a, <$item as $crate::module::SymbolItem>::Type>,
+                )*
+            }
+
+            impl<'a> $name<'a> {
+                #[doc = "Acquires the symbols of `" $name "` for the module."]
+                $vis fn acquire(
+                    module: &'a impl $crate::module::Module,
+                ) -> Result<Self, $crate::error::Error> {
+                    Ok(Self {
+                        $(
+                            $field: $crate::module::Module::acquire_symbol::<$item>(module)?,
+                        )*
+                    })
+                }
+
+                $(
+                    #[doc = "Fetches the `" $field "` symbol"]
+                    $vis fn $field(&self) -> $crate::module::SymbolGuard<
+                        '_, 'a, <$item as $crate::module::SymbolItem>::Type
+                    > {
+                        self.$field.lock()
+                    }
+                )*
+            }
+        }
+    };
+}
@@ -47,9 +47,11 @@ static CURRENT_CONTEXT: Cell<Option<bindings::FimoContext>> = Cell::new(None);
 /// subsystem.
 ///
 /// The new panic hook will forward the panic info to the tracing subsystem, by emitting an error
-/// event. If the tracing subsytem is disabled, or a panic occurs without a panic context set
-/// (see [`with_panic_context`]), the implementation will forward the panic info to the previous
-/// panic hook.
+/// event. The event is emitted inside the current span of the thread, and contains the location
+/// of the panic, along with a backtrace, if one was captured. Afterward, the subsystem is flushed,
+/// so that the event is not lost if the panic results in an abort. If the tracing subsytem is
+/// disabled, or a panic occurs without a panic context set (see [`with_panic_context`]), the
+/// implementation will forward the panic info to the previous panic hook.
 pub fn set_panic_hook() {
     std::panic::update_hook(|prev, info| {
         use crate::tracing::TracingSubsystem;
@@ -86,16 +88,40 @@ pub fn set_panic_hook() {
         let thread = std::thread::current();
         let name = thread.name().unwrap_or("<unnamed>");
 
-        if backtrace.status() == std::backtrace::BacktraceStatus::Disabled {
-            crate::emit_error!(
-                context,
-                "thread '{name}' panicked at {location}:\n{msg}\n\
-                note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace"
-            );
-        } else {
-            crate::emit_error!(context, "thread '{name}' panicked at {location}:\n{msg}");
+        match backtrace.status() {
+            std::backtrace::BacktraceStatus::Captured => {
+                crate::emit_error!(
+                    context,
+                    "thread '{name}' panicked at {location}:\n{msg}\nstack backtrace:\n{backtrace}"
+                );
+            }
+            std::backtrace::BacktraceStatus::Disabled => {
+                crate::emit_error!(
+                    context,
+                    "thread '{name}' panicked at {location}:\n{msg}\n\
+                    note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace"
+                );
+            }
+            _ => {
+                crate::emit_error!(context, "thread '{name}' panicked at {location}:\n{msg}");
+            }
         }
+
+        // The panic may be followed by an abort, e.g., when it occurs inside of
+        // `abort_on_panic`, so we must ensure that the event reaches the subscribers.
+        let _ = context.flush();
+    });
+}
+
+/// Calls a closure with the panic context of the current thread, if one is set.
+pub(crate) fn with_current_context<R>(
+    f: impl FnOnce(Option<crate::context::ContextView<'_>>) -> R,
+) -> R {
+    let context = CURRENT_CONTEXT.get().map(|context| {
+        // Safety: We controll `CURRENT_CONTEXT` and ensure that it is valid.
+        unsafe { crate::context::Context::borrow_from_ffi(context) }
     });
+    f(context)
 }
 
 /// Sets the panic context of the current thread.
@@ -1,10 +1,17 @@
 //! Time utilities.
+use alloc::{boxed::Box, sync::Arc};
+use core::{
+    ffi::c_void,
+    ops::{Add, AddAssign, Sub, SubAssign},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
 use crate::{
     bindings,
+    context::private::SealedContext,
     error::{to_result_indirect_in_place, Error},
     ffi::{FFISharable, FFITransferable},
 };
-use core::ops::{Add, AddAssign, Sub, SubAssign};
 
 /// A span between to points in time.
 #[repr(transparent)]
@@ -352,3 +359,317 @@ impl FFITransferable<bindings::FimoTime> for Time {
         Self(ffi)
     }
 }
+
+/// A point in time of a monotonic clock.
+///
+/// Unlike [`Time`], the time points are not related to a calendar, and are only meaningful when
+/// compared to other time points of the same clock.
+#[repr(transparent)]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct TimeMonotonic(bindings::FimoTimeMonotonic);
+
+impl TimeMonotonic {
+    /// Maximum time point.
+    pub const MAX_TIME_POINT: Self = Self(bindings::FimoTimeMonotonic {
+        secs: u64::MAX,
+        nanos: 999999999,
+    });
+
+    /// Returns the current time of the monotonic clock of the system.
+    pub fn now() -> Self {
+        // Safety: FFI call is safe.
+        unsafe { Self(bindings::fimo_time_monotonic_now()) }
+    }
+
+    /// Returns the duration elapsed since the time point, as measured by the system clock.
+    ///
+    /// May result in an error, if `self` is in the future.
+    pub fn elapsed(&self) -> Result<Duration, Error> {
+        // Safety: FFI call is safe.
+        let duration = unsafe {
+            to_result_indirect_in_place(|error, duration| {
+                *error = bindings::fimo_time_monotonic_elapsed(&self.0, duration.as_mut_ptr());
+            })?
+        };
+        Ok(Duration(duration))
+    }
+
+    /// Returns the difference between two time points.
+    ///
+    /// Returns an error if `self` is before `other`.
+    pub fn duration_since(&self, other: &Self) -> Result<Duration, Error> {
+        // Safety: FFI call is safe.
+        let duration = unsafe {
+            to_result_indirect_in_place(|error, duration| {
+                *error = bindings::fimo_time_monotonic_duration_since(
+                    &self.0,
+                    &other.0,
+                    duration.as_mut_ptr(),
+                );
+            })?
+        };
+        Ok(Duration(duration))
+    }
+
+    /// Returns `Some(t)` where t is the time `self + duration` if `t` can be represented as
+    /// `TimeMonotonic`, `None` otherwise.
+    pub fn checked_add(&self, duration: Duration) -> Option<Self> {
+        // Safety: FFI call is safe.
+        let time = unsafe {
+            to_result_indirect_in_place(|error, time| {
+                *error = bindings::fimo_time_monotonic_add(&self.0, &duration.0, time.as_mut_ptr());
+            })
+        };
+        match time {
+            Ok(x) => Some(Self(x)),
+            Err(_) => None,
+        }
+    }
+
+    /// Returns `t` where t is the time `self + duration` if `t` can be represented as
+    /// `TimeMonotonic`, `MAX_TIME_POINT` otherwise.
+    pub fn saturating_add(&self, duration: Duration) -> Self {
+        // Safety: FFI call is safe.
+        let time = unsafe { bindings::fimo_time_monotonic_saturating_add(&self.0, &duration.0) };
+        Self(time)
+    }
+}
+
+impl Add<Duration> for TimeMonotonic {
+    type Output = TimeMonotonic;
+
+    fn add(self, rhs: Duration) -> Self::Output {
+        self.checked_add(rhs).unwrap()
+    }
+}
+
+impl AddAssign<Duration> for TimeMonotonic {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = self.checked_add(rhs).unwrap();
+    }
+}
+
+impl FFISharable<bindings::FimoTimeMonotonic> for TimeMonotonic {
+    type BorrowedView<'a> = TimeMonotonic;
+
+    fn share_to_ffi(&self) -> bindings::FimoTimeMonotonic {
+        self.0
+    }
+
+    unsafe fn borrow_from_ffi<'a>(ffi: bindings::FimoTimeMonotonic) -> Self::BorrowedView<'a> {
+        Self(ffi)
+    }
+}
+
+impl FFITransferable<bindings::FimoTimeMonotonic> for TimeMonotonic {
+    fn into_ffi(self) -> bindings::FimoTimeMonotonic {
+        self.0
+    }
+
+    unsafe fn from_ffi(ffi: bindings::FimoTimeMonotonic) -> Self {
+        Self(ffi)
+    }
+}
+
+/// Source of the time of a [`Context`](crate::context::Context).
+///
+/// A clock is installed with [`ContextBuilder::with_clock`], and is queried by all subsystems of
+/// the context, from any thread.
+///
+/// [`ContextBuilder::with_clock`]: crate::context::ContextBuilder::with_clock
+pub trait Clock: Send + Sync + 'static {
+    /// Returns the current time of the wall clock.
+    fn now(&self) -> Time;
+
+    /// Returns the current time of the monotonic clock.
+    ///
+    /// Must never return a time point that is earlier than a previously returned one.
+    fn monotonic_now(&self) -> TimeMonotonic;
+}
+
+/// Clock using the wall clock and the monotonic clock of the system.
+///
+/// Is the default clock of a context.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Time {
+        Time::now()
+    }
+
+    fn monotonic_now(&self) -> TimeMonotonic {
+        TimeMonotonic::now()
+    }
+}
+
+/// Clock that only advances when instructed to, for deterministic tests.
+///
+/// Clones of the clock share the same time, allowing a test to advance the clock after it has
+/// been installed into a context.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::{
+///     context::ContextBuilder,
+///     time::{ClockSubsystem, Duration, MockClock, Time},
+/// };
+///
+/// let start = Time::UNIX_EPOCH + Duration::from_seconds(1000);
+/// let clock = MockClock::new(start);
+/// let context = <ContextBuilder>::new()
+///     .with_clock(clock.clone())
+///     .build()
+///     .expect("could not create context");
+///
+/// assert_eq!(context.now(), start);
+/// let monotonic = context.monotonic_now();
+///
+/// clock.advance(Duration::from_millis(1500));
+/// assert_eq!(context.now(), start + Duration::from_millis(1500));
+/// assert_eq!(
+///     context
+///         .monotonic_now()
+///         .duration_since(&monotonic)
+///         .expect("clock went backwards"),
+///     Duration::from_millis(1500)
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct MockClock(Arc<MockClockInner>);
+
+#[derive(Debug)]
+struct MockClockInner {
+    start: Time,
+    elapsed_nanos: AtomicU64,
+}
+
+impl MockClock {
+    /// Constructs a new clock, whose wall clock starts at `start`.
+    ///
+    /// The monotonic clock starts at an unspecified time point.
+    pub fn new(start: Time) -> Self {
+        Self(Arc::new(MockClockInner {
+            start,
+            elapsed_nanos: AtomicU64::new(0),
+        }))
+    }
+
+    /// Advances the wall clock and the monotonic clock by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        let _ = self
+            .0
+            .elapsed_nanos
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |x| {
+                Some(x.saturating_add(nanos))
+            });
+    }
+
+    /// Returns the total duration by which the clock has been advanced.
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_nanos(self.0.elapsed_nanos.load(Ordering::Acquire))
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Time {
+        self.0.start.saturating_add(self.elapsed())
+    }
+
+    fn monotonic_now(&self) -> TimeMonotonic {
+        TimeMonotonic(bindings::FimoTimeMonotonic { secs: 0, nanos: 0 })
+            .saturating_add(self.elapsed())
+    }
+}
+
+/// Configuration of the clock of a context.
+pub(crate) struct ClockConfig(bindings::FimoClockConfig);
+
+impl ClockConfig {
+    pub(crate) fn new<C: Clock>(clock: C) -> Self {
+        unsafe extern "C" fn now<C: Clock>(data: *mut c_void) -> bindings::FimoTime {
+            crate::panic::abort_on_panic(|| {
+                // Safety: The data is a `C`, owned by the context.
+                let clock = unsafe { &*data.cast_const().cast::<C>() };
+                clock.now().into_ffi()
+            })
+        }
+
+        unsafe extern "C" fn monotonic_now<C: Clock>(
+            data: *mut c_void,
+        ) -> bindings::FimoTimeMonotonic {
+            crate::panic::abort_on_panic(|| {
+                // Safety: The data is a `C`, owned by the context.
+                let clock = unsafe { &*data.cast_const().cast::<C>() };
+                clock.monotonic_now().into_ffi()
+            })
+        }
+
+        unsafe extern "C" fn on_drop<C: Clock>(data: *mut c_void) {
+            // Safety: The function is invoked once, after the last use of the clock.
+            drop(unsafe { Box::from_raw(data.cast::<C>()) });
+        }
+
+        Self(bindings::FimoClockConfig {
+            type_: bindings::FimoStructType::FIMO_STRUCT_TYPE_CLOCK_CONFIG,
+            next: core::ptr::null(),
+            data: Box::into_raw(Box::new(clock)).cast(),
+            now: Some(now::<C>),
+            monotonic_now: Some(monotonic_now::<C>),
+            on_drop: Some(on_drop::<C>),
+        })
+    }
+
+    /// Returns a pointer to the option.
+    ///
+    /// The ownership of the clock is transferred to the context, once the pointer is passed to
+    /// it, even if the creation of the context fails.
+    pub(crate) fn as_ffi_option_ptr(&self) -> *const bindings::FimoBaseStructIn {
+        core::ptr::from_ref(&self.0).cast()
+    }
+}
+
+impl core::fmt::Debug for ClockConfig {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ClockConfig").finish_non_exhaustive()
+    }
+}
+
+impl Drop for ClockConfig {
+    fn drop(&mut self) {
+        if let Some(on_drop) = self.0.on_drop {
+            // Safety: The config still owns the clock.
+            unsafe { on_drop(self.0.data) };
+        }
+    }
+}
+
+/// Definition of the clock subsystem.
+///
+/// The clock of a context is used by all of its subsystems, e.g., for the timestamps of the
+/// tracing events. It defaults to the [`SystemClock`], and may be replaced when building the
+/// context.
+pub trait ClockSubsystem: SealedContext {
+    /// Returns the current time of the wall clock of the context.
+    fn now(&self) -> Time;
+
+    /// Returns the current time of the monotonic clock of the context.
+    fn monotonic_now(&self) -> TimeMonotonic;
+}
+
+impl<C> ClockSubsystem for C
+where
+    C: SealedContext,
+{
+    fn now(&self) -> Time {
+        // Safety: FFI call is safe.
+        unsafe { Time(bindings::fimo_clock_now(self.share_to_ffi())) }
+    }
+
+    fn monotonic_now(&self) -> TimeMonotonic {
+        // Safety: FFI call is safe.
+        unsafe { TimeMonotonic(bindings::fimo_clock_monotonic_now(self.share_to_ffi())) }
+    }
+}
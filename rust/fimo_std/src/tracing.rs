@@ -8,15 +8,48 @@ use crate::{
     ffi::{FFISharable, FFITransferable},
     time::Time,
 };
-use alloc::boxed::Box;
+use alloc::{boxed::Box, string::String};
 use core::{
     ffi::CStr,
     fmt::{Arguments, Write},
+    marker::PhantomData,
     mem::ManuallyDrop,
     num::NonZeroUsize,
     pin::Pin,
 };
 
+mod binary;
+mod chrome;
+mod console;
+mod filter;
+mod forward;
+mod init;
+mod namespace;
+#[cfg(feature = "otlp")]
+mod otlp;
+mod process;
+mod rate_limit;
+mod route;
+mod sample;
+mod span_trace;
+mod template;
+
+pub use binary::*;
+pub use chrome::*;
+pub use console::*;
+pub use filter::*;
+pub use forward::*;
+pub use init::*;
+pub use namespace::*;
+#[cfg(feature = "otlp")]
+pub use otlp::*;
+pub use process::*;
+pub use rate_limit::*;
+pub use route::*;
+pub use sample::*;
+pub use span_trace::*;
+pub use template::*;
+
 /// Definition of the tracing subsystem.
 pub trait TracingSubsystem: SealedContext {
     /// Emits a new event.
@@ -25,6 +58,22 @@ pub trait TracingSubsystem: SealedContext {
 
     fn emit_event(&self, event: &Event, arguments: Arguments<'_>) -> error::Result;
 
+    /// Emits a new event with a [`MessageTemplate`].
+    ///
+    /// The template is preserved alongside the rendered message, and the fields are only formatted
+    /// if the event is not filtered out by the subsystem. The message may be cut of, if the length
+    /// exceeds the internal formatting buffer size.
+    fn emit_event_template(&self, event: &Event, template: &MessageTemplate<'_>) -> error::Result;
+
+    /// Emits a batch of pre-formatted events.
+    ///
+    /// The records are emitted in order, and are all timestamped with the same time. Subscribers
+    /// may handle the whole batch at once, e.g., to acquire their locks only once, which makes
+    /// this function preferable to [`emit_event`](TracingSubsystem::emit_event) when forwarding
+    /// many messages from a different logging system. Unlike the other functions, the messages are
+    /// not cut of at the internal formatting buffer size.
+    fn emit_event_batch(&self, records: &[Record<'_>]) -> error::Result;
+
     /// Checks whether the tracing subsystem is enabled.
     ///
     /// This function can be used to check whether to call into the subsystem at all. Calling this
@@ -56,6 +105,33 @@ where
         }
     }
 
+    fn emit_event_template(&self, event: &Event, template: &MessageTemplate<'_>) -> error::Result {
+        let event = bindings::FimoTracingEvent {
+            next: template.share_to_ffi().cast(),
+            ..event.0
+        };
+
+        // Safety: FFI call is safe.
+        unsafe {
+            to_result_indirect(|error| {
+                *error = bindings::fimo_tracing_event_emit_template(self.share_to_ffi(), &event);
+            })
+        }
+    }
+
+    fn emit_event_batch(&self, records: &[Record<'_>]) -> error::Result {
+        // Safety: FFI call is safe.
+        unsafe {
+            to_result_indirect(|error| {
+                *error = bindings::fimo_tracing_event_emit_batch(
+                    self.share_to_ffi(),
+                    records.as_ptr().cast(),
+                    records.len(),
+                );
+            })
+        }
+    }
+
     fn is_enabled(&self) -> bool {
         // Safety: FFI call is safe.
         unsafe { bindings::fimo_tracing_is_enabled(self.share_to_ffi()) }
@@ -72,6 +148,9 @@ where
 }
 
 /// Constructs a new [`Span`].
+///
+/// Spans above [`STATIC_MAX_LEVEL`] are compiled out, without evaluating their arguments. In that
+/// case the macro returns a disabled span, which is not entered.
 #[macro_export]
 macro_rules! tracing_span {
     ($ctx:expr, name: $name:literal, target: $target:literal, lvl: $lvl:expr, $($arg:tt)+) => {
@@ -83,8 +162,12 @@ macro_rules! tracing_span {
             );
             const DESCRIPTOR: &'static $crate::tracing::SpanDescriptor =
                 &$crate::tracing::SpanDescriptor::new(METADATA);
-            $crate::tracing::Span::new($ctx, DESCRIPTOR, core::format_args!($($arg)+))
-                .expect("could not create span")
+            if $lvl <= $crate::tracing::STATIC_MAX_LEVEL {
+                $crate::tracing::Span::new($ctx, DESCRIPTOR, core::format_args!($($arg)+))
+                    .expect("could not create span")
+            } else {
+                $crate::tracing::Span::new_disabled($ctx)
+            }
         }
     };
     ($ctx:expr, target: $target:literal, lvl: $lvl:expr, $($arg:tt)+) => {
@@ -95,8 +178,12 @@ macro_rules! tracing_span {
             );
             const DESCRIPTOR: &'static $crate::tracing::SpanDescriptor =
                 &$crate::tracing::SpanDescriptor::new(METADATA);
-            $crate::tracing::Span::new($ctx, DESCRIPTOR, core::format_args!($($arg)+))
-                .expect("could not create span")
+            if $lvl <= $crate::tracing::STATIC_MAX_LEVEL {
+                $crate::tracing::Span::new($ctx, DESCRIPTOR, core::format_args!($($arg)+))
+                    .expect("could not create span")
+            } else {
+                $crate::tracing::Span::new_disabled($ctx)
+            }
         };
     };
     ($ctx:expr, lvl: $lvl:expr, $($arg:tt)+) => {
@@ -105,8 +192,34 @@ macro_rules! tracing_span {
 }
 
 /// Emits a new [`Event`].
+///
+/// The message is either specified as format arguments, or as a [`MessageTemplate`] with the
+/// `template:` prefix, followed by the values of its fields. An optional `sample:` argument,
+/// which must be a constant expression, only emits one of every `N` events of the call site, see
+/// [`Sampler`].
+///
+/// Events above [`STATIC_MAX_LEVEL`] are compiled out, without evaluating their arguments.
 #[macro_export]
 macro_rules! tracing_emit {
+    ($ctx:expr, $(name: $name:literal,)? $(target: $target:literal,)? lvl: $lvl:expr, sample: $rate:expr, $($arg:tt)+) => {{
+        static SAMPLER: $crate::tracing::Sampler = $crate::tracing::Sampler::new($rate);
+        if $lvl <= $crate::tracing::STATIC_MAX_LEVEL && SAMPLER.sample() {
+            $crate::tracing_emit!($ctx, $(name: $name,)? $(target: $target,)? lvl: $lvl, $($arg)+);
+        }
+    }};
+    ($ctx:expr, name: $name:literal, target: $target:literal, lvl: $lvl:expr, template: $template:literal $(, $field:ident = $value:expr)* $(,)?) => {{
+        use $crate::tracing::TracingSubsystem;
+        const METADATA: &'static $crate::tracing::Metadata = $crate::tracing_metadata!(
+            name: $name,
+            target: $target,
+            lvl: $lvl
+        );
+        const EVENT: &'static $crate::tracing::Event = &$crate::tracing::Event::new(METADATA);
+        if $lvl <= $crate::tracing::STATIC_MAX_LEVEL {
+            $ctx.emit_event_template(EVENT, $crate::tracing_template!($template $(, $field = $value)*))
+                .expect("could not emit event");
+        }
+    }};
     ($ctx:expr, name: $name:literal, target: $target:literal, lvl: $lvl:expr, $($arg:tt)+) => {{
         use $crate::tracing::TracingSubsystem;
         const METADATA: &'static $crate::tracing::Metadata = $crate::tracing_metadata!(
@@ -115,7 +228,21 @@ macro_rules! tracing_emit {
             lvl: $lvl
         );
         const EVENT: &'static $crate::tracing::Event = &$crate::tracing::Event::new(METADATA);
-        $ctx.emit_event(EVENT, core::format_args!($($arg)+)).expect("could not emit event");
+        if $lvl <= $crate::tracing::STATIC_MAX_LEVEL {
+            $ctx.emit_event(EVENT, core::format_args!($($arg)+)).expect("could not emit event");
+        }
+    }};
+    ($ctx:expr, target: $target:literal, lvl: $lvl:expr, template: $template:literal $(, $field:ident = $value:expr)* $(,)?) => {{
+        use $crate::tracing::TracingSubsystem;
+        const METADATA: &'static $crate::tracing::Metadata = $crate::tracing_metadata!(
+            target: $target,
+            lvl: $lvl
+        );
+        const EVENT: &'static $crate::tracing::Event = &$crate::tracing::Event::new(METADATA);
+        if $lvl <= $crate::tracing::STATIC_MAX_LEVEL {
+            $ctx.emit_event_template(EVENT, $crate::tracing_template!($template $(, $field = $value)*))
+                .expect("could not emit event");
+        }
     }};
     ($ctx:expr, target: $target:literal, lvl: $lvl:expr, $($arg:tt)+) => {{
         use $crate::tracing::TracingSubsystem;
@@ -124,7 +251,9 @@ macro_rules! tracing_emit {
             lvl: $lvl
         );
         const EVENT: &'static $crate::tracing::Event = &$crate::tracing::Event::new(METADATA);
-        $ctx.emit_event(EVENT, core::format_args!($($arg)+)).expect("could not emit event");
+        if $lvl <= $crate::tracing::STATIC_MAX_LEVEL {
+            $ctx.emit_event(EVENT, core::format_args!($($arg)+)).expect("could not emit event");
+        }
     }};
     ($ctx:expr, lvl: $lvl:expr, $($arg:tt)+) => {
         $crate::tracing_emit!($ctx, target: "", lvl: $lvl, $($arg)+)
@@ -200,6 +329,22 @@ macro_rules! emit_error {
     };
 }
 
+/// Emits a new [`Level::Error`] event containing an error and the chain of its sources.
+///
+/// The error is formatted with [`ErrorChain`](crate::error::ErrorChain).
+#[macro_export]
+macro_rules! emit_error_chain {
+    ($ctx:expr, name: $name:literal, target: $target:literal, $err:expr $(,)?) => {
+        $crate::emit_error!($ctx, name: $name, target: $target, "{}", $crate::error::ErrorChain::new($err));
+    };
+    ($ctx:expr, target: $target:literal, $err:expr $(,)?) => {
+        $crate::emit_error!($ctx, target: $target, "{}", $crate::error::ErrorChain::new($err));
+    };
+    ($ctx:expr, $err:expr $(,)?) => {
+        $crate::emit_error!($ctx, "{}", $crate::error::ErrorChain::new($err));
+    };
+}
+
 /// Emits a new [`Level::Warn`] event.
 #[macro_export]
 macro_rules! emit_warn {
@@ -243,8 +388,20 @@ macro_rules! emit_debug {
 }
 
 /// Emits a new [`Level::Trace`] event.
+///
+/// Supports sampling the events of hot call sites with the `sample:` argument, see
+/// [`tracing_emit`](crate::tracing_emit).
 #[macro_export]
 macro_rules! emit_trace {
+    ($ctx:expr, name: $name:literal, target: $target:literal, sample: $rate:expr, $($arg:tt)+) => {
+        $crate::tracing_emit!($ctx, name: $name, target: $target, lvl: $crate::tracing::Level::Trace, sample: $rate, $($arg)+);
+    };
+    ($ctx:expr, target: $target:literal, sample: $rate:expr, $($arg:tt)+) => {
+        $crate::tracing_emit!($ctx, target: $target, lvl: $crate::tracing::Level::Trace, sample: $rate, $($arg)+);
+    };
+    ($ctx:expr, sample: $rate:expr, $($arg:tt)+) => {
+        $crate::tracing_emit!($ctx, lvl: $crate::tracing::Level::Trace, sample: $rate, $($arg)+);
+    };
     ($ctx:expr, name: $name:literal, target: $target:literal, $($arg:tt)+) => {
         $crate::tracing_emit!($ctx, name: $name, target: $target, lvl: $crate::tracing::Level::Trace, $($arg)+);
     };
@@ -326,6 +483,68 @@ macro_rules! span_trace {
     };
 }
 
+/// Maximum level of the events and spans that are not compiled out.
+///
+/// The tracing macros compile to no-ops for the events and spans above this level, independently
+/// of the level configured at runtime. The level is selected with the `max_level_*` features of
+/// the crate. In builds without debug assertions, a `release_max_level_*` feature takes precedence
+/// over the `max_level_*` features, even if it selects a higher level. If multiple features of
+/// the same kind are enabled, the lowest of their levels is selected. Defaults to
+/// [`Level::Trace`].
+pub const STATIC_MAX_LEVEL: Level = select_static_max_level(
+    cfg!(debug_assertions),
+    feature_level([
+        cfg!(feature = "max_level_off"),
+        cfg!(feature = "max_level_error"),
+        cfg!(feature = "max_level_warn"),
+        cfg!(feature = "max_level_info"),
+        cfg!(feature = "max_level_debug"),
+        cfg!(feature = "max_level_trace"),
+    ]),
+    feature_level([
+        cfg!(feature = "release_max_level_off"),
+        cfg!(feature = "release_max_level_error"),
+        cfg!(feature = "release_max_level_warn"),
+        cfg!(feature = "release_max_level_info"),
+        cfg!(feature = "release_max_level_debug"),
+        cfg!(feature = "release_max_level_trace"),
+    ]),
+);
+
+/// Returns the lowest level whose feature is enabled, with the features ordered from
+/// [`Level::Off`] to [`Level::Trace`].
+const fn feature_level(enabled: [bool; 6]) -> Option<Level> {
+    const LEVELS: [Level; 6] = [
+        Level::Off,
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ];
+
+    let mut i = 0;
+    while i < LEVELS.len() {
+        if enabled[i] {
+            return Some(LEVELS[i]);
+        }
+        i += 1;
+    }
+    None
+}
+
+const fn select_static_max_level(
+    debug_assertions: bool,
+    max_level: Option<Level>,
+    release_max_level: Option<Level>,
+) -> Level {
+    match (debug_assertions, max_level, release_max_level) {
+        (false, _, Some(level)) => level,
+        (_, Some(level), _) => level,
+        _ => Level::Trace,
+    }
+}
+
 /// Available levels in the tracing subsystem.
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub enum Level {
@@ -374,6 +593,28 @@ impl TryFrom<bindings::FimoTracingLevel> for Level {
     }
 }
 
+impl core::str::FromStr for Level {
+    type Err = Error;
+
+    /// Parses a level from its case-insensitive name, e.g. `"info"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const LEVELS: [(&str, Level); 6] = [
+            ("off", Level::Off),
+            ("error", Level::Error),
+            ("warn", Level::Warn),
+            ("info", Level::Info),
+            ("debug", Level::Debug),
+            ("trace", Level::Trace),
+        ];
+        let s = s.trim();
+        LEVELS
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(s))
+            .map(|&(_, level)| level)
+            .ok_or(Error::EINVAL)
+    }
+}
+
 #[derive(Debug)]
 #[repr(transparent)]
 pub struct Metadata(bindings::FimoTracingMetadata);
@@ -470,6 +711,12 @@ impl Event {
             metadata: &metadata.0,
         })
     }
+
+    /// Returns a reference to the contained [`Metadata`].
+    pub fn metadata(&self) -> &Metadata {
+        // Safety: The pointer must be valid.
+        unsafe { Metadata::borrow_from_ffi(self.0.metadata) }
+    }
 }
 
 impl FFISharable<*const bindings::FimoTracingEvent> for Event {
@@ -487,6 +734,64 @@ impl FFISharable<*const bindings::FimoTracingEvent> for Event {
     }
 }
 
+/// A pre-formatted [`Event`], emitted with [`TracingSubsystem::emit_event_batch`].
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::tracing::{init_simple, Event, Level, Metadata, Record, TracingSubsystem};
+///
+/// const METADATA: &Metadata = &Metadata::new(c"forwarded", c"bridge", Level::Info, None, None);
+///
+/// let guard = init_simple(Level::Info).expect("could not initialize the tracing");
+/// let event = Event::new(METADATA);
+/// let records = [
+///     Record::new(&event, b"first forwarded message"),
+///     Record::new(&event, b"second forwarded message"),
+/// ];
+/// guard
+///     .context()
+///     .emit_event_batch(&records)
+///     .expect("could not emit the records");
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct Record<'a>(bindings::FimoTracingRecord, PhantomData<&'a ()>);
+
+impl<'a> Record<'a> {
+    /// Constructs a new record.
+    pub fn new(event: &'a Event, message: &'a [u8]) -> Self {
+        Self(
+            bindings::FimoTracingRecord {
+                event: event.share_to_ffi(),
+                message: if message.is_empty() {
+                    core::ptr::null()
+                } else {
+                    message.as_ptr().cast()
+                },
+                message_length: message.len(),
+            },
+            PhantomData,
+        )
+    }
+
+    /// Returns the event of the record.
+    pub fn event(&self) -> &'a Event {
+        // Safety: The event is valid for `'a`.
+        unsafe { Event::borrow_from_ffi(self.0.event) }
+    }
+
+    /// Returns the formatted message of the record.
+    pub fn message(&self) -> &'a [u8] {
+        if self.0.message_length == 0 {
+            &[]
+        } else {
+            // Safety: The message is valid for `'a`.
+            unsafe { core::slice::from_raw_parts(self.0.message.cast(), self.0.message_length) }
+        }
+    }
+}
+
 /// Descriptor of a new span.
 #[derive(Debug)]
 #[repr(transparent)]
@@ -553,6 +858,13 @@ impl Span {
 
         Ok(Self(ctx.to_context(), span))
     }
+
+    /// Creates a span that is not entered.
+    ///
+    /// Used in place of the spans that are compiled out, see [`STATIC_MAX_LEVEL`].
+    pub fn new_disabled(ctx: ContextView<'_>) -> Self {
+        Self(ctx.to_context(), core::ptr::null_mut())
+    }
 }
 
 // Safety: `Span` is `Send` and `Sync`.
@@ -563,6 +875,10 @@ unsafe impl Sync for Span {}
 
 impl Drop for Span {
     fn drop(&mut self) {
+        if self.1.is_null() {
+            return;
+        }
+
         // Safety: FFI call is safe.
         unsafe {
             to_result_indirect(|error| {
@@ -782,6 +1098,21 @@ pub trait Subscriber: Send + Sync {
         message: &[u8],
     );
 
+    /// Emits a batch of events.
+    ///
+    /// The events share the same `time`, and must be handled in order. The default implementation
+    /// emits them one at a time, with [`emit_event`](Subscriber::emit_event).
+    fn emit_event_batch(
+        &self,
+        time: Time,
+        call_stack: &mut Self::CallStack,
+        records: &[Record<'_>],
+    ) {
+        for record in records {
+            self.emit_event(time, call_stack, record.event(), record.message());
+        }
+    }
+
     /// Flushes the messages of the `Subscriber`.
     fn flush(&self);
 }
@@ -796,16 +1127,21 @@ impl OpaqueSubscriber {
     pub const fn from_ref<T: Subscriber>(subscriber: &'static T) -> Self {
         trait VTableProvider {
             const TABLE: bindings::FimoTracingSubscriberVTable;
+            const BATCH: bindings::FimoTracingSubscriberBatchExtension;
         }
         impl<T: Subscriber> VTableProvider for T {
             const TABLE: bindings::FimoTracingSubscriberVTable =
                 OpaqueSubscriber::build_vtable::<T>(None);
+            const BATCH: bindings::FimoTracingSubscriberBatchExtension =
+                OpaqueSubscriber::build_batch_extension::<T>();
         }
 
         let vtable: &'static bindings::FimoTracingSubscriberVTable = &<T as VTableProvider>::TABLE;
+        let batch: &'static bindings::FimoTracingSubscriberBatchExtension =
+            &<T as VTableProvider>::BATCH;
         Self(bindings::FimoTracingSubscriber {
             type_: bindings::FimoStructType::FIMO_STRUCT_TYPE_TRACING_SUBSCRIBER,
-            next: core::ptr::null(),
+            next: core::ptr::from_ref(batch).cast(),
             ptr: core::ptr::from_ref(subscriber).cast_mut().cast(),
             vtable: core::ptr::from_ref(vtable),
         })
@@ -815,10 +1151,13 @@ impl OpaqueSubscriber {
     pub fn from_box<T: Subscriber>(subscriber: Box<T>) -> Self {
         trait VTableProvider {
             const TABLE: bindings::FimoTracingSubscriberVTable;
+            const BATCH: bindings::FimoTracingSubscriberBatchExtension;
         }
         impl<T: Subscriber> VTableProvider for T {
             const TABLE: bindings::FimoTracingSubscriberVTable =
                 OpaqueSubscriber::build_vtable::<T>(Some(drop_box::<T>));
+            const BATCH: bindings::FimoTracingSubscriberBatchExtension =
+                OpaqueSubscriber::build_batch_extension::<T>();
         }
         unsafe extern "C" fn drop_box<T>(ptr: *mut core::ffi::c_void) {
             // Safety: We know that the type is right.
@@ -826,9 +1165,11 @@ impl OpaqueSubscriber {
         }
 
         let vtable: &'static bindings::FimoTracingSubscriberVTable = &<T as VTableProvider>::TABLE;
+        let batch: &'static bindings::FimoTracingSubscriberBatchExtension =
+            &<T as VTableProvider>::BATCH;
         Self(bindings::FimoTracingSubscriber {
             type_: bindings::FimoStructType::FIMO_STRUCT_TYPE_TRACING_SUBSCRIBER,
-            next: core::ptr::null(),
+            next: core::ptr::from_ref(batch).cast(),
             ptr: Box::into_raw(subscriber).cast(),
             vtable: core::ptr::from_ref(vtable),
         })
@@ -1004,6 +1345,33 @@ impl OpaqueSubscriber {
             flush: Some(flush::<T>),
         }
     }
+
+    const fn build_batch_extension<T: Subscriber>() -> bindings::FimoTracingSubscriberBatchExtension
+    {
+        unsafe extern "C" fn event_emit_batch<T: Subscriber>(
+            subscriber: *mut core::ffi::c_void,
+            time: *const bindings::FimoTime,
+            stack: *mut core::ffi::c_void,
+            records: *const bindings::FimoTracingRecord,
+            record_count: usize,
+        ) {
+            // Safety:
+            unsafe {
+                let subscriber: &T = &*subscriber.cast::<T>().cast_const();
+                let time = Time::from_ffi(*time);
+                let stack = &mut *stack.cast();
+                let records =
+                    core::slice::from_raw_parts(records.cast::<Record<'_>>(), record_count);
+                subscriber.emit_event_batch(time, stack, records);
+            }
+        }
+
+        bindings::FimoTracingSubscriberBatchExtension {
+            type_: bindings::FimoStructType::FIMO_STRUCT_TYPE_TRACING_SUBSCRIBER_BATCH_EXTENSION,
+            next: core::ptr::null(),
+            event_emit_batch: Some(event_emit_batch::<T>),
+        }
+    }
 }
 
 // Safety: A `Subscriber` is `Send` and `Sync`.
@@ -1129,3 +1497,88 @@ impl Write for Formatter<'_> {
         Ok(())
     }
 }
+
+/// Writes the bytes as a JSON string, replacing invalid UTF-8 sequences.
+fn write_json_string(out: &mut String, value: &[u8]) {
+    out.push('"');
+    for c in String::from_utf8_lossy(value).chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn features(levels: &[Level]) -> [bool; 6] {
+        let mut enabled = [false; 6];
+        for &level in levels {
+            enabled[level as usize] = true;
+        }
+        enabled
+    }
+
+    #[test]
+    fn static_max_level_features() {
+        const ALL: [Level; 6] = [
+            Level::Off,
+            Level::Error,
+            Level::Warn,
+            Level::Info,
+            Level::Debug,
+            Level::Trace,
+        ];
+
+        let select = |debug_assertions, max: &[Level], release_max: &[Level]| {
+            select_static_max_level(
+                debug_assertions,
+                feature_level(features(max)),
+                feature_level(features(release_max)),
+            )
+        };
+
+        for debug_assertions in [false, true] {
+            assert_eq!(select(debug_assertions, &[], &[]), Level::Trace);
+            for level in ALL {
+                assert_eq!(select(debug_assertions, &[level], &[]), level);
+            }
+
+            // The lowest level of the same kind is selected.
+            assert_eq!(
+                select(debug_assertions, &[Level::Debug, Level::Warn], &[]),
+                Level::Warn
+            );
+            assert_eq!(select(debug_assertions, &ALL, &[]), Level::Off);
+        }
+
+        // The release features are only applied without debug assertions.
+        for level in ALL {
+            assert_eq!(select(false, &[], &[level]), level);
+            assert_eq!(select(true, &[], &[level]), Level::Trace);
+        }
+        assert_eq!(
+            select(false, &[], &[Level::Trace, Level::Info]),
+            Level::Info
+        );
+
+        // A release feature takes precedence, even if its level is higher.
+        assert_eq!(select(false, &[Level::Off], &[Level::Trace]), Level::Trace);
+        assert_eq!(
+            select(false, &[Level::Debug], &[Level::Error]),
+            Level::Error
+        );
+        assert_eq!(select(true, &[Level::Off], &[Level::Trace]), Level::Off);
+        assert_eq!(select(true, &[Level::Debug], &[Level::Error]), Level::Debug);
+    }
+}
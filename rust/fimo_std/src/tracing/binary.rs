@@ -0,0 +1,624 @@
+//! Compact binary log files.
+use crate::{
+    error::{self, Error},
+    time::{Duration, Time},
+    tracing::{filter::is_descendant, Event, Level, Record, SpanDescriptor, Subscriber},
+};
+use alloc::{boxed::Box, collections::BTreeMap, ffi::CString, sync::Arc, vec::Vec};
+use core::ffi::CStr;
+use std::{fs::File, io, path::Path, sync::Mutex};
+
+/// Magic bytes at the start of a binary log file.
+const MAGIC: &[u8; 8] = b"FIMOLOG\0";
+
+/// Version of the binary log format.
+///
+/// Version 2 appends the message template and its fields to each record.
+const FORMAT_VERSION: u64 = 2;
+
+/// Entry appending a string to the string table.
+const TAG_STRING: u8 = 0;
+
+/// Entry containing a log record.
+const TAG_RECORD: u8 = 1;
+
+/// Maximum size of a string or message accepted by a [`LogReader`].
+const MAX_ENTRY_SIZE: u64 = 16 * 1024 * 1024;
+
+/// A [`Subscriber`] which writes the events to a compact binary log.
+///
+/// The log starts with a header identifying the format, followed by a sequence of entries. Each
+/// entry starts with a tag byte, and all integers are encoded as unsigned `LEB128` varints. The
+/// names, targets and file names of the events are written only once, as string entries, which
+/// are then referred to by their index in the string table. Each record entry contains the time
+/// at which the event was emitted, its level, the indices of its strings, its line number and its
+/// message. Events emitted with a [`MessageTemplate`](crate::tracing::MessageTemplate) also store
+/// the un-interpolated message, and the names and formatted values of its fields. The spans are
+/// not recorded. Errors while writing to the log are ignored.
+///
+/// The log can be read back with a [`LogReader`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use fimo_std::{
+///     context::ContextBuilder,
+///     emit_info,
+///     tracing::{BinaryFileSubscriber, Config, Level, OpaqueSubscriber, ThreadAccess},
+/// };
+///
+/// let subscriber = BinaryFileSubscriber::create("engine.fimolog").expect("could not create log");
+/// let subscriber = OpaqueSubscriber::from_box(Box::new(subscriber));
+/// let context = <ContextBuilder>::new()
+///     .with_tracing_config(Config::new(None, Some(Level::Trace), [subscriber]))
+///     .build()
+///     .expect("could not create context");
+/// let access = ThreadAccess::new(&context).expect("could not register thread");
+///
+/// emit_info!(context, "written to the log");
+/// ```
+#[derive(Debug)]
+pub struct BinaryFileSubscriber<W> {
+    state: Mutex<WriterState<W>>,
+}
+
+#[derive(Debug)]
+struct WriterState<W> {
+    writer: W,
+    strings: BTreeMap<Box<[u8]>, u64>,
+}
+
+impl<W: io::Write + Send> BinaryFileSubscriber<W> {
+    /// Constructs a new `BinaryFileSubscriber` writing to `writer`.
+    ///
+    /// Writes the header of the log to `writer`.
+    pub fn new(mut writer: W) -> Result<Self, Error> {
+        let mut header = MAGIC.to_vec();
+        write_varint(&mut header, FORMAT_VERSION);
+        writer.write_all(&header).map_err(Error::new)?;
+        Ok(Self {
+            state: Mutex::new(WriterState {
+                writer,
+                strings: BTreeMap::new(),
+            }),
+        })
+    }
+
+    /// Consumes the subscriber, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.state
+            .into_inner()
+            .expect("could not lock the writer")
+            .writer
+    }
+
+    fn write(&self, time: Time, records: &[Record<'_>]) {
+        let mut state = self.state.lock().expect("could not lock the writer");
+        let mut buffer = Vec::new();
+        for record in records {
+            state.encode_record(&mut buffer, time, record);
+        }
+        let _ = state.writer.write_all(&buffer);
+    }
+}
+
+impl BinaryFileSubscriber<io::BufWriter<File>> {
+    /// Constructs a new `BinaryFileSubscriber` writing to the file at `path`.
+    ///
+    /// The file is created if it does not exist, and is truncated otherwise.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let file = File::create(path).map_err(Error::new)?;
+        Self::new(io::BufWriter::new(file))
+    }
+}
+
+impl<W> WriterState<W> {
+    /// Returns the index of `string` in the string table, appending a string entry to `buffer`
+    /// if it is not contained.
+    fn intern(&mut self, buffer: &mut Vec<u8>, string: &[u8]) -> u64 {
+        if let Some(&index) = self.strings.get(string) {
+            return index;
+        }
+
+        let index = self.strings.len() as u64;
+        buffer.push(TAG_STRING);
+        write_varint(buffer, string.len() as u64);
+        buffer.extend_from_slice(string);
+        self.strings.insert(string.into(), index);
+        index
+    }
+
+    fn encode_record(&mut self, buffer: &mut Vec<u8>, time: Time, record: &Record<'_>) {
+        let metadata = record.event().metadata();
+        let name = self.intern(buffer, metadata.name().to_bytes());
+        let target = self.intern(buffer, metadata.target().to_bytes());
+        let file = metadata
+            .file_name()
+            .map_or(0, |x| self.intern(buffer, x.to_bytes()) + 1);
+        let time = time
+            .duration_since(&Time::UNIX_EPOCH)
+            .unwrap_or(Duration::ZERO);
+        let message = record.message();
+
+        // The strings of the template must be interned before the record is started.
+        let template = record.event().message_template();
+        let template_index =
+            template.map_or(0, |x| self.intern(buffer, x.message().to_bytes()) + 1);
+        let fields = template
+            .map(|x| x.fields())
+            .unwrap_or_default()
+            .iter()
+            .map(|field| {
+                let name = self.intern(buffer, field.name().to_bytes());
+                (name, field.value().unwrap_or_default())
+            })
+            .collect::<Vec<_>>();
+
+        buffer.push(TAG_RECORD);
+        write_varint(buffer, time.as_secs());
+        write_varint(buffer, time.subsec_nanos().into());
+        write_varint(buffer, level_to_u64(metadata.level()));
+        write_varint(buffer, name);
+        write_varint(buffer, target);
+        write_varint(buffer, file);
+        write_varint(
+            buffer,
+            metadata.line_number().map_or(0, |x| u64::from(x) + 1),
+        );
+        write_varint(buffer, message.len() as u64);
+        buffer.extend_from_slice(message);
+        write_varint(buffer, template_index);
+        write_varint(buffer, fields.len() as u64);
+        for (name, value) in fields {
+            write_varint(buffer, name);
+            write_varint(buffer, value.len() as u64);
+            buffer.extend_from_slice(&value);
+        }
+    }
+}
+
+impl<W: io::Write + Send> Subscriber for BinaryFileSubscriber<W> {
+    type CallStack = ();
+
+    fn create_call_stack(&self, _time: Time) -> Result<Box<Self::CallStack>, Error> {
+        Ok(Box::new(()))
+    }
+
+    fn drop_call_stack(&self, _call_stack: Box<Self::CallStack>) {}
+
+    fn destroy_call_stack(&self, _time: Time, _call_stack: Box<Self::CallStack>) {}
+
+    fn unblock_call_stack(&self, _time: Time, _call_stack: &mut Self::CallStack) {}
+
+    fn suspend_call_stack(&self, _time: Time, _call_stack: &mut Self::CallStack, _block: bool) {}
+
+    fn resume_call_stack(&self, _time: Time, _call_stack: &mut Self::CallStack) {}
+
+    fn create_span(
+        &self,
+        _time: Time,
+        _span_descriptor: &SpanDescriptor,
+        _message: &[u8],
+        _call_stack: &mut Self::CallStack,
+    ) -> error::Result {
+        Ok(())
+    }
+
+    fn drop_span(&self, _call_stack: &mut Self::CallStack) {}
+
+    fn destroy_span(&self, _time: Time, _call_stack: &mut Self::CallStack) {}
+
+    fn emit_event(
+        &self,
+        time: Time,
+        _call_stack: &mut Self::CallStack,
+        event: &Event,
+        message: &[u8],
+    ) {
+        self.write(time, &[Record::new(event, message)]);
+    }
+
+    fn emit_event_batch(
+        &self,
+        time: Time,
+        _call_stack: &mut Self::CallStack,
+        records: &[Record<'_>],
+    ) {
+        self.write(time, records);
+    }
+
+    fn flush(&self) {
+        let mut state = self.state.lock().expect("could not lock the writer");
+        let _ = state.writer.flush();
+    }
+}
+
+/// A record read from a binary log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecord {
+    time: Time,
+    level: Level,
+    name: Arc<CStr>,
+    target: Arc<CStr>,
+    file: Option<Arc<CStr>>,
+    line: Option<u32>,
+    message: Vec<u8>,
+    template: Option<Arc<CStr>>,
+    fields: Vec<(Arc<CStr>, Vec<u8>)>,
+}
+
+impl LogRecord {
+    /// Returns the time at which the event was emitted.
+    pub fn time(&self) -> Time {
+        self.time
+    }
+
+    /// Returns the level of the event.
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    /// Returns the name of the event.
+    pub fn name(&self) -> &CStr {
+        &self.name
+    }
+
+    /// Returns the target of the event.
+    pub fn target(&self) -> &CStr {
+        &self.target
+    }
+
+    /// Returns the file name of the call site, if it was recorded.
+    pub fn file_name(&self) -> Option<&CStr> {
+        self.file.as_deref()
+    }
+
+    /// Returns the line number of the call site, if it was recorded.
+    pub fn line_number(&self) -> Option<u32> {
+        self.line
+    }
+
+    /// Returns the formatted message of the event.
+    pub fn message(&self) -> &[u8] {
+        &self.message
+    }
+
+    /// Returns the un-interpolated message, if the event was emitted with a message template.
+    pub fn message_template(&self) -> Option<&CStr> {
+        self.template.as_deref()
+    }
+
+    /// Returns the names and the formatted values of the fields of the message template.
+    ///
+    /// The values are cut off after 1024 bytes.
+    pub fn fields(&self) -> impl ExactSizeIterator<Item = (&CStr, &[u8])> + '_ {
+        self.fields
+            .iter()
+            .map(|(name, value)| (&**name, value.as_slice()))
+    }
+}
+
+/// Reader for the logs written by a [`BinaryFileSubscriber`].
+///
+/// The reader is an iterator over the records of the log, which are returned in the order they
+/// were written. The records can be filtered by their level and target, in which case the
+/// records not matching the filter are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::{
+///     time::Time,
+///     tracing::{BinaryFileSubscriber, Event, Level, LogReader, Metadata, Subscriber},
+/// };
+///
+/// const RENDERER: &Metadata = &Metadata::new(c"event", c"renderer", Level::Info, None, None);
+/// const AUDIO: &Metadata = &Metadata::new(c"event", c"audio", Level::Debug, None, None);
+///
+/// let subscriber = BinaryFileSubscriber::new(Vec::new()).expect("could not write the header");
+/// subscriber.emit_event(Time::now(), &mut (), &Event::new(RENDERER), b"frame rendered");
+/// subscriber.emit_event(Time::now(), &mut (), &Event::new(AUDIO), b"buffer queued");
+/// let log = subscriber.into_inner();
+///
+/// let records = LogReader::new(log.as_slice())
+///     .expect("could not read the header")
+///     .with_target(c"renderer")
+///     .collect::<Result<Vec<_>, _>>()
+///     .expect("could not read the records");
+/// assert_eq!(records.len(), 1);
+/// assert_eq!(records[0].level(), Level::Info);
+/// assert_eq!(records[0].message(), b"frame rendered");
+/// ```
+#[derive(Debug)]
+pub struct LogReader<R> {
+    reader: R,
+    version: u64,
+    strings: Vec<Arc<CStr>>,
+    max_level: Level,
+    target: Option<CString>,
+}
+
+impl<R: io::Read> LogReader<R> {
+    /// Constructs a new `LogReader` reading from `reader`.
+    ///
+    /// Returns an error if the stream does not start with the header of a binary log, or if the
+    /// log was written with an unsupported version of the format. Logs written with a previous
+    /// version of the format are still supported.
+    pub fn new(mut reader: R) -> Result<Self, Error> {
+        let mut magic = [0; MAGIC.len()];
+        reader.read_exact(&mut magic).map_err(Error::new)?;
+        if &magic != MAGIC {
+            return Err(Error::EINVAL);
+        }
+        let version = read_varint(&mut reader)?;
+        if !(1..=FORMAT_VERSION).contains(&version) {
+            return Err(Error::ENOTSUP);
+        }
+
+        Ok(Self {
+            reader,
+            version,
+            strings: Vec::new(),
+            max_level: Level::Trace,
+            target: None,
+        })
+    }
+
+    /// Only returns the records with a level of at most `level`.
+    pub fn with_max_level(mut self, level: Level) -> Self {
+        self.max_level = level;
+        self
+    }
+
+    /// Only returns the records whose target is `target`, or is nested below it.
+    pub fn with_target(mut self, target: &CStr) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    /// Reads the next record matching the filters.
+    ///
+    /// Returns `None` if the end of the log has been reached.
+    pub fn next_record(&mut self) -> Result<Option<LogRecord>, Error> {
+        loop {
+            let mut tag = [0];
+            match self.reader.read_exact(&mut tag) {
+                Ok(()) => {}
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+                Err(e) => return Err(Error::new(e)),
+            }
+
+            match tag[0] {
+                TAG_STRING => {
+                    let string = read_bytes(&mut self.reader)?;
+                    let string = CString::new(string).map_err(|_e| Error::EINVAL)?;
+                    self.strings.push(string.into());
+                }
+                TAG_RECORD => {
+                    let record = self.read_record()?;
+                    if self.matches(&record) {
+                        return Ok(Some(record));
+                    }
+                }
+                _ => return Err(Error::EINVAL),
+            }
+        }
+    }
+
+    fn read_record(&mut self) -> Result<LogRecord, Error> {
+        let secs = read_varint(&mut self.reader)?;
+        let nanos = u32::try_from(read_varint(&mut self.reader)?)
+            .ok()
+            .filter(|&x| x < 1_000_000_000)
+            .ok_or(Error::EINVAL)?;
+        let time = Time::UNIX_EPOCH
+            .checked_add(Duration::new(secs, nanos))
+            .ok_or(Error::EINVAL)?;
+        let level = level_from_u64(read_varint(&mut self.reader)?).ok_or(Error::EINVAL)?;
+        let name = self.string(read_varint(&mut self.reader)?)?;
+        let target = self.string(read_varint(&mut self.reader)?)?;
+        let file = match read_varint(&mut self.reader)? {
+            0 => None,
+            index => Some(self.string(index - 1)?),
+        };
+        let line = match read_varint(&mut self.reader)? {
+            0 => None,
+            line => Some(u32::try_from(line - 1).map_err(|_e| Error::EINVAL)?),
+        };
+        let message = read_bytes(&mut self.reader)?;
+
+        // Version 1 of the format does not store the message templates.
+        let mut template = None;
+        let mut fields = Vec::new();
+        if self.version >= 2 {
+            template = match read_varint(&mut self.reader)? {
+                0 => None,
+                index => Some(self.string(index - 1)?),
+            };
+            for _ in 0..read_varint(&mut self.reader)? {
+                let name = self.string(read_varint(&mut self.reader)?)?;
+                let value = read_bytes(&mut self.reader)?;
+                fields.push((name, value));
+            }
+        }
+
+        Ok(LogRecord {
+            time,
+            level,
+            name,
+            target,
+            file,
+            line,
+            message,
+            template,
+            fields,
+        })
+    }
+
+    fn string(&self, index: u64) -> Result<Arc<CStr>, Error> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|x| self.strings.get(x))
+            .cloned()
+            .ok_or(Error::EINVAL)
+    }
+
+    fn matches(&self, record: &LogRecord) -> bool {
+        if record.level > self.max_level {
+            return false;
+        }
+        match &self.target {
+            None => true,
+            Some(target) => {
+                let target = target.to_bytes();
+                let record_target = record.target.to_bytes();
+                record_target == target || is_descendant(record_target, target)
+            }
+        }
+    }
+}
+
+impl LogReader<io::BufReader<File>> {
+    /// Constructs a new `LogReader` reading from the file at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let file = File::open(path).map_err(Error::new)?;
+        Self::new(io::BufReader::new(file))
+    }
+}
+
+impl<R: io::Read> Iterator for LogReader<R> {
+    type Item = Result<LogRecord, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_record().transpose()
+    }
+}
+
+fn level_to_u64(level: Level) -> u64 {
+    match level {
+        Level::Off => 0,
+        Level::Error => 1,
+        Level::Warn => 2,
+        Level::Info => 3,
+        Level::Debug => 4,
+        Level::Trace => 5,
+    }
+}
+
+fn level_from_u64(level: u64) -> Option<Level> {
+    match level {
+        0 => Some(Level::Off),
+        1 => Some(Level::Error),
+        2 => Some(Level::Warn),
+        3 => Some(Level::Info),
+        4 => Some(Level::Debug),
+        5 => Some(Level::Trace),
+        _ => None,
+    }
+}
+
+fn write_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+fn read_varint(reader: &mut impl io::Read) -> Result<u64, Error> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0];
+        reader.read_exact(&mut byte).map_err(Error::new)?;
+        let bits = u64::from(byte[0] & 0x7F);
+        if bits << shift >> shift != bits {
+            return Err(Error::EINVAL);
+        }
+        value |= bits << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(Error::EINVAL)
+}
+
+fn read_bytes(reader: &mut impl io::Read) -> Result<Vec<u8>, Error> {
+    let size = read_varint(reader)?;
+    if size > MAX_ENTRY_SIZE {
+        return Err(Error::EINVAL);
+    }
+    let mut bytes = alloc::vec![0; size as usize];
+    reader.read_exact(&mut bytes).map_err(Error::new)?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bindings,
+        ffi::FFISharable,
+        tracing::{Field, MessageTemplate, Metadata},
+    };
+
+    const METADATA: &Metadata = &Metadata::new(c"event", c"assets", Level::Info, None, None);
+
+    #[test]
+    fn record_template() {
+        let fields = [Field::new(c"count", &5), Field::new(c"ms", &12.5)];
+        let template = MessageTemplate::new(c"loaded {count} assets in {ms} ms", &fields);
+        let event = Event(bindings::FimoTracingEvent {
+            next: template.share_to_ffi().cast(),
+            ..Event::new(METADATA).0
+        });
+
+        let subscriber = BinaryFileSubscriber::new(Vec::new()).expect("could not write the header");
+        subscriber.emit_event(Time::now(), &mut (), &event, b"loaded 5 assets in 12.5 ms");
+        subscriber.emit_event(Time::now(), &mut (), &Event::new(METADATA), b"plain");
+        let log = subscriber.into_inner();
+
+        let records = LogReader::new(log.as_slice())
+            .expect("could not read the header")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("could not read the records");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].message(), b"loaded 5 assets in 12.5 ms");
+        assert_eq!(
+            records[0].message_template(),
+            Some(c"loaded {count} assets in {ms} ms")
+        );
+        assert_eq!(
+            records[0].fields().collect::<Vec<_>>(),
+            [(c"count", &b"5"[..]), (c"ms", &b"12.5"[..])]
+        );
+        assert_eq!(records[1].message(), b"plain");
+        assert_eq!(records[1].message_template(), None);
+        assert_eq!(records[1].fields().len(), 0);
+    }
+
+    #[test]
+    fn read_version_1() {
+        let mut log = MAGIC.to_vec();
+        write_varint(&mut log, 1);
+        for string in [&b"event"[..], b"assets"] {
+            log.push(TAG_STRING);
+            write_varint(&mut log, string.len() as u64);
+            log.extend_from_slice(string);
+        }
+        log.push(TAG_RECORD);
+        for value in [0, 0, level_to_u64(Level::Info), 0, 1, 0, 0] {
+            write_varint(&mut log, value);
+        }
+        write_varint(&mut log, 5);
+        log.extend_from_slice(b"plain");
+
+        let records = LogReader::new(log.as_slice())
+            .expect("could not read the header")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("could not read the records");
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].target(), c"assets");
+        assert_eq!(records[0].message(), b"plain");
+        assert_eq!(records[0].message_template(), None);
+    }
+}
@@ -0,0 +1,257 @@
+//! Recording of tracing messages in the Chrome trace event format.
+use crate::{
+    error::{self, Error},
+    time::Time,
+    tracing::{write_json_string, Event, Level, Metadata, SpanDescriptor, Subscriber},
+};
+use alloc::{boxed::Box, string::String, sync::Arc};
+use core::{
+    fmt::Write,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use std::{io, path::Path, sync::Mutex};
+
+/// A [`Subscriber`] which records the spans and events in the Chrome trace event format.
+///
+/// Each call stack is recorded as its own track, named after the thread that created it. Spans
+/// are recorded as duration events, while events, and the suspension and resumption of a call
+/// stack, are recorded as instant events annotated with the name of the thread they occurred on.
+/// The recorded trace is kept in memory, until it is written with
+/// [`ChromeTraceSubscriber::finish`], and can then be opened with `chrome://tracing` or the
+/// Perfetto UI.
+///
+/// The subscriber is a handle to the shared trace, so a clone can be passed to the tracing
+/// subsystem, while the original is kept to write the trace at shutdown.
+///
+/// # Examples
+///
+/// ```no_run
+/// use fimo_std::{
+///     context::ContextBuilder,
+///     emit_info,
+///     tracing::{ChromeTraceSubscriber, Config, Level, OpaqueSubscriber, ThreadAccess},
+/// };
+///
+/// let trace = ChromeTraceSubscriber::new();
+/// let subscriber = OpaqueSubscriber::from_box(Box::new(trace.clone()));
+/// let context = <ContextBuilder>::new()
+///     .with_tracing_config(Config::new(None, Some(Level::Trace), [subscriber]))
+///     .build()
+///     .expect("could not create context");
+/// let access = ThreadAccess::new(&context).expect("could not register thread");
+///
+/// emit_info!(context, "recorded in the trace");
+///
+/// drop(access);
+/// drop(context);
+/// trace.finish("trace.json").expect("could not write trace");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChromeTraceSubscriber(Arc<TraceBuffer>);
+
+#[derive(Debug)]
+struct TraceBuffer {
+    pid: u32,
+    next_track: AtomicU64,
+    events: Mutex<String>,
+}
+
+impl ChromeTraceSubscriber {
+    /// Constructs a new `ChromeTraceSubscriber` with an empty trace.
+    pub fn new() -> Self {
+        Self(Arc::new(TraceBuffer {
+            pid: std::process::id(),
+            next_track: AtomicU64::new(0),
+            events: Mutex::new(String::new()),
+        }))
+    }
+
+    /// Writes the recorded trace to `writer`, and clears it.
+    pub fn write_to(&self, mut writer: impl io::Write) -> error::Result {
+        let events = core::mem::take(&mut *self.events());
+        let events = events.strip_prefix(',').unwrap_or(&events);
+        writeln!(writer, "{{\"traceEvents\":[{events}\n]}}").map_err(Error::new)?;
+        writer.flush().map_err(Error::new)
+    }
+
+    /// Writes the recorded trace to the file at `path`, and clears it.
+    ///
+    /// The file is created if it does not exist, and is truncated otherwise.
+    pub fn finish(&self, path: impl AsRef<Path>) -> error::Result {
+        let file = std::fs::File::create(path).map_err(Error::new)?;
+        self.write_to(io::BufWriter::new(file))
+    }
+
+    fn events(&self) -> std::sync::MutexGuard<'_, String> {
+        self.0.events.lock().expect("could not lock trace events")
+    }
+
+    /// Appends an event to the trace, with the `args` written by `f`.
+    fn record(
+        &self,
+        time: Time,
+        track: u64,
+        phase: char,
+        name: &[u8],
+        f: impl FnOnce(&mut String),
+    ) {
+        let timestamp = time
+            .duration_since(&Time::UNIX_EPOCH)
+            .map_or(0, |x| x.as_nanos());
+
+        let mut events = self.events();
+        let _ = write!(
+            events,
+            ",\n{{\"ph\":\"{phase}\",\"pid\":{},\"tid\":{track},\"ts\":{}.{:03},\"name\":",
+            self.0.pid,
+            timestamp / 1000,
+            timestamp % 1000,
+        );
+        write_json_string(&mut events, name);
+        if phase == 'i' {
+            events.push_str(",\"s\":\"t\"");
+        }
+        events.push_str(",\"args\":{");
+        f(&mut events);
+        events.push_str("}}");
+    }
+}
+
+impl Default for ChromeTraceSubscriber {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Call stack of a [`ChromeTraceSubscriber`].
+#[derive(Debug)]
+pub struct ChromeTraceCallStack {
+    track: u64,
+    thread: String,
+}
+
+impl Subscriber for ChromeTraceSubscriber {
+    type CallStack = ChromeTraceCallStack;
+
+    fn create_call_stack(&self, time: Time) -> Result<Box<Self::CallStack>, Error> {
+        let track = self.0.next_track.fetch_add(1, Ordering::Relaxed);
+        let thread = current_thread_name();
+        self.record(time, track, 'M', b"thread_name", |args| {
+            args.push_str("\"name\":");
+            let name = alloc::format!("{thread} (call stack {track})");
+            write_json_string(args, name.as_bytes());
+        });
+
+        Ok(Box::new(ChromeTraceCallStack { track, thread }))
+    }
+
+    fn drop_call_stack(&self, _call_stack: Box<Self::CallStack>) {}
+
+    fn destroy_call_stack(&self, _time: Time, _call_stack: Box<Self::CallStack>) {}
+
+    fn unblock_call_stack(&self, time: Time, call_stack: &mut Self::CallStack) {
+        self.record(time, call_stack.track, 'i', b"unblock", |args| {
+            write_thread(args, &current_thread_name());
+        });
+    }
+
+    fn suspend_call_stack(&self, time: Time, call_stack: &mut Self::CallStack, block: bool) {
+        let name: &[u8] = if block { b"block" } else { b"suspend" };
+        self.record(time, call_stack.track, 'i', name, |args| {
+            write_thread(args, &call_stack.thread);
+        });
+    }
+
+    fn resume_call_stack(&self, time: Time, call_stack: &mut Self::CallStack) {
+        call_stack.thread = current_thread_name();
+        self.record(time, call_stack.track, 'i', b"resume", |args| {
+            write_thread(args, &call_stack.thread);
+        });
+    }
+
+    fn create_span(
+        &self,
+        time: Time,
+        span_descriptor: &SpanDescriptor,
+        message: &[u8],
+        call_stack: &mut Self::CallStack,
+    ) -> error::Result {
+        let metadata = span_descriptor.metadata();
+        self.record(
+            time,
+            call_stack.track,
+            'B',
+            metadata.name().to_bytes(),
+            |args| {
+                write_thread(args, &call_stack.thread);
+                write_metadata(args, metadata, message);
+            },
+        );
+        Ok(())
+    }
+
+    fn drop_span(&self, _call_stack: &mut Self::CallStack) {}
+
+    fn destroy_span(&self, time: Time, call_stack: &mut Self::CallStack) {
+        self.record(time, call_stack.track, 'E', b"", |args| {
+            write_thread(args, &call_stack.thread);
+        });
+    }
+
+    fn emit_event(
+        &self,
+        time: Time,
+        call_stack: &mut Self::CallStack,
+        event: &Event,
+        message: &[u8],
+    ) {
+        let metadata = event.metadata();
+        self.record(
+            time,
+            call_stack.track,
+            'i',
+            metadata.name().to_bytes(),
+            |args| {
+                write_thread(args, &call_stack.thread);
+                write_metadata(args, metadata, message);
+            },
+        );
+    }
+
+    fn flush(&self) {}
+}
+
+fn current_thread_name() -> String {
+    let thread = std::thread::current();
+    match thread.name() {
+        Some(name) => name.into(),
+        None => alloc::format!("{:?}", thread.id()),
+    }
+}
+
+fn write_thread(args: &mut String, thread: &str) {
+    args.push_str("\"thread\":");
+    write_json_string(args, thread.as_bytes());
+}
+
+fn write_metadata(args: &mut String, metadata: &Metadata, message: &[u8]) {
+    let level = match metadata.level() {
+        Level::Off => "off",
+        Level::Error => "error",
+        Level::Warn => "warn",
+        Level::Info => "info",
+        Level::Debug => "debug",
+        Level::Trace => "trace",
+    };
+    let _ = write!(args, ",\"level\":\"{level}\",\"target\":");
+    write_json_string(args, metadata.target().to_bytes());
+    if let Some(file_name) = metadata.file_name() {
+        args.push_str(",\"file\":");
+        write_json_string(args, file_name.to_bytes());
+    }
+    if let Some(line_number) = metadata.line_number() {
+        let _ = write!(args, ",\"line\":{line_number}");
+    }
+    args.push_str(",\"message\":");
+    write_json_string(args, message);
+}
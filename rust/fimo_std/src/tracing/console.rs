@@ -0,0 +1,868 @@
+//! Customizable printing of tracing messages to the console.
+use crate::{
+    error::{self, Error},
+    time::Time,
+    tracing::{Event, Level, Record, SpanDescriptor, Subscriber},
+};
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    ffi::CString,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{ffi::CStr, fmt::Write as _, str::FromStr};
+use std::{
+    io::{self, IsTerminal, Write},
+    sync::{Arc, RwLock},
+};
+
+/// Stream the messages of a [`ConsoleSubscriber`] are written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ConsoleStream {
+    /// Writes all messages to `stdout`.
+    Stdout,
+    /// Writes all messages to `stderr`.
+    Stderr,
+    /// Writes errors to `stderr`, and all other messages to `stdout`.
+    #[default]
+    Split,
+}
+
+/// Controls the use of ANSI escape codes by a [`ConsoleSubscriber`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ColorMode {
+    /// Uses colors if the stream is a terminal.
+    ///
+    /// Follows the `NO_COLOR` and `CLICOLOR` conventions: A non-empty `NO_COLOR` variable disables
+    /// the colors, a `CLICOLOR_FORCE` variable, which is set to a value other than `0`, enables
+    /// them even if the stream is not a terminal, and `CLICOLOR=0` disables them.
+    #[default]
+    Auto,
+    /// Always uses colors.
+    Always,
+    /// Never uses colors.
+    Never,
+}
+
+/// Color of a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+    /// A 24-bit color, which is not supported by all terminals.
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    fn write_ansi(self, out: &mut String, background: bool) {
+        let offset = if background { 10 } else { 0 };
+        let code = match self {
+            Color::Black => 30,
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::White => 37,
+            Color::BrightBlack => 90,
+            Color::BrightRed => 91,
+            Color::BrightGreen => 92,
+            Color::BrightYellow => 93,
+            Color::BrightBlue => 94,
+            Color::BrightMagenta => 95,
+            Color::BrightCyan => 96,
+            Color::BrightWhite => 97,
+            Color::Rgb(r, g, b) => {
+                let _ = write!(out, "\x1b[{};2;{r};{g};{b}m", 38 + offset);
+                return;
+            }
+        };
+        let _ = write!(out, "\x1b[{}m", code + offset);
+    }
+}
+
+/// Style of a message, consisting of its colors and its weight.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::tracing::{Color, Style};
+///
+/// let style = Style::new()
+///     .with_foreground(Color::BrightWhite)
+///     .with_background(Color::Red)
+///     .with_bold(true);
+/// assert_ne!(style, Style::new());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Style {
+    foreground: Option<Color>,
+    background: Option<Color>,
+    bold: bool,
+}
+
+impl Style {
+    /// Constructs a new `Style` without any colors.
+    pub const fn new() -> Self {
+        Self {
+            foreground: None,
+            background: None,
+            bold: false,
+        }
+    }
+
+    /// Replaces the foreground color.
+    pub const fn with_foreground(mut self, color: Color) -> Self {
+        self.foreground = Some(color);
+        self
+    }
+
+    /// Replaces the background color.
+    pub const fn with_background(mut self, color: Color) -> Self {
+        self.background = Some(color);
+        self
+    }
+
+    /// Sets whether the message is printed in bold.
+    pub const fn with_bold(mut self, bold: bool) -> Self {
+        self.bold = bold;
+        self
+    }
+
+    /// Returns the foreground color.
+    pub fn foreground(&self) -> Option<Color> {
+        self.foreground
+    }
+
+    /// Returns the background color.
+    pub fn background(&self) -> Option<Color> {
+        self.background
+    }
+
+    /// Returns whether the message is printed in bold.
+    pub fn is_bold(&self) -> bool {
+        self.bold
+    }
+
+    fn is_plain(&self) -> bool {
+        *self == Self::new()
+    }
+
+    fn write_ansi(&self, out: &mut String) {
+        if self.bold {
+            out.push_str("\x1b[1m");
+        }
+        if let Some(color) = self.foreground {
+            color.write_ansi(out, false);
+        }
+        if let Some(color) = self.background {
+            color.write_ansi(out, true);
+        }
+    }
+}
+
+/// Styles of the levels of a [`ConsoleSubscriber`].
+///
+/// Besides the default theme, the themes [`ConsoleTheme::HIGH_CONTRAST`] and
+/// [`ConsoleTheme::COLOR_BLIND`] are provided. The styles of a theme can be adjusted with
+/// [`ConsoleTheme::with_level_style`].
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::tracing::{Color, ConsoleTheme, Level, Style};
+///
+/// let style = Style::new().with_foreground(Color::Cyan);
+/// let theme = ConsoleTheme::COLOR_BLIND.with_level_style(Level::Debug, style);
+/// assert_eq!(theme.level_style(Level::Debug), style);
+/// assert_eq!(theme.level_style(Level::Off), Style::new());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConsoleTheme {
+    error: Style,
+    warn: Style,
+    info: Style,
+    debug: Style,
+    trace: Style,
+}
+
+impl ConsoleTheme {
+    /// Default theme, which colors the levels red, yellow, green, blue and magenta.
+    pub const DEFAULT: Self = Self {
+        error: Style::new().with_foreground(Color::Red),
+        warn: Style::new().with_foreground(Color::Yellow),
+        info: Style::new().with_foreground(Color::Green),
+        debug: Style::new().with_foreground(Color::Blue),
+        trace: Style::new().with_foreground(Color::Magenta),
+    };
+
+    /// Theme using bright colors and backgrounds for the errors and warnings.
+    pub const HIGH_CONTRAST: Self = Self {
+        error: Style::new()
+            .with_foreground(Color::BrightWhite)
+            .with_background(Color::Red)
+            .with_bold(true),
+        warn: Style::new()
+            .with_foreground(Color::Black)
+            .with_background(Color::BrightYellow)
+            .with_bold(true),
+        info: Style::new().with_foreground(Color::BrightWhite),
+        debug: Style::new().with_foreground(Color::BrightCyan),
+        trace: Style::new().with_foreground(Color::White),
+    };
+
+    /// Theme using the colors of the Okabe-Ito palette, which remain distinguishable for the
+    /// common forms of color blindness.
+    ///
+    /// Errors and warnings are additionally printed in bold, so that they do not rely on their
+    /// color alone.
+    pub const COLOR_BLIND: Self = Self {
+        error: Style::new()
+            .with_foreground(Color::Rgb(213, 94, 0))
+            .with_bold(true),
+        warn: Style::new()
+            .with_foreground(Color::Rgb(230, 159, 0))
+            .with_bold(true),
+        info: Style::new().with_foreground(Color::Rgb(86, 180, 233)),
+        debug: Style::new().with_foreground(Color::Rgb(0, 158, 115)),
+        trace: Style::new().with_foreground(Color::Rgb(204, 121, 167)),
+    };
+
+    /// Replaces the style of a level.
+    ///
+    /// The style of [`Level::Off`] can not be changed.
+    pub const fn with_level_style(mut self, level: Level, style: Style) -> Self {
+        match level {
+            Level::Off => {}
+            Level::Error => self.error = style,
+            Level::Warn => self.warn = style,
+            Level::Info => self.info = style,
+            Level::Debug => self.debug = style,
+            Level::Trace => self.trace = style,
+        }
+        self
+    }
+
+    /// Returns the style of a level.
+    pub fn level_style(&self, level: Level) -> Style {
+        match level {
+            Level::Off => Style::new(),
+            Level::Error => self.error,
+            Level::Warn => self.warn,
+            Level::Info => self.info,
+            Level::Debug => self.debug,
+            Level::Trace => self.trace,
+        }
+    }
+}
+
+impl Default for ConsoleTheme {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+const ANSI_RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Literal(String),
+    Timestamp,
+    Level,
+    Channel,
+    Name,
+    Message,
+    File,
+    Line,
+    Location,
+    Span,
+    SpanArgs,
+    Spans,
+    Thread,
+}
+
+/// Format of the messages of a [`ConsoleSubscriber`].
+///
+/// A format is parsed from a template, where each `{token}` is replaced with a part of the
+/// message. A literal brace is written as `{{` or `}}`. The following tokens are available:
+///
+/// - `{timestamp}`: time of the message in RFC 3339 format, in UTC.
+/// - `{level}`: level of the message.
+/// - `{channel}` or `{target}`: target of the message, which is used as its channel.
+/// - `{name}`: name of the message.
+/// - `{message}`: formatted message.
+/// - `{file}` and `{line}`: source location of the message, or `unknown`.
+/// - `{location}`: shorthand for `{file}:{line}`.
+/// - `{span}` and `{span_args}`: name and message of the innermost span.
+/// - `{spans}`: names of all entered spans, starting with the outermost span.
+/// - `{thread}`: name of the thread emitting the message.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::tracing::ConsoleFormat;
+///
+/// let format: ConsoleFormat = "{timestamp} {level} [{channel}] {message}".parse().unwrap();
+/// assert!("{unknown}".parse::<ConsoleFormat>().is_err());
+/// # drop(format);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsoleFormat(Vec<Token>);
+
+impl ConsoleFormat {
+    /// Template of the default format.
+    pub const DEFAULT: &'static str = "{timestamp} {level} {channel}: {message}";
+
+    /// Parses a format from a template.
+    pub fn parse(template: &str) -> Result<Self, Error> {
+        let mut tokens = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.as_str().starts_with('{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.as_str().starts_with('}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let rest = chars.as_str();
+                    let end = rest
+                        .find('}')
+                        .ok_or_else(|| Error::new("unterminated format token"))?;
+                    let token = match &rest[..end] {
+                        "timestamp" => Token::Timestamp,
+                        "level" => Token::Level,
+                        "channel" | "target" => Token::Channel,
+                        "name" => Token::Name,
+                        "message" => Token::Message,
+                        "file" => Token::File,
+                        "line" => Token::Line,
+                        "location" => Token::Location,
+                        "span" => Token::Span,
+                        "span_args" => Token::SpanArgs,
+                        "spans" => Token::Spans,
+                        "thread" => Token::Thread,
+                        token => {
+                            return Err(Error::new(alloc::format!(
+                                "unknown format token `{{{token}}}`"
+                            )))
+                        }
+                    };
+                    chars = rest[end + 1..].chars();
+                    if !literal.is_empty() {
+                        tokens.push(Token::Literal(core::mem::take(&mut literal)));
+                    }
+                    tokens.push(token);
+                }
+                '}' => return Err(Error::new("unmatched `}` in format")),
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            tokens.push(Token::Literal(literal));
+        }
+
+        Ok(Self(tokens))
+    }
+}
+
+impl Default for ConsoleFormat {
+    fn default() -> Self {
+        Self::parse(Self::DEFAULT).expect("the default format should be valid")
+    }
+}
+
+impl FromStr for ConsoleFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// A [`Subscriber`] which prints the events to the console.
+///
+/// Each event is printed on its own line, according to a [`ConsoleFormat`]. The line is styled
+/// according to the level of the event and the [`ConsoleTheme`] of the subscriber, unless a style
+/// is assigned to its channel. Like in the [`ChannelFilter`](super::ChannelFilter), channels form
+/// a hierarchy separated by `::`, where a channel without a style uses the style of its closest
+/// ancestor. The styles and the color mode can be changed at runtime through a
+/// [`ConsoleStyleHandle`].
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::tracing::{
+///     Color, ConsoleStream, ConsoleSubscriber, ConsoleTheme, OpaqueSubscriber,
+/// };
+///
+/// let subscriber = ConsoleSubscriber::new()
+///     .with_format("{level} {spans} {message} ({location})".parse().unwrap())
+///     .with_stream(ConsoleStream::Stderr)
+///     .with_theme(ConsoleTheme::COLOR_BLIND)
+///     .with_channel_color(c"renderer", Color::Cyan);
+/// let handle = subscriber.style_handle();
+/// let subscriber = OpaqueSubscriber::from_box(Box::new(subscriber));
+///
+/// // Switch to another theme, while the subscriber is in use.
+/// handle.set_theme(ConsoleTheme::HIGH_CONTRAST);
+/// assert_eq!(handle.theme(), ConsoleTheme::HIGH_CONTRAST);
+/// # drop(subscriber);
+/// ```
+#[derive(Debug)]
+pub struct ConsoleSubscriber {
+    format: ConsoleFormat,
+    stream: ConsoleStream,
+    appearance: Arc<RwLock<Appearance>>,
+}
+
+impl ConsoleSubscriber {
+    /// Constructs a new `ConsoleSubscriber` with the default format and theme.
+    pub fn new() -> Self {
+        let appearance = Appearance {
+            color_mode: ColorMode::default(),
+            stdout_ansi: use_ansi(ColorMode::default(), &io::stdout()),
+            stderr_ansi: use_ansi(ColorMode::default(), &io::stderr()),
+            theme: ConsoleTheme::default(),
+            channel_styles: BTreeMap::new(),
+        };
+        Self {
+            format: ConsoleFormat::default(),
+            stream: ConsoleStream::default(),
+            appearance: Arc::new(RwLock::new(appearance)),
+        }
+    }
+
+    /// Replaces the format of the messages.
+    pub fn with_format(mut self, format: ConsoleFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Replaces the stream the messages are written to.
+    pub fn with_stream(mut self, stream: ConsoleStream) -> Self {
+        self.stream = stream;
+        self
+    }
+
+    /// Replaces the color mode.
+    pub fn with_color_mode(self, mode: ColorMode) -> Self {
+        self.style_handle().set_color_mode(mode);
+        self
+    }
+
+    /// Replaces the theme, which defines the style of each level.
+    pub fn with_theme(self, theme: ConsoleTheme) -> Self {
+        self.style_handle().set_theme(theme);
+        self
+    }
+
+    /// Replaces the style of a level in the theme.
+    pub fn with_level_style(self, level: Level, style: Style) -> Self {
+        self.style_handle().set_level_style(level, style);
+        self
+    }
+
+    /// Assigns a color to a channel and its descendants.
+    ///
+    /// Shorthand for assigning a style with only a foreground color.
+    pub fn with_channel_color(self, channel: &CStr, color: Color) -> Self {
+        self.with_channel_style(channel, Style::new().with_foreground(color))
+    }
+
+    /// Assigns a style to a channel and its descendants, replacing the style of the level.
+    pub fn with_channel_style(self, channel: &CStr, style: Style) -> Self {
+        self.style_handle().set_channel_style(channel, style);
+        self
+    }
+
+    /// Returns a handle for changing the styles and the color mode at runtime.
+    pub fn style_handle(&self) -> ConsoleStyleHandle {
+        ConsoleStyleHandle(self.appearance.clone())
+    }
+
+    /// Returns the format of the messages.
+    pub fn format(&self) -> &ConsoleFormat {
+        &self.format
+    }
+
+    /// Returns the stream the messages are written to.
+    pub fn stream(&self) -> ConsoleStream {
+        self.stream
+    }
+
+    /// Returns the color mode.
+    pub fn color_mode(&self) -> ColorMode {
+        self.style_handle().color_mode()
+    }
+
+    /// Returns the theme.
+    pub fn theme(&self) -> ConsoleTheme {
+        self.style_handle().theme()
+    }
+
+    /// Formats the line of an event, and returns whether it must be written to `stderr`.
+    fn format_line(
+        &self,
+        time: Time,
+        call_stack: &ConsoleCallStack,
+        event: &Event,
+        message: &[u8],
+    ) -> (bool, String) {
+        let level = event.metadata().level();
+        let to_stderr = match self.stream {
+            ConsoleStream::Stdout => false,
+            ConsoleStream::Stderr => true,
+            ConsoleStream::Split => level == Level::Error,
+        };
+        let style = {
+            let appearance = self.appearance.read().unwrap_or_else(|e| e.into_inner());
+            let ansi = if to_stderr {
+                appearance.stderr_ansi
+            } else {
+                appearance.stdout_ansi
+            };
+            if ansi {
+                appearance
+                    .channel_style(event.metadata().target().to_bytes())
+                    .unwrap_or_else(|| appearance.theme.level_style(level))
+            } else {
+                Style::new()
+            }
+        };
+
+        let mut line = String::new();
+        if !style.is_plain() {
+            style.write_ansi(&mut line);
+        }
+        self.format_event(&mut line, time, call_stack, event, message);
+        if !style.is_plain() {
+            line.push_str(ANSI_RESET);
+        }
+        line.push('\n');
+        (to_stderr, line)
+    }
+
+    fn format_event(
+        &self,
+        out: &mut String,
+        time: Time,
+        call_stack: &ConsoleCallStack,
+        event: &Event,
+        message: &[u8],
+    ) {
+        let metadata = event.metadata();
+        let span = call_stack.spans.last();
+        for token in &self.format.0 {
+            match token {
+                Token::Literal(x) => out.push_str(x),
+                Token::Timestamp => write_timestamp(out, time),
+                Token::Level => out.push_str(level_name(metadata.level())),
+                Token::Channel => out.push_str(&metadata.target().to_string_lossy()),
+                Token::Name => out.push_str(&metadata.name().to_string_lossy()),
+                Token::Message => out.push_str(&String::from_utf8_lossy(message)),
+                Token::File => write_file(out, metadata.file_name()),
+                Token::Line => write_line(out, metadata.line_number()),
+                Token::Location => {
+                    write_file(out, metadata.file_name());
+                    out.push(':');
+                    write_line(out, metadata.line_number());
+                }
+                Token::Span => {
+                    if let Some((name, _)) = span {
+                        out.push_str(&name.to_string_lossy());
+                    }
+                }
+                Token::SpanArgs => {
+                    if let Some((_, message)) = span {
+                        out.push_str(message);
+                    }
+                }
+                Token::Spans => {
+                    for (i, (name, _)) in call_stack.spans.iter().enumerate() {
+                        if i != 0 {
+                            out.push(':');
+                        }
+                        out.push_str(&name.to_string_lossy());
+                    }
+                }
+                Token::Thread => match std::thread::current().name() {
+                    Some(name) => out.push_str(name),
+                    None => {
+                        let _ = write!(out, "{:?}", std::thread::current().id());
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl Default for ConsoleSubscriber {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Handle for changing the appearance of a [`ConsoleSubscriber`] at runtime.
+///
+/// The changes apply to all messages printed afterward.
+#[derive(Debug, Clone)]
+pub struct ConsoleStyleHandle(Arc<RwLock<Appearance>>);
+
+impl ConsoleStyleHandle {
+    /// Replaces the color mode.
+    pub fn set_color_mode(&self, mode: ColorMode) {
+        let mut appearance = self.0.write().unwrap_or_else(|e| e.into_inner());
+        appearance.color_mode = mode;
+        appearance.stdout_ansi = use_ansi(mode, &io::stdout());
+        appearance.stderr_ansi = use_ansi(mode, &io::stderr());
+    }
+
+    /// Replaces the theme.
+    pub fn set_theme(&self, theme: ConsoleTheme) {
+        self.0.write().unwrap_or_else(|e| e.into_inner()).theme = theme;
+    }
+
+    /// Replaces the style of a level in the theme.
+    pub fn set_level_style(&self, level: Level, style: Style) {
+        let mut appearance = self.0.write().unwrap_or_else(|e| e.into_inner());
+        appearance.theme = appearance.theme.with_level_style(level, style);
+    }
+
+    /// Assigns a style to a channel and its descendants.
+    pub fn set_channel_style(&self, channel: &CStr, style: Style) {
+        self.0
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .channel_styles
+            .insert(channel.to_bytes().into(), style);
+    }
+
+    /// Removes the style of a channel, returning whether the channel had a style.
+    pub fn remove_channel_style(&self, channel: &CStr) -> bool {
+        self.0
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .channel_styles
+            .remove(channel.to_bytes())
+            .is_some()
+    }
+
+    /// Returns the color mode.
+    pub fn color_mode(&self) -> ColorMode {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).color_mode
+    }
+
+    /// Returns the theme.
+    pub fn theme(&self) -> ConsoleTheme {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).theme
+    }
+}
+
+#[derive(Debug)]
+struct Appearance {
+    color_mode: ColorMode,
+    stdout_ansi: bool,
+    stderr_ansi: bool,
+    theme: ConsoleTheme,
+    channel_styles: BTreeMap<Box<[u8]>, Style>,
+}
+
+impl Appearance {
+    fn channel_style(&self, mut channel: &[u8]) -> Option<Style> {
+        loop {
+            if let Some(style) = self.channel_styles.get(channel) {
+                return Some(*style);
+            }
+            match channel.windows(2).rposition(|w| w == b"::") {
+                Some(pos) => channel = &channel[..pos],
+                None => return None,
+            }
+        }
+    }
+}
+
+/// Call stack of a [`ConsoleSubscriber`].
+#[derive(Debug)]
+pub struct ConsoleCallStack {
+    spans: Vec<(CString, String)>,
+}
+
+impl Subscriber for ConsoleSubscriber {
+    type CallStack = ConsoleCallStack;
+
+    fn create_call_stack(&self, _time: Time) -> Result<Box<Self::CallStack>, Error> {
+        Ok(Box::new(ConsoleCallStack { spans: Vec::new() }))
+    }
+
+    fn drop_call_stack(&self, _call_stack: Box<Self::CallStack>) {}
+
+    fn destroy_call_stack(&self, _time: Time, _call_stack: Box<Self::CallStack>) {}
+
+    fn unblock_call_stack(&self, _time: Time, _call_stack: &mut Self::CallStack) {}
+
+    fn suspend_call_stack(&self, _time: Time, _call_stack: &mut Self::CallStack, _block: bool) {}
+
+    fn resume_call_stack(&self, _time: Time, _call_stack: &mut Self::CallStack) {}
+
+    fn create_span(
+        &self,
+        _time: Time,
+        span_descriptor: &SpanDescriptor,
+        message: &[u8],
+        call_stack: &mut Self::CallStack,
+    ) -> error::Result {
+        let name = span_descriptor.metadata().name().into();
+        let message = String::from_utf8_lossy(message).into_owned();
+        call_stack.spans.push((name, message));
+        Ok(())
+    }
+
+    fn drop_span(&self, call_stack: &mut Self::CallStack) {
+        call_stack.spans.pop();
+    }
+
+    fn destroy_span(&self, _time: Time, call_stack: &mut Self::CallStack) {
+        call_stack.spans.pop();
+    }
+
+    fn emit_event(
+        &self,
+        time: Time,
+        call_stack: &mut Self::CallStack,
+        event: &Event,
+        message: &[u8],
+    ) {
+        let (to_stderr, line) = self.format_line(time, call_stack, event, message);
+
+        // There is no one we could report the error to, so we ignore it.
+        if to_stderr {
+            let _ = io::stdout().flush();
+            let _ = io::stderr().lock().write_all(line.as_bytes());
+        } else {
+            let _ = io::stdout().lock().write_all(line.as_bytes());
+        }
+    }
+
+    fn emit_event_batch(
+        &self,
+        time: Time,
+        call_stack: &mut Self::CallStack,
+        records: &[Record<'_>],
+    ) {
+        // The streams are locked once, so that the batch is not interleaved with other messages.
+        let mut stdout = io::stdout().lock();
+        let mut stderr = io::stderr().lock();
+        for record in records {
+            let (to_stderr, line) =
+                self.format_line(time, call_stack, record.event(), record.message());
+            if to_stderr {
+                let _ = stdout.flush();
+                let _ = stderr.write_all(line.as_bytes());
+            } else {
+                let _ = stdout.write_all(line.as_bytes());
+            }
+        }
+    }
+
+    fn flush(&self) {
+        let _ = io::stdout().flush();
+        let _ = io::stderr().flush();
+    }
+}
+
+fn use_ansi(mode: ColorMode, stream: &impl IsTerminal) -> bool {
+    match mode {
+        ColorMode::Auto => {
+            let var = |name: &str| std::env::var_os(name).filter(|x| !x.is_empty());
+            if var("NO_COLOR").is_some() {
+                false
+            } else if var("CLICOLOR_FORCE").is_some_and(|x| x != "0") {
+                true
+            } else if var("CLICOLOR").is_some_and(|x| x == "0") {
+                false
+            } else {
+                stream.is_terminal()
+            }
+        }
+        ColorMode::Always => true,
+        ColorMode::Never => false,
+    }
+}
+
+fn level_name(level: Level) -> &'static str {
+    match level {
+        Level::Off => "OFF",
+        Level::Error => "ERROR",
+        Level::Warn => "WARN",
+        Level::Info => "INFO",
+        Level::Debug => "DEBUG",
+        Level::Trace => "TRACE",
+    }
+}
+
+fn write_file(out: &mut String, file_name: Option<&CStr>) {
+    match file_name {
+        Some(file_name) => out.push_str(&file_name.to_string_lossy()),
+        None => out.push_str("unknown"),
+    }
+}
+
+fn write_line(out: &mut String, line_number: Option<u32>) {
+    match line_number {
+        Some(line_number) => out.push_str(&line_number.to_string()),
+        None => out.push_str("unknown"),
+    }
+}
+
+/// Writes the time as an RFC 3339 timestamp in UTC, with microsecond precision.
+fn write_timestamp(out: &mut String, time: Time) {
+    let since_epoch = time
+        .duration_since(&Time::UNIX_EPOCH)
+        .map_or(0, |x| x.as_nanos());
+    let seconds = since_epoch / 1_000_000_000;
+    let micros = (since_epoch % 1_000_000_000) / 1000;
+    let days = i64::try_from(seconds / 86400).unwrap_or(i64::MAX);
+    let seconds_of_day = seconds % 86400;
+
+    // Conversion of the days since the epoch to a civil date, see
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    let _ = write!(
+        out,
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{micros:06}Z",
+        seconds_of_day / 3600,
+        (seconds_of_day / 60) % 60,
+        seconds_of_day % 60,
+    );
+}
@@ -0,0 +1,829 @@
+//! Per-channel filtering of tracing messages.
+use crate::{
+    error::{self, Error},
+    module::{ModuleSubsystem, ParameterValue},
+    time::Time,
+    tracing::{Event, Level, Record, SpanDescriptor, Subscriber},
+};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    ffi::CString,
+    vec::Vec,
+};
+use core::ffi::CStr;
+use std::sync::RwLock;
+
+/// Channels whose verbosity is tied to a feature of a module.
+///
+/// While the feature is enabled, the level of each listed channel, and of its descendants, is
+/// raised to at least the specified level. The levels set through the other methods of the
+/// [`ChannelFilter`] are left untouched, so that disabling the feature restores the levels that
+/// are configured at that point, including the changes made while the feature was enabled.
+///
+/// A feature is usually bound to a module parameter with [`ChannelFilter::bind_parameter`], which
+/// enables it whenever the parameter is set to a non-zero value.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::tracing::{ChannelFeature, Level};
+///
+/// const GPU_VALIDATION: ChannelFeature = ChannelFeature::new(
+///     c"gpu_validation",
+///     &[(c"renderer::vulkan", Level::Debug), (c"renderer::shader", Level::Trace)],
+/// );
+/// assert_eq!(GPU_VALIDATION.name(), c"gpu_validation");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ChannelFeature {
+    name: &'static CStr,
+    channels: &'static [(&'static CStr, Level)],
+}
+
+impl ChannelFeature {
+    /// Constructs a new `ChannelFeature`.
+    pub const fn new(name: &'static CStr, channels: &'static [(&'static CStr, Level)]) -> Self {
+        Self { name, channels }
+    }
+
+    /// Returns the name of the feature.
+    pub fn name(&self) -> &'static CStr {
+        self.name
+    }
+
+    /// Returns the channels associated with the feature, along with their required level.
+    pub fn channels(&self) -> &'static [(&'static CStr, Level)] {
+        self.channels
+    }
+}
+
+/// Entry of the channel hierarchy of a [`ChannelFilter`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelInfo {
+    name: CString,
+    parent: Option<CString>,
+    description: Option<CString>,
+    level: Level,
+    explicit_level: Option<Level>,
+}
+
+impl ChannelInfo {
+    /// Returns the name of the channel.
+    pub fn name(&self) -> &CStr {
+        &self.name
+    }
+
+    /// Returns the name of the parent channel, if the channel is not a root.
+    pub fn parent(&self) -> Option<&CStr> {
+        self.parent.as_deref()
+    }
+
+    /// Returns the description the channel was registered with.
+    pub fn description(&self) -> Option<&CStr> {
+        self.description.as_deref()
+    }
+
+    /// Returns the effective level of the channel.
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    /// Returns the level set for the channel itself, if any.
+    pub fn explicit_level(&self) -> Option<Level> {
+        self.explicit_level
+    }
+}
+
+/// A [`Subscriber`] adapter which filters messages by the level of their channel.
+///
+/// The channel of a message is the target contained in its [`Metadata`](super::Metadata).
+/// Channels form a hierarchy separated by `::`, where a channel without an explicit level inherits
+/// the level of its closest ancestor, or the default level, if no ancestor specifies one. The
+/// levels can be modified at runtime, but are still bounded by the maximum level of the tracing
+/// subsystem.
+///
+/// By default, only the channels that are registered or have an explicit level are known to the
+/// filter. With [`ChannelFilter::with_auto_channels`], each target seen in a message is added to
+/// the hierarchy, along with its missing ancestors, so that the channels of a module path like
+/// `fimo_tasks::worker_group::event_loop` can be inspected and adjusted without registering them
+/// manually. In this mode, `.` is also accepted as a separator, e.g., the target `renderer.vulkan`
+/// maps to the channel `renderer::vulkan`.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::tracing::{ChannelFilter, ConsoleSubscriber, Level};
+///
+/// let filter = ChannelFilter::new(ConsoleSubscriber::new(), Level::Info).with_auto_channels(true);
+/// filter.set_channel_level(c"fimo_tasks", Level::Warn);
+///
+/// assert!(!filter.is_enabled(c"fimo_tasks::worker_group::event_loop", Level::Info));
+/// assert!(filter.is_enabled(c"renderer.vulkan", Level::Info));
+///
+/// let tree = filter.channel_tree();
+/// let names: Vec<_> = tree.iter().map(|x| x.name()).collect();
+/// assert_eq!(
+///     names,
+///     [
+///         c"fimo_tasks",
+///         c"fimo_tasks::worker_group",
+///         c"fimo_tasks::worker_group::event_loop",
+///         c"renderer",
+///         c"renderer::vulkan",
+///     ]
+/// );
+/// assert_eq!(tree[2].level(), Level::Warn);
+/// ```
+#[derive(Debug)]
+pub struct ChannelFilter<T> {
+    subscriber: T,
+    auto_channels: bool,
+    levels: RwLock<ChannelLevels>,
+}
+
+impl<T: Subscriber> ChannelFilter<T> {
+    /// Constructs a new `ChannelFilter` with the given default level.
+    pub fn new(subscriber: T, default_level: Level) -> Self {
+        Self {
+            subscriber,
+            auto_channels: false,
+            levels: RwLock::new(ChannelLevels {
+                default: default_level,
+                channels: BTreeMap::new(),
+                descriptions: BTreeMap::new(),
+                discovered: BTreeMap::new(),
+                features: BTreeMap::new(),
+                floors: BTreeMap::new(),
+                bindings: Vec::new(),
+            }),
+        }
+    }
+
+    /// Sets whether the channels are created automatically from the targets of the messages.
+    pub fn with_auto_channels(mut self, enabled: bool) -> Self {
+        self.auto_channels = enabled;
+        self
+    }
+
+    /// Returns a reference to the wrapped [`Subscriber`].
+    pub fn subscriber(&self) -> &T {
+        &self.subscriber
+    }
+
+    /// Returns whether the channels are created automatically from the targets of the messages.
+    pub fn auto_channels(&self) -> bool {
+        self.auto_channels
+    }
+
+    /// Returns the level of channels without an explicit level.
+    pub fn default_level(&self) -> Level {
+        self.levels().default
+    }
+
+    /// Sets the level of channels without an explicit level.
+    pub fn set_default_level(&self, level: Level) {
+        self.levels_mut().default = level;
+    }
+
+    /// Returns the effective level of a channel.
+    pub fn channel_level(&self, channel: &CStr) -> Level {
+        self.levels().level(channel.to_bytes())
+    }
+
+    /// Sets the level of a channel and its descendants, returning the previous explicit level.
+    pub fn set_channel_level(&self, channel: &CStr, level: Level) -> Option<Level> {
+        self.levels_mut()
+            .channels
+            .insert(channel.to_bytes().into(), level)
+    }
+
+    /// Removes the explicit level of a channel, returning it.
+    ///
+    /// Afterward, the channel inherits the level of its closest ancestor.
+    pub fn clear_channel_level(&self, channel: &CStr) -> Option<Level> {
+        self.levels_mut().channels.remove(channel.to_bytes())
+    }
+
+    /// Sets the level of a channel and of all its descendants.
+    ///
+    /// Unlike [`ChannelFilter::set_channel_level`], this also replaces the explicit levels of the
+    /// descendants.
+    pub fn set_channel_level_recursive(&self, channel: &CStr, level: Level) {
+        let channel = channel.to_bytes();
+        let mut levels = self.levels_mut();
+        for (name, x) in levels.channels.iter_mut() {
+            if is_descendant(name, channel) {
+                *x = level;
+            }
+        }
+        levels.channels.insert(channel.into(), level);
+    }
+
+    /// Sets the level of all known channels matching a glob pattern, returning their number.
+    ///
+    /// In the pattern, `*` matches any sequence of characters, including `::`, while `?` matches
+    /// a single character. The known channels are the ones listed by
+    /// [`ChannelFilter::channel_tree`].
+    pub fn set_matching_channel_level(&self, pattern: &CStr, level: Level) -> usize {
+        let mut levels = self.levels_mut();
+        let matching: Vec<_> = levels
+            .known_channels()
+            .into_iter()
+            .filter(|x| glob_matches(pattern.to_bytes(), x))
+            .collect();
+        for channel in &matching {
+            levels.channels.insert(channel.clone(), level);
+        }
+        matching.len()
+    }
+
+    /// Registers a channel with a description.
+    ///
+    /// Registered channels are listed by [`ChannelFilter::channel_tree`], even if they have no
+    /// explicit level. Registering a channel again replaces its description.
+    pub fn register_channel(&self, channel: &CStr, description: Option<&CStr>) {
+        self.levels_mut().descriptions.insert(
+            channel.to_bytes().into(),
+            description.map(|x| x.to_bytes().into()),
+        );
+    }
+
+    /// Returns the hierarchy of the known channels.
+    ///
+    /// The known channels are the registered ones, the ones with an explicit level, the ones
+    /// created automatically from the targets of the messages, and their ancestors. The entries are
+    /// sorted by name, so that each parent precedes its children.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fimo_std::tracing::{ChannelFilter, ConsoleSubscriber, Level};
+    ///
+    /// let filter = ChannelFilter::new(ConsoleSubscriber::new(), Level::Warn);
+    /// filter.register_channel(c"renderer::vulkan", Some(c"Vulkan backend"));
+    /// filter.set_channel_level_recursive(c"renderer", Level::Debug);
+    ///
+    /// let tree = filter.channel_tree();
+    /// assert_eq!(tree[0].name(), c"renderer");
+    /// assert_eq!(tree[1].parent(), Some(c"renderer"));
+    /// assert_eq!(tree[1].description(), Some(c"Vulkan backend"));
+    /// assert_eq!(tree[1].level(), Level::Debug);
+    /// ```
+    pub fn channel_tree(&self) -> Vec<ChannelInfo> {
+        let levels = self.levels();
+        levels
+            .known_channels()
+            .into_iter()
+            .map(|channel| {
+                let to_cstring =
+                    |x: &[u8]| CString::new(x).expect("channel names should not contain nul bytes");
+                ChannelInfo {
+                    name: to_cstring(&channel),
+                    parent: parent_channel(&channel).map(to_cstring),
+                    description: levels
+                        .descriptions
+                        .get(&channel)
+                        .and_then(|x| x.as_deref())
+                        .map(to_cstring),
+                    level: levels.level(&channel),
+                    explicit_level: levels.channels.get(&channel).copied(),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns whether a feature is currently enabled.
+    pub fn is_feature_enabled(&self, feature: &CStr) -> bool {
+        self.levels().features.contains_key(feature.to_bytes())
+    }
+
+    /// Enables or disables the channels associated with a feature.
+    ///
+    /// Enabling an already enabled feature, or disabling an already disabled feature, is a no-op.
+    pub fn set_feature_enabled(&self, feature: &ChannelFeature, enabled: bool) {
+        let mut levels = self.levels_mut();
+        let name = feature.name.to_bytes();
+        let changed = if enabled {
+            levels.features.insert(name.into(), *feature).is_none()
+        } else {
+            levels.features.remove(name).is_some()
+        };
+        if changed {
+            levels.update_floors();
+        }
+    }
+
+    /// Binds a feature to a module parameter.
+    ///
+    /// The feature is enabled while the parameter is set to a non-zero value. A feature may be
+    /// bound to multiple parameters, in which case it is enabled while any of them is set to a
+    /// non-zero value. The filter is notified of the changes to the value through
+    /// [`ChannelFilter::parameter_changed`], which is usually called from the `on_change`
+    /// callback of the parameter, and through [`ChannelFilter::sync_parameters`], which reads
+    /// the current values of all bound parameters, e.g., after the modules have been loaded, or
+    /// the configuration was reloaded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fimo_std::module::ParameterValue;
+    /// use fimo_std::tracing::{ChannelFeature, ChannelFilter, ConsoleSubscriber, Level};
+    ///
+    /// const GPU_VALIDATION: ChannelFeature =
+    ///     ChannelFeature::new(c"gpu_validation", &[(c"renderer::vulkan", Level::Debug)]);
+    ///
+    /// let filter = ChannelFilter::new(ConsoleSubscriber::new(), Level::Info);
+    /// filter.bind_parameter(GPU_VALIDATION, c"renderer", c"gpu_validation");
+    ///
+    /// filter.parameter_changed(c"renderer", c"gpu_validation", ParameterValue::U8(1));
+    /// assert_eq!(filter.channel_level(c"renderer::vulkan"), Level::Debug);
+    ///
+    /// filter.parameter_changed(c"renderer", c"gpu_validation", ParameterValue::U8(0));
+    /// assert_eq!(filter.channel_level(c"renderer::vulkan"), Level::Info);
+    /// ```
+    pub fn bind_parameter(&self, feature: ChannelFeature, module: &CStr, parameter: &CStr) {
+        self.levels_mut().bindings.push(ParameterBinding {
+            module: module.into(),
+            parameter: parameter.into(),
+            feature,
+            enabled: false,
+        });
+    }
+
+    /// Updates the features bound to a module parameter to a new value of the parameter.
+    ///
+    /// Returns the number of features bound to the parameter.
+    pub fn parameter_changed(
+        &self,
+        module: &CStr,
+        parameter: &CStr,
+        value: ParameterValue,
+    ) -> usize {
+        let enabled = parameter_enabled(value);
+        let mut levels = self.levels_mut();
+        let mut features = Vec::new();
+        for binding in &mut levels.bindings {
+            if &*binding.module == module && &*binding.parameter == parameter {
+                binding.enabled = enabled;
+                features.push(binding.feature);
+            }
+        }
+
+        let mut changed = false;
+        for feature in &features {
+            let name = feature.name.to_bytes();
+            // The feature stays enabled while any of its bound parameters is non-zero.
+            let enabled = levels
+                .bindings
+                .iter()
+                .any(|x| x.enabled && x.feature.name.to_bytes() == name);
+            changed |= if enabled {
+                levels.features.insert(name.into(), *feature).is_none()
+            } else {
+                levels.features.remove(name).is_some()
+            };
+        }
+        if changed {
+            levels.update_floors();
+        }
+        features.len()
+    }
+
+    /// Reads the values of all bound parameters, and updates the features accordingly.
+    ///
+    /// The parameters are read with a public access. The features of the parameters that can not
+    /// be read are left unchanged, and the last of the encountered errors is returned.
+    pub fn sync_parameters(&self, ctx: &impl ModuleSubsystem) -> error::Result {
+        let bindings: Vec<_> = self
+            .levels()
+            .bindings
+            .iter()
+            .map(|x| (x.module.clone(), x.parameter.clone()))
+            .collect();
+
+        let mut result = Ok(());
+        for (module, parameter) in bindings {
+            match ParameterValue::read_public(ctx, &module, &parameter) {
+                Ok(value) => {
+                    self.parameter_changed(&module, &parameter, value);
+                }
+                Err(e) => result = Err(e),
+            }
+        }
+        result
+    }
+
+    /// Returns whether a message with the given target and level is forwarded.
+    ///
+    /// With [`ChannelFilter::with_auto_channels`], this creates the channel of the target, if it
+    /// is not yet known.
+    pub fn is_enabled(&self, target: &CStr, level: Level) -> bool {
+        let target = target.to_bytes();
+        if !self.auto_channels {
+            return level <= self.levels().level(target);
+        }
+
+        {
+            let levels = self.levels();
+            if let Some(channel) = levels.discovered.get(target) {
+                return level <= levels.level(channel);
+            }
+        }
+
+        // The target is seen for the first time.
+        let mut levels = self.levels_mut();
+        let channel = levels
+            .discovered
+            .entry(target.into())
+            .or_insert_with(|| channel_from_target(target))
+            .clone();
+        level <= levels.level(&channel)
+    }
+
+    fn levels(&self) -> std::sync::RwLockReadGuard<'_, ChannelLevels> {
+        self.levels.read().expect("could not lock channel levels")
+    }
+
+    fn levels_mut(&self) -> std::sync::RwLockWriteGuard<'_, ChannelLevels> {
+        self.levels.write().expect("could not lock channel levels")
+    }
+}
+
+/// Call stack of a [`ChannelFilter`] or a [`Router`](super::Router).
+#[derive(Debug)]
+pub struct FilteredCallStack<T> {
+    pub(super) inner: Box<T>,
+    // Whether each span of the stack was forwarded to the inner subscriber.
+    pub(super) spans: Vec<bool>,
+}
+
+impl<T: Subscriber> Subscriber for ChannelFilter<T> {
+    type CallStack = FilteredCallStack<T::CallStack>;
+
+    fn create_call_stack(&self, time: Time) -> Result<Box<Self::CallStack>, Error> {
+        let inner = self.subscriber.create_call_stack(time)?;
+        Ok(Box::new(FilteredCallStack {
+            inner,
+            spans: Vec::new(),
+        }))
+    }
+
+    fn drop_call_stack(&self, call_stack: Box<Self::CallStack>) {
+        self.subscriber.drop_call_stack(call_stack.inner);
+    }
+
+    fn destroy_call_stack(&self, time: Time, call_stack: Box<Self::CallStack>) {
+        self.subscriber.destroy_call_stack(time, call_stack.inner);
+    }
+
+    fn unblock_call_stack(&self, time: Time, call_stack: &mut Self::CallStack) {
+        self.subscriber
+            .unblock_call_stack(time, &mut call_stack.inner);
+    }
+
+    fn suspend_call_stack(&self, time: Time, call_stack: &mut Self::CallStack, block: bool) {
+        self.subscriber
+            .suspend_call_stack(time, &mut call_stack.inner, block);
+    }
+
+    fn resume_call_stack(&self, time: Time, call_stack: &mut Self::CallStack) {
+        self.subscriber
+            .resume_call_stack(time, &mut call_stack.inner);
+    }
+
+    fn create_span(
+        &self,
+        time: Time,
+        span_descriptor: &SpanDescriptor,
+        message: &[u8],
+        call_stack: &mut Self::CallStack,
+    ) -> error::Result {
+        let metadata = span_descriptor.metadata();
+        let forward = self.is_enabled(metadata.target(), metadata.level());
+        if forward {
+            self.subscriber
+                .create_span(time, span_descriptor, message, &mut call_stack.inner)?;
+        }
+        call_stack.spans.push(forward);
+        Ok(())
+    }
+
+    fn drop_span(&self, call_stack: &mut Self::CallStack) {
+        if call_stack.spans.pop().expect("span stack is empty") {
+            self.subscriber.drop_span(&mut call_stack.inner);
+        }
+    }
+
+    fn destroy_span(&self, time: Time, call_stack: &mut Self::CallStack) {
+        if call_stack.spans.pop().expect("span stack is empty") {
+            self.subscriber.destroy_span(time, &mut call_stack.inner);
+        }
+    }
+
+    fn emit_event(
+        &self,
+        time: Time,
+        call_stack: &mut Self::CallStack,
+        event: &Event,
+        message: &[u8],
+    ) {
+        let metadata = event.metadata();
+        if self.is_enabled(metadata.target(), metadata.level()) {
+            self.subscriber
+                .emit_event(time, &mut call_stack.inner, event, message);
+        }
+    }
+
+    fn emit_event_batch(
+        &self,
+        time: Time,
+        call_stack: &mut Self::CallStack,
+        records: &[Record<'_>],
+    ) {
+        let records = records
+            .iter()
+            .filter(|x| {
+                let metadata = x.event().metadata();
+                self.is_enabled(metadata.target(), metadata.level())
+            })
+            .copied()
+            .collect::<Vec<_>>();
+        if !records.is_empty() {
+            self.subscriber
+                .emit_event_batch(time, &mut call_stack.inner, &records);
+        }
+    }
+
+    fn flush(&self) {
+        self.subscriber.flush();
+    }
+}
+
+#[derive(Debug)]
+struct ChannelLevels {
+    default: Level,
+    channels: BTreeMap<Box<[u8]>, Level>,
+    descriptions: BTreeMap<Box<[u8]>, Option<Box<[u8]>>>,
+    // Channels created from the targets of the messages, keyed by the target.
+    discovered: BTreeMap<Box<[u8]>, Box<[u8]>>,
+    // Enabled features, keyed by their name.
+    features: BTreeMap<Box<[u8]>, ChannelFeature>,
+    // Minimum levels of the channels required by the enabled features.
+    floors: BTreeMap<Box<[u8]>, Level>,
+    bindings: Vec<ParameterBinding>,
+}
+
+#[derive(Debug)]
+struct ParameterBinding {
+    module: CString,
+    parameter: CString,
+    feature: ChannelFeature,
+    // Whether the parameter was last set to a non-zero value.
+    enabled: bool,
+}
+
+impl ChannelLevels {
+    fn level(&self, channel: &[u8]) -> Level {
+        let mut level = None;
+        let mut floor = None;
+        let mut current = Some(channel);
+        while let Some(channel) = current {
+            if level.is_none() {
+                level = self.channels.get(channel).copied();
+            }
+            floor = floor.max(self.floors.get(channel).copied());
+            if level.is_some() && self.floors.is_empty() {
+                break;
+            }
+            current = parent_channel(channel);
+        }
+
+        let level = level.unwrap_or(self.default);
+        floor.map_or(level, |floor| level.max(floor))
+    }
+
+    fn update_floors(&mut self) {
+        self.floors.clear();
+        for feature in self.features.values() {
+            for &(channel, level) in feature.channels {
+                let floor = self
+                    .floors
+                    .entry(channel.to_bytes().into())
+                    .or_insert(level);
+                *floor = level.max(*floor);
+            }
+        }
+    }
+
+    fn known_channels(&self) -> BTreeSet<Box<[u8]>> {
+        let mut known = BTreeSet::new();
+        for mut channel in self
+            .channels
+            .keys()
+            .chain(self.descriptions.keys())
+            .chain(self.discovered.values())
+            .chain(self.floors.keys())
+            .map(|x| &**x)
+        {
+            while known.insert(channel.into()) {
+                match parent_channel(channel) {
+                    Some(parent) => channel = parent,
+                    None => break,
+                }
+            }
+        }
+        known
+    }
+}
+
+fn parameter_enabled(value: ParameterValue) -> bool {
+    match value {
+        ParameterValue::U8(x) => x != 0,
+        ParameterValue::U16(x) => x != 0,
+        ParameterValue::U32(x) => x != 0,
+        ParameterValue::U64(x) => x != 0,
+        ParameterValue::I8(x) => x != 0,
+        ParameterValue::I16(x) => x != 0,
+        ParameterValue::I32(x) => x != 0,
+        ParameterValue::I64(x) => x != 0,
+    }
+}
+
+fn parent_channel(channel: &[u8]) -> Option<&[u8]> {
+    channel
+        .windows(2)
+        .rposition(|w| w == b"::")
+        .map(|pos| &channel[..pos])
+}
+
+fn channel_from_target(target: &[u8]) -> Box<[u8]> {
+    let mut channel = Vec::with_capacity(target.len());
+    for &c in target {
+        match c {
+            b'.' => channel.extend_from_slice(b"::"),
+            c => channel.push(c),
+        }
+    }
+    channel.into()
+}
+
+pub(super) fn is_descendant(channel: &[u8], ancestor: &[u8]) -> bool {
+    channel
+        .strip_prefix(ancestor)
+        .is_some_and(|rest| rest.starts_with(b"::"))
+}
+
+fn glob_matches(pattern: &[u8], value: &[u8]) -> bool {
+    // Position of the last `*`, and the position in `value` it is currently matched up to.
+    let mut backtrack = None;
+    let (mut p, mut v) = (0, 0);
+    while v < value.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p, v));
+                p += 1;
+            }
+            Some(&c) if c == b'?' || c == value[v] => {
+                p += 1;
+                v += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    backtrack = Some((star, matched + 1));
+                    p = star + 1;
+                    v = matched + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tracing::ConsoleSubscriber;
+
+    const VALIDATION: ChannelFeature = ChannelFeature::new(
+        c"validation",
+        &[
+            (c"renderer", Level::Debug),
+            (c"renderer::shader", Level::Trace),
+        ],
+    );
+
+    const PROFILING: ChannelFeature =
+        ChannelFeature::new(c"profiling", &[(c"renderer::vulkan", Level::Trace)]);
+
+    fn filter() -> ChannelFilter<ConsoleSubscriber> {
+        ChannelFilter::new(ConsoleSubscriber::new(), Level::Info)
+    }
+
+    #[test]
+    fn feature_raises_levels() {
+        let filter = filter();
+        filter.set_feature_enabled(&VALIDATION, true);
+        assert!(filter.is_feature_enabled(c"validation"));
+        assert_eq!(filter.channel_level(c"renderer"), Level::Debug);
+        assert_eq!(filter.channel_level(c"renderer::vulkan"), Level::Debug);
+        assert_eq!(filter.channel_level(c"renderer::shader"), Level::Trace);
+        assert_eq!(filter.channel_level(c"audio"), Level::Info);
+        assert_eq!(filter.channel_tree()[0].explicit_level(), None);
+
+        // Levels above the required ones are kept.
+        filter.set_channel_level(c"renderer::vulkan", Level::Trace);
+        assert_eq!(filter.channel_level(c"renderer::vulkan"), Level::Trace);
+
+        filter.set_feature_enabled(&VALIDATION, false);
+        assert!(!filter.is_feature_enabled(c"validation"));
+        assert_eq!(filter.channel_level(c"renderer"), Level::Info);
+        assert_eq!(filter.channel_level(c"renderer::shader"), Level::Info);
+        assert_eq!(filter.channel_level(c"renderer::vulkan"), Level::Trace);
+    }
+
+    #[test]
+    fn feature_keeps_overrides() {
+        let filter = filter();
+        filter.set_channel_level(c"renderer", Level::Warn);
+        filter.set_feature_enabled(&VALIDATION, true);
+        assert_eq!(filter.channel_level(c"renderer"), Level::Debug);
+
+        // Changes made while the feature is enabled apply once it is disabled.
+        filter.set_channel_level(c"renderer", Level::Error);
+        filter.set_channel_level(c"renderer::shader", Level::Off);
+        filter.set_default_level(Level::Warn);
+        assert_eq!(filter.channel_level(c"renderer"), Level::Debug);
+        assert_eq!(filter.channel_level(c"renderer::shader"), Level::Trace);
+
+        filter.set_feature_enabled(&VALIDATION, false);
+        assert_eq!(filter.channel_level(c"renderer"), Level::Error);
+        assert_eq!(filter.channel_level(c"renderer::shader"), Level::Off);
+        assert_eq!(filter.channel_level(c"audio"), Level::Warn);
+    }
+
+    #[test]
+    fn overlapping_features() {
+        let filter = filter();
+        filter.set_feature_enabled(&VALIDATION, true);
+        filter.set_feature_enabled(&PROFILING, true);
+        filter.set_feature_enabled(&PROFILING, true);
+        assert_eq!(filter.channel_level(c"renderer::vulkan"), Level::Trace);
+
+        filter.set_feature_enabled(&PROFILING, false);
+        assert_eq!(filter.channel_level(c"renderer::vulkan"), Level::Debug);
+        filter.set_feature_enabled(&PROFILING, false);
+        assert!(filter.is_feature_enabled(c"validation"));
+
+        filter.set_feature_enabled(&VALIDATION, false);
+        assert_eq!(filter.channel_level(c"renderer::vulkan"), Level::Info);
+    }
+
+    #[test]
+    fn parameter_binding() {
+        let filter = filter();
+        filter.bind_parameter(VALIDATION, c"renderer", c"validation");
+        filter.bind_parameter(PROFILING, c"renderer", c"validation");
+
+        assert_eq!(
+            filter.parameter_changed(c"audio", c"validation", ParameterValue::U8(1)),
+            0
+        );
+        assert!(!filter.is_feature_enabled(c"validation"));
+
+        assert_eq!(
+            filter.parameter_changed(c"renderer", c"validation", ParameterValue::I32(-1)),
+            2
+        );
+        assert!(filter.is_feature_enabled(c"validation"));
+        assert!(filter.is_feature_enabled(c"profiling"));
+        assert_eq!(filter.channel_level(c"renderer::vulkan"), Level::Trace);
+
+        filter.parameter_changed(c"renderer", c"validation", ParameterValue::U64(0));
+        assert!(!filter.is_feature_enabled(c"validation"));
+        assert!(!filter.is_feature_enabled(c"profiling"));
+        assert_eq!(filter.channel_level(c"renderer::vulkan"), Level::Info);
+    }
+
+    #[test]
+    fn multiple_parameter_bindings() {
+        let filter = filter();
+        filter.bind_parameter(VALIDATION, c"renderer", c"validation");
+        filter.bind_parameter(VALIDATION, c"renderer", c"debug");
+
+        filter.parameter_changed(c"renderer", c"validation", ParameterValue::U8(1));
+        assert!(filter.is_feature_enabled(c"validation"));
+
+        // The feature is enabled while any of the bound parameters is non-zero.
+        filter.parameter_changed(c"renderer", c"debug", ParameterValue::U8(0));
+        assert!(filter.is_feature_enabled(c"validation"));
+        filter.parameter_changed(c"renderer", c"debug", ParameterValue::U8(1));
+        filter.parameter_changed(c"renderer", c"validation", ParameterValue::U8(0));
+        assert!(filter.is_feature_enabled(c"validation"));
+
+        filter.parameter_changed(c"renderer", c"debug", ParameterValue::U8(0));
+        assert!(!filter.is_feature_enabled(c"validation"));
+        assert_eq!(filter.channel_level(c"renderer"), Level::Info);
+    }
+}
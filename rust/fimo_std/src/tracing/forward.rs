@@ -0,0 +1,346 @@
+//! Forwarding of tracing messages between processes.
+use crate::{
+    context::ContextView,
+    error::{self, Error},
+    time::Time,
+    tracing::{Event, Level, Metadata, Record, SpanDescriptor, Subscriber, TracingSubsystem},
+};
+use alloc::{boxed::Box, collections::BTreeMap, ffi::CString, vec::Vec};
+use core::ffi::CStr;
+use std::{io, sync::Mutex};
+
+/// Maximum size of a serialized event accepted by a [`ForwardListener`].
+const MAX_FRAME_SIZE: usize = 16 * 1024 * 1024;
+
+/// A [`Subscriber`] which serializes the events onto a byte stream, e.g., a pipe or a socket.
+///
+/// The subscriber is intended for child processes, like an out-of-process module host, whose
+/// events are ingested by a [`ForwardListener`] in the parent process, so that the messages of
+/// all processes appear in a single stream. Only the events are forwarded, while the spans and
+/// call stacks remain local to the child. Errors while writing to the stream are ignored.
+///
+/// # Examples
+///
+/// ```no_run
+/// use fimo_std::{
+///     context::ContextBuilder,
+///     emit_info,
+///     tracing::{Config, ForwardingSubscriber, Level, OpaqueSubscriber, ThreadAccess},
+/// };
+///
+/// // In the child process, forward all events to the parent through the standard output.
+/// let subscriber = ForwardingSubscriber::new(std::io::stdout());
+/// let subscriber = OpaqueSubscriber::from_box(Box::new(subscriber));
+/// let context = <ContextBuilder>::new()
+///     .with_tracing_config(Config::new(None, Some(Level::Trace), [subscriber]))
+///     .build()
+///     .expect("could not create context");
+/// let access = ThreadAccess::new(&context).expect("could not register thread");
+///
+/// emit_info!(context, "forwarded to the parent");
+/// ```
+#[derive(Debug)]
+pub struct ForwardingSubscriber<W> {
+    writer: Mutex<W>,
+}
+
+impl<W: io::Write + Send> ForwardingSubscriber<W> {
+    /// Constructs a new `ForwardingSubscriber` writing to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer: Mutex::new(writer),
+        }
+    }
+
+    /// Consumes the subscriber, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner().expect("could not lock the writer")
+    }
+
+    fn write(&self, frames: &[u8]) {
+        let mut writer = self.writer.lock().expect("could not lock the writer");
+        let _ = writer.write_all(frames);
+    }
+}
+
+impl<W: io::Write + Send> Subscriber for ForwardingSubscriber<W> {
+    type CallStack = ();
+
+    fn create_call_stack(&self, _time: Time) -> Result<Box<Self::CallStack>, Error> {
+        Ok(Box::new(()))
+    }
+
+    fn drop_call_stack(&self, _call_stack: Box<Self::CallStack>) {}
+
+    fn destroy_call_stack(&self, _time: Time, _call_stack: Box<Self::CallStack>) {}
+
+    fn unblock_call_stack(&self, _time: Time, _call_stack: &mut Self::CallStack) {}
+
+    fn suspend_call_stack(&self, _time: Time, _call_stack: &mut Self::CallStack, _block: bool) {}
+
+    fn resume_call_stack(&self, _time: Time, _call_stack: &mut Self::CallStack) {}
+
+    fn create_span(
+        &self,
+        _time: Time,
+        _span_descriptor: &SpanDescriptor,
+        _message: &[u8],
+        _call_stack: &mut Self::CallStack,
+    ) -> error::Result {
+        Ok(())
+    }
+
+    fn drop_span(&self, _call_stack: &mut Self::CallStack) {}
+
+    fn destroy_span(&self, _time: Time, _call_stack: &mut Self::CallStack) {}
+
+    fn emit_event(
+        &self,
+        _time: Time,
+        _call_stack: &mut Self::CallStack,
+        event: &Event,
+        message: &[u8],
+    ) {
+        let mut frame = Vec::new();
+        encode_frame(&mut frame, event, message);
+        self.write(&frame);
+    }
+
+    fn emit_event_batch(
+        &self,
+        _time: Time,
+        _call_stack: &mut Self::CallStack,
+        records: &[Record<'_>],
+    ) {
+        let mut frames = Vec::new();
+        for record in records {
+            encode_frame(&mut frames, record.event(), record.message());
+        }
+        self.write(&frames);
+    }
+
+    fn flush(&self) {
+        let mut writer = self.writer.lock().expect("could not lock the writer");
+        let _ = writer.flush();
+    }
+}
+
+/// Ingests the events serialized by a [`ForwardingSubscriber`], and emits them with the tracing
+/// subsystem of the current process.
+///
+/// The channel of each event is remapped below the channel of the listener, e.g., an event of the
+/// `renderer` channel is emitted to `<channel>::renderer`, and its message is prefixed with the
+/// identity of the child, e.g., the name of the module it hosts. As the tracing subsystem requires
+/// the metadata of an event to be static, the listener allocates it once for each distinct call
+/// site of the child, and keeps it alive for the remainder of the process.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::{
+///     time::Time,
+///     tracing::{
+///         init_simple, Event, ForwardListener, ForwardingSubscriber, Level, Metadata, Subscriber,
+///     },
+/// };
+///
+/// const METADATA: &Metadata = &Metadata::new(c"event", c"renderer", Level::Info, None, None);
+///
+/// // Serialize an event, like the child process would.
+/// let subscriber = ForwardingSubscriber::new(Vec::new());
+/// subscriber.emit_event(Time::now(), &mut (), &Event::new(METADATA), b"frame rendered");
+/// let stream = subscriber.into_inner();
+///
+/// // Emits "module_host: frame rendered" to the `host::renderer` channel.
+/// let guard = init_simple(Level::Info).expect("could not initialize the tracing");
+/// let listener = ForwardListener::new(c"host", c"module_host");
+/// listener
+///     .listen(guard.context(), stream.as_slice())
+///     .expect("could not forward the events");
+/// ```
+#[derive(Debug)]
+pub struct ForwardListener {
+    channel: CString,
+    identity: CString,
+    call_sites: Mutex<BTreeMap<CallSite, &'static Metadata>>,
+}
+
+impl ForwardListener {
+    /// Constructs a new `ForwardListener`.
+    pub fn new(channel: &CStr, identity: &CStr) -> Self {
+        Self {
+            channel: channel.into(),
+            identity: identity.into(),
+            call_sites: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Returns the channel below which the events are emitted.
+    pub fn channel(&self) -> &CStr {
+        &self.channel
+    }
+
+    /// Returns the identity attached to the messages.
+    pub fn identity(&self) -> &CStr {
+        &self.identity
+    }
+
+    /// Reads and emits the events from `reader`, until the end of the stream is reached.
+    pub fn listen(&self, ctx: &ContextView<'_>, mut reader: impl io::Read) -> error::Result {
+        while self.forward_one(ctx, &mut reader)? {}
+        Ok(())
+    }
+
+    /// Reads and emits one event from `reader`.
+    ///
+    /// Returns `false` if the end of the stream has been reached.
+    pub fn forward_one(
+        &self,
+        ctx: &ContextView<'_>,
+        reader: &mut impl io::Read,
+    ) -> Result<bool, Error> {
+        let mut size = [0; 4];
+        match reader.read_exact(&mut size) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(false),
+            Err(e) => return Err(Error::new(e)),
+        }
+        let size = u32::from_le_bytes(size) as usize;
+        if size > MAX_FRAME_SIZE {
+            return Err(Error::EINVAL);
+        }
+        let mut frame = alloc::vec![0; size];
+        reader.read_exact(&mut frame).map_err(Error::new)?;
+
+        let (call_site, message) = self.decode_frame(&frame).ok_or(Error::EINVAL)?;
+        let event = Event::new(self.intern(call_site));
+        let message = [self.identity.to_bytes(), b": ", message].concat();
+        ctx.emit_event_batch(&[Record::new(&event, &message)])?;
+        Ok(true)
+    }
+
+    fn decode_frame<'a>(&self, mut frame: &'a [u8]) -> Option<(CallSite, &'a [u8])> {
+        let level = match read_u32(&mut frame)? {
+            0 => Level::Off,
+            1 => Level::Error,
+            2 => Level::Warn,
+            3 => Level::Info,
+            4 => Level::Debug,
+            5 => Level::Trace,
+            _ => return None,
+        };
+        let line = match read_u32(&mut frame)? {
+            u32::MAX => None,
+            line => Some(line),
+        };
+        let name = CString::new(read_bytes(&mut frame)?).ok()?;
+        let target = read_bytes(&mut frame)?;
+        let target = CString::new([self.channel.to_bytes(), b"::", target].concat()).ok()?;
+        let file = match read_bytes(&mut frame)? {
+            [] => None,
+            file => Some(CString::new(file).ok()?),
+        };
+        let message = read_bytes(&mut frame)?;
+        if !frame.is_empty() {
+            return None;
+        }
+
+        let call_site = CallSite {
+            level,
+            target,
+            name,
+            file,
+            line,
+        };
+        Some((call_site, message))
+    }
+
+    fn intern(&self, call_site: CallSite) -> &'static Metadata {
+        let mut call_sites = self
+            .call_sites
+            .lock()
+            .expect("could not lock the call sites");
+        call_site.intern(&mut call_sites)
+    }
+}
+
+/// Owned copy of the [`Metadata`] of an event.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) struct CallSite {
+    pub(super) level: Level,
+    pub(super) target: CString,
+    pub(super) name: CString,
+    pub(super) file: Option<CString>,
+    pub(super) line: Option<u32>,
+}
+
+impl CallSite {
+    /// Returns the static metadata of the call site, allocating it if it is not contained in
+    /// `call_sites`.
+    pub(super) fn intern(
+        self,
+        call_sites: &mut BTreeMap<CallSite, &'static Metadata>,
+    ) -> &'static Metadata {
+        if let Some(metadata) = call_sites.get(&self) {
+            return metadata;
+        }
+
+        let leak = |x: &CStr| -> &'static CStr { Box::leak(CString::from(x).into_boxed_c_str()) };
+        let metadata = Box::leak(Box::new(Metadata::new(
+            leak(&self.name),
+            leak(&self.target),
+            self.level,
+            self.file.as_deref().map(leak),
+            self.line,
+        )));
+        call_sites.insert(self, metadata);
+        metadata
+    }
+}
+
+/// Appends a length-prefixed frame, containing the metadata of the event and its message.
+fn encode_frame(buffer: &mut Vec<u8>, event: &Event, message: &[u8]) {
+    let metadata = event.metadata();
+    let level = match metadata.level() {
+        Level::Off => 0u32,
+        Level::Error => 1,
+        Level::Warn => 2,
+        Level::Info => 3,
+        Level::Debug => 4,
+        Level::Trace => 5,
+    };
+    let file = metadata.file_name().map_or(&[][..], |x| x.to_bytes());
+
+    let start = buffer.len();
+    buffer.extend_from_slice(&[0; 4]);
+    buffer.extend_from_slice(&level.to_le_bytes());
+    buffer.extend_from_slice(&metadata.line_number().unwrap_or(u32::MAX).to_le_bytes());
+    for bytes in [
+        metadata.name().to_bytes(),
+        metadata.target().to_bytes(),
+        file,
+        message,
+    ] {
+        buffer.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        buffer.extend_from_slice(bytes);
+    }
+
+    let size = (buffer.len() - start - 4) as u32;
+    buffer[start..start + 4].copy_from_slice(&size.to_le_bytes());
+}
+
+fn read_u32(frame: &mut &[u8]) -> Option<u32> {
+    let (value, rest) = frame.split_first_chunk::<4>()?;
+    *frame = rest;
+    Some(u32::from_le_bytes(*value))
+}
+
+fn read_bytes<'a>(frame: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let size = read_u32(frame)? as usize;
+    if frame.len() < size {
+        return None;
+    }
+    let (bytes, rest) = frame.split_at(size);
+    *frame = rest;
+    Some(bytes)
+}
@@ -0,0 +1,71 @@
+//! Helpers for setting up the tracing subsystem in standalone tools.
+use crate::{
+    context::{Context, ContextBuilder},
+    error::Error,
+    tracing::{default_subscriber, Config, Level, ThreadAccess, TracingSubsystem},
+};
+
+/// Name of the environment variable read by [`init_from_env`].
+pub const LEVEL_ENV_VAR: &str = "FIMO_LOG";
+
+/// Guard returned by [`init_simple`] and [`init_from_env`].
+///
+/// Owns the constructed [`Context`] and keeps the calling thread registered with its tracing
+/// subsystem. All subscribers are flushed when the guard is dropped.
+#[derive(Debug)]
+pub struct TracingGuard {
+    _access: ThreadAccess,
+    context: Context,
+}
+
+impl TracingGuard {
+    /// Returns the context that owns the tracing subsystem.
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+}
+
+impl Drop for TracingGuard {
+    fn drop(&mut self) {
+        // There is no one we could report the error to, so we ignore it.
+        let _ = self.context.flush();
+    }
+}
+
+/// Constructs a new [`Context`] whose tracing subsystem writes all messages up to `level` to the
+/// console, and registers the calling thread with it.
+///
+/// This is intended for small tools and tests, which don't require a custom tracing setup.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::{emit_info, tracing::{init_simple, Level}};
+///
+/// let guard = init_simple(Level::Info).expect("could not initialize the tracing");
+/// emit_info!(guard.context(), "hello from the default subscriber");
+/// ```
+pub fn init_simple(level: Level) -> Result<TracingGuard, Error> {
+    let context = <ContextBuilder>::new()
+        .with_tracing_config(Config::new(None, Some(level), [default_subscriber()]))
+        .build()?;
+    let access = ThreadAccess::new(&context)?;
+
+    Ok(TracingGuard {
+        _access: access,
+        context,
+    })
+}
+
+/// Like [`init_simple`], but reads the level from the [`LEVEL_ENV_VAR`] environment variable.
+///
+/// The variable must contain the name of a [`Level`], e.g. `FIMO_LOG=debug`. Defaults to
+/// [`Level::Error`] if the variable is not set.
+pub fn init_from_env() -> Result<TracingGuard, Error> {
+    let level = match std::env::var(LEVEL_ENV_VAR) {
+        Ok(level) => level.parse()?,
+        Err(std::env::VarError::NotPresent) => Level::Error,
+        Err(std::env::VarError::NotUnicode(_)) => return Err(Error::EINVAL),
+    };
+    init_simple(level)
+}
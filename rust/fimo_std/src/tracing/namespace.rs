@@ -0,0 +1,141 @@
+//! Per-module namespaces of the tracing channels.
+use crate::{
+    context::ContextView,
+    error,
+    module::ModuleInfoView,
+    tracing::{forward::CallSite, Event, MessageTemplate, Metadata, Record, TracingSubsystem},
+};
+use alloc::{collections::BTreeMap, ffi::CString, vec::Vec};
+use core::{ffi::CStr, fmt::Arguments};
+use std::sync::Mutex;
+
+/// Metadata of the remapped call sites, shared by all namespaces.
+static CALL_SITES: Mutex<BTreeMap<CallSite, &'static Metadata>> = Mutex::new(BTreeMap::new());
+
+/// Namespace of the tracing channels of a module.
+///
+/// The events emitted through the namespace are nested below a root channel named after the
+/// module, e.g., an event of the `renderer` channel emitted by the module `gfx` is emitted to the
+/// `gfx::renderer` channel, while events without a channel are emitted to `gfx`. This prevents
+/// collisions between the channels of different modules, and allows for changing the level of all
+/// channels of a module at once, with the [`set_channel_level_recursive`] method of a
+/// [`ChannelFilter`]. Channels which are already nested below the root are left untouched.
+///
+/// The namespace can be passed to the event macros in place of a context. As the tracing
+/// subsystem requires the metadata of an event to be static, the metadata of each remapped call
+/// site is allocated once, and is kept alive for the remainder of the process. Spans are not
+/// remapped, and are still created with the context of the module.
+///
+/// [`ChannelFilter`]: super::ChannelFilter
+/// [`set_channel_level_recursive`]: super::ChannelFilter::set_channel_level_recursive
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::{
+///     emit_info,
+///     module::{Module, PseudoModule},
+///     tracing::{init_simple, Level},
+/// };
+///
+/// let guard = init_simple(Level::Info).expect("could not initialize the tracing");
+/// let module = PseudoModule::new(guard.context()).expect("could not create module");
+///
+/// let channels = module.channels();
+/// assert_eq!(channels.root(), module.module_info().name());
+///
+/// // Emitted to the `<module>::renderer` channel.
+/// emit_info!(channels, target: "renderer", "frame rendered");
+/// ```
+#[derive(Debug)]
+pub struct ModuleChannels<'a> {
+    context: ContextView<'a>,
+    root: CString,
+}
+
+impl<'a> ModuleChannels<'a> {
+    /// Constructs the namespace of the module described by `module`.
+    pub fn new(context: ContextView<'a>, module: &ModuleInfoView<'_>) -> Self {
+        Self {
+            context,
+            root: module.name().into(),
+        }
+    }
+
+    /// Returns the root channel of the namespace.
+    pub fn root(&self) -> &CStr {
+        &self.root
+    }
+
+    /// Returns the context used to emit the events.
+    pub fn context(&self) -> ContextView<'a> {
+        self.context
+    }
+
+    /// Returns the name of `channel`, when nested below the root channel.
+    pub fn channel_name(&self, channel: &CStr) -> CString {
+        let root = self.root.to_bytes();
+        let channel = channel.to_bytes();
+        let is_nested = channel
+            .strip_prefix(root)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(b"::"));
+        if is_nested {
+            return CString::new(channel).expect("the channel should not contain a nul byte");
+        }
+
+        let name = match channel {
+            [] => root.into(),
+            _ => [root, b"::", channel].concat(),
+        };
+        CString::new(name).expect("the channel should not contain a nul byte")
+    }
+
+    /// Emits a new event to the namespaced channel.
+    ///
+    /// See [`TracingSubsystem::emit_event`].
+    pub fn emit_event(&self, event: &Event, arguments: Arguments<'_>) -> error::Result {
+        self.context.emit_event(&self.remap(event), arguments)
+    }
+
+    /// Emits a new event with a [`MessageTemplate`] to the namespaced channel.
+    ///
+    /// See [`TracingSubsystem::emit_event_template`].
+    pub fn emit_event_template(
+        &self,
+        event: &Event,
+        template: &MessageTemplate<'_>,
+    ) -> error::Result {
+        self.context
+            .emit_event_template(&self.remap(event), template)
+    }
+
+    /// Emits a batch of pre-formatted events to the namespaced channels.
+    ///
+    /// See [`TracingSubsystem::emit_event_batch`].
+    pub fn emit_event_batch(&self, records: &[Record<'_>]) -> error::Result {
+        let events = records
+            .iter()
+            .map(|record| self.remap(record.event()))
+            .collect::<Vec<_>>();
+        let records = records
+            .iter()
+            .zip(&events)
+            .map(|(record, event)| Record::new(event, record.message()))
+            .collect::<Vec<_>>();
+        self.context.emit_event_batch(&records)
+    }
+
+    fn remap(&self, event: &Event) -> Event {
+        let metadata = event.metadata();
+        let call_site = CallSite {
+            level: metadata.level(),
+            target: self.channel_name(metadata.target()),
+            name: metadata.name().into(),
+            file: metadata.file_name().map(CString::from),
+            line: metadata.line_number(),
+        };
+
+        let mut call_sites = CALL_SITES.lock().expect("could not lock the call sites");
+        Event::new(call_site.intern(&mut call_sites))
+    }
+}
@@ -0,0 +1,556 @@
+//! Export of tracing messages to an OpenTelemetry collector.
+use crate::{
+    error::{self, Error},
+    time::Time,
+    tracing::{
+        write_json_string, Event, Level, MessageTemplate, Metadata, SpanDescriptor, Subscriber,
+    },
+};
+use alloc::{boxed::Box, collections::BTreeMap, string::String, sync::Arc, vec::Vec};
+use core::{
+    fmt::Write as _,
+    hash::{BuildHasher, Hasher},
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+};
+use std::{
+    hash::RandomState,
+    io::{BufRead, BufReader, Write as _},
+    net::TcpStream,
+    sync::{mpsc, Mutex},
+    thread::JoinHandle,
+};
+
+/// Default number of spans or log records, after which a batch is exported.
+const DEFAULT_BATCH_SIZE: usize = 512;
+
+/// A [`Subscriber`] which exports the spans and events to an OpenTelemetry collector.
+///
+/// The messages are exported with the OTLP/HTTP protocol, using the JSON encoding. Spans are
+/// exported as OpenTelemetry spans, where the enclosing span of the call stack becomes the parent
+/// span, and each root span starts a new trace. Events are exported as log records, which are
+/// linked to the span they were emitted in. The target of a message is mapped to the
+/// instrumentation scope. Events emitted with a [`MessageTemplate`] additionally carry the
+/// un-interpolated message as the `message.template` attribute, and the formatted value of each
+/// field as an attribute named after the field.
+///
+/// The messages are collected into batches, which are sent by a background thread, once they
+/// reach the batch size, or the subscriber is flushed. Only unencrypted `http` endpoints are
+/// supported.
+///
+/// # Examples
+///
+/// ```no_run
+/// use fimo_std::{
+///     context::ContextBuilder,
+///     tracing::{Config, Level, OpaqueSubscriber, OtlpSubscriber},
+/// };
+///
+/// let subscriber = OtlpSubscriber::new("http://localhost:4318", "my_service")
+///     .expect("could not create subscriber")
+///     .with_batch_size(128);
+/// let context = <ContextBuilder>::new()
+///     .with_tracing_config(Config::new(
+///         None,
+///         Some(Level::Info),
+///         [OpaqueSubscriber::from_box(Box::new(subscriber))],
+///     ))
+///     .build()
+///     .expect("could not create context");
+/// ```
+#[derive(Debug)]
+pub struct OtlpSubscriber {
+    batch_size: usize,
+    pending: Mutex<Batch>,
+    ids: IdGenerator,
+    failed_exports: Arc<AtomicUsize>,
+    sender: Option<mpsc::Sender<ExportRequest>>,
+    exporter: Option<JoinHandle<()>>,
+}
+
+impl OtlpSubscriber {
+    /// Constructs a new `OtlpSubscriber`, which exports to the collector at `endpoint`.
+    ///
+    /// The endpoint has the form `http://host[:port][/path]`, where the port defaults to `4318`.
+    /// The messages are sent to the `/v1/traces` and `/v1/logs` paths below it. The
+    /// `service_name` is attached to the exported resource.
+    pub fn new(endpoint: &str, service_name: &str) -> Result<Self, Error> {
+        let endpoint = Endpoint::parse(endpoint)?;
+        let resource = {
+            let mut resource = String::from("{\"attributes\":[");
+            write_string_attribute(&mut resource, b"service.name", service_name.as_bytes());
+            resource.push_str("]}");
+            resource
+        };
+
+        let failed_exports = Arc::new(AtomicUsize::new(0));
+        let (sender, receiver) = mpsc::channel();
+        let exporter = std::thread::Builder::new()
+            .name("otlp exporter".into())
+            .spawn({
+                let failed_exports = failed_exports.clone();
+                move || export_loop(endpoint, resource, receiver, failed_exports)
+            })
+            .map_err(Error::new)?;
+
+        Ok(Self {
+            batch_size: DEFAULT_BATCH_SIZE,
+            pending: Mutex::new(Batch::default()),
+            ids: IdGenerator::new(),
+            failed_exports,
+            sender: Some(sender),
+            exporter: Some(exporter),
+        })
+    }
+
+    /// Sets the number of spans or log records, after which a batch is exported.
+    ///
+    /// Defaults to `512`.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Returns the number of batches that could not be delivered to the collector.
+    pub fn failed_exports(&self) -> usize {
+        self.failed_exports.load(Ordering::Relaxed)
+    }
+
+    fn push(&self, f: impl FnOnce(&mut Batch)) {
+        let mut pending = self.pending.lock().expect("could not lock pending batch");
+        f(&mut pending);
+        if pending.spans.len() >= self.batch_size || pending.logs.len() >= self.batch_size {
+            let batch = core::mem::take(&mut *pending);
+            drop(pending);
+            self.send(ExportRequest::Batch(batch));
+        }
+    }
+
+    fn send(&self, request: ExportRequest) {
+        if let Some(sender) = &self.sender {
+            // The exporter only stops once we drop the sender.
+            let _ = sender.send(request);
+        }
+    }
+}
+
+impl Drop for OtlpSubscriber {
+    fn drop(&mut self) {
+        let batch =
+            core::mem::take(&mut *self.pending.lock().expect("could not lock pending batch"));
+        self.send(ExportRequest::Batch(batch));
+
+        // Closing the channel stops the exporter, after it has sent the remaining batches.
+        drop(self.sender.take());
+        if let Some(exporter) = self.exporter.take() {
+            let _ = exporter.join();
+        }
+    }
+}
+
+/// Call stack of an [`OtlpSubscriber`].
+#[derive(Debug)]
+pub struct OtlpCallStack {
+    trace_id: u128,
+    spans: Vec<OpenSpan>,
+}
+
+#[derive(Debug)]
+struct OpenSpan {
+    id: u64,
+    start: Time,
+    name: String,
+    scope: String,
+    attributes: String,
+}
+
+impl Subscriber for OtlpSubscriber {
+    type CallStack = OtlpCallStack;
+
+    fn create_call_stack(&self, _time: Time) -> Result<Box<Self::CallStack>, Error> {
+        Ok(Box::new(OtlpCallStack {
+            trace_id: 0,
+            spans: Vec::new(),
+        }))
+    }
+
+    fn drop_call_stack(&self, _call_stack: Box<Self::CallStack>) {}
+
+    fn destroy_call_stack(&self, _time: Time, _call_stack: Box<Self::CallStack>) {}
+
+    fn unblock_call_stack(&self, _time: Time, _call_stack: &mut Self::CallStack) {}
+
+    fn suspend_call_stack(&self, _time: Time, _call_stack: &mut Self::CallStack, _block: bool) {}
+
+    fn resume_call_stack(&self, _time: Time, _call_stack: &mut Self::CallStack) {}
+
+    fn create_span(
+        &self,
+        time: Time,
+        span_descriptor: &SpanDescriptor,
+        message: &[u8],
+        call_stack: &mut Self::CallStack,
+    ) -> error::Result {
+        // Each root span starts a new trace.
+        if call_stack.spans.is_empty() {
+            call_stack.trace_id = self.ids.trace_id();
+        }
+
+        let metadata = span_descriptor.metadata();
+        call_stack.spans.push(OpenSpan {
+            id: self.ids.span_id(),
+            start: time,
+            name: String::from_utf8_lossy(metadata.name().to_bytes()).into_owned(),
+            scope: String::from_utf8_lossy(metadata.target().to_bytes()).into_owned(),
+            attributes: metadata_attributes(metadata, message),
+        });
+        Ok(())
+    }
+
+    fn drop_span(&self, call_stack: &mut Self::CallStack) {
+        call_stack.spans.pop().expect("span stack is empty");
+    }
+
+    fn destroy_span(&self, time: Time, call_stack: &mut Self::CallStack) {
+        let span = call_stack.spans.pop().expect("span stack is empty");
+        let parent = call_stack.spans.last().map(|x| x.id);
+
+        let mut record = String::from("{\"traceId\":");
+        let _ = write!(
+            record,
+            "\"{:032x}\",\"spanId\":\"{:016x}\"",
+            call_stack.trace_id, span.id
+        );
+        if let Some(parent) = parent {
+            let _ = write!(record, ",\"parentSpanId\":\"{parent:016x}\"");
+        }
+        record.push_str(",\"name\":");
+        write_json_string(&mut record, span.name.as_bytes());
+        let _ = write!(
+            record,
+            ",\"kind\":1,\"startTimeUnixNano\":\"{}\",\"endTimeUnixNano\":\"{}\",\
+            \"attributes\":[{}]}}",
+            unix_nanos(span.start),
+            unix_nanos(time),
+            span.attributes
+        );
+
+        self.push(|batch| batch.spans.entry(span.scope).or_default().push(record));
+    }
+
+    fn emit_event(
+        &self,
+        time: Time,
+        call_stack: &mut Self::CallStack,
+        event: &Event,
+        message: &[u8],
+    ) {
+        let metadata = event.metadata();
+        let (severity_number, severity_text) = match metadata.level() {
+            Level::Off => (0, "UNSPECIFIED"),
+            Level::Error => (17, "ERROR"),
+            Level::Warn => (13, "WARN"),
+            Level::Info => (9, "INFO"),
+            Level::Debug => (5, "DEBUG"),
+            Level::Trace => (1, "TRACE"),
+        };
+
+        let mut record = String::new();
+        let _ = write!(
+            record,
+            "{{\"timeUnixNano\":\"{}\",\"severityNumber\":{severity_number},\
+            \"severityText\":\"{severity_text}\",\"body\":{{\"stringValue\":",
+            unix_nanos(time)
+        );
+        write_json_string(&mut record, message);
+        record.push('}');
+        if let Some(span) = call_stack.spans.last() {
+            let _ = write!(
+                record,
+                ",\"traceId\":\"{:032x}\",\"spanId\":\"{:016x}\"",
+                call_stack.trace_id, span.id
+            );
+        }
+        let mut attributes = metadata_attributes(metadata, b"");
+        if let Some(template) = event.message_template() {
+            template_attributes(&mut attributes, template);
+        }
+        let _ = write!(record, ",\"attributes\":[{attributes}]}}");
+
+        let scope = String::from_utf8_lossy(metadata.target().to_bytes()).into_owned();
+        self.push(|batch| batch.logs.entry(scope).or_default().push(record));
+    }
+
+    fn flush(&self) {
+        let batch =
+            core::mem::take(&mut *self.pending.lock().expect("could not lock pending batch"));
+        self.send(ExportRequest::Batch(batch));
+
+        // Wait until the exporter has sent all preceding batches.
+        let (sender, receiver) = mpsc::channel();
+        self.send(ExportRequest::Flush(sender));
+        let _ = receiver.recv();
+    }
+}
+
+/// Encoded spans and log records, grouped by their instrumentation scope.
+#[derive(Debug, Default)]
+struct Batch {
+    spans: BTreeMap<String, Vec<String>>,
+    logs: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug)]
+enum ExportRequest {
+    Batch(Batch),
+    Flush(mpsc::Sender<()>),
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    host: String,
+    path: String,
+}
+
+impl Endpoint {
+    fn parse(endpoint: &str) -> Result<Self, Error> {
+        let Some(endpoint) = endpoint.strip_prefix("http://") else {
+            return Err(Error::from_string(
+                c"only `http` OTLP endpoints are supported",
+            ));
+        };
+        let (host, path) = match endpoint.find('/') {
+            Some(pos) => endpoint.split_at(pos),
+            None => (endpoint, ""),
+        };
+        if host.is_empty() {
+            return Err(Error::EINVAL);
+        }
+
+        let host = if host.contains(':') {
+            host.into()
+        } else {
+            alloc::format!("{host}:4318")
+        };
+        Ok(Self {
+            host,
+            path: path.trim_end_matches('/').into(),
+        })
+    }
+
+    fn post(&self, path: &str, body: &str) -> Result<(), Error> {
+        let mut stream = TcpStream::connect(&self.host).map_err(Error::new)?;
+        write!(
+            stream,
+            "POST {}{path} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\n\
+            Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            self.path,
+            self.host,
+            body.len()
+        )
+        .map_err(Error::new)?;
+        stream.flush().map_err(Error::new)?;
+
+        // We only care about the status code of the response.
+        let mut status = String::new();
+        BufReader::new(stream)
+            .read_line(&mut status)
+            .map_err(Error::new)?;
+        match status.split_whitespace().nth(1) {
+            Some(code) if code.starts_with('2') => Ok(()),
+            _ => Err(Error::from_string(c"the collector rejected the export")),
+        }
+    }
+}
+
+fn export_loop(
+    endpoint: Endpoint,
+    resource: String,
+    requests: mpsc::Receiver<ExportRequest>,
+    failed_exports: Arc<AtomicUsize>,
+) {
+    for request in requests {
+        let batch = match request {
+            ExportRequest::Batch(batch) => batch,
+            ExportRequest::Flush(done) => {
+                let _ = done.send(());
+                continue;
+            }
+        };
+
+        let exports = [
+            (
+                "/v1/traces",
+                "resourceSpans",
+                "scopeSpans",
+                "spans",
+                batch.spans,
+            ),
+            (
+                "/v1/logs",
+                "resourceLogs",
+                "scopeLogs",
+                "logRecords",
+                batch.logs,
+            ),
+        ];
+        for (path, resource_key, scope_key, records_key, scopes) in exports {
+            if scopes.is_empty() {
+                continue;
+            }
+
+            let mut body = String::new();
+            let _ = write!(
+                body,
+                "{{\"{resource_key}\":[{{\"resource\":{resource},\"{scope_key}\":["
+            );
+            for (i, (scope, records)) in scopes.iter().enumerate() {
+                if i != 0 {
+                    body.push(',');
+                }
+                body.push_str("{\"scope\":{\"name\":");
+                write_json_string(&mut body, scope.as_bytes());
+                let _ = write!(body, "}},\"{records_key}\":[{}]}}", records.join(","));
+            }
+            body.push_str("]}]}");
+
+            if endpoint.post(path, &body).is_err() {
+                failed_exports.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+/// Generator of random trace and span ids.
+#[derive(Debug)]
+struct IdGenerator {
+    state: RandomState,
+    counter: AtomicU64,
+}
+
+impl IdGenerator {
+    fn new() -> Self {
+        Self {
+            state: RandomState::new(),
+            counter: AtomicU64::new(0),
+        }
+    }
+
+    fn next(&self) -> u64 {
+        let mut hasher = self.state.build_hasher();
+        hasher.write_u64(self.counter.fetch_add(1, Ordering::Relaxed));
+        // An id of zero is invalid.
+        hasher.finish().max(1)
+    }
+
+    fn trace_id(&self) -> u128 {
+        (u128::from(self.next()) << 64) | u128::from(self.next())
+    }
+
+    fn span_id(&self) -> u64 {
+        self.next()
+    }
+}
+
+fn unix_nanos(time: Time) -> u128 {
+    time.duration_since(&Time::UNIX_EPOCH)
+        .map_or(0, |x| x.as_nanos())
+}
+
+fn metadata_attributes(metadata: &Metadata, message: &[u8]) -> String {
+    let mut attributes = String::new();
+    if let Some(file_name) = metadata.file_name() {
+        write_string_attribute(&mut attributes, b"code.filepath", file_name.to_bytes());
+    }
+    if let Some(line_number) = metadata.line_number() {
+        if !attributes.is_empty() {
+            attributes.push(',');
+        }
+        let _ = write!(
+            attributes,
+            "{{\"key\":\"code.lineno\",\"value\":{{\"intValue\":\"{line_number}\"}}}}"
+        );
+    }
+    if !message.is_empty() {
+        if !attributes.is_empty() {
+            attributes.push(',');
+        }
+        write_string_attribute(&mut attributes, b"message", message);
+    }
+    attributes
+}
+
+fn template_attributes(attributes: &mut String, template: &MessageTemplate<'_>) {
+    if !attributes.is_empty() {
+        attributes.push(',');
+    }
+    write_string_attribute(
+        attributes,
+        b"message.template",
+        template.message().to_bytes(),
+    );
+    for field in template.fields() {
+        // Fields which could not be formatted are skipped.
+        let Ok(value) = field.value() else {
+            continue;
+        };
+        attributes.push(',');
+        write_string_attribute(attributes, field.name().to_bytes(), &value);
+    }
+}
+
+fn write_string_attribute(out: &mut String, key: &[u8], value: &[u8]) {
+    out.push_str("{\"key\":");
+    write_json_string(out, key);
+    out.push_str(",\"value\":{\"stringValue\":");
+    write_json_string(out, value);
+    out.push_str("}}");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bindings,
+        ffi::FFISharable,
+        tracing::{Field, MessageTemplate},
+    };
+
+    const METADATA: &Metadata = &Metadata::new(c"event", c"assets", Level::Info, None, None);
+
+    #[test]
+    fn log_record_template() {
+        let fields = [Field::new(c"count", &5), Field::new(c"ms", &12.5)];
+        let template = MessageTemplate::new(c"loaded {count} assets in {ms} ms", &fields);
+        let event = Event(bindings::FimoTracingEvent {
+            next: template.share_to_ffi().cast(),
+            ..Event::new(METADATA).0
+        });
+
+        // Nothing is listening on the port, so the batch is discarded once the subscriber is
+        // dropped.
+        let subscriber =
+            OtlpSubscriber::new("http://127.0.0.1:1", "test").expect("invalid endpoint");
+        let mut call_stack = subscriber
+            .create_call_stack(Time::now())
+            .expect("could not create call stack");
+        subscriber.emit_event(
+            Time::now(),
+            &mut call_stack,
+            &event,
+            b"loaded 5 assets in 12.5 ms",
+        );
+
+        let pending = subscriber
+            .pending
+            .lock()
+            .expect("could not lock pending batch");
+        let record = &pending.logs["assets"][0];
+        assert!(record.contains("\"body\":{\"stringValue\":\"loaded 5 assets in 12.5 ms\"}"));
+        assert!(record.contains(
+            "{\"key\":\"message.template\",\"value\":{\"stringValue\":\
+            \"loaded {count} assets in {ms} ms\"}}"
+        ));
+        assert!(record.contains("{\"key\":\"count\",\"value\":{\"stringValue\":\"5\"}}"));
+        assert!(record.contains("{\"key\":\"ms\",\"value\":{\"stringValue\":\"12.5\"}}"));
+    }
+}
@@ -0,0 +1,565 @@
+//! Enrichment of tracing events before they reach a subscriber.
+use crate::{
+    error::{self, Error},
+    time::Time,
+    tracing::{Event, Metadata, Record, SpanDescriptor, Subscriber},
+};
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    string::{String, ToString},
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use core::{
+    fmt::Display,
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+};
+use std::sync::RwLock;
+
+/// Interface of a processor of tracing events.
+///
+/// A processor may modify the message of an event, or attach additional fields to it, before it
+/// is passed to the subscriber of an [`Enricher`]. Processors are invoked on the thread emitting
+/// the event, so they should be cheap and must not emit events themselves.
+///
+/// The trait is implemented for all closures taking a [`ProcessedRecord`].
+pub trait Processor: Send + Sync {
+    /// Processes the record of an event.
+    fn process(&self, record: &mut ProcessedRecord<'_>);
+}
+
+impl<F> Processor for F
+where
+    F: Fn(&mut ProcessedRecord<'_>) + Send + Sync,
+{
+    fn process(&self, record: &mut ProcessedRecord<'_>) {
+        self(record);
+    }
+}
+
+/// Event being processed by the [`Processor`]s of an [`Enricher`].
+///
+/// Once all processors have run, the fields are appended to the message in the form
+/// ` name=value`, in the order they were added.
+#[derive(Debug)]
+pub struct ProcessedRecord<'a> {
+    event: &'a Event,
+    message: Vec<u8>,
+    fields: Vec<(Cow<'static, str>, String)>,
+}
+
+impl<'a> ProcessedRecord<'a> {
+    /// Returns the event of the record.
+    pub fn event(&self) -> &'a Event {
+        self.event
+    }
+
+    /// Returns the [`Metadata`] of the event.
+    pub fn metadata(&self) -> &'a Metadata {
+        self.event.metadata()
+    }
+
+    /// Returns the formatted message of the event.
+    pub fn message(&self) -> &[u8] {
+        &self.message
+    }
+
+    /// Returns a mutable reference to the formatted message of the event.
+    pub fn message_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.message
+    }
+
+    /// Returns the fields added by the processors.
+    pub fn fields(&self) -> &[(Cow<'static, str>, String)] {
+        &self.fields
+    }
+
+    /// Returns a mutable reference to the fields added by the processors.
+    pub fn fields_mut(&mut self) -> &mut Vec<(Cow<'static, str>, String)> {
+        &mut self.fields
+    }
+
+    /// Adds a new field.
+    pub fn add_field(&mut self, name: impl Into<Cow<'static, str>>, value: impl Display) {
+        self.fields.push((name.into(), value.to_string()));
+    }
+
+    fn render(mut self) -> Vec<u8> {
+        for (name, value) in &self.fields {
+            self.message.push(b' ');
+            self.message.extend_from_slice(name.as_bytes());
+            self.message.push(b'=');
+            self.message.extend_from_slice(value.as_bytes());
+        }
+        self.message
+    }
+}
+
+/// Ordered, shared list of [`Processor`]s.
+///
+/// The list is shared between all its clones, so that processors can be added and removed after
+/// the list has been passed to an [`Enricher`]. Processors are invoked in ascending order, and
+/// processors with the same order are invoked in the order they were added. While the list is
+/// empty, events are passed through without being copied.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::tracing::{Event, Level, Metadata, ProcessedRecord, Processors, StaticField};
+///
+/// const METADATA: &Metadata = &Metadata::new(c"event", c"app", Level::Info, None, None);
+///
+/// let processors = Processors::default();
+/// let event = Event::new(METADATA);
+/// assert_eq!(processors.apply(&event, b"ready"), None);
+///
+/// let build = processors.add(0, StaticField::new("build", "1a2b3c"));
+/// let upper = processors.add(-1, |record: &mut ProcessedRecord<'_>| {
+///     record.message_mut().make_ascii_uppercase();
+/// });
+/// assert_eq!(
+///     processors.apply(&event, b"ready").as_deref(),
+///     Some(&b"READY build=1a2b3c"[..])
+/// );
+///
+/// drop(upper);
+/// drop(build);
+/// assert!(processors.is_empty());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Processors(Arc<ProcessorList>);
+
+#[derive(Default)]
+struct ProcessorList {
+    len: AtomicUsize,
+    next_id: AtomicU64,
+    entries: RwLock<Vec<ProcessorEntry>>,
+}
+
+struct ProcessorEntry {
+    id: u64,
+    order: i32,
+    processor: Box<dyn Processor>,
+}
+
+impl Processors {
+    /// Adds a new processor to the list.
+    ///
+    /// The processor is removed once the returned guard is dropped.
+    pub fn add(&self, order: i32, processor: impl Processor + 'static) -> ProcessorGuard {
+        let id = self.0.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut entries = self.0.entries_mut();
+        let position = entries.partition_point(|x| x.order <= order);
+        entries.insert(
+            position,
+            ProcessorEntry {
+                id,
+                order,
+                processor: Box::new(processor),
+            },
+        );
+        self.0.len.store(entries.len(), Ordering::Release);
+
+        ProcessorGuard {
+            list: Arc::downgrade(&self.0),
+            id,
+        }
+    }
+
+    /// Returns the number of processors in the list.
+    pub fn len(&self) -> usize {
+        self.0.len.load(Ordering::Acquire)
+    }
+
+    /// Returns whether the list contains no processors.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Applies the processors to an event, returning the processed message.
+    ///
+    /// Returns `None` if the list is empty.
+    pub fn apply(&self, event: &Event, message: &[u8]) -> Option<Vec<u8>> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let mut record = ProcessedRecord {
+            event,
+            message: message.to_vec(),
+            fields: Vec::new(),
+        };
+        let entries = self
+            .0
+            .entries
+            .read()
+            .expect("could not lock the processors");
+        for entry in entries.iter() {
+            entry.processor.process(&mut record);
+        }
+        drop(entries);
+
+        Some(record.render())
+    }
+}
+
+impl ProcessorList {
+    fn entries_mut(&self) -> std::sync::RwLockWriteGuard<'_, Vec<ProcessorEntry>> {
+        self.entries.write().expect("could not lock the processors")
+    }
+}
+
+impl core::fmt::Debug for ProcessorList {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ProcessorList")
+            .field("len", &self.len.load(Ordering::Relaxed))
+            .finish_non_exhaustive()
+    }
+}
+
+/// RAII guard of a [`Processor`] added to [`Processors`].
+///
+/// The processor is removed from the list once the guard is dropped.
+#[derive(Debug)]
+#[must_use = "the processor is removed once the guard is dropped"]
+pub struct ProcessorGuard {
+    list: Weak<ProcessorList>,
+    id: u64,
+}
+
+impl Drop for ProcessorGuard {
+    fn drop(&mut self) {
+        let Some(list) = self.list.upgrade() else {
+            return;
+        };
+        let mut entries = list.entries_mut();
+        entries.retain(|x| x.id != self.id);
+        list.len.store(entries.len(), Ordering::Release);
+    }
+}
+
+/// A [`Subscriber`] adapter which applies a list of [`Processors`] to the events, before passing
+/// them to the wrapped subscriber.
+///
+/// The processors can be used to attach additional information to each event, e.g., the id of the
+/// process with [`ProcessId`], or to redact secrets with the [`Redactor`]. Spans are passed
+/// through unmodified.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::tracing::{ConsoleSubscriber, Enricher, Hostname, ProcessId, Processors};
+///
+/// let processors = Processors::default();
+/// let enricher = Enricher::new(ConsoleSubscriber::new(), processors.clone());
+///
+/// let _pid = processors.add(0, ProcessId);
+/// let _host = processors.add(0, Hostname::default());
+/// assert_eq!(enricher.processors().len(), 2);
+/// ```
+#[derive(Debug)]
+pub struct Enricher<T> {
+    subscriber: T,
+    processors: Processors,
+}
+
+impl<T: Subscriber> Enricher<T> {
+    /// Constructs a new `Enricher`.
+    pub fn new(subscriber: T, processors: Processors) -> Self {
+        Self {
+            subscriber,
+            processors,
+        }
+    }
+
+    /// Returns a reference to the wrapped [`Subscriber`].
+    pub fn subscriber(&self) -> &T {
+        &self.subscriber
+    }
+
+    /// Returns the list of processors applied to the events.
+    pub fn processors(&self) -> &Processors {
+        &self.processors
+    }
+}
+
+impl<T: Subscriber> Subscriber for Enricher<T> {
+    type CallStack = T::CallStack;
+
+    fn create_call_stack(&self, time: Time) -> Result<Box<Self::CallStack>, Error> {
+        self.subscriber.create_call_stack(time)
+    }
+
+    fn drop_call_stack(&self, call_stack: Box<Self::CallStack>) {
+        self.subscriber.drop_call_stack(call_stack);
+    }
+
+    fn destroy_call_stack(&self, time: Time, call_stack: Box<Self::CallStack>) {
+        self.subscriber.destroy_call_stack(time, call_stack);
+    }
+
+    fn unblock_call_stack(&self, time: Time, call_stack: &mut Self::CallStack) {
+        self.subscriber.unblock_call_stack(time, call_stack);
+    }
+
+    fn suspend_call_stack(&self, time: Time, call_stack: &mut Self::CallStack, block: bool) {
+        self.subscriber.suspend_call_stack(time, call_stack, block);
+    }
+
+    fn resume_call_stack(&self, time: Time, call_stack: &mut Self::CallStack) {
+        self.subscriber.resume_call_stack(time, call_stack);
+    }
+
+    fn create_span(
+        &self,
+        time: Time,
+        span_descriptor: &SpanDescriptor,
+        message: &[u8],
+        call_stack: &mut Self::CallStack,
+    ) -> error::Result {
+        self.subscriber
+            .create_span(time, span_descriptor, message, call_stack)
+    }
+
+    fn drop_span(&self, call_stack: &mut Self::CallStack) {
+        self.subscriber.drop_span(call_stack);
+    }
+
+    fn destroy_span(&self, time: Time, call_stack: &mut Self::CallStack) {
+        self.subscriber.destroy_span(time, call_stack);
+    }
+
+    fn emit_event(
+        &self,
+        time: Time,
+        call_stack: &mut Self::CallStack,
+        event: &Event,
+        message: &[u8],
+    ) {
+        match self.processors.apply(event, message) {
+            None => self.subscriber.emit_event(time, call_stack, event, message),
+            Some(message) => self
+                .subscriber
+                .emit_event(time, call_stack, event, &message),
+        }
+    }
+
+    fn emit_event_batch(
+        &self,
+        time: Time,
+        call_stack: &mut Self::CallStack,
+        records: &[Record<'_>],
+    ) {
+        if self.processors.is_empty() {
+            self.subscriber.emit_event_batch(time, call_stack, records);
+            return;
+        }
+
+        let messages = records
+            .iter()
+            .map(|x| {
+                self.processors
+                    .apply(x.event(), x.message())
+                    .unwrap_or_else(|| x.message().to_vec())
+            })
+            .collect::<Vec<_>>();
+        let records = records
+            .iter()
+            .zip(&messages)
+            .map(|(record, message)| Record::new(record.event(), message))
+            .collect::<Vec<_>>();
+        self.subscriber.emit_event_batch(time, call_stack, &records);
+    }
+
+    fn flush(&self) {
+        self.subscriber.flush();
+    }
+}
+
+/// A [`Processor`] adding the id of the current process as the `pid` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ProcessId;
+
+impl Processor for ProcessId {
+    fn process(&self, record: &mut ProcessedRecord<'_>) {
+        record.add_field("pid", std::process::id());
+    }
+}
+
+/// A [`Processor`] adding the name of the host as the `host` field.
+///
+/// The name is queried once, when the processor is constructed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hostname(String);
+
+impl Hostname {
+    /// Returns the name of the host.
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for Hostname {
+    fn default() -> Self {
+        #[cfg(unix)]
+        {
+            let mut buffer = [0u8; 256];
+            // Safety: The buffer is valid for its whole length.
+            let result = unsafe { libc::gethostname(buffer.as_mut_ptr().cast(), buffer.len()) };
+            if result == 0 {
+                let len = buffer.iter().position(|&x| x == 0).unwrap_or(buffer.len());
+                return Self(String::from_utf8_lossy(&buffer[..len]).into_owned());
+            }
+        }
+
+        let name = std::env::var("COMPUTERNAME")
+            .or_else(|_| std::env::var("HOSTNAME"))
+            .unwrap_or_else(|_| String::from("unknown"));
+        Self(name)
+    }
+}
+
+impl Processor for Hostname {
+    fn process(&self, record: &mut ProcessedRecord<'_>) {
+        record.add_field("host", &self.0);
+    }
+}
+
+/// A [`Processor`] adding a field with a constant value, e.g., the id of the build.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaticField {
+    name: Cow<'static, str>,
+    value: String,
+}
+
+impl StaticField {
+    /// Constructs a new `StaticField`.
+    pub fn new(name: impl Into<Cow<'static, str>>, value: impl Display) -> Self {
+        Self {
+            name: name.into(),
+            value: value.to_string(),
+        }
+    }
+}
+
+impl Processor for StaticField {
+    fn process(&self, record: &mut ProcessedRecord<'_>) {
+        record.add_field(self.name.clone(), &self.value);
+    }
+}
+
+/// A [`Processor`] which redacts secrets from the messages and fields of the events.
+///
+/// Secrets are either matched literally, or by a key, in which case the value following the key
+/// in the form `key=value` or `key: value` is redacted, up to the next whitespace. Fields with the
+/// name of a key are redacted completely. Redacted secrets are replaced with `[REDACTED]`.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::tracing::{Event, Level, Metadata, Processors, Redactor};
+///
+/// const METADATA: &Metadata = &Metadata::new(c"event", c"app", Level::Info, None, None);
+///
+/// let processors = Processors::default();
+/// let _redactor = processors.add(
+///     0,
+///     Redactor::default()
+///         .with_secret("hunter2")
+///         .with_key("token"),
+/// );
+///
+/// let event = Event::new(METADATA);
+/// let message = processors.apply(&event, b"login password=hunter2 token: abc123 user=admin");
+/// assert_eq!(
+///     message.as_deref(),
+///     Some(&b"login password=[REDACTED] token: [REDACTED] user=admin"[..])
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Redactor {
+    secrets: Vec<Vec<u8>>,
+    keys: Vec<Vec<u8>>,
+}
+
+impl Redactor {
+    const REPLACEMENT: &'static [u8] = b"[REDACTED]";
+
+    /// Adds a secret which is redacted wherever it occurs.
+    pub fn with_secret(mut self, secret: impl AsRef<[u8]>) -> Self {
+        let secret = secret.as_ref();
+        if !secret.is_empty() {
+            self.secrets.push(secret.to_vec());
+        }
+        self
+    }
+
+    /// Adds a key whose values are redacted.
+    pub fn with_key(mut self, key: impl AsRef<[u8]>) -> Self {
+        let key = key.as_ref();
+        if !key.is_empty() {
+            self.keys.push(key.to_vec());
+        }
+        self
+    }
+
+    fn redact(&self, text: &[u8]) -> Option<Vec<u8>> {
+        let mut output = Vec::with_capacity(text.len());
+        let mut redacted = false;
+        let mut pos = 0;
+        'outer: while pos < text.len() {
+            let rest = &text[pos..];
+            if let Some(secret) = self.secrets.iter().find(|x| rest.starts_with(x)) {
+                output.extend_from_slice(Self::REPLACEMENT);
+                pos += secret.len();
+                redacted = true;
+                continue;
+            }
+
+            let at_boundary = pos == 0 || !text[pos - 1].is_ascii_alphanumeric();
+            if at_boundary {
+                for key in &self.keys {
+                    let Some(after_key) = rest.strip_prefix(&key[..]) else {
+                        continue;
+                    };
+                    let separator = match after_key {
+                        [b':', b' ', ..] => 2,
+                        [b'=' | b':', ..] => 1,
+                        _ => continue,
+                    };
+                    let value_start = key.len() + separator;
+                    let value_len = rest[value_start..]
+                        .iter()
+                        .position(|x| x.is_ascii_whitespace())
+                        .unwrap_or(rest.len() - value_start);
+                    output.extend_from_slice(&rest[..value_start]);
+                    output.extend_from_slice(Self::REPLACEMENT);
+                    pos += value_start + value_len;
+                    redacted = true;
+                    continue 'outer;
+                }
+            }
+
+            output.push(text[pos]);
+            pos += 1;
+        }
+
+        redacted.then_some(output)
+    }
+}
+
+impl Processor for Redactor {
+    fn process(&self, record: &mut ProcessedRecord<'_>) {
+        if let Some(message) = self.redact(&record.message) {
+            record.message = message;
+        }
+        for (name, value) in &mut record.fields {
+            if self.keys.iter().any(|x| x == name.as_bytes()) {
+                *value = String::from_utf8_lossy(Self::REPLACEMENT).into_owned();
+            } else if let Some(redacted) = self.redact(value.as_bytes()) {
+                *value = String::from_utf8_lossy(&redacted).into_owned();
+            }
+        }
+    }
+}
@@ -0,0 +1,329 @@
+//! Rate limiting of tracing events.
+use crate::{
+    error::{self, Error},
+    time::{Duration, Time},
+    tracing::{Event, SpanDescriptor, Subscriber},
+};
+use alloc::{boxed::Box, collections::BTreeMap, format, string::String, vec::Vec};
+use core::ffi::CStr;
+use std::sync::Mutex;
+
+/// Token bucket limit of the events emitted from a single call site.
+///
+/// A call site may emit up to `burst` events at once, after which it may emit one more event
+/// each `interval`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RateLimit {
+    burst: u32,
+    interval: Duration,
+}
+
+impl RateLimit {
+    /// Constructs a new `RateLimit`.
+    pub const fn new(burst: u32, interval: Duration) -> Self {
+        Self { burst, interval }
+    }
+
+    /// Returns the maximum number of events emitted at once.
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+
+    /// Returns the time required for refilling one event.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+}
+
+/// A [`Subscriber`] adapter which limits the rate of the events emitted from each call site.
+///
+/// A call site is identified by the target, file name and line number of an event. Each call site
+/// is limited by the [`RateLimit`] of its channel, where, like in the
+/// [`ChannelFilter`](super::ChannelFilter), a channel without an explicit limit inherits the limit
+/// of its closest ancestor, or the default limit. Optionally, consecutive events with the same
+/// message are suppressed, until the suppression window has elapsed or a different message is
+/// emitted.
+///
+/// Instead of silently dropping the events, the next event passed through from the same call site
+/// is preceded by a summary with the number of suppressed events. Spans are not limited.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::{
+///     time::Duration,
+///     tracing::{ConsoleSubscriber, RateLimit, RateLimiter},
+/// };
+///
+/// let limit = RateLimit::new(10, Duration::from_seconds(1));
+/// let limiter = RateLimiter::new(ConsoleSubscriber::new(), Some(limit));
+/// limiter.set_channel_limit(c"renderer::shader", None);
+/// limiter.set_duplicate_window(Some(Duration::from_seconds(5)));
+/// assert_eq!(limiter.channel_limit(c"renderer::shader::cache"), None);
+/// assert_eq!(limiter.channel_limit(c"renderer"), Some(limit));
+/// ```
+#[derive(Debug)]
+pub struct RateLimiter<T> {
+    subscriber: T,
+    state: Mutex<LimiterState>,
+}
+
+#[derive(Debug)]
+struct LimiterState {
+    default: Option<RateLimit>,
+    channels: BTreeMap<Box<[u8]>, Option<RateLimit>>,
+    duplicate_window: Option<Duration>,
+    call_sites: BTreeMap<CallSiteKey, CallSite>,
+}
+
+type CallSiteKey = (Box<[u8]>, Option<Box<[u8]>>, Option<u32>);
+
+#[derive(Debug)]
+struct CallSite {
+    tokens: u32,
+    last_refill: Time,
+    dropped: usize,
+    last_message: Vec<u8>,
+    repeated: usize,
+    repeat_start: Time,
+}
+
+impl<T: Subscriber> RateLimiter<T> {
+    /// Constructs a new `RateLimiter` with the given default limit.
+    ///
+    /// Passing `None` as the limit disables the limit for channels without an explicit limit.
+    pub fn new(subscriber: T, default_limit: Option<RateLimit>) -> Self {
+        Self {
+            subscriber,
+            state: Mutex::new(LimiterState {
+                default: default_limit,
+                channels: BTreeMap::new(),
+                duplicate_window: None,
+                call_sites: BTreeMap::new(),
+            }),
+        }
+    }
+
+    /// Returns a reference to the wrapped [`Subscriber`].
+    pub fn subscriber(&self) -> &T {
+        &self.subscriber
+    }
+
+    /// Returns the limit of channels without an explicit limit.
+    pub fn default_limit(&self) -> Option<RateLimit> {
+        self.state().default
+    }
+
+    /// Sets the limit of channels without an explicit limit.
+    pub fn set_default_limit(&self, limit: Option<RateLimit>) {
+        self.state().default = limit;
+    }
+
+    /// Returns the effective limit of a channel.
+    pub fn channel_limit(&self, channel: &CStr) -> Option<RateLimit> {
+        self.state().limit(channel.to_bytes())
+    }
+
+    /// Sets the limit of a channel and its descendants, where `None` disables the limit.
+    pub fn set_channel_limit(&self, channel: &CStr, limit: Option<RateLimit>) {
+        self.state()
+            .channels
+            .insert(channel.to_bytes().into(), limit);
+    }
+
+    /// Removes the explicit limit of a channel.
+    ///
+    /// Afterward, the channel inherits the limit of its closest ancestor.
+    pub fn clear_channel_limit(&self, channel: &CStr) {
+        self.state().channels.remove(channel.to_bytes());
+    }
+
+    /// Returns the window in which duplicate messages are suppressed.
+    pub fn duplicate_window(&self) -> Option<Duration> {
+        self.state().duplicate_window
+    }
+
+    /// Sets the window in which duplicate messages are suppressed, where `None` disables the
+    /// suppression.
+    ///
+    /// An event repeating the previous message of its call site is suppressed, if it is emitted
+    /// within the window, starting with the first occurrence of the message. Afterward, a summary
+    /// with the number of repetitions is emitted, and a new window is started.
+    pub fn set_duplicate_window(&self, window: Option<Duration>) {
+        self.state().duplicate_window = window;
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, LimiterState> {
+        self.state.lock().expect("could not lock rate limiter")
+    }
+}
+
+impl<T: Subscriber> Subscriber for RateLimiter<T> {
+    type CallStack = T::CallStack;
+
+    fn create_call_stack(&self, time: Time) -> Result<Box<Self::CallStack>, Error> {
+        self.subscriber.create_call_stack(time)
+    }
+
+    fn drop_call_stack(&self, call_stack: Box<Self::CallStack>) {
+        self.subscriber.drop_call_stack(call_stack);
+    }
+
+    fn destroy_call_stack(&self, time: Time, call_stack: Box<Self::CallStack>) {
+        self.subscriber.destroy_call_stack(time, call_stack);
+    }
+
+    fn unblock_call_stack(&self, time: Time, call_stack: &mut Self::CallStack) {
+        self.subscriber.unblock_call_stack(time, call_stack);
+    }
+
+    fn suspend_call_stack(&self, time: Time, call_stack: &mut Self::CallStack, block: bool) {
+        self.subscriber.suspend_call_stack(time, call_stack, block);
+    }
+
+    fn resume_call_stack(&self, time: Time, call_stack: &mut Self::CallStack) {
+        self.subscriber.resume_call_stack(time, call_stack);
+    }
+
+    fn create_span(
+        &self,
+        time: Time,
+        span_descriptor: &SpanDescriptor,
+        message: &[u8],
+        call_stack: &mut Self::CallStack,
+    ) -> error::Result {
+        self.subscriber
+            .create_span(time, span_descriptor, message, call_stack)
+    }
+
+    fn drop_span(&self, call_stack: &mut Self::CallStack) {
+        self.subscriber.drop_span(call_stack);
+    }
+
+    fn destroy_span(&self, time: Time, call_stack: &mut Self::CallStack) {
+        self.subscriber.destroy_span(time, call_stack);
+    }
+
+    fn emit_event(
+        &self,
+        time: Time,
+        call_stack: &mut Self::CallStack,
+        event: &Event,
+        message: &[u8],
+    ) {
+        let Some(summaries) = self.state().admit(time, event, message) else {
+            return;
+        };
+        for summary in summaries {
+            self.subscriber
+                .emit_event(time, call_stack, event, summary.as_bytes());
+        }
+        self.subscriber.emit_event(time, call_stack, event, message);
+    }
+
+    fn flush(&self) {
+        self.subscriber.flush();
+    }
+}
+
+impl LimiterState {
+    fn limit(&self, mut channel: &[u8]) -> Option<RateLimit> {
+        loop {
+            if let Some(limit) = self.channels.get(channel) {
+                return *limit;
+            }
+            match channel.windows(2).rposition(|w| w == b"::") {
+                Some(pos) => channel = &channel[..pos],
+                None => return self.default,
+            }
+        }
+    }
+
+    /// Decides whether to pass the event through, returning the summaries to emit before it.
+    fn admit(&mut self, time: Time, event: &Event, message: &[u8]) -> Option<Vec<String>> {
+        let metadata = event.metadata();
+        let limit = self.limit(metadata.target().to_bytes());
+        let duplicate_window = self.duplicate_window;
+        if limit.is_none() && duplicate_window.is_none() {
+            return Some(Vec::new());
+        }
+
+        let key = (
+            metadata.target().to_bytes().into(),
+            metadata.file_name().map(|x| x.to_bytes().into()),
+            metadata.line_number(),
+        );
+        let call_site = self.call_sites.entry(key).or_insert_with(|| CallSite {
+            tokens: limit.map_or(0, |x| x.burst),
+            last_refill: time,
+            dropped: 0,
+            last_message: Vec::new(),
+            repeated: 0,
+            repeat_start: time,
+        });
+
+        let mut repeated = 0;
+        if let Some(window) = duplicate_window {
+            let elapsed = time.duration_since(&call_site.repeat_start);
+            let in_window = elapsed.is_ok_and(|x| x < window);
+            if in_window && call_site.last_message == message {
+                call_site.repeated += 1;
+                return None;
+            }
+
+            repeated = core::mem::take(&mut call_site.repeated);
+            call_site.last_message.clear();
+            call_site.last_message.extend_from_slice(message);
+            call_site.repeat_start = time;
+        }
+
+        let mut summaries = Vec::new();
+        if let Some(limit) = limit {
+            call_site.refill(time, limit);
+            if call_site.tokens == 0 {
+                // The repetitions are reported as part of the suppressed events.
+                call_site.dropped += repeated + 1;
+                return None;
+            }
+            call_site.tokens -= 1;
+        }
+        if repeated != 0 {
+            summaries.push(format!("last message repeated {repeated} times"));
+        }
+        if call_site.dropped != 0 {
+            summaries.push(format!(
+                "{} messages suppressed by the rate limit",
+                call_site.dropped
+            ));
+            call_site.dropped = 0;
+        }
+
+        Some(summaries)
+    }
+}
+
+impl CallSite {
+    fn refill(&mut self, time: Time, limit: RateLimit) {
+        let interval = limit.interval.as_nanos();
+        if interval == 0 {
+            self.tokens = limit.burst;
+            self.last_refill = time;
+            return;
+        }
+
+        let elapsed = time
+            .duration_since(&self.last_refill)
+            .map_or(0, |x| x.as_nanos());
+        let refilled = elapsed / interval;
+        if refilled == 0 {
+            return;
+        }
+
+        let refilled_u32 = u32::try_from(refilled).unwrap_or(u32::MAX);
+        self.tokens = self.tokens.saturating_add(refilled_u32).min(limit.burst);
+        let advance = u64::try_from(refilled * interval).unwrap_or(u64::MAX);
+        self.last_refill = self
+            .last_refill
+            .saturating_add(Duration::from_nanos(advance));
+    }
+}
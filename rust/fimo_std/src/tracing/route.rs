@@ -0,0 +1,254 @@
+//! Routing of tracing messages to subscribers.
+use crate::{
+    error::{self, Error},
+    time::Time,
+    tracing::{
+        filter::is_descendant, Event, FilteredCallStack, Level, Record, SpanDescriptor, Subscriber,
+    },
+};
+use alloc::{boxed::Box, ffi::CString, vec::Vec};
+use core::{
+    ffi::CStr,
+    ops::{Bound, RangeBounds},
+};
+use std::sync::RwLock;
+
+/// Rule selecting the messages forwarded by a [`Router`].
+///
+/// A route matches the messages of a channel, and optionally of its descendants, whose level lies
+/// in a range. Like with the [`ChannelFilter`](super::ChannelFilter), the channel of a message is
+/// the target contained in its [`Metadata`](super::Metadata), and the levels are ordered by their
+/// verbosity, i.e., `..=Level::Warn` contains the errors and warnings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    channel: Option<CString>,
+    descendants: bool,
+    levels: (Bound<Level>, Bound<Level>),
+}
+
+impl Route {
+    /// Constructs a route matching all messages.
+    pub fn all() -> Self {
+        Self {
+            channel: None,
+            descendants: true,
+            levels: (Bound::Unbounded, Bound::Unbounded),
+        }
+    }
+
+    /// Constructs a route matching all messages of a channel and its descendants.
+    pub fn channel(channel: &CStr) -> Self {
+        Self {
+            channel: Some(channel.into()),
+            ..Self::all()
+        }
+    }
+
+    /// Sets whether the route also matches the descendants of its channel.
+    pub fn with_descendants(mut self, descendants: bool) -> Self {
+        self.descendants = descendants;
+        self
+    }
+
+    /// Restricts the route to the messages with a level in the range.
+    pub fn with_levels(mut self, levels: impl RangeBounds<Level>) -> Self {
+        self.levels = (levels.start_bound().cloned(), levels.end_bound().cloned());
+        self
+    }
+
+    /// Returns the channel of the route, or `None`, if it matches all channels.
+    pub fn channel_name(&self) -> Option<&CStr> {
+        self.channel.as_deref()
+    }
+
+    /// Returns whether the route matches a message.
+    pub fn matches(&self, channel: &CStr, level: Level) -> bool {
+        if !self.levels.contains(&level) {
+            return false;
+        }
+        match &self.channel {
+            None => true,
+            Some(route) => {
+                let (route, channel) = (route.to_bytes(), channel.to_bytes());
+                route == channel || (self.descendants && is_descendant(channel, route))
+            }
+        }
+    }
+}
+
+/// A [`Subscriber`] adapter which only forwards the messages matching one of its [`Route`]s.
+///
+/// Since each subscriber of the tracing subsystem receives all messages, the routes allow
+/// attaching a subscriber to specific channels or levels. A router without any routes forwards no
+/// messages. The routes can be modified at runtime.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::tracing::{ConsoleSubscriber, Level, Route, Router};
+///
+/// let router = Router::new(
+///     ConsoleSubscriber::new(),
+///     [
+///         Route::channel(c"network"),
+///         Route::all().with_levels(..=Level::Error),
+///     ],
+/// );
+///
+/// assert!(router.is_routed(c"network::http", Level::Trace));
+/// assert!(router.is_routed(c"renderer", Level::Error));
+/// assert!(!router.is_routed(c"renderer", Level::Warn));
+/// assert!(!router.is_routed(c"networking", Level::Info));
+/// ```
+#[derive(Debug)]
+pub struct Router<T> {
+    subscriber: T,
+    routes: RwLock<Vec<Route>>,
+}
+
+impl<T: Subscriber> Router<T> {
+    /// Constructs a new `Router` with the given routes.
+    pub fn new(subscriber: T, routes: impl IntoIterator<Item = Route>) -> Self {
+        Self {
+            subscriber,
+            routes: RwLock::new(routes.into_iter().collect()),
+        }
+    }
+
+    /// Returns a reference to the wrapped [`Subscriber`].
+    pub fn subscriber(&self) -> &T {
+        &self.subscriber
+    }
+
+    /// Returns the current routes.
+    pub fn routes(&self) -> Vec<Route> {
+        self.routes
+            .read()
+            .expect("could not lock the routes")
+            .clone()
+    }
+
+    /// Adds a new route.
+    pub fn add_route(&self, route: Route) {
+        self.routes
+            .write()
+            .expect("could not lock the routes")
+            .push(route);
+    }
+
+    /// Replaces all routes.
+    pub fn set_routes(&self, routes: impl IntoIterator<Item = Route>) {
+        *self.routes.write().expect("could not lock the routes") = routes.into_iter().collect();
+    }
+
+    /// Returns whether a message is forwarded to the wrapped subscriber.
+    pub fn is_routed(&self, channel: &CStr, level: Level) -> bool {
+        self.routes
+            .read()
+            .expect("could not lock the routes")
+            .iter()
+            .any(|x| x.matches(channel, level))
+    }
+}
+
+impl<T: Subscriber> Subscriber for Router<T> {
+    type CallStack = FilteredCallStack<T::CallStack>;
+
+    fn create_call_stack(&self, time: Time) -> Result<Box<Self::CallStack>, Error> {
+        let inner = self.subscriber.create_call_stack(time)?;
+        Ok(Box::new(FilteredCallStack {
+            inner,
+            spans: Vec::new(),
+        }))
+    }
+
+    fn drop_call_stack(&self, call_stack: Box<Self::CallStack>) {
+        self.subscriber.drop_call_stack(call_stack.inner);
+    }
+
+    fn destroy_call_stack(&self, time: Time, call_stack: Box<Self::CallStack>) {
+        self.subscriber.destroy_call_stack(time, call_stack.inner);
+    }
+
+    fn unblock_call_stack(&self, time: Time, call_stack: &mut Self::CallStack) {
+        self.subscriber
+            .unblock_call_stack(time, &mut call_stack.inner);
+    }
+
+    fn suspend_call_stack(&self, time: Time, call_stack: &mut Self::CallStack, block: bool) {
+        self.subscriber
+            .suspend_call_stack(time, &mut call_stack.inner, block);
+    }
+
+    fn resume_call_stack(&self, time: Time, call_stack: &mut Self::CallStack) {
+        self.subscriber
+            .resume_call_stack(time, &mut call_stack.inner);
+    }
+
+    fn create_span(
+        &self,
+        time: Time,
+        span_descriptor: &SpanDescriptor,
+        message: &[u8],
+        call_stack: &mut Self::CallStack,
+    ) -> error::Result {
+        let metadata = span_descriptor.metadata();
+        let forward = self.is_routed(metadata.target(), metadata.level());
+        if forward {
+            self.subscriber
+                .create_span(time, span_descriptor, message, &mut call_stack.inner)?;
+        }
+        call_stack.spans.push(forward);
+        Ok(())
+    }
+
+    fn drop_span(&self, call_stack: &mut Self::CallStack) {
+        if call_stack.spans.pop().expect("span stack is empty") {
+            self.subscriber.drop_span(&mut call_stack.inner);
+        }
+    }
+
+    fn destroy_span(&self, time: Time, call_stack: &mut Self::CallStack) {
+        if call_stack.spans.pop().expect("span stack is empty") {
+            self.subscriber.destroy_span(time, &mut call_stack.inner);
+        }
+    }
+
+    fn emit_event(
+        &self,
+        time: Time,
+        call_stack: &mut Self::CallStack,
+        event: &Event,
+        message: &[u8],
+    ) {
+        let metadata = event.metadata();
+        if self.is_routed(metadata.target(), metadata.level()) {
+            self.subscriber
+                .emit_event(time, &mut call_stack.inner, event, message);
+        }
+    }
+
+    fn emit_event_batch(
+        &self,
+        time: Time,
+        call_stack: &mut Self::CallStack,
+        records: &[Record<'_>],
+    ) {
+        let records = records
+            .iter()
+            .filter(|x| {
+                let metadata = x.event().metadata();
+                self.is_routed(metadata.target(), metadata.level())
+            })
+            .copied()
+            .collect::<Vec<_>>();
+        if !records.is_empty() {
+            self.subscriber
+                .emit_event_batch(time, &mut call_stack.inner, &records);
+        }
+    }
+
+    fn flush(&self) {
+        self.subscriber.flush();
+    }
+}
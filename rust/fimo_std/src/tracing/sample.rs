@@ -0,0 +1,54 @@
+//! Sampling of tracing events.
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Sampler of the events emitted from a single call site.
+///
+/// Passes through one of every `rate` events, starting with the first one. The emit macros
+/// construct one sampler per call site when invoked with the `sample:` argument, so that a
+/// sampled-out event only costs an atomic increment, and is never formatted.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::{context::Context, emit_trace, tracing::Sampler};
+///
+/// let sampler = Sampler::new(3);
+/// let passed = (0..9).filter(|_| sampler.sample()).count();
+/// assert_eq!(passed, 3);
+///
+/// let context = Context::new().expect("could not create context");
+/// for i in 0..1000 {
+///     emit_trace!(context, sample: 100, "iteration {i}");
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Sampler {
+    rate: usize,
+    counter: AtomicUsize,
+}
+
+impl Sampler {
+    /// Constructs a new `Sampler` passing through one of every `rate` events.
+    ///
+    /// A rate of `0` or `1` passes through all events.
+    pub const fn new(rate: usize) -> Self {
+        Self {
+            rate,
+            counter: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the sample rate.
+    pub fn rate(&self) -> usize {
+        self.rate
+    }
+
+    /// Returns whether the next event should be emitted.
+    #[inline]
+    pub fn sample(&self) -> bool {
+        if self.rate <= 1 {
+            return true;
+        }
+        self.counter.fetch_add(1, Ordering::Relaxed) % self.rate == 0
+    }
+}
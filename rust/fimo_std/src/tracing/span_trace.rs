@@ -0,0 +1,230 @@
+//! Snapshots of the entered spans.
+use crate::{
+    bindings,
+    error::{to_result_indirect_in_place, Error},
+    ffi::FFISharable,
+    tracing::{Level, Metadata, TracingSubsystem},
+};
+use alloc::{borrow::ToOwned, ffi::CString, string::String, vec::Vec};
+use core::{
+    ffi::CStr,
+    fmt::{Debug, Display, Formatter},
+};
+
+/// Span captured by a [`SpanTrace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanTraceFrame {
+    name: CString,
+    target: CString,
+    level: Level,
+    message: String,
+}
+
+impl SpanTraceFrame {
+    /// Returns the name of the span.
+    pub fn name(&self) -> &CStr {
+        &self.name
+    }
+
+    /// Returns the target of the span.
+    pub fn target(&self) -> &CStr {
+        &self.target
+    }
+
+    /// Returns the level of the span.
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    /// Returns the formatted message of the span.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for SpanTraceFrame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if self.target.is_empty() {
+            write!(f, "`{}`", self.name.to_string_lossy())?;
+        } else {
+            write!(
+                f,
+                "`{}::{}`",
+                self.target.to_string_lossy(),
+                self.name.to_string_lossy()
+            )?;
+        }
+        if !self.message.is_empty() {
+            write!(f, " ({})", self.message)?;
+        }
+        Ok(())
+    }
+}
+
+/// Snapshot of the spans entered on the active call stack.
+///
+/// The snapshot owns a copy of the spans, and remains valid after they are exited. It is empty,
+/// if the tracing subsystem is disabled, or the calling thread is not registered with it.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::{
+///     span_info,
+///     tracing::{init_simple, Level, SpanTrace},
+/// };
+///
+/// let guard = init_simple(Level::Info).expect("could not initialize the tracing");
+/// let context = **guard.context();
+///
+/// let _span = span_info!(context, "loading module, name: {}", "renderer");
+/// let trace = SpanTrace::capture(&context);
+/// assert_eq!(trace.frames().len(), 1);
+/// assert_eq!(trace.frames()[0].message(), "loading module, name: renderer");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpanTrace {
+    frames: Vec<SpanTraceFrame>,
+}
+
+impl SpanTrace {
+    /// Constructs an empty `SpanTrace`.
+    pub const fn empty() -> Self {
+        Self { frames: Vec::new() }
+    }
+
+    /// Captures the spans entered on the active call stack of the calling thread.
+    pub fn capture(ctx: &impl TracingSubsystem) -> Self {
+        Self::try_capture(ctx).unwrap_or_default()
+    }
+
+    fn try_capture(ctx: &impl TracingSubsystem) -> Result<Self, Error> {
+        // Safety: FFI call is safe.
+        let count = unsafe {
+            to_result_indirect_in_place(|error, count| {
+                *error = bindings::fimo_tracing_span_stack(
+                    ctx.share_to_ffi(),
+                    core::ptr::null_mut(),
+                    0,
+                    count.as_mut_ptr(),
+                );
+            })?
+        };
+        if count == 0 {
+            return Ok(Self::empty());
+        }
+
+        let mut spans = Vec::with_capacity(count);
+        // Safety: FFI call is safe.
+        let count = unsafe {
+            to_result_indirect_in_place(|error, count| {
+                *error = bindings::fimo_tracing_span_stack(
+                    ctx.share_to_ffi(),
+                    spans.as_mut_ptr(),
+                    spans.capacity(),
+                    count.as_mut_ptr(),
+                );
+            })?
+        };
+        // Safety: The spans have been initialized, and are only read while they are entered.
+        unsafe { spans.set_len(count.min(spans.capacity())) };
+
+        let frames = spans
+            .iter()
+            .map(|span: &bindings::FimoTracingSpanInfo| {
+                // Safety: The metadata is valid while the span is entered.
+                let metadata = unsafe { Metadata::borrow_from_ffi(span.metadata) };
+                let message = if span.message.is_null() {
+                    &[][..]
+                } else {
+                    // Safety: The message is valid while the span is entered.
+                    unsafe { core::slice::from_raw_parts(span.message.cast(), span.message_length) }
+                };
+                SpanTraceFrame {
+                    name: metadata.name().to_owned(),
+                    target: metadata.target().to_owned(),
+                    level: metadata.level(),
+                    message: String::from_utf8_lossy(message).into_owned(),
+                }
+            })
+            .collect();
+        Ok(Self { frames })
+    }
+
+    /// Returns the captured spans, ordered from the outermost to the innermost span.
+    pub fn frames(&self) -> &[SpanTraceFrame] {
+        &self.frames
+    }
+
+    /// Returns whether no spans were captured.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+impl Display for SpanTrace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        for (i, frame) in self.frames.iter().enumerate() {
+            if i != 0 {
+                write!(f, " > ")?;
+            }
+            write!(f, "{frame}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Error value annotated with the spans that were entered when it was created.
+///
+/// Is constructed by [`Error::new_traced`], and renders the spans as a breadcrumb after the value,
+/// e.g., ``symbol not found, created at span `loader::load` (name: renderer)``, so that the error
+/// can be related to the trace when it is logged.
+#[derive(Clone)]
+pub struct TracedError<T> {
+    value: T,
+    trace: SpanTrace,
+}
+
+impl<T> TracedError<T> {
+    /// Annotates `value` with the spans entered on the active call stack.
+    pub fn new(ctx: &impl TracingSubsystem, value: T) -> Self {
+        Self {
+            value,
+            trace: SpanTrace::capture(ctx),
+        }
+    }
+
+    /// Returns the annotated value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns the captured spans.
+    pub fn span_trace(&self) -> &SpanTrace {
+        &self.trace
+    }
+
+    /// Consumes the error, returning the annotated value.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+}
+
+impl<T: Debug> Debug for TracedError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TracedError")
+            .field("value", &self.value)
+            .field("trace", &self.trace)
+            .finish()
+    }
+}
+
+impl<T: Display> Display for TracedError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.value)?;
+        if !self.trace.is_empty() {
+            write!(f, ", created at span {}", self.trace)?;
+        }
+        Ok(())
+    }
+}
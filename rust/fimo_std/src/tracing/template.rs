@@ -0,0 +1,277 @@
+//! Message templates with deferred interpolation.
+use crate::{
+    bindings,
+    error::{to_result_indirect_in_place, Error},
+    ffi::{FFISharable, FFITransferable},
+    tracing::{Event, Formatter},
+};
+use alloc::{vec, vec::Vec};
+use core::{
+    ffi::CStr,
+    fmt::{Display, Write},
+    marker::PhantomData,
+};
+
+/// Maximum number of bytes of a field value recorded by the subscribers.
+pub(crate) const MAX_FIELD_VALUE_SIZE: usize = 1024;
+
+/// Constructs a new [`MessageTemplate`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! tracing_template {
+    ($template:literal $(, $field:ident = $value:expr)* $(,)?) => {
+        &$crate::tracing::MessageTemplate::new(
+            {
+                const MESSAGE: &'static str = core::concat!($template, '\0');
+                const MESSAGE_CSTR: &'static core::ffi::CStr =
+                    // Safety:
+                    unsafe { core::ffi::CStr::from_bytes_with_nul_unchecked(MESSAGE.as_bytes()) };
+                MESSAGE_CSTR
+            },
+            &[$(
+                $crate::tracing::Field::new(
+                    {
+                        const NAME: &'static str = core::concat!(core::stringify!($field), '\0');
+                        const NAME_CSTR: &'static core::ffi::CStr =
+                            // Safety:
+                            unsafe { core::ffi::CStr::from_bytes_with_nul_unchecked(NAME.as_bytes()) };
+                        NAME_CSTR
+                    },
+                    &$value,
+                )
+            ),*],
+        )
+    };
+}
+
+/// A named field of a [`MessageTemplate`].
+///
+/// The value of the field is only formatted, when the message is rendered by the tracing
+/// subsystem, or when it is requested by a subscriber.
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct Field<'a>(bindings::FimoTracingField, PhantomData<&'a ()>);
+
+impl<'a> Field<'a> {
+    /// Constructs a new field, whose value is formatted with its [`Display`] implementation.
+    pub fn new<T: Display>(name: &'static CStr, value: &'a T) -> Self {
+        unsafe extern "C" fn format_value<T: Display>(
+            buffer: *mut core::ffi::c_char,
+            buffer_len: usize,
+            data: *const core::ffi::c_void,
+            written: *mut usize,
+        ) -> bindings::FimoResult {
+            // Safety: The buffer should be valid and `data` points to a `T`.
+            unsafe {
+                let mut f = Formatter::new(buffer, buffer_len);
+                let _ = write!(f, "{}", &*data.cast::<T>());
+                core::ptr::write(written, f.pos.min(f.buffer.len()));
+                Result::<_, Error>::Ok(()).into_ffi()
+            }
+        }
+
+        Self(
+            bindings::FimoTracingField {
+                name: name.as_ptr(),
+                format: Some(format_value::<T>),
+                data: core::ptr::from_ref(value).cast(),
+            },
+            PhantomData,
+        )
+    }
+
+    /// Returns the name of the field.
+    pub fn name(&self) -> &CStr {
+        // Safety: Must contain a valid string.
+        unsafe { CStr::from_ptr(self.0.name) }
+    }
+
+    /// Formats the value of the field into the buffer.
+    ///
+    /// Returns the number of written bytes. The value is cut of, if it exceeds the length of the
+    /// buffer.
+    pub fn format_value(&self, buffer: &mut [u8]) -> Result<usize, Error> {
+        let format = self.0.format.ok_or(Error::EINVAL)?;
+        // Safety: FFI call is safe.
+        let written = unsafe {
+            to_result_indirect_in_place(|error, written| {
+                *error = format(
+                    buffer.as_mut_ptr().cast(),
+                    buffer.len(),
+                    self.0.data,
+                    written.as_mut_ptr(),
+                );
+            })?
+        };
+
+        // Formatters may report the untruncated length, like `snprintf`.
+        Ok(written.min(buffer.len()))
+    }
+
+    /// Formats the value of the field, cutting it off after [`MAX_FIELD_VALUE_SIZE`] bytes.
+    pub(crate) fn value(&self) -> Result<Vec<u8>, Error> {
+        let mut buffer = vec![0; MAX_FIELD_VALUE_SIZE];
+        let written = self.format_value(&mut buffer)?;
+        buffer.truncate(written);
+        Ok(buffer)
+    }
+}
+
+/// Un-interpolated message of an [`Event`].
+///
+/// A template is a message containing placeholders of the form `{name}`, which are replaced by
+/// the value of the [`Field`] with the same name, e.g., `"loaded {count} assets in {ms} ms"`.
+/// Braces can be escaped by doubling them. Placeholders without a matching field are kept as is.
+///
+/// The template is preserved through the tracing pipeline, enabling subscribers to either use the
+/// rendered message, or to store the template and the fields separately, see
+/// [`Event::message_template`].
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::{emit_info, tracing::{init_simple, Level}};
+///
+/// let guard = init_simple(Level::Info).expect("could not initialize the tracing");
+/// let count = 5;
+/// let ms = 12.5;
+/// emit_info!(guard.context(), template: "loaded {count} assets in {ms} ms", count = count, ms = ms);
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct MessageTemplate<'a>(
+    bindings::FimoTracingMessageTemplate,
+    PhantomData<&'a [Field<'a>]>,
+);
+
+impl<'a> MessageTemplate<'a> {
+    /// Constructs a new `MessageTemplate`.
+    pub const fn new(message: &'a CStr, fields: &'a [Field<'a>]) -> Self {
+        Self(
+            bindings::FimoTracingMessageTemplate {
+                type_: bindings::FimoStructType::FIMO_STRUCT_TYPE_TRACING_MESSAGE_TEMPLATE,
+                next: core::ptr::null(),
+                message: message.as_ptr(),
+                fields: if fields.is_empty() {
+                    core::ptr::null()
+                } else {
+                    fields.as_ptr().cast()
+                },
+                field_count: fields.len(),
+            },
+            PhantomData,
+        )
+    }
+
+    /// Returns the un-interpolated message.
+    pub fn message(&self) -> &CStr {
+        // Safety: Must contain a valid string.
+        unsafe { CStr::from_ptr(self.0.message) }
+    }
+
+    /// Returns the fields referenced by the template.
+    pub fn fields(&self) -> &[Field<'a>] {
+        if self.0.fields.is_null() {
+            &[]
+        } else {
+            // Safety: `Field` is transparent and the array contains `field_count` elements.
+            unsafe { core::slice::from_raw_parts(self.0.fields.cast(), self.0.field_count) }
+        }
+    }
+
+    /// Returns the field with the given name.
+    pub fn field(&self, name: &CStr) -> Option<&Field<'a>> {
+        self.fields().iter().find(|x| x.name() == name)
+    }
+
+    /// Renders the message into the buffer, replacing the placeholders with the values of their
+    /// fields.
+    ///
+    /// Returns the number of written bytes. The message is cut of, if it exceeds the length of the
+    /// buffer.
+    pub fn render(&self, buffer: &mut [u8]) -> Result<usize, Error> {
+        // Safety: FFI call is safe.
+        unsafe {
+            to_result_indirect_in_place(|error, written| {
+                *error = bindings::fimo_tracing_format_template(
+                    buffer.as_mut_ptr().cast(),
+                    buffer.len(),
+                    core::ptr::from_ref(&self.0).cast(),
+                    written.as_mut_ptr(),
+                );
+            })
+        }
+    }
+}
+
+impl FFISharable<*const bindings::FimoTracingMessageTemplate> for MessageTemplate<'_> {
+    type BorrowedView<'a> = &'a MessageTemplate<'a>;
+
+    fn share_to_ffi(&self) -> *const bindings::FimoTracingMessageTemplate {
+        &self.0
+    }
+
+    unsafe fn borrow_from_ffi<'a>(
+        ffi: *const bindings::FimoTracingMessageTemplate,
+    ) -> Self::BorrowedView<'a> {
+        // Safety: `MessageTemplate` is transparent.
+        unsafe { &*ffi.cast() }
+    }
+}
+
+impl Event {
+    /// Returns the [`MessageTemplate`] of the event, if it was emitted with one.
+    pub fn message_template(&self) -> Option<&MessageTemplate<'_>> {
+        // Safety: FFI call is safe.
+        let template = unsafe { bindings::fimo_tracing_event_template(self.share_to_ffi()) };
+        if template.is_null() {
+            None
+        } else {
+            // Safety: The template is valid for the lifetime of the event.
+            unsafe { Some(MessageTemplate::borrow_from_ffi(template)) }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(message: &CStr, fields: &[Field<'_>]) -> Vec<u8> {
+        let template = MessageTemplate::new(message, fields);
+        let mut buffer = [0; 128];
+        let written = template
+            .render(&mut buffer)
+            .expect("could not render the template");
+        buffer[..written].to_vec()
+    }
+
+    #[test]
+    fn render_fields() {
+        let fields = [Field::new(c"count", &5), Field::new(c"ms", &12.5)];
+        assert_eq!(
+            render(c"loaded {count} assets in {ms} ms", &fields),
+            b"loaded 5 assets in 12.5 ms"
+        );
+
+        let template = MessageTemplate::new(c"{ms}", &fields);
+        let field = template.field(c"ms").expect("the field should exist");
+        assert_eq!(field.value().expect("could not format the field"), b"12.5");
+    }
+
+    #[test]
+    fn render_escaped_braces() {
+        let fields = [Field::new(c"name", &"x")];
+        assert_eq!(render(c"{{{name}}} }} {{name}}", &fields), b"{x} } {name}");
+    }
+
+    #[test]
+    fn render_missing_fields() {
+        let fields = [Field::new(c"name", &"x")];
+        assert_eq!(
+            render(c"{name} {missing} {unterminated", &fields),
+            b"x {missing} {unterminated"
+        );
+        assert_eq!(render(c"{name}", &[]), b"{name}");
+    }
+}
@@ -8,7 +8,29 @@ use crate::{
     ffi::FFITransferable,
 };
 
+mod range;
+mod string;
+
+pub use range::*;
+pub use string::*;
+
 /// Constructs a new [`Version`].
+///
+/// A string literal is parsed at compile time with [`Version::parse`], and results in a compile
+/// error if it is not a valid version.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::version::Version;
+///
+/// const VERSION: Version = fimo_std::version!("1.2.3+4");
+/// assert_eq!(VERSION, fimo_std::version!(1, 2, 3, 4));
+/// ```
+///
+/// ```compile_fail
+/// let version = fimo_std::version!("1.2.3-beta.4");
+/// ```
 #[macro_export]
 macro_rules! version {
     ($major:literal, $minor:literal, $patch:literal $(,)?) => {{
@@ -18,7 +40,13 @@ macro_rules! version {
         $crate::version::Version::new_long($major, $minor, $patch, $build)
     }};
     ($version:literal) => {{
-        $crate::version::Version::try_from($version)
+        const VERSION: $crate::version::Version = match $crate::version::Version::parse($version) {
+            ::core::option::Option::Some(version) => version,
+            ::core::option::Option::None => {
+                ::core::panic!(::core::concat!("invalid version string: ", $version))
+            }
+        };
+        VERSION
     }};
 }
 
@@ -56,6 +84,77 @@ impl Version {
         })
     }
 
+    /// Parses a string into a `Version`.
+    ///
+    /// The string must be of the form `"major.minor.patch"` or `"major.minor.patch+build"`.
+    /// Returns `None` if the string is malformed or if a number is out of range. Unlike the
+    /// [`TryFrom<&str>`](Version#impl-TryFrom<%26str>-for-Version) implementation, the parsing
+    /// can be evaluated at compile time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fimo_std::version::Version;
+    ///
+    /// const VERSION: Option<Version> = Version::parse("1.2.3");
+    /// assert_eq!(VERSION, Some(Version::new(1, 2, 3)));
+    /// assert_eq!(Version::parse("1.2"), None);
+    /// ```
+    pub const fn parse(value: &str) -> Option<Self> {
+        const fn parse_number(bytes: &[u8], start: usize, max: u64) -> Option<(u64, usize)> {
+            let mut end = start;
+            let mut number: u64 = 0;
+            while end < bytes.len() && bytes[end].is_ascii_digit() {
+                let digit = (bytes[end] - b'0') as u64;
+                number = match number.checked_mul(10) {
+                    Some(x) => match x.checked_add(digit) {
+                        Some(x) if x <= max => x,
+                        _ => return None,
+                    },
+                    None => return None,
+                };
+                end += 1;
+            }
+            if end == start {
+                return None;
+            }
+            Some((number, end))
+        }
+
+        // Parse the numbers `major`, `minor`, `patch` and the optional `build`, each of which
+        // must be followed by its separator.
+        let bytes = value.as_bytes();
+        let mut numbers = [0; 4];
+        let mut position = 0;
+        let mut i = 0;
+        while i < numbers.len() {
+            let max = if i == 3 { u64::MAX } else { u32::MAX as u64 };
+            let (number, end) = match parse_number(bytes, position, max) {
+                Some(x) => x,
+                None => return None,
+            };
+            numbers[i] = number;
+            position = end;
+            i += 1;
+
+            if position == bytes.len() && i >= 3 {
+                break;
+            }
+            let separator = if i < 3 { b'.' } else { b'+' };
+            if position == bytes.len() || bytes[position] != separator || i == 4 {
+                return None;
+            }
+            position += 1;
+        }
+
+        Some(Self::new_long(
+            numbers[0] as u32,
+            numbers[1] as u32,
+            numbers[2] as u32,
+            numbers[3],
+        ))
+    }
+
     /// Returns the length required to format the `Version`.
     ///
     /// Returns the minimum required buffer length to format
@@ -83,6 +182,7 @@ impl Version {
     /// function. A size of at least [`Self::MAX_STR_LENGTH`]
     /// is guaranteed to work, regardless of the contents
     /// of the `Version`.
+    #[deprecated(note = "use `Version::to_version_string` instead")]
     pub fn write_str<'a>(&self, buff: &'a mut [u8]) -> Result<&'a mut str, Error> {
         let mut written = 0usize;
         // Safety: The pointers are valid.
@@ -115,6 +215,7 @@ impl Version {
     /// function. A size of at least [`Self::MAX_LONG_STR_LENGTH`]
     /// is guaranteed to work, regardless of the contents
     /// of the `Version`.
+    #[deprecated(note = "use `Version::to_version_string_long` instead")]
     pub fn write_str_long<'a>(&self, buff: &'a mut [u8]) -> Result<&'a mut str, Error> {
         let mut written = 0usize;
         // Safety: The pointers are valid.
@@ -138,6 +239,22 @@ impl Version {
         unsafe { Ok(core::str::from_utf8_unchecked_mut(str_buf)) }
     }
 
+    /// Formats the `Version` into a [`VersionString`].
+    ///
+    /// The string contains the major-, minor-, and patch numbers
+    /// of the `Version`.
+    pub const fn to_version_string(&self) -> VersionString {
+        VersionString::new(self)
+    }
+
+    /// Formats the `Version` into a [`VersionString`].
+    ///
+    /// The string contains the major-, minor-, patch- and build
+    /// numbers of the `Version`.
+    pub const fn to_version_string_long(&self) -> VersionString {
+        VersionString::new_long(self)
+    }
+
     /// Compares two `Versions`.
     ///
     /// Works like the implementation of [`Ord`], but also
@@ -200,11 +317,7 @@ impl Ord for Version {
 
 impl Display for Version {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let mut buff = [0; Self::MAX_LONG_STR_LENGTH];
-        let formatted = self
-            .write_str_long(&mut buff)
-            .expect("version string should fit into the string");
-        write!(f, "{formatted}")
+        write!(f, "{}", self.to_version_string_long())
     }
 }
 
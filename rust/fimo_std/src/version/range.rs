@@ -0,0 +1,332 @@
+use alloc::vec::Vec;
+use core::{
+    cmp::{max, min, Ordering},
+    fmt::Display,
+};
+
+use crate::bindings;
+
+use super::Version;
+
+/// A half-open range of [`Version`]s.
+///
+/// Contains all versions `v` with `start <= v < end`. Like the implementation of [`Ord`] for
+/// [`Version`], the range does not consider the build numbers. A range without an end contains
+/// all versions starting from `start`.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::version::{Version, VersionRange};
+///
+/// let a = VersionRange::compatible_with(Version::new(1, 2, 0));
+/// let b = VersionRange::new(Version::new(1, 0, 0), Some(Version::new(1, 5, 0)));
+/// let c = VersionRange::compatible_with(Version::new(0, 3, 1));
+///
+/// assert!(a.contains(&Version::new(1, 9, 3)));
+/// assert!(!a.contains(&Version::new(2, 0, 0)));
+///
+/// let ab = a.intersect(&b);
+/// assert_eq!(ab.start(), Version::new(1, 2, 0));
+/// assert_eq!(ab.end(), Some(Version::new(1, 5, 0)));
+/// assert!(a.intersect(&c).is_empty());
+/// assert_eq!(
+///     a.union(&b),
+///     Some(VersionRange::new(Version::new(1, 0, 0), Some(Version::new(2, 0, 0))))
+/// );
+/// assert_eq!(a.union(&c), None);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct VersionRange {
+    start: Version,
+    end: Option<Version>,
+}
+
+impl VersionRange {
+    /// Range containing all versions.
+    pub const FULL: Self = Self::new(Version::new(0, 0, 0), None);
+
+    /// Constructs a new `VersionRange`.
+    ///
+    /// An `end` of `None` constructs a range without an upper bound.
+    pub const fn new(start: Version, end: Option<Version>) -> Self {
+        Self { start, end }
+    }
+
+    /// Constructs a range containing all versions starting from `start`.
+    pub const fn at_least(start: Version) -> Self {
+        Self::new(start, None)
+    }
+
+    /// Constructs the range of all versions [compatible](Version::compatible) with `required`.
+    pub fn compatible_with(required: Version) -> Self {
+        let bindings::FimoVersion {
+            major,
+            minor,
+            patch,
+            ..
+        } = required.0;
+
+        let end = if major == 0 {
+            match minor.checked_add(1) {
+                Some(minor) => Some(Version::new(0, minor, 0)),
+                None => Some(Version::new(1, 0, 0)),
+            }
+        } else {
+            major.checked_add(1).map(|major| Version::new(major, 0, 0))
+        };
+        Self::new(Version::new(major, minor, patch), end)
+    }
+
+    /// Returns the inclusive lower bound of the range.
+    pub fn start(&self) -> Version {
+        self.start
+    }
+
+    /// Returns the exclusive upper bound of the range, if any.
+    pub fn end(&self) -> Option<Version> {
+        self.end
+    }
+
+    /// Checks whether the range contains no versions.
+    pub fn is_empty(&self) -> bool {
+        self.end.is_some_and(|end| end <= self.start)
+    }
+
+    /// Checks whether the range contains `version`.
+    pub fn contains(&self, version: &Version) -> bool {
+        *version >= self.start && self.end.map_or(true, |end| *version < end)
+    }
+
+    /// Checks whether the two ranges have at least one version in common.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !self.intersect(other).is_empty()
+    }
+
+    /// Returns the range of the versions contained in both ranges.
+    ///
+    /// The result is empty, if the ranges do not overlap.
+    pub fn intersect(&self, other: &Self) -> Self {
+        let start = max(self.start, other.start);
+        let end = match (self.end, other.end) {
+            (Some(a), Some(b)) => Some(min(a, b)),
+            (a, b) => a.or(b),
+        };
+        Self::new(start, end)
+    }
+
+    /// Returns the range of the versions contained in either range.
+    ///
+    /// Returns `None`, if the union can not be represented as a single range, i.e., if the ranges
+    /// neither overlap nor are adjacent.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        if other.is_empty() {
+            return Some(*self);
+        }
+        if self.is_empty() {
+            return Some(*other);
+        }
+
+        let (first, second) = if self.start <= other.start {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        if first.end.is_some_and(|end| end < second.start) {
+            return None;
+        }
+
+        let end = match (first.end, second.end) {
+            (Some(a), Some(b)) => Some(max(a, b)),
+            _ => None,
+        };
+        Some(Self::new(first.start, end))
+    }
+
+    /// Compares the upper bounds of two ranges, where no bound is the greatest.
+    fn cmp_end(&self, other: &Self) -> Ordering {
+        match (self.end, other.end) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
+
+impl PartialEq for VersionRange {
+    fn eq(&self, other: &Self) -> bool {
+        self.start.cmp(&other.start) == Ordering::Equal && self.cmp_end(other) == Ordering::Equal
+    }
+}
+
+impl Eq for VersionRange {}
+
+impl Display for VersionRange {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.end {
+            Some(end) => write!(f, "[{}, {end})", self.start),
+            None => write!(f, "[{}, ∞)", self.start),
+        }
+    }
+}
+
+impl From<Version> for VersionRange {
+    /// Constructs the range of all versions compatible with the version.
+    fn from(value: Version) -> Self {
+        Self::compatible_with(value)
+    }
+}
+
+/// A set of [`Version`]s.
+///
+/// The set is represented by ordered ranges, that neither overlap, nor are adjacent to each other.
+/// This allows the dependency resolution to combine the requirements on a symbol, and to check
+/// whether they can be satisfied simultaneously.
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::version::{Version, VersionRange, VersionSet};
+///
+/// let a = VersionSet::from(Version::new(0, 1, 0)).union(&Version::new(1, 3, 0).into());
+/// let b = VersionSet::from(Version::new(1, 4, 2));
+/// assert_eq!(a.ranges().len(), 2);
+///
+/// let ab = a.intersect(&b);
+/// assert!(!ab.is_empty());
+/// assert!(ab.contains(&Version::new(1, 5, 0)));
+/// assert!(!ab.contains(&Version::new(1, 3, 0)));
+/// assert!(!ab.contains(&Version::new(0, 1, 5)));
+///
+/// let c = VersionSet::from(VersionRange::new(Version::new(2, 0, 0), None));
+/// assert!(ab.intersect(&c).is_empty());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VersionSet {
+    ranges: Vec<VersionRange>,
+}
+
+impl VersionSet {
+    /// Constructs an empty `VersionSet`.
+    pub const fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// Constructs a set containing all versions.
+    pub fn full() -> Self {
+        VersionRange::FULL.into()
+    }
+
+    /// Returns the ordered ranges of the set.
+    pub fn ranges(&self) -> &[VersionRange] {
+        &self.ranges
+    }
+
+    /// Checks whether the set contains no versions.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Checks whether the set contains `version`.
+    pub fn contains(&self, version: &Version) -> bool {
+        self.ranges.iter().any(|x| x.contains(version))
+    }
+
+    /// Returns the set of the versions contained in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut ranges: Vec<_> = self.ranges.iter().chain(&other.ranges).copied().collect();
+        ranges.sort_by_key(|x| x.start);
+
+        let mut merged: Vec<VersionRange> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            if let Some(last) = merged.last_mut() {
+                if let Some(union) = last.union(&range) {
+                    *last = union;
+                    continue;
+                }
+            }
+            merged.push(range);
+        }
+        Self { ranges: merged }
+    }
+
+    /// Returns the set of the versions contained in both sets.
+    pub fn intersect(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(x), Some(y)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let intersection = x.intersect(y);
+            if !intersection.is_empty() {
+                ranges.push(intersection);
+            }
+
+            // Advance the range ending first, as it can not overlap with any following range.
+            if x.cmp_end(y).is_le() {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Checks whether the two sets have at least one version in common.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !self.intersect(other).is_empty()
+    }
+}
+
+impl Display for VersionSet {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "{{}}");
+        }
+        for (i, range) in self.ranges.iter().enumerate() {
+            if i != 0 {
+                write!(f, " ∪ ")?;
+            }
+            write!(f, "{range}")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<VersionRange> for VersionSet {
+    fn from(value: VersionRange) -> Self {
+        if value.is_empty() {
+            Self::new()
+        } else {
+            Self {
+                ranges: alloc::vec![value],
+            }
+        }
+    }
+}
+
+impl From<Version> for VersionSet {
+    /// Constructs the set of all versions compatible with the version.
+    fn from(value: Version) -> Self {
+        VersionRange::compatible_with(value).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range_eq_ignores_build() {
+        let a = VersionRange::new(Version::new_long(1, 2, 0, 5), Some(Version::new(2, 0, 0)));
+        let b = VersionRange::new(
+            Version::new_long(1, 2, 0, 7),
+            Some(Version::new_long(2, 0, 0, 3)),
+        );
+        assert_eq!(a, b);
+        assert_eq!(VersionSet::from(a), VersionSet::from(b));
+
+        let c = VersionRange::new(Version::new(1, 2, 0), None);
+        assert_ne!(a, c);
+        assert_eq!(c, VersionRange::at_least(Version::new_long(1, 2, 0, 1)));
+    }
+}
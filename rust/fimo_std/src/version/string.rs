@@ -0,0 +1,128 @@
+use core::{
+    fmt::{Debug, Display},
+    ops::Deref,
+};
+
+use super::Version;
+
+/// Fixed-capacity string containing a formatted [`Version`].
+///
+/// The string is formatted without allocating or calling into the library, and can therefore be
+/// constructed in `const` contexts. It has a stable layout, which allows embedding it into
+/// FFI structures. Like any other [`Display`] type, it can be written into any
+/// [`core::fmt::Write`] or [`std::io::Write`].
+///
+/// # Examples
+///
+/// ```
+/// use fimo_std::version::{Version, VersionString};
+/// use std::fmt::Write;
+///
+/// const VERSION: VersionString = Version::new_long(1, 2, 3, 4).to_version_string();
+/// assert_eq!(VERSION.as_str(), "1.2.3");
+///
+/// let long = Version::new_long(1, 2, 3, 4).to_version_string_long();
+/// assert_eq!(long.as_str(), "1.2.3+4");
+///
+/// let mut buffer = String::new();
+/// write!(buffer, "v{VERSION}").unwrap();
+/// assert_eq!(buffer, "v1.2.3");
+/// ```
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct VersionString {
+    len: usize,
+    buffer: [u8; Version::MAX_LONG_STR_LENGTH],
+}
+
+impl VersionString {
+    /// Formats the major-, minor-, and patch numbers of a `Version`.
+    pub const fn new(version: &Version) -> Self {
+        let version = &version.0;
+        Self {
+            len: 0,
+            buffer: [0; Version::MAX_LONG_STR_LENGTH],
+        }
+        .push_number(version.major as u64)
+        .push_byte(b'.')
+        .push_number(version.minor as u64)
+        .push_byte(b'.')
+        .push_number(version.patch as u64)
+    }
+
+    /// Formats the major-, minor-, patch- and build numbers of a `Version`.
+    pub const fn new_long(version: &Version) -> Self {
+        Self::new(version)
+            .push_byte(b'+')
+            .push_number(version.0.build)
+    }
+
+    /// Returns the formatted string.
+    pub const fn as_str(&self) -> &str {
+        // Safety: The buffer only contains ASCII digits and separators.
+        unsafe { core::str::from_utf8_unchecked(self.as_bytes()) }
+    }
+
+    /// Returns the bytes of the formatted string.
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.buffer.split_at(self.len).0
+    }
+
+    const fn push_byte(mut self, byte: u8) -> Self {
+        self.buffer[self.len] = byte;
+        self.len += 1;
+        self
+    }
+
+    const fn push_number(mut self, mut number: u64) -> Self {
+        let mut digits = [0u8; 20];
+        let mut count = 0;
+        loop {
+            digits[count] = b'0' + (number % 10) as u8;
+            count += 1;
+            number /= 10;
+            if number == 0 {
+                break;
+            }
+        }
+        while count > 0 {
+            count -= 1;
+            self = self.push_byte(digits[count]);
+        }
+        self
+    }
+}
+
+impl Deref for VersionString {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for VersionString {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq for VersionString {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for VersionString {}
+
+impl Debug for VersionString {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl Display for VersionString {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -0,0 +1,66 @@
+use fimo_std::{
+    bootstrap::{BootstrapError, Profile},
+    declare_items,
+    error::Error,
+    export_module,
+    module::*,
+    version::Version,
+};
+
+declare_items! {
+    extern answer @ (0, 1, 0): i32;
+}
+
+export_module! {
+    mod A {
+        name: "bootstrap_a",
+        description: "Test module a",
+        exports: {
+            answer: Answer = &42,
+        },
+    }
+}
+
+#[test]
+fn bootstrap_profile() -> Result<(), Error> {
+    let engine = Profile::new()
+        .with_local_modules()
+        .require_module(c"bootstrap_a")
+        .require_symbol(c"answer", c"", Version::new(0, 1, 0))
+        .bootstrap()?;
+    assert_eq!(engine.modules().len(), 1);
+
+    let answer = engine.module().load_symbol::<Answer>()?;
+    assert_eq!(*answer.lock(), 42);
+    drop(answer);
+
+    let a = ModuleInfo::find_by_name(&**engine.context(), c"bootstrap_a")?;
+    let context = engine.shutdown()?;
+    assert!(!a.is_loaded());
+    drop(context);
+
+    Ok(())
+}
+
+#[test]
+fn bootstrap_missing_requirements() {
+    let error = Profile::new()
+        .with_local_modules()
+        .require_module(c"missing")
+        .bootstrap()
+        .unwrap_err();
+    assert!(
+        matches!(error, BootstrapError::MissingModule { name } if name.as_c_str() == c"missing")
+    );
+
+    let error = Profile::new()
+        .with_local_modules()
+        .require_symbol(c"answer", c"", Version::new(0, 2, 0))
+        .bootstrap()
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        BootstrapError::IncompatibleSymbol { module, version, .. }
+            if module.as_c_str() == c"bootstrap_a" && version == Version::new(0, 1, 0)
+    ));
+}
@@ -1,6 +1,6 @@
 use fimo_std::{
     context::ContextBuilder,
-    declare_items, emit_info,
+    declare_dependencies, declare_items, emit_info,
     error::Error,
     export_module,
     module::*,
@@ -17,6 +17,13 @@ declare_items! {
     }
 }
 
+declare_dependencies! {
+    struct Dependencies {
+        a_1: AExport1,
+        b_1: b::BExport1,
+    }
+}
+
 export_module! {
     mod A {
         name: "a",
@@ -99,6 +106,11 @@ export_module! {
             b: "b.txt",
             img: "c/d.img",
         },
+        features: {
+            tracing: { default: true },
+            beta: { default: false, stability: experimental },
+            future: { default: true, requires: (255, 0, 0) },
+        },
         namespaces: [
             b::NamespaceItem,
         ],
@@ -155,6 +167,11 @@ impl<'m> ModuleConstructor<C<'m>> for CConstructor {
             resources.img().to_string_lossy()
         );
 
+        assert!(module.feature_enabled(c"tracing")?);
+        assert!(module.feature_enabled(c"beta")?);
+        assert!(!module.feature_enabled(c"future")?);
+        assert!(module.feature_enabled(c"missing").is_err());
+
         let imports = module.imports();
         assert_eq!(*imports.a_0(), 5);
         assert_eq!(*imports.a_1(), 10);
@@ -185,6 +202,11 @@ fn load_modules() -> Result<(), Error> {
 
     let _access = ThreadAccess::new(&context)?;
 
+    // The value is converted to the type of the parameter, once the module is loaded.
+    let mut config = ParameterConfig::new();
+    config.load_toml("[\"$features\"]\nc.beta = true\n[c]\npri_pri = 8\n")?;
+    config.apply(&*context)?;
+
     LoadingSet::with_loading_set(&*context, |ctx, set| {
         set.append_modules(ctx, None, |export| {
             emit_info!(ctx, "{export}");
@@ -202,6 +224,27 @@ fn load_modules() -> Result<(), Error> {
     assert!(b.is_loaded());
     assert!(c.is_loaded());
 
+    let parameters = ParameterInfo::list(&*context, c"c")?;
+    assert_eq!(parameters.len(), 9);
+    let pub_dep = parameters
+        .iter()
+        .find(|x| x.name() == c"pub_dep")
+        .expect("parameter not found")
+        .info();
+    assert_eq!(pub_dep.parameter_type(), ParameterType::U32);
+    assert_eq!(pub_dep.read_access(), ParameterAccess::Public);
+    assert_eq!(pub_dep.write_access(), ParameterAccess::Dependency);
+
+    let features = context.module_features(c"c")?;
+    assert_eq!(features.len(), 3);
+    let future = features
+        .iter()
+        .find(|x| x.name() == c"future")
+        .expect("feature not found");
+    assert!(future.default_enabled());
+    assert!(!future.is_available());
+    assert!(!future.is_enabled());
+
     module.acquire_dependency(&a)?;
     module.acquire_dependency(&b)?;
     module.acquire_dependency(&c)?;
@@ -213,6 +256,36 @@ fn load_modules() -> Result<(), Error> {
     module.include_namespace(b::NamespaceItem::NAME)?;
     assert!(module.load_symbol::<b::BExport0>().is_ok());
 
+    // The dependencies and namespaces are acquired on demand.
+    let injected = PseudoModule::new(&*context)?;
+    let dependencies = Dependencies::acquire(&injected)?;
+    assert_eq!(*dependencies.a_1(), 10);
+    assert_eq!(*dependencies.b_1(), 77);
+    drop(dependencies);
+    drop(injected);
+
+    // Scoped symbols relinquish the dependencies they acquired, once they are dropped.
+    let scoped = PseudoModule::new(&*context)?;
+    assert_eq!(scoped.with_symbol::<b::BExport0, _>(|x| *x)?, -2);
+    assert_eq!(scoped.has_dependency(&b)?, DependencyType::NoDependency);
+    let b_0 = scoped.acquire_scoped_symbol::<b::BExport0>()?;
+    let b_1 = scoped.acquire_scoped_symbol::<b::BExport1>()?;
+    assert_eq!((*b_0, *b_1), (-2, 77));
+    assert_eq!(scoped_symbols().len(), 2);
+    drop(b_0);
+    assert_eq!(
+        scoped.has_dependency(&b)?,
+        DependencyType::DynamicDependency
+    );
+    drop(b_1);
+    assert!(scoped_symbols().is_empty());
+    assert_eq!(scoped.has_dependency(&b)?, DependencyType::NoDependency);
+    assert_eq!(
+        scoped.has_namespace_dependency(b::NamespaceItem::NAME)?,
+        DependencyType::NoDependency
+    );
+    drop(scoped);
+
     drop(module);
     assert!(!a.is_loaded());
     assert!(!b.is_loaded());
@@ -1,4 +1,6 @@
 #include <fimo_std/array_list.h>
+#include <fimo_std/bus.h>
+#include <fimo_std/clock.h>
 #include <fimo_std/context.h>
 #include <fimo_std/error.h>
 #include <fimo_std/graph.h>
@@ -0,0 +1,174 @@
+use crate::Context;
+use fimo_std::error::{to_result, Error};
+use std::{
+    any::Any,
+    ffi::c_void,
+    fmt::{Debug, Formatter},
+    future::Future,
+    panic::AssertUnwindSafe,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard},
+    task::{Poll, Waker},
+};
+
+impl Context {
+    /// Runs a closure on the blocking thread pool of the runtime.
+    ///
+    /// The blocking thread pool is a dynamically sized set of threads, which are not part of any
+    /// [`WorkerGroup`](crate::WorkerGroup), and is intended for long-running synchronous
+    /// operations, like file io, that would otherwise occupy a worker. The closure is not run
+    /// inside a task, and may therefore not call the functions that require one.
+    ///
+    /// The returned [`BlockingHandle`] is a [`Future`], which allows a task to wait for the
+    /// completion of the closure with [`Context::block_on`], without blocking its worker.
+    ///
+    /// May be called from any thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fimo_tasks::__private_with_context(|_module, context| {
+    /// use fimo_tasks::{CommandBuffer, TaskStatus, WorkerGroupBuilder};
+    /// use std::{num::NonZeroUsize, time};
+    ///
+    /// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+    ///     .with_worker_count(NonZeroUsize::new(1))
+    ///     .build(&context)
+    ///     .expect("could not create worker group");
+    ///
+    /// let mut buffer = CommandBuffer::new();
+    /// let task = buffer.spawn_task(|context| {
+    ///     let handle = context
+    ///         .spawn_blocking(|| {
+    ///             std::thread::sleep(time::Duration::from_millis(10));
+    ///             5
+    ///         })
+    ///         .expect("could not spawn blocking job");
+    ///     handle.join(context).unwrap().unwrap()
+    /// });
+    ///
+    /// buffer
+    ///     .block_on(&group)
+    ///     .expect("could not enqueue command buffer");
+    /// assert_eq!(task.completion_status(), Some(TaskStatus::Completed));
+    /// assert_eq!(task.unwrap().unwrap(), 5);
+    /// # });
+    /// ```
+    pub fn spawn_blocking<F, T>(&self, f: F) -> Result<BlockingHandle<T>, Error>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        unsafe extern "C" fn run<F, T>(data: *mut c_void)
+        where
+            F: FnOnce() -> T + Send + 'static,
+            T: Send + 'static,
+        {
+            // Safety: The data was created by `Box::into_raw` and is only consumed once.
+            let (f, inner) = unsafe { *Box::from_raw(data.cast::<(F, Arc<BlockingInner<T>>)>()) };
+            let result = std::panic::catch_unwind(AssertUnwindSafe(f));
+
+            let waker = {
+                let mut state = inner.state();
+                state.result = Some(result);
+                state.waker.take()
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+
+        let inner = Arc::new(BlockingInner {
+            state: Mutex::new(BlockingState {
+                result: None,
+                waker: None,
+                consumed: false,
+            }),
+        });
+        let data = Box::into_raw(Box::new((f, inner.clone())));
+
+        // Safety: FFI call is safe.
+        let result = unsafe {
+            to_result((self.vtable().v0.spawn_blocking.unwrap_unchecked())(
+                self.data(),
+                Some(run::<F, T>),
+                data.cast(),
+            ))
+        };
+        if let Err(e) = result {
+            // Safety: The job was not queued, so we still own the data.
+            drop(unsafe { Box::from_raw(data) });
+            return Err(e);
+        }
+
+        Ok(BlockingHandle { inner })
+    }
+}
+
+/// Handle to a closure running on the blocking thread pool.
+///
+/// Created by [`Context::spawn_blocking`]. The handle resolves to the result of the closure, or to
+/// the panic payload, if the closure panicked. Dropping the handle detaches the closure.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct BlockingHandle<T> {
+    inner: Arc<BlockingInner<T>>,
+}
+
+struct BlockingInner<T> {
+    state: Mutex<BlockingState<T>>,
+}
+
+struct BlockingState<T> {
+    result: Option<Result<T, Box<dyn Any + Send + 'static>>>,
+    waker: Option<Waker>,
+    consumed: bool,
+}
+
+impl<T> BlockingInner<T> {
+    fn state(&self) -> MutexGuard<'_, BlockingState<T>> {
+        self.state.lock().expect("could not lock blocking job")
+    }
+}
+
+impl<T> BlockingHandle<T> {
+    /// Returns whether the closure has finished executing.
+    pub fn is_completed(&self) -> bool {
+        let state = self.inner.state();
+        state.consumed || state.result.is_some()
+    }
+
+    /// Waits for the closure to finish executing and returns its result.
+    ///
+    /// If called from a task, the task yields its worker while waiting.
+    pub fn join(self, ctx: &Context) -> Result<Result<T, Box<dyn Any + Send + 'static>>, Error> {
+        ctx.block_on(self)
+    }
+}
+
+impl<T> Future for BlockingHandle<T> {
+    type Output = Result<T, Box<dyn Any + Send + 'static>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.inner.state();
+        assert!(!state.consumed, "`BlockingHandle` polled after completion");
+        match state.result.take() {
+            Some(result) => {
+                state.consumed = true;
+                state.waker = None;
+                Poll::Ready(result)
+            }
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Debug for BlockingHandle<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlockingHandle")
+            .field("completed", &self.is_completed())
+            .finish_non_exhaustive()
+    }
+}
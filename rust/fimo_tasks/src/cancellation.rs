@@ -0,0 +1,178 @@
+use crate::{channel::WakerList, Context};
+use fimo_std::error::Error;
+use std::{
+    cell::RefCell,
+    fmt::{Debug, Formatter},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+    task::Waker,
+    time::Duration,
+};
+
+crate::task_specific! {
+    static CURRENT_TOKEN: RefCell<Option<CancellationToken>> = RefCell::new(None);
+}
+
+/// Panic payload of a task that exited due to a cancellation request.
+///
+/// Tasks that are cancelled before they are started, or that are unwound with this payload
+/// after their cancellation has been requested, report a
+/// [`TaskStatus::Cancelled`](crate::TaskStatus::Cancelled) status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TaskCancelled;
+
+/// A token for requesting the cooperative cancellation of tasks.
+///
+/// A token is associated with the tasks spawned by a [`CommandBuffer`](crate::CommandBuffer)
+/// through [`set_cancellation_token`](crate::CommandBuffer::set_cancellation_token). Cancelling
+/// the token does not interrupt the tasks. Instead, the tasks are expected to check for a
+/// cancellation request at appropriate points, e.g., with [`Context::is_cancelled`], and to exit
+/// early. Tasks that have not been started yet when the token is cancelled are skipped entirely.
+///
+/// Cloning a token returns a handle to the same cancellation state.
+#[derive(Default, Clone)]
+pub struct CancellationToken(Arc<TokenState>);
+
+#[derive(Default)]
+struct TokenState {
+    cancelled: AtomicBool,
+    wakers: Mutex<WakerList>,
+}
+
+impl CancellationToken {
+    /// Constructs a new `CancellationToken`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests the cancellation of all tasks associated with the token.
+    ///
+    /// Tasks waiting in [`Context::block_on`] are resumed.
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::Release);
+        let wakers = self.wakers().take();
+        crate::channel::wake_all(wakers);
+    }
+
+    /// Returns whether the cancellation has been requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::Acquire)
+    }
+
+    fn wakers(&self) -> MutexGuard<'_, WakerList> {
+        self.0
+            .wakers
+            .lock()
+            .expect("could not lock cancellation token")
+    }
+
+    /// Registers a waker that is woken once the cancellation is requested.
+    ///
+    /// The waker is removed when the returned registration is dropped. Wakers registered after
+    /// the cancellation has been requested are not woken, so the caller must check
+    /// [`is_cancelled`](Self::is_cancelled) after the registration.
+    pub(crate) fn register_waker(&self, waker: &Waker) -> WakerRegistration<'_> {
+        self.wakers().register(waker);
+        WakerRegistration {
+            token: self,
+            waker: waker.clone(),
+        }
+    }
+
+    /// Associates the token with the current task.
+    pub(crate) fn bind_to_current(&self, ctx: &Context) {
+        CURRENT_TOKEN.set(ctx, Some(self.clone()));
+    }
+}
+
+impl Debug for CancellationToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Registration of a waker with a [`CancellationToken`].
+pub(crate) struct WakerRegistration<'a> {
+    token: &'a CancellationToken,
+    waker: Waker,
+}
+
+impl Drop for WakerRegistration<'_> {
+    fn drop(&mut self) {
+        self.token.wakers().remove(&self.waker);
+    }
+}
+
+impl Context {
+    /// Returns whether the cancellation of the current task has been requested.
+    ///
+    /// Returns `false` if called from outside a task.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fimo_tasks::__private_with_context(|_module, context| {
+    /// use fimo_tasks::{CommandBuffer, TaskStatus, WorkerGroupBuilder};
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+    ///     .with_worker_count(NonZeroUsize::new(1))
+    ///     .build(&context)
+    ///     .expect("could not create worker group");
+    ///
+    /// let mut buffer = CommandBuffer::new();
+    /// let task = buffer.spawn_task(|context| {
+    ///     context.cancellation_token().unwrap().cancel();
+    ///     assert!(context.is_cancelled());
+    /// });
+    ///
+    /// buffer
+    ///     .block_on(&group)
+    ///     .expect("could not enqueue command buffer");
+    /// assert_eq!(task.completion_status(), Some(TaskStatus::Cancelled));
+    /// # });
+    /// ```
+    pub fn is_cancelled(&self) -> bool {
+        CURRENT_TOKEN
+            .try_with(self, |token| {
+                token.borrow().as_ref().is_some_and(|x| x.is_cancelled())
+            })
+            .unwrap_or(false)
+    }
+
+    /// Exits the current task if its cancellation has been requested.
+    ///
+    /// The task is unwound with a [`TaskCancelled`] payload.
+    pub fn exit_if_cancelled(&self) {
+        if self.is_cancelled() {
+            std::panic::resume_unwind(Box::new(TaskCancelled));
+        }
+    }
+
+    /// Returns the [`CancellationToken`] associated with the current task.
+    ///
+    /// Can only be called successfully from a task.
+    pub fn cancellation_token(&self) -> Result<CancellationToken, Error> {
+        CURRENT_TOKEN
+            .try_with(self, |token| token.borrow().clone())?
+            .ok_or(Error::EPERM)
+    }
+
+    /// Pauses the execution of the current task for the specified duration, or until the task
+    /// is cancelled.
+    ///
+    /// Returns [`Error::ECANCELED`] if the cancellation of the task was requested before the
+    /// duration has elapsed.
+    ///
+    /// Can only be called successfully from a task.
+    pub fn sleep_cancellable(&self, duration: Duration) -> Result<(), Error> {
+        if self.is_cancelled() {
+            return Err(Error::ECANCELED);
+        }
+        self.block_on(crate::sleep(duration))
+    }
+}
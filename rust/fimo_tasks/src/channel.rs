@@ -0,0 +1,871 @@
+use crate::Context;
+use fimo_std::error::Error;
+use std::{
+    collections::VecDeque,
+    fmt::{Debug, Display, Formatter},
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard},
+    task::{Poll, Waker},
+};
+
+/// Creates a multi-producer, single-consumer channel without a capacity limit.
+///
+/// Sending a value never suspends the sender, while receiving a value suspends the receiving task
+/// until a value is available. The wait is performed by [`Context::block_on`], i.e., the task
+/// is parked and its worker is free to run other tasks, until the value is sent.
+///
+/// # Examples
+///
+/// ```
+/// # fimo_tasks::__private_with_context(|_module, context| {
+/// use fimo_tasks::{unbounded_channel, CommandBuffer, TaskStatus, WorkerGroupBuilder};
+/// use std::num::NonZeroUsize;
+///
+/// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+///     .with_worker_count(NonZeroUsize::new(1))
+///     .build(&context)
+///     .expect("could not create worker group");
+///
+/// let (sender, receiver) = unbounded_channel();
+/// let mut buffer = CommandBuffer::new();
+/// let consumer = buffer.spawn_task(move |context| {
+///     let mut sum = 0;
+///     while let Ok(value) = receiver.recv(context) {
+///         sum += value;
+///     }
+///     sum
+/// });
+/// buffer.spawn_task(move |_| {
+///     for i in 1..=10 {
+///         sender.try_send(i).unwrap();
+///     }
+/// });
+///
+/// buffer
+///     .block_on(&group)
+///     .expect("could not enqueue command buffer");
+/// assert_eq!(consumer.completion_status(), Some(TaskStatus::Completed));
+/// assert_eq!(consumer.unwrap().unwrap(), 55);
+/// # });
+/// ```
+pub fn unbounded_channel<T>() -> (Sender<T>, Receiver<T>) {
+    new_channel(None)
+}
+
+/// Creates a multi-producer, single-consumer channel with a capacity limit.
+///
+/// Once the channel contains `capacity` values, sending a value suspends the sending task until
+/// the receiver makes room for it. A capacity of `0` is treated as a capacity of `1`.
+pub fn bounded_channel<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    new_channel(Some(capacity.max(1)))
+}
+
+fn new_channel<T>(capacity: Option<usize>) -> (Sender<T>, Receiver<T>) {
+    let inner = Arc::new(Channel {
+        state: Mutex::new(ChannelState {
+            queue: VecDeque::new(),
+            capacity,
+            num_senders: 1,
+            receiver_alive: true,
+            send_wakers: WakerList::default(),
+            recv_wakers: WakerList::default(),
+        }),
+    });
+    (
+        Sender {
+            inner: inner.clone(),
+        },
+        Receiver { inner },
+    )
+}
+
+/// Creates a channel for sending a single value.
+///
+/// # Examples
+///
+/// ```
+/// # fimo_tasks::__private_with_context(|_module, context| {
+/// use fimo_tasks::{oneshot_channel, CommandBuffer, TaskStatus, WorkerGroupBuilder};
+/// use std::num::NonZeroUsize;
+///
+/// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+///     .with_worker_count(NonZeroUsize::new(1))
+///     .build(&context)
+///     .expect("could not create worker group");
+///
+/// let (sender, receiver) = oneshot_channel();
+/// let mut buffer = CommandBuffer::new();
+/// let task = buffer.spawn_future(receiver);
+/// buffer.spawn_task(move |_| sender.send(5).unwrap());
+///
+/// buffer
+///     .block_on(&group)
+///     .expect("could not enqueue command buffer");
+/// assert_eq!(task.completion_status(), Some(TaskStatus::Completed));
+/// assert_eq!(task.unwrap().unwrap(), Ok(5));
+/// # });
+/// ```
+pub fn oneshot_channel<T>() -> (OneshotSender<T>, OneshotReceiver<T>) {
+    let inner = Arc::new(Oneshot {
+        state: Mutex::new(OneshotState {
+            value: None,
+            sender_alive: true,
+            receiver_alive: true,
+            wakers: WakerList::default(),
+        }),
+    });
+    (
+        OneshotSender {
+            inner: inner.clone(),
+        },
+        OneshotReceiver {
+            inner,
+            consumed: false,
+        },
+    )
+}
+
+/// Error returned by [`Sender::send`] and [`SendFuture`].
+///
+/// Contains the value that could not be sent.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum SendError<T> {
+    /// The receiver has been dropped.
+    Disconnected(T),
+    /// The task could not wait for the capacity of the channel, e.g., because its cancellation
+    /// was requested.
+    Interrupted(T, Error),
+}
+
+impl<T> SendError<T> {
+    /// Returns the value that could not be sent.
+    pub fn into_inner(self) -> T {
+        match self {
+            SendError::Disconnected(value) | SendError::Interrupted(value, _) => value,
+        }
+    }
+}
+
+impl<T> Debug for SendError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SendError::Disconnected(_) => f.write_str("Disconnected(..)"),
+            SendError::Interrupted(_, e) => f.debug_tuple("Interrupted").field(e).finish(),
+        }
+    }
+}
+
+impl<T> Display for SendError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SendError::Disconnected(_) => write!(f, "sending on a disconnected channel"),
+            SendError::Interrupted(_, e) => write!(f, "sending was interrupted: {e}"),
+        }
+    }
+}
+
+impl<T> std::error::Error for SendError<T> {}
+
+/// Error returned by [`Sender::try_send`].
+///
+/// Contains the value that could not be sent.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub enum TrySendError<T> {
+    /// The channel has reached its capacity.
+    Full(T),
+    /// The receiver has been dropped.
+    Disconnected(T),
+}
+
+impl<T> TrySendError<T> {
+    /// Returns the value that could not be sent.
+    pub fn into_inner(self) -> T {
+        match self {
+            TrySendError::Full(value) | TrySendError::Disconnected(value) => value,
+        }
+    }
+}
+
+impl<T> Debug for TrySendError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrySendError::Full(_) => f.write_str("Full(..)"),
+            TrySendError::Disconnected(_) => f.write_str("Disconnected(..)"),
+        }
+    }
+}
+
+impl<T> Display for TrySendError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TrySendError::Full(_) => write!(f, "sending on a full channel"),
+            TrySendError::Disconnected(_) => write!(f, "sending on a disconnected channel"),
+        }
+    }
+}
+
+impl<T> std::error::Error for TrySendError<T> {}
+
+/// Error returned by the receiving operations of the channels.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RecvError {
+    /// All senders have been dropped and the channel is empty.
+    Disconnected,
+    /// The task could not wait for a value, e.g., because its cancellation was requested.
+    Interrupted(Error),
+}
+
+impl Display for RecvError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecvError::Disconnected => write!(f, "receiving on an empty and disconnected channel"),
+            RecvError::Interrupted(e) => write!(f, "receiving was interrupted: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for RecvError {}
+
+/// Error returned by the non-suspending receiving operations of the channels.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum TryRecvError {
+    /// The channel is empty.
+    Empty,
+    /// All senders have been dropped and the channel is empty.
+    Disconnected,
+}
+
+impl Display for TryRecvError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TryRecvError::Empty => write!(f, "receiving on an empty channel"),
+            TryRecvError::Disconnected => {
+                write!(f, "receiving on an empty and disconnected channel")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TryRecvError {}
+
+#[derive(Default)]
+pub(crate) struct WakerList(Vec<Waker>);
+
+impl WakerList {
+    pub(crate) fn register(&mut self, waker: &Waker) {
+        if !self.0.iter().any(|w| w.will_wake(waker)) {
+            self.0.push(waker.clone());
+        }
+    }
+
+    pub(crate) fn remove(&mut self, waker: &Waker) {
+        self.0.retain(|w| !w.will_wake(waker));
+    }
+
+    /// Takes the wakers, so that they can be woken after the lock is released.
+    pub(crate) fn take(&mut self) -> Vec<Waker> {
+        std::mem::take(&mut self.0)
+    }
+}
+
+pub(crate) fn wake_all(wakers: Vec<Waker>) {
+    for waker in wakers {
+        waker.wake();
+    }
+}
+
+struct Channel<T> {
+    state: Mutex<ChannelState<T>>,
+}
+
+struct ChannelState<T> {
+    queue: VecDeque<T>,
+    capacity: Option<usize>,
+    num_senders: usize,
+    receiver_alive: bool,
+    send_wakers: WakerList,
+    recv_wakers: WakerList,
+}
+
+impl<T> Channel<T> {
+    fn state(&self) -> MutexGuard<'_, ChannelState<T>> {
+        self.state.lock().expect("could not lock channel")
+    }
+}
+
+impl<T> ChannelState<T> {
+    fn is_full(&self) -> bool {
+        self.capacity.is_some_and(|x| self.queue.len() >= x)
+    }
+}
+
+/// Sending half of a channel created by [`unbounded_channel`] or [`bounded_channel`].
+///
+/// The sender can be cloned to send values from multiple tasks.
+pub struct Sender<T> {
+    inner: Arc<Channel<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Sends a value into the channel, suspending the current task while the channel is full.
+    ///
+    /// Can only suspend successfully from a task.
+    pub fn send(&self, ctx: &Context, value: T) -> Result<(), SendError<T>> {
+        let mut future = self.send_async(value);
+        match ctx.block_on(&mut future) {
+            Ok(result) => result,
+            Err(e) => {
+                let value = future.value.take().expect("value already sent");
+                Err(SendError::Interrupted(value, e))
+            }
+        }
+    }
+
+    /// Returns a future, which sends the value once the channel has room for it.
+    pub fn send_async(&self, value: T) -> SendFuture<'_, T> {
+        SendFuture {
+            sender: self,
+            value: Some(value),
+        }
+    }
+
+    /// Attempts to send a value into the channel without suspending the current task.
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        let mut state = self.inner.state();
+        if !state.receiver_alive {
+            return Err(TrySendError::Disconnected(value));
+        }
+        if state.is_full() {
+            return Err(TrySendError::Full(value));
+        }
+
+        state.queue.push_back(value);
+        let wakers = state.recv_wakers.take();
+        drop(state);
+        wake_all(wakers);
+        Ok(())
+    }
+
+    /// Returns whether the receiver has been dropped.
+    pub fn is_disconnected(&self) -> bool {
+        !self.inner.state().receiver_alive
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.inner.state().num_senders += 1;
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.inner.state();
+        state.num_senders -= 1;
+        if state.num_senders == 0 {
+            let wakers = state.recv_wakers.take();
+            drop(state);
+            wake_all(wakers);
+        }
+    }
+}
+
+impl<T> Debug for Sender<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sender").finish_non_exhaustive()
+    }
+}
+
+/// Future returned by [`Sender::send_async`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SendFuture<'a, T> {
+    sender: &'a Sender<T>,
+    value: Option<T>,
+}
+
+impl<T> Unpin for SendFuture<'_, T> {}
+
+impl<T> Future for SendFuture<'_, T> {
+    type Output = Result<(), SendError<T>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        let value = self
+            .value
+            .take()
+            .expect("`SendFuture` polled after completion");
+        match self.sender.try_send(value) {
+            Ok(()) => Poll::Ready(Ok(())),
+            Err(TrySendError::Disconnected(value)) => {
+                Poll::Ready(Err(SendError::Disconnected(value)))
+            }
+            Err(TrySendError::Full(value)) => {
+                let mut state = self.sender.inner.state();
+                if state.receiver_alive && state.is_full() {
+                    state.send_wakers.register(cx.waker());
+                } else {
+                    // The state changed in the meantime.
+                    cx.waker().wake_by_ref();
+                }
+                drop(state);
+                self.value = Some(value);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Debug for SendFuture<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SendFuture")
+            .field("sent", &self.value.is_none())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Receiving half of a channel created by [`unbounded_channel`] or [`bounded_channel`].
+pub struct Receiver<T> {
+    inner: Arc<Channel<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// Receives a value from the channel, suspending the current task while the channel is empty.
+    ///
+    /// Returns [`RecvError::Disconnected`] once all senders have been dropped and the channel is
+    /// empty. Can only suspend successfully from a task.
+    pub fn recv(&self, ctx: &Context) -> Result<T, RecvError> {
+        ctx.block_on(self.recv_async())
+            .unwrap_or_else(|e| Err(RecvError::Interrupted(e)))
+    }
+
+    /// Returns a future, which resolves to the next value of the channel.
+    pub fn recv_async(&self) -> RecvFuture<'_, T> {
+        RecvFuture { receiver: self }
+    }
+
+    /// Attempts to receive a value from the channel without suspending the current task.
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let mut state = self.inner.state();
+        match state.queue.pop_front() {
+            Some(value) => {
+                let wakers = state.send_wakers.take();
+                drop(state);
+                wake_all(wakers);
+                Ok(value)
+            }
+            None if state.num_senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Returns the number of values in the channel.
+    pub fn len(&self) -> usize {
+        self.inner.state().queue.len()
+    }
+
+    /// Returns whether the channel is empty.
+    pub fn is_empty(&self) -> bool {
+        self.inner.state().queue.is_empty()
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.inner.state();
+        state.receiver_alive = false;
+        let queue = std::mem::take(&mut state.queue);
+        let wakers = state.send_wakers.take();
+        drop(state);
+
+        // The values may access the channel when dropped.
+        drop(queue);
+        wake_all(wakers);
+    }
+}
+
+impl<T> Debug for Receiver<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Receiver")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Future returned by [`Receiver::recv_async`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct RecvFuture<'a, T> {
+    receiver: &'a Receiver<T>,
+}
+
+impl<T> Future for RecvFuture<'_, T> {
+    type Output = Result<T, RecvError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        match self.receiver.try_recv() {
+            Ok(value) => Poll::Ready(Ok(value)),
+            Err(TryRecvError::Disconnected) => Poll::Ready(Err(RecvError::Disconnected)),
+            Err(TryRecvError::Empty) => {
+                let mut state = self.receiver.inner.state();
+                if state.queue.is_empty() && state.num_senders != 0 {
+                    state.recv_wakers.register(cx.waker());
+                } else {
+                    // The state changed in the meantime.
+                    cx.waker().wake_by_ref();
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+struct Oneshot<T> {
+    state: Mutex<OneshotState<T>>,
+}
+
+struct OneshotState<T> {
+    value: Option<T>,
+    sender_alive: bool,
+    receiver_alive: bool,
+    wakers: WakerList,
+}
+
+impl<T> Oneshot<T> {
+    fn state(&self) -> MutexGuard<'_, OneshotState<T>> {
+        self.state.lock().expect("could not lock channel")
+    }
+}
+
+/// Sending half of a channel created by [`oneshot_channel`].
+pub struct OneshotSender<T> {
+    inner: Arc<Oneshot<T>>,
+}
+
+impl<T> OneshotSender<T> {
+    /// Sends the value to the receiver.
+    ///
+    /// Returns the value, if the receiver has been dropped. Never suspends the current task.
+    pub fn send(self, value: T) -> Result<(), T> {
+        let mut state = self.inner.state();
+        if !state.receiver_alive {
+            return Err(value);
+        }
+        // The receiver is woken once the sender is dropped.
+        state.value = Some(value);
+        Ok(())
+    }
+
+    /// Returns whether the receiver has been dropped.
+    pub fn is_disconnected(&self) -> bool {
+        !self.inner.state().receiver_alive
+    }
+}
+
+impl<T> Drop for OneshotSender<T> {
+    fn drop(&mut self) {
+        let mut state = self.inner.state();
+        state.sender_alive = false;
+        let wakers = state.wakers.take();
+        drop(state);
+        wake_all(wakers);
+    }
+}
+
+impl<T> Debug for OneshotSender<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OneshotSender").finish_non_exhaustive()
+    }
+}
+
+/// Receiving half of a channel created by [`oneshot_channel`].
+///
+/// The receiver is a [`Future`] resolving to the sent value, or to [`RecvError::Disconnected`],
+/// if the sender was dropped without sending a value.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct OneshotReceiver<T> {
+    inner: Arc<Oneshot<T>>,
+    consumed: bool,
+}
+
+impl<T> OneshotReceiver<T> {
+    /// Receives the value, suspending the current task until it has been sent.
+    ///
+    /// Can only suspend successfully from a task.
+    pub fn recv(mut self, ctx: &Context) -> Result<T, RecvError> {
+        ctx.block_on(&mut self)
+            .unwrap_or_else(|e| Err(RecvError::Interrupted(e)))
+    }
+
+    /// Attempts to receive the value without suspending the current task.
+    ///
+    /// Returns [`TryRecvError::Disconnected`] once the value has been received.
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        if self.consumed {
+            return Err(TryRecvError::Disconnected);
+        }
+
+        let mut state = self.inner.state();
+        match state.value.take() {
+            Some(value) => {
+                drop(state);
+                self.consumed = true;
+                Ok(value)
+            }
+            None if !state.sender_alive => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+}
+
+impl<T> Future for OneshotReceiver<T> {
+    type Output = Result<T, RecvError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        match self.try_recv() {
+            Ok(value) => Poll::Ready(Ok(value)),
+            Err(TryRecvError::Disconnected) => Poll::Ready(Err(RecvError::Disconnected)),
+            Err(TryRecvError::Empty) => {
+                let mut state = self.inner.state();
+                if state.sender_alive {
+                    state.wakers.register(cx.waker());
+                } else {
+                    // The state changed in the meantime.
+                    cx.waker().wake_by_ref();
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Drop for OneshotReceiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.inner.state();
+        state.receiver_alive = false;
+        let value = state.value.take();
+        drop(state);
+        drop(value);
+    }
+}
+
+impl<T> Debug for OneshotReceiver<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OneshotReceiver")
+            .field("consumed", &self.consumed)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Operation of a channel that can be waited on by [`Select`].
+trait SelectOperation {
+    /// Returns whether the operation can complete without suspending the task.
+    fn is_ready(&self) -> bool;
+
+    /// Registers a waker, which is woken once the operation may have become ready.
+    fn register(&self, waker: &Waker);
+}
+
+impl<T> SelectOperation for Receiver<T> {
+    fn is_ready(&self) -> bool {
+        let state = self.inner.state();
+        !state.queue.is_empty() || state.num_senders == 0
+    }
+
+    fn register(&self, waker: &Waker) {
+        self.inner.state().recv_wakers.register(waker);
+    }
+}
+
+impl<T> SelectOperation for Sender<T> {
+    fn is_ready(&self) -> bool {
+        let state = self.inner.state();
+        !state.is_full() || !state.receiver_alive
+    }
+
+    fn register(&self, waker: &Waker) {
+        self.inner.state().send_wakers.register(waker);
+    }
+}
+
+impl<T> SelectOperation for OneshotReceiver<T> {
+    fn is_ready(&self) -> bool {
+        let state = self.inner.state();
+        self.consumed || state.value.is_some() || !state.sender_alive
+    }
+
+    fn register(&self, waker: &Waker) {
+        self.inner.state().wakers.register(waker);
+    }
+}
+
+/// Waits on multiple channel operations.
+///
+/// Each operation is identified by the index returned when it is added. Waiting on the
+/// operations returns the index of an operation that is ready, i.e., that can be performed
+/// without suspending the task with the non-suspending methods of the channel, like
+/// [`Receiver::try_recv`] or [`Sender::try_send`]. A receive operation is also ready, if the
+/// channel has been disconnected. The ready operations are selected in a round-robin fashion.
+///
+/// Since other tasks may access the channels in the meantime, a send operation on a channel with
+/// multiple senders may become full again before the value is sent.
+///
+/// # Examples
+///
+/// ```
+/// # fimo_tasks::__private_with_context(|_module, context| {
+/// use fimo_tasks::{
+///     oneshot_channel, unbounded_channel, CommandBuffer, Select, TaskStatus, WorkerGroupBuilder,
+/// };
+/// use std::num::NonZeroUsize;
+///
+/// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+///     .with_worker_count(NonZeroUsize::new(1))
+///     .build(&context)
+///     .expect("could not create worker group");
+///
+/// let (sender, receiver) = unbounded_channel::<u32>();
+/// let (stop_sender, mut stop_receiver) = oneshot_channel::<()>();
+///
+/// let mut buffer = CommandBuffer::new();
+/// let task = buffer.spawn_task(move |context| {
+///     let mut select = Select::new();
+///     let values = select.recv(&receiver);
+///     let stop = select.recv_oneshot(&stop_receiver);
+///
+///     let mut sum = 0;
+///     loop {
+///         let index = select.ready(context).unwrap();
+///         if index == values {
+///             if let Ok(value) = receiver.try_recv() {
+///                 sum += value;
+///             }
+///         } else if index == stop {
+///             break;
+///         }
+///     }
+///     drop(select);
+///
+///     // Drain the remaining values.
+///     while let Ok(value) = receiver.try_recv() {
+///         sum += value;
+///     }
+///     assert!(stop_receiver.try_recv().is_ok());
+///     sum
+/// });
+/// buffer.spawn_task(move |_| {
+///     sender.try_send(1).unwrap();
+///     sender.try_send(2).unwrap();
+///     stop_sender.send(()).unwrap();
+/// });
+///
+/// buffer
+///     .block_on(&group)
+///     .expect("could not enqueue command buffer");
+/// assert_eq!(task.completion_status(), Some(TaskStatus::Completed));
+/// assert_eq!(task.unwrap().unwrap(), 3);
+/// # });
+/// ```
+#[derive(Default)]
+pub struct Select<'a> {
+    operations: Vec<&'a dyn SelectOperation>,
+    next: usize,
+}
+
+impl<'a> Select<'a> {
+    /// Constructs an empty `Select`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a receive operation, returning its index.
+    pub fn recv<T>(&mut self, receiver: &'a Receiver<T>) -> usize {
+        self.push(receiver)
+    }
+
+    /// Adds a send operation, returning its index.
+    pub fn send<T>(&mut self, sender: &'a Sender<T>) -> usize {
+        self.push(sender)
+    }
+
+    /// Adds a receive operation of a oneshot channel, returning its index.
+    pub fn recv_oneshot<T>(&mut self, receiver: &'a OneshotReceiver<T>) -> usize {
+        self.push(receiver)
+    }
+
+    /// Returns the index of a ready operation without suspending the current task.
+    pub fn try_ready(&mut self) -> Option<usize> {
+        let len = self.operations.len();
+        let index = (0..len)
+            .map(|i| (self.next + i) % len)
+            .find(|&i| self.operations[i].is_ready())?;
+        self.next = (index + 1) % len;
+        Some(index)
+    }
+
+    /// Suspends the current task until one of the operations is ready and returns its index.
+    ///
+    /// Can only suspend successfully from a task.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no operation has been added.
+    pub fn ready(&mut self, ctx: &Context) -> Result<usize, Error> {
+        ctx.block_on(self.ready_async())
+    }
+
+    /// Returns a future, which resolves to the index of a ready operation.
+    ///
+    /// # Panics
+    ///
+    /// The future panics if no operation has been added.
+    pub fn ready_async(&mut self) -> SelectFuture<'_, 'a> {
+        SelectFuture { select: self }
+    }
+
+    fn push(&mut self, operation: &'a dyn SelectOperation) -> usize {
+        self.operations.push(operation);
+        self.operations.len() - 1
+    }
+}
+
+impl Debug for Select<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Select")
+            .field("operations", &self.operations.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Future returned by [`Select::ready_async`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SelectFuture<'s, 'a> {
+    select: &'s mut Select<'a>,
+}
+
+impl Future for SelectFuture<'_, '_> {
+    type Output = usize;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        assert!(
+            !self.select.operations.is_empty(),
+            "no operation has been added to the `Select`"
+        );
+        if let Some(index) = self.select.try_ready() {
+            return Poll::Ready(index);
+        }
+
+        // Register with all operations and check again, in case one of them became ready before
+        // the registration.
+        for operation in &self.select.operations {
+            operation.register(cx.waker());
+        }
+        match self.select.try_ready() {
+            Some(index) => Poll::Ready(index),
+            None => Poll::Pending,
+        }
+    }
+}
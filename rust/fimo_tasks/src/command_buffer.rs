@@ -1,7 +1,8 @@
 use crate::{
     bindings,
     task::{RawTask, TaskHandleInner},
-    Context, TaskHandle, TaskStatus, WorkerGroup, WorkerId,
+    CancellationToken, Context, Semaphore, TaskCancelled, TaskHandle, TaskPriority, TaskStatus,
+    TaskTag, WorkerGroup, WorkerId,
 };
 use fimo_std::{
     allocator::FimoAllocator,
@@ -10,8 +11,10 @@ use fimo_std::{
 };
 use std::{
     alloc::Allocator,
+    any::Any,
     cell::UnsafeCell,
-    ffi::CString,
+    ffi::{CStr, CString},
+    future::Future,
     marker::PhantomData,
     mem::{ManuallyDrop, MaybeUninit},
     num::NonZeroUsize,
@@ -179,7 +182,38 @@ where
         f: impl FnOnce(&Context) -> T + Send + 'static,
     ) -> TaskHandle<T, A> {
         // Safety: Is safe, as `f` is `Send`.
-        unsafe { self.inner.spawn_task(f) }
+        unsafe { self.inner.spawn_task(None, f) }
+    }
+
+    /// Spawns a new task with a name, returning a [`TaskHandle`] to it.
+    ///
+    /// The name is reported by [`WorkerGroup::enumerate_tasks`], and may be used by the runtime
+    /// for tracing purposes.
+    pub fn spawn_named_task<T: Send + 'static>(
+        &mut self,
+        name: &CStr,
+        f: impl FnOnce(&Context) -> T + Send + 'static,
+    ) -> TaskHandle<T, A> {
+        // Safety: Is safe, as `f` is `Send`.
+        unsafe { self.inner.spawn_task(Some(name.to_owned()), f) }
+    }
+
+    /// Spawns a new task driving a [`Future`] to completion, returning a [`TaskHandle`] to it.
+    ///
+    /// The future is driven by [`Context::block_on`]. An error of the runtime while driving the
+    /// future causes the abortion of the task, unless the task has been cancelled.
+    pub fn spawn_future<F>(&mut self, future: F) -> TaskHandle<F::Output, A>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        self.spawn_task(move |context| {
+            let result = context.block_on(future);
+            if result.is_err() {
+                context.exit_if_cancelled();
+            }
+            result.expect("could not drive the future to completion")
+        })
     }
 
     /// Inserts a barrier to synchronize the execution of the commands in the buffer.
@@ -193,17 +227,28 @@ where
     /// Inserts a dependency to another command buffer.
     ///
     /// This call ensures that the other command buffer is completed before the following commands
-    /// are started to be executed.
-    ///
-    /// # Note
-    ///
-    /// The command buffer must have been enqueued on the same [`WorkerGroup`] as the current
-    /// command buffer. Synchronization between multiple [`WorkerGroup`]s currently requires the
-    /// use of external synchronization mechanisms.
+    /// are started to be executed. The other command buffer may have been enqueued on a different
+    /// [`WorkerGroup`]. If it is aborted, the current command buffer is aborted as well.
     pub fn wait_command_buffer<T: Allocator>(&mut self, handle: CommandBufferHandle<'ctx, T>) {
         self.inner.wait_command_buffer(handle.handle);
     }
 
+    /// Inserts a dependency to a [`Semaphore`].
+    ///
+    /// This call ensures that the counter of the semaphore has reached `value` before the
+    /// following commands are started to be executed.
+    pub fn wait_semaphore(&mut self, semaphore: &Semaphore<'ctx>, value: u64) {
+        self.inner.wait_semaphore(semaphore.clone(), value);
+    }
+
+    /// Signals a [`Semaphore`] once all previous commands have been completed.
+    ///
+    /// Sets the counter of the semaphore to `value`, which must be greater than its counter value
+    /// at the time of the signal. Otherwise, the command buffer is aborted.
+    pub fn signal_semaphore(&mut self, semaphore: &Semaphore<'ctx>, value: u64) {
+        self.inner.signal_semaphore(semaphore.clone(), value);
+    }
+
     /// Specifies the single worker that is allowed to execute the following commands.
     pub fn set_worker(&mut self, worker: WorkerId) {
         self.inner.set_worker(worker);
@@ -219,6 +264,36 @@ where
         self.inner.set_stack_size(size);
     }
 
+    /// Specifies the tag of the following tasks.
+    ///
+    /// The execution times of the tasks are accounted to the specified tag.
+    pub fn set_tag(&mut self, tag: TaskTag) {
+        self.inner.set_tag(tag);
+    }
+
+    /// Specifies the scheduling priority of the following tasks.
+    ///
+    /// Tasks are spawned with the [`TaskPriority::Normal`] priority by default.
+    pub fn set_priority(&mut self, priority: TaskPriority) {
+        self.inner.set_priority(priority);
+    }
+
+    /// Specifies the [`FailurePolicy`] of the following tasks.
+    ///
+    /// Tasks are spawned with the [`FailurePolicy::AbortAll`] policy by default. The failed and
+    /// skipped tasks are reported by [`CommandBufferHandle::failures`].
+    pub fn set_failure_policy(&mut self, policy: FailurePolicy) {
+        self.inner.set_failure_policy(policy);
+    }
+
+    /// Specifies the [`CancellationToken`] of the following tasks.
+    ///
+    /// Cancelling the token requests the cancellation of all the tasks associated with it. A value
+    /// of `None` assigns a new token to each following task.
+    pub fn set_cancellation_token(&mut self, token: Option<CancellationToken>) {
+        self.inner.set_cancellation_token(token);
+    }
+
     /// Enqueues the command buffer into the [`WorkerGroup`].
     ///
     /// Enqueues the command buffer and returns a handle, which may be used to query the completion
@@ -282,6 +357,201 @@ where
     }
 }
 
+/// A reusable description of the commands of a [`CommandBuffer`].
+///
+/// The template is recorded once, and may be replayed any number of times with different inputs.
+/// Replaying a template only allocates the submitted tasks, and reserves the space required by the
+/// recorded commands up front. Submission state, like dependencies to semaphores or other command
+/// buffers, is not part of the template, and may be added to the [`CommandBuffer`] the template is
+/// recorded into.
+///
+/// # Examples
+///
+/// ```
+/// # fimo_tasks::__private_with_context(|_module, context| {
+/// use fimo_tasks::{CommandBufferStatus, CommandBufferTemplate, WorkerGroupBuilder};
+/// use std::{
+///     num::NonZeroUsize,
+///     sync::{
+///         atomic::{AtomicUsize, Ordering},
+///         Arc,
+///     },
+/// };
+///
+/// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+///     .with_worker_count(NonZeroUsize::new(2))
+///     .build(&context)
+///     .expect("could not create worker group");
+///
+/// let mut template = CommandBufferTemplate::<Arc<AtomicUsize>>::new();
+/// template.spawn_task(|_, counter| {
+///     counter.fetch_add(1, Ordering::Relaxed);
+/// });
+/// template.spawn_task(|_, counter| {
+///     counter.fetch_add(1, Ordering::Relaxed);
+/// });
+/// template.wait_barrier();
+/// template.spawn_task(|_, counter| assert_eq!(counter.load(Ordering::Relaxed), 2));
+///
+/// for _ in 0..3 {
+///     let counter = Arc::new(AtomicUsize::new(0));
+///     let status = template
+///         .instantiate(counter.clone())
+///         .block_on(&group)
+///         .expect("could not enqueue command buffer");
+///     assert_eq!(status, CommandBufferStatus::Completed);
+///     assert_eq!(counter.load(Ordering::Relaxed), 2);
+/// }
+/// # });
+/// ```
+pub struct CommandBufferTemplate<I> {
+    commands: Vec<TemplateCommand<I>>,
+}
+
+impl<I> CommandBufferTemplate<I>
+where
+    I: Send + Sync + 'static,
+{
+    /// Builds a new empty template.
+    pub fn new() -> Self {
+        Self {
+            commands: Vec::new(),
+        }
+    }
+
+    /// Returns whether the template is empty.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Returns the number of commands contained in the template.
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Records a task to be spawned on each replay of the template.
+    ///
+    /// The task is provided with a shared reference to the input of the replay.
+    pub fn spawn_task(&mut self, f: impl Fn(&Context, &I) + Send + Sync + 'static) {
+        self.commands.push(TemplateCommand::Task(Arc::new(f)));
+    }
+
+    /// Records a barrier.
+    ///
+    /// See [`CommandBuffer::wait_barrier`].
+    pub fn wait_barrier(&mut self) {
+        self.commands.push(TemplateCommand::Barrier);
+    }
+
+    /// Records the single worker that is allowed to execute the following commands.
+    ///
+    /// See [`CommandBuffer::set_worker`].
+    pub fn set_worker(&mut self, worker: WorkerId) {
+        self.commands.push(TemplateCommand::SetWorker(worker));
+    }
+
+    /// Records that all workers are allowed to execute the following commands.
+    ///
+    /// See [`CommandBuffer::enable_all_workers`].
+    pub fn enable_all_workers(&mut self) {
+        self.commands.push(TemplateCommand::EnableAllWorkers);
+    }
+
+    /// Records the minimum stack size for the following commands.
+    ///
+    /// See [`CommandBuffer::set_stack_size`].
+    pub fn set_stack_size(&mut self, size: Option<NonZeroUsize>) {
+        self.commands.push(TemplateCommand::SetStackSize(size));
+    }
+
+    /// Records the tag of the following tasks.
+    ///
+    /// See [`CommandBuffer::set_tag`].
+    pub fn set_tag(&mut self, tag: TaskTag) {
+        self.commands.push(TemplateCommand::SetTag(tag));
+    }
+
+    /// Records the scheduling priority of the following tasks.
+    ///
+    /// See [`CommandBuffer::set_priority`].
+    pub fn set_priority(&mut self, priority: TaskPriority) {
+        self.commands.push(TemplateCommand::SetPriority(priority));
+    }
+
+    /// Records the failure policy of the following tasks.
+    ///
+    /// See [`CommandBuffer::set_failure_policy`].
+    pub fn set_failure_policy(&mut self, policy: FailurePolicy) {
+        self.commands
+            .push(TemplateCommand::SetFailurePolicy(policy));
+    }
+
+    /// Builds a new [`CommandBuffer`] containing the recorded commands.
+    pub fn instantiate<'ctx>(&self, input: I) -> CommandBuffer<'ctx> {
+        let mut buffer = CommandBuffer::new();
+        self.record_into(&mut buffer, input);
+        buffer
+    }
+
+    /// Appends the recorded commands to an existing [`CommandBuffer`].
+    ///
+    /// The settings of the buffer, like the stack size or the priority, that are not overwritten by
+    /// the template also apply to the recorded tasks. The tasks of the template are spawned without
+    /// returning a [`TaskHandle`], and their completion may be observed through the status of the
+    /// command buffer.
+    pub fn record_into<A>(&self, buffer: &mut CommandBuffer<'_, A>, input: I)
+    where
+        A: Allocator + Clone + Send + 'static,
+    {
+        let input = Arc::new(input);
+        buffer.reserve(self.commands.len());
+        for command in &self.commands {
+            match command {
+                TemplateCommand::Task(f) => {
+                    let f = f.clone();
+                    let input = input.clone();
+                    drop(buffer.spawn_task(move |context| f(context, &input)));
+                }
+                TemplateCommand::Barrier => buffer.wait_barrier(),
+                TemplateCommand::SetWorker(worker) => buffer.set_worker(*worker),
+                TemplateCommand::EnableAllWorkers => buffer.enable_all_workers(),
+                TemplateCommand::SetStackSize(size) => buffer.set_stack_size(*size),
+                TemplateCommand::SetTag(tag) => buffer.set_tag(*tag),
+                TemplateCommand::SetPriority(priority) => buffer.set_priority(*priority),
+                TemplateCommand::SetFailurePolicy(policy) => buffer.set_failure_policy(*policy),
+            }
+        }
+    }
+}
+
+impl<I> Default for CommandBufferTemplate<I>
+where
+    I: Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I> std::fmt::Debug for CommandBufferTemplate<I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CommandBufferTemplate")
+            .field("len", &self.commands.len())
+            .finish_non_exhaustive()
+    }
+}
+
+enum TemplateCommand<I> {
+    Task(Arc<dyn Fn(&Context, &I) + Send + Sync>),
+    Barrier,
+    SetWorker(WorkerId),
+    EnableAllWorkers,
+    SetStackSize(Option<NonZeroUsize>),
+    SetTag(TaskTag),
+    SetPriority(TaskPriority),
+    SetFailurePolicy(FailurePolicy),
+}
+
 /// A list of commands to be executed by a [`WorkerGroup`].
 #[derive(Debug)]
 pub struct ScopedCommandBuffer<'scope, 'env, A: Allocator = FimoAllocator> {
@@ -338,7 +608,37 @@ where
         f: impl FnOnce(&Context) -> T + Send + 'scope,
     ) -> TaskHandle<T, A> {
         // Safety: Is safe, as `f` is `Send`.
-        unsafe { self.inner.spawn_task(f) }
+        unsafe { self.inner.spawn_task(None, f) }
+    }
+
+    /// Spawns a new task with a name, returning a [`TaskHandle`] to it.
+    ///
+    /// See [`CommandBuffer::spawn_named_task`].
+    pub fn spawn_named_task<T: Send + 'scope>(
+        &mut self,
+        name: &CStr,
+        f: impl FnOnce(&Context) -> T + Send + 'scope,
+    ) -> TaskHandle<T, A> {
+        // Safety: Is safe, as `f` is `Send`.
+        unsafe { self.inner.spawn_task(Some(name.to_owned()), f) }
+    }
+
+    /// Spawns a new task driving a [`Future`] to completion, returning a [`TaskHandle`] to it.
+    ///
+    /// The future is driven by [`Context::block_on`]. An error of the runtime while driving the
+    /// future causes the abortion of the task, unless the task has been cancelled.
+    pub fn spawn_future<F>(&mut self, future: F) -> TaskHandle<F::Output, A>
+    where
+        F: Future + Send + 'scope,
+        F::Output: Send + 'scope,
+    {
+        self.spawn_task(move |context| {
+            let result = context.block_on(future);
+            if result.is_err() {
+                context.exit_if_cancelled();
+            }
+            result.expect("could not drive the future to completion")
+        })
     }
 
     /// Inserts a barrier to synchronize the execution of the commands in the buffer.
@@ -352,17 +652,28 @@ where
     /// Inserts a dependency to another command buffer.
     ///
     /// This call ensures that the other command buffer is completed before the following commands
-    /// are started to be executed.
-    ///
-    /// # Note
-    ///
-    /// The command buffer must have been enqueued on the same [`WorkerGroup`] as the current
-    /// command buffer. Synchronization between multiple [`WorkerGroup`]s currently requires the
-    /// use of external synchronization mechanisms.
+    /// are started to be executed. The other command buffer may have been enqueued on a different
+    /// [`WorkerGroup`]. If it is aborted, the current command buffer is aborted as well.
     pub fn wait_command_buffer<T: Allocator>(&mut self, handle: CommandBufferHandle<'env, T>) {
         self.inner.wait_command_buffer(handle.handle);
     }
 
+    /// Inserts a dependency to a [`Semaphore`].
+    ///
+    /// This call ensures that the counter of the semaphore has reached `value` before the
+    /// following commands are started to be executed.
+    pub fn wait_semaphore(&mut self, semaphore: &Semaphore<'env>, value: u64) {
+        self.inner.wait_semaphore(semaphore.clone(), value);
+    }
+
+    /// Signals a [`Semaphore`] once all previous commands have been completed.
+    ///
+    /// Sets the counter of the semaphore to `value`, which must be greater than its counter value
+    /// at the time of the signal. Otherwise, the command buffer is aborted.
+    pub fn signal_semaphore(&mut self, semaphore: &Semaphore<'env>, value: u64) {
+        self.inner.signal_semaphore(semaphore.clone(), value);
+    }
+
     /// Specifies the single worker that is allowed to execute the following commands.
     pub fn set_worker(&mut self, worker: WorkerId) {
         self.inner.set_worker(worker);
@@ -378,6 +689,36 @@ where
         self.inner.set_stack_size(size);
     }
 
+    /// Specifies the tag of the following tasks.
+    ///
+    /// The execution times of the tasks are accounted to the specified tag.
+    pub fn set_tag(&mut self, tag: TaskTag) {
+        self.inner.set_tag(tag);
+    }
+
+    /// Specifies the scheduling priority of the following tasks.
+    ///
+    /// Tasks are spawned with the [`TaskPriority::Normal`] priority by default.
+    pub fn set_priority(&mut self, priority: TaskPriority) {
+        self.inner.set_priority(priority);
+    }
+
+    /// Specifies the [`FailurePolicy`] of the following tasks.
+    ///
+    /// Tasks are spawned with the [`FailurePolicy::AbortAll`] policy by default. The failed and
+    /// skipped tasks are reported by [`CommandBufferHandle::failures`].
+    pub fn set_failure_policy(&mut self, policy: FailurePolicy) {
+        self.inner.set_failure_policy(policy);
+    }
+
+    /// Specifies the [`CancellationToken`] of the following tasks.
+    ///
+    /// Cancelling the token requests the cancellation of all the tasks associated with it. A value
+    /// of `None` assigns a new token to each following task.
+    pub fn set_cancellation_token(&mut self, token: Option<CancellationToken>) {
+        self.inner.set_cancellation_token(token);
+    }
+
     /// Enqueues the command buffer into the [`WorkerGroup`].
     ///
     /// Enqueues the command buffer and returns a handle, which may be used to query the completion
@@ -477,14 +818,127 @@ where
     }
 }
 
+impl<'scope, 'env, A> Scope<'scope, 'env, A>
+where
+    A: Allocator + Clone + Send + 'static,
+{
+    /// Spawns a new scoped task, returning a [`ScopedTaskHandle`] to it.
+    ///
+    /// Unlike tasks spawned through a [`CommandBuffer`], the task may borrow non-`'static` data
+    /// from outside the scope, as the task is guaranteed to be completed before the end of the
+    /// scope. The task is enqueued in its own [`ScopedCommandBuffer`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fimo_tasks::__private_with_context(|_module, context| {
+    /// use fimo_tasks::{CommandBuffer, WorkerGroupBuilder};
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+    ///     .with_worker_count(NonZeroUsize::new(2))
+    ///     .build(&context)
+    ///     .expect("could not create worker group");
+    ///
+    /// let mut values = vec![1, 2, 3];
+    /// let sum = CommandBuffer::scope(&group, |scope| {
+    ///     let sum = scope
+    ///         .spawn(|_| values.iter().sum::<i32>())
+    ///         .expect("could not spawn task");
+    ///     scope
+    ///         .spawn(|_| values.len())
+    ///         .expect("could not spawn task");
+    ///     sum.join().unwrap()
+    /// });
+    ///
+    /// // All tasks are completed, so we can modify the values again.
+    /// values.push(4);
+    /// assert_eq!(sum, 6);
+    /// # });
+    /// ```
+    pub fn spawn<F, T>(&'scope self, f: F) -> Result<ScopedTaskHandle<'scope, 'env, T, A>, Error>
+    where
+        F: FnOnce(&Context) -> T + Send + 'scope,
+        T: Send + 'scope,
+    {
+        let mut buffer = ScopedCommandBuffer::new(self);
+        let task = buffer.spawn_task(f);
+        let handle = buffer.enqueue(|_| {})?;
+
+        Ok(ScopedTaskHandle {
+            scope: self,
+            task,
+            handle,
+        })
+    }
+}
+
+/// Handle to a task spawned with [`Scope::spawn`].
+pub struct ScopedTaskHandle<'scope, 'env, T, A: Allocator = FimoAllocator> {
+    scope: &'scope Scope<'scope, 'env, A>,
+    task: TaskHandle<T, A>,
+    handle: CommandBufferHandle<'env, A>,
+}
+
+impl<T, A> ScopedTaskHandle<'_, '_, T, A>
+where
+    A: Allocator + Clone + Send + 'static,
+{
+    /// Returns whether the task has been completed.
+    pub fn is_completed(&self) -> bool {
+        self.task.is_completed()
+    }
+
+    /// Requests the cancellation of the task.
+    ///
+    /// See [`TaskHandle::cancel`] for more details.
+    pub fn cancel(&self) {
+        self.task.cancel();
+    }
+
+    /// Returns the [`CancellationToken`] associated with the task.
+    pub fn cancellation_token(&self) -> &CancellationToken {
+        self.task.cancellation_token()
+    }
+
+    /// Waits for the task to complete and returns its result.
+    ///
+    /// Like [`ScopedCommandBuffer::block_on`], this method may be called from any thread. If the
+    /// current thread is managed by the [`WorkerGroup`] of the scope, only the current task is
+    /// blocked.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the runtime is not able to wait on the completion of the task.
+    pub fn join(self) -> Result<T, Box<dyn Any + Send + 'static>> {
+        let mut buffer = ScopedCommandBuffer::new(self.scope);
+        buffer.wait_command_buffer(self.handle);
+        buffer.block_on().expect("could not join the scoped task");
+        self.task.unwrap()
+    }
+}
+
+impl<T, A: Allocator + std::fmt::Debug> std::fmt::Debug for ScopedTaskHandle<'_, '_, T, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScopedTaskHandle")
+            .field("handle", &self.handle)
+            .finish_non_exhaustive()
+    }
+}
+
 #[derive(Debug)]
 enum Command<'scope, 'ctx, A: Allocator> {
     Task(Box<RawTask<'scope, A>, A>),
     Barrier,
     Handle(CommandBufferHandleInner<'ctx>),
+    WaitSemaphore(Semaphore<'ctx>, u64),
+    SignalSemaphore(Semaphore<'ctx>, u64),
     SetWorker(WorkerId),
     EnableAllWorkers,
     SetStackSize(usize),
+    SetTag(TaskTag),
+    SetPriority(TaskPriority),
+    SetFailurePolicy(FailurePolicy),
 }
 
 /// Completion status of a [`CommandBuffer`] or [`ScopedCommandBuffer`].
@@ -498,10 +952,148 @@ pub enum CommandBufferStatus {
     Aborted(usize),
 }
 
+/// Execution state of an enqueued [`CommandBuffer`] or [`ScopedCommandBuffer`].
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum CommandBufferState {
+    /// The command buffer is waiting on another command buffer or on a [`Semaphore`].
+    Blocked,
+    /// The commands of the command buffer are being executed.
+    Running,
+    /// All commands of the command buffer have been completed.
+    Completed,
+    /// The command buffer has been aborted, either due to an error in one of its commands, or due
+    /// to the abortion of one of its dependencies.
+    Aborted,
+}
+
+impl TryFrom<bindings::FiTasksCommandBufferState> for CommandBufferState {
+    type Error = Error;
+
+    fn try_from(value: bindings::FiTasksCommandBufferState) -> Result<Self, Self::Error> {
+        match value {
+            bindings::FiTasksCommandBufferState::FI_TASKS_COMMAND_BUFFER_STATE_BLOCKED => {
+                Ok(Self::Blocked)
+            }
+            bindings::FiTasksCommandBufferState::FI_TASKS_COMMAND_BUFFER_STATE_RUNNING => {
+                Ok(Self::Running)
+            }
+            bindings::FiTasksCommandBufferState::FI_TASKS_COMMAND_BUFFER_STATE_COMPLETED => {
+                Ok(Self::Completed)
+            }
+            bindings::FiTasksCommandBufferState::FI_TASKS_COMMAND_BUFFER_STATE_ABORTED => {
+                Ok(Self::Aborted)
+            }
+            _ => Err(Error::EINVAL),
+        }
+    }
+}
+
+/// Reaction of a [`CommandBuffer`] or [`ScopedCommandBuffer`] to the failure of one of its tasks.
+///
+/// A task fails if it panics, or if it is aborted by the runtime. Tasks that finish after their
+/// cancellation are not considered to have failed.
+#[derive(Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum FailurePolicy {
+    /// Aborts the command buffer.
+    ///
+    /// The commands that have not been processed yet are skipped, while the tasks that are already
+    /// running are run to completion.
+    #[default]
+    AbortAll,
+    /// Aborts the command buffer once it reaches a command that depends on the failed task.
+    ///
+    /// A barrier, a semaphore signal and the end of the buffer depend on all preceding tasks. The
+    /// commands before the first of them are processed as usual.
+    AbortDependents,
+    /// Ignores the failure of the task and continues processing the commands.
+    Continue,
+}
+
+impl From<FailurePolicy> for bindings::FiTasksFailurePolicy {
+    fn from(value: FailurePolicy) -> Self {
+        match value {
+            FailurePolicy::AbortAll => Self::FI_TASKS_FAILURE_POLICY_ABORT_ALL,
+            FailurePolicy::AbortDependents => Self::FI_TASKS_FAILURE_POLICY_ABORT_DEPENDENTS,
+            FailurePolicy::Continue => Self::FI_TASKS_FAILURE_POLICY_CONTINUE,
+        }
+    }
+}
+
+impl TryFrom<bindings::FiTasksFailurePolicy> for FailurePolicy {
+    type Error = Error;
+
+    fn try_from(value: bindings::FiTasksFailurePolicy) -> Result<Self, Self::Error> {
+        match value {
+            bindings::FiTasksFailurePolicy::FI_TASKS_FAILURE_POLICY_ABORT_ALL => Ok(Self::AbortAll),
+            bindings::FiTasksFailurePolicy::FI_TASKS_FAILURE_POLICY_ABORT_DEPENDENTS => {
+                Ok(Self::AbortDependents)
+            }
+            bindings::FiTasksFailurePolicy::FI_TASKS_FAILURE_POLICY_CONTINUE => Ok(Self::Continue),
+            _ => Err(Error::EINVAL),
+        }
+    }
+}
+
+/// Reason for which a command of a command buffer has not been executed successfully.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum CommandFailureReason {
+    /// The command failed, e.g., its task was aborted.
+    Failed,
+    /// The task was skipped, as the command buffer was aborted.
+    Skipped,
+    /// The task was skipped due to the shutdown of the [`WorkerGroup`].
+    Shutdown,
+}
+
+/// Failed or skipped command of a command buffer.
+///
+/// See [`CommandBufferHandle::failures`].
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub struct CommandFailure(bindings::FiTasksCommandFailure);
+
+impl CommandFailure {
+    /// Returns the index of the command.
+    pub fn index(&self) -> usize {
+        self.0.index
+    }
+
+    /// Returns the reason of the failure.
+    pub fn reason(&self) -> CommandFailureReason {
+        match self.0.reason {
+            bindings::FiTasksCommandFailureReason::FI_TASKS_COMMAND_FAILURE_REASON_SKIPPED => {
+                CommandFailureReason::Skipped
+            }
+            bindings::FiTasksCommandFailureReason::FI_TASKS_COMMAND_FAILURE_REASON_SHUTDOWN => {
+                CommandFailureReason::Shutdown
+            }
+            _ => CommandFailureReason::Failed,
+        }
+    }
+
+    /// Returns the index of the command that caused the abort of the command buffer.
+    ///
+    /// Is equal to [`CommandFailure::index`] for failed commands.
+    pub fn cause(&self) -> usize {
+        self.0.cause
+    }
+}
+
+impl std::fmt::Debug for CommandFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CommandFailure")
+            .field("index", &self.index())
+            .field("reason", &self.reason())
+            .field("cause", &self.cause())
+            .finish()
+    }
+}
+
 #[derive(Debug)]
 struct RawCommandBuffer<'scope, 'ctx, A: Allocator = FimoAllocator> {
     label: Option<CString>,
     commands: Vec<Command<'scope, 'ctx, A>, A>,
+    cancellation_token: Option<CancellationToken>,
 }
 
 impl<'scope, 'ctx, A> RawCommandBuffer<'scope, 'ctx, A>
@@ -512,6 +1104,7 @@ where
         Self {
             label,
             commands: Vec::new_in(alloc),
+            cancellation_token: None,
         }
     }
 
@@ -533,13 +1126,17 @@ where
 
     unsafe fn spawn_task<T: Send + 'scope>(
         &mut self,
+        label: Option<CString>,
         f: impl FnOnce(&Context) -> T + Send + 'scope,
     ) -> TaskHandle<T, A> {
         let alloc = self.commands.allocator().clone();
         let handle = Arc::new_in(
             TaskHandleInner {
                 completed: AtomicBool::new(false),
+                cancelled: AtomicBool::new(false),
+                token: self.cancellation_token.clone().unwrap_or_default(),
                 value: UnsafeCell::new(MaybeUninit::uninit()),
+                wakers: Default::default(),
             },
             alloc.clone(),
         );
@@ -547,9 +1144,21 @@ where
         let f = {
             let handle = handle.clone();
             move |context: &Context| {
-                let result =
-                    std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || f(context)));
-                let success = result.is_ok();
+                // Skip the task if it was cancelled before being started.
+                let token = &handle.token;
+                let skipped = token.is_cancelled();
+                let result = if skipped {
+                    Err(Box::new(TaskCancelled) as Box<dyn Any + Send + 'static>)
+                } else {
+                    std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
+                        token.bind_to_current(context);
+                        f(context)
+                    }))
+                };
+                let success = match &result {
+                    Ok(_) => true,
+                    Err(e) => skipped || (e.is::<TaskCancelled>() && token.is_cancelled()),
+                };
 
                 // Safety: We are the only ones that have access to the value at this point in time.
                 unsafe {
@@ -567,12 +1176,14 @@ where
 
         let s = {
             let handle = handle.clone();
-            move |_status: TaskStatus| {
-                handle.completed.store(true, Ordering::Release);
+            move |status: TaskStatus| {
+                let cancelled = status == TaskStatus::Completed && handle.token.is_cancelled();
+                handle.cancelled.store(cancelled, Ordering::Relaxed);
+                handle.complete();
             }
         };
 
-        let task = RawTask::new_in(None, f, s, alloc);
+        let task = RawTask::new_in(label, f, s, alloc);
         self.commands.push(Command::Task(task));
 
         TaskHandle { inner: handle }
@@ -586,6 +1197,15 @@ where
         self.commands.push(Command::Handle(handle));
     }
 
+    fn wait_semaphore(&mut self, semaphore: Semaphore<'ctx>, value: u64) {
+        self.commands.push(Command::WaitSemaphore(semaphore, value));
+    }
+
+    fn signal_semaphore(&mut self, semaphore: Semaphore<'ctx>, value: u64) {
+        self.commands
+            .push(Command::SignalSemaphore(semaphore, value));
+    }
+
     fn set_worker(&mut self, worker: WorkerId) {
         self.commands.push(Command::SetWorker(worker));
     }
@@ -599,6 +1219,22 @@ where
             .push(Command::SetStackSize(size.map_or(0, |x| x.get())));
     }
 
+    fn set_tag(&mut self, tag: TaskTag) {
+        self.commands.push(Command::SetTag(tag));
+    }
+
+    fn set_priority(&mut self, priority: TaskPriority) {
+        self.commands.push(Command::SetPriority(priority));
+    }
+
+    fn set_failure_policy(&mut self, policy: FailurePolicy) {
+        self.commands.push(Command::SetFailurePolicy(policy));
+    }
+
+    fn set_cancellation_token(&mut self, token: Option<CancellationToken>) {
+        self.cancellation_token = token;
+    }
+
     fn into_raw_command_buffer<F>(
         self,
         f: F,
@@ -695,6 +1331,24 @@ where
                                         }
                                     }
                                 }
+                                bindings::FiTasksCommandBufferEntryType::FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_WAIT_SEMAPHORE => {
+                                    // Safety:
+                                    unsafe {
+                                        let semaphore = entry.data.wait_semaphore.semaphore;
+                                        if let Some(release)  = (*semaphore.vtable).v0.release {
+                                            release(semaphore.data);
+                                        }
+                                    }
+                                }
+                                bindings::FiTasksCommandBufferEntryType::FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_SIGNAL_SEMAPHORE => {
+                                    // Safety:
+                                    unsafe {
+                                        let semaphore = entry.data.signal_semaphore.semaphore;
+                                        if let Some(release)  = (*semaphore.vtable).v0.release {
+                                            release(semaphore.data);
+                                        }
+                                    }
+                                }
                                 _ => {}
                             }
                         }
@@ -757,6 +1411,18 @@ where
                     type_: bindings::FiTasksCommandBufferEntryType::FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_WAIT_COMMAND_BUFFER,
                     data: bindings::FiTasksCommandBufferEntryData { wait_command_buffer: ManuallyDrop::new(handle.into_raw_handle()) },
                 }),
+                Command::WaitSemaphore(semaphore, value) => entry.write(bindings::FiTasksCommandBufferEntry {
+                    type_: bindings::FiTasksCommandBufferEntryType::FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_WAIT_SEMAPHORE,
+                    data: bindings::FiTasksCommandBufferEntryData {
+                        wait_semaphore: ManuallyDrop::new(bindings::FiTasksCommandBufferSemaphoreOperation { semaphore: semaphore.into_raw(), value }),
+                    },
+                }),
+                Command::SignalSemaphore(semaphore, value) => entry.write(bindings::FiTasksCommandBufferEntry {
+                    type_: bindings::FiTasksCommandBufferEntryType::FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_SIGNAL_SEMAPHORE,
+                    data: bindings::FiTasksCommandBufferEntryData {
+                        signal_semaphore: ManuallyDrop::new(bindings::FiTasksCommandBufferSemaphoreOperation { semaphore: semaphore.into_raw(), value }),
+                    },
+                }),
                 Command::SetWorker(worker) => entry.write(bindings::FiTasksCommandBufferEntry {
                     type_: bindings::FiTasksCommandBufferEntryType::FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_SET_WORKER,
                     data: bindings::FiTasksCommandBufferEntryData {set_worker: ManuallyDrop::new(worker.0)},
@@ -769,6 +1435,18 @@ where
                     type_: bindings::FiTasksCommandBufferEntryType::FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_SET_STACK_SIZE,
                     data: bindings::FiTasksCommandBufferEntryData {set_stack_size: ManuallyDrop::new(size)},
                 }),
+                Command::SetTag(tag) => entry.write(bindings::FiTasksCommandBufferEntry {
+                    type_: bindings::FiTasksCommandBufferEntryType::FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_SET_TAG,
+                    data: bindings::FiTasksCommandBufferEntryData {set_tag: ManuallyDrop::new(tag.0)},
+                }),
+                Command::SetPriority(priority) => entry.write(bindings::FiTasksCommandBufferEntry {
+                    type_: bindings::FiTasksCommandBufferEntryType::FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_SET_PRIORITY,
+                    data: bindings::FiTasksCommandBufferEntryData {set_priority: ManuallyDrop::new(priority.into())},
+                }),
+                Command::SetFailurePolicy(policy) => entry.write(bindings::FiTasksCommandBufferEntry {
+                    type_: bindings::FiTasksCommandBufferEntryType::FI_TASKS_COMMAND_BUFFER_ENTRY_TYPE_SET_FAILURE_POLICY,
+                    data: bindings::FiTasksCommandBufferEntryData {set_failure_policy: ManuallyDrop::new(policy.into())},
+                }),
             };
         }
 
@@ -906,6 +1584,61 @@ impl<'ctx, A: Allocator> CommandBufferHandle<'ctx, A> {
         }
     }
 
+    /// Returns the execution state of the command buffer.
+    ///
+    /// Unlike [`CommandBufferHandle::completion_status`], the state also reports whether the
+    /// command buffer is blocked on one of its dependencies.
+    pub fn state(&self) -> Result<CommandBufferState, Error> {
+        self.handle.state()
+    }
+
+    /// Returns the commands that have failed or have been skipped so far, sorted by their index.
+    ///
+    /// How the failure of a task affects the remaining commands is specified by its
+    /// [`FailurePolicy`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fimo_tasks::__private_with_context(|_module, context| {
+    /// use fimo_tasks::{
+    ///     CommandBuffer, CommandBufferStatus, CommandFailureReason, FailurePolicy,
+    ///     WorkerGroupBuilder,
+    /// };
+    /// use std::{num::NonZeroUsize, sync::mpsc};
+    ///
+    /// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+    ///     .with_worker_count(NonZeroUsize::new(1))
+    ///     .build(&context)
+    ///     .expect("could not create worker group");
+    ///
+    /// let mut buffer = CommandBuffer::new();
+    /// buffer.set_failure_policy(FailurePolicy::AbortDependents);
+    /// buffer.spawn_task(|_| panic!("failure"));
+    /// let sibling = buffer.spawn_task(|_| 5);
+    /// buffer.wait_barrier();
+    /// buffer.spawn_task(|_| {});
+    ///
+    /// let (sender, receiver) = mpsc::channel();
+    /// let handle = buffer
+    ///     .enqueue(&group, move |status| sender.send(status).unwrap())
+    ///     .expect("could not enqueue command buffer");
+    /// assert_eq!(receiver.recv().unwrap(), CommandBufferStatus::Aborted(1));
+    /// assert_eq!(sibling.unwrap().unwrap(), 5);
+    ///
+    /// let failures = handle.failures().unwrap();
+    /// assert_eq!(failures.len(), 2);
+    /// assert_eq!(failures[0].index(), 1);
+    /// assert_eq!(failures[0].reason(), CommandFailureReason::Failed);
+    /// assert_eq!(failures[1].index(), 4);
+    /// assert_eq!(failures[1].reason(), CommandFailureReason::Skipped);
+    /// assert_eq!(failures[1].cause(), 1);
+    /// # });
+    /// ```
+    pub fn failures(&self) -> Result<Vec<CommandFailure>, Error> {
+        self.handle.failures()
+    }
+
     /// Returns the [`WorkerGroup`] which executes the command buffer.
     pub fn worker_group(&self) -> Result<WorkerGroup<'ctx>, Error> {
         self.handle.worker_group()
@@ -913,19 +1646,21 @@ impl<'ctx, A: Allocator> CommandBufferHandle<'ctx, A> {
 
     /// Blocks the current task until the command buffer has been completed.
     ///
-    /// Can only be called from a task running in the same [`WorkerGroup`].
+    /// Can only be called from a task.
     pub fn join(self) -> Result<CommandBufferStatus, CommandBufferHandleError<'ctx, A>> {
         let this = ManuallyDrop::new(self);
 
-        // Safety: FFI call is safe
-        let result = unsafe {
-            to_result_indirect_in_place(|err, aborted| {
-                *err = (this.handle.vtable().v0.wait_on.unwrap_unchecked())(
-                    this.handle.data(),
-                    aborted.as_mut_ptr(),
-                );
-            })
-        };
+        let result = crate::suspension_point("join", || {
+            // Safety: FFI call is safe
+            unsafe {
+                to_result_indirect_in_place(|err, aborted| {
+                    *err = (this.handle.vtable().v0.wait_on.unwrap_unchecked())(
+                        this.handle.data(),
+                        aborted.as_mut_ptr(),
+                    );
+                })
+            }
+        });
 
         match result {
             Ok(_) => Ok(this.completion_status().unwrap()),
@@ -958,6 +1693,41 @@ impl<'ctx> CommandBufferHandleInner<'ctx> {
         Ok(WorkerGroup(group, PhantomData))
     }
 
+    fn state(&self) -> Result<CommandBufferState, Error> {
+        // Safety: FFI call is safe
+        let state = unsafe { self.vtable().v0.state.unwrap_unchecked()(self.data()) };
+        state.try_into()
+    }
+
+    fn failures(&self) -> Result<Vec<CommandFailure>, Error> {
+        let mut num_failures = 0;
+        // Safety: FFI call is safe
+        let failures = unsafe {
+            to_result_indirect_in_place(|err, failures| {
+                *err = self.vtable().v0.failures.unwrap_unchecked()(
+                    self.data(),
+                    failures.as_mut_ptr(),
+                    &mut num_failures,
+                );
+            })?
+        };
+        if failures.is_null() {
+            return Ok(Vec::new());
+        }
+
+        // We can cast the pointer to an `CommandFailure` pointer, since the two types have the
+        // same layout.
+        let failures = failures.cast::<CommandFailure>();
+
+        // Safety: The API guarantees that we are returned a contiguous range of memory containing
+        // the failures, which is owned by us.
+        unsafe {
+            let result = std::slice::from_raw_parts(failures, num_failures).to_vec();
+            fimo_std::bindings::fimo_free(failures.cast());
+            Ok(result)
+        }
+    }
+
     fn into_raw_handle(self) -> bindings::FiTasksCommandBufferHandle {
         let this = ManuallyDrop::new(self);
         this.handle
@@ -1037,3 +1807,58 @@ impl<A: Allocator> std::fmt::Display for CommandBufferHandleError<'_, A> {
 }
 
 impl<A: Allocator> std::error::Error for CommandBufferHandleError<'_, A> where Self: std::fmt::Debug {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WorkerGroupBuilder;
+
+    /// Panic payload counting the number of times it was dropped.
+    struct CountedDrop(Arc<AtomicUsize>);
+
+    impl Drop for CountedDrop {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::AcqRel);
+        }
+    }
+
+    #[test]
+    fn abort_handler_drops_payload_once() {
+        crate::__private_with_context(|_module, context| {
+            let group = WorkerGroupBuilder::new(
+                c"abort_handler_drops_payload_once",
+                &[Default::default()],
+                None,
+            )
+            .with_worker_count(NonZeroUsize::new(1))
+            .build(context)
+            .expect("could not create worker group");
+
+            // The tasks spawned by a command buffer catch their panics, so we record a raw task,
+            // whose panic payload is passed to the abortion handler.
+            let drops = Arc::new(AtomicUsize::new(0));
+            let status = Arc::new(Mutex::new(None));
+            let task = {
+                let drops = drops.clone();
+                let status = status.clone();
+                RawTask::new_in(
+                    None,
+                    move |_: &Context| -> Result<(), ()> {
+                        std::panic::panic_any(CountedDrop(drops))
+                    },
+                    move |x| *status.lock().unwrap() = Some(x),
+                    FimoAllocator,
+                )
+            };
+            let mut buffer = CommandBuffer::new();
+            buffer.inner.commands.push(Command::Task(task));
+
+            let result = buffer
+                .block_on(&group)
+                .expect("could not enqueue command buffer");
+            assert_eq!(result, CommandBufferStatus::Aborted(0));
+            assert_eq!(*status.lock().unwrap(), Some(TaskStatus::Aborted));
+            assert_eq!(drops.load(Ordering::Acquire), 1);
+        });
+    }
+}
@@ -0,0 +1,450 @@
+use crate::{
+    channel::{wake_all, WakerList},
+    Context,
+};
+use fimo_std::error::Error;
+use std::{
+    fmt::{Debug, Formatter},
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard},
+    task::Poll,
+};
+
+/// A source of wakeups, which is notified manually.
+///
+/// An event source allows code outside the runtime, e.g., a callback of a foreign library or a
+/// thread waiting on a device, to resume the tasks waiting on it. Each notification resumes at
+/// most one waiter, and notifications which have not been consumed yet are coalesced, i.e., a
+/// wait returns immediately if the source has been notified since the last wait completed.
+///
+/// The source is a handle to a shared state, so clones of it notify the same waiters.
+///
+/// # Examples
+///
+/// ```
+/// # fimo_tasks::__private_with_context(|_module, context| {
+/// use fimo_tasks::{CommandBuffer, EventSource, TaskStatus, WorkerGroupBuilder};
+/// use std::num::NonZeroUsize;
+///
+/// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+///     .with_worker_count(NonZeroUsize::new(1))
+///     .build(&context)
+///     .expect("could not create worker group");
+///
+/// let source = EventSource::new();
+/// let mut buffer = CommandBuffer::new();
+/// let task = buffer.spawn_task({
+///     let source = source.clone();
+///     move |context| source.wait(context).unwrap()
+/// });
+///
+/// // Notify the task from a thread outside the runtime.
+/// std::thread::spawn(move || source.notify());
+///
+/// buffer
+///     .block_on(&group)
+///     .expect("could not enqueue command buffer");
+/// assert_eq!(task.completion_status(), Some(TaskStatus::Completed));
+/// # });
+/// ```
+#[derive(Clone, Default)]
+pub struct EventSource {
+    inner: Arc<Mutex<EventState>>,
+}
+
+#[derive(Default)]
+struct EventState {
+    notified: bool,
+    wakers: WakerList,
+}
+
+impl EventSource {
+    /// Constructs a new `EventSource`, which has not been notified.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Notifies the source, resuming one of its waiters.
+    ///
+    /// May be called from any thread.
+    pub fn notify(&self) {
+        let wakers = {
+            let mut state = self.state();
+            state.notified = true;
+            state.wakers.take()
+        };
+        wake_all(wakers);
+    }
+
+    /// Consumes a pending notification without suspending the current task.
+    ///
+    /// Returns whether the source had been notified.
+    pub fn try_wait(&self) -> bool {
+        std::mem::take(&mut self.state().notified)
+    }
+
+    /// Suspends the current task until the source is notified.
+    ///
+    /// Returns [`Error::ECANCELED`] if the cancellation of the current task is requested while
+    /// waiting. Can only suspend successfully from a task.
+    pub fn wait(&self, ctx: &Context) -> Result<(), Error> {
+        ctx.block_on(self.wait_async())
+    }
+
+    /// Returns a future, which resolves once the source is notified.
+    pub fn wait_async(&self) -> EventWait<'_> {
+        EventWait { source: self }
+    }
+
+    fn state(&self) -> MutexGuard<'_, EventState> {
+        self.inner.lock().expect("could not lock event source")
+    }
+}
+
+impl Debug for EventSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventSource")
+            .field("notified", &self.state().notified)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Future returned by [`EventSource::wait_async`].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct EventWait<'a> {
+    source: &'a EventSource,
+}
+
+impl Future for EventWait<'_> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.source.state();
+        if std::mem::take(&mut state.notified) {
+            Poll::Ready(())
+        } else {
+            state.wakers.register(cx.waker());
+            Poll::Pending
+        }
+    }
+}
+
+/// Operation whose readiness is awaited by [`Context::wait_io`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Interest {
+    /// The file descriptor can be read from without blocking.
+    Readable,
+    /// The file descriptor can be written to without blocking.
+    Writable,
+}
+
+#[cfg(unix)]
+pub use readiness::*;
+
+#[cfg(unix)]
+mod readiness {
+    use super::Interest;
+    use crate::Context;
+    use fimo_std::error::Error;
+    use std::{
+        collections::BTreeMap,
+        future::Future,
+        os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd},
+        pin::Pin,
+        sync::{Mutex, MutexGuard, OnceLock},
+        task::{Poll, Waker},
+    };
+
+    impl Context {
+        /// Suspends the current task until the file descriptor is ready for the operation of
+        /// `interest`.
+        ///
+        /// The file descriptor is monitored by a reactor thread shared by all worker groups, which
+        /// resumes the task once the file descriptor is ready, or once an error or hang-up has
+        /// been reported for it. In that case, the subsequent operation returns the error. This
+        /// allows tasks to wait for external I/O, like sockets or pipes, without blocking their
+        /// worker. The readiness may be spurious, so the file descriptor should be in
+        /// non-blocking mode.
+        ///
+        /// Returns [`Error::ECANCELED`] if the cancellation of the current task is requested while
+        /// waiting. Can only suspend successfully from a task.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # fimo_tasks::__private_with_context(|_module, context| {
+        /// use fimo_tasks::{CommandBuffer, Interest, WorkerGroupBuilder};
+        /// use std::{
+        ///     io::{Read, Write},
+        ///     num::NonZeroUsize,
+        ///     os::unix::net::UnixStream,
+        /// };
+        ///
+        /// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+        ///     .with_worker_count(NonZeroUsize::new(1))
+        ///     .build(&context)
+        ///     .expect("could not create worker group");
+        ///
+        /// let (mut sender, mut receiver) = UnixStream::pair().expect("could not create sockets");
+        /// receiver.set_nonblocking(true).unwrap();
+        ///
+        /// let mut buffer = CommandBuffer::new();
+        /// let task = buffer.spawn_task(move |context| {
+        ///     context.wait_io(&receiver, Interest::Readable).unwrap();
+        ///     let mut value = [0; 5];
+        ///     receiver.read_exact(&mut value).unwrap();
+        ///     value
+        /// });
+        ///
+        /// sender.write_all(b"hello").unwrap();
+        /// buffer
+        ///     .block_on(&group)
+        ///     .expect("could not enqueue command buffer");
+        /// assert_eq!(&task.unwrap().unwrap(), b"hello");
+        /// # });
+        /// ```
+        pub fn wait_io(&self, fd: &impl AsFd, interest: Interest) -> Result<(), Error> {
+            self.block_on(io_ready(fd.as_fd(), interest))?
+        }
+    }
+
+    /// Creates a future that resolves once the file descriptor is ready for the operation of
+    /// `interest`.
+    ///
+    /// See [`Context::wait_io`] for more details.
+    pub fn io_ready(fd: BorrowedFd<'_>, interest: Interest) -> IoReady<'_> {
+        IoReady {
+            fd,
+            interest,
+            token: None,
+        }
+    }
+
+    /// Future returned by [`io_ready`].
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct IoReady<'fd> {
+        fd: BorrowedFd<'fd>,
+        interest: Interest,
+        token: Option<u64>,
+    }
+
+    impl Future for IoReady<'_> {
+        type Output = Result<(), Error>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+            let reactor = match Reactor::get() {
+                Ok(reactor) => reactor,
+                Err(e) => return Poll::Ready(Err(e)),
+            };
+            match self.token {
+                None => {
+                    let events = match self.interest {
+                        Interest::Readable => libc::POLLIN,
+                        Interest::Writable => libc::POLLOUT,
+                    };
+                    let token = reactor.register(self.fd.as_raw_fd(), events, cx.waker());
+                    self.token = Some(token);
+                    Poll::Pending
+                }
+                Some(token) => {
+                    if reactor.poll_ready(token, cx.waker()) {
+                        reactor.deregister(token);
+                        self.token = None;
+                        Poll::Ready(Ok(()))
+                    } else {
+                        Poll::Pending
+                    }
+                }
+            }
+        }
+    }
+
+    impl Drop for IoReady<'_> {
+        fn drop(&mut self) {
+            if let (Some(token), Ok(reactor)) = (self.token, Reactor::get()) {
+                reactor.deregister(token);
+            }
+        }
+    }
+
+    /// Thread polling the registered file descriptors.
+    struct Reactor {
+        state: Mutex<ReactorState>,
+        wake_sender: OwnedFd,
+    }
+
+    struct ReactorState {
+        next_token: u64,
+        registrations: BTreeMap<u64, Registration>,
+    }
+
+    struct Registration {
+        fd: RawFd,
+        events: libc::c_short,
+        ready: bool,
+        waker: Waker,
+    }
+
+    impl Reactor {
+        fn get() -> Result<&'static Reactor, Error> {
+            static REACTOR: OnceLock<Result<Reactor, libc::c_int>> = OnceLock::new();
+            REACTOR
+                .get_or_init(Reactor::spawn)
+                .as_ref()
+                .map_err(|&errnum| Error::from_errno(errnum))
+        }
+
+        fn spawn() -> Result<Reactor, libc::c_int> {
+            // The reactor is interrupted by writing to a pipe, e.g., when a file descriptor is
+            // registered.
+            let mut fds = [0; 2];
+            // Safety: FFI call is safe.
+            if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+                return Err(last_errno());
+            }
+            // Safety: The file descriptors are owned by us.
+            let (receiver, sender) =
+                unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+            for fd in &fds {
+                // Safety: FFI call is safe.
+                unsafe {
+                    libc::fcntl(*fd, libc::F_SETFL, libc::O_NONBLOCK);
+                    libc::fcntl(*fd, libc::F_SETFD, libc::FD_CLOEXEC);
+                }
+            }
+
+            std::thread::Builder::new()
+                .name("fimo_tasks I/O reactor".into())
+                .spawn(move || {
+                    let reactor = Reactor::get().expect("the reactor should be initialized");
+                    reactor.run(receiver);
+                })
+                .map_err(|e| e.raw_os_error().unwrap_or(libc::EAGAIN))?;
+
+            Ok(Reactor {
+                state: Mutex::new(ReactorState {
+                    next_token: 0,
+                    registrations: BTreeMap::new(),
+                }),
+                wake_sender: sender,
+            })
+        }
+
+        fn register(&self, fd: RawFd, events: libc::c_short, waker: &Waker) -> u64 {
+            let token = {
+                let mut state = self.state();
+                let token = state.next_token;
+                state.next_token += 1;
+                state.registrations.insert(
+                    token,
+                    Registration {
+                        fd,
+                        events,
+                        ready: false,
+                        waker: waker.clone(),
+                    },
+                );
+                token
+            };
+            self.interrupt();
+            token
+        }
+
+        fn poll_ready(&self, token: u64, waker: &Waker) -> bool {
+            let mut state = self.state();
+            let registration = state
+                .registrations
+                .get_mut(&token)
+                .expect("the registration should exist");
+            if !registration.ready && !registration.waker.will_wake(waker) {
+                registration.waker = waker.clone();
+            }
+            registration.ready
+        }
+
+        fn deregister(&self, token: u64) {
+            // The reactor skips the stale entries of its current poll.
+            self.state().registrations.remove(&token);
+        }
+
+        fn interrupt(&self) {
+            // The pipe is only full if the reactor has not yet consumed the previous interrupts,
+            // in which case it will rebuild its poll set anyway.
+            // Safety: FFI call is safe.
+            unsafe { libc::write(self.wake_sender.as_raw_fd(), [0u8].as_ptr().cast(), 1) };
+        }
+
+        fn run(&self, receiver: OwnedFd) -> ! {
+            let mut fds = Vec::new();
+            let mut tokens = Vec::new();
+            loop {
+                fds.clear();
+                tokens.clear();
+                fds.push(libc::pollfd {
+                    fd: receiver.as_raw_fd(),
+                    events: libc::POLLIN,
+                    revents: 0,
+                });
+                for (&token, registration) in &self.state().registrations {
+                    if !registration.ready {
+                        tokens.push(token);
+                        fds.push(libc::pollfd {
+                            fd: registration.fd,
+                            events: registration.events,
+                            revents: 0,
+                        });
+                    }
+                }
+
+                // Safety: FFI call is safe.
+                let count = unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, -1) };
+                if count < 0 {
+                    // Retry if interrupted by a signal, or if the resources are exhausted
+                    // temporarily.
+                    continue;
+                }
+
+                if fds[0].revents != 0 {
+                    let mut buffer = [0u8; 64];
+                    // Safety: FFI call is safe.
+                    while unsafe {
+                        libc::read(
+                            receiver.as_raw_fd(),
+                            buffer.as_mut_ptr().cast(),
+                            buffer.len(),
+                        )
+                    } > 0
+                    {}
+                }
+
+                let mut wakers = Vec::new();
+                {
+                    let mut state = self.state();
+                    for (token, fd) in tokens.iter().zip(&fds[1..]) {
+                        if fd.revents == 0 {
+                            continue;
+                        }
+                        if let Some(registration) = state.registrations.get_mut(token) {
+                            registration.ready = true;
+                            wakers.push(registration.waker.clone());
+                        }
+                    }
+                }
+                wakers.into_iter().for_each(Waker::wake);
+            }
+        }
+
+        fn state(&self) -> MutexGuard<'_, ReactorState> {
+            self.state.lock().expect("could not lock the reactor")
+        }
+    }
+
+    fn last_errno() -> libc::c_int {
+        std::io::Error::last_os_error()
+            .raw_os_error()
+            .unwrap_or(libc::EIO)
+    }
+}
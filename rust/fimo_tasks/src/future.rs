@@ -0,0 +1,313 @@
+use crate::{bindings, Context};
+use fimo_std::{
+    error::{to_result_indirect_in_place, Error},
+    ffi::FFITransferable,
+};
+use std::{
+    cell::RefCell,
+    ffi::c_void,
+    future::Future,
+    pin::{pin, Pin},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Poll, Wake, Waker},
+    time::{Duration, Instant},
+};
+
+std::thread_local! {
+    static CURRENT_EXECUTOR: RefCell<Option<Arc<ExecutorState>>> = const { RefCell::new(None) };
+}
+
+impl Context {
+    /// Drives a [`Future`] to completion on the current task.
+    ///
+    /// While the future is pending, the task is parked, freeing its worker for the other tasks of
+    /// the [`WorkerGroup`](crate::WorkerGroup), and resumes polling the future once it is woken.
+    /// The waker may be invoked from any thread. Futures returned by [`sleep`] and [`sleep_until`]
+    /// suspend the task until their deadline has been reached.
+    ///
+    /// Returns [`Error::ECANCELED`] if the cancellation of the current task is requested while the
+    /// future is pending.
+    ///
+    /// Can only be called successfully from a task.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fimo_tasks::__private_with_context(|_module, context| {
+    /// use fimo_tasks::{CommandBuffer, TaskStatus, WorkerGroupBuilder};
+    /// use std::{num::NonZeroUsize, time};
+    ///
+    /// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+    ///     .with_worker_count(NonZeroUsize::new(1))
+    ///     .build(&context)
+    ///     .expect("could not create worker group");
+    ///
+    /// let mut buffer = CommandBuffer::new();
+    /// let task = buffer.spawn_task(|context| {
+    ///     context.block_on(async { 5 }).unwrap()
+    /// });
+    ///
+    /// buffer
+    ///     .block_on(&group)
+    ///     .expect("could not enqueue command buffer");
+    /// assert_eq!(task.completion_status(), Some(TaskStatus::Completed));
+    /// assert_eq!(task.unwrap().unwrap(), 5);
+    /// # });
+    /// ```
+    ///
+    /// The parked task is resumed by wake-ups from other threads, and by cancellation requests:
+    ///
+    /// ```
+    /// # fimo_tasks::__private_with_context(|_module, context| {
+    /// use fimo_std::error::Error;
+    /// use fimo_tasks::{oneshot_channel, CancellationToken, CommandBuffer, WorkerGroupBuilder};
+    /// use std::{num::NonZeroUsize, time::Duration};
+    ///
+    /// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+    ///     .with_worker_count(NonZeroUsize::new(1))
+    ///     .build(&context)
+    ///     .expect("could not create worker group");
+    ///
+    /// let (sender, receiver) = oneshot_channel();
+    /// let (started_sender, started_receiver) = std::sync::mpsc::channel();
+    /// let token = CancellationToken::new();
+    /// let mut buffer = CommandBuffer::new();
+    /// buffer.set_cancellation_token(Some(token.clone()));
+    /// let received = buffer.spawn_task(move |context| context.block_on(receiver).unwrap());
+    /// let pending = buffer.spawn_task(move |context| {
+    ///     started_sender.send(()).unwrap();
+    ///     context.block_on(std::future::pending::<()>())
+    /// });
+    ///
+    /// let thread = std::thread::spawn(move || {
+    ///     std::thread::sleep(Duration::from_millis(10));
+    ///     sender.send(5).unwrap();
+    ///     started_receiver.recv().unwrap();
+    ///     token.cancel();
+    /// });
+    /// buffer
+    ///     .block_on(&group)
+    ///     .expect("could not enqueue command buffer");
+    /// thread.join().unwrap();
+    ///
+    /// assert_eq!(received.unwrap().unwrap(), Ok(5));
+    /// assert_eq!(pending.unwrap().unwrap(), Err(Error::ECANCELED));
+    /// # });
+    /// ```
+    pub fn block_on<F: Future>(&self, future: F) -> Result<F::Output, Error> {
+        let state = Arc::new(ExecutorState {
+            ctx: Context(self.0),
+            notified: AtomicBool::new(false),
+            deadline: Mutex::new(None),
+        });
+        let waker = Waker::from(state.clone());
+        let mut cx = std::task::Context::from_waker(&waker);
+        let mut future = pin!(future);
+
+        // Unpark the task if its cancellation is requested while the future is pending.
+        let token = self.cancellation_token().ok();
+        let _registration = token.as_ref().map(|token| token.register_waker(&waker));
+
+        loop {
+            state.clear_deadline();
+            let previous = CURRENT_EXECUTOR.replace(Some(state.clone()));
+            let result = future.as_mut().poll(&mut cx);
+            CURRENT_EXECUTOR.set(previous);
+
+            match result {
+                Poll::Ready(value) => return Ok(value),
+                Poll::Pending => state.wait()?,
+            }
+        }
+    }
+
+    /// Parks the current task in the queue associated with `key`, if `validate` returns `true`.
+    ///
+    /// `validate` is called while the queue is locked.
+    fn park_conditionally<V: FnOnce() -> bool>(
+        &self,
+        key: *const c_void,
+        validate: V,
+        timeout: Option<Duration>,
+    ) -> Result<(), Error> {
+        unsafe extern "C" fn validate_ffi<V: FnOnce() -> bool>(data: *mut c_void) -> bool {
+            // Safety: The data is the `Option<V>` owned by `park_conditionally`.
+            let validate = unsafe { &mut *data.cast::<Option<V>>() };
+            validate.take().is_some_and(|f| f())
+        }
+
+        let mut validate = Some(validate);
+        let timeout = timeout.map(|timeout| {
+            fimo_std::time::Duration::new(timeout.as_secs(), timeout.subsec_nanos()).into_ffi()
+        });
+        let timeout = timeout
+            .as_ref()
+            .map_or(std::ptr::null(), std::ptr::from_ref);
+
+        crate::suspension_point("park", || {
+            // Safety: FFI call is safe
+            unsafe {
+                to_result_indirect_in_place(|err, result| {
+                    *err = (self.vtable().v0.park_conditionally.unwrap_unchecked())(
+                        self.data(),
+                        key,
+                        Some(validate_ffi::<V>),
+                        (&raw mut validate).cast(),
+                        None,
+                        std::ptr::null_mut(),
+                        None,
+                        std::ptr::null_mut(),
+                        std::ptr::null(),
+                        timeout,
+                        result.as_mut_ptr(),
+                    );
+                })
+                .map(|_: bindings::FiTasksParkResult| ())
+            }
+        })
+    }
+
+    /// Unparks one task from the queue associated with `key`.
+    fn unpark_one(&self, key: *const c_void) -> Result<(), Error> {
+        // Safety: FFI call is safe
+        unsafe {
+            to_result_indirect_in_place(|err, result| {
+                *err = (self.vtable().v0.unpark_one.unwrap_unchecked())(
+                    self.data(),
+                    key,
+                    None,
+                    std::ptr::null_mut(),
+                    result.as_mut_ptr(),
+                );
+            })
+            .map(|_: bindings::FiTasksUnparkResult| ())
+        }
+    }
+}
+
+/// Creates a future that completes after the specified duration.
+///
+/// When driven by [`Context::block_on`], the task is suspended until the duration has elapsed.
+/// Other executors will poll the future repeatedly until its completion.
+pub fn sleep(duration: Duration) -> Sleep {
+    sleep_until(Instant::now() + duration)
+}
+
+/// Creates a future that completes once the deadline has been reached.
+///
+/// See [`sleep`] for more details.
+pub fn sleep_until(deadline: Instant) -> Sleep {
+    Sleep { deadline }
+}
+
+/// Future returned by [`sleep`] and [`sleep_until`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Sleep {
+    deadline: Instant,
+}
+
+impl Sleep {
+    /// Returns the instant at which the future will complete.
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        if Instant::now() >= self.deadline {
+            return Poll::Ready(());
+        }
+
+        // Register the deadline if we are driven by our executor, otherwise we must be polled
+        // again.
+        let registered = CURRENT_EXECUTOR.with_borrow(|executor| match executor {
+            Some(state) if cx.waker().will_wake(&Waker::from(state.clone())) => {
+                state.register_deadline(self.deadline);
+                true
+            }
+            _ => false,
+        });
+        if !registered {
+            cx.waker().wake_by_ref();
+        }
+
+        Poll::Pending
+    }
+}
+
+#[derive(Debug)]
+struct ExecutorState {
+    ctx: Context,
+    notified: AtomicBool,
+    deadline: Mutex<Option<Instant>>,
+}
+
+impl ExecutorState {
+    fn clear_deadline(&self) {
+        let mut guard = self.deadline.lock().expect("could not lock deadline");
+        *guard = None;
+    }
+
+    fn register_deadline(&self, deadline: Instant) {
+        let mut guard = self.deadline.lock().expect("could not lock deadline");
+        *guard = Some(guard.map_or(deadline, |x| x.min(deadline)));
+    }
+
+    /// Key of the parking lot queue of the task driving the future.
+    fn key(&self) -> *const c_void {
+        std::ptr::from_ref(self).cast()
+    }
+
+    fn wait(&self) -> Result<(), Error> {
+        loop {
+            if self.notified.swap(false, Ordering::Acquire) {
+                return Ok(());
+            }
+            if self.ctx.is_cancelled() {
+                return Err(Error::ECANCELED);
+            }
+
+            let deadline = *self.deadline.lock().expect("could not lock deadline");
+            let timeout = match deadline {
+                None => None,
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return Ok(());
+                    }
+                    Some(deadline - now)
+                }
+            };
+
+            // The flag is checked again while the queue is locked, so that a wake-up occurring
+            // before the task is enqueued is not lost.
+            self.ctx.park_conditionally(
+                self.key(),
+                || !self.notified.load(Ordering::Acquire),
+                timeout,
+            )?;
+        }
+    }
+}
+
+impl Wake for ExecutorState {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        // Only the first wake-up needs to unpark the task, as it consumes the notification
+        // before parking again.
+        if !self.notified.swap(true, Ordering::AcqRel) {
+            // The task may not be parked yet, in which case it observes the notification.
+            let _ = self.ctx.unpark_one(self.key());
+        }
+    }
+}
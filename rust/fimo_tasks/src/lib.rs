@@ -12,18 +12,47 @@ use fimo_std::{
 pub mod bindings;
 pub mod symbols;
 
+mod blocking;
+mod cancellation;
+mod channel;
 mod command_buffer;
+mod event;
+mod future;
 mod local;
+mod parallel;
+mod semaphore;
+mod service;
+mod shutdown;
 mod task;
+mod task_hook;
+mod task_processor;
+mod timer;
+#[cfg(feature = "tokio")]
+mod tokio_bridge;
 mod worker_group;
 
+pub use blocking::*;
+pub use cancellation::*;
+pub use channel::*;
 pub use command_buffer::*;
+pub use event::*;
 use fimo_std::{
     ffi::FFISharable,
+    module::SuspendedSymbols,
     tracing::{Config, Level, ThreadAccess},
 };
+pub use future::*;
 pub use local::*;
+pub use parallel::*;
+pub use semaphore::*;
+pub use service::*;
+pub use shutdown::*;
 pub use task::*;
+pub use task_hook::*;
+pub use task_processor::*;
+pub use timer::*;
+#[cfg(feature = "tokio")]
+pub use tokio_bridge::*;
 pub use worker_group::*;
 
 /// Context of runtime.
@@ -114,6 +143,48 @@ impl Context {
         Ok(TaskId(id))
     }
 
+    /// Returns the scheduling priority of the current task.
+    ///
+    /// Can only be called successfully from a task.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fimo_tasks::__private_with_context(|_module, context| {
+    /// use fimo_tasks::{CommandBuffer, TaskPriority, TaskStatus, WorkerGroupBuilder};
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+    ///     .with_worker_count(NonZeroUsize::new(1))
+    ///     .build(&context)
+    ///     .expect("could not create worker group");
+    ///
+    /// let mut buffer = CommandBuffer::new();
+    /// buffer.set_priority(TaskPriority::High);
+    /// let task = buffer.spawn_task(|context| {
+    ///     assert_eq!(context.task_priority().unwrap(), TaskPriority::High);
+    /// });
+    ///
+    /// buffer
+    ///     .block_on(&group)
+    ///     .expect("could not enqueue command buffer");
+    /// assert_eq!(task.completion_status(), Some(TaskStatus::Completed));
+    /// # });
+    /// ```
+    pub fn task_priority(&self) -> Result<TaskPriority, Error> {
+        // Safety: FFI call is safe
+        let priority = unsafe {
+            to_result_indirect_in_place(|err, priority| {
+                *err = (self.vtable().v0.task_priority.unwrap_unchecked())(
+                    self.data(),
+                    priority.as_mut_ptr(),
+                );
+            })?
+        };
+
+        priority.try_into()
+    }
+
     /// Returns the unique id of the current worker.
     ///
     /// Can only be called successfully from a task.
@@ -308,8 +379,75 @@ impl Context {
     /// # });
     /// ```
     pub fn yield_now(&self) -> Result<(), Error> {
+        suspension_point("yield", || {
+            // Safety: FFI call is safe
+            unsafe { to_result((self.vtable().v0.yield_.unwrap_unchecked())(self.data())) }
+        })
+    }
+
+    /// Yields the execution of the current task, if it has exhausted its time slice.
+    ///
+    /// Each time a task is resumed by a worker, it is assigned a time slice, whose length is
+    /// configured with [`WorkerGroupBuilder::with_time_slice`]. Long-running tasks should call
+    /// this function periodically, to allow other tasks to be scheduled. Is cheaper than
+    /// [`Context::yield_now`], as the task is only suspended once the budget is exhausted, and
+    /// the budget is checked without synchronizing with the worker. Debug builds of the runtime
+    /// report the tasks that run longer than their time slice between two checkpoints.
+    ///
+    /// Can only be called successfully from a task.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fimo_tasks::__private_with_context(|_module, context| {
+    /// use fimo_tasks::{CommandBuffer, TaskStatus, WorkerGroupBuilder};
+    /// use std::{num::NonZeroUsize, time::Duration};
+    ///
+    /// // Outside a worker group.
+    /// assert!(context.checkpoint().is_err());
+    ///
+    /// // Inside a worker group.
+    /// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+    ///     .with_worker_count(NonZeroUsize::new(1))
+    ///     .with_time_slice(Some(Duration::from_millis(1)))
+    ///     .build(&context)
+    ///     .expect("could not create worker group");
+    ///
+    /// let mut buffer = CommandBuffer::new();
+    /// let task = buffer.spawn_task(move |context| {
+    ///     for _ in 0..1000 {
+    ///         assert!(context.checkpoint().is_ok());
+    ///     }
+    /// });
+    ///
+    /// buffer
+    ///     .block_on(&group)
+    ///     .expect("could not enqueue command buffer");
+    /// assert_eq!(task.completion_status(), Some(TaskStatus::Completed));
+    /// # });
+    /// ```
+    pub fn checkpoint(&self) -> Result<(), Error> {
+        // Safety: FFI call is safe
+        let exhausted = unsafe {
+            to_result_indirect_in_place(|err, exhausted| {
+                *err = (self.vtable().v0.time_slice_exhausted.unwrap_unchecked())(
+                    self.data(),
+                    exhausted.as_mut_ptr(),
+                );
+            })?
+        };
+        if !exhausted {
+            return Ok(());
+        }
+
+        // The task is only suspended once its time slice is exhausted, so we don't report the
+        // symbols held by it, but must still move them to the worker resuming the task.
+        let symbols = SuspendedSymbols::suspend();
         // Safety: FFI call is safe
-        unsafe { to_result((self.vtable().v0.yield_.unwrap_unchecked())(self.data())) }
+        let result =
+            unsafe { to_result((self.vtable().v0.yield_.unwrap_unchecked())(self.data())) };
+        symbols.resume();
+        result
     }
 
     /// Pauses the execution of the current task for the specified duration.
@@ -354,13 +492,58 @@ impl Context {
         let nanos = duration.subsec_nanos();
         let duration = fimo_std::time::Duration::new(secs, nanos);
 
-        // Safety: FFI call is safe
-        unsafe {
-            to_result((self.vtable().v0.sleep.unwrap_unchecked())(
-                self.data(),
-                duration.into_ffi(),
-            ))
-        }
+        suspension_point("sleep", || {
+            // Safety: FFI call is safe
+            unsafe {
+                to_result((self.vtable().v0.sleep.unwrap_unchecked())(
+                    self.data(),
+                    duration.into_ffi(),
+                ))
+            }
+        })
+    }
+
+    /// Puts the current task to sleep until the specified time has been reached.
+    ///
+    /// Returns immediately if the time lies in the past. Can only be called successfully from a
+    /// task.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fimo_tasks::__private_with_context(|_module, context| {
+    /// use fimo_std::time::{Duration, Time};
+    /// use fimo_tasks::{CommandBuffer, TaskStatus, WorkerGroupBuilder};
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+    ///     .with_worker_count(NonZeroUsize::new(1))
+    ///     .build(&context)
+    ///     .expect("could not create worker group");
+    ///
+    /// let mut buffer = CommandBuffer::new();
+    /// let task = buffer.spawn_task(move |context| {
+    ///     let deadline = Time::now() + Duration::from_millis(10);
+    ///     context.sleep_until(deadline).unwrap();
+    ///     assert!(Time::now() >= deadline);
+    /// });
+    ///
+    /// buffer
+    ///     .block_on(&group)
+    ///     .expect("could not enqueue command buffer");
+    /// assert_eq!(task.completion_status(), Some(TaskStatus::Completed));
+    /// # });
+    /// ```
+    pub fn sleep_until(&self, time: fimo_std::time::Time) -> Result<(), Error> {
+        suspension_point("sleep", || {
+            // Safety: FFI call is safe
+            unsafe {
+                to_result((self.vtable().v0.sleep_until.unwrap_unchecked())(
+                    self.data(),
+                    time.into_ffi(),
+                ))
+            }
+        })
     }
 
     #[inline(always)]
@@ -375,6 +558,21 @@ impl Context {
     }
 }
 
+/// Invokes an operation that may suspend the current task.
+///
+/// A task may be resumed by a different worker, so the symbols locked by the task are moved to
+/// the resuming thread. As the symbols can not be unloaded while they are locked, the locks held
+/// across a successful operation are reported.
+fn suspension_point<T>(operation: &str, f: impl FnOnce() -> Result<T, Error>) -> Result<T, Error> {
+    let symbols = SuspendedSymbols::suspend();
+    let result = f();
+    if result.is_ok() {
+        symbols.report(operation);
+    }
+    symbols.resume();
+    result
+}
+
 // Safety: Sound by invariant
 unsafe impl Send for Context {}
 
@@ -0,0 +1,170 @@
+use crate::{CommandBuffer, CommandBufferStatus, Context, ScopedCommandBuffer, WorkerGroup};
+use fimo_std::error::Error;
+use std::{num::NonZeroUsize, ops::Range};
+
+/// Number of chunks assigned to each worker by the default chunking heuristic.
+///
+/// Using more chunks than workers allows the work stealing to balance uneven workloads.
+const CHUNKS_PER_WORKER: usize = 4;
+
+/// Executes `body` for each index in `range`, splitting the work across the [`WorkerGroup`].
+///
+/// The range is split into chunks of `chunk_size` indices, each processed sequentially by one
+/// task. If `chunk_size` is `None`, the chunk size is derived from the number of workers of the
+/// group. Blocks until all indices have been processed.
+///
+/// # Panics
+///
+/// If the `body` panics, the panic is propagated to the caller, after all tasks have finished.
+///
+/// # Examples
+///
+/// ```
+/// # fimo_tasks::__private_with_context(|_module, context| {
+/// use fimo_tasks::{parallel_for, WorkerGroupBuilder};
+/// use std::{num::NonZeroUsize, sync::atomic::{AtomicUsize, Ordering}};
+///
+/// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+///     .with_worker_count(NonZeroUsize::new(4))
+///     .build(&context)
+///     .expect("could not create worker group");
+///
+/// let sum = AtomicUsize::new(0);
+/// parallel_for(&group, 0..100, None, |_, i| {
+///     sum.fetch_add(i, Ordering::Relaxed);
+/// })
+/// .expect("could not run the tasks");
+/// assert_eq!(sum.into_inner(), 4950);
+/// # });
+/// ```
+pub fn parallel_for<F>(
+    group: &WorkerGroup<'_>,
+    range: Range<usize>,
+    chunk_size: Option<NonZeroUsize>,
+    body: F,
+) -> Result<(), Error>
+where
+    F: Fn(&Context, usize) + Sync,
+{
+    let start = range.start;
+    run_chunks(group, range.len(), chunk_size, |context, chunk| {
+        for i in chunk {
+            body(context, start + i);
+        }
+    })?;
+    Ok(())
+}
+
+/// Maps each element of `items` with `f`, splitting the work across the [`WorkerGroup`].
+///
+/// Returns the mapped elements in the order of `items`. See [`parallel_for`] for a description of
+/// the chunking.
+///
+/// # Panics
+///
+/// If `f` panics, the panic is propagated to the caller, after all tasks have finished.
+pub fn parallel_map<T, U, F>(
+    group: &WorkerGroup<'_>,
+    items: &[T],
+    chunk_size: Option<NonZeroUsize>,
+    f: F,
+) -> Result<Vec<U>, Error>
+where
+    T: Sync,
+    U: Send,
+    F: Fn(&Context, &T) -> U + Sync,
+{
+    let chunks = run_chunks(group, items.len(), chunk_size, |context, chunk| {
+        items[chunk]
+            .iter()
+            .map(|x| f(context, x))
+            .collect::<Vec<_>>()
+    })?;
+    Ok(chunks.into_iter().flatten().collect())
+}
+
+/// Maps each element of `items` with `map` and combines the results with `reduce`, splitting the
+/// work across the [`WorkerGroup`].
+///
+/// Each chunk is reduced by its own task, after which the results of the chunks are reduced in
+/// order on the calling thread. Therefore, `reduce` must be associative, but is not required to be
+/// commutative. Returns `None` if `items` is empty. See [`parallel_for`] for a description of the
+/// chunking.
+///
+/// # Panics
+///
+/// If `map` or `reduce` panics, the panic is propagated to the caller, after all tasks have
+/// finished.
+pub fn parallel_reduce<T, U, M, R>(
+    group: &WorkerGroup<'_>,
+    items: &[T],
+    chunk_size: Option<NonZeroUsize>,
+    map: M,
+    reduce: R,
+) -> Result<Option<U>, Error>
+where
+    T: Sync,
+    U: Send,
+    M: Fn(&Context, &T) -> U + Sync,
+    R: Fn(U, U) -> U + Sync,
+{
+    let chunks = run_chunks(group, items.len(), chunk_size, |context, chunk| {
+        items[chunk]
+            .iter()
+            .map(|x| map(context, x))
+            .reduce(&reduce)
+            .expect("chunks should not be empty")
+    })?;
+    Ok(chunks.into_iter().reduce(&reduce))
+}
+
+/// Splits `0..len` into chunks and runs `f` for each chunk in a separate task.
+fn run_chunks<T, F>(
+    group: &WorkerGroup<'_>,
+    len: usize,
+    chunk_size: Option<NonZeroUsize>,
+    f: F,
+) -> Result<Vec<T>, Error>
+where
+    T: Send,
+    F: Fn(&Context, Range<usize>) -> T + Sync,
+{
+    if len == 0 {
+        return Ok(Vec::new());
+    }
+
+    let chunk_size = match chunk_size {
+        Some(x) => x.get(),
+        None => {
+            let num_workers = group.workers()?.len().max(1);
+            len.div_ceil(num_workers * CHUNKS_PER_WORKER)
+        }
+    };
+
+    let f = &f;
+    CommandBuffer::scope(group, |scope| {
+        let mut buffer = ScopedCommandBuffer::new(scope);
+        let handles = (0..len)
+            .step_by(chunk_size)
+            .map(|start| {
+                let chunk = start..start.saturating_add(chunk_size).min(len);
+                buffer.spawn_task(move |context| f(context, chunk))
+            })
+            .collect::<Vec<_>>();
+
+        if let CommandBufferStatus::Aborted(_) = buffer.block_on()? {
+            // Propagate the panic of the first failed task.
+            for handle in handles.into_iter().filter(|x| x.is_completed()) {
+                if let Err(e) = handle.unwrap() {
+                    std::panic::resume_unwind(e);
+                }
+            }
+            panic!("a parallel task was aborted");
+        }
+
+        Ok(handles
+            .into_iter()
+            .map(|x| x.unwrap().unwrap_or_else(std::panic::resume_unwind))
+            .collect())
+    })
+}
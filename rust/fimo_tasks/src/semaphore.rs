@@ -0,0 +1,138 @@
+use crate::{bindings, Context};
+use fimo_std::error::{to_result, to_result_indirect_in_place, Error};
+use std::marker::PhantomData;
+
+/// A timeline semaphore.
+///
+/// A semaphore contains a monotonically increasing counter, which can be signaled either by a
+/// [`CommandBuffer`](crate::CommandBuffer), or directly with [`Semaphore::signal`]. Command buffers
+/// can wait until the counter reaches some value, allowing the expression of dependencies between
+/// command buffers enqueued on different [`WorkerGroup`](crate::WorkerGroup)s.
+///
+/// # Examples
+///
+/// ```
+/// # fimo_tasks::__private_with_context(|_module, context| {
+/// use fimo_tasks::{CommandBuffer, Semaphore, WorkerGroupBuilder};
+/// use std::num::NonZeroUsize;
+///
+/// let producer = WorkerGroupBuilder::new(c"producer", &[Default::default()], None)
+///     .with_worker_count(NonZeroUsize::new(1))
+///     .build(&context)
+///     .expect("could not create worker group");
+/// let consumer = WorkerGroupBuilder::new(c"consumer", &[Default::default()], None)
+///     .with_worker_count(NonZeroUsize::new(1))
+///     .build(&context)
+///     .expect("could not create worker group");
+///
+/// let semaphore = Semaphore::new(&context, 0).expect("could not create semaphore");
+///
+/// let mut second = CommandBuffer::new();
+/// second.wait_semaphore(&semaphore, 1);
+/// let value = second.spawn_task(|_| 5);
+/// let second = second
+///     .enqueue(&consumer, |_| {})
+///     .expect("could not enqueue command buffer");
+/// assert!(!second.is_completed());
+///
+/// let mut first = CommandBuffer::new();
+/// first.spawn_task(|_| {});
+/// first.signal_semaphore(&semaphore, 1);
+/// first
+///     .block_on(&producer)
+///     .expect("could not enqueue command buffer");
+/// assert_eq!(semaphore.value(), 1);
+///
+/// while !second.is_completed() {
+///     std::thread::yield_now();
+/// }
+/// assert_eq!(value.unwrap().unwrap(), 5);
+/// # });
+/// ```
+pub struct Semaphore<'ctx>(
+    pub(super) bindings::FiTasksSemaphore,
+    pub(super) PhantomData<fn() -> &'ctx ()>,
+);
+
+impl<'ctx> Semaphore<'ctx> {
+    /// Creates a new semaphore with the initial counter value.
+    pub fn new(ctx: &'ctx Context, initial_value: u64) -> Result<Self, Error> {
+        // Safety: FFI call is safe
+        let semaphore = unsafe {
+            to_result_indirect_in_place(|err, semaphore| {
+                *err = (ctx.vtable().v0.create_semaphore.unwrap_unchecked())(
+                    ctx.data(),
+                    initial_value,
+                    semaphore.as_mut_ptr(),
+                );
+            })?
+        };
+
+        Ok(Self(semaphore, PhantomData))
+    }
+
+    /// Returns the current counter value.
+    pub fn value(&self) -> u64 {
+        // Safety: FFI call is safe
+        unsafe { self.vtable().v0.value.unwrap_unchecked()(self.data()) }
+    }
+
+    /// Sets the counter to `value`, resuming all command buffers waiting on a smaller or equal
+    /// value.
+    ///
+    /// The value must be greater than the current counter value.
+    pub fn signal(&self, value: u64) -> Result<(), Error> {
+        // Safety: FFI call is safe
+        unsafe {
+            to_result(self.vtable().v0.signal.unwrap_unchecked()(
+                self.data(),
+                value,
+            ))
+        }
+    }
+
+    pub(super) fn into_raw(self) -> bindings::FiTasksSemaphore {
+        let this = std::mem::ManuallyDrop::new(self);
+        this.0
+    }
+
+    #[inline(always)]
+    fn data(&self) -> *mut std::ffi::c_void {
+        self.0.data
+    }
+
+    #[inline(always)]
+    fn vtable(&self) -> &bindings::FiTasksSemaphoreVTable {
+        // Safety: The VTable is always initialized
+        unsafe { &*self.0.vtable }
+    }
+}
+
+// Safety: Sound by invariant
+unsafe impl Send for Semaphore<'_> {}
+
+// Safety: Sound by invariant
+unsafe impl Sync for Semaphore<'_> {}
+
+impl std::fmt::Debug for Semaphore<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Semaphore")
+            .field("value", &self.value())
+            .finish()
+    }
+}
+
+impl Clone for Semaphore<'_> {
+    fn clone(&self) -> Self {
+        // Safety: We own the reference therefore we can acquire another one.
+        unsafe { self.vtable().v0.acquire.unwrap_unchecked()(self.data()) }
+        Self(self.0, PhantomData)
+    }
+}
+
+impl Drop for Semaphore<'_> {
+    fn drop(&mut self) {
+        // Safety: We own the reference therefore we can release it.
+        unsafe { self.vtable().v0.release.unwrap_unchecked()(self.data()) }
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{Context, WorkerGroup};
+use fimo_std::error::{to_result_indirect, to_result_indirect_in_place, Error};
+use std::ffi::{c_void, CStr};
+
+/// A service shared by all tasks of a [`WorkerGroup`].
+///
+/// Services are objects, like caches or pools of resources, that are constructed once per worker
+/// group and registered with [`WorkerGroup::register_service`]. They can then be acquired by any
+/// task of the group with [`Context::group_service`], and are destroyed once the group has shut
+/// down, in the reverse order of their registration.
+///
+/// The services are stored behind an FFI-safe interface, and are identified by their
+/// [`NAME`](GroupService::NAME). This allows the service to be registered by a different module
+/// than the ones using it.
+///
+/// # Safety
+///
+/// The name must uniquely identify the type. If the service is shared with other modules, all
+/// modules must agree on the layout of the type, e.g., by making it `#[repr(C)]`.
+pub unsafe trait GroupService: Send + Sync + 'static {
+    /// Unique name of the service.
+    const NAME: &'static CStr;
+}
+
+impl WorkerGroup<'_> {
+    /// Registers a service with the worker group.
+    ///
+    /// Once registered, the service is owned by the worker group, and can be acquired by its tasks
+    /// with [`Context::group_service`]. The service is dropped after the worker group has shut
+    /// down, i.e., after all of its tasks have completed. Fails if a service with the same name is
+    /// already registered, or if the worker group has already shut down.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fimo_tasks::__private_with_context(|_module, context| {
+    /// use fimo_tasks::{CommandBuffer, GroupService, WorkerGroupBuilder};
+    /// use std::{ffi::CStr, num::NonZeroUsize, sync::atomic::{AtomicUsize, Ordering}};
+    ///
+    /// #[derive(Default)]
+    /// struct Counter(AtomicUsize);
+    ///
+    /// // Safety: The name is unique.
+    /// unsafe impl GroupService for Counter {
+    ///     const NAME: &'static CStr = c"doctest::Counter";
+    /// }
+    ///
+    /// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+    ///     .with_worker_count(NonZeroUsize::new(2))
+    ///     .build(&context)
+    ///     .expect("could not create worker group");
+    /// group
+    ///     .register_service(Counter::default())
+    ///     .expect("could not register the service");
+    /// assert!(group.register_service(Counter::default()).is_err());
+    ///
+    /// let mut buffer = CommandBuffer::new();
+    /// let tasks = (0..4)
+    ///     .map(|_| {
+    ///         buffer.spawn_task(|context| {
+    ///             let counter = context.group_service::<Counter>().unwrap();
+    ///             counter.0.fetch_add(1, Ordering::Relaxed) + 1
+    ///         })
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// buffer
+    ///     .block_on(&group)
+    ///     .expect("could not enqueue command buffer");
+    ///
+    /// let mut counts = tasks
+    ///     .into_iter()
+    ///     .map(|x| x.unwrap().unwrap())
+    ///     .collect::<Vec<_>>();
+    /// counts.sort();
+    /// assert_eq!(counts, [1, 2, 3, 4]);
+    /// # });
+    /// ```
+    pub fn register_service<T: GroupService>(&self, service: T) -> Result<(), Error> {
+        extern "C" fn drop_service<T>(ptr: *mut c_void) {
+            fimo_std::panic::abort_on_panic(|| {
+                let ptr = ptr.cast::<T>();
+                assert!(!ptr.is_null());
+
+                // Safety: The value was allocated through a box.
+                unsafe {
+                    drop(Box::from_raw(ptr));
+                }
+            });
+        }
+
+        let service = Box::into_raw(Box::new(service));
+
+        // Safety: FFI call is safe
+        let result = unsafe {
+            to_result_indirect(|err| {
+                *err = self.vtable().v0.service_register.unwrap_unchecked()(
+                    self.data(),
+                    T::NAME.as_ptr(),
+                    service.cast(),
+                    Some(drop_service::<T>),
+                );
+            })
+        };
+        if result.is_err() {
+            // Safety: On failure, the ownership of the service remains with us.
+            unsafe { drop(Box::from_raw(service)) };
+        }
+        result
+    }
+}
+
+impl Context {
+    /// Acquires a service registered with the worker group of the current task.
+    ///
+    /// See [`WorkerGroup::register_service`] for more details.
+    ///
+    /// Can only be called successfully from a task. Fails if the service is not registered with
+    /// the worker group of the task.
+    pub fn group_service<T: GroupService>(&self) -> Result<&T, Error> {
+        let group = self.worker_group()?;
+
+        // Safety: FFI call is safe
+        let service = unsafe {
+            to_result_indirect_in_place(|err, service| {
+                *err = group.vtable().v0.service_get.unwrap_unchecked()(
+                    group.data(),
+                    T::NAME.as_ptr(),
+                    service.as_mut_ptr(),
+                );
+            })?
+        };
+
+        // Safety: The service was registered with the name of `T`, and is only destroyed after
+        // the worker group has shut down, which requires the current task to complete.
+        unsafe { Ok(&*service.cast::<T>().cast_const()) }
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{
+    bindings, oneshot_channel, Context, OneshotReceiver, OneshotSender, TryRecvError, WorkerGroup,
+};
+use fimo_std::error::{to_result_indirect, Error};
+use std::{ffi::c_void, future::Future, pin::Pin, task::Poll};
+
+/// Shutdown behavior of a [`WorkerGroup`].
+///
+/// In every mode, the worker group stops accepting new commands, and the tasks that have already
+/// been started are run to completion.
+#[derive(Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum ShutdownMode {
+    /// Runs all enqueued command buffers to completion.
+    #[default]
+    Graceful,
+    /// Aborts the enqueued command buffers at their first command that has not been processed
+    /// yet. Tasks that have already been spawned are run to completion.
+    Drain,
+    /// Like [`ShutdownMode::Drain`], but additionally aborts the spawned tasks that have not been
+    /// started by a worker yet, and cancels the pending timers.
+    Immediate,
+}
+
+impl From<ShutdownMode> for bindings::FiTasksShutdownMode {
+    fn from(value: ShutdownMode) -> Self {
+        match value {
+            ShutdownMode::Graceful => Self::FI_TASKS_SHUTDOWN_MODE_GRACEFUL,
+            ShutdownMode::Drain => Self::FI_TASKS_SHUTDOWN_MODE_DRAIN,
+            ShutdownMode::Immediate => Self::FI_TASKS_SHUTDOWN_MODE_IMMEDIATE,
+        }
+    }
+}
+
+/// Handle to the shutdown of a [`WorkerGroup`].
+///
+/// The handle completes once all tasks of the worker group have finished, and its workers have
+/// exited. It can be awaited as a [`Future`], or waited on from a task with
+/// [`Shutdown::wait`].
+///
+/// # Examples
+///
+/// ```
+/// # fimo_tasks::__private_with_context(|_module, context| {
+/// use fimo_tasks::{CommandBuffer, ShutdownMode, WorkerGroupBuilder};
+/// use std::num::NonZeroUsize;
+///
+/// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+///     .with_worker_count(NonZeroUsize::new(1))
+///     .build(&context)
+///     .expect("could not create worker group");
+/// let other = WorkerGroupBuilder::new(c"doctest other", &[Default::default()], None)
+///     .with_worker_count(NonZeroUsize::new(1))
+///     .build(&context)
+///     .expect("could not create worker group");
+///
+/// let shutdown = other
+///     .shutdown(ShutdownMode::Drain)
+///     .expect("could not shut down worker group");
+/// assert!(!other.is_open());
+///
+/// let mut buffer = CommandBuffer::new();
+/// buffer.spawn_task(move |context| shutdown.wait(context).unwrap());
+/// buffer
+///     .block_on(&group)
+///     .expect("could not enqueue command buffer");
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct Shutdown {
+    receiver: OneshotReceiver<()>,
+    completed: bool,
+}
+
+impl Shutdown {
+    /// Returns whether the worker group has shut down.
+    pub fn is_completed(&mut self) -> bool {
+        if !self.completed {
+            self.completed = !matches!(self.receiver.try_recv(), Err(TryRecvError::Empty));
+        }
+        self.completed
+    }
+
+    /// Suspends the current task until the worker group has shut down.
+    ///
+    /// Can only suspend successfully from a task, which must not belong to the worker group that
+    /// is being shut down.
+    pub fn wait(mut self, ctx: &Context) -> Result<(), Error> {
+        ctx.block_on(&mut self)
+    }
+}
+
+impl Future for Shutdown {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        if self.completed {
+            return Poll::Ready(());
+        }
+
+        // The value is sent once the group has shut down. The sender is only dropped without
+        // sending it, if the module is unloaded beforehand.
+        match Pin::new(&mut self.receiver).poll(cx) {
+            Poll::Ready(_) => {
+                self.completed = true;
+                Poll::Ready(())
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl WorkerGroup<'_> {
+    /// Requests the shutdown of the worker group.
+    ///
+    /// Like with [`request_close`](WorkerGroup::request_close), the worker group stops accepting
+    /// new commands. The handling of the enqueued commands depends on the `mode`. A shutdown may
+    /// be requested multiple times, in which case the most aggressive mode is applied. The
+    /// returned [`Shutdown`] handle completes once the worker group has shut down.
+    pub fn shutdown(&self, mode: ShutdownMode) -> Result<Shutdown, Error> {
+        unsafe extern "C" fn on_complete(data: *mut c_void) {
+            fimo_std::panic::abort_on_panic(|| {
+                // Safety: The function is invoked once, with the data passed to the request.
+                let sender = unsafe { Box::from_raw(data.cast::<OneshotSender<()>>()) };
+                let _ = sender.send(());
+            });
+        }
+
+        let (sender, receiver) = oneshot_channel();
+        let data = Box::into_raw(Box::new(sender));
+        let config = bindings::FiTasksShutdownConfig {
+            next: std::ptr::null_mut(),
+            mode: mode.into(),
+            on_complete: Some(on_complete),
+            data: data.cast(),
+        };
+
+        // Safety: FFI call is safe
+        let result = unsafe {
+            to_result_indirect(|err| {
+                *err = self.vtable().v0.request_shutdown.unwrap_unchecked()(self.data(), &config);
+            })
+        };
+        match result {
+            Ok(_) => Ok(Shutdown {
+                receiver,
+                completed: false,
+            }),
+            Err(e) => {
+                // Safety: The function is not invoked on failure, so we still own the data.
+                drop(unsafe { Box::from_raw(data) });
+                Err(e)
+            }
+        }
+    }
+}
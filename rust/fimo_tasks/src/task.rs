@@ -1,15 +1,23 @@
-use crate::{bindings, Context};
+use crate::{
+    bindings,
+    channel::{wake_all, WakerList},
+    CancellationToken, Context,
+};
+use fimo_std::error::Error;
 use std::{
     alloc::Allocator,
     any::Any,
     cell::UnsafeCell,
     ffi::CString,
+    future::{poll_fn, Future},
     marker::PhantomData,
     mem::MaybeUninit,
+    pin::Pin,
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc,
+        Arc, Mutex,
     },
+    task::{Poll, Waker},
 };
 
 /// A unique identifier for a task.
@@ -19,6 +27,63 @@ use std::{
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct TaskId(pub usize);
 
+/// A user-provided tag for a task.
+///
+/// Tags are assigned to tasks through the [`CommandBuffer`](crate::CommandBuffer), and are used
+/// by the runtime to aggregate the execution times of related tasks, e.g., all tasks belonging to
+/// the same logical system. See [`WorkerGroup::task_times`](crate::WorkerGroup::task_times).
+#[repr(transparent)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TaskTag(pub usize);
+
+impl TaskTag {
+    /// Tag of all tasks that have not been assigned a tag.
+    pub const UNTAGGED: TaskTag = TaskTag(0);
+}
+
+/// Scheduling priority of a task.
+///
+/// Priorities are assigned to tasks through the [`CommandBuffer`](crate::CommandBuffer). The
+/// runtime dispatches tasks with a higher priority first, but periodically dispatches tasks with a
+/// lower priority that have been waiting for too long, so that they are not starved.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TaskPriority {
+    /// Background work, which may be delayed.
+    Low,
+    /// Default priority of a task.
+    #[default]
+    Normal,
+    /// Work that should be dispatched before the normal tasks.
+    High,
+    /// Latency critical work.
+    Critical,
+}
+
+impl From<TaskPriority> for bindings::FiTasksTaskPriority {
+    fn from(value: TaskPriority) -> Self {
+        match value {
+            TaskPriority::Low => Self::FI_TASKS_TASK_PRIORITY_LOW,
+            TaskPriority::Normal => Self::FI_TASKS_TASK_PRIORITY_NORMAL,
+            TaskPriority::High => Self::FI_TASKS_TASK_PRIORITY_HIGH,
+            TaskPriority::Critical => Self::FI_TASKS_TASK_PRIORITY_CRITICAL,
+        }
+    }
+}
+
+impl TryFrom<bindings::FiTasksTaskPriority> for TaskPriority {
+    type Error = Error;
+
+    fn try_from(value: bindings::FiTasksTaskPriority) -> Result<Self, Self::Error> {
+        match value {
+            bindings::FiTasksTaskPriority::FI_TASKS_TASK_PRIORITY_LOW => Ok(Self::Low),
+            bindings::FiTasksTaskPriority::FI_TASKS_TASK_PRIORITY_NORMAL => Ok(Self::Normal),
+            bindings::FiTasksTaskPriority::FI_TASKS_TASK_PRIORITY_HIGH => Ok(Self::High),
+            bindings::FiTasksTaskPriority::FI_TASKS_TASK_PRIORITY_CRITICAL => Ok(Self::Critical),
+            _ => Err(Error::EINVAL),
+        }
+    }
+}
+
 /// Status of a task that has finished being executed by the runtime.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum TaskStatus {
@@ -26,6 +91,11 @@ pub enum TaskStatus {
     Completed,
     /// Task has been aborted.
     Aborted,
+    /// Task has finished after its cancellation was requested.
+    ///
+    /// The result of a task that was cancelled before it started contains a
+    /// [`TaskCancelled`](crate::TaskCancelled) panic payload.
+    Cancelled,
 }
 
 #[repr(C)]
@@ -67,22 +137,20 @@ where
                 f(&Context(context))
             }));
 
-            let abort = !matches!(result, Ok(Ok(_)));
-            drop(result);
+            // The panic payload is passed to the abortion handler, which is run by the worker on
+            // its cleanup stack, as its destructor may require more stack space than is left.
+            let error = match result {
+                Ok(Ok(_)) => return,
+                Ok(Err(_)) => std::ptr::null_mut(),
+                Err(payload) => Box::into_raw(Box::new(payload)).cast(),
+            };
 
-            if abort {
-                let context = Context(context);
-                // Safety: FFI call is safe
-                unsafe {
-                    context.vtable().v0.abort.unwrap_unchecked()(
-                        context.data(),
-                        std::ptr::null_mut(),
-                    )
-                };
-
-                // In case of an error from the abort operation we abort the entire process.
-                std::process::abort();
-            }
+            let context = Context(context);
+            // Safety: FFI call is safe
+            unsafe { context.vtable().v0.abort.unwrap_unchecked()(context.data(), error) };
+
+            // In case of an error from the abort operation we abort the entire process.
+            std::process::abort();
         }
 
         unsafe extern "C" fn on_complete<'a, S, A>(
@@ -108,12 +176,17 @@ where
         unsafe extern "C" fn on_abort<'a, S, A>(
             data: *mut std::ffi::c_void,
             task: *mut bindings::FiTasksTask,
-            _error: *mut std::ffi::c_void,
+            error: *mut std::ffi::c_void,
         ) where
             S: FnOnce(TaskStatus) + 'a,
             A: Allocator + Clone,
         {
             fimo_std::panic::abort_on_panic(|| {
+                if !error.is_null() {
+                    // Safety: The error is the panic payload passed by `start`.
+                    drop(unsafe { Box::from_raw(error.cast::<Box<dyn Any + Send>>()) });
+                }
+
                 // Safety: We are the only ones with a reference to the task.
                 let task = unsafe { &mut *task.cast::<RawTask<'_, A>>() };
                 let allocator = task.allocator.clone();
@@ -226,7 +299,35 @@ pub struct TaskHandle<T, A: Allocator> {
 
 pub(super) struct TaskHandleInner<T> {
     pub(super) completed: AtomicBool,
+    pub(super) cancelled: AtomicBool,
+    pub(super) token: CancellationToken,
     pub(super) value: UnsafeCell<MaybeUninit<Result<T, Box<dyn Any + Send + 'static>>>>,
+    pub(super) wakers: Mutex<WakerList>,
+}
+
+impl<T> TaskHandleInner<T> {
+    /// Marks the task as completed and wakes all waiters.
+    pub(super) fn complete(&self) {
+        self.completed.store(true, Ordering::Release);
+        let wakers = self
+            .wakers
+            .lock()
+            .expect("could not lock task wakers")
+            .take();
+        wake_all(wakers);
+    }
+
+    /// Registers a waker, which is woken once the task has been completed.
+    ///
+    /// Returns `false` if the task has already been completed.
+    fn register(&self, waker: &Waker) -> bool {
+        let mut wakers = self.wakers.lock().expect("could not lock task wakers");
+        if self.completed.load(Ordering::Acquire) {
+            return false;
+        }
+        wakers.register(waker);
+        true
+    }
 }
 
 // Safety: Is sound as a `TaskHandleInner` essentially works like a `Mutex` on `T`.
@@ -244,10 +345,32 @@ where
         self.inner.completed.load(Ordering::Acquire)
     }
 
+    /// Requests the cancellation of the task.
+    ///
+    /// The request is shared with all other tasks associated with the same [`CancellationToken`].
+    pub fn cancel(&self) {
+        self.inner.token.cancel();
+    }
+
+    /// Returns the [`CancellationToken`] associated with the task.
+    pub fn cancellation_token(&self) -> &CancellationToken {
+        &self.inner.token
+    }
+
+    /// Returns a future that completes once the task has been completed.
+    ///
+    /// Unlike [`Context::wait_all`], the future is not bound to a task, and can be awaited by any
+    /// executor, e.g., by a foreign async runtime.
+    pub fn wait_async(&self) -> TaskWait<'_, T, A> {
+        TaskWait { handle: self }
+    }
+
     /// Returns the completion status of the task, if it has finished executing.
     pub fn completion_status(&self) -> Option<TaskStatus> {
         if !self.is_completed() {
             None
+        } else if self.inner.cancelled.load(Ordering::Relaxed) {
+            Some(TaskStatus::Cancelled)
         } else {
             // Safety: The task has been completed, therefore we are allowed to read from value.
             let value = unsafe { (*self.inner.value.get()).assume_init_ref() };
@@ -316,3 +439,114 @@ where
         }
     }
 }
+
+/// Future returned by [`TaskHandle::wait_async`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct TaskWait<'a, T, A: Allocator> {
+    handle: &'a TaskHandle<T, A>,
+}
+
+impl<T, A: Allocator> Future for TaskWait<'_, T, A> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        if self.handle.inner.register(cx.waker()) {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    }
+}
+
+impl<T, A: Allocator> std::fmt::Debug for TaskWait<'_, T, A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskWait")
+            .field("is_completed", &self.handle.is_completed())
+            .finish()
+    }
+}
+
+impl Context {
+    /// Suspends the current task until all tasks of `handles` have been completed.
+    ///
+    /// The task is woken once the last of the tasks has been completed, which enables fork-join
+    /// patterns across tasks enqueued with different command buffers, possibly on different
+    /// [`WorkerGroup`](crate::WorkerGroup)s. The handles must belong to enqueued command buffers,
+    /// otherwise the wait never finishes.
+    ///
+    /// Returns [`Error::ECANCELED`] if the cancellation of the current task is requested while
+    /// waiting. Can only suspend successfully from a task.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fimo_tasks::__private_with_context(|_module, context| {
+    /// use fimo_tasks::{CommandBuffer, WorkerGroupBuilder};
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+    ///     .with_worker_count(NonZeroUsize::new(2))
+    ///     .build(&context)
+    ///     .expect("could not create worker group");
+    ///
+    /// let mut fork = CommandBuffer::new();
+    /// let handles = [fork.spawn_task(|_| 1), fork.spawn_task(|_| 2)];
+    /// let fork = fork
+    ///     .enqueue(&group, |_| {})
+    ///     .expect("could not enqueue command buffer");
+    ///
+    /// let mut join = CommandBuffer::new();
+    /// let sum = join.spawn_task(move |context| {
+    ///     context.wait_all(&handles).unwrap();
+    ///     handles.map(|x| x.unwrap().unwrap()).iter().sum::<i32>()
+    /// });
+    /// join
+    ///     .block_on(&group)
+    ///     .expect("could not enqueue command buffer");
+    /// assert_eq!(sum.unwrap().unwrap(), 3);
+    /// # drop(fork);
+    /// # });
+    /// ```
+    pub fn wait_all<T, A: Allocator>(&self, handles: &[TaskHandle<T, A>]) -> Result<(), Error> {
+        let mut next = 0;
+        self.block_on(poll_fn(|cx| {
+            while let Some(handle) = handles.get(next) {
+                if handle.inner.register(cx.waker()) {
+                    return Poll::Pending;
+                }
+                next += 1;
+            }
+            Poll::Ready(())
+        }))
+    }
+
+    /// Suspends the current task until one of the tasks of `handles` has been completed, and
+    /// returns its index.
+    ///
+    /// If multiple tasks have been completed, the smallest index is returned. See
+    /// [`Context::wait_all`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `handles` is empty.
+    pub fn wait_any<T, A: Allocator>(&self, handles: &[TaskHandle<T, A>]) -> Result<usize, Error> {
+        assert!(!handles.is_empty(), "no task handle to wait on");
+        self.block_on(poll_fn(|cx| {
+            if let Some(index) = handles.iter().position(|x| x.is_completed()) {
+                return Poll::Ready(index);
+            }
+
+            // Register with all tasks and check again, in case one of them was completed before
+            // the registration.
+            for handle in handles {
+                if !handle.inner.register(cx.waker()) {
+                    break;
+                }
+            }
+            match handles.iter().position(|x| x.is_completed()) {
+                Some(index) => Poll::Ready(index),
+                None => Poll::Pending,
+            }
+        }))
+    }
+}
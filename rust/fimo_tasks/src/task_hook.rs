@@ -0,0 +1,233 @@
+use crate::{bindings, Context, TaskId, WorkerGroupId, WorkerId};
+use fimo_std::{
+    error::{to_result_indirect_in_place, Error},
+    ffi::FFITransferable,
+    time::Time,
+};
+use std::{ffi::c_void, marker::PhantomData};
+
+/// Kind of a [`TaskEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TaskEventKind {
+    /// The task has been spawned by the event loop of its worker group.
+    Spawned,
+    /// The task is about to be started by a worker.
+    Started,
+    /// The task has suspended its execution, e.g., by yielding or waiting.
+    Yielded,
+    /// The task is about to be resumed by a worker.
+    Resumed,
+    /// The task has run to completion.
+    Completed,
+    /// The task has been aborted.
+    Failed,
+}
+
+/// A lifecycle event of a task, observed by a [`TaskHook`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TaskEvent {
+    /// Kind of the event.
+    pub kind: TaskEventKind,
+    /// Id of the task.
+    pub task: TaskId,
+    /// Id of the worker group of the task.
+    pub worker_group: WorkerGroupId,
+    /// Id of the worker emitting the event, or `None` for [`TaskEventKind::Spawned`].
+    pub worker: Option<WorkerId>,
+    /// Time at which the event occurred.
+    pub time: Time,
+}
+
+impl TaskEvent {
+    fn from_ffi(event: &bindings::FiTasksTaskEvent) -> Option<Self> {
+        let kind = match event.type_ {
+            bindings::FiTasksTaskEventType::FI_TASKS_TASK_EVENT_TYPE_SPAWNED => {
+                TaskEventKind::Spawned
+            }
+            bindings::FiTasksTaskEventType::FI_TASKS_TASK_EVENT_TYPE_STARTED => {
+                TaskEventKind::Started
+            }
+            bindings::FiTasksTaskEventType::FI_TASKS_TASK_EVENT_TYPE_YIELDED => {
+                TaskEventKind::Yielded
+            }
+            bindings::FiTasksTaskEventType::FI_TASKS_TASK_EVENT_TYPE_RESUMED => {
+                TaskEventKind::Resumed
+            }
+            bindings::FiTasksTaskEventType::FI_TASKS_TASK_EVENT_TYPE_COMPLETED => {
+                TaskEventKind::Completed
+            }
+            bindings::FiTasksTaskEventType::FI_TASKS_TASK_EVENT_TYPE_FAILED => {
+                TaskEventKind::Failed
+            }
+            _ => return None,
+        };
+
+        Some(Self {
+            kind,
+            task: TaskId(event.task_id),
+            worker_group: WorkerGroupId(event.worker_group_id),
+            worker: event.has_worker.then_some(WorkerId(event.worker_id)),
+            // Safety: The time is passed by value.
+            time: unsafe { Time::from_ffi(event.time) },
+        })
+    }
+}
+
+/// RAII guard of a hook observing the lifecycle events of all tasks.
+///
+/// The callback of the hook is invoked until the guard is dropped.
+///
+/// # Examples
+///
+/// ```
+/// # fimo_tasks::__private_with_context(|_module, context| {
+/// use fimo_tasks::{CommandBuffer, TaskEventKind, TaskStatus, WorkerGroupBuilder};
+/// use std::{
+///     num::NonZeroUsize,
+///     sync::{Arc, Mutex},
+/// };
+///
+/// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+///     .with_worker_count(NonZeroUsize::new(1))
+///     .build(&context)
+///     .expect("could not create worker group");
+///
+/// let events = Arc::new(Mutex::new(Vec::new()));
+/// let hook = context
+///     .register_task_hook({
+///         let events = events.clone();
+///         move |event| events.lock().unwrap().push(event.kind)
+///     })
+///     .expect("could not register hook");
+///
+/// let mut buffer = CommandBuffer::new();
+/// let task = buffer.spawn_task(|context| context.yield_now().unwrap());
+/// buffer
+///     .block_on(&group)
+///     .expect("could not enqueue command buffer");
+/// assert_eq!(task.completion_status(), Some(TaskStatus::Completed));
+///
+/// drop(hook);
+/// assert_eq!(
+///     *events.lock().unwrap(),
+///     [
+///         TaskEventKind::Spawned,
+///         TaskEventKind::Started,
+///         TaskEventKind::Yielded,
+///         TaskEventKind::Resumed,
+///         TaskEventKind::Completed,
+///     ]
+/// );
+/// # });
+/// ```
+#[repr(transparent)]
+pub struct TaskHook<'ctx>(bindings::FiTasksTaskHook, PhantomData<fn() -> &'ctx ()>);
+
+impl TaskHook<'_> {
+    /// Returns whether the hook is still registered.
+    ///
+    /// A hook is unregistered automatically, once the runtime is shut down.
+    pub fn is_active(&self) -> bool {
+        // Safety: FFI call is safe
+        unsafe { self.vtable().v0.is_active.unwrap_unchecked()(self.data()) }
+    }
+
+    #[inline(always)]
+    fn data(&self) -> *mut c_void {
+        self.0.data
+    }
+
+    #[inline(always)]
+    fn vtable(&self) -> &bindings::FiTasksTaskHookVTable {
+        // Safety: The VTable is always initialized
+        unsafe { &*self.0.vtable }
+    }
+}
+
+impl Context {
+    /// Registers a hook observing the lifecycle events of all tasks.
+    ///
+    /// The callback `f` is invoked when a task is spawned, started, yielded, resumed, completed or
+    /// aborted, until the returned guard is dropped. It may be invoked concurrently by the workers
+    /// and event loops of all worker groups, and is not run inside a task. As it is invoked on
+    /// the hot path of the scheduler, it should only perform short, non-blocking operations, like
+    /// recording the event. While no hook is registered, the events are not constructed.
+    ///
+    /// May be called from any thread.
+    pub fn register_task_hook<F>(&self, f: F) -> Result<TaskHook<'_>, Error>
+    where
+        F: Fn(&TaskEvent) + Send + Sync + 'static,
+    {
+        unsafe extern "C" fn on_event<F: Fn(&TaskEvent) + Send + Sync + 'static>(
+            data: *mut c_void,
+            event: *const bindings::FiTasksTaskEvent,
+        ) {
+            fimo_std::panic::abort_on_panic(|| {
+                // Safety: The hook is only invoked with valid events.
+                let (f, event) = unsafe { (&*data.cast_const().cast::<F>(), &*event) };
+                if let Some(event) = TaskEvent::from_ffi(event) {
+                    f(&event);
+                }
+            });
+        }
+
+        unsafe extern "C" fn cleanup<F: Fn(&TaskEvent) + Send + Sync + 'static>(data: *mut c_void) {
+            fimo_std::panic::abort_on_panic(|| {
+                // Safety: The cleanup function is invoked once, after the last invocation of the
+                // callback.
+                drop(unsafe { Box::from_raw(data.cast::<F>()) });
+            });
+        }
+
+        let data = Box::into_raw(Box::new(f));
+        let config = bindings::FiTasksTaskHookConfig {
+            next: std::ptr::null_mut(),
+            on_event: Some(on_event::<F>),
+            data: data.cast(),
+            cleanup: Some(cleanup::<F>),
+        };
+
+        // Safety: FFI call is safe
+        let hook = unsafe {
+            to_result_indirect_in_place(|err, hook| {
+                *err = self.vtable().v0.register_task_hook.unwrap_unchecked()(
+                    self.data(),
+                    &config,
+                    hook.as_mut_ptr(),
+                );
+            })
+        };
+        match hook {
+            Ok(hook) => Ok(TaskHook(hook, PhantomData)),
+            Err(e) => {
+                // Safety: The hook was not registered, so we still own the data.
+                drop(unsafe { Box::from_raw(data) });
+                Err(e)
+            }
+        }
+    }
+}
+
+// Safety: Sound by invariant
+unsafe impl Send for TaskHook<'_> {}
+
+// Safety: Sound by invariant
+unsafe impl Sync for TaskHook<'_> {}
+
+impl std::fmt::Debug for TaskHook<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskHook")
+            .field("is_active", &self.is_active())
+            .finish()
+    }
+}
+
+impl Drop for TaskHook<'_> {
+    fn drop(&mut self) {
+        // Safety: We own the reference therefore we can remove the hook and release it.
+        unsafe {
+            self.vtable().v0.remove.unwrap_unchecked()(self.data());
+            self.vtable().v0.release.unwrap_unchecked()(self.data());
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use crate::Context;
+use fimo_std::{
+    ffi::FFISharable,
+    tracing::{ProcessedRecord, Processor},
+};
+
+/// A tracing [`Processor`] adding the id of the current task as the `task` field.
+///
+/// Events emitted outside of a task are passed through unmodified.
+///
+/// # Examples
+///
+/// ```
+/// # fimo_tasks::__private_with_context(|_module, context| {
+/// use fimo_std::tracing::Processors;
+/// use fimo_tasks::TaskIdProcessor;
+///
+/// let processors = Processors::default();
+/// // Safety: The processor is removed before the context is released.
+/// let guard = processors.add(0, unsafe { TaskIdProcessor::new(context) });
+/// drop(guard);
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct TaskIdProcessor(Context);
+
+impl TaskIdProcessor {
+    /// Constructs a new `TaskIdProcessor`.
+    ///
+    /// # Safety
+    ///
+    /// The context must remain valid for as long as the processor is in use, i.e., the processor
+    /// must be removed before the dependency to the tasks module is released.
+    pub unsafe fn new(context: &Context) -> Self {
+        Self(Context(context.share_to_ffi()))
+    }
+}
+
+impl Processor for TaskIdProcessor {
+    fn process(&self, record: &mut ProcessedRecord<'_>) {
+        if let Ok(id) = self.0.task_id() {
+            record.add_field("task", id.0);
+        }
+    }
+}
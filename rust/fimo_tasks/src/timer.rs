@@ -0,0 +1,193 @@
+use crate::{bindings, WorkerGroup};
+use fimo_std::{
+    error::{to_result_indirect_in_place, Error},
+    ffi::FFITransferable,
+    time::Time,
+};
+use std::{ffi::c_void, marker::PhantomData, mem::ManuallyDrop, time::Duration};
+
+/// A timer scheduled on the event loop of a [`WorkerGroup`].
+///
+/// The timer invokes its callback once its deadline has been reached, and, if it is periodic, at a
+/// fixed rate afterward, until it is cancelled or the worker group is closed. Dropping the last
+/// handle to the timer does not cancel it.
+///
+/// # Examples
+///
+/// ```
+/// # fimo_tasks::__private_with_context(|_module, context| {
+/// use fimo_std::time::Time;
+/// use fimo_tasks::WorkerGroupBuilder;
+/// use std::{
+///     num::NonZeroUsize,
+///     sync::{
+///         atomic::{AtomicUsize, Ordering},
+///         Arc,
+///     },
+///     time::Duration,
+/// };
+///
+/// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+///     .with_worker_count(NonZeroUsize::new(1))
+///     .build(&context)
+///     .expect("could not create worker group");
+///
+/// let count = Arc::new(AtomicUsize::new(0));
+/// let timer = group
+///     .create_timer(Time::now(), Some(Duration::from_millis(1)), {
+///         let count = count.clone();
+///         move |_group| {
+///             count.fetch_add(1, Ordering::Relaxed);
+///         }
+///     })
+///     .expect("could not create timer");
+///
+/// while count.load(Ordering::Relaxed) < 3 {
+///     std::thread::yield_now();
+/// }
+/// assert!(timer.is_active());
+/// timer.cancel();
+/// assert!(!timer.is_active());
+/// # });
+/// ```
+#[repr(transparent)]
+pub struct Timer<'ctx>(bindings::FiTasksTimer, PhantomData<fn() -> &'ctx ()>);
+
+impl Timer<'_> {
+    /// Cancels the timer.
+    ///
+    /// Afterward, the callback won't be invoked anymore, but an invocation that started before the
+    /// call may still be running. May be called from inside the callback.
+    pub fn cancel(&self) {
+        // Safety: FFI call is safe
+        unsafe { self.vtable().v0.cancel.unwrap_unchecked()(self.data()) }
+    }
+
+    /// Returns whether the timer may still invoke its callback.
+    pub fn is_active(&self) -> bool {
+        // Safety: FFI call is safe
+        unsafe { self.vtable().v0.is_active.unwrap_unchecked()(self.data()) }
+    }
+
+    #[inline(always)]
+    fn data(&self) -> *mut c_void {
+        self.0.data
+    }
+
+    #[inline(always)]
+    fn vtable(&self) -> &bindings::FiTasksTimerVTable {
+        // Safety: The VTable is always initialized
+        unsafe { &*self.0.vtable }
+    }
+}
+
+impl<'ctx> WorkerGroup<'ctx> {
+    /// Schedules a timer on the event loop of the worker group.
+    ///
+    /// The callback `f` is invoked by the event loop once `deadline` has been reached, and, if a
+    /// `period` is specified, each `period` afterward. Periods missed while the event loop was
+    /// busy are skipped. A deadline in the past invokes the callback immediately.
+    ///
+    /// The callback is not run inside a task, and should only perform short operations, like
+    /// enqueueing a [`CommandBuffer`](crate::CommandBuffer) onto the provided worker group, or
+    /// signaling a [`Semaphore`](crate::Semaphore).
+    pub fn create_timer<F>(
+        &self,
+        deadline: Time,
+        period: Option<Duration>,
+        f: F,
+    ) -> Result<Timer<'ctx>, Error>
+    where
+        F: FnMut(&WorkerGroup<'_>) + Send + 'static,
+    {
+        struct TimerData<F> {
+            f: F,
+            group: WorkerGroup<'static>,
+        }
+
+        unsafe extern "C" fn callback<F: FnMut(&WorkerGroup<'_>) + Send + 'static>(
+            data: *mut c_void,
+        ) {
+            fimo_std::panic::abort_on_panic(|| {
+                // Safety: The timer does not invoke the callback concurrently.
+                let data = unsafe { &mut *data.cast::<TimerData<F>>() };
+                (data.f)(&data.group);
+            });
+        }
+
+        unsafe extern "C" fn cleanup<F: FnMut(&WorkerGroup<'_>) + Send + 'static>(
+            data: *mut c_void,
+        ) {
+            fimo_std::panic::abort_on_panic(|| {
+                // Safety: The cleanup function is invoked once, after the last invocation of the
+                // callback.
+                drop(unsafe { Box::from_raw(data.cast::<TimerData<F>>()) });
+            });
+        }
+
+        let group = ManuallyDrop::new(self.clone());
+        let data = Box::into_raw(Box::new(TimerData {
+            f,
+            group: WorkerGroup(group.0, PhantomData),
+        }));
+
+        let period = period.unwrap_or(Duration::ZERO);
+        let config = bindings::FiTasksTimerConfig {
+            next: std::ptr::null_mut(),
+            deadline: deadline.into_ffi(),
+            period: fimo_std::time::Duration::new(period.as_secs(), period.subsec_nanos())
+                .into_ffi(),
+            callback: Some(callback::<F>),
+            data: data.cast(),
+            cleanup: Some(cleanup::<F>),
+        };
+
+        // Safety: FFI call is safe
+        let timer = unsafe {
+            to_result_indirect_in_place(|err, timer| {
+                *err = self.vtable().v0.create_timer.unwrap_unchecked()(
+                    self.data(),
+                    &config,
+                    timer.as_mut_ptr(),
+                );
+            })
+        };
+        match timer {
+            Ok(timer) => Ok(Timer(timer, PhantomData)),
+            Err(e) => {
+                // Safety: The timer was not created, so we still own the data.
+                drop(unsafe { Box::from_raw(data) });
+                Err(e)
+            }
+        }
+    }
+}
+
+// Safety: Sound by invariant
+unsafe impl Send for Timer<'_> {}
+
+// Safety: Sound by invariant
+unsafe impl Sync for Timer<'_> {}
+
+impl std::fmt::Debug for Timer<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Timer")
+            .field("is_active", &self.is_active())
+            .finish()
+    }
+}
+
+impl Clone for Timer<'_> {
+    fn clone(&self) -> Self {
+        // Safety: We own the reference therefore we can acquire another one.
+        unsafe { self.vtable().v0.acquire.unwrap_unchecked()(self.data()) }
+        Self(self.0, PhantomData)
+    }
+}
+
+impl Drop for Timer<'_> {
+    fn drop(&mut self) {
+        // Safety: We own the reference therefore we can release it.
+        unsafe { self.vtable().v0.release.unwrap_unchecked()(self.data()) }
+    }
+}
@@ -0,0 +1,241 @@
+use crate::Context;
+use fimo_std::{context::ContextView, error::Error, tracing::ThreadAccess};
+use std::{cell::RefCell, future::Future, num::NonZeroUsize, time::Duration};
+use tokio::{
+    runtime::{Builder, Handle, Runtime},
+    task::JoinHandle,
+};
+
+std::thread_local! {
+    static THREAD_ACCESS: RefCell<Option<ThreadAccess>> = const { RefCell::new(None) };
+}
+
+/// Default duration a [`TokioBridge`] waits for its tasks to finish during the shutdown.
+const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Bridge to a tokio runtime, for using tokio-based libraries from fimo code.
+///
+/// The bridge runs a multi-threaded tokio runtime on a set of dedicated threads, which are
+/// registered with the tracing subsystem of the context, and keep the context alive until they
+/// exit. Tokio futures can be spawned onto the runtime with [`spawn`], or can be awaited from a
+/// task with [`block_on`], which suspends the task instead of its worker. In the other direction,
+/// the futures of the runtime, like [`TaskHandle::wait_async`](crate::TaskHandle::wait_async) or
+/// [`BlockingHandle`](crate::BlockingHandle), can be awaited from the tokio runtime.
+///
+/// The bridge must be shut down before the tasks module is unloaded, as the tokio tasks may still
+/// reference the symbols of the module. Dropping the bridge shuts down the tokio runtime, waiting
+/// for its tasks to finish for at most the shutdown timeout, before releasing the context.
+///
+/// [`spawn`]: TokioBridge::spawn
+/// [`block_on`]: TokioBridge::block_on
+///
+/// # Examples
+///
+/// ```
+/// # fimo_tasks::__private_with_context(|module, context| {
+/// use fimo_std::module::Module;
+/// use fimo_tasks::{CommandBuffer, TokioBridgeBuilder, WorkerGroupBuilder};
+/// use std::{num::NonZeroUsize, sync::Arc};
+///
+/// let bridge = TokioBridgeBuilder::new()
+///     .with_worker_count(NonZeroUsize::new(1))
+///     .build(&module.context())
+///     .expect("could not create the tokio bridge");
+/// let bridge = Arc::new(bridge);
+///
+/// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+///     .with_worker_count(NonZeroUsize::new(1))
+///     .build(&context)
+///     .expect("could not create worker group");
+///
+/// // Await a tokio future from a task.
+/// let mut buffer = CommandBuffer::new();
+/// let task = buffer.spawn_task({
+///     let bridge = bridge.clone();
+///     move |context| {
+///         let future = async {
+///             tokio::task::yield_now().await;
+///             5
+///         };
+///         bridge.block_on(context, future).unwrap()
+///     }
+/// });
+/// buffer
+///     .block_on(&group)
+///     .expect("could not enqueue command buffer");
+/// assert_eq!(task.unwrap().unwrap(), 5);
+///
+/// // Await a task from tokio.
+/// let mut buffer = CommandBuffer::new();
+/// let task = buffer.spawn_task(|_| 6);
+/// let _handle = buffer
+///     .enqueue(&group, |_| {})
+///     .expect("could not enqueue command buffer");
+/// let value = bridge.handle().block_on(async move {
+///     task.wait_async().await;
+///     task.unwrap().unwrap()
+/// });
+/// assert_eq!(value, 6);
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct TokioBridge {
+    runtime: Option<Runtime>,
+    shutdown_timeout: Duration,
+    context: fimo_std::context::Context,
+}
+
+impl TokioBridge {
+    /// Returns a handle to the tokio runtime.
+    pub fn handle(&self) -> &Handle {
+        self.runtime().handle()
+    }
+
+    /// Spawns a future onto the tokio runtime.
+    ///
+    /// The returned [`JoinHandle`] is a future, which can be awaited from a task with
+    /// [`Context::block_on`]. Dropping the handle detaches the future.
+    ///
+    /// May be called from any thread.
+    pub fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        self.runtime().spawn(future)
+    }
+
+    /// Runs a future on the tokio runtime, and suspends the current task until it is completed.
+    ///
+    /// The future is aborted, if the cancellation of the current task is requested while waiting,
+    /// in which case [`Error::ECANCELED`] is returned. The same error is returned, if the runtime
+    /// is shut down before the future is completed. A panic of the future is propagated to the
+    /// caller.
+    ///
+    /// Can only be called successfully from a task.
+    pub fn block_on<F>(&self, ctx: &Context, future: F) -> Result<F::Output, Error>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        struct AbortOnDrop<T>(JoinHandle<T>);
+        impl<T> Drop for AbortOnDrop<T> {
+            fn drop(&mut self) {
+                self.0.abort();
+            }
+        }
+
+        let mut handle = AbortOnDrop(self.spawn(future));
+        match ctx.block_on(&mut handle.0)? {
+            Ok(value) => Ok(value),
+            Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+            Err(_) => Err(Error::ECANCELED),
+        }
+    }
+
+    /// Shuts down the tokio runtime.
+    ///
+    /// Waits for the tasks of the runtime to finish, for at most the shutdown timeout. Is
+    /// equivalent to dropping the bridge.
+    pub fn shutdown(self) {
+        drop(self);
+    }
+
+    fn runtime(&self) -> &Runtime {
+        self.runtime
+            .as_ref()
+            .expect("the runtime should be alive until the bridge is dropped")
+    }
+}
+
+impl Drop for TokioBridge {
+    fn drop(&mut self) {
+        // The runtime must be shut down before we release our reference to the context, as its
+        // threads may still emit events.
+        if let Some(runtime) = self.runtime.take() {
+            fimo_std::emit_debug!(self.context, "shutting down the tokio bridge");
+            runtime.shutdown_timeout(self.shutdown_timeout);
+        }
+    }
+}
+
+/// A builder for a [`TokioBridge`].
+#[derive(Debug, Clone)]
+pub struct TokioBridgeBuilder<'a> {
+    thread_name: &'a str,
+    worker_count: Option<NonZeroUsize>,
+    shutdown_timeout: Duration,
+}
+
+impl<'a> TokioBridgeBuilder<'a> {
+    /// Constructs a new `TokioBridgeBuilder`.
+    pub fn new() -> Self {
+        Self {
+            thread_name: "fimo-tokio",
+            worker_count: None,
+            shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+        }
+    }
+
+    /// Sets the name of the threads of the tokio runtime.
+    ///
+    /// Defaults to `fimo-tokio`.
+    pub fn with_thread_name(mut self, name: &'a str) -> Self {
+        self.thread_name = name;
+        self
+    }
+
+    /// Sets the number of worker threads of the tokio runtime.
+    ///
+    /// A value of `None` uses the default of tokio, i.e., one worker for each logical core present
+    /// in the system.
+    ///
+    /// Defaults to `None`.
+    pub fn with_worker_count(mut self, count: Option<NonZeroUsize>) -> Self {
+        self.worker_count = count;
+        self
+    }
+
+    /// Sets the maximum duration to wait for the tasks of the tokio runtime to finish, when the
+    /// bridge is shut down.
+    ///
+    /// Defaults to five seconds.
+    pub fn with_shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_timeout = timeout;
+        self
+    }
+
+    /// Creates a new [`TokioBridge`].
+    pub fn build(self, ctx: &ContextView<'_>) -> Result<TokioBridge, Error> {
+        let context = ctx.to_context();
+
+        let mut builder = Builder::new_multi_thread();
+        builder
+            .enable_all()
+            .thread_name(self.thread_name)
+            .on_thread_start({
+                let context = context.clone();
+                move || {
+                    let access = ThreadAccess::new(&context).ok();
+                    THREAD_ACCESS.set(access);
+                }
+            })
+            .on_thread_stop(|| drop(THREAD_ACCESS.take()));
+        if let Some(count) = self.worker_count {
+            builder.worker_threads(count.get());
+        }
+        let runtime = builder.build().map_err(Error::new)?;
+
+        Ok(TokioBridge {
+            runtime: Some(runtime),
+            shutdown_timeout: self.shutdown_timeout,
+            context,
+        })
+    }
+}
+
+impl Default for TokioBridgeBuilder<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
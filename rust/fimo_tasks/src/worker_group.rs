@@ -1,9 +1,18 @@
-use crate::{bindings, Context};
+use crate::{bindings, Context, TaskId, TaskTag};
 use fimo_std::{
     allocator::FimoAllocator,
     error::{to_result_indirect, to_result_indirect_in_place, Error},
+    ffi::FFITransferable,
+};
+use std::{
+    alloc::{AllocError, Allocator, Layout},
+    ffi::{CStr, CString},
+    fmt::Formatter,
+    marker::PhantomData,
+    num::NonZeroUsize,
+    ptr::NonNull,
+    time::Duration,
 };
-use std::{ffi::CStr, fmt::Formatter, marker::PhantomData, num::NonZeroUsize};
 
 /// A unique identifier for a [`WorkerGroup`].
 #[repr(transparent)]
@@ -69,6 +78,51 @@ impl WorkerGroup<'_> {
         }
     }
 
+    /// Changes the number of workers of the worker group.
+    ///
+    /// The request is processed asynchronously, in order with the command buffers enqueued into
+    /// the group. Additional workers are spawned immediately. Superfluous workers, starting with
+    /// the most recently spawned ones, retire gracefully: they hand over the tasks they have not
+    /// started yet, and exit once the tasks they have already started have finished. The workers
+    /// spawned by a resize are assigned new ids, and are not pinned to any cpus.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fimo_tasks::__private_with_context(|_module, context| {
+    /// use fimo_tasks::{CommandBuffer, WorkerGroupBuilder};
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+    ///     .with_worker_count(NonZeroUsize::new(1))
+    ///     .build(&context)
+    ///     .expect("could not create worker group");
+    ///
+    /// group.resize(NonZeroUsize::new(2).unwrap()).unwrap();
+    /// let mut buffer = CommandBuffer::new();
+    /// let task = buffer.spawn_task(|_| 5);
+    /// buffer
+    ///     .block_on(&group)
+    ///     .expect("could not enqueue command buffer");
+    /// assert_eq!(task.unwrap().unwrap(), 5);
+    /// assert_eq!(group.stats().unwrap().active_workers(), 2);
+    ///
+    /// group.resize(NonZeroUsize::new(1).unwrap()).unwrap();
+    /// CommandBuffer::new()
+    ///     .block_on(&group)
+    ///     .expect("could not enqueue command buffer");
+    /// assert_eq!(group.stats().unwrap().active_workers(), 1);
+    /// # });
+    /// ```
+    pub fn resize(&self, num_workers: NonZeroUsize) -> Result<(), Error> {
+        // Safety: FFI call is safe
+        unsafe {
+            to_result_indirect(|err| {
+                *err = self.vtable().v0.resize.unwrap_unchecked()(self.data(), num_workers.get());
+            })
+        }
+    }
+
     /// Fetches a list of worker ids available in the worker group.
     pub fn workers(&self) -> Result<Box<[WorkerId], FimoAllocator>, Error> {
         let mut num_workers = 0;
@@ -121,6 +175,220 @@ impl WorkerGroup<'_> {
         unsafe { Ok(Box::from_raw_in(sizes, FimoAllocator)) }
     }
 
+    /// Fetches the accumulated execution times of the tasks of the worker group.
+    ///
+    /// The times are grouped by the [`TaskTag`] assigned to the tasks, and only account for the
+    /// tasks that have already finished their execution.
+    pub fn task_times(&self) -> Result<Box<[TaskTimes], FimoAllocator>, Error> {
+        let mut num_tags = 0;
+        // Safety: FFI call is safe
+        let times = unsafe {
+            to_result_indirect_in_place(|err, times| {
+                *err = self.vtable().v0.task_times.unwrap_unchecked()(
+                    self.data(),
+                    times.as_mut_ptr(),
+                    &mut num_tags,
+                );
+            })?
+        };
+
+        // We can cast the pointer to an `TaskTimes` pointer, since the two types
+        // have the same layout.
+        let times = times.cast::<TaskTimes>();
+
+        // The API guarantees that we are returned a contiguous range of memory containing the
+        // times.
+        let times = std::ptr::slice_from_raw_parts_mut(times, num_tags);
+
+        // Safety: According to the API, the slice has been allocated with the fimo allocator,
+        // therefore we are allowed to construct a box with the same allocator.
+        unsafe { Ok(Box::from_raw_in(times, FimoAllocator)) }
+    }
+
+    /// Fetches the work stealing statistics of the worker group.
+    pub fn steal_stats(&self) -> Result<StealStats, Error> {
+        // Safety: FFI call is safe
+        let stats = unsafe {
+            to_result_indirect_in_place(|err, stats| {
+                *err = self.vtable().v0.steal_stats.unwrap_unchecked()(
+                    self.data(),
+                    stats.as_mut_ptr(),
+                );
+            })?
+        };
+
+        Ok(StealStats(stats))
+    }
+
+    /// Fetches the scheduler statistics of the worker group.
+    ///
+    /// The statistics are collected while the group is running, and may therefore be slightly
+    /// inconsistent with one another.
+    pub fn stats(&self) -> Result<WorkerGroupStats, Error> {
+        // Safety: FFI call is safe
+        let stats = unsafe {
+            to_result_indirect_in_place(|err, stats| {
+                *err = self.vtable().v0.stats.unwrap_unchecked()(self.data(), stats.as_mut_ptr());
+            })?
+        };
+
+        Ok(WorkerGroupStats(stats))
+    }
+
+    /// Fetches the usage statistics of the stacks of the worker group, sorted by the stack size.
+    pub fn stack_stats(&self) -> Result<Box<[StackStats], FimoAllocator>, Error> {
+        let mut num_sizes = 0;
+        // Safety: FFI call is safe
+        let stats = unsafe {
+            to_result_indirect_in_place(|err, stats| {
+                *err = self.vtable().v0.stack_stats.unwrap_unchecked()(
+                    self.data(),
+                    stats.as_mut_ptr(),
+                    &mut num_sizes,
+                );
+            })?
+        };
+
+        // We can cast the pointer to an `StackStats` pointer, since the two types
+        // have the same layout.
+        let stats = stats.cast::<StackStats>();
+
+        // The API guarantees that we are returned a contiguous range of memory containing the
+        // statistics.
+        let stats = std::ptr::slice_from_raw_parts_mut(stats, num_sizes);
+
+        // Safety: According to the API, the slice has been allocated with the fimo allocator,
+        // therefore we are allowed to construct a box with the same allocator.
+        unsafe { Ok(Box::from_raw_in(stats, FimoAllocator)) }
+    }
+
+    /// Fetches the statistics of each worker of the worker group, sorted by the worker id.
+    pub fn worker_stats(&self) -> Result<Box<[WorkerStats], FimoAllocator>, Error> {
+        let mut num_workers = 0;
+        // Safety: FFI call is safe
+        let stats = unsafe {
+            to_result_indirect_in_place(|err, stats| {
+                *err = self.vtable().v0.worker_stats.unwrap_unchecked()(
+                    self.data(),
+                    stats.as_mut_ptr(),
+                    &mut num_workers,
+                );
+            })?
+        };
+
+        // We can cast the pointer to an `WorkerStats` pointer, since the two types
+        // have the same layout.
+        let stats = stats.cast::<WorkerStats>();
+
+        // The API guarantees that we are returned a contiguous range of memory containing the
+        // statistics.
+        let stats = std::ptr::slice_from_raw_parts_mut(stats, num_workers);
+
+        // Safety: According to the API, the slice has been allocated with the fimo allocator,
+        // therefore we are allowed to construct a box with the same allocator.
+        unsafe { Ok(Box::from_raw_in(stats, FimoAllocator)) }
+    }
+
+    /// Enumerates the tasks of the worker group that have not finished yet, sorted by their id.
+    ///
+    /// Tasks that are waiting for the allocation of a stack are not included. The snapshot is not
+    /// atomic, i.e., the tasks may change their state while they are being enumerated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fimo_tasks::__private_with_context(|_module, context| {
+    /// use fimo_tasks::{CommandBuffer, TaskState, WorkerGroupBuilder};
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+    ///     .with_worker_count(NonZeroUsize::new(1))
+    ///     .build(&context)
+    ///     .expect("could not create worker group");
+    ///
+    /// let mut buffer = CommandBuffer::new();
+    /// let task = buffer.spawn_named_task(c"inspector", |context| {
+    ///     let id = context.task_id().unwrap();
+    ///     let tasks = context.worker_group().unwrap().enumerate_tasks().unwrap();
+    ///     let info = tasks.into_iter().find(|t| t.id() == id).unwrap();
+    ///     assert_eq!(info.name(), Some(c"inspector"));
+    ///     assert_eq!(info.state(), TaskState::Running);
+    ///     assert_eq!(info.worker(), Some(context.worker_id().unwrap()));
+    /// });
+    /// buffer
+    ///     .block_on(&group)
+    ///     .expect("could not enqueue command buffer");
+    /// task.unwrap().unwrap();
+    /// assert!(group.enumerate_tasks().unwrap().is_empty());
+    /// # });
+    /// ```
+    pub fn enumerate_tasks(&self) -> Result<Vec<TaskInfo>, Error> {
+        let mut num_tasks = 0;
+        // Safety: FFI call is safe
+        let infos = unsafe {
+            to_result_indirect_in_place(|err, infos| {
+                *err = self.vtable().v0.enumerate_tasks.unwrap_unchecked()(
+                    self.data(),
+                    infos.as_mut_ptr(),
+                    &mut num_tasks,
+                );
+            })?
+        };
+        if infos.is_null() {
+            return Ok(Vec::new());
+        }
+
+        // Safety: The API guarantees that we are returned a contiguous range of memory containing
+        // the snapshots, followed by their labels.
+        let tasks = unsafe { std::slice::from_raw_parts(infos, num_tasks) }
+            .iter()
+            // Safety: The labels are stored in the same allocation.
+            .map(|info| unsafe { TaskInfo::from_ffi(info) })
+            .collect();
+
+        // Safety: According to the API, the snapshots have been allocated with the fimo allocator,
+        // and are owned by us.
+        unsafe { fimo_std::bindings::fimo_free(infos.cast()) };
+        tasks
+    }
+
+    /// Fetches the sorted indices of the cpus the worker is allowed to run on.
+    ///
+    /// Reports the effective affinity of the worker thread, as applied by the operating system,
+    /// which may differ from the one requested through [`WorkerGroupBuilder::with_affinity`].
+    /// Returns [`Error::ENOTSUP`] if the affinity can not be queried on the current platform.
+    pub fn worker_affinity(&self, worker: WorkerId) -> Result<Box<[usize], FimoAllocator>, Error> {
+        let mut num_cpus = 0;
+        // Safety: FFI call is safe
+        let cpus = unsafe {
+            to_result_indirect_in_place(|err, cpus| {
+                *err = self.vtable().v0.worker_affinity.unwrap_unchecked()(
+                    self.data(),
+                    worker.0,
+                    cpus.as_mut_ptr(),
+                    &mut num_cpus,
+                );
+            })?
+        };
+
+        // The API guarantees that we are returned a contiguous range of memory containing the
+        // cpus.
+        let cpus = std::ptr::slice_from_raw_parts_mut(cpus, num_cpus);
+
+        // Safety: According to the API, the slice has been allocated with the fimo allocator,
+        // therefore we are allowed to construct a box with the same allocator.
+        unsafe { Ok(Box::from_raw_in(cpus, FimoAllocator)) }
+    }
+
+    /// Returns a handle to the allocator of the worker group.
+    ///
+    /// See [`GroupAllocator`] for more info.
+    pub fn allocator(&self) -> GroupAllocator {
+        // Safety: We own the reference therefore we can acquire another one.
+        unsafe { self.vtable().v0.acquire.unwrap_unchecked()(self.data()) }
+        GroupAllocator(self.0)
+    }
+
     #[inline(always)]
     pub(super) fn data(&self) -> *mut std::ffi::c_void {
         self.0.data
@@ -133,6 +401,385 @@ impl WorkerGroup<'_> {
     }
 }
 
+/// Accumulated execution times and allocations of all tasks with the same [`TaskTag`].
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub struct TaskTimes(bindings::FiTasksTaskTimes);
+
+impl TaskTimes {
+    /// Returns the tag of the tasks.
+    pub fn tag(&self) -> TaskTag {
+        TaskTag(self.0.tag)
+    }
+
+    /// Returns the number of finished tasks.
+    pub fn num_tasks(&self) -> usize {
+        self.0.num_tasks
+    }
+
+    /// Returns the number of execution slices, i.e., the number of times a task was resumed by a
+    /// worker.
+    pub fn num_slices(&self) -> usize {
+        self.0.num_slices
+    }
+
+    /// Returns the time spent by the workers executing the tasks.
+    pub fn wall_time(&self) -> Duration {
+        Duration::new(self.0.wall_time.secs, self.0.wall_time.nanos)
+    }
+
+    /// Returns the CPU time consumed by the workers while executing the tasks.
+    ///
+    /// Is zero if the platform does not provide a per-thread CPU clock.
+    pub fn cpu_time(&self) -> Duration {
+        Duration::new(self.0.cpu_time.secs, self.0.cpu_time.nanos)
+    }
+
+    /// Returns the number of allocations performed by the tasks with the [`FimoAllocator`].
+    ///
+    /// Is zero unless enabled with [`WorkerGroupBuilder::with_allocation_tracking`].
+    pub fn allocations(&self) -> usize {
+        self.0.allocations
+    }
+
+    /// Returns the number of deallocations performed by the tasks with the [`FimoAllocator`].
+    ///
+    /// Is zero unless enabled with [`WorkerGroupBuilder::with_allocation_tracking`].
+    pub fn deallocations(&self) -> usize {
+        self.0.deallocations
+    }
+
+    /// Returns the number of bytes allocated by the tasks with the [`FimoAllocator`].
+    ///
+    /// Is zero unless enabled with [`WorkerGroupBuilder::with_allocation_tracking`].
+    pub fn allocated_bytes(&self) -> usize {
+        self.0.allocated_bytes
+    }
+
+    /// Returns the number of bytes deallocated by the tasks with the [`FimoAllocator`].
+    ///
+    /// Is zero unless enabled with [`WorkerGroupBuilder::with_allocation_tracking`].
+    pub fn deallocated_bytes(&self) -> usize {
+        self.0.deallocated_bytes
+    }
+}
+
+impl std::fmt::Debug for TaskTimes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskTimes")
+            .field("tag", &self.tag())
+            .field("num_tasks", &self.num_tasks())
+            .field("num_slices", &self.num_slices())
+            .field("wall_time", &self.wall_time())
+            .field("cpu_time", &self.cpu_time())
+            .field("allocations", &self.allocations())
+            .field("deallocations", &self.deallocations())
+            .field("allocated_bytes", &self.allocated_bytes())
+            .field("deallocated_bytes", &self.deallocated_bytes())
+            .finish()
+    }
+}
+
+/// Work stealing statistics of a [`WorkerGroup`].
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub struct StealStats(bindings::FiTasksWorkerGroupStealStats);
+
+impl StealStats {
+    /// Returns the number of successful steals from the global queue.
+    pub fn global_steals(&self) -> usize {
+        self.0.global_steals
+    }
+
+    /// Returns the number of successful steals from the queues of other workers.
+    pub fn peer_steals(&self) -> usize {
+        self.0.peer_steals
+    }
+
+    /// Returns the total number of tasks acquired through stealing.
+    pub fn stolen_tasks(&self) -> usize {
+        self.0.stolen_tasks
+    }
+}
+
+impl std::fmt::Debug for StealStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StealStats")
+            .field("global_steals", &self.global_steals())
+            .field("peer_steals", &self.peer_steals())
+            .field("stolen_tasks", &self.stolen_tasks())
+            .finish()
+    }
+}
+
+/// Scheduler statistics of a [`WorkerGroup`].
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub struct WorkerGroupStats(bindings::FiTasksWorkerGroupStats);
+
+impl WorkerGroupStats {
+    /// Returns the number of tasks spawned in the worker group.
+    pub fn spawned_tasks(&self) -> usize {
+        self.0.spawned_tasks
+    }
+
+    /// Returns the number of tasks that completed successfully.
+    pub fn completed_tasks(&self) -> usize {
+        self.0.completed_tasks
+    }
+
+    /// Returns the number of tasks that were aborted.
+    pub fn aborted_tasks(&self) -> usize {
+        self.0.aborted_tasks
+    }
+
+    /// Returns the number of tasks waiting in the global queue of the worker group.
+    pub fn queued_tasks(&self) -> usize {
+        self.0.queued_tasks
+    }
+
+    /// Returns the number of stacks currently in use by the tasks.
+    pub fn acquired_stacks(&self) -> usize {
+        self.0.acquired_stacks
+    }
+
+    /// Returns the number of allocated stacks kept in the pools of the worker group.
+    pub fn pooled_stacks(&self) -> usize {
+        self.0.pooled_stacks
+    }
+
+    /// Returns the average time between spawning a task and the start of its execution.
+    pub fn average_latency(&self) -> Duration {
+        Duration::new(self.0.average_latency.secs, self.0.average_latency.nanos)
+    }
+
+    /// Returns the number of workers, excluding the workers that are retiring.
+    pub fn active_workers(&self) -> usize {
+        self.0.active_workers
+    }
+
+    /// Returns the number of workers that retired due to a [`WorkerGroup::resize`].
+    pub fn retired_workers(&self) -> usize {
+        self.0.retired_workers
+    }
+}
+
+impl std::fmt::Debug for WorkerGroupStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorkerGroupStats")
+            .field("spawned_tasks", &self.spawned_tasks())
+            .field("completed_tasks", &self.completed_tasks())
+            .field("aborted_tasks", &self.aborted_tasks())
+            .field("queued_tasks", &self.queued_tasks())
+            .field("acquired_stacks", &self.acquired_stacks())
+            .field("pooled_stacks", &self.pooled_stacks())
+            .field("average_latency", &self.average_latency())
+            .field("active_workers", &self.active_workers())
+            .field("retired_workers", &self.retired_workers())
+            .finish()
+    }
+}
+
+/// Statistics of a single worker of a [`WorkerGroup`].
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub struct WorkerStats(bindings::FiTasksWorkerStats);
+
+impl WorkerStats {
+    /// Returns the id of the worker.
+    pub fn worker(&self) -> WorkerId {
+        WorkerId(self.0.worker)
+    }
+
+    /// Returns the number of tasks waiting in the local queue of the worker.
+    pub fn queued_tasks(&self) -> usize {
+        self.0.queued_tasks
+    }
+
+    /// Returns the time the worker spent executing tasks.
+    pub fn busy_time(&self) -> Duration {
+        Duration::new(self.0.busy_time.secs, self.0.busy_time.nanos)
+    }
+
+    /// Returns the time the worker spent without executing a task.
+    pub fn idle_time(&self) -> Duration {
+        Duration::new(self.0.idle_time.secs, self.0.idle_time.nanos)
+    }
+}
+
+impl std::fmt::Debug for WorkerStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WorkerStats")
+            .field("worker", &self.worker())
+            .field("queued_tasks", &self.queued_tasks())
+            .field("busy_time", &self.busy_time())
+            .field("idle_time", &self.idle_time())
+            .finish()
+    }
+}
+
+/// Usage statistics of the stacks of one size of a [`WorkerGroup`].
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub struct StackStats(bindings::FiTasksStackStats);
+
+impl StackStats {
+    /// Returns the size of the stacks.
+    pub fn size(&self) -> usize {
+        self.0.size
+    }
+
+    /// Returns the number of stacks currently in use by the tasks.
+    pub fn acquired_stacks(&self) -> usize {
+        self.0.acquired_stacks
+    }
+
+    /// Returns the number of allocated stacks kept for reuse.
+    pub fn pooled_stacks(&self) -> usize {
+        self.0.pooled_stacks
+    }
+
+    /// Returns the maximum number of stacks that were in use at the same time.
+    pub fn peak_acquired_stacks(&self) -> usize {
+        self.0.peak_acquired_stacks
+    }
+
+    /// Returns the number of tasks that were assigned a larger stack, as the maximum residency of
+    /// the stack size was reached.
+    pub fn spilled_tasks(&self) -> usize {
+        self.0.spilled_tasks
+    }
+
+    /// Returns the maximum number of bytes used by any task executed on a stack of this size.
+    ///
+    /// Is `None` if the usage is not tracked, or no task has finished yet.
+    pub fn peak_usage(&self) -> Option<NonZeroUsize> {
+        NonZeroUsize::new(self.0.peak_usage)
+    }
+}
+
+impl std::fmt::Debug for StackStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StackStats")
+            .field("size", &self.size())
+            .field("acquired_stacks", &self.acquired_stacks())
+            .field("pooled_stacks", &self.pooled_stacks())
+            .field("peak_acquired_stacks", &self.peak_acquired_stacks())
+            .field("spilled_tasks", &self.spilled_tasks())
+            .field("peak_usage", &self.peak_usage())
+            .finish()
+    }
+}
+
+/// Scheduling state of a task of a [`WorkerGroup`].
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum TaskState {
+    /// The task is waiting to be started or resumed by a worker.
+    Queued,
+    /// The task is being executed by a worker.
+    Running,
+    /// The task is suspended until the event it waits on occurs.
+    Blocked,
+}
+
+/// Event a blocked task is waiting on.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum TaskWaitReason {
+    /// The task is sleeping until a point in time.
+    Sleep,
+    /// The task is waiting on the completion of a command buffer.
+    CommandBuffer,
+    /// The task is parked until it is unparked, or its timeout is reached.
+    Parked,
+}
+
+/// Snapshot of an unfinished task of a [`WorkerGroup`].
+///
+/// See [`WorkerGroup::enumerate_tasks`].
+#[derive(Debug, Clone)]
+pub struct TaskInfo {
+    id: TaskId,
+    name: Option<CString>,
+    tag: TaskTag,
+    state: TaskState,
+    wait_reason: Option<TaskWaitReason>,
+    worker: Option<WorkerId>,
+}
+
+impl TaskInfo {
+    /// # Safety
+    ///
+    /// The label of the snapshot must be null or point to a valid string.
+    unsafe fn from_ffi(info: &bindings::FiTasksTaskInfo) -> Result<Self, Error> {
+        let name = if info.label.is_null() {
+            None
+        } else {
+            // Safety: Ensured by the caller.
+            Some(unsafe { CStr::from_ptr(info.label) }.to_owned())
+        };
+        let state = match info.state {
+            bindings::FiTasksTaskState::FI_TASKS_TASK_STATE_QUEUED => TaskState::Queued,
+            bindings::FiTasksTaskState::FI_TASKS_TASK_STATE_RUNNING => TaskState::Running,
+            bindings::FiTasksTaskState::FI_TASKS_TASK_STATE_BLOCKED => TaskState::Blocked,
+            _ => return Err(Error::EINVAL),
+        };
+        let wait_reason = match info.wait_reason {
+            bindings::FiTasksTaskWaitReason::FI_TASKS_TASK_WAIT_REASON_NONE => None,
+            bindings::FiTasksTaskWaitReason::FI_TASKS_TASK_WAIT_REASON_SLEEP => {
+                Some(TaskWaitReason::Sleep)
+            }
+            bindings::FiTasksTaskWaitReason::FI_TASKS_TASK_WAIT_REASON_COMMAND_BUFFER => {
+                Some(TaskWaitReason::CommandBuffer)
+            }
+            bindings::FiTasksTaskWaitReason::FI_TASKS_TASK_WAIT_REASON_PARKED => {
+                Some(TaskWaitReason::Parked)
+            }
+            _ => return Err(Error::EINVAL),
+        };
+
+        Ok(Self {
+            id: TaskId(info.id),
+            name,
+            tag: TaskTag(info.tag),
+            state,
+            wait_reason,
+            worker: info.has_worker.then_some(WorkerId(info.worker)),
+        })
+    }
+
+    /// Returns the id of the task.
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    /// Returns the name of the task, if it was spawned with one.
+    pub fn name(&self) -> Option<&CStr> {
+        self.name.as_deref()
+    }
+
+    /// Returns the tag of the task.
+    pub fn tag(&self) -> TaskTag {
+        self.tag
+    }
+
+    /// Returns the scheduling state of the task.
+    pub fn state(&self) -> TaskState {
+        self.state
+    }
+
+    /// Returns the event the task is waiting on, if it is [blocked](TaskState::Blocked).
+    pub fn wait_reason(&self) -> Option<TaskWaitReason> {
+        self.wait_reason
+    }
+
+    /// Returns the worker the task is bound to, if it has been started.
+    ///
+    /// A task is bound to the worker that started it, and is always resumed by the same worker.
+    pub fn worker(&self) -> Option<WorkerId> {
+        self.worker
+    }
+}
+
 // Safety: Sound by invariant
 unsafe impl Send for WorkerGroup<'_> {}
 
@@ -163,6 +810,145 @@ impl Drop for WorkerGroup<'_> {
     }
 }
 
+/// Allocator of a [`WorkerGroup`].
+///
+/// Serves the allocations with the hooks specified through
+/// [`WorkerGroupBuilder::with_allocator_hooks`], or with the [`FimoAllocator`], if the worker
+/// group does not specify any hooks. Small allocations are rounded up to a size class, and the
+/// freed blocks are cached by the worker group until it is destroyed. The handle keeps the worker
+/// group alive, and can be used for allocations that live no longer than the group, like command
+/// buffers and the private data of its tasks. Like a [`TokioBridge`](crate::TokioBridge), the
+/// handle must not outlive the tasks module.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(allocator_api)]
+/// # fimo_tasks::__private_with_context(|_module, context| {
+/// use fimo_tasks::{CommandBuffer, WorkerGroupBuilder};
+/// use std::num::NonZeroUsize;
+///
+/// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+///     .with_worker_count(NonZeroUsize::new(1))
+///     .build(&context)
+///     .expect("could not create worker group");
+///
+/// let allocator = group.allocator();
+/// let mut buffer = CommandBuffer::new_in(allocator.clone());
+/// let task = buffer.spawn_task(move |_| Box::new_in(5, allocator));
+/// buffer
+///     .block_on(&group)
+///     .expect("could not enqueue command buffer");
+/// assert_eq!(*task.unwrap().unwrap(), 5);
+/// # });
+/// ```
+#[repr(transparent)]
+pub struct GroupAllocator(bindings::FiTasksWorkerGroup);
+
+impl GroupAllocator {
+    #[inline(always)]
+    fn data(&self) -> *mut std::ffi::c_void {
+        self.0.data
+    }
+
+    #[inline(always)]
+    fn vtable(&self) -> &bindings::FiTasksWorkerGroupVTable {
+        // Safety: The VTable is always initialized
+        unsafe { &*self.0.vtable }
+    }
+
+    /// Attempts to allocate a block of memory from the worker group.
+    ///
+    /// Behaves like [`Allocator::allocate`], but returns the error reported by the worker group.
+    pub fn try_allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, Error> {
+        if layout.size() == 0 {
+            // Zero-sized allocations are not forwarded to the worker group, and are represented by
+            // a dangling pointer with the requested alignment.
+            let ptr = std::ptr::null_mut::<u8>().wrapping_add(layout.align());
+            // Safety: The alignment is never zero.
+            let ptr = unsafe { NonNull::new_unchecked(ptr) };
+            return Ok(NonNull::slice_from_raw_parts(ptr, 0));
+        }
+
+        // Safety: FFI call is safe
+        let ptr = unsafe {
+            to_result_indirect_in_place(|err, ptr| {
+                *err = self.vtable().v0.alloc.unwrap_unchecked()(
+                    self.data(),
+                    layout.size(),
+                    layout.align(),
+                    ptr.as_mut_ptr(),
+                );
+            })?
+        };
+        let ptr = NonNull::new(ptr.cast::<u8>()).ok_or(Error::ENOMEM)?;
+        Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
+    }
+}
+
+// Safety: The allocator returns blocks owned by the worker group, which remain valid while a
+// reference to the group is held.
+unsafe impl Allocator for GroupAllocator {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        // An `AllocError` can not carry the error reported by the worker group, which is only
+        // available through `try_allocate`.
+        match self.try_allocate(layout) {
+            Ok(block) => Ok(block),
+            Err(error) => {
+                drop(error);
+                Err(AllocError)
+            }
+        }
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        if layout.size() == 0 {
+            return;
+        }
+
+        // Safety: Ensured by the caller.
+        unsafe {
+            self.vtable().v0.free.unwrap_unchecked()(
+                self.data(),
+                ptr.as_ptr().cast(),
+                layout.size(),
+                layout.align(),
+            )
+        }
+    }
+}
+
+// Safety: Sound by invariant
+unsafe impl Send for GroupAllocator {}
+
+// Safety: Sound by invariant
+unsafe impl Sync for GroupAllocator {}
+
+impl std::fmt::Debug for GroupAllocator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // Safety: FFI call is safe
+        let id = unsafe { self.vtable().v0.id.unwrap_unchecked()(self.data()) };
+        f.debug_struct("GroupAllocator")
+            .field("worker_group", &WorkerGroupId(id))
+            .finish_non_exhaustive()
+    }
+}
+
+impl Clone for GroupAllocator {
+    fn clone(&self) -> Self {
+        // Safety: We own the reference therefore we can acquire another one.
+        unsafe { self.vtable().v0.acquire.unwrap_unchecked()(self.data()) }
+        Self(self.0)
+    }
+}
+
+impl Drop for GroupAllocator {
+    fn drop(&mut self) {
+        // Safety: We own the reference therefore we can release it.
+        unsafe { self.vtable().v0.release.unwrap_unchecked()(self.data()) }
+    }
+}
+
 /// Result of a [`WorkerGroup`] query.
 pub struct WorkerGroupQuery<'ctx> {
     pub(super) query: *mut bindings::FiTasksWorkerGroupQuery,
@@ -262,7 +1048,12 @@ pub struct WorkerGroupBuilder<'a> {
     stacks: &'a [WorkerGroupStackDescriptor],
     default_stack: usize,
     worker_count: Option<NonZeroUsize>,
+    steal_batch_size: Option<NonZeroUsize>,
+    affinity: Option<WorkerAffinity<'a>>,
     is_queryable: bool,
+    track_allocations: bool,
+    time_slice: Option<Duration>,
+    allocator_hooks: Option<Box<dyn AllocatorHooks>>,
 }
 
 impl<'a> WorkerGroupBuilder<'a> {
@@ -286,7 +1077,12 @@ impl<'a> WorkerGroupBuilder<'a> {
             stacks,
             default_stack,
             worker_count: None,
+            steal_batch_size: None,
+            affinity: None,
             is_queryable: false,
+            track_allocations: false,
+            time_slice: None,
+            allocator_hooks: None,
         }
     }
 
@@ -300,6 +1096,30 @@ impl<'a> WorkerGroupBuilder<'a> {
         self
     }
 
+    /// Sets the maximum number of tasks an idle worker steals at once from the global queue or
+    /// from the queue of another worker.
+    ///
+    /// A value of `None` uses the default batch size of the runtime.
+    ///
+    /// Defaults to `None`.
+    pub fn with_steal_batch_size(mut self, size: Option<NonZeroUsize>) -> Self {
+        self.steal_batch_size = size;
+        self
+    }
+
+    /// Sets the cpu affinity of the worker threads of the new [`WorkerGroup`].
+    ///
+    /// The affinity is applied when the worker threads are spawned. Pinning workers is currently
+    /// only supported on Linux. On other platforms, building the worker group fails with
+    /// [`Error::ENOTSUP`], if an affinity is specified. A value of `None` does not pin the
+    /// workers.
+    ///
+    /// Defaults to `None`.
+    pub fn with_affinity(mut self, affinity: Option<WorkerAffinity<'a>>) -> Self {
+        self.affinity = affinity;
+        self
+    }
+
     /// Sets whether to make the new [`WorkerGroup`] queryable through the [`Context`].
     ///
     /// Specifying this does not stop others to acquire a reference to the worker group through its
@@ -309,6 +1129,47 @@ impl<'a> WorkerGroupBuilder<'a> {
         self
     }
 
+    /// Sets whether to record the allocations performed by the tasks of the new [`WorkerGroup`].
+    ///
+    /// The allocations performed with the [`FimoAllocator`] are counted for each task, while it
+    /// is executed by a worker, and are accumulated by the tag of the task. The counts are
+    /// reported by [`WorkerGroup::task_times`], and are emitted as a trace event when a task
+    /// finishes. Only the allocations of modules sharing their instance of the fimo library with
+    /// the runtime are recorded, e.g., when the modules are linked statically. Enabling the
+    /// tracking adds a small overhead to each allocation.
+    ///
+    /// Defaults to `false`.
+    pub fn with_allocation_tracking(mut self, enabled: bool) -> Self {
+        self.track_allocations = enabled;
+        self
+    }
+
+    /// Sets the execution budget of the tasks of the new [`WorkerGroup`].
+    ///
+    /// The budget is renewed each time a task is resumed by a worker. Once it is exhausted, the
+    /// task yields at its next call to [`Context::checkpoint`]. In debug builds, the runtime
+    /// reports the tasks that exceed their budget without reaching a checkpoint. A value of
+    /// `None` uses the default budget of the runtime.
+    ///
+    /// Defaults to `None`.
+    pub fn with_time_slice(mut self, time_slice: Option<Duration>) -> Self {
+        self.time_slice = time_slice;
+        self
+    }
+
+    /// Sets the hooks serving the allocations of the [`GroupAllocator`] of the new
+    /// [`WorkerGroup`].
+    ///
+    /// The hooks are passed the NUMA node requested through [`WorkerGroupBuilder::with_affinity`],
+    /// and are dropped once the worker group is destroyed. A value of `None` uses the
+    /// [`FimoAllocator`].
+    ///
+    /// Defaults to `None`.
+    pub fn with_allocator_hooks(mut self, hooks: Option<Box<dyn AllocatorHooks>>) -> Self {
+        self.allocator_hooks = hooks;
+        self
+    }
+
     /// Creates a new [`WorkerGroup`].
     pub fn build(self, ctx: &Context) -> Result<WorkerGroup<'_>, Error> {
         // Safety: `WorkerGroupStackDescriptor` has a `transparent` layout.
@@ -319,6 +1180,88 @@ impl<'a> WorkerGroupBuilder<'a> {
             >(self.stacks)
         };
 
+        let worker_cpus = self
+            .affinity
+            .map_or(&[][..], |x| x.worker_cpus)
+            .iter()
+            .map(|cpus| bindings::FiTasksWorkerCpuSet {
+                cpus: if cpus.is_empty() {
+                    std::ptr::null()
+                } else {
+                    cpus.as_ptr()
+                },
+                num_cpus: cpus.len(),
+            })
+            .collect::<Vec<_>>();
+        let affinity = self
+            .affinity
+            .map(|affinity| bindings::FiTasksWorkerGroupConfigAffinity {
+                next: std::ptr::null_mut(),
+                policy: affinity.policy.into(),
+                numa_node: affinity
+                    .numa_node
+                    .map_or(-1, |x| x.try_into().unwrap_or(isize::MAX)),
+                worker_cpus: if worker_cpus.is_empty() {
+                    std::ptr::null()
+                } else {
+                    worker_cpus.as_ptr()
+                },
+                num_worker_cpus: worker_cpus.len(),
+            });
+
+        unsafe extern "C" fn alloc(
+            data: *mut std::ffi::c_void,
+            size: usize,
+            alignment: usize,
+            numa_node: isize,
+        ) -> *mut std::ffi::c_void {
+            fimo_std::panic::abort_on_panic(|| {
+                // Safety: The hooks are valid until `on_drop` is invoked.
+                let hooks = unsafe { &*data.cast_const().cast::<Box<dyn AllocatorHooks>>() };
+                let Ok(layout) = Layout::from_size_align(size, alignment) else {
+                    return std::ptr::null_mut();
+                };
+                hooks
+                    .allocate(layout, usize::try_from(numa_node).ok())
+                    .map_or(std::ptr::null_mut(), |ptr| ptr.as_ptr().cast())
+            })
+        }
+
+        unsafe extern "C" fn free(
+            data: *mut std::ffi::c_void,
+            ptr: *mut std::ffi::c_void,
+            size: usize,
+            alignment: usize,
+        ) {
+            fimo_std::panic::abort_on_panic(|| {
+                // Safety: The hooks are valid until `on_drop` is invoked, and the block was
+                // allocated with the same layout.
+                unsafe {
+                    let hooks = &*data.cast_const().cast::<Box<dyn AllocatorHooks>>();
+                    let layout = Layout::from_size_align_unchecked(size, alignment);
+                    hooks.deallocate(NonNull::new_unchecked(ptr.cast()), layout);
+                }
+            });
+        }
+
+        unsafe extern "C" fn on_drop(data: *mut std::ffi::c_void) {
+            fimo_std::panic::abort_on_panic(|| {
+                // Safety: `on_drop` is invoked once, after all blocks have been freed.
+                drop(unsafe { Box::from_raw(data.cast::<Box<dyn AllocatorHooks>>()) });
+            });
+        }
+
+        let hooks_data = self
+            .allocator_hooks
+            .map(|hooks| Box::into_raw(Box::new(hooks)));
+        let allocator = hooks_data.map(|data| bindings::FiTasksWorkerGroupConfigAllocator {
+            next: std::ptr::null_mut(),
+            data: data.cast(),
+            alloc: Some(alloc),
+            free: Some(free),
+            on_drop: Some(on_drop),
+        });
+
         let config = bindings::FiTasksWorkerGroupConfig {
             next: std::ptr::null_mut(),
             name: self.name.as_ptr(),
@@ -326,7 +1269,21 @@ impl<'a> WorkerGroupBuilder<'a> {
             num_stacks: self.stacks.len(),
             default_stack_index: self.default_stack,
             number_of_workers: self.worker_count.map_or(0, |x| x.get()),
+            steal_batch_size: self.steal_batch_size.map_or(0, |x| x.get()),
+            affinity: affinity
+                .as_ref()
+                .map_or(std::ptr::null(), std::ptr::from_ref),
             is_queryable: self.is_queryable,
+            track_allocations: self.track_allocations,
+            time_slice: self
+                .time_slice
+                .map_or(fimo_std::time::Duration::ZERO, |x| {
+                    fimo_std::time::Duration::new(x.as_secs(), x.subsec_nanos())
+                })
+                .into_ffi(),
+            allocator: allocator
+                .as_ref()
+                .map_or(std::ptr::null(), std::ptr::from_ref),
         };
 
         // Safety: FFI call is safe
@@ -337,10 +1294,122 @@ impl<'a> WorkerGroupBuilder<'a> {
                     config,
                     group.as_mut_ptr(),
                 );
-            })?
+            })
         };
+        match group {
+            Ok(group) => Ok(WorkerGroup(group, PhantomData)),
+            Err(e) => {
+                if let Some(data) = hooks_data {
+                    // Safety: The worker group was not created, so we still own the hooks.
+                    drop(unsafe { Box::from_raw(data) });
+                }
+                Err(e)
+            }
+        }
+    }
+}
 
-        Ok(WorkerGroup(group, PhantomData))
+/// Hooks serving the allocations of the [`GroupAllocator`] of a [`WorkerGroup`].
+///
+/// The hooks may be invoked concurrently from any thread.
+pub trait AllocatorHooks: Send + Sync + std::fmt::Debug + 'static {
+    /// Allocates a block of memory with the given layout.
+    ///
+    /// `numa_node` is the NUMA node the workers of the group are bound to, if any. Returns `None`
+    /// if the allocation fails.
+    fn allocate(&self, layout: Layout, numa_node: Option<usize>) -> Option<NonNull<u8>>;
+
+    /// Frees a block of memory.
+    ///
+    /// # Safety
+    ///
+    /// The block must have been allocated by [`AllocatorHooks::allocate`] with the same layout.
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+}
+
+/// Policy for pinning the workers of a [`WorkerGroup`] to the cpus of the system.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WorkerAffinityPolicy {
+    /// Workers are not pinned, unless an explicit cpu set is specified for the worker.
+    #[default]
+    None,
+    /// Workers are pinned to a single cpu each, distributing them evenly over the available cpus.
+    Spread,
+    /// Workers are pinned to a single cpu each, packing them onto adjacent cpus.
+    Compact,
+}
+
+impl From<WorkerAffinityPolicy> for bindings::FiTasksWorkerAffinityPolicy {
+    fn from(value: WorkerAffinityPolicy) -> Self {
+        match value {
+            WorkerAffinityPolicy::None => Self::FI_TASKS_WORKER_AFFINITY_POLICY_NONE,
+            WorkerAffinityPolicy::Spread => Self::FI_TASKS_WORKER_AFFINITY_POLICY_SPREAD,
+            WorkerAffinityPolicy::Compact => Self::FI_TASKS_WORKER_AFFINITY_POLICY_COMPACT,
+        }
+    }
+}
+
+/// Cpu affinity of the workers of a [`WorkerGroup`].
+///
+/// # Examples
+///
+/// ```no_run
+/// # fimo_tasks::__private_with_context(|_module, context| {
+/// use fimo_tasks::{WorkerAffinity, WorkerAffinityPolicy, WorkerGroupBuilder, WorkerId};
+/// use std::num::NonZeroUsize;
+///
+/// // Pin the first worker to the cpus 0 and 1, and spread the others over the first NUMA node.
+/// let affinity = WorkerAffinity::new(WorkerAffinityPolicy::Spread)
+///     .with_numa_node(Some(0))
+///     .with_worker_cpus(&[&[0, 1]]);
+/// let group = WorkerGroupBuilder::new(c"doctest", &[Default::default()], None)
+///     .with_worker_count(NonZeroUsize::new(4))
+///     .with_affinity(Some(affinity))
+///     .build(&context)
+///     .expect("could not create worker group");
+///
+/// let cpus = group.worker_affinity(WorkerId(0)).unwrap();
+/// assert_eq!(&*cpus, &[0, 1]);
+/// # });
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WorkerAffinity<'a> {
+    policy: WorkerAffinityPolicy,
+    numa_node: Option<usize>,
+    worker_cpus: &'a [&'a [usize]],
+}
+
+impl<'a> WorkerAffinity<'a> {
+    /// Constructs a new `WorkerAffinity` with the given policy.
+    pub fn new(policy: WorkerAffinityPolicy) -> Self {
+        Self {
+            policy,
+            numa_node: None,
+            worker_cpus: &[],
+        }
+    }
+
+    /// Restricts the cpus available to the policy to the ones of the given NUMA node.
+    ///
+    /// With the [`WorkerAffinityPolicy::None`] policy, the workers are pinned to all cpus of the
+    /// node. A value of `None` does not restrict the cpus.
+    ///
+    /// Defaults to `None`.
+    pub fn with_numa_node(mut self, node: Option<usize>) -> Self {
+        self.numa_node = node;
+        self
+    }
+
+    /// Sets explicit cpu sets for the workers.
+    ///
+    /// The i-th set applies to the i-th worker and takes precedence over the policy. Workers
+    /// without a set, or with an empty set, are pinned according to the policy. Must not contain
+    /// more sets than workers.
+    ///
+    /// Defaults to no sets.
+    pub fn with_worker_cpus(mut self, cpus: &'a [&'a [usize]]) -> Self {
+        self.worker_cpus = cpus;
+        self
     }
 }
 
@@ -361,6 +1430,8 @@ impl WorkerGroupStackDescriptor {
                 residency_target: 0,
                 max_residency: 0,
                 enable_stack_overflow_protection: true,
+                enable_usage_tracking: false,
+                enable_spilling: false,
             },
         }
     }
@@ -428,6 +1499,31 @@ impl WorkerGroupStackDescriptor {
         self.config.enable_stack_overflow_protection = enabled;
         self
     }
+
+    /// Sets whether to track the maximum number of bytes used by the tasks.
+    ///
+    /// The stacks are filled with a known pattern when they are allocated, and scanned each time a
+    /// task releases them. This increases the cost of allocating and releasing a stack, and
+    /// commits the entire memory of the stack. The result is reported by
+    /// [`WorkerGroup::stack_stats`].
+    ///
+    /// Defaults to `false`.
+    pub fn with_usage_tracking(&mut self, enabled: bool) -> &mut Self {
+        self.config.enable_usage_tracking = enabled;
+        self
+    }
+
+    /// Sets whether the tasks may be assigned a larger stack, when the maximum residency is
+    /// reached.
+    ///
+    /// If disabled, the tasks are put on hold until a stack of the requested size becomes
+    /// available.
+    ///
+    /// Defaults to `false`.
+    pub fn with_spilling(&mut self, enabled: bool) -> &mut Self {
+        self.config.enable_spilling = enabled;
+        self
+    }
 }
 
 impl Default for WorkerGroupStackDescriptor {
@@ -447,6 +1543,8 @@ impl std::fmt::Debug for WorkerGroupStackDescriptor {
                 "stack_overflow_protection",
                 &self.config.enable_stack_overflow_protection,
             )
+            .field("usage_tracking", &self.config.enable_usage_tracking)
+            .field("spilling", &self.config.enable_spilling)
             .finish_non_exhaustive()
     }
 }
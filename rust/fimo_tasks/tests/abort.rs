@@ -0,0 +1,117 @@
+use fimo_tasks::{
+    CommandBuffer, CommandBufferStatus, TaskStatus, WorkerGroupBuilder, WorkerGroupStackDescriptor,
+};
+use std::{
+    cell::RefCell,
+    hint::black_box,
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+/// Size of the task stacks.
+const TASK_STACK_SIZE: usize = 128 * 1024;
+
+/// Size of the buffer used by the destructors, which does not fit onto the task stack.
+const DESTRUCTOR_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Value requiring more stack space to be dropped than is available to the task.
+struct LargeDrop(Arc<AtomicBool>);
+
+impl Drop for LargeDrop {
+    fn drop(&mut self) {
+        let buffer = black_box([0u8; DESTRUCTOR_BUFFER_SIZE]);
+        black_box(&buffer);
+        self.0.store(true, Ordering::Release);
+    }
+}
+
+/// Panic payload counting the number of times it was dropped.
+struct CountedDrop(Arc<AtomicUsize>);
+
+impl Drop for CountedDrop {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+fimo_tasks::task_specific! {
+    static LOCAL: RefCell<Option<LargeDrop>> = RefCell::new(None);
+}
+
+fn recurse_and_panic(depth: usize) -> usize {
+    let frame = black_box([depth as u8; 1024]);
+    if depth == 0 {
+        std::panic::panic_any(LargeDrop(Arc::default()));
+    }
+    black_box(recurse_and_panic(depth - 1)) + usize::from(frame[0])
+}
+
+#[test]
+fn abort_deep_stack() {
+    fimo_tasks::__private_with_context(|_module, context| {
+        let mut stack = WorkerGroupStackDescriptor::new();
+        stack.with_size(NonZeroUsize::new(TASK_STACK_SIZE));
+        let group = WorkerGroupBuilder::new(c"abort_deep_stack", &[stack], None)
+            .with_worker_count(NonZeroUsize::new(1))
+            .build(&context)
+            .expect("could not create worker group");
+
+        let dropped = Arc::new(AtomicBool::new(false));
+        let mut buffer = CommandBuffer::new();
+        let task = {
+            let dropped = dropped.clone();
+            buffer.spawn_task(move |context| {
+                // The local data is cleaned up by the worker, after the task has been aborted.
+                LOCAL.set(context, Some(LargeDrop(dropped)));
+                recurse_and_panic(TASK_STACK_SIZE / 2048)
+            })
+        };
+
+        let status = buffer
+            .block_on(&group)
+            .expect("could not enqueue command buffer");
+        assert_eq!(status, CommandBufferStatus::Aborted(0));
+        assert_eq!(task.completion_status(), Some(TaskStatus::Aborted));
+        assert!(dropped.load(Ordering::Acquire));
+
+        // The panic payload is dropped by the current thread.
+        let payload = task.unwrap().expect_err("task should have panicked");
+        assert!(payload.is::<LargeDrop>());
+    });
+}
+
+#[test]
+fn abort_drops_payload_once() {
+    fimo_tasks::__private_with_context(|_module, context| {
+        let group =
+            WorkerGroupBuilder::new(c"abort_drops_payload_once", &[Default::default()], None)
+                .with_worker_count(NonZeroUsize::new(1))
+                .build(&context)
+                .expect("could not create worker group");
+
+        let drops = Arc::new(AtomicUsize::new(0));
+        let mut buffer = CommandBuffer::new();
+        let task = {
+            let drops = drops.clone();
+            buffer.spawn_task(move |_| {
+                std::panic::panic_any(CountedDrop(drops));
+            })
+        };
+
+        let status = buffer
+            .block_on(&group)
+            .expect("could not enqueue command buffer");
+        assert_eq!(status, CommandBufferStatus::Aborted(0));
+        assert_eq!(task.completion_status(), Some(TaskStatus::Aborted));
+
+        // The payload is owned by the handle, until it is extracted.
+        assert_eq!(drops.load(Ordering::Acquire), 0);
+        let payload = task.unwrap().expect_err("task should have panicked");
+        assert!(payload.is::<CountedDrop>());
+        drop(payload);
+        assert_eq!(drops.load(Ordering::Acquire), 1);
+    });
+}
@@ -0,0 +1,79 @@
+use fimo_std::{
+    bindings::FimoModuleRawSymbol,
+    ffi::FFITransferable,
+    module::{held_symbols, set_held_symbols_hook, Symbol},
+};
+use fimo_tasks::{CommandBuffer, TaskStatus, WorkerGroupBuilder};
+use std::{
+    cell::Cell,
+    num::NonZeroUsize,
+    sync::{atomic::AtomicUsize, Arc, Mutex},
+};
+
+#[test]
+fn yield_while_holding_symbol() {
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    {
+        let reports = reports.clone();
+        set_held_symbols_hook(Some(Box::new(move |operation, symbols| {
+            let symbols = symbols
+                .iter()
+                .map(|x| (x.type_name(), x.location().file(), x.location().line()))
+                .collect::<Vec<_>>();
+            reports
+                .lock()
+                .unwrap()
+                .push((operation.to_string(), symbols));
+        })));
+    }
+
+    let value = Box::leak(Box::new(5u32));
+    let raw = Box::leak(Box::new(FimoModuleRawSymbol {
+        data: Cell::new(std::ptr::from_ref(value).cast()),
+        lock: AtomicUsize::new(0),
+    }));
+    // Safety: The symbol is valid for the rest of the program.
+    let symbol = unsafe { Symbol::<'static, u32>::from_ffi(raw) };
+
+    fimo_tasks::__private_with_context(|_module, context| {
+        let group = WorkerGroupBuilder::new(c"held_symbols", &[Default::default()], None)
+            .with_worker_count(NonZeroUsize::new(2))
+            .build(&context)
+            .expect("could not create worker group");
+
+        let mut buffer = CommandBuffer::new();
+        let task = buffer.spawn_task(move |context| {
+            let guard = symbol.lock();
+            let line = line!() - 1;
+            assert_eq!(*guard, 5);
+            assert_eq!(guard.location().line(), line);
+
+            // The guard is reported, and follows the task to the resuming worker.
+            context.yield_now().expect("could not yield");
+            let held = held_symbols();
+            assert_eq!(held.len(), 1);
+            assert_eq!(held[0].location().line(), line);
+
+            // Released guards are no longer reported.
+            drop(guard);
+            assert!(held_symbols().is_empty());
+            context.yield_now().expect("could not yield");
+            line
+        });
+
+        buffer
+            .block_on(&group)
+            .expect("could not enqueue command buffer");
+        assert_eq!(task.completion_status(), Some(TaskStatus::Completed));
+
+        let line = task.unwrap().expect("task should have completed");
+        let reports = reports.lock().unwrap();
+        assert_eq!(
+            *reports,
+            [("yield".to_string(), vec![("u32", file!(), line)])]
+        );
+    });
+
+    set_held_symbols_hook(None);
+    assert_eq!(symbol.lock().location().file(), file!());
+}
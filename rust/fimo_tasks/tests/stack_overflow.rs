@@ -0,0 +1,62 @@
+#![cfg(unix)]
+
+use fimo_tasks::{CommandBuffer, WorkerGroupBuilder, WorkerGroupStackDescriptor};
+use std::{hint::black_box, num::NonZeroUsize, os::unix::process::ExitStatusExt, process::Command};
+
+/// Environment variable instructing the test binary to overflow the stack of a task.
+const CHILD_ENV: &str = "FIMO_TASKS_STACK_OVERFLOW_CHILD";
+
+/// Size of the task stacks.
+const TASK_STACK_SIZE: usize = 128 * 1024;
+
+fn recurse(depth: usize) -> usize {
+    let frame = black_box([depth as u8; 1024]);
+    if depth == black_box(usize::MAX) {
+        return depth;
+    }
+    black_box(recurse(depth + 1)) + usize::from(frame[0])
+}
+
+/// Overflows the stack of a task, when run as the child process of `stack_overflow`.
+#[test]
+fn stack_overflow_child() {
+    if std::env::var_os(CHILD_ENV).is_none() {
+        return;
+    }
+
+    fimo_tasks::__private_with_context(|_module, context| {
+        let mut stack = WorkerGroupStackDescriptor::new();
+        stack.with_size(NonZeroUsize::new(TASK_STACK_SIZE));
+        let group = WorkerGroupBuilder::new(c"stack_overflow", &[stack], None)
+            .with_worker_count(NonZeroUsize::new(1))
+            .build(&context)
+            .expect("could not create worker group");
+
+        let mut buffer = CommandBuffer::new();
+        buffer.spawn_task(|_| recurse(0));
+        let _ = buffer.block_on(&group);
+        unreachable!("the task should have overflowed its stack");
+    });
+}
+
+#[test]
+fn stack_overflow() {
+    let output = Command::new(std::env::current_exe().expect("could not find the test binary"))
+        .args(["stack_overflow_child", "--exact", "--nocapture"])
+        .env(CHILD_ENV, "1")
+        .output()
+        .expect("could not spawn the child process");
+
+    // The handler reports the overflow, and terminates the process with the original signal.
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let signal = output.status.signal();
+    assert!(
+        matches!(signal, Some(libc::SIGSEGV | libc::SIGBUS)),
+        "unexpected exit status {:?}, stderr:\n{stderr}",
+        output.status
+    );
+    assert!(
+        stderr.contains("of worker group \"stack_overflow\" has overflowed its stack of"),
+        "the overflow was not reported, stderr:\n{stderr}"
+    );
+}
@@ -0,0 +1,39 @@
+use fimo_tasks::{CommandBuffer, FailurePolicy, WorkerGroupBuilder};
+use std::num::NonZeroUsize;
+
+#[test]
+fn task_accounting() {
+    fimo_tasks::__private_with_context(|_module, context| {
+        let group = WorkerGroupBuilder::new(c"task_accounting", &[Default::default()], None)
+            .with_worker_count(NonZeroUsize::new(2))
+            .build(&context)
+            .expect("could not create worker group");
+
+        let stats = group.stats().expect("could not fetch the statistics");
+        assert_eq!(stats.spawned_tasks(), 0);
+        assert_eq!(stats.completed_tasks(), 0);
+        assert_eq!(stats.aborted_tasks(), 0);
+
+        let mut buffer = CommandBuffer::new();
+        buffer.set_failure_policy(FailurePolicy::Continue);
+        for _ in 0..3 {
+            buffer.spawn_task(|_| {});
+        }
+        buffer.spawn_task(|_| panic!("abort the task"));
+        buffer
+            .block_on(&group)
+            .expect("could not enqueue command buffer");
+
+        let stats = group.stats().expect("could not fetch the statistics");
+        assert_eq!(stats.spawned_tasks(), 4);
+        assert_eq!(stats.completed_tasks(), 3);
+        assert_eq!(stats.aborted_tasks(), 1);
+        assert_eq!(stats.queued_tasks(), 0);
+
+        let worker_stats = group
+            .worker_stats()
+            .expect("could not fetch the worker statistics");
+        assert_eq!(worker_stats.len(), 2);
+        assert!(worker_stats.iter().all(|x| x.queued_tasks() == 0));
+    });
+}
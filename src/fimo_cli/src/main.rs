@@ -0,0 +1,350 @@
+//! Command line tool for inspecting and managing fimo modules.
+
+use fimo_std::{
+    bootstrap::{inspect_binary, module_binaries, Profile, SymbolRequirement},
+    context::Context,
+    error::Error,
+    module::{ModuleExport, ParameterConfig},
+    tracing::{default_subscriber, Config, Level},
+};
+use std::{
+    ffi::CString,
+    fmt::Display,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+const USAGE: &str = "\
+Usage: fimo-cli <command> [<args>]
+
+Commands:
+    inspect <path>      Print the modules exported by a binary without loading them
+    tree <dir>          Print the dependency graph of the modules in a directory
+    verify <dir>...     Check that the modules in the directories can be loaded together
+    run [<options>]     Bootstrap an engine instance and shut it down again
+
+Options of `run`:
+    --module-dir <dir>          Load the modules in the directory
+    --require <module>          Fail if the module can not be loaded
+    --config <file>             Load the initial parameter values from a TOML or JSON file
+    --param <module.param=val>  Set the initial value of a parameter
+    --log-level <level>         Print the events up to the level (error, warn, info, debug, trace)
+
+The initial parameter values are also read from the `FIMO_PARAM_<module>__<param>` variables.
+";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("inspect") => inspect(&args[1..]),
+        Some("tree") => tree(&args[1..]),
+        Some("verify") => verify(&args[1..]),
+        Some("run") => run(&args[1..]),
+        Some("help" | "-h" | "--help") => {
+            print!("{USAGE}");
+            Ok(ExitCode::SUCCESS)
+        }
+        _ => Err(usage_error("expected a command")),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn usage_error(msg: impl Display) -> Error {
+    Error::new(format!("{msg}\n\n{USAGE}"))
+}
+
+/// Owned description of a module exported by a binary.
+#[derive(Debug)]
+struct ModuleEntry {
+    name: String,
+    binary: PathBuf,
+    imports: Vec<SymbolRequirement>,
+    exports: Vec<SymbolRequirement>,
+}
+
+impl ModuleEntry {
+    fn new(export: &ModuleExport<'_>, binary: &Path) -> Self {
+        let imports = export
+            .imported_symbols()
+            .iter()
+            .map(|x| SymbolRequirement::new(x.name(), x.namespace(), x.version()))
+            .collect();
+        let exports = export
+            .exported_symbols()
+            .iter()
+            .map(|x| SymbolRequirement::new(x.name(), x.namespace(), x.version()))
+            .chain(
+                export
+                    .exported_dynamic_symbols()
+                    .iter()
+                    .map(|x| SymbolRequirement::new(x.name(), x.namespace(), x.version())),
+            )
+            .collect();
+
+        Self {
+            name: export.name().to_string_lossy().into_owned(),
+            binary: binary.into(),
+            imports,
+            exports,
+        }
+    }
+
+    /// Returns the version of the symbol exported by the module, if any.
+    fn exported_version(&self, symbol: &SymbolRequirement) -> Option<&SymbolRequirement> {
+        self.exports
+            .iter()
+            .find(|x| x.name() == symbol.name() && x.namespace() == symbol.namespace())
+    }
+
+    /// Returns whether the module exports a symbol satisfying the import.
+    fn satisfies(&self, import: &SymbolRequirement) -> bool {
+        self.exported_version(import)
+            .is_some_and(|x| x.version().compatible(&import.version()))
+    }
+}
+
+/// Collects the modules of the binaries in the directories without loading them.
+fn collect_modules(context: &Context, dirs: &[String]) -> Result<Vec<ModuleEntry>, Error> {
+    let mut modules = Vec::new();
+    for dir in dirs {
+        for binary in module_binaries(dir)? {
+            inspect_binary(&**context, Some(&binary), |export| {
+                modules.push(ModuleEntry::new(&export, &binary));
+            })?;
+        }
+    }
+    modules.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(modules)
+}
+
+fn inspect(args: &[String]) -> Result<ExitCode, Error> {
+    let [path] = args else {
+        return Err(usage_error("`inspect` expects the path to a binary"));
+    };
+
+    let context = Context::new()?;
+    let mut count = 0;
+    inspect_binary(&*context, Some(Path::new(path)), |export| {
+        count += 1;
+        print_export(&export);
+    })?;
+    if count == 0 {
+        println!("`{path}` does not export any modules");
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn print_export(export: &ModuleExport<'_>) {
+    fn section<T: Display>(title: &str, items: &[T]) {
+        if items.is_empty() {
+            return;
+        }
+        println!("  {title}:");
+        for item in items {
+            println!("    {item}");
+        }
+    }
+
+    println!("{export}");
+    println!("  context version: {}", export.context_version());
+    section("parameters", export.parameters());
+    section("resources", export.resources());
+    section("imported namespaces", export.imported_namespaces());
+    section("imported symbols", export.imported_symbols());
+    section("exported symbols", export.exported_symbols());
+    section(
+        "exported dynamic symbols",
+        export.exported_dynamic_symbols(),
+    );
+    section("modifiers", export.modifiers());
+}
+
+fn tree(args: &[String]) -> Result<ExitCode, Error> {
+    let [dir] = args else {
+        return Err(usage_error("`tree` expects the path to a directory"));
+    };
+
+    let context = Context::new()?;
+    let modules = collect_modules(&context, std::slice::from_ref(dir))?;
+
+    // Start with the modules no other module depends on. If every module is part of a cycle, the
+    // graph is printed starting from each module.
+    let mut roots: Vec<_> = modules
+        .iter()
+        .filter(|module| {
+            !modules
+                .iter()
+                .any(|x| x.imports.iter().any(|import| module.satisfies(import)))
+        })
+        .collect();
+    if roots.is_empty() {
+        roots = modules.iter().collect();
+    }
+
+    let mut stack = Vec::new();
+    for root in roots {
+        print_tree(&modules, root, &mut stack);
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn print_tree<'a>(modules: &'a [ModuleEntry], module: &'a ModuleEntry, stack: &mut Vec<&'a str>) {
+    let indent = "  ".repeat(stack.len());
+    let binary = module.binary.file_name().unwrap_or_default();
+    if stack.contains(&module.name.as_str()) {
+        println!("{indent}{} (cycle)", module.name);
+        return;
+    }
+    println!("{indent}{} ({})", module.name, binary.to_string_lossy());
+
+    stack.push(&module.name);
+    let mut dependencies: Vec<&ModuleEntry> = Vec::new();
+    for import in &module.imports {
+        match modules.iter().find(|x| x.satisfies(import)) {
+            Some(dependency) => {
+                if !dependencies.iter().any(|x| x.name == dependency.name) {
+                    dependencies.push(dependency);
+                }
+            }
+            None => println!("{indent}  {import} (unresolved)"),
+        }
+    }
+    for dependency in dependencies {
+        print_tree(modules, dependency, stack);
+    }
+    stack.pop();
+}
+
+fn verify(args: &[String]) -> Result<ExitCode, Error> {
+    if args.is_empty() {
+        return Err(usage_error("`verify` expects at least one directory"));
+    }
+
+    let context = Context::new()?;
+    let modules = collect_modules(&context, args)?;
+
+    let mut issues = Vec::new();
+    for (i, module) in modules.iter().enumerate() {
+        let others = modules[..i].iter().chain(&modules[i + 1..]);
+        if let Some(other) = modules[..i].iter().find(|x| x.name == module.name) {
+            issues.push(format!(
+                "module `{}` of `{}` is also exported by `{}`",
+                module.name,
+                module.binary.display(),
+                other.binary.display()
+            ));
+        }
+
+        for export in &module.exports {
+            if let Some(other) = modules[..i]
+                .iter()
+                .find(|x| x.exported_version(export).is_some())
+            {
+                issues.push(format!(
+                    "module `{}`: symbol {export} is also exported by module `{}`",
+                    module.name, other.name
+                ));
+            }
+        }
+
+        for import in &module.imports {
+            if others.clone().any(|x| x.satisfies(import)) {
+                continue;
+            }
+            let found = others
+                .clone()
+                .find_map(|x| x.exported_version(import).map(|export| (x, export)));
+            issues.push(match found {
+                Some((other, export)) => format!(
+                    "module `{}`: import {import} is only exported with version {} by module `{}`",
+                    module.name,
+                    export.version(),
+                    other.name
+                ),
+                None => format!(
+                    "module `{}`: import {import} is not exported by any module",
+                    module.name
+                ),
+            });
+        }
+    }
+
+    for issue in &issues {
+        println!("{issue}");
+    }
+    if issues.is_empty() {
+        println!("{} modules verified", modules.len());
+        Ok(ExitCode::SUCCESS)
+    } else {
+        println!("{} issues found in {} modules", issues.len(), modules.len());
+        Ok(ExitCode::FAILURE)
+    }
+}
+
+fn run(args: &[String]) -> Result<ExitCode, Error> {
+    let mut profile = Profile::new();
+    let mut config = ParameterConfig::new();
+    let mut overrides = ParameterConfig::new();
+    let mut level = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| usage_error(format!("`{arg}` expects a value")))
+        };
+        match arg.as_str() {
+            "--module-dir" => profile = profile.with_module_dir(value()?),
+            "--require" => {
+                let name = CString::new(value()?.as_str()).map_err(Error::new)?;
+                profile = profile.require_module(&name);
+            }
+            "--config" => config.load_file(value()?)?,
+            "--param" => {
+                let (key, value) = value()?
+                    .split_once('=')
+                    .ok_or_else(|| usage_error("`--param` expects `module.param=value`"))?;
+                overrides.load_toml(&format!("{key} = {value}"))?;
+            }
+            "--log-level" => {
+                let value = value()?;
+                level = Some(match value.parse::<Level>() {
+                    Ok(level) => level,
+                    Err(_) => return Err(usage_error(format!("unknown level `{value}`"))),
+                });
+            }
+            arg => return Err(usage_error(format!("unknown option `{arg}`"))),
+        }
+    }
+
+    config.load_env()?;
+    for (module, parameter, _, value) in overrides.iter() {
+        config.set(module, parameter, value);
+    }
+    let profile = profile.with_parameter_config(config);
+
+    match level {
+        Some(level) => run_profile(profile.with_tracing_config(Config::new(
+            None,
+            Some(level),
+            [default_subscriber()],
+        ))),
+        None => run_profile(profile),
+    }
+}
+
+fn run_profile<const N: usize>(profile: Profile<N>) -> Result<ExitCode, Error> {
+    let engine = profile.bootstrap()?;
+    for module in engine.modules() {
+        println!("loaded {module}");
+    }
+    engine.shutdown()?;
+    Ok(ExitCode::SUCCESS)
+}